@@ -0,0 +1,110 @@
+// 在自己的Bevy应用中嵌入黑白棋
+//
+// 运行：cargo run --example embed_in_bevy_app
+//
+// 示例同时展示三种用法：启动前直接调用引擎让AI分析开局，
+// 然后把完整游戏作为插件加入应用，并在游戏系统之后运行自己的系统；
+// 游戏本身不包含联机传输层，宿主应用负责收发观战消息帧，
+// 这里用进程内的MatchRelay代替服务器，在难度选择界面按F6观战一局已经开始的对局
+
+use bevy::prelude::*;
+use reversi::{
+    net::{
+        decode, encode, ClientId, ClientMessage, MatchRelay, Role, SpectatorLink, PROTOCOL_VERSION,
+    },
+    prelude::*,
+    state::GameTransitions,
+    GameTransition,
+};
+
+/// 示例中观战者的连接标识
+const SPECTATOR: ClientId = 3;
+
+/// 代替联机服务器的进程内对局转发器
+#[derive(Resource)]
+struct LocalMatch(MatchRelay);
+
+fn main() {
+    // 只使用引擎：不需要Bevy应用
+    let board = Board::new();
+    if let Some(ai_move) = AiDifficulty::Beginner.get_ai_move(&board, PlayerColor::Black) {
+        let (row, col) = Board::position_to_coords(ai_move.position);
+        println!("Engine suggests opening at row {row}, col {col}");
+    }
+
+    // 嵌入完整游戏
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "Embedded Reversi".to_string(),
+                ..default()
+            }),
+            ..default()
+        }))
+        .add_plugins(ReversiPlugin)
+        .insert_resource(LocalMatch(local_match()))
+        .add_systems(
+            Update,
+            log_state_changes
+                .after(GameSystems::Common)
+                .run_if(state_changed::<GameState>),
+        )
+        .add_systems(
+            Update,
+            (
+                spectate_on_key.run_if(in_state(GameState::DifficultySelection)),
+                relay_spectator_frames,
+            ),
+        )
+        .run();
+}
+
+/// 宿主应用自己的系统：游戏状态变化时输出日志
+fn log_state_changes(state: Res<State<GameState>>) {
+    info!("Reversi entered {:?}", state.get());
+}
+
+/// 两名对弈者已经加入并走了两步的对局
+fn local_match() -> MatchRelay {
+    let mut relay = MatchRelay::new(Board::new_standard());
+    for player in [1, 2] {
+        relay.handle(
+            player,
+            ClientMessage::Hello {
+                version: PROTOCOL_VERSION,
+                role: Role::Player,
+            },
+        );
+    }
+    for (player, position) in [(1, 19), (2, 18)] {
+        let command = relay.log().next_command(MoveAction::Place(position));
+        relay.handle(player, ClientMessage::Move(command));
+    }
+    relay
+}
+
+/// 按F6请求观战，进入观战状态时游戏排队发送握手
+fn spectate_on_key(keys: Res<ButtonInput<KeyCode>>, mut transitions: GameTransitions) {
+    if keys.just_pressed(KeyCode::F6) {
+        transitions.request(GameTransition::Spectate);
+    }
+}
+
+/// 传输层：把观战者待发送的消息帧交给转发器，再把转发器的回复放入收到的消息帧
+fn relay_spectator_frames(mut link: ResMut<SpectatorLink>, mut local_match: ResMut<LocalMatch>) {
+    if link.outgoing.is_empty() {
+        return;
+    }
+    for frame in std::mem::take(&mut link.outgoing) {
+        let Ok(message) = decode::<ClientMessage>(&frame) else {
+            continue;
+        };
+        // 观战者发送Leave时转发器移除观战者，下次进入观战重新握手
+        let replies = local_match.0.handle(SPECTATOR, message);
+        for reply in replies.into_iter().filter(|out| out.to == SPECTATOR) {
+            if let Ok(frame) = encode(&reply.message) {
+                link.incoming.push_back(frame);
+            }
+        }
+    }
+}
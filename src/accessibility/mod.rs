@@ -0,0 +1,173 @@
+// 无障碍模块 - 为屏幕阅读器提供走棋播报，并提供减少动效设置
+//
+// 每一步落子、跳过回合和对局结果都会生成一条本地化的播报文本，
+// 显示在屏幕上的实时区域中，并通过 bevy_a11y 推送给支持的辅助技术
+
+use crate::{
+    game::{Board, PlayerColor},
+    input::{InputAction, InputMap},
+    localization::{LanguageSettings, LocalizedTexts},
+    preferences::Preferences,
+};
+use accesskit::{Live, Node as AccessKitNode, Role};
+use bevy::{a11y::AccessibilityNode, prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// 无障碍设置资源
+#[derive(Resource)]
+pub struct AccessibilitySettings {
+    /// 是否启用走棋播报
+    pub announcements_enabled: bool,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            announcements_enabled: true,
+        }
+    }
+}
+
+/// 动效设置资源
+///
+/// 开启减少动效后，所有动画系统（淡入、加载文字脉冲等）直接显示最终状态，
+/// 新增的动画系统也需要检查该设置
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct MotionSettings {
+    /// 是否减少动效
+    pub reduced_motion: bool,
+}
+
+/// 动效设置变化时写回偏好设置，下次启动时恢复
+pub fn sync_motion_preferences(
+    motion_settings: Res<MotionSettings>,
+    mut preferences: ResMut<Preferences>,
+) {
+    if motion_settings.is_changed() && preferences.motion != *motion_settings {
+        preferences.motion = motion_settings.clone();
+    }
+}
+
+/// 播报事件 - 由游戏逻辑在落子、跳过和结束时发送
+#[derive(Event, Clone, Copy, Debug)]
+pub enum AnnouncementEvent {
+    /// 某方落子，并翻转了若干棋子
+    Move {
+        color: PlayerColor,
+        position: u8,
+        flipped: u32,
+    },
+    /// 某方无棋可走，跳过回合
+    Pass { color: PlayerColor },
+    /// 对局结束
+    Result {
+        winner: Option<PlayerColor>,
+        black: u32,
+        white: u32,
+    },
+}
+
+/// 实时播报区域标记组件
+#[derive(Component)]
+pub struct AnnouncementText;
+
+/// 创建播报区域对应的无障碍节点
+///
+/// 使用 Polite 级别的实时区域，屏幕阅读器会在空闲时朗读内容变化
+pub fn announcement_accessibility_node() -> AccessibilityNode {
+    let mut node = AccessKitNode::new(Role::Label);
+    node.set_live(Live::Polite);
+    AccessibilityNode(node)
+}
+
+/// 获取棋子颜色的本地化名称
+pub fn color_name(texts: &LocalizedTexts, color: PlayerColor) -> &'static str {
+    match color {
+        PlayerColor::Black => texts.black_name,
+        PlayerColor::White => texts.white_name,
+    }
+}
+
+/// 将播报事件格式化为当前语言的文本
+pub fn format_announcement(texts: &LocalizedTexts, event: &AnnouncementEvent) -> String {
+    match *event {
+        AnnouncementEvent::Move {
+            color,
+            position,
+            flipped,
+        } => {
+            let format = if flipped == 1 {
+                texts.announce_move_single
+            } else {
+                texts.announce_move
+            };
+            format
+                .replacen("{}", color_name(texts, color), 1)
+                .replacen("{}", &Board::position_to_notation(position), 1)
+                .replacen("{}", &flipped.to_string(), 1)
+        }
+        AnnouncementEvent::Pass { color } => {
+            texts
+                .announce_pass
+                .replacen("{}", color_name(texts, color), 1)
+        }
+        AnnouncementEvent::Result {
+            winner,
+            black,
+            white,
+        } => {
+            let outcome = match winner {
+                Some(PlayerColor::Black) => texts.black_wins,
+                Some(PlayerColor::White) => texts.white_wins,
+                None => texts.draw,
+            };
+            texts
+                .announce_result
+                .replacen("{}", outcome, 1)
+                .replacen("{}", &black.to_string(), 1)
+                .replacen("{}", &white.to_string(), 1)
+        }
+    }
+}
+
+/// 将播报事件写入实时区域
+pub fn post_announcements(
+    mut announcement_events: EventReader<AnnouncementEvent>,
+    mut region_query: Query<(&mut Text, &mut AccessibilityNode), With<AnnouncementText>>,
+    settings: Res<AccessibilitySettings>,
+    language_settings: Res<LanguageSettings>,
+) {
+    if !settings.announcements_enabled {
+        announcement_events.clear();
+
+        // 关闭时清空实时区域，使用空格占位保持布局稳定
+        if settings.is_changed() {
+            for (mut text, mut node) in region_query.iter_mut() {
+                node.clear_label();
+                **text = " ".to_string();
+            }
+        }
+        return;
+    }
+
+    let texts = language_settings.get_texts();
+    for event in announcement_events.read() {
+        let message = format_announcement(texts, event);
+        for (mut text, mut node) in region_query.iter_mut() {
+            node.set_label(message.clone());
+            **text = message.clone();
+        }
+    }
+}
+
+/// 切换走棋播报（默认N键）
+pub fn toggle_announcements_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut settings: ResMut<AccessibilitySettings>,
+) {
+    if input_map.just_pressed(InputAction::ToggleAnnouncements, &keyboard_input) {
+        settings.announcements_enabled = !settings.announcements_enabled;
+    }
+}
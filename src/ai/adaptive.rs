@@ -0,0 +1,118 @@
+// 自适应难度 - 根据玩家近期战绩在对局之间调整AI强度
+//
+// 每局结束后统计最近若干局的胜率，胜率高于50%时加深搜索、减少失误，
+// 低于50%时减浅搜索、增加失误，使玩家胜率大致维持在一半左右
+
+use super::endgame::GameOutcome;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// 计算胜率时统计的最近对局数
+pub const ADAPTIVE_RECENT_GAMES: usize = 10;
+
+/// 自适应难度的搜索深度范围
+pub const ADAPTIVE_MIN_DEPTH: u8 = 1;
+pub const ADAPTIVE_MAX_DEPTH: u8 = 8;
+
+/// 自适应难度的失误倾向上限
+pub const ADAPTIVE_MAX_MISTAKE: f32 = 0.4;
+
+/// 自适应难度失误时的候选走法数
+pub const ADAPTIVE_MISTAKE_TOP_K: usize = 4;
+
+/// 失误倾向换算为失误温度的系数，0.3对应初级的温度60
+const MISTAKE_TEMPERATURE_SCALE: f32 = 200.0;
+
+/// 每次调整时失误倾向的变化量
+const MISTAKE_STEP: f32 = 0.05;
+
+/// 自适应难度当前的AI强度
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveLevel {
+    /// 搜索深度
+    pub max_depth: u8,
+
+    /// 失误倾向，0到ADAPTIVE_MAX_MISTAKE，按比例换算为失误温度
+    ///
+    /// 沿用旧版"失误概率"的字段名和取值范围，已保存的战绩无需迁移
+    pub mistake_probability: f32,
+}
+
+impl Default for AdaptiveLevel {
+    fn default() -> Self {
+        // 从接近中级的强度开始
+        Self {
+            max_depth: 3,
+            mistake_probability: 0.2,
+        }
+    }
+}
+
+impl AdaptiveLevel {
+    /// 搜索参数中使用的失误温度
+    pub fn mistake_temperature(&self) -> f32 {
+        self.mistake_probability * MISTAKE_TEMPERATURE_SCALE
+    }
+
+    /// 提高一级强度：加深搜索并减少失误
+    pub fn stronger(self) -> Self {
+        Self {
+            max_depth: (self.max_depth + 1).min(ADAPTIVE_MAX_DEPTH),
+            mistake_probability: (self.mistake_probability - MISTAKE_STEP).max(0.0),
+        }
+    }
+
+    /// 降低一级强度：减浅搜索并增加失误
+    pub fn weaker(self) -> Self {
+        Self {
+            max_depth: self.max_depth.saturating_sub(1).max(ADAPTIVE_MIN_DEPTH),
+            mistake_probability: (self.mistake_probability + MISTAKE_STEP)
+                .min(ADAPTIVE_MAX_MISTAKE),
+        }
+    }
+}
+
+/// 自适应难度状态 - 当前强度和玩家最近的对局结果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptiveState {
+    /// 下一局使用的AI强度
+    pub level: AdaptiveLevel,
+
+    /// 玩家最近的对局结果（从玩家视角），最旧的在前
+    pub recent_results: VecDeque<GameOutcome>,
+}
+
+impl AdaptiveState {
+    /// 玩家最近对局的胜率，平局计为半胜，没有记录时返回None
+    pub fn win_rate(&self) -> Option<f32> {
+        if self.recent_results.is_empty() {
+            return None;
+        }
+
+        let points: f32 = self
+            .recent_results
+            .iter()
+            .map(|outcome| match outcome {
+                GameOutcome::Win => 1.0,
+                GameOutcome::Draw => 0.5,
+                GameOutcome::Loss => 0.0,
+            })
+            .sum();
+        Some(points / self.recent_results.len() as f32)
+    }
+
+    /// 记录一局自适应对局的结果，并据此调整下一局的强度
+    pub fn record_result(&mut self, outcome: GameOutcome) {
+        self.recent_results.push_back(outcome);
+        while self.recent_results.len() > ADAPTIVE_RECENT_GAMES {
+            self.recent_results.pop_front();
+        }
+
+        match self.win_rate() {
+            Some(rate) if rate > 0.5 => self.level = self.level.stronger(),
+            Some(rate) if rate < 0.5 => self.level = self.level.weaker(),
+            _ => {}
+        }
+    }
+}
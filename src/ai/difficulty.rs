@@ -1,27 +1,63 @@
 // AI难度系统 - 实现多级别AI对手
 //
-// 通过调整搜索深度、时间限制和错误概率来模拟不同水平的AI对手
+// 通过调整搜索深度、时间限制和失误温度来模拟不同水平的AI对手
 // 让玩家可以根据自己的水平选择合适的挑战难度
 
-use super::minimax::find_best_move_with_time_limit;
-use crate::game::{Board, Move, PlayerColor};
+use super::{
+    adaptive::{AdaptiveLevel, ADAPTIVE_MISTAKE_TOP_K},
+    endgame::{is_solvable, solve_endgame_within, SolveLimits, ENDGAME_SOLVER_MAX_EMPTIES},
+    evaluation::EvalConfig,
+    evaluator::EvaluatorKind,
+    minimax::{find_best_move, find_best_move_with_time_limit, SearchResult, TimeManager},
+    mistakes::{rank_moves, sample_move},
+    opening_book::book_moves,
+    scheduler::{AiScheduler, SearchPermit, SearchPriority},
+    transposition::{SearchPreferences, TableStats, TranspositionTable},
+};
+use crate::game::{position_hash, Board, Move, PlayerColor};
 use bevy::{
+    platform::time::Instant,
     prelude::*,
     tasks::{AsyncComputeTaskPool, Task},
 };
 use futures_lite::future;
-use rand::{random, Rng};
+use rand::{random, rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 // 时间相关功能：根据平台支持情况选择合适的Duration类型
 #[cfg(any(target_arch = "wasm32", target_family = "wasm"))]
 use core::time::Duration;
 #[cfg(not(any(target_arch = "wasm32", target_family = "wasm")))]
 use std::time::Duration;
 
+/// 看门狗的硬性时限为搜索时限的倍数
+pub const WATCHDOG_TIME_FACTOR: u32 = 3;
+
+/// 看门狗的最短时限，避免时限很短的难度在残局求解时被误判为卡死
+pub const WATCHDOG_MIN_DEADLINE: Duration = Duration::from_secs(1);
+
+/// 备用走法的搜索深度
+const FALLBACK_DEPTH: u8 = 2;
+
+/// 使用开局库的难度在对局前几步直接走开局库中的棋
+pub const BOOK_PLIES: u32 = 2;
+
 /// AI难度级别枚举
 ///
-/// 定义了四个不同的AI难度级别，每个级别都有对应的搜索参数配置
-#[derive(Component, Debug, Clone, Copy)]
+/// 定义了一个随机走棋的娱乐难度、五个固定的AI难度级别和一个自适应难度，每个级别都有对应的搜索参数配置
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum AiDifficulty {
+    /// 混乱难度 - 比初级更弱，适合儿童和快速检查界面流程
+    /// 不搜索，立即在合法走法中等概率随机选择；携带随机种子，同样的种子在同样的局面下总是走同样的棋
+    Chaos(u64),
+
     /// 初级难度 - 适合新手玩家
     /// 搜索深度较浅，会偶尔犯错
     Beginner,
@@ -37,6 +73,14 @@ pub enum AiDifficulty {
     /// 专家难度 - 最高难度
     /// 搜索深度最深，完美发挥
     Expert,
+
+    /// 专家+难度 - 搜索参数与专家相同，使用神经网络评估（需要启用nn-eval特性）
+    /// 网络权重缺失时使用启发式评估，与专家难度相同
+    ExpertPlus,
+
+    /// 自适应难度 - 根据玩家近期战绩在对局之间调整强度
+    /// 携带本局使用的搜索深度和失误倾向
+    Adaptive(AdaptiveLevel),
 }
 
 /// AI搜索参数配置
@@ -52,79 +96,367 @@ pub struct SearchParams {
     /// 确保游戏流畅性
     pub time_limit: Duration,
 
-    /// 失误概率 - 模拟AI犯错的可能性
-    /// 0.0表示完美发挥，1.0表示完全随机
-    pub mistake_probability: f32,
+    /// 失误温度 - 对前k个候选走法的评估分数做softmax时使用的温度
+    /// 0.0表示总是走最佳走法，温度越高越容易选中评估较差的走法
+    pub mistake_temperature: f32,
 
-    /// 是否使用开局库 - 预设的开局走法
-    /// 未来可能用于优化开局表现
-    #[allow(dead_code)]
-    pub use_opening_book: bool,
+    /// 失误候选数 - 只在评估最高的前k个走法中抽样
+    /// 1表示总是走最佳走法
+    pub mistake_top_k: usize,
+
+    /// 残局完美求解的空位阈值 - 空位数不超过该值时搜索到终局
+    /// 0表示不使用残局求解
+    pub endgame_empties: u32,
+
+    /// 评估器种类 - 搜索叶子节点和失误模型使用的局面评估
+    pub evaluator: EvaluatorKind,
+
+    /// 开局库步数 - 对局的前几步直接从开局库中随机选择（包括对称等价的走法），不搜索
+    /// 0表示不使用开局库
+    pub book_plies: u32,
+
+    /// 随机走棋 - 不搜索，按种子在合法走法中等概率选择并立即走出
+    /// 只有混乱难度使用，其余参数不起作用
+    pub random_moves: bool,
 }
 
 impl AiDifficulty {
+    /// 练习中对局时可以调整到的固定难度，从弱到强
+    pub const PRACTICE_LADDER: [AiDifficulty; 5] = [
+        AiDifficulty::Beginner,
+        AiDifficulty::Intermediate,
+        AiDifficulty::Advanced,
+        AiDifficulty::Expert,
+        AiDifficulty::ExpertPlus,
+    ];
+
+    /// 根据英文名称获取固定难度级别（不区分大小写），用于命令行参数
+    ///
+    /// 混乱难度可以写成"chaos:42"指定随机种子，只写"chaos"时随机选择种子
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        if let Some(seed) = name.strip_prefix("chaos:") {
+            return seed.parse().ok().map(Self::Chaos);
+        }
+        match name.as_str() {
+            "chaos" => Some(Self::Chaos(random())),
+            "beginner" | "easy" => Some(Self::Beginner),
+            "intermediate" | "medium" => Some(Self::Intermediate),
+            "advanced" | "hard" => Some(Self::Advanced),
+            "expert" => Some(Self::Expert),
+            "expert+" | "expertplus" | "expert_plus" => Some(Self::ExpertPlus),
+            _ => None,
+        }
+    }
+
     /// 获取对应难度级别的搜索参数
     ///
-    /// 根据AI难度返回相应的搜索配置，包括搜索深度、时间限制和错误率
+    /// 根据AI难度返回相应的搜索配置，包括搜索深度、时间限制和失误温度
     pub fn get_search_params(&self) -> SearchParams {
         match self {
-            // 初级：搜索2层，100ms时限，30%错误率
+            // 混乱：不搜索，随机走棋
+            Self::Chaos(_) => SearchParams {
+                max_depth: 0,
+                time_limit: Duration::ZERO,
+                mistake_temperature: 0.0,
+                mistake_top_k: 1,
+                endgame_empties: 0,
+                book_plies: 0,
+                evaluator: EvaluatorKind::Heuristic,
+                random_moves: true,
+            },
+            // 初级：搜索2层，100ms时限，常在前5个走法中选到次优的棋
             Self::Beginner => SearchParams {
                 max_depth: 2,
                 time_limit: Duration::from_millis(100),
-                mistake_probability: 0.3, // 30%概率犯错，模拟新手
-                use_opening_book: false,
+                mistake_temperature: 60.0, // 高温度，模拟新手
+                mistake_top_k: 5,
+                endgame_empties: 0,
+                book_plies: 0,
+                evaluator: EvaluatorKind::Heuristic,
+                random_moves: false,
             },
-            // 中级：搜索4层，500ms时限，15%错误率
+            // 中级：搜索4层，500ms时限，偶尔在前3个走法中失误
             Self::Intermediate => SearchParams {
                 max_depth: 4,
                 time_limit: Duration::from_millis(500),
-                mistake_probability: 0.15, // 15%概率犯错，偶尔失误
-                use_opening_book: false,
+                mistake_temperature: 30.0, // 偶尔失误
+                mistake_top_k: 3,
+                endgame_empties: 0,
+                book_plies: 0,
+                evaluator: EvaluatorKind::Heuristic,
+                random_moves: false,
             },
-            // 高级：搜索6层，2秒时限，5%错误率
+            // 高级：搜索6层，2秒时限，很少失误，最后8个空位完美求解
             Self::Advanced => SearchParams {
                 max_depth: 6,
                 time_limit: Duration::from_secs(2),
-                mistake_probability: 0.05, // 5%概率犯错，很少出错
-                use_opening_book: true,
+                mistake_temperature: 10.0, // 只在评估接近时选第二好的棋
+                mistake_top_k: 2,
+                endgame_empties: 8, // 最后8个空位完美求解
+                book_plies: BOOK_PLIES,
+                evaluator: EvaluatorKind::Heuristic,
+                random_moves: false,
             },
-            // 专家：搜索12层，5秒时限，0%错误率
+            // 专家：搜索12层，5秒时限，不失误，最后12个空位完美求解
             Self::Expert => SearchParams {
                 max_depth: 12,
                 time_limit: Duration::from_secs(5),
-                mistake_probability: 0.0, // 完美发挥，不犯错
-                use_opening_book: true,
+                mistake_temperature: 0.0, // 完美发挥，不犯错
+                mistake_top_k: 1,
+                endgame_empties: ENDGAME_SOLVER_MAX_EMPTIES,
+                book_plies: BOOK_PLIES,
+                evaluator: EvaluatorKind::Heuristic,
+                random_moves: false,
+            },
+            // 专家+：与专家相同的搜索，叶子节点使用神经网络评估
+            Self::ExpertPlus => SearchParams {
+                max_depth: 12,
+                time_limit: Duration::from_secs(5),
+                mistake_temperature: 0.0, // 完美发挥，不犯错
+                mistake_top_k: 1,
+                endgame_empties: ENDGAME_SOLVER_MAX_EMPTIES,
+                book_plies: BOOK_PLIES,
+                evaluator: EvaluatorKind::Network,
+                random_moves: false,
             },
+            // 自适应：深度和失误温度由玩家战绩决定，每层100ms时限
+            Self::Adaptive(level) => SearchParams {
+                max_depth: level.max_depth,
+                time_limit: Duration::from_millis(100 * level.max_depth as u64),
+                mistake_temperature: level.mistake_temperature(),
+                mistake_top_k: ADAPTIVE_MISTAKE_TOP_K,
+                endgame_empties: 0,
+                book_plies: 0,
+                evaluator: EvaluatorKind::Heuristic,
+                random_moves: false,
+            },
+        }
+    }
+
+    /// 估计的等级分，在难度选择界面上作为参考
+    ///
+    /// 只是按搜索深度和失误倾向粗略估计的数值，没有经过实际对局测定。
+    /// 自适应难度按当前强度估计，取50的整数倍
+    pub fn estimated_elo(&self) -> u32 {
+        match self {
+            Self::Chaos(_) => 200,
+            Self::Beginner => 800,
+            Self::Intermediate => 1200,
+            Self::Advanced => 1600,
+            Self::Expert => 2000,
+            Self::ExpertPlus => 2100,
+            Self::Adaptive(level) => {
+                let elo =
+                    600.0 + 150.0 * level.max_depth as f32 - 1000.0 * level.mistake_probability;
+                ((elo / 50.0).round() * 50.0).max(400.0) as u32
+            }
         }
     }
 
+    /// 在PRACTICE_LADDER中调整`steps`档（正数变强），到两端时不再变化
+    ///
+    /// 不在阶梯中的难度（混乱、自适应）按估计的等级分找到相邻的一档，例如混乱难度调强一档为初级；
+    /// 这个方向上没有相邻的一档时不变
+    pub fn stepped(self, steps: i32) -> Self {
+        let ladder = Self::PRACTICE_LADDER;
+        let elo = self.estimated_elo();
+        let index = match ladder.iter().position(|&difficulty| difficulty == self) {
+            Some(index) => index as i32 + steps,
+            None if steps > 0 => {
+                let Some(stronger) = ladder
+                    .iter()
+                    .position(|difficulty| difficulty.estimated_elo() > elo)
+                else {
+                    return self;
+                };
+                stronger as i32 + steps - 1
+            }
+            None if steps < 0 => {
+                let Some(weaker) = ladder
+                    .iter()
+                    .rposition(|difficulty| difficulty.estimated_elo() < elo)
+                else {
+                    return self;
+                };
+                weaker as i32 + steps + 1
+            }
+            None => return self,
+        };
+        ladder[index.clamp(0, ladder.len() as i32 - 1) as usize]
+    }
+
     /// 获取AI的下一步棋
     ///
     /// 根据当前棋盘状态和AI难度，计算出最佳走法
-    /// 可能会根据错误概率故意选择非最优解，模拟真实对手
+    /// 可能会按失误温度选择评估稍差的走法，模拟真实对手
     pub fn get_ai_move(&self, board: &Board, player: PlayerColor) -> Option<Move> {
-        let params = self.get_search_params();
+        self.get_ai_move_with(board, player, EvalConfig::standard())
+    }
+
+    /// 使用指定评估配置获取AI的下一步棋
+    ///
+    /// 评估配置用于启发式评估，实际使用的评估器由难度的搜索参数决定
+    pub fn get_ai_move_with(
+        &self,
+        board: &Board,
+        player: PlayerColor,
+        eval: &EvalConfig,
+    ) -> Option<Move> {
+        self.get_ai_move_with_table(board, player, eval, None)
+    }
+
+    /// 使用置换表获取AI的下一步棋，置换表在同一方的多次走棋之间保留
+    pub fn get_ai_move_with_table(
+        &self,
+        board: &Board,
+        player: PlayerColor,
+        eval: &EvalConfig,
+        table: Option<&TranspositionTable>,
+    ) -> Option<Move> {
+        let time_limit = self.get_search_params().time_limit;
+        self.get_ai_move_with_budget(board, player, eval, table, time_limit)
+    }
+
+    /// 使用指定的搜索时间获取AI的下一步棋，时间由对局中的时间管理按局面复杂度分配
+    pub fn get_ai_move_with_budget(
+        &self,
+        board: &Board,
+        player: PlayerColor,
+        eval: &EvalConfig,
+        table: Option<&TranspositionTable>,
+        time_limit: Duration,
+    ) -> Option<Move> {
+        self.search_ai_move(board, player, eval, table, time_limit, None)
+            .best_move
+    }
+
+    /// 与`get_ai_move_with_budget`相同，同时返回搜索深度和节点数等统计
+    ///
+    /// 结果中的走法是最终走出的棋（可能是按失误温度抽样的走法）；
+    /// 残局完美求解时深度为空位数，不需要搜索的走法深度和节点数为0。
+    /// `cancel`被设置后搜索尽快返回，`completed`为false，结果应当丢弃
+    pub fn search_ai_move(
+        &self,
+        board: &Board,
+        player: PlayerColor,
+        eval: &EvalConfig,
+        table: Option<&TranspositionTable>,
+        time_limit: Duration,
+        cancel: Option<&AtomicBool>,
+    ) -> SearchResult {
+        if let Some(instant_move) = self.instant_move(board, player) {
+            return SearchResult {
+                best_move: Some(instant_move),
+                completed: true,
+                ..default()
+            };
+        }
 
-        // 使用Minimax算法搜索最佳走法
-        let result =
-            find_best_move_with_time_limit(board, params.time_limit, params.max_depth, player);
+        let params = self.get_search_params();
+        let evaluator = params.evaluator.evaluator(eval);
 
-        // 根据失误概率决定是否故意犯错
-        if params.mistake_probability > 0.0 && random::<f32>() < params.mistake_probability {
-            // 故意选择随机走法，模拟人类失误
-            self.make_random_mistake(board, player)
+        // 残局空位足够少时在时限内完美求解，超时后改用Minimax算法搜索最佳走法，
+        // 两者合计不超过时限的2倍，不会触发看门狗
+        let solved = (params.endgame_empties > 0 && is_solvable(board, params.endgame_empties))
+            .then(|| {
+                let limits = SolveLimits {
+                    deadline: Some(Instant::now() + time_limit),
+                    cancel,
+                };
+                solve_endgame_within(board, player, limits)
+            })
+            .flatten();
+        let mut result = if let Some(solved) = solved {
+            SearchResult {
+                best_move: solved.best_move,
+                evaluation: solved.score,
+                depth_reached: board.get_empty_squares().count_ones() as u8,
+                nodes_evaluated: solved.nodes,
+                completed: true,
+            }
         } else {
-            // 返回最佳走法
-            result.best_move
+            if let Some(table) = table {
+                table.new_search();
+            }
+            find_best_move_with_time_limit(
+                board,
+                time_limit,
+                params.max_depth,
+                player,
+                evaluator,
+                table,
+                cancel,
+            )
+        };
+        if cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            return result;
         }
+
+        // 按温度在评估最高的前k个走法中抽样，温度为0时直接返回最佳走法
+        if params.mistake_temperature > 0.0 && params.mistake_top_k > 1 {
+            let ranked = rank_moves(board, player, evaluator, result.best_move);
+            result.best_move = sample_move(
+                &ranked,
+                params.mistake_temperature,
+                params.mistake_top_k,
+                random(),
+            );
+        }
+        result
     }
 
-    /// 模拟AI犯错 - 随机选择一个合法走法
+    /// 不需要搜索、可以立即走出的棋：混乱难度的随机走法或开局库中的走法
+    pub fn instant_move(&self, board: &Board, player: PlayerColor) -> Option<Move> {
+        match self {
+            Self::Chaos(seed) => seeded_random_move(board, player, *seed),
+            _ => self.book_move(board, player),
+        }
+    }
+
+    /// 开局库中的走法
     ///
-    /// 当AI需要故意犯错时调用，从所有合法走法中随机选择一个
-    /// 这样可以让低难度AI更像真实的初学者
-    fn make_random_mistake(&self, board: &Board, player: PlayerColor) -> Option<Move> {
+    /// 只在对局的前`book_plies`步（按棋盘上的棋子数计算）且局面在开局库中时给出，
+    /// 在所有对称等价的开局库走法中随机选择一个；这些走法强度相同，随机选择让开局更多变
+    pub fn book_move(&self, board: &Board, player: PlayerColor) -> Option<Move> {
+        let plies = (board.black | board.white).count_ones().saturating_sub(4);
+        if plies >= self.get_search_params().book_plies {
+            return None;
+        }
+        let moves = book_moves(board, player);
+        if moves.is_empty() {
+            return None;
+        }
+        let index = rand::thread_rng().gen_range(0..moves.len());
+        Some(Move {
+            position: moves[index],
+        })
+    }
+
+    /// 看门狗的硬性时限，计算超过该时间视为卡死
+    pub fn watchdog_deadline(&self) -> Duration {
+        (self.get_search_params().time_limit * WATCHDOG_TIME_FACTOR).max(WATCHDOG_MIN_DEADLINE)
+    }
+
+    /// 备用走法：计算任务失败或超时时使用浅层搜索的最佳走法，
+    /// 浅层搜索也失败时随机选择一个合法走法
+    pub fn fallback_move(
+        &self,
+        board: &Board,
+        player: PlayerColor,
+        eval: &EvalConfig,
+    ) -> Option<Move> {
+        let evaluator = self.get_search_params().evaluator.evaluator(eval);
+        catch_unwind(AssertUnwindSafe(|| {
+            find_best_move(board, FALLBACK_DEPTH, player, evaluator).best_move
+        }))
+        .ok()
+        .flatten()
+        .or_else(|| self.random_legal_move(board, player))
+    }
+
+    /// 从所有合法走法中随机选择一个，浅层搜索失败时的最后手段
+    fn random_legal_move(&self, board: &Board, player: PlayerColor) -> Option<Move> {
         let valid_moves = board.get_valid_moves_list(player);
         if valid_moves.is_empty() {
             return None;
@@ -136,6 +468,68 @@ impl AiDifficulty {
     }
 }
 
+/// 按种子在合法走法中等概率随机选择一个，种子与局面一起决定结果，没有合法走法时返回None
+pub fn seeded_random_move(board: &Board, player: PlayerColor, seed: u64) -> Option<Move> {
+    let valid_moves = board.get_valid_moves_list(player);
+    if valid_moves.is_empty() {
+        return None;
+    }
+    let mut rng = StdRng::seed_from_u64(seed ^ position_hash(board, player));
+    Some(valid_moves[rng.gen_range(0..valid_moves.len())])
+}
+
+/// AI计算任务失败的原因
+#[derive(Debug, Clone, PartialEq)]
+pub enum AiFailure {
+    /// 计算任务发生panic
+    Panicked(String),
+    /// 计算超过看门狗时限
+    TimedOut(Duration),
+}
+
+impl fmt::Display for AiFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panicked(message) => write!(f, "AI task panicked: {message}"),
+            Self::TimedOut(elapsed) => write!(f, "AI task stalled for {elapsed:?}"),
+        }
+    }
+}
+
+/// 正在进行的计算，看门狗用它判断超时并计算备用走法
+struct ThinkingJob {
+    started: Instant,
+    board: Board,
+    eval: Arc<EvalConfig>,
+}
+
+/// 已完成计算的用时统计，看门狗改用备用走法时同样计入
+#[derive(Debug, Clone, Copy, Default)]
+struct SearchTimes {
+    last: Option<Duration>,
+    total: Duration,
+    count: u32,
+}
+
+impl SearchTimes {
+    fn record(&mut self, elapsed: Duration) {
+        self.last = Some(elapsed);
+        self.total += elapsed;
+        self.count += 1;
+    }
+}
+
+/// 一次搜索看得多深、多快，用于走棋后的搜索说明
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchSummary {
+    /// 搜索的深度（步数），残局完美求解时为当时的空位数
+    pub depth: u8,
+    /// 搜索的节点数
+    pub nodes: u64,
+    /// 计算用时
+    pub elapsed: Duration,
+}
+
 /// AI玩家组件
 ///
 /// 在Bevy ECS系统中表示AI玩家实体的组件
@@ -153,11 +547,37 @@ pub struct AiPlayer {
     pub thinking_timer: Timer,
 
     /// 当前AI计算任务 - 用于异步计算
-    /// None表示没有正在进行的计算
-    pub current_task: Option<Task<Option<Move>>>,
+    /// None表示没有正在进行的计算，任务发生panic时结果为Err
+    pub current_task: Option<Task<Result<SearchResult, String>>>,
 
     /// AI是否正在思考
     pub is_thinking: bool,
+
+    /// 当前计算的开始时间和局面
+    job: Option<ThinkingJob>,
+
+    /// 当前计算的取消标志，调度器取消或看门狗放弃计算时设置，搜索检查到后尽快返回；
+    /// 从调度器取得的许可由计算任务持有，计算真正结束时才归还预算
+    cancel: Option<Arc<AtomicBool>>,
+
+    /// 置换表的大小（MB），来自搜索设置
+    table_size_mb: u32,
+
+    /// 置换表，第一次计算时按table_size_mb创建，在本局的多次走棋之间保留
+    table: Option<Arc<TranspositionTable>>,
+
+    /// 本局计算用时的统计，不含模拟思考的等待时间
+    search_times: SearchTimes,
+
+    /// 最近一次计算的搜索统计，外部引擎和备用走法没有
+    last_search: Option<SearchSummary>,
+
+    /// 本局的时间管理，按局面复杂度分配每步的搜索时间
+    time_manager: TimeManager,
+
+    /// 代替内置搜索走棋的外部引擎
+    #[cfg(feature = "remote_engine")]
+    pub remote: Option<Arc<super::remote::RemoteEngine>>,
 }
 
 impl AiPlayer {
@@ -177,40 +597,263 @@ impl AiPlayer {
             thinking_timer: Timer::new(Duration::from_millis(1000), TimerMode::Once),
             current_task: None,
             is_thinking: false,
+            job: None,
+            cancel: None,
+            table_size_mb: SearchPreferences::default().table_size_mb,
+            table: None,
+            search_times: SearchTimes::default(),
+            last_search: None,
+            time_manager: TimeManager::new(difficulty.get_search_params().time_limit),
+            #[cfg(feature = "remote_engine")]
+            remote: None,
+        }
+    }
+
+    /// 对局中修改难度，之后的走棋使用新难度的搜索参数，正在进行的计算不受影响；
+    /// 整局的时间预算按新难度的每步时间重新计算
+    pub fn set_difficulty(&mut self, difficulty: AiDifficulty) {
+        if self.difficulty != difficulty {
+            self.difficulty = difficulty;
+            self.time_manager = TimeManager::new(difficulty.get_search_params().time_limit);
+        }
+    }
+
+    /// 置换表的大小（MB）
+    pub fn table_size_mb(&self) -> u32 {
+        self.table_size_mb
+    }
+
+    /// 修改置换表的大小，大小变化时丢弃现有的表，下次计算时按新大小创建
+    pub fn set_table_size_mb(&mut self, size_mb: u32) {
+        if self.table_size_mb != size_mb {
+            self.table_size_mb = size_mb;
+            self.table = None;
         }
     }
 
+    /// 置换表的统计数据，还没有创建置换表时为None
+    pub fn table_stats(&self) -> Option<TableStats> {
+        self.table.as_ref().map(|table| table.stats())
+    }
+
+    /// 最近一次计算的用时，本局还没有完成计算时为None
+    pub fn last_search_time(&self) -> Option<Duration> {
+        self.search_times.last
+    }
+
+    /// 最近一次计算的搜索深度、节点数和用时；本局还没有完成计算，
+    /// 或最近一次计算没有经过搜索（外部引擎、备用走法）时为None
+    pub fn last_search(&self) -> Option<SearchSummary> {
+        self.last_search
+    }
+
+    /// 本局每次计算的平均用时，还没有完成计算时为None
+    pub fn average_search_time(&self) -> Option<Duration> {
+        (self.search_times.count > 0).then(|| self.search_times.total / self.search_times.count)
+    }
+
+    /// 本局的时间管理
+    pub fn time_manager(&self) -> &TimeManager {
+        &self.time_manager
+    }
+
+    /// 改由外部引擎走棋，内置搜索只在外部引擎失败时使用
+    #[cfg(feature = "remote_engine")]
+    pub fn with_remote_engine(mut self, engine: super::remote::RemoteEngine) -> Self {
+        self.remote = Some(Arc::new(engine));
+        self
+    }
+
+    /// 可以立即走出、不需要思考的走法（混乱难度的随机走法或开局库走法）；使用外部引擎时由引擎决定
+    pub fn instant_move(&self, board: &Board) -> Option<Move> {
+        #[cfg(feature = "remote_engine")]
+        if self.remote.is_some() {
+            return None;
+        }
+        self.difficulty.instant_move(board, self.color)
+    }
+
+    /// 看门狗的硬性时限，使用外部引擎时加上等待引擎回复的时限
+    pub fn watchdog_deadline(&self) -> Duration {
+        let deadline = self.difficulty.watchdog_deadline();
+        #[cfg(feature = "remote_engine")]
+        if self.remote.is_some() {
+            return deadline + super::remote::REMOTE_ENGINE_TIMEOUT;
+        }
+        deadline
+    }
+
     /// 开始异步AI计算
     ///
     /// 在后台线程池中启动AI计算任务，避免阻塞主线程
     pub fn start_thinking(&mut self, board: &Board) {
+        self.start_thinking_with(board, Arc::new(EvalConfig::standard().clone()));
+    }
+
+    /// 使用指定评估配置开始异步AI计算
+    pub fn start_thinking_with(&mut self, board: &Board, eval: Arc<EvalConfig>) {
         if self.current_task.is_some() || self.is_thinking {
             return; // 已经在思考中
         }
+        self.spawn_search(board, eval, None);
+    }
 
+    /// 在后台线程池中启动计算任务，调度器的许可移动到任务中，计算返回时归还
+    fn spawn_search(&mut self, board: &Board, eval: Arc<EvalConfig>, permit: Option<SearchPermit>) {
+        let cancel = permit.as_ref().map_or_else(
+            || Arc::new(AtomicBool::new(false)),
+            SearchPermit::cancel_flag,
+        );
+        self.cancel = Some(cancel.clone());
         let board_copy = *board;
         let difficulty = self.difficulty;
         let player = self.color;
+        let time_limit = self.time_manager.budget(board, player);
+        let table_size_mb = self.table_size_mb;
+        let table = self
+            .table
+            .get_or_insert_with(|| Arc::new(TranspositionTable::with_size_mb(table_size_mb)))
+            .clone();
+        #[cfg(feature = "remote_engine")]
+        let remote = self.remote.clone();
+        self.job = Some(ThinkingJob {
+            started: Instant::now(),
+            board: board_copy,
+            eval: eval.clone(),
+        });
 
+        // 捕获计算中的panic，由看门狗改用备用走法，而不是让对局一直等待
         let task_pool = AsyncComputeTaskPool::get();
-        let task = task_pool.spawn(async move { difficulty.get_ai_move(&board_copy, player) });
+        let task = task_pool.spawn(async move {
+            let _permit = permit;
+            catch_unwind(AssertUnwindSafe(|| {
+                #[cfg(feature = "remote_engine")]
+                if let Some(remote) = &remote {
+                    return SearchResult {
+                        best_move: remote.choose_move(difficulty, &board_copy, player, &eval),
+                        ..default()
+                    };
+                }
+                difficulty.search_ai_move(
+                    &board_copy,
+                    player,
+                    &eval,
+                    Some(&table),
+                    time_limit,
+                    Some(&cancel),
+                )
+            }))
+            .map_err(|payload| panic_message(payload.as_ref()))
+        });
 
         self.current_task = Some(task);
         self.is_thinking = true;
     }
 
+    /// 向调度器申请许可后开始异步AI计算
+    ///
+    /// # 返回
+    /// 是否正在思考；调度器没有给出许可时不开始计算，调用方稍后再试
+    pub fn start_scheduled_thinking(
+        &mut self,
+        board: &Board,
+        eval: Arc<EvalConfig>,
+        scheduler: &AiScheduler,
+        priority: SearchPriority,
+    ) -> bool {
+        if self.current_task.is_some() || self.is_thinking {
+            return true;
+        }
+        let Some(permit) = scheduler.request(priority) else {
+            return false;
+        };
+        self.spawn_search(board, eval, Some(permit));
+        true
+    }
+
     /// 检查AI计算是否完成，并返回结果
     ///
+    /// 同时充当看门狗：计算任务panic或超过硬性时限时取消任务，
+    /// 记录日志并改用备用走法，保证对局不会一直等待AI。
+    /// 计算被调度器取消时丢弃任务并结束思考，由调用方重新开始；
+    /// 被取消的搜索很快返回，在此之前仍占用调度器的预算
+    ///
     /// # 返回
-    /// Some(move) 如果AI计算完成，None 如果还在计算中
+    /// Some(move) 如果AI计算完成，None 如果还在计算中或已被取消
     pub fn check_thinking_result(&mut self) -> Option<Option<Move>> {
-        if let Some(task) = &mut self.current_task {
-            if let Some(result) = future::block_on(future::poll_once(task)) {
-                self.current_task = None;
-                self.is_thinking = false;
-                return Some(result);
+        if self.current_task.is_some()
+            && self
+                .cancel
+                .as_ref()
+                .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+        {
+            debug!("AI search cancelled by a higher priority search");
+            self.finish_thinking();
+            return None;
+        }
+
+        let task = self.current_task.as_mut()?;
+        let failure = match future::block_on(future::poll_once(task)) {
+            Some(Ok(result)) => {
+                self.last_search = None;
+                if let Some(job) = &self.job {
+                    let elapsed = job.started.elapsed();
+                    self.record_search_time(elapsed);
+                    self.last_search = (result.depth_reached > 0).then_some(SearchSummary {
+                        depth: result.depth_reached,
+                        nodes: result.nodes_evaluated,
+                        elapsed,
+                    });
+                }
+                self.finish_thinking();
+                return Some(result.best_move);
+            }
+            Some(Err(message)) => AiFailure::Panicked(message),
+            None => {
+                let elapsed = self.job.as_ref()?.started.elapsed();
+                if elapsed <= self.watchdog_deadline() {
+                    return None;
+                }
+                AiFailure::TimedOut(elapsed)
             }
+        };
+
+        // 设置取消标志让仍在运行的搜索尽快返回，丢弃任务，结果被忽略
+        if let Some(cancel) = &self.cancel {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        let job = self.job.take();
+        self.last_search = None;
+        if let Some(job) = &job {
+            self.record_search_time(job.started.elapsed());
         }
-        None
+        self.finish_thinking();
+        warn!("AI watchdog: {failure}, falling back to a shallow search");
+        Some(job.and_then(|job| {
+            self.difficulty
+                .fallback_move(&job.board, self.color, &job.eval)
+        }))
+    }
+
+    /// 计入用时统计和本局的时间预算
+    fn record_search_time(&mut self, elapsed: Duration) {
+        self.search_times.record(elapsed);
+        self.time_manager.record(elapsed);
     }
+
+    fn finish_thinking(&mut self) {
+        self.current_task = None;
+        self.job = None;
+        self.cancel = None;
+        self.is_thinking = false;
+    }
+}
+
+/// panic信息的文本
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
 }
@@ -0,0 +1,244 @@
+// 残局完美求解器 - 在剩余空位较少时搜索到终局
+//
+// 残局阶段的评估函数只是近似值，空位足够少时可以直接搜索到游戏结束，
+// 得到精确的终局子数差，从而保证残局下出最优着法。
+// 空位较多时角优先、再按对手走法数从少到多排序以加快剪枝；
+// 求解可以设置截止时间和取消标志，超出限制时放弃，由调用方改用普通搜索
+
+use crate::game::{Board, Move, PlayerColor, CORNER_MASK};
+use bevy::platform::time::Instant;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 完美求解支持的最大空位数
+///
+/// 超过该空位数时搜索量增长过快，不适合在游戏中实时求解
+pub const ENDGAME_SOLVER_MAX_EMPTIES: u32 = 12;
+
+/// 空位数多于该值时对走法排序
+const ORDERING_MIN_EMPTIES: u32 = 6;
+
+/// 每搜索多少个节点检查一次截止时间和取消标志
+const LIMIT_CHECK_INTERVAL: u64 = 1024;
+
+/// 对局结果（胜/负/平）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameOutcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl GameOutcome {
+    /// 根据终局子数差判断胜负
+    pub fn from_score(score: i32) -> Self {
+        match score {
+            s if s > 0 => Self::Win,
+            s if s < 0 => Self::Loss,
+            _ => Self::Draw,
+        }
+    }
+}
+
+/// 残局求解结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EndgameResult {
+    /// 最佳走法，无棋可走时为None
+    pub best_move: Option<Move>,
+
+    /// 双方完美下法时的终局子数差（己方 - 对方）
+    pub score: i32,
+
+    /// 搜索的节点数
+    pub nodes: u64,
+}
+
+impl EndgameResult {
+    /// 双方完美下法时的对局结果
+    pub fn outcome(&self) -> GameOutcome {
+        GameOutcome::from_score(self.score)
+    }
+}
+
+/// 求解的限制条件，超出任一限制时放弃求解
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SolveLimits<'a> {
+    /// 截止时间，到达后放弃求解
+    pub deadline: Option<Instant>,
+    /// 取消标志，被设置后放弃求解
+    pub cancel: Option<&'a AtomicBool>,
+}
+
+/// 求解残局，返回指定玩家的最佳走法和精确终局子数差
+///
+/// 不限制空位数，调用方应先确认空位数不超过[`ENDGAME_SOLVER_MAX_EMPTIES`]
+///
+/// # 参数
+/// * `board` - 当前棋盘状态
+/// * `player` - 轮到走棋的玩家
+pub fn solve_endgame(board: &Board, player: PlayerColor) -> EndgameResult {
+    solve_endgame_within(board, player, SolveLimits::default())
+        .expect("solver without limits always finishes")
+}
+
+/// 在限制条件内求解残局，超出时间或被取消时返回None
+///
+/// 对局AI用它在搜索时限内求解，超时后改用普通搜索，避免被看门狗判为超时
+pub fn solve_endgame_within(
+    board: &Board,
+    player: PlayerColor,
+    limits: SolveLimits,
+) -> Option<EndgameResult> {
+    let mut solver = Solver::new(limits);
+    let children = ordered_children(board, player);
+
+    // 无棋可走时直接求解对手走棋后的结果
+    if children.is_empty() {
+        let score = -solver.negamax(board, player.opposite(), -i32::MAX, i32::MAX, true)?;
+        return Some(EndgameResult {
+            best_move: None,
+            score,
+            nodes: solver.nodes,
+        });
+    }
+
+    let mut best_move = None;
+    let mut alpha = -i32::MAX;
+
+    for (chess_move, new_board) in children {
+        let score = -solver.negamax(&new_board, player.opposite(), -i32::MAX, -alpha, false)?;
+        if best_move.is_none() || score > alpha {
+            alpha = score;
+            best_move = Some(chess_move);
+        }
+    }
+
+    Some(EndgameResult {
+        best_move,
+        score: alpha,
+        nodes: solver.nodes,
+    })
+}
+
+/// 求解每个合法走法，返回走法位置和走该步后双方完美下法的终局子数差（己方 - 对方）
+///
+/// 与[`solve_endgame`]不同，每个走法都用完整窗口搜索，得到的都是精确值而不只是上下界；
+/// 无棋可走时返回空列表
+pub fn solve_each_move(board: &Board, player: PlayerColor) -> Vec<(u8, i32)> {
+    let mut solver = Solver::new(SolveLimits::default());
+    board
+        .get_valid_moves_list(player)
+        .into_iter()
+        .map(|chess_move| {
+            let mut new_board = *board;
+            new_board.make_move(chess_move.position, player);
+            let score = solver
+                .negamax(&new_board, player.opposite(), -i32::MAX, i32::MAX, false)
+                .expect("solver without limits always finishes");
+            (chess_move.position, -score)
+        })
+        .collect()
+}
+
+/// 判断当前局面是否适合完美求解
+pub fn is_solvable(board: &Board, max_empties: u32) -> bool {
+    board.get_empty_squares().count_ones() <= max_empties
+}
+
+/// 终局子数差（己方 - 对方）
+fn final_score(board: &Board, player: PlayerColor) -> i32 {
+    board.count_pieces(player) as i32 - board.count_pieces(player.opposite()) as i32
+}
+
+/// 合法走法和走后的局面，空位较多时按角优先、对手走法数从少到多排序
+///
+/// 好的走法先搜索能让Alpha-Beta剪掉更多分支；空位很少时排序本身的开销大于收益，保持原顺序
+fn ordered_children(board: &Board, player: PlayerColor) -> Vec<(Move, Board)> {
+    let mut children: Vec<(Move, Board)> = board
+        .get_valid_moves_list(player)
+        .into_iter()
+        .map(|chess_move| {
+            let mut new_board = *board;
+            new_board.make_move(chess_move.position, player);
+            (chess_move, new_board)
+        })
+        .collect();
+    if board.get_empty_squares().count_ones() > ORDERING_MIN_EMPTIES {
+        children.sort_by_cached_key(|(chess_move, new_board)| {
+            let corner = CORNER_MASK & (1 << chess_move.position) != 0;
+            (
+                !corner,
+                new_board.get_valid_moves(player.opposite()).count_ones(),
+            )
+        });
+    }
+    children
+}
+
+/// 一次求解的节点计数和限制条件
+struct Solver<'a> {
+    nodes: u64,
+    limits: SolveLimits<'a>,
+}
+
+impl<'a> Solver<'a> {
+    fn new(limits: SolveLimits<'a>) -> Self {
+        Self { nodes: 0, limits }
+    }
+
+    /// 是否超出限制，从第一个节点开始每隔一定节点数才检查一次时间
+    fn out_of_limits(&self) -> bool {
+        if self.nodes % LIMIT_CHECK_INTERVAL != 0 {
+            return false;
+        }
+        self.limits
+            .cancel
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+            || self
+                .limits
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Negamax搜索（带Alpha-Beta剪枝），搜索到游戏结束，超出限制时返回None
+    ///
+    /// `passed` 表示上一手是否为跳过，连续两次跳过即游戏结束
+    fn negamax(
+        &mut self,
+        board: &Board,
+        player: PlayerColor,
+        alpha: i32,
+        beta: i32,
+        passed: bool,
+    ) -> Option<i32> {
+        if self.out_of_limits() {
+            return None;
+        }
+        self.nodes += 1;
+
+        let children = ordered_children(board, player);
+        if children.is_empty() {
+            if passed {
+                // 双方都无棋可走，游戏结束
+                return Some(final_score(board, player));
+            }
+            return Some(-self.negamax(board, player.opposite(), -beta, -alpha, true)?);
+        }
+
+        let mut alpha = alpha;
+        let mut best = -i32::MAX;
+
+        for (_, new_board) in children {
+            let score = -self.negamax(&new_board, player.opposite(), -beta, -alpha, false)?;
+            best = best.max(score);
+            alpha = alpha.max(score);
+
+            // Alpha-Beta剪枝
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        Some(best)
+    }
+}
@@ -8,6 +8,8 @@
 // - 奇偶性：残局中的先手优势
 
 use crate::game::{Board, PlayerColor};
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
 
 /// 棋盘位置权重表
 ///
@@ -31,6 +33,7 @@ const POSITION_WEIGHTS: [i32; 64] = [
 ///
 /// 根据游戏阶段动态调整各项评估因子的权重
 /// 不同阶段的战略重点不同，需要相应调整评估标准
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct EvaluationWeights {
     /// 角位控制权重 - 角位的重要性
     pub corner: f32,
@@ -84,6 +87,100 @@ impl EvaluationWeights {
     }
 }
 
+/// 对局阶段，与评估权重的阶段划分相同
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamePhase {
+    /// 开局(0-20子)
+    Opening,
+    /// 中局(21-45子)
+    Midgame,
+    /// 残局(46-64子)
+    Endgame,
+}
+
+impl GamePhase {
+    /// 根据棋盘上的棋子数判断阶段
+    pub fn of(piece_count: u32) -> Self {
+        match piece_count {
+            0..=20 => Self::Opening,
+            21..=45 => Self::Midgame,
+            _ => Self::Endgame,
+        }
+    }
+
+    /// 棋盘所处的阶段
+    pub fn of_board(board: &Board) -> Self {
+        Self::of(board.count_pieces(PlayerColor::Black) + board.count_pieces(PlayerColor::White))
+    }
+}
+
+/// 完整的评估配置 - 三个阶段的权重和位置权重表
+///
+/// 默认配置与内置的权重相同，也可以从RON文件加载，
+/// 用于在无界面对战中比较不同的评估函数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalConfig {
+    /// 开局阶段(0-20步)的权重
+    pub opening: EvaluationWeights,
+    /// 中局阶段(21-45步)的权重
+    pub midgame: EvaluationWeights,
+    /// 残局阶段(46-60步)的权重
+    pub endgame: EvaluationWeights,
+    /// 位置权重表，按行排列
+    pub position_weights: [[i32; 8]; 8],
+}
+
+/// 内置的默认评估配置
+static STANDARD_EVAL_CONFIG: LazyLock<EvalConfig> = LazyLock::new(EvalConfig::default);
+
+impl Default for EvalConfig {
+    fn default() -> Self {
+        let mut position_weights = [[0; 8]; 8];
+        for (position, weight) in POSITION_WEIGHTS.iter().enumerate() {
+            position_weights[position / 8][position % 8] = *weight;
+        }
+
+        Self {
+            opening: EvaluationWeights::for_stage(0),
+            midgame: EvaluationWeights::for_stage(21),
+            endgame: EvaluationWeights::for_stage(46),
+            position_weights,
+        }
+    }
+}
+
+impl EvalConfig {
+    /// 内置的默认评估配置，避免搜索时重复构造
+    pub fn standard() -> &'static Self {
+        &STANDARD_EVAL_CONFIG
+    }
+
+    /// 根据棋盘上的棋子数返回对应阶段的权重
+    pub fn weights_for(&self, move_number: u32) -> &EvaluationWeights {
+        match GamePhase::of(move_number) {
+            GamePhase::Opening => &self.opening,
+            GamePhase::Midgame => &self.midgame,
+            GamePhase::Endgame => &self.endgame,
+        }
+    }
+
+    /// 指定位置的位置权重
+    pub fn position_weight(&self, position: u8) -> i32 {
+        self.position_weights[(position / 8) as usize][(position % 8) as usize]
+    }
+
+    /// 从RON文本解析评估配置
+    pub fn from_ron(contents: &str) -> Result<Self, String> {
+        ron::from_str(contents).map_err(|err| err.to_string())
+    }
+
+    /// 序列化为RON文本
+    pub fn to_ron(&self) -> Result<String, String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+    }
+}
+
 /// 棋盘评估主函数
 ///
 /// 综合所有评估因子，计算当前局面对指定玩家的价值
@@ -96,18 +193,23 @@ impl EvaluationWeights {
 /// # 返回
 /// 局面评估分数，范围通常在-10000到+10000之间
 pub fn evaluate_board(board: &Board, player: PlayerColor) -> i32 {
+    evaluate_board_with(board, player, EvalConfig::standard())
+}
+
+/// 使用指定评估配置评估棋盘
+pub fn evaluate_board_with(board: &Board, player: PlayerColor, config: &EvalConfig) -> i32 {
     // 计算当前步数，用于确定游戏阶段
     let move_count =
         board.count_pieces(PlayerColor::Black) + board.count_pieces(PlayerColor::White);
 
     // 获取当前阶段的权重配置
-    let weights = EvaluationWeights::for_stage(move_count);
+    let weights = config.weights_for(move_count);
 
     // 计算各项评估分数
     let corner_score = evaluate_corners(board, player) as f32;
     let stability_score = evaluate_stability(board, player) as f32;
     let mobility_score = evaluate_mobility(board, player) as f32;
-    let positional_score = evaluate_positional(board, player) as f32;
+    let positional_score = evaluate_positional_with(board, player, config) as f32;
     let parity_score = evaluate_parity(board, player) as f32;
 
     // 加权求和得到最终评估分数
@@ -118,6 +220,72 @@ pub fn evaluate_board(board: &Board, player: PlayerColor) -> i32 {
         + parity_score * weights.parity) as i32
 }
 
+/// 评估分解 - 局面中各项评估因子的原始计数，按颜色分别统计，用于界面展示
+///
+/// 与评估函数使用相同的定义，稳定子同样是简化的边位棋子
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvalBreakdown {
+    /// 双方的合法走法数（黑、白）
+    pub mobility: [u32; 2],
+    /// 双方的稳定子数（黑、白）
+    pub stable: [u32; 2],
+    /// 双方占据的角数（黑、白）
+    pub corners: [u32; 2],
+    /// 剩余空位数
+    pub empties: u32,
+}
+
+/// 按颜色取数组中的下标
+fn color_index(color: PlayerColor) -> usize {
+    match color {
+        PlayerColor::Black => 0,
+        PlayerColor::White => 1,
+    }
+}
+
+impl EvalBreakdown {
+    /// 统计局面中的各项因子
+    pub fn of(board: &Board) -> Self {
+        let per_color = |count: fn(&Board, PlayerColor) -> u32| {
+            [
+                count(board, PlayerColor::Black),
+                count(board, PlayerColor::White),
+            ]
+        };
+        Self {
+            mobility: per_color(|board, color| board.get_valid_moves(color).count_ones()),
+            stable: per_color(stable_piece_count),
+            corners: per_color(|board, color| {
+                [0, 7, 56, 63]
+                    .into_iter()
+                    .filter(|&corner| board.get_piece(corner) == Some(color))
+                    .count() as u32
+            }),
+            empties: board.get_empty_squares().count_ones(),
+        }
+    }
+
+    /// 一方的合法走法数
+    pub fn mobility_of(&self, color: PlayerColor) -> u32 {
+        self.mobility[color_index(color)]
+    }
+
+    /// 一方的稳定子数
+    pub fn stable_of(&self, color: PlayerColor) -> u32 {
+        self.stable[color_index(color)]
+    }
+
+    /// 一方占据的角数
+    pub fn corners_of(&self, color: PlayerColor) -> u32 {
+        self.corners[color_index(color)]
+    }
+
+    /// 剩余空位是否为奇数，奇数时轮到走棋的一方（没有跳过时）将走最后一步
+    pub fn odd_parity(&self) -> bool {
+        self.empties % 2 == 1
+    }
+}
+
 /// 角位控制评估
 ///
 /// 角位是黑白棋中最重要的位置，一旦占据就永远不会被翻转
@@ -160,6 +328,12 @@ pub fn evaluate_corners(board: &Board, player: PlayerColor) -> i32 {
 /// # 返回
 /// 稳定性分数，每个稳定棋子贡献50分
 pub fn evaluate_stability(board: &Board, player: PlayerColor) -> i32 {
+    // 每个稳定棋子价值50分
+    stable_piece_count(board, player) as i32 * 50
+}
+
+/// 统计一方的稳定棋子数量
+fn stable_piece_count(board: &Board, player: PlayerColor) -> u32 {
     // 获取该玩家的棋子位置位图
     let player_pieces = match player {
         PlayerColor::Black => board.black,
@@ -180,8 +354,7 @@ pub fn evaluate_stability(board: &Board, player: PlayerColor) -> i32 {
         }
     }
 
-    // 每个稳定棋子价值50分
-    stable_count * 50
+    stable_count
 }
 
 /// 判断指定位置的棋子是否稳定
@@ -238,19 +411,20 @@ pub fn evaluate_mobility(board: &Board, player: PlayerColor) -> i32 {
 /// # 参数
 /// * `board` - 当前棋盘状态
 /// * `player` - 要评估的玩家颜色
+/// * `config` - 提供位置权重表的评估配置
 ///
 /// # 返回
-/// 位置价值分数，基于POSITION_WEIGHTS表计算
-pub fn evaluate_positional(board: &Board, player: PlayerColor) -> i32 {
+/// 位置价值分数，默认配置下基于POSITION_WEIGHTS表计算
+pub fn evaluate_positional_with(board: &Board, player: PlayerColor, config: &EvalConfig) -> i32 {
     let mut score = 0;
 
     // 遍历棋盘上的每个位置
     for position in 0..64 {
         match board.get_piece(position) {
             // 己方棋子：加上该位置的权重值
-            Some(color) if color == player => score += POSITION_WEIGHTS[position as usize],
+            Some(color) if color == player => score += config.position_weight(position),
             // 对手棋子：减去该位置的权重值
-            Some(_) => score -= POSITION_WEIGHTS[position as usize],
+            Some(_) => score -= config.position_weight(position),
             // 空位：不影响分数
             None => {}
         }
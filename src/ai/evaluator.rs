@@ -0,0 +1,59 @@
+// 评估器接口 - 搜索在叶子节点调用的局面评估
+//
+// Minimax搜索、失误模型和备用走法只依赖Evaluator接口，
+// 新的评估方式（棋型评估、神经网络等）实现该接口并在EvaluatorKind中登记即可，
+// 不需要修改搜索代码。难度的搜索参数决定使用哪种评估器
+
+use super::evaluation::{evaluate_board_with, EvalConfig};
+use crate::game::{Board, PlayerColor};
+use serde::{Deserialize, Serialize};
+
+/// 局面评估器
+///
+/// 返回局面对指定玩家的价值，正值表示有利；搜索可能在多个线程中同时调用
+pub trait Evaluator: Send + Sync {
+    fn evaluate(&self, board: &Board, player: PlayerColor) -> i32;
+}
+
+/// 默认的启发式评估：按评估配置中的权重综合角、稳定子、行动力、位置和奇偶性
+impl Evaluator for EvalConfig {
+    fn evaluate(&self, board: &Board, player: PlayerColor) -> i32 {
+        evaluate_board_with(board, player, self)
+    }
+}
+
+/// 可选的评估器种类
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EvaluatorKind {
+    /// 按评估配置加权的启发式评估
+    #[default]
+    Heuristic,
+
+    /// 量化神经网络评估（需要启用nn-eval特性），网络没有加载时使用启发式评估
+    Network,
+}
+
+impl EvaluatorKind {
+    /// 根据英文名称获取评估器种类（不区分大小写），用于命令行参数
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "heuristic" => Some(Self::Heuristic),
+            "network" | "nn" => Some(Self::Network),
+            _ => None,
+        }
+    }
+
+    /// 该种类的评估器，`heuristic`为当前使用的启发式评估配置
+    pub fn evaluator<'a>(&self, heuristic: &'a EvalConfig) -> &'a dyn Evaluator {
+        match self {
+            Self::Heuristic => heuristic,
+            Self::Network => {
+                #[cfg(feature = "nn-eval")]
+                if let Some(network) = super::nn_eval::installed_network() {
+                    return network;
+                }
+                heuristic
+            }
+        }
+    }
+}
@@ -6,17 +6,113 @@
 // 算法特点：
 // - Alpha-Beta剪枝：大幅减少搜索节点数
 // - 迭代加深：逐步增加搜索深度，支持时间控制
+// - 置换表：可选，缓存各深度的结果和最佳走法，迭代加深时先搜索上一轮的最佳走法
 // - 并行搜索：桌面版支持多线程加速
 // - 跨平台：Web版使用单线程，保持兼容性
+// - 时间管理：按局面复杂度在整局预算内分配每步的搜索时间
+// - 取消：调度器取消搜索时，搜索在迭代之间、根走法之间和递归中检查取消标志并尽快返回
 
-use super::evaluation::evaluate_board;
+use super::{
+    evaluator::Evaluator,
+    transposition::{Bound, TranspositionTable},
+};
 use crate::game::{Board, Move, PlayerColor};
 // 只在非WebAssembly平台导入并行计算库
 #[cfg(not(target_arch = "wasm32"))]
 use rayon::prelude::*;
-// 时间相关功能：在支持的平台上使用，不支持的平台跳过
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+// 计时功能：在支持的平台上使用，不支持的平台跳过
 #[cfg(not(any(target_arch = "wasm32", target_family = "wasm")))]
-use std::time::{Duration, Instant};
+use std::time::Instant;
+
+/// 整局预算按每方大约走多少步计算
+pub const GAME_BUDGET_MOVES: u32 = 30;
+
+/// 只有一步可走时使用的时间比例，同时也是任何局面的最低比例
+pub const FORCED_MOVE_FACTOR: f32 = 0.1;
+
+/// 有多步可走时复杂度的下限和上限（相对于每步的基础时间）
+pub const MIN_COMPLEXITY: f32 = 0.25;
+pub const MAX_COMPLEXITY: f32 = 2.0;
+
+/// 中局一般的可走步数，复杂度按它衡量行动力
+const TYPICAL_MOBILITY: f32 = 10.0;
+
+/// 不在A列、H列的格子
+const NOT_A_FILE: u64 = 0xFEFE_FEFE_FEFE_FEFE;
+const NOT_H_FILE: u64 = 0x7F7F_7F7F_7F7F_7F7F;
+
+/// 与掩码中任一格相邻（含自身）的格子
+fn neighbourhood(mask: u64) -> u64 {
+    let row = mask | ((mask << 1) & NOT_A_FILE) | ((mask >> 1) & NOT_H_FILE);
+    row | (row << 8) | (row >> 8)
+}
+
+/// 局面复杂度，作为每步基础时间的倍数
+///
+/// 只有一步可走时返回`FORCED_MOVE_FACTOR`；否则按可走步数和不稳定棋子（与空格相邻、还可能被翻转）
+/// 的比例估计，中局一般的局面约为1，限制在`MIN_COMPLEXITY`和`MAX_COMPLEXITY`之间
+pub fn position_complexity(board: &Board, player: PlayerColor) -> f32 {
+    let mobility = board.get_valid_moves(player).count_ones();
+    if mobility <= 1 {
+        return FORCED_MOVE_FACTOR;
+    }
+
+    let discs = board.black | board.white;
+    let unstable = discs & neighbourhood(board.get_empty_squares());
+    let unstable_share = unstable.count_ones() as f32 / discs.count_ones().max(1) as f32;
+    let mobility_share = (mobility as f32 / TYPICAL_MOBILITY).min(2.0);
+
+    (0.25 + 0.5 * mobility_share + 0.5 * unstable_share).clamp(MIN_COMPLEXITY, MAX_COMPLEXITY)
+}
+
+/// 时间管理：在整局预算内为每步分配搜索时间
+///
+/// 复杂的中局多用时间，只有一步可走时几乎不用；
+/// 每步最多使用剩余预算按剩余步数平均后的`MAX_COMPLEXITY`倍，预算用完后每步仍有最低时间
+#[derive(Debug, Clone)]
+pub struct TimeManager {
+    /// 每步的基础时间，即难度设置中的时间限制
+    per_move: Duration,
+    /// 整局预算
+    game_budget: Duration,
+    /// 本局已用的时间
+    spent: Duration,
+}
+
+impl TimeManager {
+    pub fn new(per_move: Duration) -> Self {
+        Self {
+            per_move,
+            game_budget: per_move * GAME_BUDGET_MOVES,
+            spent: Duration::ZERO,
+        }
+    }
+
+    /// 本局剩余的预算
+    pub fn remaining(&self) -> Duration {
+        self.game_budget.saturating_sub(self.spent)
+    }
+
+    /// 为当前局面分配的搜索时间
+    pub fn budget(&self, board: &Board, player: PlayerColor) -> Duration {
+        let scaled = self.per_move.mul_f32(position_complexity(board, player));
+        // 己方大约还要走一半的空格
+        let moves_left = board.get_empty_squares().count_ones().div_ceil(2).max(1);
+        let share = (self.remaining() / moves_left).mul_f32(MAX_COMPLEXITY);
+        scaled
+            .min(share)
+            .max(self.per_move.mul_f32(FORCED_MOVE_FACTOR))
+    }
+
+    /// 记录一步实际使用的时间
+    pub fn record(&mut self, elapsed: Duration) {
+        self.spent += elapsed;
+    }
+}
 
 /// 搜索结果结构体
 ///
@@ -27,19 +123,15 @@ pub struct SearchResult {
     pub best_move: Option<Move>,
 
     /// 该走法的评估分数
-    #[allow(dead_code)]
     pub evaluation: i32,
 
     /// 实际达到的搜索深度
-    #[allow(dead_code)]
     pub depth_reached: u8,
 
-    /// 评估的节点总数
-    #[allow(dead_code)]
+    /// 搜索的节点总数，迭代加深时包括各轮迭代
     pub nodes_evaluated: u64,
 
     /// 搜索是否完整完成（未被时间限制中断）
-    #[allow(dead_code)]
     pub completed: bool,
 }
 
@@ -55,6 +147,7 @@ pub struct SearchResult {
 /// * `beta` - Beta值（最小化玩家的最好选择上界）
 /// * `maximizing` - 当前层是否为最大化层（AI回合）
 /// * `player` - 要优化的目标玩家
+/// * `eval` - 叶子节点使用的评估器
 ///
 /// # 返回
 /// 当前局面的评估分数
@@ -65,10 +158,56 @@ pub fn minimax(
     beta: i32,
     maximizing: bool,
     player: PlayerColor,
+    eval: &(impl Evaluator + ?Sized),
 ) -> i32 {
+    let context = SearchContext {
+        player,
+        eval,
+        table: None,
+        cancel: None,
+    };
+    search(&context, board, depth, alpha, beta, maximizing, &mut 0)
+}
+
+/// 取消标志是否已经设置
+fn is_cancelled(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+}
+
+/// 一次搜索中不变的参数
+struct SearchContext<'a, E: Evaluator + ?Sized> {
+    /// 要优化的目标玩家
+    player: PlayerColor,
+    /// 叶子节点使用的评估器
+    eval: &'a E,
+    /// 可选的置换表
+    table: Option<&'a TranspositionTable>,
+    /// 可选的取消标志，来自调度器的许可
+    cancel: Option<&'a AtomicBool>,
+}
+
+/// Minimax递归搜索，有置换表时先查表，搜索完成后写入结果
+///
+/// 每访问一个节点（包括叶子和直接从置换表返回的节点）`nodes`加一；
+/// 搜索被取消后立即返回无意义的分数，也不再写入置换表，整个结果由根节点丢弃
+fn search<E: Evaluator + ?Sized>(
+    context: &SearchContext<E>,
+    board: &Board,
+    depth: u8,
+    alpha: i32,
+    beta: i32,
+    maximizing: bool,
+    nodes: &mut u64,
+) -> i32 {
+    let player = context.player;
+    *nodes += 1;
+    if is_cancelled(context.cancel) {
+        return 0;
+    }
+
     // 递归终止条件：达到搜索深度或游戏结束
     if depth == 0 || board.is_game_over() {
-        return evaluate_board(board, player);
+        return context.eval.evaluate(board, player);
     }
 
     // 确定当前层的玩家
@@ -78,17 +217,46 @@ pub fn minimax(
         player.opposite() // 最小化层：对手玩家
     };
 
-    let moves = board.get_valid_moves_list(current_player);
+    let mut moves = board.get_valid_moves_list(current_player);
 
     // 如果当前玩家无法走棋，跳过该层继续搜索
     if moves.is_empty() {
-        return minimax(board, depth - 1, alpha, beta, !maximizing, player);
+        return search(context, board, depth - 1, alpha, beta, !maximizing, nodes);
+    }
+
+    // 查询置换表：足够深的结果直接使用或收窄窗口，最佳走法排到最前面先搜索
+    let mut alpha = alpha;
+    let mut beta = beta;
+    let table = context.table.map(|table| {
+        (
+            table,
+            TranspositionTable::key(board, current_player, player),
+        )
+    });
+    if let Some(entry) = table.and_then(|(table, key)| table.probe(key)) {
+        if entry.depth >= depth {
+            match entry.bound {
+                Bound::Exact => return entry.score,
+                Bound::Lower => alpha = alpha.max(entry.score),
+                Bound::Upper => beta = beta.min(entry.score),
+            }
+            if beta <= alpha {
+                return entry.score;
+            }
+        }
+        if let Some(index) = entry
+            .best_move
+            .and_then(|best| moves.iter().position(|m| m.position == best))
+        {
+            moves.swap(0, index);
+        }
     }
+    let (alpha_before, beta_before) = (alpha, beta);
+    let mut best_move = None;
 
-    if maximizing {
+    let best_score = if maximizing {
         // 最大化层：寻找对AI最有利的走法
         let mut max_eval = i32::MIN;
-        let mut alpha = alpha;
 
         for chess_move in moves {
             // 尝试每一个可能的走法
@@ -96,11 +264,14 @@ pub fn minimax(
             new_board.make_move(chess_move.position, current_player);
 
             // 递归搜索下一层（切换到最小化层）
-            let eval = minimax(&new_board, depth - 1, alpha, beta, false, player);
+            let score = search(context, &new_board, depth - 1, alpha, beta, false, nodes);
 
             // 更新最大值
-            max_eval = max_eval.max(eval);
-            alpha = alpha.max(eval);
+            if score > max_eval || best_move.is_none() {
+                max_eval = score;
+                best_move = Some(chess_move.position);
+            }
+            alpha = alpha.max(score);
 
             // Alpha-Beta剪枝：如果beta <= alpha，后续分支不可能更好
             if beta <= alpha {
@@ -111,7 +282,6 @@ pub fn minimax(
     } else {
         // 最小化层：寻找对AI最不利的走法（对手的最佳应对）
         let mut min_eval = i32::MAX;
-        let mut beta = beta;
 
         for chess_move in moves {
             // 尝试每一个可能的走法
@@ -119,11 +289,14 @@ pub fn minimax(
             new_board.make_move(chess_move.position, current_player);
 
             // 递归搜索下一层（切换到最大化层）
-            let eval = minimax(&new_board, depth - 1, alpha, beta, true, player);
+            let score = search(context, &new_board, depth - 1, alpha, beta, true, nodes);
 
             // 更新最小值
-            min_eval = min_eval.min(eval);
-            beta = beta.min(eval);
+            if score < min_eval || best_move.is_none() {
+                min_eval = score;
+                best_move = Some(chess_move.position);
+            }
+            beta = beta.min(score);
 
             // Alpha-Beta剪枝：如果beta <= alpha，后续分支不可能更好
             if beta <= alpha {
@@ -131,7 +304,19 @@ pub fn minimax(
             }
         }
         min_eval
+    };
+
+    if let Some((table, key)) = table.filter(|_| !is_cancelled(context.cancel)) {
+        let bound = if best_score <= alpha_before {
+            Bound::Upper
+        } else if best_score >= beta_before {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        table.store(key, depth, best_score, bound, best_move);
     }
+    best_score
 }
 
 /// 寻找最佳走法
@@ -143,10 +328,37 @@ pub fn minimax(
 /// * `board` - 当前棋盘状态
 /// * `depth` - 搜索深度
 /// * `player` - 要寻找最佳走法的玩家
+/// * `eval` - 评估器
 ///
 /// # 返回
 /// 包含最佳走法和相关信息的SearchResult
-pub fn find_best_move(board: &Board, depth: u8, player: PlayerColor) -> SearchResult {
+pub fn find_best_move(
+    board: &Board,
+    depth: u8,
+    player: PlayerColor,
+    eval: &(impl Evaluator + ?Sized),
+) -> SearchResult {
+    find_best_move_with_table(board, depth, player, eval, None, None)
+}
+
+/// 使用置换表寻找最佳走法
+///
+/// 置换表可以在多个并行搜索的根走法之间共享，同一局面只需搜索一次；
+/// `cancel`被设置后搜索尽快返回，结果没有走法且`completed`为false
+pub fn find_best_move_with_table(
+    board: &Board,
+    depth: u8,
+    player: PlayerColor,
+    eval: &(impl Evaluator + ?Sized),
+    table: Option<&TranspositionTable>,
+    cancel: Option<&AtomicBool>,
+) -> SearchResult {
+    let context = SearchContext {
+        player,
+        eval,
+        table,
+        cancel,
+    };
     let moves = board.get_valid_moves_list(player);
 
     // 如果没有可用走法，返回默认结果
@@ -154,22 +366,31 @@ pub fn find_best_move(board: &Board, depth: u8, player: PlayerColor) -> SearchRe
         return SearchResult::default();
     }
 
-    // 评估所有可能的走法
+    // 评估所有可能的走法，每个根走法单独统计节点数
     // 根据编译目标选择并行或串行处理
-    let move_evaluations: Vec<(Move, i32)> = {
+    let evaluate = |&chess_move: &Move| {
+        let mut new_board = *board;
+        new_board.make_move(chess_move.position, player);
+        let mut nodes = 0;
+        // 搜索对手的最佳应对（最小化层）
+        let evaluation = search(
+            &context,
+            &new_board,
+            depth - 1,
+            i32::MIN,
+            i32::MAX,
+            false,
+            &mut nodes,
+        );
+        (chess_move, evaluation, nodes)
+    };
+    let move_evaluations: Vec<(Move, i32, u64)> = {
         #[cfg(not(target_arch = "wasm32"))]
         {
             // 桌面版：使用Rayon并行计算，加速搜索
             moves
                 .par_iter() // 并行迭代器
-                .map(|&chess_move| {
-                    let mut new_board = *board;
-                    new_board.make_move(chess_move.position, player);
-                    // 搜索对手的最佳应对（最小化层）
-                    let evaluation =
-                        minimax(&new_board, depth - 1, i32::MIN, i32::MAX, false, player);
-                    (chess_move, evaluation)
-                })
+                .map(evaluate)
                 .collect()
         }
         #[cfg(target_arch = "wasm32")]
@@ -177,29 +398,36 @@ pub fn find_best_move(board: &Board, depth: u8, player: PlayerColor) -> SearchRe
             // Web版：使用单线程计算，保持兼容性
             moves
                 .iter() // 普通迭代器
-                .map(|&chess_move| {
-                    let mut new_board = *board;
-                    new_board.make_move(chess_move.position, player);
-                    // 搜索对手的最佳应对（最小化层）
-                    let evaluation =
-                        minimax(&new_board, depth - 1, i32::MIN, i32::MAX, false, player);
-                    (chess_move, evaluation)
-                })
+                .map(evaluate)
                 .collect()
         }
     };
 
+    // 根节点加上各根走法的子树
+    let nodes_evaluated = 1 + move_evaluations
+        .iter()
+        .map(|(_, _, nodes)| nodes)
+        .sum::<u64>();
+
+    // 被取消时各根走法的分数没有意义
+    if is_cancelled(cancel) {
+        return SearchResult {
+            nodes_evaluated,
+            ..SearchResult::default()
+        };
+    }
+
     // 选择评分最高的走法
-    let (best_move, best_eval) = move_evaluations
+    let (best_move, best_eval, _) = move_evaluations
         .into_iter()
-        .max_by_key(|(_, eval)| *eval) // 按评估分数排序
+        .max_by_key(|(_, score, _)| *score) // 按评估分数排序
         .unwrap();
 
     SearchResult {
         best_move: Some(best_move),
         evaluation: best_eval,
         depth_reached: depth,
-        nodes_evaluated: 0, // TODO: 实际实现中应该统计节点数
+        nodes_evaluated,
         completed: true,
     }
 }
@@ -214,9 +442,12 @@ pub fn find_best_move(board: &Board, depth: u8, player: PlayerColor) -> SearchRe
 /// * `time_limit` - 搜索时间限制（在不支持时间的平台上被忽略）
 /// * `max_depth` - 最大搜索深度
 /// * `player` - 要寻找最佳走法的玩家
+/// * `eval` - 评估器
+/// * `table` - 可选的置换表，各轮迭代共享
+/// * `cancel` - 可选的取消标志，设置后不再开始新的一轮，正在进行的一轮也尽快返回
 ///
 /// # 返回
-/// 在时间限制内找到的最佳搜索结果
+/// 在时间限制内找到的最佳搜索结果，深度1的结果即使超时也保留；被取消时为取消前完成的最深一轮，`completed`为false
 ///
 /// # 算法优势
 /// - 时间控制：保证在限定时间内返回结果（支持的平台）
@@ -229,24 +460,32 @@ pub fn find_best_move_with_time_limit(
     time_limit: Duration,
     max_depth: u8,
     player: PlayerColor,
+    eval: &(impl Evaluator + ?Sized),
+    table: Option<&TranspositionTable>,
+    cancel: Option<&AtomicBool>,
 ) -> SearchResult {
     let start_time = Instant::now();
     let mut best_result = SearchResult::default();
+    let mut nodes_evaluated = 0;
 
     // 迭代加深：从深度1开始逐步增加搜索深度
     for depth in 1..=max_depth {
         let elapsed = start_time.elapsed();
 
-        // 如果已经用了90%的时间，停止搜索以确保有足够时间返回结果
-        if elapsed >= time_limit.mul_f32(0.9) {
+        // 如果已经用了90%的时间，停止搜索以确保有足够时间返回结果；
+        // 深度1总是搜索，保证时间再少也有走法可用
+        if (depth > 1 && elapsed >= time_limit.mul_f32(0.9)) || is_cancelled(cancel) {
             break;
         }
 
-        // 在当前深度进行搜索
-        let result = find_best_move(board, depth, player);
+        // 在当前深度进行搜索，超时或被取消的一轮也计入节点数
+        let result = find_best_move_with_table(board, depth, player, eval, table, cancel);
+        nodes_evaluated += result.nodes_evaluated;
 
         // 检查搜索是否在时间限制内完成
-        if start_time.elapsed() < time_limit {
+        if is_cancelled(cancel) {
+            break;
+        } else if depth == 1 || start_time.elapsed() < time_limit {
             // 搜索完成，更新最佳结果
             best_result = result;
         } else {
@@ -255,6 +494,11 @@ pub fn find_best_move_with_time_limit(
         }
     }
 
+    // 没有搜到最大深度时说明被时间限制中断
+    best_result.completed = best_result.best_move.is_some()
+        && best_result.depth_reached == max_depth
+        && !is_cancelled(cancel);
+    best_result.nodes_evaluated = nodes_evaluated;
     best_result
 }
 
@@ -265,11 +509,14 @@ pub fn find_best_move_with_time_limit(
 #[cfg(any(target_arch = "wasm32", target_family = "wasm"))]
 pub fn find_best_move_with_time_limit(
     board: &Board,
-    _time_limit: core::time::Duration, // 参数保持兼容但不使用
+    _time_limit: Duration, // 参数保持兼容但不使用
     max_depth: u8,
     player: PlayerColor,
+    eval: &(impl Evaluator + ?Sized),
+    table: Option<&TranspositionTable>,
+    cancel: Option<&AtomicBool>,
 ) -> SearchResult {
     // 在不支持时间的平台上，直接使用最大深度搜索
     // 这样既保证了API兼容性，又避免了时间相关的错误
-    find_best_move(board, max_depth, player)
+    find_best_move_with_table(board, max_depth, player, eval, table, cancel)
 }
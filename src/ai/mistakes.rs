@@ -0,0 +1,87 @@
+// 失误模型 - 按温度在评估最高的前k个走法中抽样，模拟人类棋手的失误
+//
+// 每个候选走法按一步静态评估打分，搜索得到的最佳走法排在首位；
+// 对前k个走法的分数做softmax，温度越高，评估较差的走法被选中的概率越大。
+// 与从全部合法走法中均匀随机相比，低难度AI会下出"看起来合理但不够好"的棋，而不是明显的臭棋
+
+use super::evaluator::Evaluator;
+use crate::game::{Board, Move, PlayerColor};
+
+/// 候选走法按一步静态评估从高到低排列
+///
+/// `preferred`（通常是搜索得到的最佳走法）排在最前，分数不低于其余走法的最高分，
+/// 温度很低时几乎总是选中它
+pub fn rank_moves(
+    board: &Board,
+    player: PlayerColor,
+    eval: &(impl Evaluator + ?Sized),
+    preferred: Option<Move>,
+) -> Vec<(Move, i32)> {
+    let mut ranked: Vec<(Move, i32)> = board
+        .get_valid_moves_list(player)
+        .into_iter()
+        .map(|candidate| {
+            let mut after = *board;
+            after.make_move(candidate.position, player);
+            (candidate, eval.evaluate(&after, player))
+        })
+        .collect();
+    ranked.sort_by_key(|&(candidate, score)| (-score, candidate.position));
+
+    if let Some(index) = preferred.and_then(|preferred| {
+        ranked
+            .iter()
+            .position(|(candidate, _)| candidate.position == preferred.position)
+    }) {
+        let (preferred, score) = ranked.remove(index);
+        let top = ranked.first().map_or(score, |&(_, top)| top.max(score));
+        ranked.insert(0, (preferred, top));
+    }
+    ranked
+}
+
+/// 前k个候选走法各自被选中的概率，按排列顺序对应
+///
+/// 温度不大于0或k不大于1时只选第一个走法
+pub fn selection_probabilities(scores: &[i32], temperature: f32, top_k: usize) -> Vec<f32> {
+    let candidates = &scores[..top_k.max(1).min(scores.len())];
+    if temperature <= 0.0 || candidates.len() <= 1 {
+        return candidates
+            .iter()
+            .enumerate()
+            .map(|(index, _)| if index == 0 { 1.0 } else { 0.0 })
+            .collect();
+    }
+
+    // 减去最高分避免指数溢出
+    let max = candidates.iter().copied().max().unwrap_or_default();
+    let weights: Vec<f32> = candidates
+        .iter()
+        .map(|&score| ((score - max) as f32 / temperature).exp())
+        .collect();
+    let total: f32 = weights.iter().sum();
+    weights.into_iter().map(|weight| weight / total).collect()
+}
+
+/// 按选择概率抽取一个走法，`roll`为[0, 1)之间的随机数
+pub fn sample_move(
+    ranked: &[(Move, i32)],
+    temperature: f32,
+    top_k: usize,
+    roll: f32,
+) -> Option<Move> {
+    let scores: Vec<i32> = ranked.iter().map(|&(_, score)| score).collect();
+    let probabilities = selection_probabilities(&scores, temperature, top_k);
+
+    let mut cumulative = 0.0;
+    for (&(candidate, _), probability) in ranked.iter().zip(&probabilities) {
+        cumulative += probability;
+        if roll < cumulative {
+            return Some(candidate);
+        }
+    }
+    // 浮点误差导致累计概率略小于1时选最后一个候选
+    ranked
+        .get(probabilities.len().saturating_sub(1))
+        .map(|&(candidate, _)| candidate)
+}
@@ -5,16 +5,71 @@
 // - 棋盘评估函数
 // - 搜索算法优化
 
+/// 自适应难度模块
+/// 根据玩家近期战绩调整AI强度
+pub mod adaptive;
+
 /// AI难度级别定义模块
 pub mod difficulty;
 
+/// 开局库模块
+/// 常见的命名开局，用于开局练习
+pub mod opening_book;
+
+/// 残局完美求解模块
+/// 空位较少时搜索到终局，得到精确结果
+pub mod endgame;
+
 /// 棋盘评估函数模块
 /// 实现了综合的位置评估策略
 pub mod evaluation;
 
+/// 评估器接口模块
+/// 搜索使用的局面评估接口和可选的评估器种类
+pub mod evaluator;
+
+/// AI自我对弈模块
+/// 两个AI轮流走棋完成整局，用于演示模式等
+pub mod self_play;
+
+/// 搜索调度模块
+/// 所有后台搜索共用一份并发预算，按优先级取消搜索
+pub mod scheduler;
+
+/// 无界面AI对战模块
+/// 批量对局比较不同难度和评估配置
+pub mod tournament;
+
+/// 评估权重自动调优模块（需要启用tuning特性）
+#[cfg(feature = "tuning")]
+pub mod tuning;
+
+/// 外部引擎走棋模块（需要启用remote_engine特性）
+#[cfg(feature = "remote_engine")]
+pub mod remote;
+
+/// 神经网络评估模块（需要启用nn-eval特性）
+#[cfg(feature = "nn-eval")]
+pub mod nn_eval;
+
+/// 失误模型模块
+/// 按温度在前k个候选走法中抽样，模拟人类的失误
+pub mod mistakes;
+
+/// 胜率模型模块
+/// 把评估分数和剩余空位映射为胜率
+pub mod win_probability;
+
+/// 置换表模块
+/// 固定内存上限的局面缓存，深度优先加老化的替换策略
+pub mod transposition;
+
 /// Minimax搜索算法模块
 /// 包含Alpha-Beta剪枝和时间控制
 pub mod minimax;
 
 // 重新导出常用类型，方便外部模块使用
 pub use difficulty::*;
+pub use evaluator::{Evaluator, EvaluatorKind};
+pub use scheduler::{AiScheduler, SearchPermit, SearchPriority};
+pub use transposition::{SearchPreferences, TableStats, TranspositionTable};
@@ -0,0 +1,185 @@
+// 神经网络评估 - 纯Rust实现的小型量化网络，作为专家+难度的评估器（需要启用nn-eval特性）
+//
+// 网络结构：128个输入（己方64格、对方64格是否有子）→ 一层截断ReLU隐藏层 → 1个输出，
+// 权重为8位整数，推理只用整数加法和乘法，不依赖外部推理库。
+//
+// 权重文件格式（小端序）：
+//   "RVNN" | 版本(u8)=1 | 隐藏层大小H(u16) | 输出右移位数(u8)
+//   | 输入权重 i8[H×128] | 隐藏层偏置 i32[H] | 输出权重 i8[H] | 输出偏置 i32
+//
+// 权重由游戏加载后通过install_network安装；还没有安装时专家+难度使用启发式评估
+
+use super::evaluator::Evaluator;
+use crate::game::{Board, PlayerColor};
+use std::{fmt, sync::OnceLock};
+
+/// 权重文件的文件头
+pub const NETWORK_MAGIC: &[u8; 4] = b"RVNN";
+
+/// 支持的权重文件版本
+pub const NETWORK_VERSION: u8 = 1;
+
+/// 网络的输入数：己方和对方各64格
+pub const NETWORK_INPUTS: usize = 128;
+
+/// 隐藏层激活值的上限（截断ReLU）
+const ACTIVATION_MAX: i32 = 127;
+
+/// 终局时每个子差对应的分数，远大于网络输出，保证搜索优先选择确定的胜局
+const TERMINAL_DISC_SCORE: i32 = 10_000;
+
+/// 已安装的网络，第一次安装后不再替换
+static NETWORK: OnceLock<NnEvaluator> = OnceLock::new();
+
+/// 权重文件解析失败的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NnLoadError {
+    /// 文件头不是"RVNN"
+    BadMagic,
+    /// 不支持的版本
+    UnsupportedVersion(u8),
+    /// 隐藏层大小为0
+    EmptyHiddenLayer,
+    /// 文件长度与隐藏层大小不符
+    SizeMismatch { expected: usize, actual: usize },
+}
+
+impl fmt::Display for NnLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a network weights file"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported network weights version {version}")
+            }
+            Self::EmptyHiddenLayer => write!(f, "network has no hidden units"),
+            Self::SizeMismatch { expected, actual } => {
+                write!(f, "expected {expected} bytes of weights, got {actual}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NnLoadError {}
+
+/// 量化的单隐藏层评估网络
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NnEvaluator {
+    /// 输入权重，按隐藏单元排列，每个单元128个
+    pub input_weights: Vec<i8>,
+    /// 隐藏层偏置
+    pub hidden_bias: Vec<i32>,
+    /// 输出权重，每个隐藏单元一个
+    pub output_weights: Vec<i8>,
+    /// 输出偏置
+    pub output_bias: i32,
+    /// 输出右移的位数，把网络输出缩放到评估分数的范围
+    pub output_shift: u8,
+}
+
+/// 文件头的长度：文件头、版本、隐藏层大小和右移位数
+const HEADER_LEN: usize = 4 + 1 + 2 + 1;
+
+impl NnEvaluator {
+    /// 隐藏层大小
+    pub fn hidden_size(&self) -> usize {
+        self.hidden_bias.len()
+    }
+
+    /// 从权重文件内容解析网络
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, NnLoadError> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != NETWORK_MAGIC {
+            return Err(NnLoadError::BadMagic);
+        }
+        if bytes[4] != NETWORK_VERSION {
+            return Err(NnLoadError::UnsupportedVersion(bytes[4]));
+        }
+        let hidden = usize::from(u16::from_le_bytes([bytes[5], bytes[6]]));
+        if hidden == 0 {
+            return Err(NnLoadError::EmptyHiddenLayer);
+        }
+        let output_shift = bytes[7];
+
+        let expected = HEADER_LEN + hidden * NETWORK_INPUTS + hidden * 4 + hidden + 4;
+        if bytes.len() != expected {
+            return Err(NnLoadError::SizeMismatch {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let (input_weights, rest) = bytes[HEADER_LEN..].split_at(hidden * NETWORK_INPUTS);
+        let (hidden_bias, rest) = rest.split_at(hidden * 4);
+        let (output_weights, output_bias) = rest.split_at(hidden);
+        let read_i32 = |chunk: &[u8]| i32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        Ok(Self {
+            input_weights: input_weights.iter().map(|&byte| byte as i8).collect(),
+            hidden_bias: hidden_bias.chunks_exact(4).map(read_i32).collect(),
+            output_weights: output_weights.iter().map(|&byte| byte as i8).collect(),
+            output_bias: read_i32(output_bias),
+            output_shift,
+        })
+    }
+
+    /// 序列化为权重文件内容
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.input_weights.len() * 2);
+        bytes.extend_from_slice(NETWORK_MAGIC);
+        bytes.push(NETWORK_VERSION);
+        bytes.extend_from_slice(&(self.hidden_size() as u16).to_le_bytes());
+        bytes.push(self.output_shift);
+        bytes.extend(self.input_weights.iter().map(|&weight| weight as u8));
+        for bias in &self.hidden_bias {
+            bytes.extend_from_slice(&bias.to_le_bytes());
+        }
+        bytes.extend(self.output_weights.iter().map(|&weight| weight as u8));
+        bytes.extend_from_slice(&self.output_bias.to_le_bytes());
+        bytes
+    }
+
+    /// 网络对局面的原始输出，从`player`的角度
+    fn forward(&self, board: &Board, player: PlayerColor) -> i32 {
+        let (own, opponent) = match player {
+            PlayerColor::Black => (board.black, board.white),
+            PlayerColor::White => (board.white, board.black),
+        };
+
+        let mut output = self.output_bias;
+        for (unit, weights) in self.input_weights.chunks_exact(NETWORK_INPUTS).enumerate() {
+            let mut activation = self.hidden_bias[unit];
+            for (offset, mut squares) in [(0, own), (64, opponent)] {
+                while squares != 0 {
+                    let square = squares.trailing_zeros() as usize;
+                    activation += i32::from(weights[offset + square]);
+                    squares &= squares - 1;
+                }
+            }
+            output += activation.clamp(0, ACTIVATION_MAX) * i32::from(self.output_weights[unit]);
+        }
+        output >> self.output_shift
+    }
+}
+
+impl Evaluator for NnEvaluator {
+    fn evaluate(&self, board: &Board, player: PlayerColor) -> i32 {
+        // 终局直接按子差计分，不需要网络判断
+        if board.is_game_over() {
+            let margin =
+                board.count_pieces(player) as i32 - board.count_pieces(player.opposite()) as i32;
+            return margin * TERMINAL_DISC_SCORE;
+        }
+        self.forward(board, player)
+    }
+}
+
+/// 安装网络，之后专家+难度使用该网络评估
+///
+/// # 返回
+/// 是否安装成功；已经安装过网络时保留原来的网络
+pub fn install_network(network: NnEvaluator) -> bool {
+    NETWORK.set(network).is_ok()
+}
+
+/// 已安装的网络
+pub fn installed_network() -> Option<&'static NnEvaluator> {
+    NETWORK.get()
+}
@@ -0,0 +1,102 @@
+// 开局库 - 常见的命名开局，从标准初始局面开始，黑棋先行
+//
+// 走法用棋谱记法书写，例如"F5"。开局练习模式按开局库自动走完开局，再把对局交给玩家；
+// AI在对局开头直接从开局库中挑选走法（包括对称等价的走法），不必搜索
+
+use crate::game::{Board, GameLog, MoveAction, PlayerColor, Symmetry};
+
+/// 一个命名开局
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Opening {
+    /// 开局的通用名称
+    pub name: &'static str,
+    /// 双方依次的走法
+    pub moves: &'static [&'static str],
+}
+
+impl Opening {
+    /// 开局的步数
+    pub fn plies(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// 开局走法对应的位置，记法无效时返回None
+    pub fn positions(&self) -> Option<Vec<u8>> {
+        self.moves
+            .iter()
+            .map(|notation| Board::notation_to_position(notation))
+            .collect()
+    }
+
+    /// 从标准初始局面走完前plies步后的命令日志，走法不合法时返回None
+    pub fn play(&self, plies: usize) -> Option<GameLog> {
+        let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+        for position in self.positions()?.into_iter().take(plies) {
+            log.apply(log.next_command(MoveAction::Place(position)))
+                .ok()?;
+        }
+        Some(log)
+    }
+}
+
+/// 开局库中的全部开局
+pub const OPENING_BOOK: &[Opening] = &[
+    Opening {
+        name: "Perpendicular",
+        moves: &["F5", "D6"],
+    },
+    Opening {
+        name: "Diagonal",
+        moves: &["F5", "F6"],
+    },
+    Opening {
+        name: "Parallel",
+        moves: &["F5", "F4"],
+    },
+    Opening {
+        name: "Tiger",
+        moves: &["F5", "D6", "C3", "D3", "C4"],
+    },
+    Opening {
+        name: "Cow",
+        moves: &["F5", "D6", "C5", "F4", "E3"],
+    },
+    Opening {
+        name: "Buffalo",
+        moves: &["F5", "F6", "E6", "F4", "C3"],
+    },
+    Opening {
+        name: "Heath",
+        moves: &["F5", "F6", "E6", "F4", "G5"],
+    },
+];
+
+/// 当前局面在开局库中的后续走法，包括对称等价局面中的走法
+///
+/// 局面与某个开局的前若干步（经过任意对称变换后）相同且轮到同一方时，
+/// 把该开局的下一步按同一变换映射回当前局面。结果去重并从小到大排列
+pub fn book_moves(board: &Board, player: PlayerColor) -> Vec<u8> {
+    let mut moves = Vec::new();
+    for opening in OPENING_BOOK {
+        let Some(positions) = opening.positions() else {
+            continue;
+        };
+        for (plies, &next) in positions.iter().enumerate() {
+            let Some(log) = opening.play(plies) else {
+                break;
+            };
+            if log.to_move() != player {
+                continue;
+            }
+            let book_board = log.board();
+            for symmetry in Symmetry::ALL {
+                if book_board.transformed(symmetry) == *board {
+                    moves.push(symmetry.apply_position(next));
+                }
+            }
+        }
+    }
+    moves.sort_unstable();
+    moves.dedup();
+    moves
+}
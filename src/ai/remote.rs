@@ -0,0 +1,263 @@
+// 外部引擎 - 把AI走棋交给外部引擎进程或HTTP服务（需要启用remote_engine特性）
+//
+// 协议只有一行：发送局面字符串（见game::position，Edax等引擎常用的格式），收到一步棋。
+// 回复为棋谱记法（例如"F5"），无棋可走时回复"PS"。
+// 进程模式每步启动一次引擎，把局面写入标准输入，从标准输出读取第一行；
+// HTTP模式向地址POST局面字符串，响应体即为走法，只支持http。
+// 引擎出错、超时或给出不合法的走法时，改用内置搜索，对局不会中断
+
+use super::{evaluation::EvalConfig, AiDifficulty};
+pub use crate::game::position_string;
+use crate::game::{Board, Move, PlayerColor};
+use bevy::prelude::*;
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+/// 启动进程模式的命令行参数
+pub const REMOTE_ENGINE_FLAG: &str = "--remote-engine";
+
+/// 启动HTTP模式的命令行参数
+pub const REMOTE_ENGINE_URL_FLAG: &str = "--remote-engine-url";
+
+/// 等待外部引擎回复的时限
+pub const REMOTE_ENGINE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 外部引擎的连接方式
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub enum RemoteEngine {
+    /// 每步启动一次的引擎进程
+    Process { program: String, args: Vec<String> },
+    /// 接收POST请求的HTTP地址
+    Http { url: String },
+}
+
+/// 外部引擎请求失败的原因
+#[derive(Debug)]
+pub enum RemoteEngineError {
+    /// 启动进程、连接或读写失败
+    Io(io::Error),
+    /// 超过时限没有回复
+    TimedOut,
+    /// HTTP地址无效或响应状态不是200
+    Http(String),
+    /// 回复不是有效的走法
+    InvalidReply(String),
+}
+
+impl fmt::Display for RemoteEngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "remote engine I/O error: {err}"),
+            Self::TimedOut => write!(
+                f,
+                "remote engine did not reply within {REMOTE_ENGINE_TIMEOUT:?}"
+            ),
+            Self::Http(message) => write!(f, "remote engine HTTP error: {message}"),
+            Self::InvalidReply(reply) => write!(f, "remote engine replied with {reply:?}"),
+        }
+    }
+}
+
+impl std::error::Error for RemoteEngineError {}
+
+impl From<io::Error> for RemoteEngineError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// 解析引擎的回复，"PS"或"pass"表示跳过回合，返回None
+pub fn parse_engine_move(reply: &str) -> Result<Option<u8>, RemoteEngineError> {
+    let reply = reply.trim();
+    if reply.eq_ignore_ascii_case("ps") || reply.eq_ignore_ascii_case("pass") {
+        return Ok(None);
+    }
+    Board::notation_to_position(reply)
+        .map(Some)
+        .ok_or_else(|| RemoteEngineError::InvalidReply(reply.to_string()))
+}
+
+impl RemoteEngine {
+    /// 从命令行参数读取外部引擎，没有相关参数时返回Ok(None)
+    ///
+    /// `--remote-engine "edax-wrapper --level 10"`按空白拆分为程序和参数，
+    /// `--remote-engine-url http://localhost:8080/move`使用HTTP服务
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+            match arg.as_str() {
+                REMOTE_ENGINE_FLAG => {
+                    let command = value()?;
+                    let mut words = command.split_whitespace().map(str::to_string);
+                    let program = words
+                        .next()
+                        .ok_or_else(|| format!("{REMOTE_ENGINE_FLAG} expects a command"))?;
+                    return Ok(Some(Self::Process {
+                        program,
+                        args: words.collect(),
+                    }));
+                }
+                REMOTE_ENGINE_URL_FLAG => {
+                    let url = value()?;
+                    split_http_url(&url).map_err(|err| err.to_string())?;
+                    return Ok(Some(Self::Http { url }));
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    /// 请求外部引擎给出一步棋，跳过回合时返回None
+    ///
+    /// 只检查回复的格式，走法是否合法由调用方检查
+    pub fn request_move(
+        &self,
+        board: &Board,
+        to_move: PlayerColor,
+    ) -> Result<Option<u8>, RemoteEngineError> {
+        let position = position_string(board, to_move);
+        let reply = match self {
+            Self::Process { program, args } => request_from_process(program, args, &position)?,
+            Self::Http { url } => request_from_http(url, &position)?,
+        };
+        parse_engine_move(&reply)
+    }
+
+    /// 由外部引擎选择走法，引擎失败或给出不合法的走法时改用内置搜索
+    pub fn choose_move(
+        &self,
+        difficulty: AiDifficulty,
+        board: &Board,
+        player: PlayerColor,
+        eval: &EvalConfig,
+    ) -> Option<Move> {
+        let valid_moves = board.get_valid_moves_list(player);
+        match self.request_move(board, player) {
+            Ok(Some(position)) => {
+                if let Some(valid) = valid_moves.iter().find(|m| m.position == position) {
+                    return Some(*valid);
+                }
+                warn!(
+                    "Remote engine suggested illegal move {}, using built-in search",
+                    Board::position_to_notation(position)
+                );
+            }
+            Ok(None) if valid_moves.is_empty() => return None,
+            Ok(None) => warn!("Remote engine passed with legal moves left, using built-in search"),
+            Err(err) => warn!("{err}, using built-in search"),
+        }
+        difficulty.get_ai_move_with(board, player, eval)
+    }
+}
+
+/// 启动引擎进程，写入局面并读取第一行回复，超时后结束进程
+fn request_from_process(
+    program: &str,
+    args: &[String],
+    position: &str,
+) -> Result<String, RemoteEngineError> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // 写完局面后关闭标准输入，只读一行的引擎也能正常结束
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "{position}")?;
+    }
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| RemoteEngineError::Io(io::Error::other("engine stdout is not available")))?;
+
+    // 读取放在单独的线程中，主线程按时限等待
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut line = String::new();
+        let result = BufReader::new(stdout).read_line(&mut line).map(|_| line);
+        let _ = sender.send(result);
+    });
+    let reply = receiver.recv_timeout(REMOTE_ENGINE_TIMEOUT);
+
+    let _ = child.kill();
+    let _ = child.wait();
+    match reply {
+        Ok(line) => Ok(line?),
+        Err(_) => Err(RemoteEngineError::TimedOut),
+    }
+}
+
+/// 把http地址拆分为主机（含端口）和路径
+fn split_http_url(url: &str) -> Result<(String, String), RemoteEngineError> {
+    let rest = url.strip_prefix("http://").ok_or_else(|| {
+        RemoteEngineError::Http(format!("only http:// URLs are supported: {url}"))
+    })?;
+    let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    if host.is_empty() {
+        return Err(RemoteEngineError::Http(format!("missing host in {url}")));
+    }
+    let host = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{host}:80")
+    };
+    let path = if path.is_empty() { "/" } else { path };
+    Ok((host, path.to_string()))
+}
+
+/// 向HTTP服务POST局面字符串，返回响应体
+fn request_from_http(url: &str, position: &str) -> Result<String, RemoteEngineError> {
+    let (host, path) = split_http_url(url)?;
+    let address = host
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| RemoteEngineError::Http(format!("cannot resolve {host}")))?;
+
+    let mut stream = TcpStream::connect_timeout(&address, REMOTE_ENGINE_TIMEOUT)?;
+    stream.set_read_timeout(Some(REMOTE_ENGINE_TIMEOUT))?;
+    stream.set_write_timeout(Some(REMOTE_ENGINE_TIMEOUT))?;
+    write!(
+        stream,
+        "POST {path} HTTP/1.1\r\nHost: {host}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{position}",
+        position.len()
+    )?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(|err| {
+        if matches!(
+            err.kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+        ) {
+            RemoteEngineError::TimedOut
+        } else {
+            RemoteEngineError::Io(err)
+        }
+    })?;
+    http_response_body(&response).map(str::to_string)
+}
+
+/// 检查HTTP响应的状态并取出响应体
+pub fn http_response_body(response: &str) -> Result<&str, RemoteEngineError> {
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| RemoteEngineError::Http("malformed response".to_string()))?;
+    let status = head.lines().next().unwrap_or_default();
+    if status.split_whitespace().nth(1) != Some("200") {
+        return Err(RemoteEngineError::Http(status.to_string()));
+    }
+    Ok(body)
+}
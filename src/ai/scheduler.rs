@@ -0,0 +1,154 @@
+// 搜索调度 - 对局AI和演示对弈等所有后台搜索共用一份并发预算
+//
+// 每个搜索开始前都要向调度器申请许可，许可在搜索结束或持有者被销毁时自动归还。
+// 高优先级的搜索开始时取消所有低优先级的搜索，低优先级的搜索在有高优先级搜索进行时
+// 不会开始，避免多个搜索同时占满所有核心导致设备发热降频。
+// 取消只设置许可中的标志，搜索在检查到标志后尽快返回；许可交给执行搜索的任务持有，
+// 被取消的搜索真正结束之前仍然占用预算
+
+use bevy::prelude::*;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, MutexGuard,
+};
+
+/// 默认同时进行的搜索数
+pub const DEFAULT_SEARCH_BUDGET: usize = 2;
+
+/// 搜索的优先级，越靠后越优先
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SearchPriority {
+    /// 演示对弈等不影响对局的后台搜索
+    Background,
+    /// 对局中AI走棋的搜索
+    Move,
+}
+
+/// 正在进行的一个搜索
+#[derive(Debug)]
+struct ActiveSearch {
+    id: u64,
+    priority: SearchPriority,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    active: Vec<ActiveSearch>,
+    next_id: u64,
+}
+
+/// 搜索调度器，限制同时进行的搜索数并按优先级取消搜索
+#[derive(Resource, Debug, Clone)]
+pub struct AiScheduler {
+    budget: usize,
+    state: Arc<Mutex<SchedulerState>>,
+}
+
+impl Default for AiScheduler {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEARCH_BUDGET)
+    }
+}
+
+impl AiScheduler {
+    /// 创建最多同时进行`budget`个搜索的调度器（至少为1）
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget: budget.max(1),
+            state: Arc::default(),
+        }
+    }
+
+    /// 同时进行的搜索数上限
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// 正在进行的搜索数，包括已被取消、还没有结束的搜索
+    pub fn active(&self) -> usize {
+        lock(&self.state).active.len()
+    }
+
+    /// 申请开始一个搜索
+    ///
+    /// 有更高优先级的搜索正在进行时返回None；否则取消所有优先级更低的搜索，
+    /// 预算（包括被取消、还没有结束的搜索）用完时同样返回None，调用方稍后再试
+    pub fn request(&self, priority: SearchPriority) -> Option<SearchPermit> {
+        let mut state = lock(&self.state);
+        let live = || {
+            state
+                .active
+                .iter()
+                .filter(|search| !search.cancelled.load(Ordering::Relaxed))
+        };
+        if live().any(|search| search.priority > priority) {
+            return None;
+        }
+
+        for search in live().filter(|search| search.priority < priority) {
+            search.cancelled.store(true, Ordering::Relaxed);
+        }
+        if state.active.len() >= self.budget {
+            return None;
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        state.active.push(ActiveSearch {
+            id,
+            priority,
+            cancelled: cancelled.clone(),
+        });
+        Some(SearchPermit {
+            id,
+            priority,
+            cancelled,
+            state: self.state.clone(),
+        })
+    }
+}
+
+/// 进行一个搜索的许可，销毁时归还预算
+///
+/// 许可应当移动到执行搜索的任务中，搜索返回时随任务一起销毁
+#[derive(Debug)]
+pub struct SearchPermit {
+    id: u64,
+    priority: SearchPriority,
+    cancelled: Arc<AtomicBool>,
+    state: Arc<Mutex<SchedulerState>>,
+}
+
+impl SearchPermit {
+    /// 搜索的优先级
+    pub fn priority(&self) -> SearchPriority {
+        self.priority
+    }
+
+    /// 是否已被更高优先级的搜索取消，被取消的搜索应尽快丢弃
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// 取消标志，传给搜索函数在搜索中检查；许可移动到任务中后，持有者用它判断是否已被取消
+    pub fn cancel_flag(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
+}
+
+impl Drop for SearchPermit {
+    fn drop(&mut self) {
+        lock(&self.state)
+            .active
+            .retain(|search| search.id != self.id);
+    }
+}
+
+/// 持有锁的线程panic后仍继续使用调度状态，其中只有计数信息
+fn lock(state: &Mutex<SchedulerState>) -> MutexGuard<'_, SchedulerState> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,95 @@
+// AI自我对弈 - 由两个AI玩家轮流走棋完成一整局
+//
+// 走法通过命令日志应用，思考在后台任务池中进行，不阻塞主线程；
+// 在游戏中作为后台搜索向调度器申请许可，对局AI需要走棋时让出计算资源
+
+use super::{evaluation::EvalConfig, AiDifficulty, AiPlayer, AiScheduler, SearchPriority};
+use crate::game::{Board, GameLog, MoveAction, PlayerColor};
+use std::sync::Arc;
+
+/// 一局AI自我对弈
+pub struct SelfPlayGame {
+    log: GameLog,
+    black: AiPlayer,
+    white: AiPlayer,
+}
+
+impl SelfPlayGame {
+    /// 从初始局面创建自我对弈，黑棋先行
+    pub fn new(board: Board, black: AiDifficulty, white: AiDifficulty) -> Self {
+        Self {
+            log: GameLog::new(board, PlayerColor::Black),
+            black: AiPlayer::new(black, PlayerColor::Black),
+            white: AiPlayer::new(white, PlayerColor::White),
+        }
+    }
+
+    /// 对局的命令日志
+    pub fn log(&self) -> &GameLog {
+        &self.log
+    }
+
+    /// 对局是否已经结束
+    pub fn is_finished(&self) -> bool {
+        self.log.board().is_game_over()
+    }
+
+    /// 推进对局：轮到的AI还没开始思考时开始思考，思考完成时应用走法
+    ///
+    /// # 返回
+    /// 本次调用是否应用了新的走法
+    pub fn poll(&mut self) -> bool {
+        self.advance(None)
+    }
+
+    /// 作为后台搜索推进对局，调度器没有给出许可时暂不思考
+    pub fn poll_scheduled(&mut self, scheduler: &AiScheduler) -> bool {
+        self.advance(Some(scheduler))
+    }
+
+    fn advance(&mut self, scheduler: Option<&AiScheduler>) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        let board = self.log.board();
+        let ai_player = match self.log.to_move() {
+            PlayerColor::Black => &mut self.black,
+            PlayerColor::White => &mut self.white,
+        };
+
+        if !ai_player.is_thinking {
+            match scheduler {
+                Some(scheduler) => {
+                    let eval = Arc::new(EvalConfig::standard().clone());
+                    ai_player.start_scheduled_thinking(
+                        &board,
+                        eval,
+                        scheduler,
+                        SearchPriority::Background,
+                    );
+                }
+                None => ai_player.start_thinking(&board),
+            }
+            return false;
+        }
+
+        let Some(result) = ai_player.check_thinking_result() else {
+            return false;
+        };
+
+        let action = result.map_or(MoveAction::Pass, |ai_move| {
+            MoveAction::Place(ai_move.position)
+        });
+        if self.log.apply(self.log.next_command(action)).is_err() {
+            return false;
+        }
+
+        // 对手无棋可走但对局未结束时，记录跳过回合
+        let board = self.log.board();
+        if !board.is_game_over() && !board.has_valid_moves(self.log.to_move()) {
+            let _ = self.log.apply(self.log.next_command(MoveAction::Pass));
+        }
+        true
+    }
+}
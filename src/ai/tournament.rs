@@ -0,0 +1,293 @@
+// 无界面AI对战 - 两组难度/评估配置之间批量对局，统计胜率和平均子差
+//
+// 用法：cargo run -- --headless-tournament [--games N] [--openings N]
+//                    [--first 难度[:评估配置.ron]] [--second 难度[:评估配置.ron]]
+//
+// 每个随机开局都会交换颜色各下一局，抵消先后手和开局带来的偏差
+
+use super::{evaluation::EvalConfig, AiDifficulty};
+use crate::game::{Board, PlayerColor};
+use rand::seq::SliceRandom;
+use std::fmt;
+
+/// 启用无界面对战的命令行参数
+pub const TOURNAMENT_FLAG: &str = "--headless-tournament";
+
+/// 对战中的一方：难度和评估配置
+#[derive(Debug, Clone)]
+pub struct TournamentPlayer {
+    /// 命令行中给出的名称，用于输出
+    pub label: String,
+    pub difficulty: AiDifficulty,
+    pub eval: EvalConfig,
+}
+
+impl TournamentPlayer {
+    /// 使用默认评估配置创建对战方
+    pub fn new(difficulty: AiDifficulty) -> Self {
+        Self {
+            label: format!("{difficulty:?}"),
+            difficulty,
+            eval: EvalConfig::default(),
+        }
+    }
+
+    /// 解析"难度[:评估配置文件]"形式的参数
+    ///
+    /// 难度名（chaos:种子）和文件路径（Windows盘符）都可能带冒号，
+    /// 因此取能解析为难度的最长前缀，冒号之后的其余部分作为评估配置文件
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (difficulty, eval_path) = std::iter::once((spec.len(), None))
+            .chain(
+                spec.rmatch_indices(':')
+                    .map(|(index, _)| (index, Some(&spec[index + 1..]))),
+            )
+            .find_map(|(end, path)| {
+                AiDifficulty::from_name(&spec[..end]).map(|difficulty| (difficulty, path))
+            })
+            .ok_or_else(|| format!("unknown difficulty: {spec}"))?;
+        let eval = match eval_path {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path)
+                    .map_err(|err| format!("failed to read {path}: {err}"))?;
+                EvalConfig::from_ron(&contents).map_err(|err| format!("{path}: {err}"))?
+            }
+            None => EvalConfig::default(),
+        };
+
+        Ok(Self {
+            label: spec.to_string(),
+            difficulty,
+            eval,
+        })
+    }
+
+    fn choose_move(&self, board: &Board, player: PlayerColor) -> Option<u8> {
+        self.difficulty
+            .get_ai_move_with(board, player, &self.eval)
+            .map(|chosen| chosen.position)
+    }
+}
+
+/// 对战配置
+#[derive(Debug, Clone)]
+pub struct TournamentConfig {
+    /// 对局总数，按开局成对进行，奇数时最后一个开局只下一局
+    pub games: u32,
+    /// 每局开始时随机走的步数
+    pub opening_plies: u32,
+    pub first: TournamentPlayer,
+    pub second: TournamentPlayer,
+}
+
+impl Default for TournamentConfig {
+    fn default() -> Self {
+        Self {
+            games: 20,
+            opening_plies: 4,
+            first: TournamentPlayer::new(AiDifficulty::Intermediate),
+            second: TournamentPlayer::new(AiDifficulty::Intermediate),
+        }
+    }
+}
+
+impl TournamentConfig {
+    /// 从命令行参数解析对战配置，没有对战参数时返回None
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let args: Vec<String> = args.into_iter().collect();
+        if !args.iter().any(|arg| arg == TOURNAMENT_FLAG) {
+            return Ok(None);
+        }
+
+        let mut config = Self::default();
+        let mut iter = args.iter().filter(|arg| *arg != TOURNAMENT_FLAG);
+        while let Some(arg) = iter.next() {
+            let mut value = || {
+                iter.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+            match arg.as_str() {
+                "--games" => {
+                    config.games = value()?
+                        .parse()
+                        .map_err(|_| "--games expects a number".to_string())?;
+                }
+                "--openings" => {
+                    config.opening_plies = value()?
+                        .parse()
+                        .map_err(|_| "--openings expects a number".to_string())?;
+                }
+                "--first" => config.first = TournamentPlayer::parse(value()?)?,
+                "--second" => config.second = TournamentPlayer::parse(value()?)?,
+                _ => return Err(format!("unknown argument: {arg}")),
+            }
+        }
+        Ok(Some(config))
+    }
+}
+
+/// 一局对战的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TournamentGame {
+    /// 第一方是否执黑
+    pub first_is_black: bool,
+    /// 第一方的棋子数减去第二方的棋子数
+    pub disc_diff: i32,
+}
+
+/// 对战统计结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TournamentResult {
+    pub games: Vec<TournamentGame>,
+}
+
+impl TournamentResult {
+    pub fn first_wins(&self) -> usize {
+        self.games.iter().filter(|game| game.disc_diff > 0).count()
+    }
+
+    pub fn second_wins(&self) -> usize {
+        self.games.iter().filter(|game| game.disc_diff < 0).count()
+    }
+
+    pub fn draws(&self) -> usize {
+        self.games.iter().filter(|game| game.disc_diff == 0).count()
+    }
+
+    /// 第一方的得分率，平局计半局
+    pub fn first_score_rate(&self) -> f32 {
+        if self.games.is_empty() {
+            return 0.0;
+        }
+        (self.first_wins() as f32 + self.draws() as f32 * 0.5) / self.games.len() as f32
+    }
+
+    /// 第一方的平均子差
+    pub fn average_disc_diff(&self) -> f32 {
+        if self.games.is_empty() {
+            return 0.0;
+        }
+        self.games.iter().map(|game| game.disc_diff).sum::<i32>() as f32 / self.games.len() as f32
+    }
+}
+
+impl fmt::Display for TournamentResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.games.len().max(1) as f32;
+        writeln!(f, "games:        {}", self.games.len())?;
+        writeln!(
+            f,
+            "first wins:   {} ({:.1}%)",
+            self.first_wins(),
+            self.first_wins() as f32 / total * 100.0
+        )?;
+        writeln!(
+            f,
+            "second wins:  {} ({:.1}%)",
+            self.second_wins(),
+            self.second_wins() as f32 / total * 100.0
+        )?;
+        writeln!(f, "draws:        {}", self.draws())?;
+        writeln!(f, "score rate:   {:.1}%", self.first_score_rate() * 100.0)?;
+        write!(f, "avg disc diff: {:+.2}", self.average_disc_diff())
+    }
+}
+
+/// 生成随机开局：从标准初始局面随机走若干步
+///
+/// # 返回
+/// 开局后的棋盘和轮到走棋的一方
+pub fn random_opening(plies: u32) -> (Board, PlayerColor) {
+    let mut rng = rand::thread_rng();
+    let mut board = Board::new_standard();
+    let mut to_move = PlayerColor::Black;
+
+    for _ in 0..plies {
+        let moves = board.get_valid_moves_list(to_move);
+        let Some(chosen) = moves.choose(&mut rng) else {
+            break;
+        };
+        board.make_move(chosen.position, to_move);
+        to_move = to_move.opposite();
+    }
+    (board, to_move)
+}
+
+/// 从给定局面下完一局，返回第一方的子差
+pub fn play_game(
+    first: &TournamentPlayer,
+    second: &TournamentPlayer,
+    first_color: PlayerColor,
+    mut board: Board,
+    mut to_move: PlayerColor,
+) -> i32 {
+    while !board.is_game_over() {
+        let player = if to_move == first_color {
+            first
+        } else {
+            second
+        };
+
+        if let Some(position) = player.choose_move(&board, to_move) {
+            board.make_move(position, to_move);
+        }
+        to_move = to_move.opposite();
+    }
+
+    board.count_pieces(first_color) as i32 - board.count_pieces(first_color.opposite()) as i32
+}
+
+/// 运行整个对战，每下完一局调用一次回调
+pub fn run_tournament(
+    config: &TournamentConfig,
+    mut on_game: impl FnMut(&TournamentGame),
+) -> TournamentResult {
+    let mut result = TournamentResult::default();
+    let mut opening = random_opening(config.opening_plies);
+
+    for index in 0..config.games {
+        // 每个开局交换颜色各下一局
+        let first_is_black = index % 2 == 0;
+        if first_is_black && index > 0 {
+            opening = random_opening(config.opening_plies);
+        }
+
+        let first_color = if first_is_black {
+            PlayerColor::Black
+        } else {
+            PlayerColor::White
+        };
+        let disc_diff = play_game(
+            &config.first,
+            &config.second,
+            first_color,
+            opening.0,
+            opening.1,
+        );
+
+        let game = TournamentGame {
+            first_is_black,
+            disc_diff,
+        };
+        on_game(&game);
+        result.games.push(game);
+    }
+    result
+}
+
+/// 命令行入口：运行对战并打印每局结果和汇总
+pub fn run_from_cli(config: &TournamentConfig) {
+    println!(
+        "{} vs {}: {} games, {} random opening plies",
+        config.first.label, config.second.label, config.games, config.opening_plies
+    );
+
+    let mut played = 0;
+    let result = run_tournament(config, |game| {
+        played += 1;
+        let color = if game.first_is_black { "B" } else { "W" };
+        println!("game {played:>4} (first {color}): {:+}", game.disc_diff);
+    });
+
+    println!("{result}");
+}
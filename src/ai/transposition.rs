@@ -0,0 +1,307 @@
+// 置换表 - 缓存搜索过的局面，内存占用固定，可在手机和Web上使用
+//
+// 表由2的幂个桶组成，每个桶4个条目、正好64字节。大小按设置中的MB数向下取到2的幂，
+// 创建后不再增长。每个条目是两个原子整数（键异或数据、数据），多线程并行搜索时无锁读写，
+// 读到被其他线程写了一半的条目时键校验失败，视为未命中。
+//
+// 替换策略：同一局面的条目在新结果搜索得更深、是精确值或来自以前的搜索时覆盖；
+// 否则替换桶中最不值得保留的条目——空条目优先，其次是越旧、越浅的条目（深度优先加老化）。
+// 每次根搜索开始时调用new_search推进代数，以前搜索留下的条目逐渐被替换
+
+use crate::{
+    game::{position_hash, Board, PlayerColor},
+    platform::Platform,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+/// 每个桶的条目数
+pub const BUCKET_ENTRIES: usize = 4;
+
+/// 每个条目占用的字节数
+const ENTRY_BYTES: usize = 16;
+
+/// 每个桶占用的字节数
+const BUCKET_BYTES: usize = BUCKET_ENTRIES * ENTRY_BYTES;
+
+/// 设置中可选的置换表大小（MB）
+pub const TABLE_SIZES_MB: [u32; 6] = [1, 4, 8, 16, 32, 64];
+
+/// 替换时每落后一代相当于少搜索的层数
+const AGE_PENALTY: i32 = 4;
+
+/// 以白棋为搜索视角时混入键中的常数，两种视角的分数互不混用
+const WHITE_PERSPECTIVE: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// 条目中的分数类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    /// 精确值
+    Exact,
+    /// 下界：搜索发生了beta剪枝，真实分数不低于该值
+    Lower,
+    /// 上界：所有走法都没有超过alpha，真实分数不高于该值
+    Upper,
+}
+
+impl Bound {
+    fn bits(self) -> u64 {
+        match self {
+            Self::Exact => 1,
+            Self::Lower => 2,
+            Self::Upper => 3,
+        }
+    }
+
+    fn from_bits(bits: u64) -> Option<Self> {
+        match bits {
+            1 => Some(Self::Exact),
+            2 => Some(Self::Lower),
+            3 => Some(Self::Upper),
+            _ => None,
+        }
+    }
+}
+
+/// 查到的条目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableEntry {
+    /// 搜索视角的分数
+    pub score: i32,
+    /// 得到该分数时的剩余搜索深度
+    pub depth: u8,
+    /// 分数类型
+    pub bound: Bound,
+    /// 该局面的最佳走法，用于走法排序
+    pub best_move: Option<u8>,
+    /// 写入时的代数
+    age: u8,
+}
+
+impl TableEntry {
+    /// 数据布局：分数32位、深度8位、分数类型2位、最佳走法7位（位置加1，0表示没有）、代数8位
+    fn pack(&self) -> u64 {
+        u64::from(self.score as u32)
+            | u64::from(self.depth) << 32
+            | self.bound.bits() << 40
+            | u64::from(self.best_move.map_or(0, |position| position + 1)) << 42
+            | u64::from(self.age) << 49
+    }
+
+    fn unpack(data: u64) -> Option<Self> {
+        let best_move = ((data >> 42) & 0x7F) as u8;
+        Some(Self {
+            score: data as u32 as i32,
+            depth: (data >> 32) as u8,
+            bound: Bound::from_bits((data >> 40) & 0b11)?,
+            best_move: best_move.checked_sub(1),
+            age: (data >> 49) as u8,
+        })
+    }
+}
+
+/// 一个条目：键异或数据和数据，两者一致时条目有效
+#[derive(Default)]
+struct Slot {
+    check: AtomicU64,
+    data: AtomicU64,
+}
+
+impl Slot {
+    fn load(&self) -> (u64, u64) {
+        let data = self.data.load(Ordering::Relaxed);
+        (self.check.load(Ordering::Relaxed) ^ data, data)
+    }
+
+    fn store(&self, key: u64, data: u64) {
+        self.check.store(key ^ data, Ordering::Relaxed);
+        self.data.store(data, Ordering::Relaxed);
+    }
+}
+
+/// 置换表的统计数据
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TableStats {
+    /// 占用的字节数
+    pub size_bytes: usize,
+    /// 条目总数
+    pub capacity: usize,
+    /// 查询次数
+    pub probes: u64,
+    /// 查到同一局面的次数
+    pub hits: u64,
+    /// 写入次数
+    pub stores: u64,
+}
+
+impl TableStats {
+    /// 命中率，没有查询时为0
+    pub fn hit_rate(&self) -> f32 {
+        if self.probes == 0 {
+            0.0
+        } else {
+            self.hits as f32 / self.probes as f32
+        }
+    }
+}
+
+/// 固定大小的置换表，可以在多个搜索线程之间共享
+pub struct TranspositionTable {
+    slots: Box<[Slot]>,
+    bucket_mask: usize,
+    age: AtomicU8,
+    probes: AtomicU64,
+    hits: AtomicU64,
+    stores: AtomicU64,
+}
+
+impl TranspositionTable {
+    /// 创建不超过指定MB数的置换表，桶数取2的幂，至少一个桶
+    pub fn with_size_mb(size_mb: u32) -> Self {
+        let bytes = size_mb as usize * 1024 * 1024;
+        let buckets = (bytes / BUCKET_BYTES).max(1);
+        // 向下取到2的幂，保证不超过内存上限
+        let buckets = 1usize << buckets.ilog2();
+        Self {
+            slots: (0..buckets * BUCKET_ENTRIES)
+                .map(|_| Slot::default())
+                .collect(),
+            bucket_mask: buckets - 1,
+            age: AtomicU8::new(0),
+            probes: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            stores: AtomicU64::new(0),
+        }
+    }
+
+    /// 桶的数量
+    pub fn buckets(&self) -> usize {
+        self.bucket_mask + 1
+    }
+
+    /// 局面在表中的键：局面哈希加上搜索视角
+    pub fn key(board: &Board, to_move: PlayerColor, perspective: PlayerColor) -> u64 {
+        let hash = position_hash(board, to_move);
+        match perspective {
+            PlayerColor::Black => hash,
+            PlayerColor::White => hash ^ WHITE_PERSPECTIVE,
+        }
+    }
+
+    /// 开始新的根搜索，以前的条目变旧、更容易被替换
+    pub fn new_search(&self) {
+        self.age.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 清空所有条目和统计数据
+    pub fn clear(&self) {
+        for slot in self.slots.iter() {
+            slot.store(0, 0);
+        }
+        self.age.store(0, Ordering::Relaxed);
+        for counter in [&self.probes, &self.hits, &self.stores] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// 查询局面
+    pub fn probe(&self, key: u64) -> Option<TableEntry> {
+        self.probes.fetch_add(1, Ordering::Relaxed);
+        let entry = self
+            .bucket(key)
+            .iter()
+            .map(Slot::load)
+            .find(|(slot_key, data)| *slot_key == key && *data != 0)
+            .and_then(|(_, data)| TableEntry::unpack(data));
+        if entry.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        entry
+    }
+
+    /// 写入局面的搜索结果
+    pub fn store(&self, key: u64, depth: u8, score: i32, bound: Bound, best_move: Option<u8>) {
+        let age = self.age.load(Ordering::Relaxed);
+        let bucket = self.bucket(key);
+
+        let existing = bucket.iter().find_map(|slot| {
+            let (slot_key, data) = slot.load();
+            (slot_key == key && data != 0).then(|| (slot, TableEntry::unpack(data)))
+        });
+        let slot = match existing {
+            // 同一局面：只在新结果更有价值时覆盖
+            Some((slot, Some(old))) => {
+                if depth < old.depth && bound != Bound::Exact && old.age == age {
+                    return;
+                }
+                slot
+            }
+            Some((slot, None)) => slot,
+            None => bucket
+                .iter()
+                .min_by_key(|slot| {
+                    let (_, data) = slot.load();
+                    TableEntry::unpack(data).map_or(i32::MIN, |old| {
+                        i32::from(old.depth) - AGE_PENALTY * i32::from(age.wrapping_sub(old.age))
+                    })
+                })
+                .expect("buckets are never empty"),
+        };
+
+        let entry = TableEntry {
+            score,
+            depth,
+            bound,
+            best_move,
+            age,
+        };
+        slot.store(key, entry.pack());
+        self.stores.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 当前的统计数据
+    pub fn stats(&self) -> TableStats {
+        TableStats {
+            size_bytes: self.slots.len() * ENTRY_BYTES,
+            capacity: self.slots.len(),
+            probes: self.probes.load(Ordering::Relaxed),
+            hits: self.hits.load(Ordering::Relaxed),
+            stores: self.stores.load(Ordering::Relaxed),
+        }
+    }
+
+    fn bucket(&self, key: u64) -> &[Slot] {
+        let start = (key as usize & self.bucket_mask) * BUCKET_ENTRIES;
+        &self.slots[start..start + BUCKET_ENTRIES]
+    }
+}
+
+/// 搜索相关的偏好设置
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct SearchPreferences {
+    /// 对局AI置换表的大小（MB）
+    pub table_size_mb: u32,
+}
+
+impl Default for SearchPreferences {
+    /// 桌面端默认32MB，Web和移动端默认8MB
+    fn default() -> Self {
+        Self {
+            table_size_mb: match Platform::current() {
+                Platform::Desktop => 32,
+                Platform::Web | Platform::Mobile => 8,
+            },
+        }
+    }
+}
+
+impl SearchPreferences {
+    /// 切换到下一个可选的置换表大小，最大值之后回到最小值
+    pub fn next_table_size(&mut self) {
+        self.table_size_mb = TABLE_SIZES_MB
+            .into_iter()
+            .find(|&size| size > self.table_size_mb)
+            .unwrap_or(TABLE_SIZES_MB[0]);
+    }
+}
@@ -0,0 +1,279 @@
+// 评估权重自动调优 - 通过自我对弈用SPSA搜索更好的评估配置
+//
+// 用法：cargo run --release --features tuning -- --tune [--iterations N] [--games N]
+//        [--difficulty 难度] [--openings N] [--output 路径]
+//
+// 每次迭代对所有参数同时做随机±扰动，让正负两组配置对战，
+// 按对战结果沿扰动方向更新参数；定期与默认配置对战，保存表现最好的配置
+
+use super::{
+    evaluation::{EvalConfig, EvaluationWeights},
+    tournament::{run_tournament, TournamentConfig, TournamentPlayer},
+    AiDifficulty,
+};
+use rand::Rng;
+
+/// 启用调优的命令行参数
+pub const TUNING_FLAG: &str = "--tune";
+
+/// 默认输出路径
+pub const DEFAULT_OUTPUT: &str = "assets/ai/eval_config.ron";
+
+/// 位置权重表按对称性划分的格子，每个代表一类等价位置（左上象限的上三角）
+const POSITION_CLASSES: [(usize, usize); 10] = [
+    (0, 0),
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (1, 1),
+    (1, 2),
+    (1, 3),
+    (2, 2),
+    (2, 3),
+    (3, 3),
+];
+
+/// 阶段权重的扰动步长
+const WEIGHT_STEP: f32 = 0.1;
+
+/// 位置权重的扰动步长
+const POSITION_STEP: f32 = 4.0;
+
+/// 调优配置
+#[derive(Debug, Clone)]
+pub struct TuningConfig {
+    /// SPSA迭代次数
+    pub iterations: u32,
+    /// 每次迭代正负配置之间的对局数
+    pub games_per_iteration: u32,
+    /// 每隔多少次迭代与默认配置比较一次
+    pub check_every: u32,
+    /// 与默认配置比较时的对局数
+    pub check_games: u32,
+    /// 调优时双方使用的搜索难度
+    pub difficulty: AiDifficulty,
+    /// 每局开始时随机走的步数
+    pub opening_plies: u32,
+    /// 更新步长系数
+    pub learning_rate: f32,
+    /// 最佳配置的输出路径
+    pub output: String,
+}
+
+impl Default for TuningConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 100,
+            games_per_iteration: 8,
+            check_every: 10,
+            check_games: 40,
+            difficulty: AiDifficulty::Beginner,
+            opening_plies: 6,
+            learning_rate: 1.0,
+            output: DEFAULT_OUTPUT.to_string(),
+        }
+    }
+}
+
+impl TuningConfig {
+    /// 从命令行参数解析调优配置，没有调优参数时返回None
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let args: Vec<String> = args.into_iter().collect();
+        if !args.iter().any(|arg| arg == TUNING_FLAG) {
+            return Ok(None);
+        }
+
+        let mut config = Self::default();
+        let mut iter = args.iter().filter(|arg| *arg != TUNING_FLAG);
+        while let Some(arg) = iter.next() {
+            let value = iter
+                .next()
+                .ok_or_else(|| format!("missing value for {arg}"))?;
+            let number = || {
+                value
+                    .parse::<u32>()
+                    .map_err(|_| format!("{arg} expects a number"))
+            };
+            match arg.as_str() {
+                "--iterations" => config.iterations = number()?,
+                "--games" => config.games_per_iteration = number()?,
+                "--check-every" => config.check_every = number()?.max(1),
+                "--check-games" => config.check_games = number()?,
+                "--openings" => config.opening_plies = number()?,
+                "--difficulty" => {
+                    config.difficulty = AiDifficulty::from_name(value)
+                        .ok_or_else(|| format!("unknown difficulty: {value}"))?;
+                }
+                "--output" => config.output = value.clone(),
+                _ => return Err(format!("unknown argument: {arg}")),
+            }
+        }
+        Ok(Some(config))
+    }
+}
+
+/// 阶段权重按固定顺序展开
+fn stage_params(weights: &EvaluationWeights) -> [f32; 5] {
+    [
+        weights.corner,
+        weights.stability,
+        weights.mobility,
+        weights.positional,
+        weights.parity,
+    ]
+}
+
+fn set_stage_params(weights: &mut EvaluationWeights, params: &[f32]) {
+    weights.corner = params[0];
+    weights.stability = params[1];
+    weights.mobility = params[2];
+    weights.positional = params[3];
+    weights.parity = params[4];
+}
+
+/// 将评估配置展开为参数向量：三个阶段各5个权重，加上10类位置权重
+pub fn to_params(config: &EvalConfig) -> Vec<f32> {
+    let mut params = Vec::with_capacity(25);
+    for weights in [&config.opening, &config.midgame, &config.endgame] {
+        params.extend(stage_params(weights));
+    }
+    for (row, col) in POSITION_CLASSES {
+        params.push(config.position_weights[row][col] as f32);
+    }
+    params
+}
+
+/// 由参数向量构造评估配置，位置权重按八方向对称展开
+pub fn from_params(params: &[f32]) -> EvalConfig {
+    let mut config = EvalConfig::default();
+    for (index, weights) in [
+        &mut config.opening,
+        &mut config.midgame,
+        &mut config.endgame,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        set_stage_params(weights, &params[index * 5..index * 5 + 5]);
+    }
+
+    for (class, (row, col)) in POSITION_CLASSES.into_iter().enumerate() {
+        let weight = params[15 + class].round() as i32;
+        for (r, c) in [(row, col), (col, row)] {
+            config.position_weights[r][c] = weight;
+            config.position_weights[r][7 - c] = weight;
+            config.position_weights[7 - r][c] = weight;
+            config.position_weights[7 - r][7 - c] = weight;
+        }
+    }
+    config
+}
+
+/// 每个参数的扰动步长
+fn param_steps() -> Vec<f32> {
+    let mut steps = vec![WEIGHT_STEP; 15];
+    steps.extend([POSITION_STEP; POSITION_CLASSES.len()]);
+    steps
+}
+
+/// 所有参数同时按各自的符号和步长偏移，scale为本次迭代的扰动幅度（正负两组配置的scale互为相反数）
+pub fn perturb(params: &[f32], signs: &[f32], steps: &[f32], scale: f32) -> Vec<f32> {
+    params
+        .iter()
+        .zip(signs)
+        .zip(steps)
+        .map(|((param, sign), step)| param + scale * sign * step)
+        .collect()
+}
+
+/// 按正向配置的得分率更新参数：高于一半时沿扰动方向前进，反之后退，正好一半时不变
+pub fn spsa_update(params: &mut [f32], signs: &[f32], steps: &[f32], scale: f32, score: f32) {
+    let gradient = (score - 0.5) * 2.0;
+    for ((param, sign), step) in params.iter_mut().zip(signs).zip(steps) {
+        *param += scale * gradient * sign * step;
+    }
+}
+
+/// 两组评估配置对战，返回第一组的得分率
+fn match_score(config: &TuningConfig, first: EvalConfig, second: EvalConfig, games: u32) -> f32 {
+    let tournament = TournamentConfig {
+        games,
+        opening_plies: config.opening_plies,
+        first: TournamentPlayer {
+            eval: first,
+            ..TournamentPlayer::new(config.difficulty)
+        },
+        second: TournamentPlayer {
+            eval: second,
+            ..TournamentPlayer::new(config.difficulty)
+        },
+    };
+    run_tournament(&tournament, |_| {}).first_score_rate()
+}
+
+/// 运行SPSA调优，返回与默认配置对战表现最好的配置及其得分率
+pub fn tune(config: &TuningConfig, mut on_progress: impl FnMut(u32, f32)) -> (EvalConfig, f32) {
+    let mut rng = rand::thread_rng();
+    let baseline = EvalConfig::default();
+    let steps = param_steps();
+    let mut params = to_params(&baseline);
+    let mut best = (baseline.clone(), 0.5);
+
+    for iteration in 1..=config.iterations {
+        // 扰动幅度随迭代逐渐减小
+        let decay = 1.0 / (iteration as f32).powf(0.101);
+        let signs: Vec<f32> = (0..params.len())
+            .map(|_| if rng.gen_bool(0.5) { 1.0 } else { -1.0 })
+            .collect();
+
+        let plus = from_params(&perturb(&params, &signs, &steps, decay));
+        let minus = from_params(&perturb(&params, &signs, &steps, -decay));
+
+        let score = match_score(config, plus, minus, config.games_per_iteration);
+        spsa_update(
+            &mut params,
+            &signs,
+            &steps,
+            config.learning_rate * decay,
+            score,
+        );
+
+        if iteration % config.check_every == 0 || iteration == config.iterations {
+            let candidate = from_params(&params);
+            let score = match_score(
+                config,
+                candidate.clone(),
+                baseline.clone(),
+                config.check_games,
+            );
+            on_progress(iteration, score);
+            if score > best.1 {
+                best = (candidate, score);
+            }
+        }
+    }
+    best
+}
+
+/// 命令行入口：运行调优并把最佳配置写入文件
+pub fn run_from_cli(config: &TuningConfig) -> Result<(), String> {
+    println!(
+        "tuning with {:?}: {} iterations x {} games",
+        config.difficulty, config.iterations, config.games_per_iteration
+    );
+
+    let (best, score) = tune(config, |iteration, score| {
+        println!("iteration {iteration:>4}: {:.1}% vs default", score * 100.0);
+    });
+
+    if let Some(dir) = std::path::Path::new(&config.output).parent() {
+        std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    }
+    std::fs::write(&config.output, best.to_ron()?).map_err(|err| err.to_string())?;
+    println!(
+        "best config ({:.1}% vs default) written to {}",
+        score * 100.0,
+        config.output
+    );
+    Ok(())
+}
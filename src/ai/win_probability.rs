@@ -0,0 +1,153 @@
+// 胜率模型 - 把评估分数和剩余空位映射为胜率
+//
+// 逻辑回归：p = sigmoid(bias + e × (eval_weight + late_weight × 进度))，
+// 其中e为缩放后的评估分数，进度 = 1 - 空位数 / 60。越接近终局，同样的评估优势越能决定胜负。
+//
+// 运行时只使用写死的BAKED系数，不做任何训练；
+// 需要重新拟合时，用collect_samples生成自我对弈样本，再用fit离线求出系数替换BAKED
+
+use super::{evaluation::evaluate_board, tournament::random_opening, AiDifficulty};
+use crate::game::{Board, PlayerColor};
+
+/// 评估分数的缩放比例，使系数保持在1附近
+const EVAL_SCALE: f32 = 100.0;
+
+/// 初始局面的空位数
+const INITIAL_EMPTIES: f32 = 60.0;
+
+/// 一个训练样本：某一方视角下的局面特征和最终结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WinSample {
+    /// 该方视角的评估分数
+    pub eval: i32,
+    /// 剩余空位数
+    pub empties: u32,
+    /// 最终结果：胜1.0，平0.5，负0.0
+    pub outcome: f32,
+}
+
+/// 胜率模型的系数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WinProbabilityModel {
+    pub bias: f32,
+    /// 评估分数在开局时的权重
+    pub eval_weight: f32,
+    /// 评估分数权重随进度增加的部分
+    pub late_weight: f32,
+}
+
+impl Default for WinProbabilityModel {
+    fn default() -> Self {
+        Self::BAKED
+    }
+}
+
+impl WinProbabilityModel {
+    /// 内置系数：开局时200分的优势约为六成胜率，残局时约为九成
+    pub const BAKED: Self = Self {
+        bias: 0.0,
+        eval_weight: 0.2,
+        late_weight: 0.9,
+    };
+
+    /// 给定评估分数和剩余空位的胜率（0.0 - 1.0）
+    pub fn probability(&self, eval: i32, empties: u32) -> f32 {
+        sigmoid(self.logit(&features(eval, empties)))
+    }
+
+    /// 棋盘上指定一方的胜率，终局时直接按子数给出结果
+    pub fn for_board(&self, board: &Board, player: PlayerColor) -> f32 {
+        if board.is_game_over() {
+            return outcome(board, player);
+        }
+        let empties = board.get_empty_squares().count_ones();
+        self.probability(evaluate_board(board, player), empties)
+    }
+
+    fn logit(&self, [scaled_eval, progress]: &[f32; 2]) -> f32 {
+        self.bias + scaled_eval * (self.eval_weight + self.late_weight * progress)
+    }
+}
+
+/// 缩放后的评估分数和对局进度
+fn features(eval: i32, empties: u32) -> [f32; 2] {
+    let progress = (1.0 - empties as f32 / INITIAL_EMPTIES).clamp(0.0, 1.0);
+    [eval as f32 / EVAL_SCALE, progress]
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// 终局时指定一方的结果
+fn outcome(board: &Board, player: PlayerColor) -> f32 {
+    let own = board.count_pieces(player);
+    let opponent = board.count_pieces(player.opposite());
+    match own.cmp(&opponent) {
+        std::cmp::Ordering::Greater => 1.0,
+        std::cmp::Ordering::Equal => 0.5,
+        std::cmp::Ordering::Less => 0.0,
+    }
+}
+
+/// 用批量梯度下降最小化交叉熵，拟合模型系数
+pub fn fit(samples: &[WinSample], iterations: u32, learning_rate: f32) -> WinProbabilityModel {
+    let mut model = WinProbabilityModel {
+        bias: 0.0,
+        eval_weight: 0.0,
+        late_weight: 0.0,
+    };
+    if samples.is_empty() {
+        return model;
+    }
+
+    let count = samples.len() as f32;
+    for _ in 0..iterations {
+        let mut gradient = [0.0f32; 3];
+        for sample in samples {
+            let features = features(sample.eval, sample.empties);
+            let error = sigmoid(model.logit(&features)) - sample.outcome;
+            let [scaled_eval, progress] = features;
+            gradient[0] += error;
+            gradient[1] += error * scaled_eval;
+            gradient[2] += error * scaled_eval * progress;
+        }
+
+        model.bias -= learning_rate * gradient[0] / count;
+        model.eval_weight -= learning_rate * gradient[1] / count;
+        model.late_weight -= learning_rate * gradient[2] / count;
+    }
+    model
+}
+
+/// 让同一难度的AI自我对弈若干局，记录双方视角下每个局面的样本
+///
+/// 每局先随机走opening_plies步，避免所有对局相同
+pub fn collect_samples(games: u32, difficulty: AiDifficulty, opening_plies: u32) -> Vec<WinSample> {
+    let mut samples = Vec::new();
+
+    for _ in 0..games {
+        let (mut board, mut to_move) = random_opening(opening_plies);
+        let mut positions = Vec::new();
+
+        while !board.is_game_over() {
+            positions.push(board);
+            if let Some(chosen) = difficulty.get_ai_move(&board, to_move) {
+                board.make_move(chosen.position, to_move);
+            }
+            to_move = to_move.opposite();
+        }
+
+        for position in positions {
+            let empties = position.get_empty_squares().count_ones();
+            for player in [PlayerColor::Black, PlayerColor::White] {
+                samples.push(WinSample {
+                    eval: evaluate_board(&position, player),
+                    empties,
+                    outcome: outcome(&board, player),
+                });
+            }
+        }
+    }
+    samples
+}
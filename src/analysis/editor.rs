@@ -0,0 +1,264 @@
+// 局面编辑刷子 - 分析和回放查看器中整行、整列填充，镜像，清空和交换颜色
+//
+// 分析面板中的工具栏列出各个刷子：镜像、清空和交换颜色点击后立即作用于当前局面；
+// 整行和整列先选中刷子，再点击棋盘上的一格，用轮到的一方填满该格所在的行或列。
+// 编辑后的局面替换变着树（轮到的一方不变），与载入局面字符串相同。
+// 每次局面变化都检查是否是对局中可能出现的局面，不可能的局面显示原因并且不给出胜率
+
+use super::{AnalysisSession, GameTree};
+use crate::{
+    fonts::LocalizedText,
+    game::{Board, PlayerColor, Symmetry},
+    localization::TextKey,
+    ui::{spawn_button, ButtonColors, ButtonStyle},
+};
+use bevy::prelude::*;
+
+/// 中央四格（D4、E4、D5、E5），开局时就有棋子，之后不会变空
+const CENTER_MASK: u64 = 0x0000_0018_1800_0000;
+
+/// 不在A列、H列的格子
+const NOT_A_FILE: u64 = 0xFEFE_FEFE_FEFE_FEFE;
+const NOT_H_FILE: u64 = 0x7F7F_7F7F_7F7F_7F7F;
+
+/// 刷子按钮的颜色：普通和选中
+const BRUSH_COLOR: Color = Color::srgb(0.25, 0.25, 0.3);
+const BRUSH_ARMED_COLOR: Color = Color::srgb(0.45, 0.4, 0.15);
+
+/// 编辑局面的刷子
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardBrush {
+    /// 用轮到的一方填满一行
+    FillRow,
+    /// 用轮到的一方填满一列
+    FillColumn,
+    /// 左右镜像
+    Mirror,
+    /// 清空棋盘
+    Clear,
+    /// 黑白互换
+    SwapColors,
+}
+
+impl BoardBrush {
+    /// 工具栏中的顺序
+    pub const ALL: [BoardBrush; 5] = [
+        BoardBrush::FillRow,
+        BoardBrush::FillColumn,
+        BoardBrush::Mirror,
+        BoardBrush::Clear,
+        BoardBrush::SwapColors,
+    ];
+
+    pub fn label(self) -> TextKey {
+        match self {
+            Self::FillRow => TextKey::BrushFillRow,
+            Self::FillColumn => TextKey::BrushFillColumn,
+            Self::Mirror => TextKey::BrushMirror,
+            Self::Clear => TextKey::BrushClear,
+            Self::SwapColors => TextKey::BrushSwapColors,
+        }
+    }
+
+    /// 是否需要先在棋盘上点选一格
+    pub fn needs_square(self) -> bool {
+        matches!(self, Self::FillRow | Self::FillColumn)
+    }
+
+    /// 作用于局面；整行和整列填充以`square`所在的行或列为准，`color`为填充的颜色
+    pub fn apply(self, board: &Board, square: u8, color: PlayerColor) -> Board {
+        match self {
+            Self::FillRow => fill(board, 0xFF << (square / 8 * 8), color),
+            Self::FillColumn => fill(board, 0x0101_0101_0101_0101 << (square % 8), color),
+            Self::Mirror => board.transformed(Symmetry::FlipHorizontal),
+            Self::Clear => Board { black: 0, white: 0 },
+            Self::SwapColors => Board {
+                black: board.white,
+                white: board.black,
+            },
+        }
+    }
+}
+
+/// 把掩码中的格子都换成指定颜色的棋子
+fn fill(board: &Board, mask: u64, color: PlayerColor) -> Board {
+    match color {
+        PlayerColor::Black => Board {
+            black: board.black | mask,
+            white: board.white & !mask,
+        },
+        PlayerColor::White => Board {
+            black: board.black & !mask,
+            white: board.white | mask,
+        },
+    }
+}
+
+/// 对局中不可能出现的局面
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionProblem {
+    /// 棋子少于开局时的4枚
+    TooFewDiscs,
+    /// 中央四格有空格
+    EmptyCenter,
+    /// 有棋子与中央的棋子不相连（每步都落在已有棋子旁边）
+    Disconnected,
+}
+
+impl PositionProblem {
+    pub fn text_key(self) -> TextKey {
+        match self {
+            Self::TooFewDiscs => TextKey::PositionTooFewDiscs,
+            Self::EmptyCenter => TextKey::PositionEmptyCenter,
+            Self::Disconnected => TextKey::PositionDisconnected,
+        }
+    }
+}
+
+/// 与掩码中任一格相邻（含自身）的格子
+fn neighbourhood(mask: u64) -> u64 {
+    let row = mask | ((mask << 1) & NOT_A_FILE) | ((mask >> 1) & NOT_H_FILE);
+    row | (row << 8) | (row >> 8)
+}
+
+/// 检查局面能否在对局中出现
+///
+/// 只检查明显不可能的棋子分布：开局的4枚棋子在中央，之后每步都落在已有棋子旁边，
+/// 所以棋子不少于4枚、中央四格都有棋子，而且所有棋子八方向相连。通过检查不代表一定能走到该局面
+pub fn validate_position(board: &Board) -> Result<(), PositionProblem> {
+    let discs = board.black | board.white;
+    if discs.count_ones() < 4 {
+        return Err(PositionProblem::TooFewDiscs);
+    }
+    if discs & CENTER_MASK != CENTER_MASK {
+        return Err(PositionProblem::EmptyCenter);
+    }
+
+    let mut connected = CENTER_MASK;
+    loop {
+        let grown = neighbourhood(connected) & discs;
+        if grown == connected {
+            break;
+        }
+        connected = grown;
+    }
+    if connected != discs {
+        return Err(PositionProblem::Disconnected);
+    }
+    Ok(())
+}
+
+/// 当前选中、等待点选格子的刷子
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BrushState {
+    pub armed: Option<BoardBrush>,
+}
+
+/// 用刷子编辑变着树的当前局面，编辑后的局面作为新变着树的起点，轮到的一方不变
+pub fn apply_brush(tree: &GameTree, brush: BoardBrush, square: u8) -> GameTree {
+    let board = brush.apply(&tree.board(), square, tree.to_move());
+    GameTree::new(board, tree.to_move())
+}
+
+/// 工具栏中的刷子按钮
+#[derive(Component)]
+pub struct BrushButton(pub BoardBrush);
+
+/// 工具栏下方的提示：选中刷子时的操作说明，或者局面不可能出现的原因
+#[derive(Component)]
+pub struct BrushStatusText;
+
+/// 在分析面板中创建刷子工具栏和提示
+pub fn spawn_brush_toolbar(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
+    parent
+        .spawn(Node {
+            column_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|row| {
+            let style = ButtonStyle {
+                width: Val::Auto,
+                height: Val::Auto,
+                padding: UiRect::axes(Val::Px(6.0), Val::Px(3.0)),
+                color: BRUSH_COLOR,
+                radius: 4.0,
+                font_size: 12.0,
+                ..default()
+            };
+            for brush in BoardBrush::ALL {
+                spawn_button(row, &style, font, brush.label(), BrushButton(brush));
+            }
+        });
+    parent.spawn((
+        Text::default(),
+        TextKey::BrushFillRowHint,
+        TextFont {
+            font: font.clone(),
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.6, 0.4)),
+        LocalizedText,
+        Visibility::Hidden,
+        BrushStatusText,
+    ));
+}
+
+/// 点击刷子按钮：整行和整列选中（再点一次取消），其余刷子立即作用于当前局面
+pub fn handle_brush_buttons(
+    buttons: Query<(&Interaction, &BrushButton), Changed<Interaction>>,
+    mut brush_state: ResMut<BrushState>,
+    mut session: ResMut<AnalysisSession>,
+) {
+    for (interaction, BrushButton(brush)) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if brush.needs_square() {
+            brush_state.armed = (brush_state.armed != Some(*brush)).then_some(*brush);
+        } else {
+            brush_state.armed = None;
+            session.tree = apply_brush(&session.tree, *brush, 0);
+        }
+    }
+}
+
+/// 按选中的刷子高亮按钮，并显示操作说明或局面不可能出现的原因
+pub fn update_brush_toolbar(
+    brush_state: Res<BrushState>,
+    session: Res<AnalysisSession>,
+    mut buttons: Query<(&BrushButton, &mut ButtonColors, &mut BackgroundColor)>,
+    mut status_query: Query<(&mut TextKey, &mut Visibility), With<BrushStatusText>>,
+) {
+    if !brush_state.is_changed() && !session.is_changed() {
+        return;
+    }
+    for (BrushButton(brush), mut colors, mut background) in buttons.iter_mut() {
+        let color = if brush_state.armed == Some(*brush) {
+            BRUSH_ARMED_COLOR
+        } else {
+            BRUSH_COLOR
+        };
+        colors.set_if_neq(ButtonColors::for_color(color));
+        background.set_if_neq(BackgroundColor(color));
+    }
+
+    let key = match brush_state.armed {
+        Some(BoardBrush::FillRow) => Some(TextKey::BrushFillRowHint),
+        Some(BoardBrush::FillColumn) => Some(TextKey::BrushFillColumnHint),
+        _ => validate_position(&session.tree.board())
+            .err()
+            .map(PositionProblem::text_key),
+    };
+    for (mut status, mut visibility) in status_query.iter_mut() {
+        match key {
+            Some(key) => {
+                status.set_if_neq(key);
+                visibility.set_if_neq(Visibility::Inherited);
+            }
+            None => {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+        }
+    }
+}
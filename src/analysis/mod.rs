@@ -0,0 +1,377 @@
+// 复盘分析 - 对局结束后从任意一步尝试其他走法，形成变着树
+//
+// 结果界面按A进入分析模式，棋盘显示变着树的当前局面：
+// - 点击棋盘：为当前轮到的一方尝试走法，新走法会形成变着
+// - 左右方向键或左右滑动：沿当前分支后退、前进
+// - 上下方向键：切换同一局面的其他变着
+// - Home：回到主线（原始对局）
+// - Esc：退出分析，棋盘恢复为终局局面
+//
+// 难度选择界面的历史对局列表也用同一个查看器回放已保存的对局（Replay状态），退出后回到菜单
+//
+// 状态面板同时显示胜率模型给出的当前局面黑方胜率；桌面端和Web版的查看器底部还有局面字符串输入栏
+// （见position_input），可以载入开局书或论坛中的局面；分析面板中的刷子工具栏（见editor）可以直接编辑局面，
+// 不可能出现的局面不显示胜率
+
+pub mod editor;
+pub mod position_input;
+pub mod tree;
+
+pub use editor::*;
+pub use position_input::*;
+pub use tree::*;
+
+use crate::{
+    ai::win_probability::WinProbabilityModel,
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    game::{Board, GameLog, MoveAction, PlayerColor},
+    history::SWIPE_MIN_DISTANCE,
+    input::{InputAction, InputMap},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    platform::{Platform, PlatformProfile},
+    state::{GameState, GameTransition, GameTransitions},
+    ui::{
+        board_square_at, spawn_valid_move_indicators, sync_pieces, BoardColors, BoardUI, Piece,
+        ToDelete, ValidMoveIndicator,
+    },
+};
+use bevy::prelude::*;
+
+/// 分析模式的变着树，进入分析时以本局命令日志为主线创建
+#[derive(Resource, Debug, Clone)]
+pub struct AnalysisSession {
+    pub tree: GameTree,
+}
+
+/// 分析状态文本（当前步数和所在分支）
+#[derive(Component)]
+pub struct AnalysisStatusText;
+
+/// 当前局面的黑方胜率
+#[derive(Component)]
+pub struct AnalysisWinChanceText;
+
+/// 结果界面按A（默认）进入分析模式
+pub fn enter_analysis_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut transitions: GameTransitions,
+) {
+    if input_map.just_pressed(InputAction::Analyze, &keyboard_input) {
+        transitions.request(GameTransition::Analyze);
+    }
+}
+
+/// 创建变着树和分析面板
+pub fn start_analysis(
+    mut commands: Commands,
+    log_query: Query<&GameLog>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    platform_profile: Res<PlatformProfile>,
+) {
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+    commands.insert_resource(AnalysisSession {
+        tree: GameTree::from_log(log),
+    });
+    commands.insert_resource(BrushState::default());
+
+    let font = get_font_for_language(&language_settings, &font_assets);
+    spawn_analysis_panel(&mut commands, &font, GameState::Analysis);
+    if has_position_input(&platform_profile) {
+        spawn_position_input_bar(&mut commands, &font, GameState::Analysis);
+    }
+}
+
+/// 打开历史对局回放：变着树已由历史对局列表创建，这里只创建分析面板
+pub fn start_replay(
+    mut commands: Commands,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    platform_profile: Res<PlatformProfile>,
+) {
+    commands.insert_resource(BrushState::default());
+    let font = get_font_for_language(&language_settings, &font_assets);
+    spawn_analysis_panel(&mut commands, &font, GameState::Replay);
+    if has_position_input(&platform_profile) {
+        spawn_position_input_bar(&mut commands, &font, GameState::Replay);
+    }
+}
+
+/// 局面字符串输入栏需要键盘，只在桌面端和Web版显示
+fn has_position_input(platform_profile: &PlatformProfile) -> bool {
+    matches!(platform_profile.platform, Platform::Desktop | Platform::Web)
+}
+
+/// 结束回放时清理回放专用的棋盘
+pub fn cleanup_replay_board(mut commands: Commands, board_ui_query: Query<Entity, With<BoardUI>>) {
+    for entity in board_ui_query.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+}
+
+/// 分析面板：当前步数、分支、胜率和操作说明，离开指定状态时自动清理
+fn spawn_analysis_panel(commands: &mut Commands, font: &Handle<Font>, scope: GameState) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                right: Val::Px(0.0),
+                top: Val::Px(60.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            StateScoped(scope),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(4.0),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                    BorderRadius::all(Val::Px(6.0)),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::default(),
+                        TextKey::AnalysisStatus,
+                        TextArgs::default(),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        LocalizedText,
+                        AnalysisStatusText,
+                    ));
+                    panel.spawn((
+                        Text::default(),
+                        TextKey::AnalysisWinChance,
+                        TextArgs::default(),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.85, 0.5)),
+                        LocalizedText,
+                        AnalysisWinChanceText,
+                    ));
+                    spawn_brush_toolbar(panel, font);
+                    panel.spawn((
+                        Text::default(),
+                        TextKey::AnalysisControls,
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        LocalizedText,
+                    ));
+                });
+        });
+}
+
+/// 分析模式的棋盘点击、滑动和键盘导航
+pub fn analysis_input(
+    mut session: ResMut<AnalysisSession>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    touch_input: Res<Touches>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut transitions: GameTransitions,
+    mut brush_state: ResMut<BrushState>,
+) {
+    if input_map.just_pressed(InputAction::LeaveAnalysis, &keyboard_input) {
+        transitions.request(GameTransition::LeaveAnalysis);
+        return;
+    }
+
+    // 只在确实发生变化时修改资源，避免每帧重绘棋盘
+    let tree = &mut session.bypass_change_detection().tree;
+    let mut changed = false;
+
+    if input_map.just_pressed(InputAction::StepBack, &keyboard_input) {
+        changed |= tree.back();
+    }
+    if input_map.just_pressed(InputAction::StepForward, &keyboard_input) {
+        changed |= tree.forward();
+    }
+    if input_map.just_pressed(InputAction::PreviousVariation, &keyboard_input) {
+        changed |= tree.switch_variation(-1);
+    }
+    if input_map.just_pressed(InputAction::NextVariation, &keyboard_input) {
+        changed |= tree.switch_variation(1);
+    }
+    if input_map.just_pressed(InputAction::MainLine, &keyboard_input) {
+        changed |= tree.to_main_line();
+    }
+
+    // 点击和轻触尝试走法，水平滑动前后移动
+    let mut tapped = Vec::new();
+    if mouse_input.just_pressed(MouseButton::Left) {
+        tapped.extend(windows.single().ok().and_then(Window::cursor_position));
+    }
+    for touch in touch_input.iter_just_released() {
+        let distance = touch.position() - touch.start_position();
+        if distance.x.abs() >= SWIPE_MIN_DISTANCE && distance.x.abs() > distance.y.abs() * 2.0 {
+            // 向右滑动后退，向左滑动前进，与对局中的历史浏览一致
+            changed |= if distance.x > 0.0 {
+                tree.back()
+            } else {
+                tree.forward()
+            };
+        } else if distance.length() < SWIPE_MIN_DISTANCE {
+            tapped.push(touch.position());
+        }
+    }
+
+    if let Ok((camera, camera_transform)) = camera_query.single() {
+        for screen_position in tapped {
+            let Some(position) = board_square_at(camera, camera_transform, screen_position) else {
+                continue;
+            };
+            // 选中整行或整列刷子时点击的格子用于编辑局面，否则尝试走法，不合法的走法直接忽略
+            if let Some(brush) = brush_state.armed.take() {
+                *tree = apply_brush(tree, brush, position);
+                changed = true;
+            } else {
+                changed |= tree.play(MoveAction::Place(position)).is_ok();
+            }
+        }
+    }
+
+    if changed {
+        session.set_changed();
+    }
+}
+
+/// 变着树的当前位置变化时重绘棋子和可落子提示
+pub fn render_analysis_board(
+    mut commands: Commands,
+    session: Res<AnalysisSession>,
+    piece_query: Query<(Entity, &Piece, &ChildOf), Without<ToDelete>>,
+    indicator_query: Query<Entity, With<ValidMoveIndicator>>,
+    board_root: Query<Entity, With<BoardUI>>,
+    colors: Res<BoardColors>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !session.is_changed() {
+        return;
+    }
+    let Ok(root) = board_root.single() else {
+        return;
+    };
+
+    for entity in indicator_query.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+
+    // 前进、后退和切换变着时只替换变化的棋子
+    let tree = &session.tree;
+    sync_pieces(
+        &mut commands,
+        root,
+        &piece_query,
+        &tree.board(),
+        &colors,
+        &mut meshes,
+        &mut materials,
+    );
+    spawn_valid_move_indicators(
+        &mut commands,
+        root,
+        tree.board().get_valid_moves(tree.to_move()),
+        &colors,
+        &mut meshes,
+        &mut materials,
+    );
+}
+
+/// 更新分析状态文本
+pub fn update_analysis_status(
+    session: Res<AnalysisSession>,
+    mut status_query: Query<&mut TextArgs, With<AnalysisStatusText>>,
+    mut win_chance_query: Query<
+        (&mut TextKey, &mut TextArgs),
+        (With<AnalysisWinChanceText>, Without<AnalysisStatusText>),
+    >,
+) {
+    if !session.is_changed() {
+        return;
+    }
+
+    let tree = &session.tree;
+    let branch = if tree.is_main_line(tree.current()) {
+        TextArg::Key(TextKey::AnalysisMainLine)
+    } else {
+        let (index, count) = tree.variation_index();
+        TextArg::Format(
+            TextKey::AnalysisVariation,
+            vec![
+                TextArg::Value((index + 1).to_string()),
+                TextArg::Value(count.to_string()),
+            ],
+        )
+    };
+
+    for mut args in status_query.iter_mut() {
+        args.0 = vec![TextArg::Value(tree.ply().to_string()), branch.clone()];
+    }
+
+    // 玩家执黑，胜率按黑方视角显示；不可能出现的局面不评估
+    let valid = validate_position(&tree.board()).is_ok();
+    let win_chance = WinProbabilityModel::BAKED.for_board(&tree.board(), PlayerColor::Black);
+    for (mut key, mut args) in win_chance_query.iter_mut() {
+        if valid {
+            key.set_if_neq(TextKey::AnalysisWinChance);
+            args.0 = vec![TextArg::Value(format!("{:.0}", win_chance * 100.0))];
+        } else {
+            key.set_if_neq(TextKey::AnalysisWinChanceUnavailable);
+            args.0.clear();
+        }
+    }
+}
+
+/// 退出分析：移除变着树，棋盘恢复为终局局面
+pub fn finish_analysis(
+    mut commands: Commands,
+    board_query: Query<&Board>,
+    piece_query: Query<(Entity, &Piece, &ChildOf), Without<ToDelete>>,
+    indicator_query: Query<Entity, With<ValidMoveIndicator>>,
+    board_root: Query<Entity, With<BoardUI>>,
+    colors: Res<BoardColors>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.remove_resource::<AnalysisSession>();
+
+    for entity in indicator_query.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+    if let (Ok(board), Ok(root)) = (board_query.single(), board_root.single()) {
+        sync_pieces(
+            &mut commands,
+            root,
+            &piece_query,
+            board,
+            &colors,
+            &mut meshes,
+            &mut materials,
+        );
+    }
+}
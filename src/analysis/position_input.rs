@@ -0,0 +1,340 @@
+// 局面字符串输入 - 分析和回放查看器中粘贴局面字符串载入局面，或复制当前局面的字符串
+//
+// 桌面端和Web版在查看器底部显示输入栏：键入X、O、-和空格，或者Ctrl+V（macOS上Cmd+V）粘贴，
+// 回车或点击"载入"后用局面字符串（见game::position）创建新的变着树；
+// 点击"复制"或Ctrl+C把当前局面的字符串复制到剪贴板并填入输入栏。
+// 粘贴时小写的x、o和表示空格的"."会被规范化，方便从开局书或论坛中直接复制
+
+use super::{AnalysisSession, GameTree};
+use crate::{
+    fonts::LocalizedText,
+    game::{parse_position_string, position_string, POSITION_STRING_LEN},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    platform::clipboard,
+    state::GameState,
+    ui::{spawn_button, ButtonStyle},
+};
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+/// 输入栏下方的提示
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PositionStatus {
+    /// 操作说明
+    #[default]
+    Hint,
+    /// 已载入输入的局面
+    Loaded,
+    /// 输入的不是有效的局面字符串
+    Invalid,
+    /// 已复制当前局面
+    Copied,
+    /// 无法读写剪贴板
+    ClipboardFailed,
+}
+
+impl PositionStatus {
+    pub fn text_key(self) -> TextKey {
+        match self {
+            Self::Hint => TextKey::PositionInputHint,
+            Self::Loaded => TextKey::PositionLoaded,
+            Self::Invalid => TextKey::PositionInvalid,
+            Self::Copied => TextKey::PositionCopied,
+            Self::ClipboardFailed => TextKey::PositionClipboardFailed,
+        }
+    }
+}
+
+/// 按键对输入栏的作用
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionKeyAction {
+    /// 文本发生变化
+    Edited,
+    /// 载入当前文本
+    Submit,
+    /// 与输入栏无关的按键
+    Ignored,
+}
+
+/// 输入栏中的文本和上一次操作的结果
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PositionInput {
+    pub text: String,
+    pub status: PositionStatus,
+}
+
+/// 规范化局面字符串中的一个字符：小写的x、o转为大写，"."视为空格
+fn normalize_char(character: char) -> char {
+    match character {
+        'x' => 'X',
+        'o' => 'O',
+        '.' => '-',
+        other => other,
+    }
+}
+
+impl PositionInput {
+    /// 处理一次按键：局面字符串中的字符追加到文本，退格删除最后一个字符，回车载入
+    pub fn apply_key(&mut self, key: &Key) -> PositionKeyAction {
+        let typed: Vec<char> = match key {
+            Key::Character(characters) => characters
+                .chars()
+                .map(normalize_char)
+                .filter(|character| matches!(character, 'X' | 'O' | '-'))
+                .collect(),
+            Key::Space => vec![' '],
+            Key::Backspace => {
+                if self.text.pop().is_none() {
+                    return PositionKeyAction::Ignored;
+                }
+                self.status = PositionStatus::Hint;
+                return PositionKeyAction::Edited;
+            }
+            Key::Enter if !self.text.is_empty() => return PositionKeyAction::Submit,
+            _ => return PositionKeyAction::Ignored,
+        };
+
+        let mut edited = false;
+        for character in typed {
+            if self.text.chars().count() < POSITION_STRING_LEN {
+                self.text.push(character);
+                edited = true;
+            }
+        }
+        if !edited {
+            return PositionKeyAction::Ignored;
+        }
+        self.status = PositionStatus::Hint;
+        PositionKeyAction::Edited
+    }
+
+    /// 用粘贴的文本替换输入，去掉首尾空白并规范化字符
+    pub fn paste(&mut self, text: &str) {
+        self.text = text.trim().chars().map(normalize_char).collect();
+        self.status = PositionStatus::Hint;
+    }
+
+    /// 把输入解析为新的变着树，无效时返回None并提示
+    pub fn load(&mut self) -> Option<GameTree> {
+        let parsed = parse_position_string(self.text.trim());
+        self.status = if parsed.is_some() {
+            PositionStatus::Loaded
+        } else {
+            PositionStatus::Invalid
+        };
+        parsed.map(|(board, to_move)| GameTree::new(board, to_move))
+    }
+
+    /// 把当前局面的字符串填入输入栏并复制到剪贴板
+    pub fn copy_current(&mut self, tree: &GameTree) {
+        self.text = position_string(&tree.board(), tree.to_move());
+        self.status = match clipboard::copy_text(&self.text) {
+            Ok(()) => PositionStatus::Copied,
+            Err(err) => {
+                warn!("Failed to copy the position string: {err}");
+                PositionStatus::ClipboardFailed
+            }
+        };
+    }
+}
+
+/// 输入栏
+#[derive(Component)]
+pub struct PositionInputBar;
+
+/// 输入栏中的文本
+#[derive(Component)]
+pub struct PositionInputText;
+
+/// 输入栏下方的提示
+#[derive(Component)]
+pub struct PositionStatusText;
+
+/// 载入按钮
+#[derive(Component)]
+pub struct PositionLoadButton;
+
+/// 复制按钮
+#[derive(Component)]
+pub struct PositionCopyButton;
+
+/// 输入栏中显示的文本参数，末尾显示光标
+fn input_args(input: &PositionInput) -> TextArgs {
+    TextArgs(vec![TextArg::Value(format!("{}_", input.text))])
+}
+
+/// 在查看器底部创建输入栏，离开指定状态时自动清理
+pub fn spawn_position_input_bar(commands: &mut Commands, font: &Handle<Font>, scope: GameState) {
+    let input = PositionInput::default();
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                right: Val::Px(0.0),
+                bottom: Val::Px(8.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            PositionInputBar,
+            StateScoped(scope),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        max_width: Val::Px(380.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(4.0),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                    BorderRadius::all(Val::Px(6.0)),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::default(),
+                        TextKey::PositionInputFormat,
+                        input_args(&input),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        LocalizedText,
+                        PositionInputText,
+                    ));
+                    panel
+                        .spawn(Node {
+                            column_gap: Val::Px(8.0),
+                            ..default()
+                        })
+                        .with_children(|row| {
+                            spawn_bar_button(row, font, TextKey::PositionLoad, PositionLoadButton);
+                            spawn_bar_button(row, font, TextKey::PositionCopy, PositionCopyButton);
+                        });
+                    panel.spawn((
+                        Text::default(),
+                        input.status.text_key(),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 11.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        LocalizedText,
+                        PositionStatusText,
+                    ));
+                });
+        });
+    commands.insert_resource(input);
+}
+
+fn spawn_bar_button(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    label: TextKey,
+    marker: impl Component,
+) {
+    let style = ButtonStyle {
+        width: Val::Px(80.0),
+        height: Val::Px(32.0),
+        color: Color::srgb(0.25, 0.25, 0.3),
+        radius: 5.0,
+        font_size: 14.0,
+        ..default()
+    };
+    spawn_button(parent, &style, font, label, marker);
+}
+
+/// 读取输入栏的键盘输入、粘贴和复制快捷键以及两个按钮，载入的局面替换变着树
+///
+/// 没有输入栏时（移动端）忽略键盘输入
+pub fn handle_position_input(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    load_buttons: Query<&Interaction, (Changed<Interaction>, With<PositionLoadButton>)>,
+    copy_buttons: Query<&Interaction, (Changed<Interaction>, With<PositionCopyButton>)>,
+    bar_query: Query<(), With<PositionInputBar>>,
+    mut input: ResMut<PositionInput>,
+    mut session: ResMut<AnalysisSession>,
+    language_settings: Res<LanguageSettings>,
+) {
+    if bar_query.is_empty() {
+        keyboard_events.clear();
+        return;
+    }
+
+    let shortcut = keyboard_input.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    let mut submit = load_buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+    let mut copy = copy_buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        if shortcut {
+            match event.key_code {
+                KeyCode::KeyV => {
+                    let prompt = language_settings
+                        .get_texts()
+                        .get(TextKey::PositionPastePrompt);
+                    match clipboard::paste_text(prompt) {
+                        Ok(text) => input.paste(&text),
+                        Err(err) => {
+                            warn!("Failed to paste a position string: {err}");
+                            input.status = PositionStatus::ClipboardFailed;
+                        }
+                    }
+                }
+                KeyCode::KeyC => copy = true,
+                _ => {}
+            }
+            continue;
+        }
+        submit |= input.apply_key(&event.logical_key) == PositionKeyAction::Submit;
+    }
+
+    if submit {
+        if let Some(tree) = input.load() {
+            session.tree = tree;
+        }
+    }
+    if copy {
+        input.copy_current(&session.tree);
+    }
+}
+
+/// 输入变化后更新输入栏的文本和提示
+pub fn update_position_input_text(
+    input: Res<PositionInput>,
+    mut text_query: Query<&mut TextArgs, With<PositionInputText>>,
+    mut status_query: Query<&mut TextKey, With<PositionStatusText>>,
+) {
+    if !input.is_changed() {
+        return;
+    }
+    for mut args in text_query.iter_mut() {
+        args.set_if_neq(input_args(&input));
+    }
+    for mut key in status_query.iter_mut() {
+        key.set_if_neq(input.status.text_key());
+    }
+}
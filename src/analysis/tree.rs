@@ -0,0 +1,219 @@
+// 变着树 - 以走棋命令为边的对局树
+//
+// 根节点是初始局面，每个子节点比父节点多应用一条命令。每个节点的第一个子节点是
+// 该局面的主要延续，从根节点沿第一个子节点一直走下去就是主线（即原始对局）；
+// 在任意节点尝试其他走法会追加新的子节点，形成变着
+
+use crate::game::{Board, CommandError, GameLog, MoveAction, MoveCommand, PlayerColor};
+
+/// 节点编号，根节点为0
+pub type NodeId = usize;
+
+/// 树中的一个局面
+#[derive(Debug, Clone)]
+struct Node {
+    /// 从根节点到该节点的命令日志
+    log: GameLog,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+/// 对局变着树
+#[derive(Debug, Clone)]
+pub struct GameTree {
+    nodes: Vec<Node>,
+    current: NodeId,
+}
+
+impl GameTree {
+    /// 根节点
+    pub const ROOT: NodeId = 0;
+
+    /// 从初始局面创建只有根节点的树
+    pub fn new(initial: Board, first_player: PlayerColor) -> Self {
+        Self {
+            nodes: vec![Node {
+                log: GameLog::new(initial, first_player),
+                parent: None,
+                children: Vec::new(),
+            }],
+            current: Self::ROOT,
+        }
+    }
+
+    /// 以对局日志作为主线创建树，当前位置为对局的最后一步
+    pub fn from_log(log: &GameLog) -> Self {
+        let mut tree = Self::new(log.initial(), log.first_player());
+        for command in log.commands() {
+            tree.current = tree
+                .child_for(tree.current, command.action)
+                .expect("commands in a game log are valid");
+        }
+        tree
+    }
+
+    /// 当前节点
+    pub fn current(&self) -> NodeId {
+        self.current
+    }
+
+    /// 当前节点的命令日志
+    pub fn log(&self) -> &GameLog {
+        &self.nodes[self.current].log
+    }
+
+    /// 当前局面
+    pub fn board(&self) -> Board {
+        self.log().board()
+    }
+
+    /// 当前局面轮到的玩家
+    pub fn to_move(&self) -> PlayerColor {
+        self.log().to_move()
+    }
+
+    /// 当前节点距离初始局面的步数
+    pub fn ply(&self) -> usize {
+        self.log().commands().len()
+    }
+
+    /// 到达指定节点的命令，根节点为None
+    pub fn command(&self, node: NodeId) -> Option<MoveCommand> {
+        self.nodes[node].log.commands().last().copied()
+    }
+
+    /// 父节点
+    pub fn parent(&self, node: NodeId) -> Option<NodeId> {
+        self.nodes[node].parent
+    }
+
+    /// 子节点，第一个为主要延续
+    pub fn children(&self, node: NodeId) -> &[NodeId] {
+        &self.nodes[node].children
+    }
+
+    /// 节点是否在主线上
+    pub fn is_main_line(&self, node: NodeId) -> bool {
+        let mut node = node;
+        while let Some(parent) = self.parent(node) {
+            if self.children(parent).first() != Some(&node) {
+                return false;
+            }
+            node = parent;
+        }
+        true
+    }
+
+    /// 当前节点在兄弟节点中的序号和兄弟节点的数量（含自身），根节点为(0, 1)
+    pub fn variation_index(&self) -> (usize, usize) {
+        match self.parent(self.current) {
+            Some(parent) => {
+                let siblings = self.children(parent);
+                let index = siblings
+                    .iter()
+                    .position(|&node| node == self.current)
+                    .unwrap_or(0);
+                (index, siblings.len())
+            }
+            None => (0, 1),
+        }
+    }
+
+    /// 在当前局面走棋，已有相同走法时进入该分支，否则创建新的变着
+    ///
+    /// 走棋后对手无棋可走而对局未结束时，自动记录跳过回合
+    pub fn play(&mut self, action: MoveAction) -> Result<NodeId, CommandError> {
+        let mut node = self.child_for(self.current, action)?;
+
+        let log = &self.nodes[node].log;
+        let board = log.board();
+        if !board.is_game_over() && !board.has_valid_moves(log.to_move()) {
+            node = self.child_for(node, MoveAction::Pass)?;
+        }
+
+        self.current = node;
+        Ok(node)
+    }
+
+    /// 后退一步，已在根节点时返回false
+    pub fn back(&mut self) -> bool {
+        match self.parent(self.current) {
+            Some(parent) => {
+                self.current = parent;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 沿当前分支的主要延续前进一步，没有后续时返回false
+    pub fn forward(&mut self) -> bool {
+        match self.children(self.current).first() {
+            Some(&child) => {
+                self.current = child;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 切换到同一局面的相邻变着，offset为正时切换到后面的变着，循环切换
+    pub fn switch_variation(&mut self, offset: isize) -> bool {
+        let Some(parent) = self.parent(self.current) else {
+            return false;
+        };
+        let (index, count) = self.variation_index();
+        if count < 2 {
+            return false;
+        }
+
+        let next = (index as isize + offset).rem_euclid(count as isize) as usize;
+        self.current = self.children(parent)[next];
+        true
+    }
+
+    /// 回到主线：跳到当前节点最近的主线祖先，已在主线上时返回false
+    pub fn to_main_line(&mut self) -> bool {
+        if self.is_main_line(self.current) {
+            return false;
+        }
+
+        let mut node = self.current;
+        while !self.is_main_line(node) {
+            node = self.parent(node).expect("the root is on the main line");
+        }
+        self.current = node;
+        true
+    }
+
+    /// 跳到指定节点
+    pub fn go_to(&mut self, node: NodeId) {
+        if node < self.nodes.len() {
+            self.current = node;
+        }
+    }
+
+    /// 查找或创建父节点下对应走法的子节点
+    fn child_for(&mut self, parent: NodeId, action: MoveAction) -> Result<NodeId, CommandError> {
+        let command = self.nodes[parent].log.next_command(action);
+        if let Some(&existing) = self
+            .children(parent)
+            .iter()
+            .find(|&&child| self.command(child) == Some(command))
+        {
+            return Ok(existing);
+        }
+
+        let mut log = self.nodes[parent].log.clone();
+        log.apply(command)?;
+
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            log,
+            parent: Some(parent),
+            children: Vec::new(),
+        });
+        self.nodes[parent].children.push(id);
+        Ok(id)
+    }
+}
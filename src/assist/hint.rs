@@ -0,0 +1,199 @@
+// 最佳走法提示 - 在后台为玩家的当前局面持续计算最佳走法，按提示键时立即显示
+//
+// 轮到本机玩家走棋时，以后台优先级向搜索调度器申请许可，从HINT_MIN_DEPTH层开始搜索当前局面，
+// 每次完成后保存结果，再逐层加深到HINT_MAX_DEPTH。两次搜索的开始至少间隔HINT_REFRESH_SECS，
+// 局面变化时丢弃旧的搜索和结果；AI需要走棋时调度器会取消这里的搜索，之后再重新申请
+
+use super::ActiveMatchRules;
+use crate::{
+    ai::{evaluation::EvalConfig, minimax::find_best_move_with_table, AiScheduler, SearchPriority},
+    config_assets::ActiveEvalConfig,
+    game::{Board, PlayerColor},
+    input::PlayerSeats,
+    preferences::Preferences,
+    sandbox::SandboxMode,
+    ui::CurrentPlayer,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use futures_lite::future;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// 第一次搜索的深度
+pub const HINT_MIN_DEPTH: u8 = 2;
+
+/// 逐层加深的最大深度
+pub const HINT_MAX_DEPTH: u8 = 6;
+
+/// 两次搜索开始之间的最短间隔（秒）
+pub const HINT_REFRESH_SECS: f32 = 0.5;
+
+/// 正在后台进行的一次搜索
+struct HintSearch {
+    depth: u8,
+    /// 搜索任务持有调度器的许可，搜索真正结束时才归还预算
+    task: Task<Option<u8>>,
+    /// 许可的取消标志
+    cancel: Arc<AtomicBool>,
+}
+
+/// 当前局面缓存的最佳走法
+#[derive(Resource, Default)]
+pub struct HintCache {
+    /// 缓存对应的局面和轮到的一方
+    position: Option<(Board, PlayerColor)>,
+    /// 最佳走法和搜索深度
+    best: Option<(u8, u8)>,
+    search: Option<HintSearch>,
+    /// 上一次搜索开始的时间
+    last_started: Option<Duration>,
+}
+
+impl HintCache {
+    /// 局面的最佳走法，还没有算出或缓存的不是该局面时返回None
+    pub fn best_move(&self, board: &Board, player: PlayerColor) -> Option<u8> {
+        self.best
+            .filter(|_| self.position == Some((*board, player)))
+            .map(|(position, _)| position)
+    }
+
+    /// 缓存的最佳走法的搜索深度
+    pub fn depth(&self) -> Option<u8> {
+        self.best.map(|(_, depth)| depth)
+    }
+
+    /// 是否正在后台搜索
+    pub fn is_searching(&self) -> bool {
+        self.search.is_some()
+    }
+
+    /// 丢弃缓存和正在进行的搜索，保留上一次搜索的开始时间以继续限制频率
+    pub fn reset(&mut self) {
+        // 让仍在运行的搜索尽快返回并归还预算
+        if let Some(search) = &self.search {
+            search.cancel.store(true, Ordering::Relaxed);
+        }
+        *self = Self {
+            last_started: self.last_started,
+            ..default()
+        };
+    }
+
+    /// 推进缓存：局面变化时重新开始，取回完成的搜索，间隔足够时开始下一层搜索
+    ///
+    /// `now`为游戏开始后经过的时间；调度器没有给出许可时等下一次调用
+    pub fn refresh(
+        &mut self,
+        board: &Board,
+        player: PlayerColor,
+        now: Duration,
+        scheduler: &AiScheduler,
+        eval: &Arc<EvalConfig>,
+    ) {
+        if self.position != Some((*board, player)) {
+            self.reset();
+            self.position = Some((*board, player));
+        }
+
+        if let Some(search) = self.search.as_mut() {
+            if search.cancel.load(Ordering::Relaxed) {
+                self.search = None;
+            } else if let Some(result) = future::block_on(future::poll_once(&mut search.task)) {
+                let depth = search.depth;
+                self.search = None;
+                if let Some(position) = result {
+                    self.best = Some((position, depth));
+                }
+            }
+            return;
+        }
+
+        let depth = match self.depth() {
+            None => HINT_MIN_DEPTH,
+            Some(depth) if depth < HINT_MAX_DEPTH => depth + 1,
+            Some(_) => return,
+        };
+        let refresh = Duration::from_secs_f32(HINT_REFRESH_SECS);
+        if self
+            .last_started
+            .is_some_and(|started| now.saturating_sub(started) < refresh)
+        {
+            return;
+        }
+        let Some(permit) = scheduler.request(SearchPriority::Background) else {
+            return;
+        };
+
+        let (board, eval) = (*board, eval.clone());
+        let cancel = permit.cancel_flag();
+        let search_cancel = cancel.clone();
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let _permit = permit;
+            find_best_move_with_table(
+                &board,
+                depth,
+                player,
+                eval.as_ref(),
+                None,
+                Some(&search_cancel),
+            )
+            .best_move
+            .map(|best| best.position)
+        });
+        self.search = Some(HintSearch {
+            depth,
+            task,
+            cancel,
+        });
+        self.last_started = Some(now);
+    }
+}
+
+/// 是否可以为当前一方提供最佳走法提示：本机玩家的回合、不在沙盒中、辅助等级和对局规则都允许
+pub fn hints_available(
+    player: PlayerColor,
+    seats: &PlayerSeats,
+    sandbox: &SandboxMode,
+    preferences: &Preferences,
+    match_rules: &ActiveMatchRules,
+) -> bool {
+    seats.source(player).is_human()
+        && !sandbox.0
+        && preferences
+            .assist
+            .allowed(match_rules.allowed())
+            .best_move_hint
+}
+
+/// 轮到玩家且可以提示时在后台刷新最佳走法，否则丢弃缓存
+pub fn refresh_hint_cache(
+    board_query: Query<&Board>,
+    current_player: Res<CurrentPlayer>,
+    seats: Res<PlayerSeats>,
+    sandbox: Res<SandboxMode>,
+    preferences: Res<Preferences>,
+    match_rules: Res<ActiveMatchRules>,
+    scheduler: Res<AiScheduler>,
+    eval: Res<ActiveEvalConfig>,
+    time: Res<Time>,
+    mut cache: ResMut<HintCache>,
+) {
+    let player = current_player.0;
+    let board = board_query.single().ok().filter(|board| {
+        hints_available(player, &seats, &sandbox, &preferences, &match_rules)
+            && board.get_valid_moves(player) != 0
+    });
+    match board {
+        Some(board) => cache.refresh(board, player, time.elapsed(), &scheduler, &eval.0),
+        None if cache.position.is_some() => cache.reset(),
+        None => {}
+    }
+}
@@ -0,0 +1,214 @@
+// 辅助模式 - 帮助玩家避免明显的失误
+//
+// 辅助等级与AI难度无关，决定棋盘上给出多少帮助：不提示、显示可落子位置、
+// 再标注每步翻转的棋子数，或者允许使用让角提醒、教练点评、比分预测和最佳走法提示等基于局面评估的提示。
+// 最低的硬核等级还在对局中隐藏子数，用于练习读盘。
+// 辅助模式下，玩家选择的走法如果会让对手下一步占到角，
+// 会先弹出确认对话框，而不是直接落子。
+// 联机对局中各项辅助功能是否可用由握手时收到的对局规则决定，本地对局不受限制
+
+pub mod hint;
+
+pub use hint::*;
+
+use crate::game::{Board, PlayerColor, CORNER_MASK};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 辅助等级，每一级包含前一级的帮助
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AssistLevel {
+    /// 硬核：不显示可落子位置，终局前隐藏子数和阶段信息
+    Hardcore,
+    /// 不显示可落子位置
+    Off,
+    /// 显示可落子位置
+    LegalMoves,
+    /// 在可落子位置上标注翻转的棋子数
+    FlipCounts,
+    /// 允许使用基于局面评估的提示
+    #[default]
+    Hints,
+}
+
+impl AssistLevel {
+    /// 循环切换到下一个等级
+    pub fn next(self) -> Self {
+        match self {
+            Self::Hardcore => Self::Off,
+            Self::Off => Self::LegalMoves,
+            Self::LegalMoves => Self::FlipCounts,
+            Self::FlipCounts => Self::Hints,
+            Self::Hints => Self::Hardcore,
+        }
+    }
+
+    /// 该等级允许的辅助功能
+    pub fn rules(self) -> MatchRules {
+        let hints = self >= Self::Hints;
+        MatchRules {
+            legal_move_hints: self >= Self::LegalMoves,
+            corner_warning: hints,
+            coach: hints,
+            score_projection: hints,
+            best_move_hint: hints,
+        }
+    }
+
+    /// 是否在可落子位置上标注翻转的棋子数
+    pub fn shows_flip_counts(self) -> bool {
+        self >= Self::FlipCounts
+    }
+
+    /// 是否在终局前隐藏子数
+    pub fn hides_score(self) -> bool {
+        self == Self::Hardcore
+    }
+}
+
+/// 辅助模式相关的偏好设置
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct AssistPreferences {
+    /// 辅助等级
+    pub level: AssistLevel,
+    /// 是否开启辅助模式
+    pub enabled: bool,
+    /// 辅助模式下是否在让出角位前提醒
+    pub corner_warning: bool,
+    /// 是否开启教练模式，每走一步后在棋盘下方给出点评（与辅助模式无关）
+    pub coach: bool,
+}
+
+impl Default for AssistPreferences {
+    fn default() -> Self {
+        Self {
+            level: AssistLevel::default(),
+            enabled: false,
+            corner_warning: true,
+            coach: false,
+        }
+    }
+}
+
+impl AssistPreferences {
+    /// 是否需要检查让出角位
+    pub fn warns_about_corners(&self) -> bool {
+        self.enabled && self.corner_warning
+    }
+
+    /// 对局规则和辅助等级都允许的辅助功能
+    pub fn allowed(&self, rules: MatchRules) -> MatchRules {
+        rules.intersect(self.level.rules())
+    }
+
+    /// 按对局规则和辅助等级关闭不允许使用的辅助功能后的设置，不修改保存的偏好
+    pub fn permitted(&self, rules: MatchRules) -> Self {
+        let rules = self.allowed(rules);
+        Self {
+            level: self.level,
+            enabled: self.enabled,
+            corner_warning: self.corner_warning && rules.corner_warning,
+            coach: self.coach && rules.coach,
+        }
+    }
+}
+
+/// 对局中允许使用的辅助功能
+///
+/// 联机对局的规则由服务器在大厅建局时确定，握手时随Welcome下发，
+/// 客户端的辅助系统据此关闭会泄露局面评估的功能
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct MatchRules {
+    /// 显示可落子位置
+    pub legal_move_hints: bool,
+    /// 辅助模式的让出角位提醒
+    pub corner_warning: bool,
+    /// 教练模式的点评
+    pub coach: bool,
+    /// 残局比分预测
+    pub score_projection: bool,
+    /// 按提示键显示最佳走法
+    pub best_move_hint: bool,
+}
+
+impl MatchRules {
+    /// 不限制任何辅助功能，本地对局使用
+    pub const UNRESTRICTED: Self = Self {
+        legal_move_hints: true,
+        corner_warning: true,
+        coach: true,
+        score_projection: true,
+        best_move_hint: true,
+    };
+
+    /// 两组规则都允许的辅助功能
+    pub fn intersect(self, other: Self) -> Self {
+        Self {
+            legal_move_hints: self.legal_move_hints && other.legal_move_hints,
+            corner_warning: self.corner_warning && other.corner_warning,
+            coach: self.coach && other.coach,
+            score_projection: self.score_projection && other.score_projection,
+            best_move_hint: self.best_move_hint && other.best_move_hint,
+        }
+    }
+}
+
+impl Default for MatchRules {
+    /// 联机对局的默认规则：只保留可落子提示，关闭所有基于局面评估的辅助
+    fn default() -> Self {
+        Self {
+            legal_move_hints: true,
+            corner_warning: false,
+            coach: false,
+            score_projection: false,
+            best_move_hint: false,
+        }
+    }
+}
+
+/// 当前对局生效的对局规则，本地对局为None
+///
+/// 联机传输层在握手完成后写入服务器下发的规则，离开联机对局时恢复为None
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveMatchRules(pub Option<MatchRules>);
+
+impl ActiveMatchRules {
+    /// 当前允许使用的辅助功能
+    pub fn allowed(&self) -> MatchRules {
+        self.0.unwrap_or(MatchRules::UNRESTRICTED)
+    }
+}
+
+/// 等待玩家确认的走法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingCornerWarning {
+    /// 玩家选择的落子位置
+    pub position: u8,
+    /// 落子后对手可以占据的角
+    pub corner: u8,
+    /// 可以占角的对手
+    pub opponent: PlayerColor,
+}
+
+/// 让出角位提醒状态，pending不为None时显示确认对话框并暂停棋盘输入
+#[derive(Resource, Debug, Default)]
+pub struct CornerWarningState {
+    pub pending: Option<PendingCornerWarning>,
+}
+
+/// 一步搜索：检查落子后对手能否立即占据落子前还占不到的角，返回第一个新让出的角
+///
+/// 落子前对手就能占的角不算这步让出的，不再提醒；调用方需保证走法合法
+pub fn corner_conceded(board: &Board, position: u8, player: PlayerColor) -> Option<u8> {
+    let mut after = *board;
+    if !after.make_move(position, player) {
+        return None;
+    }
+
+    let opponent = player.opposite();
+    let before = board.get_valid_moves(opponent) & CORNER_MASK;
+    let corners = after.get_valid_moves(opponent) & CORNER_MASK & !before;
+    (corners != 0).then(|| corners.trailing_zeros() as u8)
+}
@@ -0,0 +1,98 @@
+// 局面事件音效 - 从每步落子的结果中识别占角、占满一条边和即将全灭，播放对应的提示音
+//
+// 走棋命令应用后发送MoveOutcome，分类系统比较落子前后的棋盘，识别出的事件按重要性只取一个：
+// 即将全灭、占角、占满一条边。提示音在普通的落子和翻转音效之外播放，与落子一样带上落子的一方
+
+use super::{PlaySoundEvent, SoundType};
+use crate::{
+    ai::evaluation::GamePhase,
+    game::{Board, PlayerColor, CORNER_MASK},
+};
+use bevy::prelude::*;
+
+/// 四条边的位掩码：上、下、左、右
+const EDGE_MASKS: [u64; 4] = [
+    0x0000_0000_0000_00FF,
+    0xFF00_0000_0000_0000,
+    0x0101_0101_0101_0101,
+    0x8080_8080_8080_8080,
+];
+
+/// 对手的棋子减少到不超过该数时提示即将全灭
+pub const WIPEOUT_THREAT_DISCS: u32 = 3;
+
+/// 一步落子的结果
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveOutcome {
+    /// 落子的一方
+    pub player: PlayerColor,
+    /// 落子位置
+    pub position: u8,
+    /// 落子前的棋盘
+    pub before: Board,
+    /// 落子后的棋盘
+    pub after: Board,
+}
+
+/// 值得用提示音强调的局面事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEventCue {
+    /// 对手的棋子减少到只剩几枚
+    WipeoutThreat,
+    /// 占到一个角
+    CornerCaptured,
+    /// 一条边被占满
+    EdgeCompleted,
+}
+
+impl GameEventCue {
+    /// 事件对应的音效
+    pub fn sound_type(self) -> SoundType {
+        match self {
+            Self::WipeoutThreat => SoundType::WipeoutThreat,
+            Self::CornerCaptured => SoundType::CornerCapture,
+            Self::EdgeCompleted => SoundType::EdgeComplete,
+        }
+    }
+}
+
+impl MoveOutcome {
+    /// 这一步引发的最重要的事件，没有值得强调的事件时返回None
+    ///
+    /// 开局阶段双方子数都很少，不提示即将全灭；只在对手的子数刚降到阈值以下时提示一次
+    pub fn classify(&self) -> Option<GameEventCue> {
+        let opponent = self.player.opposite();
+        let remaining = self.after.count_pieces(opponent);
+        if remaining > 0
+            && remaining <= WIPEOUT_THREAT_DISCS
+            && self.before.count_pieces(opponent) > WIPEOUT_THREAT_DISCS
+            && GamePhase::of_board(&self.after) != GamePhase::Opening
+        {
+            return Some(GameEventCue::WipeoutThreat);
+        }
+        if CORNER_MASK & (1u64 << self.position) != 0 {
+            return Some(GameEventCue::CornerCaptured);
+        }
+
+        let full = |board: &Board, edge: u64| board.get_empty_squares() & edge == 0;
+        EDGE_MASKS
+            .iter()
+            .any(|&edge| full(&self.after, edge) && !full(&self.before, edge))
+            .then_some(GameEventCue::EdgeCompleted)
+    }
+}
+
+/// 局面事件分类：为每步落子识别出的事件播放提示音
+pub fn classify_game_events(
+    mut outcomes: EventReader<MoveOutcome>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+) {
+    for outcome in outcomes.read() {
+        if let Some(cue) = outcome.classify() {
+            sound_events.write(PlaySoundEvent {
+                sound_type: cue.sound_type(),
+                actor: Some(outcome.player),
+            });
+        }
+    }
+}
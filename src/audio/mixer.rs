@@ -0,0 +1,232 @@
+// 音频混音 - 限制同时播放的音效数，胜负音效播放时压低其他音效，并复用播放实体
+//
+// 一次落子会在同一帧触发落子、翻转等多个音效。混音层把播放请求分配给数量固定的声道实体：
+// 同一帧内相同的音效只播放一次；声道播放完毕后替换AudioPlayer重新播放，不再为每个音效生成新实体；
+// 所有声道都在播放时丢弃普通音效，胜负音效则抢占最早开始的普通音效声道。
+// 胜负音效播放期间其他音效的音量降低，胜负音效结束后恢复。
+// AI的落子音效升调播放，玩家不看屏幕也能听出是谁落的子
+
+use super::{AudioAssets, AudioSettings, PlaySoundEvent, SoundType};
+use crate::ai::AiPlayer;
+use bevy::{audio::Volume, prelude::*};
+
+/// 同时播放的音效上限
+pub const MAX_VOICES: usize = 4;
+
+/// 胜负音效播放期间其他音效的音量比例
+pub const DUCK_FACTOR: f32 = 0.3;
+
+/// AI落子和翻转音效的播放速度倍数，大于1时音调升高
+pub const AI_PLACEMENT_SPEED: f32 = 1.25;
+
+/// 一个可复用的播放声道
+#[derive(Component, Debug)]
+pub struct AudioVoice {
+    /// 当前音效是否为胜负音效
+    pub sting: bool,
+    /// 开始播放的时间（秒），用于选择被抢占的声道
+    pub started_at: f32,
+}
+
+/// 分配声道时使用的声道快照
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VoiceSlot {
+    pub sting: bool,
+    pub started_at: f32,
+    /// 是否正在播放（或已分配、等待开始播放）
+    pub busy: bool,
+}
+
+/// 一个播放请求的声道分配结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceChoice {
+    /// 复用空闲的声道
+    Reuse(usize),
+    /// 声道数未达上限，新建声道
+    Spawn,
+    /// 停止并抢占正在播放的声道
+    Steal(usize),
+    /// 没有可用的声道，丢弃该音效
+    Drop,
+}
+
+/// 为一个播放请求选择声道
+///
+/// 优先复用空闲声道，其次新建声道；声道已满时只有胜负音效可以抢占，
+/// 优先抢占最早开始的普通音效，全部是胜负音效时抢占最早开始的一个
+pub fn choose_voice(slots: &[VoiceSlot], sting: bool) -> VoiceChoice {
+    if let Some(index) = slots.iter().position(|slot| !slot.busy) {
+        return VoiceChoice::Reuse(index);
+    }
+    if slots.len() < MAX_VOICES {
+        return VoiceChoice::Spawn;
+    }
+    if !sting {
+        return VoiceChoice::Drop;
+    }
+
+    let oldest = |only_sfx: bool| {
+        slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| !only_sfx || !slot.sting)
+            .min_by(|(_, a), (_, b)| a.started_at.total_cmp(&b.started_at))
+            .map(|(index, _)| index)
+    };
+    oldest(true)
+        .or_else(|| oldest(false))
+        .map_or(VoiceChoice::Drop, VoiceChoice::Steal)
+}
+
+impl SoundType {
+    /// 是否为胜负音效
+    pub fn is_sting(&self) -> bool {
+        matches!(self, Self::Victory | Self::Defeat)
+    }
+
+    /// 是否为落子时播放的音效
+    pub fn is_placement(&self) -> bool {
+        matches!(self, Self::PiecePlace | Self::PieceFlip)
+    }
+}
+
+/// 音效的播放速度，AI的落子音效在原速度上升调
+pub fn voice_speed(sound_type: SoundType, by_ai: bool) -> f32 {
+    if by_ai && sound_type.is_placement() {
+        sound_type.playback_speed() * AI_PLACEMENT_SPEED
+    } else {
+        sound_type.playback_speed()
+    }
+}
+
+/// 普通音效的音量
+fn sfx_volume(audio_settings: &AudioSettings, ducked: bool) -> f32 {
+    if ducked {
+        audio_settings.volume * DUCK_FACTOR
+    } else {
+        audio_settings.volume
+    }
+}
+
+pub fn play_sound_system(
+    mut commands: Commands,
+    mut sound_events: EventReader<PlaySoundEvent>,
+    audio_assets: Res<AudioAssets>,
+    audio_settings: Res<AudioSettings>,
+    audio_sources: Res<Assets<AudioSource>>,
+    time: Res<Time>,
+    ai_query: Query<&AiPlayer>,
+    mut voice_query: Query<(Entity, &mut AudioVoice, Option<&AudioSink>)>,
+) {
+    if !audio_settings.enabled {
+        sound_events.clear();
+        return;
+    }
+
+    // 同一帧内相同的音效只播放一次，胜负音效先分配，使同一帧的其他音效也被压低
+    let mut requests: Vec<(SoundType, bool)> = Vec::new();
+    for event in sound_events.read() {
+        let by_ai = event
+            .actor
+            .is_some_and(|actor| ai_query.iter().any(|ai_player| ai_player.color == actor));
+        let request = (event.sound_type, by_ai);
+        if !requests.contains(&request) {
+            requests.push(request);
+        }
+    }
+    if requests.is_empty() {
+        return;
+    }
+    requests.sort_by_key(|(sound_type, _)| !sound_type.is_sting());
+
+    let mut entities = Vec::new();
+    let mut slots = Vec::new();
+    for (entity, voice, sink) in voice_query.iter() {
+        entities.push(Some(entity));
+        slots.push(VoiceSlot {
+            sting: voice.sting,
+            started_at: voice.started_at,
+            // 还没有AudioSink的声道刚分配了音效，等待开始播放
+            busy: sink.is_none_or(|sink| !sink.empty()),
+        });
+    }
+
+    let now = time.elapsed_secs();
+    for (sound_type, by_ai) in requests {
+        let audio_source = audio_assets.handle(sound_type);
+
+        // 音效在后台加载，尚未加载完成时直接跳过，避免加载完成后才延迟播放
+        if !audio_sources.contains(audio_source) {
+            continue;
+        }
+
+        let sting = sound_type.is_sting();
+        let ducked = slots.iter().any(|slot| slot.sting && slot.busy);
+        let volume = if sting {
+            audio_settings.volume
+        } else {
+            sfx_volume(&audio_settings, ducked)
+        };
+        let player = (
+            AudioPlayer::new(audio_source.clone()),
+            PlaybackSettings::ONCE
+                .with_volume(Volume::Linear(volume))
+                .with_speed(voice_speed(sound_type, by_ai)),
+        );
+        let voice = AudioVoice {
+            sting,
+            started_at: now,
+        };
+        let slot = VoiceSlot {
+            sting,
+            started_at: now,
+            busy: true,
+        };
+
+        let index = match choose_voice(&slots, sting) {
+            VoiceChoice::Drop => continue,
+            VoiceChoice::Spawn => {
+                commands.spawn((player, voice));
+                entities.push(None);
+                slots.push(slot);
+                continue;
+            }
+            VoiceChoice::Reuse(index) | VoiceChoice::Steal(index) => index,
+        };
+
+        slots[index] = slot;
+        let Some(entity) = entities[index] else {
+            // 本帧新建的声道还没有生成实体，不会被复用或抢占
+            continue;
+        };
+        let Ok((_, mut existing, sink)) = voice_query.get_mut(entity) else {
+            continue;
+        };
+        if let Some(sink) = sink {
+            sink.stop();
+        }
+        *existing = voice;
+        // 移除旧的AudioSink后，Bevy会按新的AudioPlayer重新开始播放
+        commands.entity(entity).remove::<AudioSink>().insert(player);
+    }
+}
+
+/// 胜负音效播放期间压低正在播放的普通音效，结束后恢复
+pub fn update_audio_ducking(
+    audio_settings: Res<AudioSettings>,
+    mut voice_query: Query<(&AudioVoice, &mut AudioSink)>,
+) {
+    let ducked = voice_query
+        .iter()
+        .any(|(voice, sink)| voice.sting && !sink.empty());
+    let volume = sfx_volume(&audio_settings, ducked);
+
+    for (voice, mut sink) in voice_query.iter_mut() {
+        if voice.sting || sink.empty() {
+            continue;
+        }
+        if (sink.volume().to_linear() - volume).abs() > f32::EPSILON {
+            sink.set_volume(Volume::Linear(volume));
+        }
+    }
+}
@@ -1,3 +1,14 @@
+pub mod cues;
+pub mod mixer;
+
+pub use cues::*;
+pub use mixer::*;
+
+use crate::{
+    game::PlayerColor,
+    input::{InputAction, InputMap},
+    platform::PlatformProfile,
+};
 use bevy::prelude::*;
 
 #[derive(Resource)]
@@ -7,12 +18,17 @@ pub struct AudioAssets {
     pub victory: Handle<AudioSource>,
     pub defeat: Handle<AudioSource>,
     pub invalid_move: Handle<AudioSource>,
+    pub pass: Handle<AudioSource>,
+    pub low_time: Handle<AudioSource>,
+    pub corner_capture: Handle<AudioSource>,
+    pub edge_complete: Handle<AudioSource>,
+    pub wipeout_threat: Handle<AudioSource>,
 }
 
 #[derive(Resource)]
 pub struct AudioSettings {
     pub enabled: bool,
-    #[allow(dead_code)]
+    /// 主音量，胜负音效按此音量播放，其他音效播放期间按比例降低
     pub volume: f32,
 }
 
@@ -28,57 +44,153 @@ impl Default for AudioSettings {
 #[derive(Event)]
 pub struct PlaySoundEvent {
     pub sound_type: SoundType,
+    /// 落子的一方，用于区分玩家和AI的落子音效；与落子无关的音效为None
+    pub actor: Option<PlayerColor>,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SoundType {
     PiecePlace,
     PieceFlip,
     Victory,
     Defeat,
     InvalidMove,
+    /// 跳过回合，降调播放落子音效
+    Pass,
+    /// 计时对局中玩家时间不足，升调播放无效落子音效
+    LowTime,
+    /// 占角，降调播放翻转音效
+    CornerCapture,
+    /// 一条边被占满，升调播放翻转音效
+    EdgeComplete,
+    /// 一方只剩几枚棋子，降调播放无效落子音效
+    WipeoutThreat,
 }
 
-pub fn load_audio_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let audio_assets = AudioAssets {
-        piece_place: asset_server.load("sounds/piece_place.ogg"),
-        piece_flip: asset_server.load("sounds/piece_flip.ogg"),
-        victory: asset_server.load("sounds/victory.ogg"),
-        defeat: asset_server.load("sounds/defeat.ogg"),
-        invalid_move: asset_server.load("sounds/invalid_move.ogg"),
-    };
+impl SoundType {
+    /// 全部音效类型
+    pub const ALL: [SoundType; 10] = [
+        SoundType::PiecePlace,
+        SoundType::PieceFlip,
+        SoundType::Victory,
+        SoundType::Defeat,
+        SoundType::InvalidMove,
+        SoundType::Pass,
+        SoundType::LowTime,
+        SoundType::CornerCapture,
+        SoundType::EdgeComplete,
+        SoundType::WipeoutThreat,
+    ];
 
-    commands.insert_resource(audio_assets);
+    /// 音效的完整品质路径，精简档次的路径见PlatformProfile::asset_path
+    pub fn asset_path(self) -> &'static str {
+        match self {
+            Self::PiecePlace => "sounds/piece_place.ogg",
+            Self::PieceFlip => "sounds/piece_flip.ogg",
+            Self::Victory => "sounds/victory.ogg",
+            Self::Defeat => "sounds/defeat.ogg",
+            Self::InvalidMove => "sounds/invalid_move.ogg",
+            Self::Pass => "sounds/piece_place.ogg",
+            Self::LowTime => "sounds/invalid_move.ogg",
+            Self::CornerCapture => "sounds/piece_flip.ogg",
+            Self::EdgeComplete => "sounds/piece_flip.ogg",
+            Self::WipeoutThreat => "sounds/invalid_move.ogg",
+        }
+    }
+
+    /// 播放速度，小于1时音调降低、大于1时音调升高，用于与共用同一文件的音效区分
+    pub fn playback_speed(self) -> f32 {
+        match self {
+            Self::Pass => 0.7,
+            Self::LowTime => 1.6,
+            Self::CornerCapture => 0.75,
+            Self::EdgeComplete => 1.4,
+            Self::WipeoutThreat => 0.6,
+            _ => 1.0,
+        }
+    }
 }
 
-pub fn play_sound_system(
-    mut commands: Commands,
-    mut sound_events: EventReader<PlaySoundEvent>,
-    audio_assets: Res<AudioAssets>,
-    audio_settings: Res<AudioSettings>,
-) {
-    if !audio_settings.enabled {
-        return;
+impl AudioAssets {
+    /// 全部音效句柄
+    pub fn handles(&self) -> [&Handle<AudioSource>; 10] {
+        [
+            &self.piece_place,
+            &self.piece_flip,
+            &self.victory,
+            &self.defeat,
+            &self.invalid_move,
+            &self.pass,
+            &self.low_time,
+            &self.corner_capture,
+            &self.edge_complete,
+            &self.wipeout_threat,
+        ]
     }
 
-    for event in sound_events.read() {
-        let audio_source = match event.sound_type {
-            SoundType::PiecePlace => &audio_assets.piece_place,
-            SoundType::PieceFlip => &audio_assets.piece_flip,
-            SoundType::Victory => &audio_assets.victory,
-            SoundType::Defeat => &audio_assets.defeat,
-            SoundType::InvalidMove => &audio_assets.invalid_move,
-        };
+    /// 音效类型对应的句柄
+    pub fn handle(&self, sound_type: SoundType) -> &Handle<AudioSource> {
+        match sound_type {
+            SoundType::PiecePlace => &self.piece_place,
+            SoundType::PieceFlip => &self.piece_flip,
+            SoundType::Victory => &self.victory,
+            SoundType::Defeat => &self.defeat,
+            SoundType::InvalidMove => &self.invalid_move,
+            SoundType::Pass => &self.pass,
+            SoundType::LowTime => &self.low_time,
+            SoundType::CornerCapture => &self.corner_capture,
+            SoundType::EdgeComplete => &self.edge_complete,
+            SoundType::WipeoutThreat => &self.wipeout_threat,
+        }
+    }
 
-        commands.spawn(AudioPlayer::new(audio_source.clone()));
+    /// 音效类型对应的可变句柄
+    pub fn handle_mut(&mut self, sound_type: SoundType) -> &mut Handle<AudioSource> {
+        match sound_type {
+            SoundType::PiecePlace => &mut self.piece_place,
+            SoundType::PieceFlip => &mut self.piece_flip,
+            SoundType::Victory => &mut self.victory,
+            SoundType::Defeat => &mut self.defeat,
+            SoundType::InvalidMove => &mut self.invalid_move,
+            SoundType::Pass => &mut self.pass,
+            SoundType::LowTime => &mut self.low_time,
+            SoundType::CornerCapture => &mut self.corner_capture,
+            SoundType::EdgeComplete => &mut self.edge_complete,
+            SoundType::WipeoutThreat => &mut self.wipeout_threat,
+        }
     }
 }
 
+pub fn load_audio_assets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    profile: Res<PlatformProfile>,
+) {
+    let load =
+        |sound_type: SoundType| asset_server.load(profile.asset_path(sound_type.asset_path()));
+    let audio_assets = AudioAssets {
+        piece_place: load(SoundType::PiecePlace),
+        piece_flip: load(SoundType::PieceFlip),
+        victory: load(SoundType::Victory),
+        defeat: load(SoundType::Defeat),
+        invalid_move: load(SoundType::InvalidMove),
+        pass: load(SoundType::Pass),
+        low_time: load(SoundType::LowTime),
+        corner_capture: load(SoundType::CornerCapture),
+        edge_complete: load(SoundType::EdgeComplete),
+        wipeout_threat: load(SoundType::WipeoutThreat),
+    };
+
+    commands.insert_resource(audio_assets);
+}
+
+/// 开关音效（默认M键）
 pub fn toggle_audio_system(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
     mut audio_settings: ResMut<AudioSettings>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::KeyM) {
+    if input_map.just_pressed(InputAction::ToggleSound, &keyboard_input) {
         audio_settings.enabled = !audio_settings.enabled;
     }
 }
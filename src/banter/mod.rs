@@ -0,0 +1,179 @@
+// AI闲聊 - 对局中AI对手按事件说一句轻松的话，显示在AI头像旁的气泡中
+//
+// 触发事件只看刚走的一步：占角、一次翻转很多棋子、AI思考了很久，按这个顺序取第一条。
+// 说完一句后有一段冷却时间，冷却期间的事件不再触发，避免气泡频繁弹出；设置中可以关闭
+
+use crate::{
+    ai::AiPlayer, game::MoveAction, localization::TextKey, preferences::Preferences,
+    record::GameRecord, ui::FlipChainEvent,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 四个角
+const CORNERS: [u8; 4] = [0, 7, 56, 63];
+
+/// 一步至少翻转这么多棋子时算作大翻转
+pub const BIG_FLIP: usize = 6;
+
+/// AI一步思考超过该时间时感叹一句
+pub const LONG_THINK: Duration = Duration::from_secs(4);
+
+/// 气泡的显示时间
+pub const BANTER_DISPLAY: Duration = Duration::from_secs(3);
+
+/// 两句话之间的最短间隔（从上一句出现时算起）
+pub const BANTER_COOLDOWN: Duration = Duration::from_secs(10);
+
+/// AI说的一句话
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Banter {
+    /// 玩家占到了角
+    PlayerCorner,
+    /// AI占到了角
+    AiCorner,
+    /// 玩家一步翻转了很多棋子
+    PlayerBigFlip,
+    /// AI一步翻转了很多棋子
+    AiBigFlip,
+    /// AI这一步想了很久
+    LongThink,
+}
+
+impl Banter {
+    /// 气泡中的文本键
+    pub fn text_key(self) -> TextKey {
+        match self {
+            Self::PlayerCorner => TextKey::BanterPlayerCorner,
+            Self::AiCorner => TextKey::BanterAiCorner,
+            Self::PlayerBigFlip => TextKey::BanterPlayerBigFlip,
+            Self::AiBigFlip => TextKey::BanterAiBigFlip,
+            Self::LongThink => TextKey::BanterLongThink,
+        }
+    }
+}
+
+/// 一步落子引发的闲聊，没有值得一说的事件时返回None
+///
+/// # 参数
+/// * `position` - 落子位置
+/// * `flipped` - 翻转的棋子数
+/// * `by_ai` - 是否是AI的落子
+/// * `think_time` - 这一步的思考时间
+pub fn banter_for_move(
+    position: u8,
+    flipped: usize,
+    by_ai: bool,
+    think_time: Duration,
+) -> Option<Banter> {
+    if CORNERS.contains(&position) {
+        return Some(if by_ai {
+            Banter::AiCorner
+        } else {
+            Banter::PlayerCorner
+        });
+    }
+    if flipped >= BIG_FLIP {
+        return Some(if by_ai {
+            Banter::AiBigFlip
+        } else {
+            Banter::PlayerBigFlip
+        });
+    }
+    (by_ai && think_time >= LONG_THINK).then_some(Banter::LongThink)
+}
+
+/// 闲聊相关的偏好设置
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct BanterPreferences {
+    /// 是否显示AI的闲聊
+    pub enabled: bool,
+}
+
+impl Default for BanterPreferences {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// 当前显示的一句话和它出现的时间
+#[derive(Resource, Debug, Default)]
+pub struct BanterState {
+    line: Option<Banter>,
+    said_at: Option<Duration>,
+}
+
+impl BanterState {
+    /// 正在显示的一句话
+    pub fn line(&self) -> Option<Banter> {
+        self.line
+    }
+
+    /// 说一句话，冷却时间内不说并返回false
+    pub fn say(&mut self, banter: Banter, now: Duration) -> bool {
+        if self
+            .said_at
+            .is_some_and(|said_at| now.saturating_sub(said_at) < BANTER_COOLDOWN)
+        {
+            return false;
+        }
+        self.line = Some(banter);
+        self.said_at = Some(now);
+        true
+    }
+
+    /// 显示时间过后收起气泡
+    pub fn expire(&mut self, now: Duration) {
+        if self
+            .said_at
+            .is_some_and(|said_at| now.saturating_sub(said_at) >= BANTER_DISPLAY)
+        {
+            self.line = None;
+        }
+    }
+
+    /// 立即收起气泡，不影响冷却
+    pub fn hide(&mut self) {
+        self.line = None;
+    }
+}
+
+/// 落子后按事件触发闲聊，需要在思考时间记录之后运行；没有AI的对局（沙盒、联机）不触发
+pub fn trigger_banter(
+    mut flip_events: EventReader<FlipChainEvent>,
+    ai_query: Query<&AiPlayer>,
+    record: Res<GameRecord>,
+    preferences: Res<Preferences>,
+    mut banter: ResMut<BanterState>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed();
+    let ai_color = ai_query.single().ok().map(|ai_player| ai_player.color);
+    if !preferences.banter.enabled || ai_color.is_none() {
+        flip_events.clear();
+        if banter.line.is_some() {
+            banter.hide();
+        }
+        return;
+    }
+
+    for event in flip_events.read() {
+        let think_time = record
+            .moves
+            .iter()
+            .rev()
+            .find(|recorded| recorded.action == MoveAction::Place(event.position))
+            .map_or(Duration::ZERO, |recorded| recorded.think_time);
+        let flipped = event.rays.iter().map(Vec::len).sum();
+        let by_ai = Some(event.player) == ai_color;
+        if let Some(line) = banter_for_move(event.position, flipped, by_ai, think_time) {
+            banter.say(line, now);
+        }
+    }
+
+    if banter.line.is_some() {
+        banter.expire(now);
+    }
+}
@@ -0,0 +1,333 @@
+// 启动加载 - 加载画面立即显示，字体和音效在后台加载并逐项显示进度
+//
+// 加载画面的进度条和资源列表只使用Bevy内置字体，不等待任何资源即可显示。
+// 中文字体是进入菜单的前提，加载完成（或失败）后立即离开加载画面；
+// 音效不阻塞启动，继续在后台加载，屏幕底部的小提示显示加载进度，加载完成前的播放请求会被跳过。
+// 这样网络较慢的Web版本首屏不再被体积很大的中文字体拖慢。
+// 全部资源结束加载后检查一次加载失败的文件（见validate_boot_assets），
+// 记录在MissingAssets中，由界面列出缺失的文件（见asset_warning_ui），方便检查打包是否完整
+
+use crate::{
+    audio::AudioAssets,
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    ui::ToDelete,
+};
+use bevy::{
+    asset::{LoadState, UntypedAssetId},
+    prelude::*,
+};
+
+/// 进度条的宽度
+const PROGRESS_BAR_WIDTH: f32 = 240.0;
+
+/// 单个资源的加载状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootStatus {
+    Loading,
+    Loaded,
+    Failed,
+}
+
+impl BootStatus {
+    /// 资源列表中显示的状态（内置字体只包含ASCII字符）
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Loading => "...",
+            Self::Loaded => "ok",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// 资源服务器中的加载状态对应的状态
+    pub fn from_load_state(load_state: &LoadState) -> Self {
+        match load_state {
+            LoadState::Loaded => Self::Loaded,
+            LoadState::Failed(_) => Self::Failed,
+            LoadState::NotLoaded | LoadState::Loading => Self::Loading,
+        }
+    }
+
+    /// 本地化的状态文本键，用于使用游戏字体的界面
+    pub fn text_key(self) -> TextKey {
+        match self {
+            Self::Loading => TextKey::AssetLoading,
+            Self::Loaded => TextKey::AssetLoaded,
+            Self::Failed => TextKey::AssetFailed,
+        }
+    }
+}
+
+/// 启动时跟踪的资源
+#[derive(Debug, Clone)]
+pub struct BootAsset {
+    pub handle: UntypedHandle,
+    /// 是否需要加载完成后才能离开加载画面
+    pub required: bool,
+}
+
+impl BootAsset {
+    /// 资源文件名
+    pub fn name(&self) -> String {
+        self.handle
+            .path()
+            .and_then(|path| path.path().file_name())
+            .map_or_else(
+                || "?".to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            )
+    }
+
+    /// 资源在资源目录中的路径，例如"sounds/victory.ogg"
+    pub fn path(&self) -> String {
+        self.handle.path().map_or_else(
+            || "?".to_string(),
+            |path| path.path().to_string_lossy().into_owned(),
+        )
+    }
+}
+
+/// 启动时跟踪的全部资源
+#[derive(Resource, Debug, Default)]
+pub struct BootAssets {
+    pub assets: Vec<BootAsset>,
+}
+
+impl BootAssets {
+    pub fn track(&mut self, handle: impl Into<UntypedHandle>, required: bool) {
+        self.assets.push(BootAsset {
+            handle: handle.into(),
+            required,
+        });
+    }
+
+    /// 用新的句柄替换正在跟踪的资源，例如精简资源加载失败后改为加载完整资源
+    pub fn replace(&mut self, old: UntypedAssetId, handle: UntypedHandle) {
+        for asset in self.assets.iter_mut() {
+            if asset.handle.id() == old {
+                asset.handle = handle.clone();
+            }
+        }
+    }
+
+    /// 查询资源的加载状态
+    pub fn status(asset: &BootAsset, asset_server: &AssetServer) -> BootStatus {
+        BootStatus::from_load_state(&asset_server.load_state(asset.handle.id()))
+    }
+
+    /// 已经结束加载（成功或失败）的资源数
+    pub fn finished(&self, asset_server: &AssetServer) -> usize {
+        self.assets
+            .iter()
+            .filter(|asset| Self::status(asset, asset_server) != BootStatus::Loading)
+            .count()
+    }
+
+    /// 加载失败的资源路径，共用同一文件的资源只列一次
+    pub fn failed_paths(&self, asset_server: &AssetServer) -> Vec<String> {
+        let mut paths: Vec<String> = self
+            .assets
+            .iter()
+            .filter(|asset| Self::status(asset, asset_server) == BootStatus::Failed)
+            .map(BootAsset::path)
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
+    /// 整体进度（0.0 - 1.0），按资源个数计算
+    pub fn progress(&self, asset_server: &AssetServer) -> f32 {
+        if self.assets.is_empty() {
+            return 1.0;
+        }
+        self.finished(asset_server) as f32 / self.assets.len() as f32
+    }
+
+    /// 必需的资源是否都已结束加载，失败的资源使用回退字体，不阻塞启动
+    pub fn required_ready(&self, asset_server: &AssetServer) -> bool {
+        self.assets
+            .iter()
+            .filter(|asset| asset.required)
+            .all(|asset| Self::status(asset, asset_server) != BootStatus::Loading)
+    }
+}
+
+/// 启动检查发现的缺失资源
+#[derive(Resource, Debug, Default)]
+pub struct MissingAssets {
+    /// 是否已经检查过，全部资源结束加载后检查一次
+    pub checked: bool,
+    /// 加载失败的资源路径
+    pub paths: Vec<String>,
+}
+
+/// 加载画面的进度条填充部分
+#[derive(Component)]
+pub struct BootProgressFill;
+
+/// 加载画面中一个资源的状态行，值为资源序号
+#[derive(Component)]
+pub struct BootAssetRow(pub usize);
+
+/// 离开加载画面后仍在加载的资源提示
+#[derive(Component)]
+pub struct BackgroundLoadingIndicator;
+
+/// 登记启动时要跟踪的资源，需要在字体和音效开始加载之后运行
+pub fn track_boot_assets(
+    font_assets: Res<FontAssets>,
+    audio_assets: Res<AudioAssets>,
+    mut boot_assets: ResMut<BootAssets>,
+) {
+    boot_assets.track(font_assets.chinese_font.clone(), true);
+    for sound in audio_assets.handles() {
+        boot_assets.track(sound.clone(), false);
+    }
+}
+
+/// 全部资源结束加载后记录加载失败的文件，需要在精简资源回退到完整资源之后运行，
+/// 精简资源加载失败时已经换成正在加载的完整资源，不会被当作缺失
+pub fn validate_boot_assets(
+    asset_server: Res<AssetServer>,
+    boot_assets: Res<BootAssets>,
+    mut missing_assets: ResMut<MissingAssets>,
+) {
+    if missing_assets.checked || boot_assets.finished(&asset_server) < boot_assets.assets.len() {
+        return;
+    }
+
+    let paths = boot_assets.failed_paths(&asset_server);
+    for path in &paths {
+        warn!("Asset {path} is missing or failed to load");
+    }
+    missing_assets.checked = true;
+    missing_assets.paths = paths;
+}
+
+/// 在加载画面中创建进度条和资源列表
+pub fn spawn_boot_progress(
+    parent: &mut ChildSpawnerCommands,
+    boot_assets: &BootAssets,
+    font: &Handle<Font>,
+) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(PROGRESS_BAR_WIDTH),
+                height: Val::Px(8.0),
+                margin: UiRect::vertical(Val::Px(16.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.2)),
+            BorderRadius::all(Val::Px(4.0)),
+        ))
+        .with_children(|bar| {
+            bar.spawn((
+                Node {
+                    width: Val::Percent(0.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(Color::WHITE),
+                BorderRadius::all(Val::Px(4.0)),
+                BootProgressFill,
+            ));
+        });
+
+    for (index, asset) in boot_assets.assets.iter().enumerate() {
+        parent.spawn((
+            Text::new(format!("{} {}", asset.name(), BootStatus::Loading.label())),
+            TextFont {
+                font: font.clone(),
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            BootAssetRow(index),
+        ));
+    }
+}
+
+/// 更新进度条和每个资源的状态
+pub fn update_boot_progress(
+    asset_server: Res<AssetServer>,
+    boot_assets: Res<BootAssets>,
+    mut fill_query: Query<&mut Node, With<BootProgressFill>>,
+    mut row_query: Query<(&mut Text, &BootAssetRow)>,
+) {
+    let progress = boot_assets.progress(&asset_server);
+    for mut node in fill_query.iter_mut() {
+        node.width = Val::Percent(progress * 100.0);
+    }
+
+    for (mut text, BootAssetRow(index)) in row_query.iter_mut() {
+        let Some(asset) = boot_assets.assets.get(*index) else {
+            continue;
+        };
+        let status = BootAssets::status(asset, &asset_server);
+        let label = format!("{} {}", asset.name(), status.label());
+        if text.0 != label {
+            text.0 = label;
+        }
+    }
+}
+
+/// 离开加载画面时，还有资源在加载则显示后台加载提示
+pub fn spawn_background_loading_indicator(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    boot_assets: Res<BootAssets>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    if boot_assets.finished(&asset_server) == boot_assets.assets.len() {
+        return;
+    }
+
+    commands.spawn((
+        // 底部居中，避开对局界面四角的面板
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            bottom: Val::Px(4.0),
+            ..default()
+        },
+        Text::default(),
+        TextKey::BackgroundLoading,
+        TextArgs::default(),
+        TextLayout::new_with_justify(JustifyText::Center),
+        TextFont {
+            font: get_font_for_language(&language_settings, &font_assets),
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+        LocalizedText,
+        BackgroundLoadingIndicator,
+    ));
+}
+
+/// 更新后台加载提示，全部加载完成后移除
+pub fn update_background_loading_indicator(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    boot_assets: Res<BootAssets>,
+    mut indicator_query: Query<(Entity, &mut TextArgs), With<BackgroundLoadingIndicator>>,
+) {
+    let Ok((entity, mut args)) = indicator_query.single_mut() else {
+        return;
+    };
+
+    let finished = boot_assets.finished(&asset_server);
+    let total = boot_assets.assets.len();
+    if finished == total {
+        commands.entity(entity).insert(ToDelete);
+        return;
+    }
+
+    args.set_if_neq(TextArgs(vec![
+        TextArg::Value(finished.to_string()),
+        TextArg::Value(total.to_string()),
+    ]));
+}
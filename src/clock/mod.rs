@@ -0,0 +1,236 @@
+// 对局计时 - 快棋、中速、休闲等计时预设，每步加秒，时间用完判负
+//
+// 计时规则在难度选择界面或设置面板中选择（RulesConfig::time_control），开始新对局时生效，
+// 对局中途修改不影响当前对局。轮到哪一方就扣哪一方的时间，暂停和开局自动走棋期间不计时；
+// 每落一子加上该规则的加秒，悔棋不退还时间。玩家的时间低于警告线时播放一次提示音，时钟闪烁；
+// 任一方时间用完时对局结束，对方获胜。沙盒和联机对局没有AI，不计时
+
+use crate::{
+    ai::AiPlayer,
+    audio::{PlaySoundEvent, SoundType},
+    game::{GameLog, MoveAction, PlayerColor},
+    localization::{TextArg, TextArgs, TextKey},
+    preferences::Preferences,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 剩余时间低于该值时提示并闪烁
+pub const LOW_TIME: Duration = Duration::from_secs(10);
+
+/// 计时规则
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeControl {
+    /// 不计时
+    #[default]
+    Unlimited,
+    /// 快棋：3分钟，每步加2秒
+    Blitz,
+    /// 中速：10分钟，每步加5秒
+    Rapid,
+    /// 休闲：30分钟，不加秒
+    Casual,
+}
+
+impl TimeControl {
+    /// 每方的初始时间，不计时时为None
+    pub fn base(self) -> Option<Duration> {
+        match self {
+            Self::Unlimited => None,
+            Self::Blitz => Some(Duration::from_secs(3 * 60)),
+            Self::Rapid => Some(Duration::from_secs(10 * 60)),
+            Self::Casual => Some(Duration::from_secs(30 * 60)),
+        }
+    }
+
+    /// 每落一子增加的时间
+    pub fn increment(self) -> Duration {
+        match self {
+            Self::Unlimited | Self::Casual => Duration::ZERO,
+            Self::Blitz => Duration::from_secs(2),
+            Self::Rapid => Duration::from_secs(5),
+        }
+    }
+
+    /// 是否计时
+    pub fn is_timed(self) -> bool {
+        self.base().is_some()
+    }
+
+    /// 显示的名称，计时规则带有"分钟+加秒"的写法
+    pub fn name_key(self) -> TextKey {
+        match self {
+            Self::Unlimited => TextKey::TimeControlUnlimited,
+            Self::Blitz => TextKey::TimeControlBlitz,
+            Self::Rapid => TextKey::TimeControlRapid,
+            Self::Casual => TextKey::TimeControlCasual,
+        }
+    }
+
+    /// 难度选择界面中计时按钮文本的参数
+    pub fn text_args(self) -> TextArgs {
+        TextArgs(vec![TextArg::Key(self.name_key())])
+    }
+
+    /// 依次切换的计时规则
+    pub fn next(self) -> Self {
+        match self {
+            Self::Unlimited => Self::Blitz,
+            Self::Blitz => Self::Rapid,
+            Self::Rapid => Self::Casual,
+            Self::Casual => Self::Unlimited,
+        }
+    }
+}
+
+/// 计时过程中发生的提示
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockAlert {
+    /// 一方的剩余时间刚刚低于警告线
+    LowTime(PlayerColor),
+    /// 一方的时间用完
+    Flagged(PlayerColor),
+}
+
+/// 当前对局双方的剩余时间
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct GameClock {
+    control: TimeControl,
+    /// 黑方和白方的剩余时间
+    remaining: [Duration; 2],
+    /// 是否已经提示过时间不足，每方每局只提示一次
+    warned: [bool; 2],
+    /// 时间用完的一方
+    flagged: Option<PlayerColor>,
+    /// 已经加过秒的命令数
+    counted: usize,
+}
+
+fn side(color: PlayerColor) -> usize {
+    match color {
+        PlayerColor::Black => 0,
+        PlayerColor::White => 1,
+    }
+}
+
+impl GameClock {
+    /// 按计时规则开始计时，不计时的规则下时钟不走
+    pub fn new(control: TimeControl) -> Self {
+        let base = control.base().unwrap_or_default();
+        Self {
+            control,
+            remaining: [base; 2],
+            ..default()
+        }
+    }
+
+    /// 本局的计时规则
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// 本局是否计时
+    pub fn is_timed(&self) -> bool {
+        self.control.is_timed()
+    }
+
+    /// 一方的剩余时间
+    pub fn remaining(&self, color: PlayerColor) -> Duration {
+        self.remaining[side(color)]
+    }
+
+    /// 一方的剩余时间是否低于警告线
+    pub fn is_low(&self, color: PlayerColor) -> bool {
+        self.is_timed() && self.remaining(color) <= LOW_TIME
+    }
+
+    /// 时间用完的一方
+    pub fn flagged(&self) -> Option<PlayerColor> {
+        self.flagged
+    }
+
+    /// 扣除走棋一方的时间，返回这次扣时引发的提示；不计时或已经有一方超时时不扣时
+    pub fn tick(&mut self, color: PlayerColor, elapsed: Duration) -> Option<ClockAlert> {
+        if !self.is_timed() || self.flagged.is_some() {
+            return None;
+        }
+        let index = side(color);
+        self.remaining[index] = self.remaining[index].saturating_sub(elapsed);
+        if self.remaining[index].is_zero() {
+            self.flagged = Some(color);
+            return Some(ClockAlert::Flagged(color));
+        }
+        if self.remaining[index] <= LOW_TIME && !self.warned[index] {
+            self.warned[index] = true;
+            return Some(ClockAlert::LowTime(color));
+        }
+        None
+    }
+
+    /// 一方落子后加秒
+    pub fn add_increment(&mut self, color: PlayerColor) {
+        if self.is_timed() && self.flagged.is_none() {
+            self.remaining[side(color)] += self.control.increment();
+        }
+    }
+}
+
+/// 时钟显示的文本，按整秒向上取整，只有时间真正用完时才显示0:00
+pub fn clock_text(remaining: Duration) -> String {
+    let secs = remaining.as_millis().div_ceil(1000);
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// 新对局开始时按计时规则重置时钟，落子后加秒，并扣除走棋一方的时间
+pub fn tick_game_clock(
+    log_query: Query<Ref<GameLog>>,
+    ai_query: Query<&AiPlayer>,
+    preferences: Res<Preferences>,
+    mut clock: ResMut<GameClock>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+    time: Res<Time>,
+) {
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+    let ai_color = ai_query.single().ok().map(|ai_player| ai_player.color);
+
+    // 新对局开始：没有AI的对局不计时
+    if log.is_added() {
+        let control = if ai_color.is_some() {
+            preferences.rules.time_control
+        } else {
+            TimeControl::Unlimited
+        };
+        *clock = GameClock::new(control);
+        clock.counted = log.commands().len();
+    }
+    if !clock.is_timed() {
+        return;
+    }
+
+    // 悔棋后从撤销到的位置继续计数，已经用掉的时间不退还
+    if clock.counted > log.commands().len() {
+        clock.counted = log.commands().len();
+    }
+    for command in log.commands().iter().skip(clock.counted) {
+        if matches!(command.action, MoveAction::Place(_)) {
+            clock.add_increment(command.player);
+        }
+    }
+    clock.counted = log.commands().len();
+
+    if log.is_game_over() {
+        return;
+    }
+    // 只提示玩家自己的时间不足，AI不需要提示音
+    if let Some(ClockAlert::LowTime(color)) = clock.tick(log.to_move(), time.delta()) {
+        if Some(color) != ai_color {
+            sound_events.write(PlaySoundEvent {
+                sound_type: SoundType::LowTime,
+                actor: None,
+            });
+        }
+    }
+}
@@ -0,0 +1,155 @@
+// 教练模式 - 玩家每走一步后，根据评估分数和局面特征给出一句简短点评
+//
+// 点评只看一步：用静态评估比较所有合法走法，再检查占角、X位、让角和行动力等特征，
+// 按重要程度取第一条。点评以文本键和参数的形式保存，由棋盘下方的提示栏显示
+
+use crate::{
+    ai::{evaluation::evaluate_board, AiPlayer},
+    assist::{corner_conceded, ActiveMatchRules},
+    game::{Board, GameLog, MoveAction, MoveCommand, PlayerColor},
+    localization::{TextArg, TextKey},
+    preferences::Preferences,
+};
+use bevy::prelude::*;
+
+/// 四个角和对应的X位
+const CORNER_X_SQUARES: [(u8, u8); 4] = [(0, 9), (7, 14), (56, 49), (63, 54)];
+
+/// 比最佳走法少多少评估分时提示有更好的走法
+pub const INACCURACY_MARGIN: i32 = 60;
+
+/// 对手剩余的走法不超过该数时视为限制了对手
+const FEW_REPLIES: u32 = 3;
+
+/// 一步棋的点评，按重要程度排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoachComment {
+    /// 占到了角
+    TookCorner,
+    /// 对手下一步可以占据该角
+    ConcedesCorner { corner: u8 },
+    /// 下在空角旁边的X位
+    RiskyXSquare,
+    /// 有评估明显更好的走法
+    BetterMove { best: u8 },
+    /// 对手可走的棋很少，且没有走法能让对手更少
+    LimitedOpponent,
+    /// 双方行动力之差比落子前更有利
+    KeptMobility,
+    /// 没有明显的优缺点
+    Solid,
+}
+
+impl CoachComment {
+    /// 提示栏显示的文本键和参数
+    pub fn text(&self) -> (TextKey, Vec<TextArg>) {
+        let square = |position: u8| vec![TextArg::Value(Board::position_to_notation(position))];
+        match *self {
+            Self::TookCorner => (TextKey::CoachTookCorner, Vec::new()),
+            Self::ConcedesCorner { corner } => (TextKey::CoachConcedesCorner, square(corner)),
+            Self::RiskyXSquare => (TextKey::CoachRiskyXSquare, Vec::new()),
+            Self::BetterMove { best } => (TextKey::CoachBetterMove, square(best)),
+            Self::LimitedOpponent => (TextKey::CoachLimitedOpponent, Vec::new()),
+            Self::KeptMobility => (TextKey::CoachKeptMobility, Vec::new()),
+            Self::Solid => (TextKey::CoachSolid, Vec::new()),
+        }
+    }
+}
+
+/// 点评一步棋，不合法的走法返回None
+pub fn coach_move(board: &Board, position: u8, player: PlayerColor) -> Option<CoachComment> {
+    let mut after = *board;
+    if !after.make_move(position, player) {
+        return None;
+    }
+
+    if CORNER_X_SQUARES
+        .iter()
+        .any(|&(corner, _)| corner == position)
+    {
+        return Some(CoachComment::TookCorner);
+    }
+    if let Some(corner) = corner_conceded(board, position, player) {
+        return Some(CoachComment::ConcedesCorner { corner });
+    }
+    if CORNER_X_SQUARES
+        .iter()
+        .any(|&(corner, x_square)| x_square == position && board.is_empty(corner))
+    {
+        return Some(CoachComment::RiskyXSquare);
+    }
+
+    // 一步静态评估，比较所有合法走法
+    let opponent = player.opposite();
+    let alternatives: Vec<(u8, i32, u32)> = board
+        .get_valid_moves_list(player)
+        .into_iter()
+        .map(|candidate| {
+            let mut next = *board;
+            next.make_move(candidate.position, player);
+            (
+                candidate.position,
+                evaluate_board(&next, player),
+                next.get_valid_moves(opponent).count_ones(),
+            )
+        })
+        .collect();
+
+    let played = evaluate_board(&after, player);
+    if let Some(&(best, best_score, _)) = alternatives.iter().max_by_key(|(_, score, _)| *score) {
+        if best_score - played >= INACCURACY_MARGIN {
+            return Some(CoachComment::BetterMove { best });
+        }
+    }
+
+    let replies = after.get_valid_moves(opponent).count_ones();
+    let fewest = alternatives.iter().map(|&(_, _, replies)| replies).min();
+    if alternatives.len() > 1 && replies <= FEW_REPLIES && fewest == Some(replies) {
+        return Some(CoachComment::LimitedOpponent);
+    }
+
+    let margin = |board: &Board| {
+        board.get_valid_moves(player).count_ones() as i32
+            - board.get_valid_moves(opponent).count_ones() as i32
+    };
+    if margin(&after) > margin(board).max(0) {
+        return Some(CoachComment::KeptMobility);
+    }
+
+    Some(CoachComment::Solid)
+}
+
+/// 最近一步的点评，新对局开始时清空
+#[derive(Resource, Debug, Default)]
+pub struct CoachState {
+    pub comment: Option<CoachComment>,
+}
+
+/// 教练模式开启时点评玩家的每一步，需要在走棋命令应用之前运行以取得落子前的局面
+pub fn coach_player_moves(
+    mut move_commands: EventReader<MoveCommand>,
+    log_query: Query<&GameLog>,
+    ai_query: Query<&AiPlayer>,
+    preferences: Res<Preferences>,
+    match_rules: Res<ActiveMatchRules>,
+    mut coach: ResMut<CoachState>,
+) {
+    if !preferences.assist.permitted(match_rules.allowed()).coach {
+        move_commands.clear();
+        return;
+    }
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+    let ai_color = ai_query.single().ok().map(|ai| ai.color);
+
+    for command in move_commands.read() {
+        let MoveAction::Place(position) = command.action else {
+            continue;
+        };
+        if Some(command.player) == ai_color || log.validate(command).is_err() {
+            continue;
+        }
+        coach.comment = coach_move(&log.board(), position, command.player);
+    }
+}
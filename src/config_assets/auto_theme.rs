@@ -0,0 +1,151 @@
+// 自动主题 - 按系统深色模式或本地时间在浅色和深色棋盘主题之间切换
+//
+// 主题选项为浅色、深色或自动。自动时优先使用系统的深色模式设置（窗口系统报告时），
+// 否则按本地时间：19点到次日7点使用深色主题。每分钟检查一次，
+// 选项或系统设置变化时立即检查，切换后由apply_theme_changes换用对应的主题文件
+
+use crate::preferences::Preferences;
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowTheme, WindowThemeChanged},
+};
+use serde::{Deserialize, Serialize};
+
+/// 自动主题检查本地时间的间隔（秒）
+pub const AUTO_THEME_CHECK_SECS: f32 = 60.0;
+
+/// 深色主题开始的本地时间（时）
+pub const DARK_FROM_HOUR: u32 = 19;
+
+/// 深色主题结束的本地时间（时）
+pub const DARK_UNTIL_HOUR: u32 = 7;
+
+/// 棋盘主题选项，保存在偏好设置中
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ThemeMode {
+    /// 浅色主题（经典绿色棋盘）
+    #[default]
+    Light,
+    /// 深色主题
+    Dark,
+    /// 按系统设置或本地时间自动切换
+    Auto,
+}
+
+/// 实际使用的棋盘配色
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ThemePalette {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl ThemeMode {
+    /// 循环切换到下一个选项
+    pub fn next(self) -> Self {
+        match self {
+            Self::Light => Self::Dark,
+            Self::Dark => Self::Auto,
+            Self::Auto => Self::Light,
+        }
+    }
+
+    /// 选项对应的配色，自动时优先使用系统设置，其次是本地时间，都不可用时使用浅色
+    pub fn palette(self, os_hint: Option<WindowTheme>, local_hour: Option<u32>) -> ThemePalette {
+        match self {
+            Self::Light => ThemePalette::Light,
+            Self::Dark => ThemePalette::Dark,
+            Self::Auto => match (os_hint, local_hour) {
+                (Some(WindowTheme::Dark), _) => ThemePalette::Dark,
+                (Some(WindowTheme::Light), _) => ThemePalette::Light,
+                (None, Some(hour)) => ThemePalette::for_hour(hour),
+                (None, None) => ThemePalette::Light,
+            },
+        }
+    }
+}
+
+impl ThemePalette {
+    /// 本地时间对应的配色：夜间使用深色
+    pub fn for_hour(hour: u32) -> Self {
+        if !(DARK_UNTIL_HOUR..DARK_FROM_HOUR).contains(&hour) {
+            Self::Dark
+        } else {
+            Self::Light
+        }
+    }
+}
+
+/// 当前使用的配色和系统报告的深色模式设置
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActiveTheme {
+    pub palette: ThemePalette,
+    /// 系统的深色模式设置，窗口系统不支持时为None
+    pub os_hint: Option<WindowTheme>,
+}
+
+/// 本地时间的小时数，浏览器中使用Date
+#[cfg(target_arch = "wasm32")]
+pub fn local_hour() -> Option<u32> {
+    Some(js_sys::Date::new_0().get_hours())
+}
+
+/// 本地时间的小时数，按系统时区换算
+#[cfg(unix)]
+pub fn local_hour() -> Option<u32> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs() as libc::time_t;
+    // SAFETY: localtime_r只读取now并写入调用方提供的tm，不保留指针
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    let result = unsafe { libc::localtime_r(&now, &mut tm) };
+    (!result.is_null()).then_some(tm.tm_hour as u32)
+}
+
+/// 其他平台没有可用的时区信息，自动主题只使用系统设置
+#[cfg(not(any(target_arch = "wasm32", unix)))]
+pub fn local_hour() -> Option<u32> {
+    None
+}
+
+/// 按主题选项、系统设置和本地时间更新当前配色
+///
+/// 系统设置在窗口创建时读取，之后通过WindowThemeChanged事件更新；
+/// 本地时间每分钟检查一次，选项变化时立即检查
+pub fn update_active_theme(
+    preferences: Res<Preferences>,
+    mut active_theme: ResMut<ActiveTheme>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut theme_events: EventReader<WindowThemeChanged>,
+    time: Res<Time<Real>>,
+    mut check_timer: Local<Option<Timer>>,
+) {
+    let timer = check_timer
+        .get_or_insert_with(|| Timer::from_seconds(AUTO_THEME_CHECK_SECS, TimerMode::Repeating));
+    let mut should_check = timer.tick(time.delta()).just_finished()
+        || preferences.is_changed()
+        || active_theme.is_added();
+
+    let mut os_hint = active_theme.os_hint;
+    if let Some(event) = theme_events.read().last() {
+        os_hint = Some(event.theme);
+    } else if os_hint.is_none() {
+        os_hint = window_query
+            .single()
+            .ok()
+            .and_then(|window| window.window_theme);
+    }
+    if os_hint != active_theme.os_hint {
+        active_theme.os_hint = os_hint;
+        should_check = true;
+    }
+
+    if should_check {
+        let palette = preferences.theme.palette(os_hint, local_hour());
+        if active_theme.palette != palette {
+            info!("Board theme switched to {palette:?}");
+            active_theme.palette = palette;
+        }
+    }
+}
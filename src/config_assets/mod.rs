@@ -0,0 +1,305 @@
+// 配置资源 - 从RON文件加载AI评估配置和棋盘主题，文件修改后立即生效
+//
+// 评估配置（assets/ai/*.eval.ron）和棋盘主题（assets/themes/*.theme.ron）作为Bevy资源加载，
+// 加载完成或文件被修改时（AssetEvent::Added/Modified）更新ActiveEvalConfig和BoardColors。
+// 开发版本启用了dev_native特性（Bevy的file_watcher），保存文件后即可看到新的AI权重和棋盘颜色；
+// 其他版本只在启动时读取一次，文件缺失或解析失败时使用内置的默认值。
+// 浅色和深色主题各有一个文件，当前使用哪一个由主题选项决定（见auto_theme）；
+// 主题中的质感风格（纹理、投影）在精简资源档下固定为Flat；
+// 启用nn-eval特性时还会加载专家+难度的网络权重（见network）
+
+pub mod auto_theme;
+#[cfg(feature = "nn-eval")]
+pub mod network;
+
+pub use auto_theme::*;
+#[cfg(feature = "nn-eval")]
+pub use network::*;
+
+use crate::{
+    ai::evaluation::EvalConfig,
+    game::PlayerColor,
+    platform::{AssetTier, PlatformProfile},
+    ui::{BoardColors, BoardLine, BoardSquare, BoardStyle, Piece, ValidMoveIndicator},
+};
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use std::{marker::PhantomData, sync::Arc};
+
+/// 内置AI使用的评估配置文件
+const EVAL_CONFIG_PATH: &str = "ai/standard.eval.ron";
+
+/// 默认的棋盘主题文件，也是浅色主题
+const THEME_PATH: &str = "themes/classic.theme.ron";
+
+/// 深色棋盘主题文件
+const DARK_THEME_PATH: &str = "themes/dark.theme.ron";
+
+/// 从RON文件加载的评估配置
+#[derive(Asset, TypePath, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct EvalConfigAsset(pub EvalConfig);
+
+/// 棋盘主题，颜色为sRGB的[红, 绿, 蓝, 不透明度]，style为纹理和投影的质感风格
+///
+/// 缺少的字段使用默认主题的值
+#[derive(Asset, TypePath, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct BoardTheme {
+    pub board_color: [f32; 4],
+    pub square_color: [f32; 4],
+    pub line_color: [f32; 4],
+    pub black_piece_color: [f32; 4],
+    pub white_piece_color: [f32; 4],
+    pub valid_move_color: [f32; 4],
+    pub hover_color: [f32; 4],
+    pub illegal_hover_color: [f32; 4],
+    pub style: BoardStyle,
+}
+
+impl Default for BoardTheme {
+    fn default() -> Self {
+        let colors = BoardColors::default();
+        let array = |color: Color| color.to_srgba().to_f32_array();
+        Self {
+            board_color: array(colors.board_color),
+            square_color: array(colors.square_color),
+            line_color: array(colors.line_color),
+            black_piece_color: array(colors.black_piece_color),
+            white_piece_color: array(colors.white_piece_color),
+            valid_move_color: array(colors.valid_move_color),
+            hover_color: array(colors.hover_color),
+            illegal_hover_color: array(colors.illegal_hover_color),
+            style: colors.style,
+        }
+    }
+}
+
+impl BoardTheme {
+    /// 内置的深色主题，与assets/themes/dark.theme.ron相同
+    pub fn dark() -> Self {
+        Self {
+            board_color: [0.11, 0.2, 0.16, 1.0],
+            square_color: [0.09, 0.18, 0.14, 1.0],
+            line_color: [0.04, 0.1, 0.07, 1.0],
+            black_piece_color: [0.06, 0.06, 0.08, 1.0],
+            white_piece_color: [0.85, 0.85, 0.82, 1.0],
+            valid_move_color: [1.0, 1.0, 1.0, 0.25],
+            hover_color: [1.0, 1.0, 1.0, 0.2],
+            illegal_hover_color: [0.0, 0.0, 0.0, 0.25],
+            style: BoardStyle::Felt,
+        }
+    }
+
+    /// 配色对应的内置主题，主题文件缺失或还没有加载时使用
+    pub fn builtin(palette: ThemePalette) -> Self {
+        match palette {
+            ThemePalette::Light => Self::default(),
+            ThemePalette::Dark => Self::dark(),
+        }
+    }
+
+    /// 从RON文本解析棋盘主题
+    pub fn from_ron(contents: &str) -> Result<Self, String> {
+        ron::from_str(contents).map_err(|err| err.to_string())
+    }
+
+    /// 转换为棋盘渲染使用的颜色
+    pub fn colors(&self) -> BoardColors {
+        let color = |[red, green, blue, alpha]: [f32; 4]| Color::srgba(red, green, blue, alpha);
+        BoardColors {
+            board_color: color(self.board_color),
+            square_color: color(self.square_color),
+            line_color: color(self.line_color),
+            black_piece_color: color(self.black_piece_color),
+            white_piece_color: color(self.white_piece_color),
+            valid_move_color: color(self.valid_move_color),
+            hover_color: color(self.hover_color),
+            illegal_hover_color: color(self.illegal_hover_color),
+            style: self.style,
+        }
+    }
+
+    /// 按资源档次转换为棋盘颜色，精简资源档的设备性能较弱，使用Flat风格
+    pub fn colors_for(&self, asset_tier: AssetTier) -> BoardColors {
+        let mut colors = self.colors();
+        if asset_tier == AssetTier::Compact {
+            colors.style = BoardStyle::Flat;
+        }
+        colors
+    }
+}
+
+/// 按扩展名加载RON格式资源的通用加载器
+pub struct RonAssetLoader<A> {
+    extensions: &'static [&'static str],
+    _asset: PhantomData<fn() -> A>,
+}
+
+impl<A> RonAssetLoader<A> {
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            _asset: PhantomData,
+        }
+    }
+}
+
+impl<A> AssetLoader for RonAssetLoader<A>
+where
+    A: Asset + for<'de> Deserialize<'de>,
+{
+    type Asset = A;
+    type Settings = ();
+    type Error = String;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<A, String> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|err| err.to_string())?;
+        ron::de::from_bytes(&bytes).map_err(|err| err.to_string())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
+
+/// 配置资源的句柄，保持句柄使资源不被卸载，文件修改后可以重新加载
+#[derive(Resource)]
+pub struct ConfigAssets {
+    pub eval: Handle<EvalConfigAsset>,
+    pub theme: Handle<BoardTheme>,
+    pub dark_theme: Handle<BoardTheme>,
+}
+
+impl ConfigAssets {
+    /// 配色对应的主题文件句柄
+    pub fn theme_for(&self, palette: ThemePalette) -> &Handle<BoardTheme> {
+        match palette {
+            ThemePalette::Light => &self.theme,
+            ThemePalette::Dark => &self.dark_theme,
+        }
+    }
+}
+
+/// 对局中AI使用的评估配置，评估配置文件加载或修改后更新
+#[derive(Resource, Debug, Clone)]
+pub struct ActiveEvalConfig(pub Arc<EvalConfig>);
+
+impl Default for ActiveEvalConfig {
+    fn default() -> Self {
+        Self(Arc::new(EvalConfig::standard().clone()))
+    }
+}
+
+pub fn load_config_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ConfigAssets {
+        eval: asset_server.load(EVAL_CONFIG_PATH),
+        theme: asset_server.load(THEME_PATH),
+        dark_theme: asset_server.load(DARK_THEME_PATH),
+    });
+}
+
+/// 评估配置文件加载或修改后，之后的AI思考使用新配置（正在进行的思考不受影响）
+pub fn apply_eval_config_changes(
+    mut asset_events: EventReader<AssetEvent<EvalConfigAsset>>,
+    config_assets: Res<ConfigAssets>,
+    eval_assets: Res<Assets<EvalConfigAsset>>,
+    mut active_eval: ResMut<ActiveEvalConfig>,
+) {
+    for event in asset_events.read() {
+        if !event.is_loaded_with_dependencies(&config_assets.eval)
+            && !event.is_modified(&config_assets.eval)
+        {
+            continue;
+        }
+        if let Some(EvalConfigAsset(config)) = eval_assets.get(&config_assets.eval) {
+            info!("Evaluation config reloaded from {EVAL_CONFIG_PATH}");
+            active_eval.0 = Arc::new(config.clone());
+        }
+    }
+}
+
+/// 当前主题文件加载或修改后、或者配色切换后更新棋盘颜色
+pub fn apply_theme_changes(
+    mut asset_events: EventReader<AssetEvent<BoardTheme>>,
+    config_assets: Res<ConfigAssets>,
+    themes: Res<Assets<BoardTheme>>,
+    active_theme: Res<ActiveTheme>,
+    profile: Res<PlatformProfile>,
+    mut colors: ResMut<BoardColors>,
+) {
+    let handle = config_assets.theme_for(active_theme.palette);
+    let reloaded = asset_events
+        .read()
+        .any(|event| event.is_loaded_with_dependencies(handle) || event.is_modified(handle));
+    if !reloaded && !active_theme.is_changed() {
+        return;
+    }
+
+    let new_colors = match themes.get(handle) {
+        Some(theme) => {
+            info!("{:?} board theme reloaded", active_theme.palette);
+            theme.colors_for(profile.asset_tier)
+        }
+        None => BoardTheme::builtin(active_theme.palette).colors_for(profile.asset_tier),
+    };
+    *colors = new_colors;
+}
+
+/// 棋盘颜色变化后重新着色已经生成的格子、网格线、棋子和可落子指示
+///
+/// 悬停高亮每帧按当前颜色设置，之后生成的实体直接使用新颜色
+pub fn recolor_board(
+    colors: Res<BoardColors>,
+    mut square_query: Query<(&BoardSquare, &mut Sprite), Without<BoardLine>>,
+    mut line_query: Query<&mut Sprite, With<BoardLine>>,
+    piece_query: Query<(&Piece, &MeshMaterial2d<ColorMaterial>)>,
+    indicator_query: Query<&MeshMaterial2d<ColorMaterial>, With<ValidMoveIndicator>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !colors.is_changed() || colors.is_added() {
+        return;
+    }
+
+    for (square, mut sprite) in square_query.iter_mut() {
+        let (row, col) = (square.position / 8, square.position % 8);
+        sprite.color = if (row + col) % 2 == 0 {
+            colors.board_color
+        } else {
+            colors.square_color
+        };
+    }
+    for mut sprite in line_query.iter_mut() {
+        sprite.color = colors.line_color;
+    }
+
+    let mut set_color = |material: &MeshMaterial2d<ColorMaterial>, color: Color| {
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.color = color;
+        }
+    };
+    for (piece, material) in piece_query.iter() {
+        set_color(
+            material,
+            match piece.color {
+                PlayerColor::Black => colors.black_piece_color,
+                PlayerColor::White => colors.white_piece_color,
+            },
+        );
+    }
+    for material in indicator_query.iter() {
+        set_color(material, colors.valid_move_color);
+    }
+}
@@ -0,0 +1,84 @@
+// 网络权重资源 - 启用nn-eval特性时加载专家+难度使用的量化网络
+//
+// 权重文件（assets/ai/expert_plus.nn）作为Bevy资源加载，加载完成后安装为专家+难度的评估器。
+// 文件缺失或格式错误时记录一次警告，专家+难度继续使用启发式评估
+
+use crate::ai::nn_eval::{install_network, NnEvaluator};
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+
+/// 专家+难度的网络权重文件
+const NETWORK_WEIGHTS_PATH: &str = "ai/expert_plus.nn";
+
+/// 从文件加载的网络权重
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct NetworkWeights(pub NnEvaluator);
+
+/// 网络权重文件的加载器
+#[derive(Default)]
+pub struct NetworkWeightsLoader;
+
+impl AssetLoader for NetworkWeightsLoader {
+    type Asset = NetworkWeights;
+    type Settings = ();
+    type Error = String;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<NetworkWeights, String> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|err| err.to_string())?;
+        NnEvaluator::from_bytes(&bytes)
+            .map(NetworkWeights)
+            .map_err(|err| err.to_string())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["nn"]
+    }
+}
+
+/// 网络权重的句柄，加载完成前保持句柄
+#[derive(Resource)]
+pub struct NetworkWeightsHandle(pub Handle<NetworkWeights>);
+
+pub fn load_network_weights(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(NetworkWeightsHandle(
+        asset_server.load(NETWORK_WEIGHTS_PATH),
+    ));
+}
+
+/// 权重加载完成后安装网络，加载失败时提示改用启发式评估
+pub fn install_network_weights(
+    handle: Option<Res<NetworkWeightsHandle>>,
+    weights: Res<Assets<NetworkWeights>>,
+    asset_server: Res<AssetServer>,
+    mut done: Local<bool>,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+    if *done {
+        return;
+    }
+
+    if let Some(NetworkWeights(network)) = weights.get(&handle.0) {
+        info!(
+            "Expert+ network loaded from {NETWORK_WEIGHTS_PATH} ({} hidden units)",
+            network.hidden_size()
+        );
+        install_network(network.clone());
+        *done = true;
+    } else if asset_server.load_state(&handle.0).is_failed() {
+        warn!("No usable network at {NETWORK_WEIGHTS_PATH}, Expert+ uses the heuristic evaluation");
+        *done = true;
+    }
+}
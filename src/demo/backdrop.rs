@@ -0,0 +1,162 @@
+// 菜单背景棋盘 - 语言选择和难度选择界面后面缓慢自我对弈的暗色棋盘
+//
+// 两个初级AI作为后台搜索对弈（见ai::self_play），局面用缩略棋盘绘制（见ui::mini_board），
+// 上面覆盖一层半透明的暗色遮罩，不抢菜单的视觉焦点。背景位于所有菜单节点之下且不参与指针拾取，
+// 不影响菜单的输入。对局在两个菜单之间延续；减少动效时停在当前局面，演示模式播放时隐藏
+
+use super::AttractMode;
+use crate::{
+    accessibility::MotionSettings,
+    ai::{self_play::SelfPlayGame, AiDifficulty, AiScheduler},
+    game::Board,
+    ui::MiniBoardView,
+};
+use bevy::prelude::*;
+
+/// 背景对局每步之间的间隔（秒），比演示模式慢，只作为点缀
+pub const BACKDROP_MOVE_INTERVAL_SECS: f32 = 1.5;
+
+/// 背景对局结束后重新开始前的停顿（秒）
+const BACKDROP_RESTART_DELAY_SECS: f32 = 4.0;
+
+/// 背景棋盘每格的边长
+const BACKDROP_CELL_SIZE: f32 = 56.0;
+
+/// 暗色遮罩的不透明度
+const BACKDROP_DIM: f32 = 0.6;
+
+/// 菜单背景的自我对弈
+#[derive(Resource)]
+pub struct MenuBackdrop {
+    game: SelfPlayGame,
+    /// 走棋间隔计时
+    move_timer: Timer,
+}
+
+impl Default for MenuBackdrop {
+    fn default() -> Self {
+        Self {
+            game: new_backdrop_game(),
+            move_timer: Timer::from_seconds(BACKDROP_MOVE_INTERVAL_SECS, TimerMode::Once),
+        }
+    }
+}
+
+impl MenuBackdrop {
+    /// 背景对局的当前局面
+    pub fn board(&self) -> Board {
+        self.game.log().board()
+    }
+
+    /// 背景对局已经走过的步数
+    pub fn moves_played(&self) -> usize {
+        self.game.log().commands().len()
+    }
+}
+
+/// 菜单背景的根节点
+#[derive(Component)]
+pub struct MenuBackdropUI;
+
+/// 菜单背景中显示局面的缩略棋盘
+#[derive(Component)]
+pub struct MenuBackdropBoard;
+
+/// 背景对局使用两个初级AI，走得快且局面变化多
+fn new_backdrop_game() -> SelfPlayGame {
+    SelfPlayGame::new(Board::new(), AiDifficulty::Beginner, AiDifficulty::Beginner)
+}
+
+/// 进入菜单时在所有菜单节点之下生成背景棋盘
+///
+/// `S`为菜单所在的游戏状态，背景实体离开该状态时自动清理
+pub fn spawn_menu_backdrop<S: States>(
+    mut commands: Commands,
+    backdrop: Res<MenuBackdrop>,
+    state: Res<State<S>>,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            Pickable::IGNORE,
+            GlobalZIndex(-1),
+            MenuBackdropUI,
+            StateScoped(state.get().clone()),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node::default(),
+                MiniBoardView::new(backdrop.board(), BACKDROP_CELL_SIZE),
+                Pickable::IGNORE,
+                MenuBackdropBoard,
+            ));
+            // 遮罩在缩略棋盘之后生成，绘制在棋盘之上
+            root.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(Color::BLACK.with_alpha(BACKDROP_DIM)),
+                Pickable::IGNORE,
+            ));
+        });
+}
+
+/// 推进背景对局并更新缩略棋盘的局面，演示模式播放时隐藏背景并暂停
+pub fn advance_menu_backdrop(
+    mut backdrop: ResMut<MenuBackdrop>,
+    scheduler: Res<AiScheduler>,
+    attract_mode: Res<AttractMode>,
+    motion_settings: Res<MotionSettings>,
+    mut root_query: Query<&mut Visibility, With<MenuBackdropUI>>,
+    mut board_query: Query<&mut MiniBoardView, With<MenuBackdropBoard>>,
+    time: Res<Time>,
+) {
+    let visibility = if attract_mode.is_active() {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    for mut root_visibility in root_query.iter_mut() {
+        root_visibility.set_if_neq(visibility);
+    }
+
+    // 减少动效时停在当前局面
+    if !attract_mode.is_active() && !motion_settings.reduced_motion {
+        let backdrop = &mut *backdrop;
+        backdrop.move_timer.tick(time.delta());
+        if backdrop.move_timer.finished() {
+            let moved = if backdrop.game.is_finished() {
+                // 对局结束停顿之后开始新的一局
+                backdrop.game = new_backdrop_game();
+                true
+            } else {
+                backdrop.game.poll_scheduled(&scheduler)
+            };
+            if moved {
+                let interval = if backdrop.game.is_finished() {
+                    BACKDROP_RESTART_DELAY_SECS
+                } else {
+                    BACKDROP_MOVE_INTERVAL_SECS
+                };
+                backdrop.move_timer = Timer::from_seconds(interval, TimerMode::Once);
+            }
+        }
+    }
+
+    let board = backdrop.board();
+    for mut view in board_query.iter_mut() {
+        if view.board != board {
+            view.board = board;
+        }
+    }
+}
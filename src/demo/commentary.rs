@@ -0,0 +1,303 @@
+// 演示对局解说 - AI自我对弈时在侧边栏滚动显示的文字解说
+//
+// 解说由命令日志逐步重放生成，与界面无关：每一步给出棋谱坐标和翻转数，
+// 再按需要补充占角、胜率大幅变化（见ai::win_probability）和对手可走步数很少（见EvalBreakdown）的说明，
+// 对局结束时给出比分。每行保存文本键和参数，切换语言时按当前语言重新显示。
+// 演示模式播放时由演示对局驱动；宽屏窗口才显示侧边栏，窄屏时菜单两侧没有空间
+
+use super::AttractMode;
+use crate::{
+    ai::{evaluation::EvalBreakdown, win_probability::WinProbabilityModel},
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    game::{Board, GameLog, MoveAction, MoveCommand, PlayerColor},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+};
+use bevy::{prelude::*, window::PrimaryWindow};
+use std::collections::VecDeque;
+
+/// 窗口逻辑宽度达到该值时才显示解说侧边栏
+pub const COMMENTARY_MIN_WINDOW_WIDTH: f32 = 800.0;
+
+/// 侧边栏保留的解说行数，更早的行滚出
+pub const COMMENTARY_LINES: usize = 14;
+
+/// 一步之后胜率变化至少这么多个百分点时算作局势大幅变化
+pub const BIG_SWING_POINTS: i32 = 20;
+
+/// 一步之后胜率变化至少这么多个百分点时说明一方稍占上风
+pub const NOTABLE_SWING_POINTS: i32 = 8;
+
+/// 对手可走的步数不超过该值时提醒
+const FEW_MOVES: u32 = 2;
+
+/// 侧边栏的宽度
+const PANEL_WIDTH: f32 = 280.0;
+
+/// 四个角
+const CORNERS: [u8; 4] = [0, 7, 56, 63];
+
+/// 一方的名称
+fn side_name(player: PlayerColor) -> TextArg {
+    TextArg::Key(match player {
+        PlayerColor::Black => TextKey::BlackName,
+        PlayerColor::White => TextKey::WhiteName,
+    })
+}
+
+/// 黑方的胜率（百分比，四舍五入）
+fn black_chance(board: &Board) -> i32 {
+    (WinProbabilityModel::BAKED.for_board(board, PlayerColor::Black) * 100.0).round() as i32
+}
+
+/// 一条走棋命令的解说行，`before`为走棋前的局面
+pub fn move_commentary(before: &Board, command: &MoveCommand) -> Vec<(TextKey, TextArgs)> {
+    let mut lines = Vec::new();
+    let player = command.player;
+    let mut after = *before;
+    match command.action {
+        MoveAction::Place(position) => {
+            after.make_move(position, player);
+            let flipped = before.diff(&after).flipped.count_ones();
+            let notation = TextArg::Value(Board::position_to_notation(position));
+            lines.push((
+                TextKey::CommentaryMoveFormat,
+                TextArgs(vec![
+                    TextArg::Number(u64::from(command.sequence) + 1),
+                    side_name(player),
+                    notation.clone(),
+                    TextArg::Number(flipped.into()),
+                ]),
+            ));
+            if CORNERS.contains(&position) {
+                lines.push((
+                    TextKey::CommentaryCornerFormat,
+                    TextArgs(vec![side_name(player), notation]),
+                ));
+            }
+        }
+        MoveAction::Pass => lines.push((
+            TextKey::CommentaryPassFormat,
+            TextArgs(vec![side_name(player)]),
+        )),
+    }
+
+    if after.is_game_over() {
+        lines.push((
+            TextKey::CommentaryGameOverFormat,
+            TextArgs(vec![
+                TextArg::Number(after.count_pieces(PlayerColor::Black).into()),
+                TextArg::Number(after.count_pieces(PlayerColor::White).into()),
+            ]),
+        ));
+        return lines;
+    }
+
+    // 胜率变化按受益的一方描述
+    let chance = black_chance(&after);
+    let swing = chance - black_chance(before);
+    let (favoured, favoured_chance) = if swing > 0 {
+        (PlayerColor::Black, chance)
+    } else {
+        (PlayerColor::White, 100 - chance)
+    };
+    let swing_key = if swing.abs() >= BIG_SWING_POINTS {
+        Some(TextKey::CommentarySwingFormat)
+    } else if swing.abs() >= NOTABLE_SWING_POINTS {
+        Some(TextKey::CommentaryEdgeFormat)
+    } else {
+        None
+    };
+    if let Some(key) = swing_key {
+        lines.push((
+            key,
+            TextArgs(vec![
+                side_name(favoured),
+                TextArg::Number(favoured_chance as u64),
+            ]),
+        ));
+    }
+
+    let opponent = player.opposite();
+    let opponent_moves = EvalBreakdown::of(&after).mobility_of(opponent);
+    if (1..=FEW_MOVES).contains(&opponent_moves) {
+        lines.push((
+            TextKey::CommentaryMobilityFormat,
+            TextArgs(vec![
+                side_name(opponent),
+                TextArg::Number(opponent_moves.into()),
+            ]),
+        ));
+    }
+    lines
+}
+
+/// 滚动解说的内容，跟随一局对局的命令日志增量生成
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CommentaryFeed {
+    lines: VecDeque<(TextKey, TextArgs)>,
+    /// 已解说的对局的初始局面和解说到的局面
+    replay: Option<(Board, Board)>,
+    /// 已解说的命令数
+    commands_seen: usize,
+}
+
+impl CommentaryFeed {
+    /// 当前保留的解说行，最新的在最后
+    pub fn lines(&self) -> impl Iterator<Item = &(TextKey, TextArgs)> {
+        self.lines.iter()
+    }
+
+    /// 是否没有任何解说
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// 清空解说
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// 为日志中还没有解说的命令生成解说，日志换成新的一局（或被悔棋）时从头开始
+    ///
+    /// # 返回
+    /// 解说内容是否发生了变化
+    pub fn sync(&mut self, log: &GameLog) -> bool {
+        let commands = log.commands();
+        let same_game = self
+            .replay
+            .is_some_and(|(initial, _)| initial == log.initial())
+            && commands.len() >= self.commands_seen;
+        if !same_game {
+            let changed = !self.is_empty();
+            self.clear();
+            self.replay = Some((log.initial(), log.initial()));
+            if commands.is_empty() {
+                return changed;
+            }
+        }
+
+        let Some((_, board)) = self.replay.as_mut() else {
+            return false;
+        };
+        let new_commands = &commands[self.commands_seen..];
+        for command in new_commands {
+            let lines = move_commentary(board, command);
+            if let MoveAction::Place(position) = command.action {
+                board.make_move(position, command.player);
+            }
+            self.lines.extend(lines);
+        }
+        while self.lines.len() > COMMENTARY_LINES {
+            self.lines.pop_front();
+        }
+        self.commands_seen = commands.len();
+        !same_game || !new_commands.is_empty()
+    }
+}
+
+/// 解说侧边栏的根节点
+#[derive(Component)]
+pub struct CommentaryPanel;
+
+/// 演示对局进行时跟随对局更新解说，演示结束时清空
+pub fn update_demo_commentary(attract_mode: Res<AttractMode>, mut feed: ResMut<CommentaryFeed>) {
+    let Some(log) = attract_mode.log() else {
+        if !feed.is_empty() {
+            feed.clear();
+        }
+        return;
+    };
+    if feed.bypass_change_detection().sync(log) {
+        feed.set_changed();
+    }
+}
+
+/// 宽屏窗口中演示对局进行时显示解说侧边栏，解说变化时重新生成
+///
+/// `S`为菜单所在的游戏状态，侧边栏离开该状态时自动清理
+pub fn manage_commentary_panel<S: States>(
+    mut commands: Commands,
+    feed: Res<CommentaryFeed>,
+    attract_mode: Res<AttractMode>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    panel_query: Query<Entity, With<CommentaryPanel>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    state: Res<State<S>>,
+) {
+    let wide = window_query
+        .single()
+        .is_ok_and(|window| window.width() >= COMMENTARY_MIN_WINDOW_WIDTH);
+    let show = wide && attract_mode.is_active();
+    let panel_open = !panel_query.is_empty();
+    if show == panel_open && !(show && feed.is_changed()) {
+        return;
+    }
+
+    for entity in panel_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    if show {
+        let font = get_font_for_language(&language_settings, &font_assets);
+        spawn_commentary_panel(&mut commands, &font, &feed, state.get().clone());
+    }
+}
+
+fn spawn_commentary_panel<S: States>(
+    commands: &mut Commands,
+    font: &Handle<Font>,
+    feed: &CommentaryFeed,
+    scope: S,
+) {
+    let text_font = |font_size: f32| TextFont {
+        font: font.clone(),
+        font_size,
+        ..default()
+    };
+    let newest = feed.lines.len().saturating_sub(1);
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(16.0),
+                top: Val::Px(16.0),
+                bottom: Val::Px(16.0),
+                width: Val::Px(PANEL_WIDTH),
+                flex_direction: FlexDirection::Column,
+                overflow: Overflow::clip(),
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+            BorderRadius::all(Val::Px(10.0)),
+            Pickable::IGNORE,
+            CommentaryPanel,
+            StateScoped(scope),
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::default(),
+                TextKey::CommentaryTitle,
+                text_font(18.0),
+                TextColor(Color::WHITE),
+                LocalizedText,
+            ));
+            for (index, (key, args)) in feed.lines().enumerate() {
+                // 最新的一行高亮，之前的行变暗
+                let color = if index == newest {
+                    Color::WHITE
+                } else {
+                    Color::srgb(0.65, 0.65, 0.65)
+                };
+                panel.spawn((
+                    Text::default(),
+                    *key,
+                    args.clone(),
+                    text_font(14.0),
+                    TextColor(color),
+                    LocalizedText,
+                ));
+            }
+        });
+}
@@ -0,0 +1,255 @@
+// 演示模式 - 菜单界面长时间无操作时，在背景中播放AI对弈
+//
+// 演示棋盘以半透明方式绘制在菜单后面，任何输入都会立即停止演示；
+// 演示实体带有StateScoped组件，离开菜单状态时自动清理；宽屏时侧边栏显示对局解说（见commentary）。
+// 菜单背景中一直缓慢进行的自我对弈见backdrop，演示播放时背景棋盘隐藏
+
+pub mod backdrop;
+pub mod commentary;
+
+pub use backdrop::*;
+pub use commentary::*;
+
+use crate::{
+    accessibility::MotionSettings,
+    ai::{self_play::SelfPlayGame, AiDifficulty, AiScheduler},
+    game::{Board, GameLog, PlayerColor},
+    ui::{BoardColors, BOARD_SIZE, PIECE_RADIUS, SQUARE_SIZE},
+};
+use bevy::{
+    input::{keyboard::KeyboardInput, mouse::MouseWheel},
+    prelude::*,
+    window::CursorMoved,
+};
+
+/// 无操作多久后开始演示（秒）
+pub const DEMO_IDLE_SECS: f32 = 120.0;
+
+/// 演示对局每步之间的间隔（秒）
+const DEMO_MOVE_INTERVAL_SECS: f32 = 0.8;
+
+/// 演示对局结束后重新开始前的停顿（秒）
+const DEMO_RESTART_DELAY_SECS: f32 = 3.0;
+
+/// 演示棋盘的不透明度
+const DEMO_OPACITY: f32 = 0.35;
+
+/// 演示模式状态
+#[derive(Resource)]
+pub struct AttractMode {
+    /// 无操作计时
+    idle_timer: Timer,
+    /// 正在进行的演示对局
+    game: Option<SelfPlayGame>,
+    /// 走棋间隔计时
+    move_timer: Timer,
+}
+
+impl Default for AttractMode {
+    fn default() -> Self {
+        Self {
+            idle_timer: Timer::from_seconds(DEMO_IDLE_SECS, TimerMode::Once),
+            game: None,
+            move_timer: Timer::from_seconds(DEMO_MOVE_INTERVAL_SECS, TimerMode::Once),
+        }
+    }
+}
+
+impl AttractMode {
+    /// 演示是否正在进行
+    pub fn is_active(&self) -> bool {
+        self.game.is_some()
+    }
+
+    /// 正在演示的对局的命令日志
+    pub fn log(&self) -> Option<&GameLog> {
+        self.game.as_ref().map(SelfPlayGame::log)
+    }
+}
+
+/// 演示棋盘实体标记
+#[derive(Component)]
+pub struct DemoBoard;
+
+/// 演示棋子实体标记
+#[derive(Component)]
+pub struct DemoPiece;
+
+/// 进入菜单时重置演示状态
+pub fn reset_attract_mode(mut attract_mode: ResMut<AttractMode>) {
+    *attract_mode = AttractMode::default();
+}
+
+/// 统计无操作时间，超时后开始演示，有任何输入时停止演示
+///
+/// `S`为菜单所在的游戏状态，演示实体离开该状态时自动清理
+pub fn update_attract_mode<S: States>(
+    mut commands: Commands,
+    mut attract_mode: ResMut<AttractMode>,
+    state: Res<State<S>>,
+    keyboard_events: EventReader<KeyboardInput>,
+    cursor_events: EventReader<CursorMoved>,
+    wheel_events: EventReader<MouseWheel>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    touch_input: Res<Touches>,
+    motion_settings: Res<MotionSettings>,
+    demo_query: Query<Entity, Or<(With<DemoBoard>, With<DemoPiece>)>>,
+    colors: Res<BoardColors>,
+    time: Res<Time>,
+) {
+    let has_input = !keyboard_events.is_empty()
+        || !cursor_events.is_empty()
+        || !wheel_events.is_empty()
+        || mouse_input.get_just_pressed().next().is_some()
+        || touch_input.iter().next().is_some();
+
+    if has_input {
+        // 任何输入都停止演示并重新计时
+        if attract_mode.is_active() {
+            for entity in demo_query.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+        *attract_mode = AttractMode::default();
+        return;
+    }
+
+    // 减少动效时不播放演示
+    if motion_settings.reduced_motion || attract_mode.is_active() {
+        return;
+    }
+
+    attract_mode.idle_timer.tick(time.delta());
+    if attract_mode.idle_timer.finished() {
+        attract_mode.game = Some(new_demo_game());
+        spawn_demo_board(&mut commands, &colors, state.get().clone());
+    }
+}
+
+/// 推进演示对局，并在局面变化时重绘演示棋子
+pub fn advance_demo_game<S: States>(
+    mut commands: Commands,
+    mut attract_mode: ResMut<AttractMode>,
+    scheduler: Res<AiScheduler>,
+    state: Res<State<S>>,
+    piece_query: Query<Entity, With<DemoPiece>>,
+    colors: Res<BoardColors>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    let attract_mode = &mut *attract_mode;
+    let Some(game) = attract_mode.game.as_mut() else {
+        return;
+    };
+
+    // 刚开始演示时先画出初始局面
+    let mut board_changed = piece_query.is_empty();
+
+    attract_mode.move_timer.tick(time.delta());
+    if attract_mode.move_timer.finished() {
+        if game.is_finished() {
+            // 对局结束停顿之后开始新的一局
+            *game = new_demo_game();
+            board_changed = true;
+        } else if game.poll_scheduled(&scheduler) {
+            board_changed = true;
+        }
+
+        if board_changed {
+            // 对局结束时停顿片刻再开始新的一局
+            let interval = if game.is_finished() {
+                DEMO_RESTART_DELAY_SECS
+            } else {
+                DEMO_MOVE_INTERVAL_SECS
+            };
+            attract_mode.move_timer = Timer::from_seconds(interval, TimerMode::Once);
+        }
+    }
+
+    if !board_changed {
+        return;
+    }
+
+    for entity in piece_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let board = game.log().board();
+    for position in 0..64 {
+        if let Some(color) = board.get_piece(position) {
+            let (row, col) = Board::position_to_coords(position);
+            let piece_color = match color {
+                PlayerColor::Black => colors.black_piece_color,
+                PlayerColor::White => colors.white_piece_color,
+            };
+
+            commands.spawn((
+                Mesh2d(meshes.add(Circle::new(PIECE_RADIUS))),
+                MeshMaterial2d(
+                    materials.add(ColorMaterial::from(piece_color.with_alpha(DEMO_OPACITY))),
+                ),
+                Transform::from_xyz(
+                    (col as f32 - 3.5) * SQUARE_SIZE,
+                    (3.5 - row as f32) * SQUARE_SIZE,
+                    2.0,
+                ),
+                DemoPiece,
+                StateScoped(state.get().clone()),
+            ));
+        }
+    }
+}
+
+/// 演示使用两个中级AI，走得快且不会一边倒
+fn new_demo_game() -> SelfPlayGame {
+    SelfPlayGame::new(
+        Board::new(),
+        AiDifficulty::Intermediate,
+        AiDifficulty::Intermediate,
+    )
+}
+
+fn spawn_demo_board<S: States>(commands: &mut Commands, colors: &BoardColors, scope: S) {
+    for row in 0..8 {
+        for col in 0..8 {
+            let square_color = if (row + col) % 2 == 0 {
+                colors.board_color
+            } else {
+                colors.square_color
+            };
+
+            commands.spawn((
+                Sprite::from_color(
+                    square_color.with_alpha(DEMO_OPACITY),
+                    Vec2::splat(SQUARE_SIZE),
+                ),
+                Transform::from_xyz(
+                    (col as f32 - 3.5) * SQUARE_SIZE,
+                    (3.5 - row as f32) * SQUARE_SIZE,
+                    0.0,
+                ),
+                DemoBoard,
+                StateScoped(scope.clone()),
+            ));
+        }
+    }
+
+    // 棋盘网格线
+    let line_color = colors.line_color.with_alpha(DEMO_OPACITY);
+    for i in 0..9 {
+        let offset = (i as f32 - 4.0) * SQUARE_SIZE;
+        commands.spawn((
+            Sprite::from_color(line_color, Vec2::new(1.5, BOARD_SIZE)),
+            Transform::from_xyz(offset, 0.0, 1.0),
+            DemoBoard,
+            StateScoped(scope.clone()),
+        ));
+        commands.spawn((
+            Sprite::from_color(line_color, Vec2::new(BOARD_SIZE, 1.5)),
+            Transform::from_xyz(0.0, offset, 1.0),
+            DemoBoard,
+            StateScoped(scope.clone()),
+        ));
+    }
+}
@@ -0,0 +1,311 @@
+// 显示设置模块 - 窗口尺寸预设、全屏切换、棋盘布局和界面缩放
+//
+// 窗口尺寸和位置会记录到偏好设置中，下次启动时恢复；
+// 窗口尺寸变化时重新计算棋盘的显示大小。界面中的字号和节点尺寸按紧凑预设（400×600）设计，
+// 通过Bevy的UiScale按窗口尺寸和文字大小设置整体缩放，棋盘四周留给界面的边距随之缩放，保持比例一致
+
+use crate::{
+    input::{InputAction, InputMap},
+    preferences::Preferences,
+    ui::BOARD_SIZE,
+};
+use bevy::{
+    prelude::*,
+    window::{MonitorSelection, PrimaryWindow, WindowMode, WindowMoved, WindowResized},
+};
+use serde::{Deserialize, Serialize};
+
+/// 棋盘左右两侧保留的总宽度
+const BOARD_MARGIN_X: f32 = 80.0;
+
+/// 棋盘上下两侧保留的总高度（顶部和底部玩家区域）
+const BOARD_MARGIN_Y: f32 = 280.0;
+
+/// 棋盘最小显示尺寸，避免窗口过小时无法操作
+const MIN_BOARD_SIZE: f32 = 160.0;
+
+/// 按窗口尺寸得到的界面缩放的下限和上限，文字大小设置在此之上再缩放
+const MIN_WINDOW_UI_SCALE: f32 = 0.75;
+const MAX_WINDOW_UI_SCALE: f32 = 2.5;
+
+/// 窗口尺寸预设
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowPreset {
+    /// 400×600 手机比例
+    #[default]
+    Compact,
+    /// 540×810
+    Medium,
+    /// 720×1080
+    Large,
+}
+
+impl WindowPreset {
+    /// 预设对应的窗口逻辑尺寸
+    pub fn size(&self) -> (f32, f32) {
+        match self {
+            Self::Compact => (400.0, 600.0),
+            Self::Medium => (540.0, 810.0),
+            Self::Large => (720.0, 1080.0),
+        }
+    }
+
+    /// 循环切换到下一个预设
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Compact => Self::Medium,
+            Self::Medium => Self::Large,
+            Self::Large => Self::Compact,
+        }
+    }
+
+    /// 显示用的尺寸文本，例如"400×600"
+    pub fn label(&self) -> String {
+        let (width, height) = self.size();
+        format!("{width}×{height}")
+    }
+}
+
+/// 文字大小（无障碍选项），放大或缩小整个界面，包括字号和按钮、面板等节点的尺寸
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextSize {
+    Small,
+    #[default]
+    Normal,
+    Large,
+    ExtraLarge,
+}
+
+impl TextSize {
+    /// 在窗口缩放之上的缩放倍数
+    pub fn factor(self) -> f32 {
+        match self {
+            Self::Small => 0.85,
+            Self::Normal => 1.0,
+            Self::Large => 1.2,
+            Self::ExtraLarge => 1.4,
+        }
+    }
+
+    /// 循环切换到下一个选项
+    pub fn next(self) -> Self {
+        match self {
+            Self::Small => Self::Normal,
+            Self::Normal => Self::Large,
+            Self::Large => Self::ExtraLarge,
+            Self::ExtraLarge => Self::Small,
+        }
+    }
+}
+
+/// 界面缩放比例：窗口相对紧凑预设的比例（取宽高中较小的一边）乘以文字大小的倍数
+///
+/// 窗口尺寸为逻辑像素，已经包含了系统的DPI缩放
+pub fn ui_scale_for_window(width: f32, height: f32, text_size: TextSize) -> f32 {
+    let (design_width, design_height) = WindowPreset::Compact.size();
+    let window_scale = (width / design_width)
+        .min(height / design_height)
+        .clamp(MIN_WINDOW_UI_SCALE, MAX_WINDOW_UI_SCALE);
+    window_scale * text_size.factor()
+}
+
+/// 显示相关的偏好设置
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct DisplayPreferences {
+    /// 最近选择的窗口预设
+    pub preset: WindowPreset,
+    /// 是否全屏
+    pub fullscreen: bool,
+    /// 上次窗口化时的逻辑尺寸
+    pub window_size: Option<(f32, f32)>,
+    /// 上次窗口化时的位置（物理像素）
+    pub window_position: Option<(i32, i32)>,
+    /// 是否显示性能信息层
+    pub performance_overlay: bool,
+    /// 文字大小
+    pub text_size: TextSize,
+    /// AI走棋后是否在头像旁显示搜索深度、用时和节点数
+    pub search_caption: bool,
+}
+
+impl DisplayPreferences {
+    /// 启动时使用的窗口尺寸：优先恢复上次尺寸，否则使用预设
+    pub fn window_resolution(&self) -> (f32, f32) {
+        self.window_size.unwrap_or_else(|| self.preset.size())
+    }
+
+    /// 启动时使用的窗口位置
+    pub fn window_position(&self) -> WindowPosition {
+        match self.window_position {
+            Some((x, y)) => WindowPosition::At(IVec2::new(x, y)),
+            None => WindowPosition::Automatic,
+        }
+    }
+
+    /// 当前设置对应的窗口模式
+    pub fn window_mode(&self) -> WindowMode {
+        if self.fullscreen {
+            WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+        } else {
+            WindowMode::Windowed
+        }
+    }
+}
+
+/// 切换全屏事件
+#[derive(Event)]
+pub struct ToggleFullscreenEvent;
+
+/// 切换到下一个窗口预设事件
+#[derive(Event)]
+pub struct CycleWindowPresetEvent;
+
+/// 棋盘布局资源 - 记录棋盘在屏幕上的实际显示尺寸
+#[derive(Resource)]
+pub struct BoardLayout {
+    /// 棋盘在屏幕上的边长（逻辑像素）
+    pub board_size: f32,
+}
+
+impl Default for BoardLayout {
+    fn default() -> Self {
+        Self {
+            board_size: BOARD_SIZE,
+        }
+    }
+}
+
+impl BoardLayout {
+    /// 根据窗口尺寸计算棋盘边长，四周为界面保留的边距按界面缩放比例放大
+    pub fn board_size_for_window(width: f32, height: f32, ui_scale: f32) -> f32 {
+        (width - BOARD_MARGIN_X * ui_scale)
+            .min(height - BOARD_MARGIN_Y * ui_scale)
+            .max(MIN_BOARD_SIZE)
+    }
+
+    /// 相机缩放比例：棋盘世界坐标固定为BOARD_SIZE，通过缩放相机适配屏幕
+    pub fn camera_scale(&self) -> f32 {
+        BOARD_SIZE / self.board_size
+    }
+
+    /// 将窗口坐标（左上角为原点，y轴向下）转换为棋盘世界坐标
+    ///
+    /// 与相机的`viewport_to_world_2d`等价：相机位于原点，正交投影按camera_scale缩放
+    pub fn screen_to_world(&self, screen_position: Vec2, window_size: Vec2) -> Vec2 {
+        let centered = screen_position - window_size / 2.0;
+        Vec2::new(centered.x, -centered.y) * self.camera_scale()
+    }
+}
+
+/// 显示快捷键：默认F11切换全屏，F10切换窗口预设
+pub fn display_shortcuts_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut fullscreen_events: EventWriter<ToggleFullscreenEvent>,
+    mut preset_events: EventWriter<CycleWindowPresetEvent>,
+) {
+    if input_map.just_pressed(InputAction::ToggleFullscreen, &keyboard_input) {
+        fullscreen_events.write(ToggleFullscreenEvent);
+    }
+    if input_map.just_pressed(InputAction::CycleWindowSize, &keyboard_input) {
+        preset_events.write(CycleWindowPresetEvent);
+    }
+}
+
+/// 应用全屏和窗口预设切换
+pub fn apply_display_events(
+    mut fullscreen_events: EventReader<ToggleFullscreenEvent>,
+    mut preset_events: EventReader<CycleWindowPresetEvent>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut preferences: ResMut<Preferences>,
+) {
+    let Ok(mut window) = window_query.single_mut() else {
+        return;
+    };
+
+    for _event in fullscreen_events.read() {
+        preferences.display.fullscreen = !preferences.display.fullscreen;
+        window.mode = preferences.display.window_mode();
+    }
+
+    for _event in preset_events.read() {
+        let preset = preferences.display.preset.next();
+        let (width, height) = preset.size();
+        preferences.display.preset = preset;
+        preferences.display.window_size = Some((width, height));
+
+        // 切换预设时退出全屏，否则尺寸变化不可见
+        preferences.display.fullscreen = false;
+        window.mode = WindowMode::Windowed;
+        window.resolution.set(width, height);
+    }
+}
+
+/// 记录窗口化时的尺寸和位置
+pub fn track_window_geometry(
+    mut resized_events: EventReader<WindowResized>,
+    mut moved_events: EventReader<WindowMoved>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut preferences: ResMut<Preferences>,
+) {
+    let Ok(primary) = primary_window.single() else {
+        return;
+    };
+
+    // 全屏时的尺寸和位置不应覆盖窗口化设置
+    if preferences.display.fullscreen {
+        resized_events.clear();
+        moved_events.clear();
+        return;
+    }
+
+    if let Some(event) = resized_events.read().filter(|e| e.window == primary).last() {
+        let size = Some((event.width, event.height));
+        if preferences.display.window_size != size {
+            preferences.display.window_size = size;
+        }
+    }
+
+    if let Some(event) = moved_events.read().filter(|e| e.window == primary).last() {
+        let position = Some((event.position.x, event.position.y));
+        if preferences.display.window_position != position {
+            preferences.display.window_position = position;
+        }
+    }
+}
+
+/// 根据窗口尺寸和文字大小设置重新计算界面缩放和棋盘布局，并调整相机缩放
+pub fn update_board_layout(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    preferences: Res<Preferences>,
+    mut ui_scale: ResMut<UiScale>,
+    mut layout: ResMut<BoardLayout>,
+    mut camera_query: Query<&mut Projection, With<Camera2d>>,
+) {
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+
+    let scale = ui_scale_for_window(
+        window.width(),
+        window.height(),
+        preferences.display.text_size,
+    );
+    if (ui_scale.0 - scale).abs() > f32::EPSILON {
+        ui_scale.0 = scale;
+    }
+
+    let board_size = BoardLayout::board_size_for_window(window.width(), window.height(), scale);
+    if (layout.board_size - board_size).abs() > f32::EPSILON {
+        layout.board_size = board_size;
+    }
+
+    if layout.is_changed() {
+        for mut projection in camera_query.iter_mut() {
+            if let Projection::Orthographic(orthographic) = projection.as_mut() {
+                orthographic.scale = layout.camera_scale();
+            }
+        }
+    }
+}
@@ -1,16 +1,26 @@
-use crate::localization::{Language, LanguageSettings};
+use crate::{
+    localization::{Language, LanguageSettings},
+    platform::PlatformProfile,
+};
 use bevy::prelude::*;
 
+/// 中文字体的完整品质路径，精简档次的路径见PlatformProfile::asset_path
+pub const CHINESE_FONT_PATH: &str = "fonts/NotoSerifCJKsc-Regular.otf";
+
 #[derive(Resource, Default)]
 pub struct FontAssets {
     pub default_font: Handle<Font>,
     pub chinese_font: Handle<Font>,
 }
 
-pub fn load_font_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn load_font_assets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    profile: Res<PlatformProfile>,
+) {
     let font_assets = FontAssets {
         default_font: Handle::default(), // Bevy默认字体
-        chinese_font: asset_server.load("fonts/NotoSerifCJKsc-Regular.otf"),
+        chinese_font: asset_server.load(profile.asset_path(CHINESE_FONT_PATH)),
     };
 
     commands.insert_resource(font_assets);
@@ -1,13 +1,17 @@
 use bevy::prelude::*;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Clone, Copy, Debug, PartialEq)]
+/// 四个角的位掩码（A1、H1、A8、H8）
+pub const CORNER_MASK: u64 = 0x8100_0000_0000_0081;
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Board {
     pub black: u64,
     pub white: u64,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PlayerColor {
     Black,
     White,
@@ -115,6 +119,22 @@ impl Board {
         (row, col)
     }
 
+    /// 将位置转换为棋谱记法，例如位置37对应"F5"
+    pub fn position_to_notation(position: u8) -> String {
+        let (row, col) = Self::position_to_coords(position);
+        format!("{}{}", (b'A' + col as u8) as char, row + 1)
+    }
+
+    /// 将棋谱记法转换为位置，例如"F5"或"f5"对应位置37，记法无效时返回None
+    pub fn notation_to_position(notation: &str) -> Option<u8> {
+        let &[col, row] = notation.as_bytes() else {
+            return None;
+        };
+        let col = col.to_ascii_uppercase().checked_sub(b'A')?;
+        let row = row.checked_sub(b'1')?;
+        (col < 8 && row < 8).then(|| row * 8 + col)
+    }
+
     #[allow(dead_code)]
     pub fn coords_to_position(row: usize, col: usize) -> u8 {
         (row * 8 + col) as u8
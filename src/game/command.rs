@@ -0,0 +1,284 @@
+// 走棋命令 - 以有序命令日志驱动棋盘状态
+//
+// 棋盘只能通过按序号应用经过校验的走棋命令来改变，
+// 相同的初始局面和命令序列总能得到相同的结果，便于多端同步、悔棋和回放校验
+
+use super::{Board, PlayerColor, VictoryCondition};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 走棋动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MoveAction {
+    /// 在指定位置落子
+    Place(u8),
+
+    /// 无棋可走，跳过回合
+    Pass,
+}
+
+/// 走棋命令
+///
+/// 序号从0开始连续递增，只有序号等于日志中下一个序号的命令才会被接受
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveCommand {
+    /// 命令序号
+    pub sequence: u32,
+
+    /// 走棋的玩家
+    pub player: PlayerColor,
+
+    /// 走棋动作
+    pub action: MoveAction,
+}
+
+/// 落子规则
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlacementRule {
+    /// 标准规则：落子必须翻转至少一枚对方棋子，无棋可走时跳过回合
+    #[default]
+    Standard,
+
+    /// 自由落子：任意空格都可以落子，照常翻转被夹住的棋子，双方轮流落子直到棋盘下满
+    Free,
+}
+
+/// 走棋命令校验失败的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommandError {
+    /// 序号与日志中下一个序号不一致
+    OutOfSequence { expected: u32, actual: u32 },
+
+    /// 不是该玩家的回合
+    WrongPlayer { expected: PlayerColor },
+
+    /// 不合法的落子位置
+    IllegalMove(u8),
+
+    /// 有棋可走时不允许跳过回合
+    IllegalPass,
+
+    /// 对局已经结束
+    GameOver,
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfSequence { expected, actual } => {
+                write!(f, "expected sequence {expected}, got {actual}")
+            }
+            Self::WrongPlayer { expected } => write!(f, "it is {expected:?}'s turn"),
+            Self::IllegalMove(position) => {
+                write!(
+                    f,
+                    "illegal move at {}",
+                    Board::position_to_notation(*position)
+                )
+            }
+            Self::IllegalPass => write!(f, "cannot pass while legal moves exist"),
+            Self::GameOver => write!(f, "the game is already over"),
+        }
+    }
+}
+
+/// 对局命令日志
+///
+/// 保存初始局面和已应用的命令，当前局面和轮到的玩家都由命令推导得出
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct GameLog {
+    initial: Board,
+    first_player: PlayerColor,
+    commands: Vec<MoveCommand>,
+    board: Board,
+    to_move: PlayerColor,
+    rule: PlacementRule,
+    victory: VictoryCondition,
+}
+
+impl GameLog {
+    /// 从初始局面创建空日志
+    pub fn new(initial: Board, first_player: PlayerColor) -> Self {
+        Self {
+            initial,
+            first_player,
+            commands: Vec::new(),
+            board: initial,
+            to_move: first_player,
+            rule: PlacementRule::Standard,
+            victory: VictoryCondition::Standard,
+        }
+    }
+
+    /// 使用指定的落子规则，只应在应用任何命令之前调用
+    pub fn with_rule(mut self, rule: PlacementRule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// 使用指定的胜负条件，只应在应用任何命令之前调用
+    pub fn with_victory(mut self, victory: VictoryCondition) -> Self {
+        self.victory = victory;
+        self
+    }
+
+    /// 从初始局面依次应用命令，重建日志并校验每一条命令
+    pub fn replay(
+        initial: Board,
+        first_player: PlayerColor,
+        commands: &[MoveCommand],
+    ) -> Result<Self, CommandError> {
+        let mut log = Self::new(initial, first_player);
+        for command in commands {
+            log.apply(*command)?;
+        }
+        Ok(log)
+    }
+
+    /// 初始局面
+    #[allow(dead_code)]
+    pub fn initial(&self) -> Board {
+        self.initial
+    }
+
+    /// 先行的玩家
+    #[allow(dead_code)]
+    pub fn first_player(&self) -> PlayerColor {
+        self.first_player
+    }
+
+    /// 落子规则
+    pub fn rule(&self) -> PlacementRule {
+        self.rule
+    }
+
+    /// 胜负条件
+    pub fn victory(&self) -> VictoryCondition {
+        self.victory
+    }
+
+    /// 按落子规则判断对局是否结束：标准规则下双方都无棋可走，自由落子时棋盘下满；
+    /// 胜负条件规定了目标子数时，任一方达到目标也立即结束
+    pub fn is_game_over(&self) -> bool {
+        if self.victory.ends_early(&self.board) {
+            return true;
+        }
+        match self.rule {
+            PlacementRule::Standard => self.board.is_game_over(),
+            PlacementRule::Free => self.board.get_empty_squares() == 0,
+        }
+    }
+
+    /// 轮到的玩家是否必须跳过回合，自由落子时总有空格可下
+    pub fn must_pass(&self) -> bool {
+        self.rule == PlacementRule::Standard
+            && !self.is_game_over()
+            && !self.board.has_valid_moves(self.to_move)
+    }
+
+    /// 按胜负条件判断的胜者，对局未结束或平局时为None
+    pub fn winner(&self) -> Option<PlayerColor> {
+        if !self.is_game_over() {
+            return None;
+        }
+        self.victory.winner(&self.board)
+    }
+
+    /// 当前局面
+    pub fn board(&self) -> Board {
+        self.board
+    }
+
+    /// 当前轮到的玩家
+    pub fn to_move(&self) -> PlayerColor {
+        self.to_move
+    }
+
+    /// 已应用的命令
+    pub fn commands(&self) -> &[MoveCommand] {
+        &self.commands
+    }
+
+    /// 下一条命令应使用的序号
+    pub fn next_sequence(&self) -> u32 {
+        self.commands.len() as u32
+    }
+
+    /// 为当前轮到的玩家创建下一条命令
+    pub fn next_command(&self, action: MoveAction) -> MoveCommand {
+        MoveCommand {
+            sequence: self.next_sequence(),
+            player: self.to_move,
+            action,
+        }
+    }
+
+    /// 校验命令能否应用到当前局面
+    pub fn validate(&self, command: &MoveCommand) -> Result<(), CommandError> {
+        if command.sequence != self.next_sequence() {
+            return Err(CommandError::OutOfSequence {
+                expected: self.next_sequence(),
+                actual: command.sequence,
+            });
+        }
+        if self.is_game_over() {
+            return Err(CommandError::GameOver);
+        }
+        if command.player != self.to_move {
+            return Err(CommandError::WrongPlayer {
+                expected: self.to_move,
+            });
+        }
+
+        match (self.rule, command.action) {
+            (PlacementRule::Standard, MoveAction::Place(position))
+                if !self.board.is_valid_move(position, command.player) =>
+            {
+                Err(CommandError::IllegalMove(position))
+            }
+            (PlacementRule::Free, MoveAction::Place(position))
+                if position >= 64 || !self.board.is_empty(position) =>
+            {
+                Err(CommandError::IllegalMove(position))
+            }
+            (PlacementRule::Standard, MoveAction::Pass)
+                if self.board.has_valid_moves(command.player) =>
+            {
+                Err(CommandError::IllegalPass)
+            }
+            (PlacementRule::Free, MoveAction::Pass) => Err(CommandError::IllegalPass),
+            _ => Ok(()),
+        }
+    }
+
+    /// 校验并应用命令，成功后轮到对手
+    pub fn apply(&mut self, command: MoveCommand) -> Result<(), CommandError> {
+        self.validate(&command)?;
+
+        if let MoveAction::Place(position) = command.action {
+            match self.rule {
+                PlacementRule::Standard => self.board.make_move(position, command.player),
+                PlacementRule::Free => self.board.place_free(position, command.player),
+            };
+        }
+        self.to_move = command.player.opposite();
+        self.commands.push(command);
+        Ok(())
+    }
+
+    /// 撤销最后一条命令，通过重放其余命令恢复局面
+    pub fn undo(&mut self) -> Option<MoveCommand> {
+        let undone = self.commands.pop()?;
+        let commands = std::mem::take(&mut self.commands);
+        let mut log = Self::new(self.initial, self.first_player)
+            .with_rule(self.rule)
+            .with_victory(self.victory);
+        for command in commands {
+            log.apply(command)
+                .expect("previously applied commands replay cleanly");
+        }
+        *self = log;
+        Some(undone)
+    }
+}
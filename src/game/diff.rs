@@ -0,0 +1,57 @@
+// 局面差异 - 两个局面之间新放、移除和变色的格子
+//
+// 棋子渲染、翻转动画、联机重新同步和回放步进都需要知道局面之间哪些格子变了，
+// 统一用Board::diff计算，不再各自比较棋子
+
+use super::Board;
+use serde::{Deserialize, Serialize};
+
+/// 从一个局面到另一个局面的变化，每个字段都是64位格子掩码
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoardDiff {
+    /// 原来为空、之后有棋子的格子
+    pub placed: u64,
+    /// 原来有棋子、之后为空的格子（悔棋或回看历史时出现）
+    pub removed: u64,
+    /// 两边都有棋子但颜色不同的格子
+    pub flipped: u64,
+}
+
+impl BoardDiff {
+    /// 两个局面是否相同
+    pub fn is_empty(&self) -> bool {
+        self.changed() == 0
+    }
+
+    /// 发生任何变化的格子
+    pub fn changed(&self) -> u64 {
+        self.placed | self.removed | self.flipped
+    }
+
+    /// 掩码中的格子位置，从小到大
+    pub fn squares(mask: u64) -> impl Iterator<Item = u8> {
+        (0..64u8).filter(move |position| mask & (1u64 << position) != 0)
+    }
+
+    /// 反向的变化，即从之后的局面回到原来的局面
+    pub fn reversed(&self) -> Self {
+        Self {
+            placed: self.removed,
+            removed: self.placed,
+            flipped: self.flipped,
+        }
+    }
+}
+
+impl Board {
+    /// 从本局面变为另一个局面时的变化
+    pub fn diff(&self, other: &Board) -> BoardDiff {
+        let before = self.black | self.white;
+        let after = other.black | other.white;
+        BoardDiff {
+            placed: after & !before,
+            removed: before & !after,
+            flipped: (self.black & other.white) | (self.white & other.black),
+        }
+    }
+}
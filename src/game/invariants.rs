@@ -0,0 +1,235 @@
+// 局面不变量检查 - 调试构建中每次应用走棋命令后校验棋盘状态，出错时带着可复现的局面崩溃
+//
+// 日志变化后从初始局面重放全部命令，逐步检查：黑白位棋盘不重叠、落子格原来为空、
+// 翻转的棋子与逐格沿八个方向查找的参考实现一致、棋子数守恒；最后检查棋盘组件与日志一致、
+// 合法走法缓存与重新计算的结果一致。发现问题时panic，信息中带局面字符串和走法，
+// 可以直接用parse_position_string复现。只在debug_assertions下运行（见invariant_checks_enabled）
+
+use super::{position_string, Board, GameLog, LegalMovesCache, MoveAction, PlayerColor};
+use bevy::prelude::*;
+use std::fmt;
+
+/// 违反的不变量
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// 同一格子同时有黑子和白子
+    OverlappingDiscs { squares: u64 },
+    /// 在已有棋子的格子上落子
+    OccupiedSquare { position: u8 },
+    /// 变化的格子与落子和翻转不符（出现了落子格以外的新棋子或棋子消失）
+    UnexpectedChange { placed: u64, removed: u64 },
+    /// 翻转的棋子与参考实现不一致
+    FlipMismatch { expected: u64, actual: u64 },
+    /// 跳过回合时棋盘发生了变化
+    ChangedOnPass,
+    /// 日志中的命令重放时被拒绝
+    ReplayRejected,
+    /// 棋盘组件与日志的局面不一致
+    BoardOutOfSync,
+    /// 合法走法缓存与重新计算的结果不一致
+    StaleLegalMoves {
+        player: PlayerColor,
+        cached: u64,
+        computed: u64,
+    },
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OverlappingDiscs { squares } => {
+                write!(f, "black and white discs overlap at {squares:#018x}")
+            }
+            Self::OccupiedSquare { position } => write!(
+                f,
+                "placed on occupied square {}",
+                Board::position_to_notation(*position)
+            ),
+            Self::UnexpectedChange { placed, removed } => write!(
+                f,
+                "unexpected change: placed {placed:#018x}, removed {removed:#018x}"
+            ),
+            Self::FlipMismatch { expected, actual } => write!(
+                f,
+                "flipped {actual:#018x} ({} discs), expected {expected:#018x} ({} discs)",
+                actual.count_ones(),
+                expected.count_ones()
+            ),
+            Self::ChangedOnPass => write!(f, "board changed on a pass"),
+            Self::ReplayRejected => write!(f, "logged command was rejected on replay"),
+            Self::BoardOutOfSync => write!(f, "board component differs from the game log"),
+            Self::StaleLegalMoves {
+                player,
+                cached,
+                computed,
+            } => write!(
+                f,
+                "legal moves for {player:?} cached as {cached:#018x}, computed {computed:#018x}"
+            ),
+        }
+    }
+}
+
+/// 落子会翻转的棋子，逐格沿八个方向查找，作为位运算实现的参照
+pub fn reference_flips(board: &Board, position: u8, player: PlayerColor) -> u64 {
+    let (row, col) = Board::position_to_coords(position);
+    let mut flipped = 0;
+    for (dr, dc) in [
+        (-1, -1),
+        (-1, 0),
+        (-1, 1),
+        (0, -1),
+        (0, 1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+    ] {
+        let mut ray = 0u64;
+        let (mut r, mut c) = (row as i32 + dr, col as i32 + dc);
+        while (0..8).contains(&r) && (0..8).contains(&c) {
+            let square = (r * 8 + c) as u8;
+            match board.get_piece(square) {
+                Some(color) if color == player => {
+                    flipped |= ray;
+                    break;
+                }
+                Some(_) => ray |= 1u64 << square,
+                None => break,
+            }
+            r += dr;
+            c += dc;
+        }
+    }
+    flipped
+}
+
+/// 检查一步棋前后的局面
+pub fn check_move(
+    before: &Board,
+    after: &Board,
+    player: PlayerColor,
+    action: MoveAction,
+) -> Result<(), InvariantViolation> {
+    let overlap = after.black & after.white;
+    if overlap != 0 {
+        return Err(InvariantViolation::OverlappingDiscs { squares: overlap });
+    }
+
+    let MoveAction::Place(position) = action else {
+        return if before == after {
+            Ok(())
+        } else {
+            Err(InvariantViolation::ChangedOnPass)
+        };
+    };
+    if !before.is_empty(position) {
+        return Err(InvariantViolation::OccupiedSquare { position });
+    }
+
+    let diff = before.diff(after);
+    let mask = 1u64 << position;
+    if diff.placed != mask || diff.removed != 0 {
+        return Err(InvariantViolation::UnexpectedChange {
+            placed: diff.placed,
+            removed: diff.removed,
+        });
+    }
+    let expected = reference_flips(before, position, player);
+    let player_discs = match player {
+        PlayerColor::Black => after.black,
+        PlayerColor::White => after.white,
+    };
+    if diff.flipped != expected || player_discs & (expected | mask) != expected | mask {
+        return Err(InvariantViolation::FlipMismatch {
+            expected,
+            actual: diff.flipped,
+        });
+    }
+    Ok(())
+}
+
+/// 合法走法缓存与重新计算的结果是否一致
+pub fn check_legal_moves(cache: &LegalMovesCache, board: &Board) -> Result<(), InvariantViolation> {
+    for player in [PlayerColor::Black, PlayerColor::White] {
+        let cached = cache.moves(board, player);
+        let computed = board.get_valid_moves(player);
+        if cached != computed {
+            return Err(InvariantViolation::StaleLegalMoves {
+                player,
+                cached,
+                computed,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 复现问题需要的局面和走法，例如"position \"...X\" move F5"
+pub fn reproduction(board: &Board, player: PlayerColor, action: MoveAction) -> String {
+    let action = match action {
+        MoveAction::Place(position) => Board::position_to_notation(position),
+        MoveAction::Pass => "pass".to_string(),
+    };
+    format!(
+        "position \"{}\" move {action}",
+        position_string(board, player)
+    )
+}
+
+/// 从初始局面重放日志，逐步检查，返回第一个问题和复现信息
+pub fn check_log(log: &GameLog) -> Result<(), (InvariantViolation, String)> {
+    let mut replay = GameLog::new(log.initial(), log.first_player())
+        .with_rule(log.rule())
+        .with_victory(log.victory());
+    for command in log.commands() {
+        let before = replay.board();
+        let fail = |violation| {
+            (
+                violation,
+                reproduction(&before, command.player, command.action),
+            )
+        };
+        // 日志中的命令都已校验过，重放被拒绝说明规则实现前后不一致
+        replay
+            .apply(*command)
+            .map_err(|_| fail(InvariantViolation::ReplayRejected))?;
+        check_move(&before, &replay.board(), command.player, command.action).map_err(fail)?;
+    }
+    Ok(())
+}
+
+/// 只在调试构建中检查不变量
+pub fn invariant_checks_enabled() -> bool {
+    cfg!(debug_assertions)
+}
+
+/// 走棋命令应用、合法走法缓存刷新之后检查不变量，发现问题时带着复现信息panic
+pub fn assert_board_invariants(
+    log_query: Query<(Ref<GameLog>, &Board)>,
+    cache: Res<LegalMovesCache>,
+) {
+    let Ok((log, board)) = log_query.single() else {
+        return;
+    };
+    if !log.is_changed() {
+        return;
+    }
+
+    if let Err((violation, reproduction)) = check_log(&log) {
+        panic!("Board invariant violated: {violation}; reproduce with {reproduction}");
+    }
+    let current = || position_string(&log.board(), log.to_move());
+    if *board != log.board() {
+        panic!(
+            "Board invariant violated: {}; current position \"{}\"",
+            InvariantViolation::BoardOutOfSync,
+            current()
+        );
+    }
+    if let Err(violation) = check_legal_moves(&cache, board) {
+        panic!(
+            "Board invariant violated: {violation}; current position \"{}\"",
+            current()
+        );
+    }
+}
@@ -0,0 +1,68 @@
+// 合法走法缓存 - 每个局面只计算一次双方的合法走法位掩码
+//
+// 悬停高亮、可落子指示、状态文本和终局判定每帧都要查询合法走法。
+// 走棋命令应用后棋盘改变，缓存随之重新计算；查询的局面与缓存不同时直接计算，结果总是正确的
+
+use super::{Board, PlayerColor};
+use bevy::prelude::*;
+
+/// 当前棋盘双方的合法走法
+#[derive(Resource, Debug, Default)]
+pub struct LegalMovesCache {
+    /// 缓存对应的局面
+    board: Option<Board>,
+    black: u64,
+    white: u64,
+}
+
+impl LegalMovesCache {
+    /// 为指定局面重新计算合法走法，局面未变化时不计算，返回是否重新计算
+    pub fn update(&mut self, board: &Board) -> bool {
+        if self.board == Some(*board) {
+            return false;
+        }
+        self.board = Some(*board);
+        self.black = board.get_valid_moves(PlayerColor::Black);
+        self.white = board.get_valid_moves(PlayerColor::White);
+        true
+    }
+
+    /// 指定局面中一方的合法走法位掩码
+    pub fn moves(&self, board: &Board, player: PlayerColor) -> u64 {
+        if self.board != Some(*board) {
+            return board.get_valid_moves(player);
+        }
+        match player {
+            PlayerColor::Black => self.black,
+            PlayerColor::White => self.white,
+        }
+    }
+
+    /// 落子是否合法
+    pub fn is_legal(&self, board: &Board, position: u8, player: PlayerColor) -> bool {
+        position < 64 && self.moves(board, player) & (1u64 << position) != 0
+    }
+
+    /// 一方是否有棋可走
+    pub fn has_moves(&self, board: &Board, player: PlayerColor) -> bool {
+        self.moves(board, player) != 0
+    }
+
+    /// 双方都无棋可走时对局结束
+    pub fn is_game_over(&self, board: &Board) -> bool {
+        !self.has_moves(board, PlayerColor::Black) && !self.has_moves(board, PlayerColor::White)
+    }
+}
+
+/// 棋盘变化后刷新缓存，需要在走棋命令应用之后、读取缓存的系统之前运行
+pub fn refresh_legal_moves_cache(
+    board_query: Query<Ref<Board>>,
+    mut cache: ResMut<LegalMovesCache>,
+) {
+    let Ok(board) = board_query.single() else {
+        return;
+    };
+    if board.is_changed() {
+        cache.update(&board);
+    }
+}
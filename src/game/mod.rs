@@ -1,4 +1,19 @@
 pub mod board;
+pub mod command;
+pub mod diff;
+pub mod invariants;
+pub mod legal_moves;
+pub mod position;
 pub mod rules;
+pub mod symmetry;
+pub mod victory;
 
 pub use board::*;
+pub use command::*;
+pub use diff::*;
+pub use invariants::*;
+pub use legal_moves::*;
+pub use position::*;
+pub use rules::FlipRay;
+pub use symmetry::*;
+pub use victory::*;
@@ -0,0 +1,76 @@
+// 局面编码 - 局面字符串和局面哈希
+//
+// 局面字符串为64个字符（A1到H8逐行，X为黑棋、O为白棋、-为空格）加一个空格和轮到的一方（X或O），
+// 外部引擎和联机重新同步都使用这种格式。局面哈希用于联机时每步比对双方局面，
+// 使用固定的FNV-1a算法，不同平台和版本算出的结果相同
+
+use super::{Board, PlayerColor};
+
+/// 局面字符串的长度
+pub const POSITION_STRING_LEN: usize = 66;
+
+fn color_char(color: PlayerColor) -> char {
+    match color {
+        PlayerColor::Black => 'X',
+        PlayerColor::White => 'O',
+    }
+}
+
+fn char_color(c: char) -> Option<PlayerColor> {
+    match c {
+        'X' => Some(PlayerColor::Black),
+        'O' => Some(PlayerColor::White),
+        _ => None,
+    }
+}
+
+/// 局面字符串：64个格子加轮到的一方
+pub fn position_string(board: &Board, to_move: PlayerColor) -> String {
+    let mut position: String = (0..64)
+        .map(|square| board.get_piece(square).map_or('-', color_char))
+        .collect();
+    position.push(' ');
+    position.push(color_char(to_move));
+    position
+}
+
+/// 解析局面字符串，格式不正确时返回None
+pub fn parse_position_string(position: &str) -> Option<(Board, PlayerColor)> {
+    let (squares, to_move) = position.split_once(' ')?;
+    if squares.chars().count() != 64 {
+        return None;
+    }
+
+    let mut board = Board { black: 0, white: 0 };
+    for (square, c) in squares.chars().enumerate() {
+        match c {
+            'X' => board.black |= 1 << square,
+            'O' => board.white |= 1 << square,
+            '-' => {}
+            _ => return None,
+        }
+    }
+
+    let mut to_move = to_move.chars();
+    let color = to_move.next().and_then(char_color)?;
+    if to_move.next().is_some() {
+        return None;
+    }
+    Some((board, color))
+}
+
+/// 局面哈希，包含棋盘和轮到的一方
+pub fn position_hash(board: &Board, to_move: PlayerColor) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    board
+        .black
+        .to_le_bytes()
+        .into_iter()
+        .chain(board.white.to_le_bytes())
+        .chain([color_char(to_move) as u8])
+        .fold(FNV_OFFSET, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        })
+}
@@ -11,6 +11,37 @@ const DIRECTIONS: [(i8, i8); 8] = [
     (1, 1),
 ];
 
+/// 一个方向上被翻转的棋子
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlipRay {
+    /// 方向：每一步的（行、列）变化
+    pub direction: (i8, i8),
+    /// 被翻转的棋子，按与落子位置的距离由近到远排列
+    pub discs: Vec<u8>,
+}
+
+impl FlipRay {
+    /// 是否沿同一行（横线）
+    pub fn is_horizontal(&self) -> bool {
+        self.direction.0 == 0
+    }
+
+    /// 是否沿同一列（竖线）
+    pub fn is_vertical(&self) -> bool {
+        self.direction.1 == 0
+    }
+
+    /// 是否沿对角线
+    pub fn is_diagonal(&self) -> bool {
+        self.direction.0 != 0 && self.direction.1 != 0
+    }
+
+    /// 这条线上离落子位置最远的被翻转棋子
+    pub fn farthest(&self) -> Option<u8> {
+        self.discs.last().copied()
+    }
+}
+
 impl Board {
     pub fn get_valid_moves(&self, player: PlayerColor) -> u64 {
         let (own, opp) = match player {
@@ -55,6 +86,23 @@ impl Board {
             return false;
         }
 
+        self.place_and_flip(position, player);
+        true
+    }
+
+    /// 自由落子：任意空格都可以落子，照常翻转被夹住的对方棋子（可能一枚也没有）
+    ///
+    /// 位置超出棋盘或已有棋子时返回false
+    pub fn place_free(&mut self, position: u8, player: PlayerColor) -> bool {
+        if position >= 64 || !self.is_empty(position) {
+            return false;
+        }
+
+        self.place_and_flip(position, player);
+        true
+    }
+
+    fn place_and_flip(&mut self, position: u8, player: PlayerColor) {
         let mask = 1u64 << position;
         let flipped = self.get_flipped_discs(position, player);
 
@@ -68,8 +116,6 @@ impl Board {
                 self.black &= !flipped;
             }
         }
-
-        true
     }
 
     fn get_moves_in_direction(&self, own: u64, opp: u64, empty: u64, dx: i8, dy: i8) -> u64 {
@@ -144,6 +190,68 @@ impl Board {
         flipped
     }
 
+    /// 落子后被翻转的棋子数，不合法的落子返回0
+    pub fn flip_count(&self, position: u8, player: PlayerColor) -> u32 {
+        if !self.is_valid_move(position, player) {
+            return 0;
+        }
+        self.get_flipped_discs(position, player).count_ones()
+    }
+
+    /// 落子后被翻转的棋子，按方向分组，每组按与落子位置的距离由近到远排列
+    ///
+    /// 不合法的落子返回空列表，没有翻转棋子的方向不出现在结果中
+    pub fn flipped_rays(&self, position: u8, player: PlayerColor) -> Vec<Vec<u8>> {
+        self.get_flipped_discs_by_direction(position, player)
+            .into_iter()
+            .map(|ray| ray.discs)
+            .collect()
+    }
+
+    /// 落子后被翻转的棋子，按方向分组并带上方向，供翻转动画、合法性说明和点评描述吃掉的是哪条线
+    ///
+    /// 每组按与落子位置的距离由近到远排列；不合法的落子返回空列表，没有翻转棋子的方向不出现在结果中
+    pub fn get_flipped_discs_by_direction(
+        &self,
+        position: u8,
+        player: PlayerColor,
+    ) -> Vec<FlipRay> {
+        if !self.is_valid_move(position, player) {
+            return Vec::new();
+        }
+
+        let flipped = self.get_flipped_discs(position, player);
+        let row = (position / 8) as i8;
+        let col = (position % 8) as i8;
+        let mut rays = Vec::new();
+
+        for &(dx, dy) in &DIRECTIONS {
+            let mut discs = Vec::new();
+            let mut r = row + dx;
+            let mut c = col + dy;
+
+            while (0..8).contains(&r) && (0..8).contains(&c) {
+                let check_pos = (r * 8 + c) as u8;
+                if flipped & (1u64 << check_pos) == 0 {
+                    break;
+                }
+                discs.push(check_pos);
+
+                r += dx;
+                c += dy;
+            }
+
+            if !discs.is_empty() {
+                rays.push(FlipRay {
+                    direction: (dx, dy),
+                    discs,
+                });
+            }
+        }
+
+        rays
+    }
+
     pub fn has_valid_moves(&self, player: PlayerColor) -> bool {
         self.get_valid_moves(player) != 0
     }
@@ -0,0 +1,136 @@
+// 棋盘对称 - 棋盘的8种旋转和镜像变换，以及对称局面的规范代表
+//
+// 黑白棋的规则在正方形的8种对称变换（二面体群D4）下不变，
+// 互为对称的局面评估相同、合法走法一一对应。开局库、分析中的局面去重和棋型评估
+// 都可以先把局面变换到规范形式，再用对应的变换把走法换算回原局面
+
+use super::Board;
+
+/// 棋盘的一种对称变换，坐标为(行, 列)，A1在左上角
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    /// 不变
+    Identity,
+    /// 顺时针旋转90度：(r, c) → (c, 7-r)
+    Rotate90,
+    /// 旋转180度：(r, c) → (7-r, 7-c)
+    Rotate180,
+    /// 顺时针旋转270度：(r, c) → (7-c, r)
+    Rotate270,
+    /// 左右镜像：(r, c) → (r, 7-c)
+    FlipHorizontal,
+    /// 上下镜像：(r, c) → (7-r, c)
+    FlipVertical,
+    /// 沿A1-H8对角线镜像：(r, c) → (c, r)
+    FlipDiagonal,
+    /// 沿H1-A8对角线镜像：(r, c) → (7-c, 7-r)
+    FlipAntiDiagonal,
+}
+
+impl Symmetry {
+    /// 全部8种变换，Identity在最前
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::FlipDiagonal,
+        Symmetry::FlipAntiDiagonal,
+    ];
+
+    /// 逆变换，把变换后的位置换算回原位置
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::Rotate90 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate90,
+            other => other,
+        }
+    }
+
+    /// 变换一个位置
+    pub fn apply_position(self, position: u8) -> u8 {
+        let (row, col) = (position / 8, position % 8);
+        let (row, col) = match self {
+            Symmetry::Identity => (row, col),
+            Symmetry::Rotate90 => (col, 7 - row),
+            Symmetry::Rotate180 => (7 - row, 7 - col),
+            Symmetry::Rotate270 => (7 - col, row),
+            Symmetry::FlipHorizontal => (row, 7 - col),
+            Symmetry::FlipVertical => (7 - row, col),
+            Symmetry::FlipDiagonal => (col, row),
+            Symmetry::FlipAntiDiagonal => (7 - col, 7 - row),
+        };
+        row * 8 + col
+    }
+
+    /// 变换一个位棋盘，第i位表示位置i
+    pub fn apply_bits(self, bits: u64) -> u64 {
+        match self {
+            Symmetry::Identity => bits,
+            Symmetry::Rotate90 => flip_horizontal(transpose(bits)),
+            Symmetry::Rotate180 => bits.reverse_bits(),
+            Symmetry::Rotate270 => transpose(bits).swap_bytes(),
+            Symmetry::FlipHorizontal => flip_horizontal(bits),
+            Symmetry::FlipVertical => bits.swap_bytes(),
+            Symmetry::FlipDiagonal => transpose(bits),
+            Symmetry::FlipAntiDiagonal => transpose(bits).reverse_bits(),
+        }
+    }
+}
+
+/// 每行内部左右翻转：(r, c) → (r, 7-c)
+fn flip_horizontal(bits: u64) -> u64 {
+    bits.reverse_bits().swap_bytes()
+}
+
+/// 沿A1-H8对角线转置：(r, c) → (c, r)，按2×2、4×4、8×8块依次交换
+fn transpose(mut bits: u64) -> u64 {
+    const K1: u64 = 0x5500_5500_5500_5500;
+    const K2: u64 = 0x3333_0000_3333_0000;
+    const K4: u64 = 0x0f0f_0f0f_0000_0000;
+    let swap = K4 & (bits ^ (bits << 28));
+    bits ^= swap ^ (swap >> 28);
+    let swap = K2 & (bits ^ (bits << 14));
+    bits ^= swap ^ (swap >> 14);
+    let swap = K1 & (bits ^ (bits << 7));
+    bits ^= swap ^ (swap >> 7);
+    bits
+}
+
+impl Board {
+    /// 对棋盘做一种对称变换
+    pub fn transformed(&self, symmetry: Symmetry) -> Board {
+        Board {
+            black: symmetry.apply_bits(self.black),
+            white: symmetry.apply_bits(self.white),
+        }
+    }
+
+    /// 8种对称变换后的棋盘，顺序与`Symmetry::ALL`相同
+    pub fn symmetries(&self) -> [Board; 8] {
+        Symmetry::ALL.map(|symmetry| self.transformed(symmetry))
+    }
+
+    /// 得到规范形式的变换：变换后(黑棋, 白棋)位棋盘最小，相同时取`Symmetry::ALL`中靠前的
+    pub fn canonical_symmetry(&self) -> Symmetry {
+        Symmetry::ALL
+            .into_iter()
+            .min_by_key(|&symmetry| {
+                let board = self.transformed(symmetry);
+                (board.black, board.white)
+            })
+            .unwrap_or(Symmetry::Identity)
+    }
+
+    /// 对称局面的规范代表，互为对称的局面规范形式相同
+    pub fn canonical_form(&self) -> Board {
+        self.transformed(self.canonical_symmetry())
+    }
+
+    /// 局面与另一个局面是否互为对称
+    pub fn is_symmetric_to(&self, other: &Board) -> bool {
+        self.canonical_form() == other.canonical_form()
+    }
+}
@@ -0,0 +1,91 @@
+// 胜负条件 - 自定义对局的胜负规则，用于休闲的变体玩法
+//
+// 标准规则下对局在双方都无棋可走时结束，子多者胜。自定义规则在此基础上调整：
+// 领先规定子数才算获胜（否则算平局），或者任一方先达到规定子数时立即结束并获胜。
+// 规则保存在偏好设置中（RulesConfig），开始新对局时写入命令日志，对局中途修改不影响当前对局；
+// 同样保存在其中的还有多局比赛的赛制（见series）和对局计时规则（见clock）
+
+use super::{Board, PlayerColor};
+use crate::{clock::TimeControl, series::MatchFormat};
+use serde::{Deserialize, Serialize};
+
+/// 设置面板中"领先获胜"的默认子数
+pub const DEFAULT_WIN_MARGIN: u32 = 10;
+
+/// 设置面板中"先到获胜"的默认子数
+pub const DEFAULT_TARGET_DISCS: u32 = 40;
+
+/// 胜负条件
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VictoryCondition {
+    /// 标准规则：终局时子多者胜
+    #[default]
+    Standard,
+
+    /// 终局时至少领先指定子数才算获胜，领先不足算平局
+    WinByMargin(u32),
+
+    /// 任一方棋子数达到指定值时对局立即结束，该方获胜；都没有达到时按终局子数判断
+    FirstTo(u32),
+}
+
+impl VictoryCondition {
+    /// 设置面板中依次切换的规则
+    pub fn next(self) -> Self {
+        match self {
+            Self::Standard => Self::WinByMargin(DEFAULT_WIN_MARGIN),
+            Self::WinByMargin(_) => Self::FirstTo(DEFAULT_TARGET_DISCS),
+            Self::FirstTo(_) => Self::Standard,
+        }
+    }
+
+    /// 先达到目标子数的一方，其他规则下总是None
+    pub fn reached_target(self, board: &Board) -> Option<PlayerColor> {
+        let Self::FirstTo(target) = self else {
+            return None;
+        };
+        [PlayerColor::Black, PlayerColor::White]
+            .into_iter()
+            .find(|color| board.count_pieces(*color) >= target)
+    }
+
+    /// 对局是否因达到目标子数而提前结束
+    pub fn ends_early(self, board: &Board) -> bool {
+        self.reached_target(board).is_some()
+    }
+
+    /// 按本规则判断已结束对局的胜者，平局时为None
+    pub fn winner(self, board: &Board) -> Option<PlayerColor> {
+        if let Some(color) = self.reached_target(board) {
+            return Some(color);
+        }
+
+        let black = board.count_pieces(PlayerColor::Black) as i32;
+        let white = board.count_pieces(PlayerColor::White) as i32;
+        let required = match self {
+            Self::WinByMargin(margin) => margin.max(1) as i32,
+            Self::Standard | Self::FirstTo(_) => 1,
+        };
+        if black - white >= required {
+            Some(PlayerColor::Black)
+        } else if white - black >= required {
+            Some(PlayerColor::White)
+        } else {
+            None
+        }
+    }
+}
+
+/// 自定义对局规则，保存在偏好设置中，开始新对局时生效
+///
+/// 新增字段需要提供默认值，以兼容旧版本保存的文件
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct RulesConfig {
+    /// 胜负条件
+    pub victory: VictoryCondition,
+    /// 多局比赛的赛制，每局交换执子颜色
+    pub match_format: MatchFormat,
+    /// 对局计时规则，只用于有AI的对局
+    pub time_control: TimeControl,
+}
@@ -0,0 +1,197 @@
+// 走棋历史模块 - 记录每一步后的棋盘快照，并支持浏览历史局面
+//
+// 对局中可以通过手势回看之前的局面：
+// - 触摸：左右滑动
+// - 触控板：水平滚动
+// - 键盘：左右方向键
+// 任何新的点击、触摸或落子都会回到实时局面
+
+use crate::{
+    game::{Board, PlayerColor},
+    input::{InputAction, InputMap},
+    ui::CurrentPlayer,
+};
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+
+/// 判定为滑动手势的最小水平距离（逻辑像素），小于该距离视为点击
+pub const SWIPE_MIN_DISTANCE: f32 = 60.0;
+
+/// 触控板像素滚动累计多少距离切换一步
+const SCROLL_PIXELS_PER_STEP: f32 = 40.0;
+
+/// 单个历史快照
+#[derive(Debug, Clone, Copy)]
+pub struct HistorySnapshot {
+    /// 该步之后的棋盘
+    pub board: Board,
+    /// 该步之后轮到的玩家
+    #[allow(dead_code)]
+    pub current_player: PlayerColor,
+}
+
+/// 走棋历史资源 - 第0个快照为开局局面
+#[derive(Resource, Default)]
+pub struct MoveHistory {
+    pub snapshots: Vec<HistorySnapshot>,
+}
+
+impl MoveHistory {
+    /// 已走的步数（不含开局局面）
+    pub fn move_count(&self) -> usize {
+        self.snapshots.len().saturating_sub(1)
+    }
+
+    /// 获取指定索引的快照
+    pub fn get(&self, index: usize) -> Option<&HistorySnapshot> {
+        self.snapshots.get(index)
+    }
+}
+
+/// 历史浏览状态 - None表示显示实时局面
+#[derive(Resource, Default)]
+pub struct HistoryPreview {
+    pub index: Option<usize>,
+}
+
+impl HistoryPreview {
+    /// 是否正在浏览历史局面
+    pub fn is_previewing(&self) -> bool {
+        self.index.is_some()
+    }
+
+    /// 后退一步，从实时局面开始时跳到上一步
+    pub fn step_back(&mut self, history: &MoveHistory) {
+        let latest = history.move_count();
+        if latest == 0 {
+            return;
+        }
+        let current = self.index.unwrap_or(latest);
+        self.index = Some(current.saturating_sub(1));
+    }
+
+    /// 前进一步，到达最新一步时回到实时局面
+    pub fn step_forward(&mut self, history: &MoveHistory) {
+        if let Some(current) = self.index {
+            let next = current + 1;
+            self.index = if next >= history.move_count() {
+                None
+            } else {
+                Some(next)
+            };
+        }
+    }
+
+    /// 回到实时局面
+    pub fn snap_to_live(&mut self) {
+        if self.index.is_some() {
+            self.index = None;
+        }
+    }
+
+    /// 当前应显示的棋盘：浏览历史时为快照，否则为实时棋盘
+    pub fn displayed_board(&self, history: &MoveHistory, live: &Board) -> Board {
+        self.index
+            .and_then(|index| history.get(index))
+            .map(|snapshot| snapshot.board)
+            .unwrap_or(*live)
+    }
+}
+
+/// 记录棋盘快照
+///
+/// 新棋盘生成时重置历史，棋盘变化时追加快照并回到实时局面
+pub fn record_history_snapshots(
+    board_query: Query<Ref<Board>>,
+    current_player: Res<CurrentPlayer>,
+    mut history: ResMut<MoveHistory>,
+    mut preview: ResMut<HistoryPreview>,
+) {
+    let Ok(board) = board_query.single() else {
+        return;
+    };
+
+    let snapshot = HistorySnapshot {
+        board: *board,
+        current_player: current_player.0,
+    };
+
+    if board.is_added() {
+        history.snapshots = vec![snapshot];
+        preview.snap_to_live();
+    } else if board.is_changed() {
+        // 棋子只增不减，局面不会重复出现；回到之前的局面说明发生了悔棋，丢弃之后的快照
+        if let Some(index) = history
+            .snapshots
+            .iter()
+            .position(|earlier| earlier.board == *board)
+        {
+            if index + 1 < history.snapshots.len() {
+                history.snapshots.truncate(index + 1);
+                preview.snap_to_live();
+            }
+        } else {
+            history.snapshots.push(snapshot);
+            preview.snap_to_live();
+        }
+    }
+}
+
+/// 历史浏览手势：水平滚动、左右滑动和方向键
+pub fn history_gesture_system(
+    mut wheel_events: EventReader<MouseWheel>,
+    touch_input: Res<Touches>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    history: Res<MoveHistory>,
+    mut preview: ResMut<HistoryPreview>,
+    mut scroll_accumulator: Local<f32>,
+) {
+    // 正数表示后退，负数表示前进
+    let mut steps: i32 = 0;
+
+    for event in wheel_events.read() {
+        match event.unit {
+            MouseScrollUnit::Line => {
+                if event.x > 0.0 {
+                    steps += 1;
+                } else if event.x < 0.0 {
+                    steps -= 1;
+                }
+            }
+            MouseScrollUnit::Pixel => {
+                *scroll_accumulator += event.x;
+                while scroll_accumulator.abs() >= SCROLL_PIXELS_PER_STEP {
+                    let direction = scroll_accumulator.signum();
+                    steps += direction as i32;
+                    *scroll_accumulator -= direction * SCROLL_PIXELS_PER_STEP;
+                }
+            }
+        }
+    }
+
+    // 向右滑动回看上一步，向左滑动前进
+    for touch in touch_input.iter_just_released() {
+        let distance = touch.position() - touch.start_position();
+        if distance.x.abs() >= SWIPE_MIN_DISTANCE && distance.x.abs() > distance.y.abs() * 2.0 {
+            steps += if distance.x > 0.0 { 1 } else { -1 };
+        }
+    }
+
+    if input_map.just_pressed(InputAction::StepBack, &keyboard_input) {
+        steps += 1;
+    }
+    if input_map.just_pressed(InputAction::StepForward, &keyboard_input) {
+        steps -= 1;
+    }
+
+    for _ in 0..steps.abs() {
+        if steps > 0 {
+            preview.step_back(&history);
+        } else {
+            preview.step_forward(&history);
+        }
+    }
+}
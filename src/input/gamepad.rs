@@ -0,0 +1,145 @@
+// 手柄输入 - 把手柄按键转换为与具体界面无关的操作
+//
+// 按键映射：
+// - 十字键 / 左摇杆：移动棋盘光标或界面焦点
+// - A（下方按键）：落子或按下焦点按钮
+// - B（右方按键）/ Start：打开或关闭暂停菜单
+// - 肩键和扳机：在界面按钮之间循环切换焦点
+
+use bevy::prelude::*;
+
+/// 摇杆判定为方向输入的阈值
+const STICK_THRESHOLD: f32 = 0.6;
+
+/// 摇杆保持同一方向时重复移动的间隔（秒）
+const STICK_REPEAT_SECS: f32 = 0.25;
+
+/// 手柄操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamepadAction {
+    /// 按方向移动一格，x向右、y向下为正，与棋盘的行列方向一致
+    Move(IVec2),
+    /// 确认：落子或按下焦点按钮
+    Confirm,
+    /// 打开或关闭暂停菜单
+    Pause,
+    /// 循环切换界面焦点，正数向后
+    CycleFocus(i32),
+}
+
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GamepadActionEvent(pub GamepadAction);
+
+/// 手柄操作的棋盘光标，指针操作时隐藏
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardCursor {
+    pub position: u8,
+    pub visible: bool,
+}
+
+impl Default for BoardCursor {
+    fn default() -> Self {
+        // 从中心附近开始，开局的可落子位置都在一两步之内
+        Self {
+            position: 19,
+            visible: false,
+        }
+    }
+}
+
+impl BoardCursor {
+    /// 按方向移动一格，停在棋盘边缘
+    pub fn step(&mut self, direction: IVec2) {
+        let row = (self.position / 8) as i32 + direction.y;
+        let col = (self.position % 8) as i32 + direction.x;
+        self.position = (row.clamp(0, 7) * 8 + col.clamp(0, 7)) as u8;
+        self.visible = true;
+    }
+}
+
+/// 摇杆方向的重复状态
+#[derive(Default)]
+pub struct StickRepeat {
+    direction: IVec2,
+    elapsed: f32,
+}
+
+/// 读取所有手柄的输入并转换为手柄操作
+pub fn read_gamepad_actions(
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    mut stick_repeat: Local<StickRepeat>,
+    mut actions: EventWriter<GamepadActionEvent>,
+) {
+    let mut stick = Vec2::ZERO;
+
+    for gamepad in gamepads.iter() {
+        let buttons = [
+            (GamepadButton::DPadUp, GamepadAction::Move(IVec2::NEG_Y)),
+            (GamepadButton::DPadDown, GamepadAction::Move(IVec2::Y)),
+            (GamepadButton::DPadLeft, GamepadAction::Move(IVec2::NEG_X)),
+            (GamepadButton::DPadRight, GamepadAction::Move(IVec2::X)),
+            (GamepadButton::South, GamepadAction::Confirm),
+            (GamepadButton::East, GamepadAction::Pause),
+            (GamepadButton::Start, GamepadAction::Pause),
+            (GamepadButton::LeftTrigger, GamepadAction::CycleFocus(-1)),
+            (GamepadButton::LeftTrigger2, GamepadAction::CycleFocus(-1)),
+            (GamepadButton::RightTrigger, GamepadAction::CycleFocus(1)),
+            (GamepadButton::RightTrigger2, GamepadAction::CycleFocus(1)),
+        ];
+        for (button, action) in buttons {
+            if gamepad.just_pressed(button) {
+                actions.write(GamepadActionEvent(action));
+            }
+        }
+
+        if gamepad.left_stick().length() > stick.length() {
+            stick = gamepad.left_stick();
+        }
+    }
+
+    // 摇杆：推到新方向时立即移动，保持不动时按固定间隔重复
+    let direction = stick_direction(stick);
+    if direction == IVec2::ZERO {
+        *stick_repeat = StickRepeat::default();
+        return;
+    }
+    if direction != stick_repeat.direction {
+        *stick_repeat = StickRepeat {
+            direction,
+            elapsed: 0.0,
+        };
+        actions.write(GamepadActionEvent(GamepadAction::Move(direction)));
+        return;
+    }
+
+    stick_repeat.elapsed += time.delta_secs();
+    if stick_repeat.elapsed >= STICK_REPEAT_SECS {
+        stick_repeat.elapsed = 0.0;
+        actions.write(GamepadActionEvent(GamepadAction::Move(direction)));
+    }
+}
+
+/// 摇杆的主方向，未超过阈值时为零
+fn stick_direction(stick: Vec2) -> IVec2 {
+    if stick.length() < STICK_THRESHOLD {
+        IVec2::ZERO
+    } else if stick.x.abs() > stick.y.abs() {
+        IVec2::new(stick.x.signum() as i32, 0)
+    } else {
+        // 摇杆向上为正，棋盘行号向下增加
+        IVec2::new(0, -stick.y.signum() as i32)
+    }
+}
+
+/// 使用鼠标或触摸时隐藏棋盘光标
+pub fn hide_board_cursor_on_pointer(
+    mut cursor_moved: EventReader<CursorMoved>,
+    touch_input: Res<Touches>,
+    mut board_cursor: ResMut<BoardCursor>,
+) {
+    let pointer_used = cursor_moved.read().count() > 0 || touch_input.any_just_pressed();
+    if pointer_used && board_cursor.visible {
+        board_cursor.visible = false;
+    }
+}
@@ -0,0 +1,273 @@
+// 键盘快捷键 - 快捷键动作和按键的对应表
+//
+// 所有键盘快捷键都通过InputMap查询，不在各个系统中直接写按键；
+// 快捷键帮助（?或H键）按这张表和当前游戏状态生成，修改绑定后帮助内容随之更新
+
+use crate::state::GameState;
+use bevy::prelude::*;
+
+/// 快捷键动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputAction {
+    /// 显示或关闭快捷键帮助
+    ToggleHelp,
+    /// 开关音效
+    ToggleSound,
+    /// 开关走棋播报
+    ToggleAnnouncements,
+    /// 切换全屏
+    ToggleFullscreen,
+    /// 切换窗口尺寸预设
+    CycleWindowSize,
+    /// 暂停或继续对局
+    Pause,
+    /// 悔棋
+    Undo,
+    /// 显示后台算好的最佳走法
+    Hint,
+    /// 显示格子编号（调试用）
+    ToggleSquareLabels,
+    /// 显示搜索统计（调试用）
+    ToggleDebugOverlay,
+    /// 显示性能信息
+    TogglePerformanceOverlay,
+    /// 回看上一步
+    StepBack,
+    /// 前进一步
+    StepForward,
+    /// 终局后开始下一局
+    Restart,
+    /// 终局后进入复盘分析
+    Analyze,
+    /// 终局后导出棋谱
+    ExportTranscript,
+    /// 终局后导出分析报告
+    ExportReport,
+    /// 分析中切换到上一个变着
+    PreviousVariation,
+    /// 分析中切换到下一个变着
+    NextVariation,
+    /// 分析中回到主线
+    MainLine,
+    /// 离开复盘分析或回放
+    LeaveAnalysis,
+}
+
+impl InputAction {
+    /// 所有动作，也是快捷键帮助中的顺序
+    pub const ALL: [InputAction; 21] = [
+        InputAction::ToggleHelp,
+        InputAction::ToggleSound,
+        InputAction::ToggleAnnouncements,
+        InputAction::ToggleFullscreen,
+        InputAction::CycleWindowSize,
+        InputAction::Pause,
+        InputAction::Undo,
+        InputAction::Hint,
+        InputAction::ToggleSquareLabels,
+        InputAction::ToggleDebugOverlay,
+        InputAction::TogglePerformanceOverlay,
+        InputAction::StepBack,
+        InputAction::StepForward,
+        InputAction::Restart,
+        InputAction::Analyze,
+        InputAction::ExportTranscript,
+        InputAction::ExportReport,
+        InputAction::PreviousVariation,
+        InputAction::NextVariation,
+        InputAction::MainLine,
+        InputAction::LeaveAnalysis,
+    ];
+
+    /// 默认绑定的按键
+    pub fn default_keys(self) -> &'static [KeyCode] {
+        match self {
+            // Slash即?键，按住Shift时同样触发
+            Self::ToggleHelp => &[KeyCode::Slash, KeyCode::KeyH],
+            Self::ToggleSound => &[KeyCode::KeyM],
+            Self::ToggleAnnouncements => &[KeyCode::KeyN],
+            Self::ToggleFullscreen => &[KeyCode::F11],
+            Self::CycleWindowSize => &[KeyCode::F10],
+            Self::Pause => &[KeyCode::Escape],
+            Self::Undo => &[KeyCode::KeyU],
+            Self::Hint => &[KeyCode::KeyT],
+            Self::ToggleSquareLabels => &[KeyCode::F3],
+            Self::ToggleDebugOverlay => &[KeyCode::F4],
+            Self::TogglePerformanceOverlay => &[KeyCode::F2],
+            Self::StepBack => &[KeyCode::ArrowLeft],
+            Self::StepForward => &[KeyCode::ArrowRight],
+            Self::Restart => &[KeyCode::Space, KeyCode::Enter],
+            Self::Analyze => &[KeyCode::KeyA],
+            Self::ExportTranscript => &[KeyCode::KeyE],
+            Self::ExportReport => &[KeyCode::KeyR],
+            Self::PreviousVariation => &[KeyCode::ArrowUp],
+            Self::NextVariation => &[KeyCode::ArrowDown],
+            Self::MainLine => &[KeyCode::Home],
+            Self::LeaveAnalysis => &[KeyCode::Escape],
+        }
+    }
+
+    /// 动作在指定游戏状态下是否可用
+    pub fn is_active(self, state: GameState) -> bool {
+        let analysis = matches!(state, GameState::Analysis | GameState::Replay);
+        match self {
+            Self::ToggleHelp
+            | Self::ToggleSound
+            | Self::ToggleAnnouncements
+            | Self::ToggleFullscreen
+            | Self::CycleWindowSize
+            | Self::TogglePerformanceOverlay => true,
+            Self::Pause
+            | Self::Undo
+            | Self::Hint
+            | Self::ToggleSquareLabels
+            | Self::ToggleDebugOverlay => state == GameState::Playing,
+            Self::StepBack | Self::StepForward => state == GameState::Playing || analysis,
+            Self::Restart | Self::Analyze | Self::ExportTranscript | Self::ExportReport => {
+                state == GameState::GameOver
+            }
+            Self::PreviousVariation | Self::NextVariation | Self::MainLine => analysis,
+            // 观战只读，只能退出
+            Self::LeaveAnalysis => analysis || state == GameState::Spectating,
+        }
+    }
+}
+
+/// 快捷键绑定表，按InputAction::ALL的顺序保存每个动作的按键
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct InputMap {
+    bindings: Vec<(InputAction, Vec<KeyCode>)>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self {
+            bindings: InputAction::ALL
+                .into_iter()
+                .map(|action| (action, action.default_keys().to_vec()))
+                .collect(),
+        }
+    }
+}
+
+impl InputMap {
+    /// 动作绑定的按键
+    pub fn keys(&self, action: InputAction) -> &[KeyCode] {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map_or(&[], |(_, keys)| keys.as_slice())
+    }
+
+    /// 修改动作绑定的按键，空列表表示取消绑定
+    pub fn bind(&mut self, action: InputAction, keys: Vec<KeyCode>) {
+        match self.bindings.iter_mut().find(|(bound, _)| *bound == action) {
+            Some((_, bound_keys)) => *bound_keys = keys,
+            None => self.bindings.push((action, keys)),
+        }
+    }
+
+    /// 动作绑定的任一按键是否刚被按下
+    pub fn just_pressed(&self, action: InputAction, keyboard: &ButtonInput<KeyCode>) -> bool {
+        self.keys(action)
+            .iter()
+            .any(|key| keyboard.just_pressed(*key))
+    }
+
+    /// 同just_pressed，但正在输入棋谱时忽略字母键，只响应其他按键
+    pub fn just_pressed_unless_typing(
+        &self,
+        action: InputAction,
+        keyboard: &ButtonInput<KeyCode>,
+        typing: bool,
+    ) -> bool {
+        self.keys(action)
+            .iter()
+            .filter(|key| !(typing && is_letter_key(**key)))
+            .any(|key| keyboard.just_pressed(*key))
+    }
+
+    /// 当前游戏状态下可用且绑定了按键的动作
+    pub fn active_bindings(
+        &self,
+        state: GameState,
+    ) -> impl Iterator<Item = (InputAction, &[KeyCode])> {
+        self.bindings
+            .iter()
+            .filter(move |(action, keys)| action.is_active(state) && !keys.is_empty())
+            .map(|(action, keys)| (*action, keys.as_slice()))
+    }
+}
+
+/// 是否是字母键，棋谱输入框显示时字母键用于键入棋谱
+pub fn is_letter_key(key: KeyCode) -> bool {
+    key_letter(key).is_some()
+}
+
+/// 字母键对应的大写字母
+fn key_letter(key: KeyCode) -> Option<char> {
+    const LETTERS: [KeyCode; 26] = [
+        KeyCode::KeyA,
+        KeyCode::KeyB,
+        KeyCode::KeyC,
+        KeyCode::KeyD,
+        KeyCode::KeyE,
+        KeyCode::KeyF,
+        KeyCode::KeyG,
+        KeyCode::KeyH,
+        KeyCode::KeyI,
+        KeyCode::KeyJ,
+        KeyCode::KeyK,
+        KeyCode::KeyL,
+        KeyCode::KeyM,
+        KeyCode::KeyN,
+        KeyCode::KeyO,
+        KeyCode::KeyP,
+        KeyCode::KeyQ,
+        KeyCode::KeyR,
+        KeyCode::KeyS,
+        KeyCode::KeyT,
+        KeyCode::KeyU,
+        KeyCode::KeyV,
+        KeyCode::KeyW,
+        KeyCode::KeyX,
+        KeyCode::KeyY,
+        KeyCode::KeyZ,
+    ];
+    LETTERS
+        .iter()
+        .position(|letter| *letter == key)
+        .map(|index| (b'A' + index as u8) as char)
+}
+
+/// 按键上印的名称，不需要翻译的按键返回Some，空格和回车等需要翻译的按键返回None
+pub fn key_symbol(key: KeyCode) -> Option<String> {
+    if let Some(letter) = key_letter(key) {
+        return Some(letter.to_string());
+    }
+    let symbol = match key {
+        KeyCode::Slash => "?",
+        KeyCode::Escape => "Esc",
+        KeyCode::ArrowLeft => "←",
+        KeyCode::ArrowRight => "→",
+        KeyCode::ArrowUp => "↑",
+        KeyCode::ArrowDown => "↓",
+        KeyCode::Home => "Home",
+        KeyCode::F1 => "F1",
+        KeyCode::F2 => "F2",
+        KeyCode::F3 => "F3",
+        KeyCode::F4 => "F4",
+        KeyCode::F5 => "F5",
+        KeyCode::F6 => "F6",
+        KeyCode::F7 => "F7",
+        KeyCode::F8 => "F8",
+        KeyCode::F9 => "F9",
+        KeyCode::F10 => "F10",
+        KeyCode::F11 => "F11",
+        KeyCode::F12 => "F12",
+        KeyCode::Space | KeyCode::Enter => return None,
+        // 其他按键使用KeyCode的名称
+        other => return Some(format!("{other:?}")),
+    };
+    Some(symbol.to_string())
+}
@@ -0,0 +1,96 @@
+// 输入处理 - 输入偏好设置、手柄操作和防止连击的落子输入闸门
+//
+// 快速连点可能在AI回合切换前后产生多个落子事件。闸门以回合状态（命令序号和轮到的玩家）为键：
+// 同一回合内一旦接受了合法落子，在命令日志前进之前不再接受新的落子；
+// 另外两次输入之间必须间隔一个很短的去抖时间
+
+pub mod gamepad;
+pub mod keymap;
+pub mod notation;
+pub mod premove;
+pub mod source;
+
+pub use gamepad::*;
+pub use keymap::*;
+pub use notation::*;
+pub use premove::*;
+pub use source::*;
+
+use crate::game::{GameLog, PlayerColor};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 输入相关的偏好设置
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct InputPreferences {
+    /// 桌面端是否使用自定义光标
+    pub custom_cursor: bool,
+    /// 桌面端是否显示棋谱输入框
+    pub notation_input: bool,
+    /// AI回合中点击的格子是否记为预走
+    pub premove: bool,
+}
+
+/// 两次输入之间的最短间隔
+pub const INPUT_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// 回合状态，命令日志每前进一步都会变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TurnState {
+    /// 下一条命令的序号
+    pub sequence: u32,
+    /// 轮到的玩家
+    pub to_move: PlayerColor,
+}
+
+impl TurnState {
+    /// 命令日志当前的回合状态
+    pub fn of(log: &GameLog) -> Self {
+        Self {
+            sequence: log.next_sequence(),
+            to_move: log.to_move(),
+        }
+    }
+}
+
+/// 落子输入闸门
+#[derive(Resource, Debug, Clone, Default)]
+pub struct InputGate {
+    /// 已经接受过合法落子的回合
+    locked_turn: Option<TurnState>,
+    /// 上一次被接受的输入时间
+    last_input: Option<Duration>,
+}
+
+impl InputGate {
+    /// 判断一次落子输入能否通过
+    ///
+    /// # 参数
+    /// * `turn` - 输入时的回合状态
+    /// * `now` - 当前时间
+    /// * `legal` - 该落子是否合法，合法落子通过后锁定本回合
+    pub fn admit(&mut self, turn: TurnState, now: Duration, legal: bool) -> bool {
+        if self.locked_turn == Some(turn) {
+            return false;
+        }
+        if self
+            .last_input
+            .is_some_and(|last| now.saturating_sub(last) < INPUT_DEBOUNCE)
+        {
+            return false;
+        }
+
+        self.last_input = Some(now);
+        if legal {
+            self.locked_turn = Some(turn);
+        }
+        true
+    }
+
+    /// 解除本回合的锁定，用于已接受的落子被取消的情况（例如让角提醒中选择取消）
+    pub fn release(&mut self) {
+        self.locked_turn = None;
+    }
+}
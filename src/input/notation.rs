@@ -0,0 +1,151 @@
+// 棋谱输入 - 桌面端在输入框中键入走法（例如"f5"）后按回车落子
+//
+// 只接受字母和数字，最多两个字符，退格删除；回车时用棋谱记法解析走法，
+// 与鼠标落子一样经过输入闸门并发送PlayerMoveEvent。记法无效或走法不合法时保留输入并标红，
+// 便于读屏用户和照着开局书下棋的玩家修改后重新提交
+
+use super::{InputGate, TurnState};
+use crate::{
+    ai::AiPlayer,
+    assist::CornerWarningState,
+    audio::{PlaySoundEvent, SoundType},
+    game::{Board, GameLog, MoveAction},
+    history::HistoryPreview,
+    plugin::PlayerMoveEvent,
+    ui::{NotationInputBox, UiState},
+};
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+/// 输入框最多容纳的字符数，棋谱记法为一个字母加一个数字
+pub const NOTATION_INPUT_MAX_CHARS: usize = 2;
+
+/// 输入框中的文本和上一次提交的结果
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotationInput {
+    pub text: String,
+    /// 上一次提交的记法无效或走法不合法，修改文本后清除
+    pub rejected: bool,
+}
+
+/// 按键对输入框的作用
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotationKeyAction {
+    /// 文本发生变化
+    Edited,
+    /// 提交当前文本
+    Submit,
+    /// 与输入框无关的按键
+    Ignored,
+}
+
+impl NotationInput {
+    /// 处理一次按键：字母和数字追加到文本，退格删除最后一个字符，回车提交
+    pub fn apply_key(&mut self, key: &Key) -> NotationKeyAction {
+        match key {
+            Key::Character(characters) => {
+                let mut edited = false;
+                for character in characters.chars().filter(char::is_ascii_alphanumeric) {
+                    if self.text.len() < NOTATION_INPUT_MAX_CHARS {
+                        self.text.push(character.to_ascii_lowercase());
+                        edited = true;
+                    }
+                }
+                if !edited {
+                    return NotationKeyAction::Ignored;
+                }
+            }
+            Key::Backspace => {
+                if self.text.pop().is_none() {
+                    return NotationKeyAction::Ignored;
+                }
+            }
+            Key::Enter if !self.text.is_empty() => return NotationKeyAction::Submit,
+            _ => return NotationKeyAction::Ignored,
+        }
+        self.rejected = false;
+        NotationKeyAction::Edited
+    }
+}
+
+/// 解析输入的记法，返回落子位置和该走法当前是否合法，记法无效时返回None
+pub fn notation_move(text: &str, log: &GameLog) -> Option<(u8, bool)> {
+    let position = Board::notation_to_position(text.trim())?;
+    let legal = log
+        .validate(&log.next_command(MoveAction::Place(position)))
+        .is_ok();
+    Some((position, legal))
+}
+
+/// 读取键盘输入，回车时把输入框中的走法作为玩家落子提交
+///
+/// 输入框只在桌面端开启棋谱输入后显示，没有输入框时忽略键盘输入
+pub fn handle_notation_input(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut notation_input: ResMut<NotationInput>,
+    box_query: Query<(), With<NotationInputBox>>,
+    mut move_events: EventWriter<PlayerMoveEvent>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+    log_query: Query<&GameLog>,
+    ai_query: Query<&AiPlayer>,
+    ui_state: Res<UiState>,
+    corner_warning: Res<CornerWarningState>,
+    mut history_preview: ResMut<HistoryPreview>,
+    mut input_gate: ResMut<InputGate>,
+    time: Res<Time<Real>>,
+) {
+    if box_query.is_empty() {
+        keyboard_events.clear();
+        return;
+    }
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed
+            || notation_input.apply_key(&event.logical_key) != NotationKeyAction::Submit
+        {
+            continue;
+        }
+
+        // 与鼠标落子相同：暂停、让角提醒打开或轮到AI时不接受落子，保留输入
+        if ui_state.paused || corner_warning.pending.is_some() {
+            continue;
+        }
+        if history_preview.is_previewing() {
+            history_preview.snap_to_live();
+            continue;
+        }
+        let Ok(log) = log_query.single() else {
+            continue;
+        };
+        if ai_query
+            .single()
+            .is_ok_and(|ai_player| ai_player.color == log.to_move())
+        {
+            continue;
+        }
+
+        let Some((position, legal)) = notation_move(&notation_input.text, log) else {
+            notation_input.rejected = true;
+            sound_events.write(PlaySoundEvent {
+                sound_type: SoundType::InvalidMove,
+                actor: None,
+            });
+            continue;
+        };
+        if !input_gate.admit(TurnState::of(log), time.elapsed(), legal) {
+            continue;
+        }
+        // 不合法的走法由落子处理播放无效音效
+        move_events.write(PlayerMoveEvent { position });
+        if legal {
+            notation_input.text.clear();
+        } else {
+            notation_input.rejected = true;
+        }
+    }
+}
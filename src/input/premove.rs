@@ -0,0 +1,79 @@
+// 预走 - AI回合中点击的格子先记下，轮到玩家时仍然合法就立即落子
+//
+// 在设置中开启后，AI思考期间点击空格会在该格显示标记；再次点击同一格、右键或开始新对局时取消。
+// 轮到玩家时预走的格子不再合法（例如AI的落子占据或改变了局面）就直接丢弃，不提示无效落子
+
+use super::{InputGate, TurnState};
+use crate::{
+    ai::AiPlayer,
+    game::{GameLog, MoveAction},
+    plugin::PlayerMoveEvent,
+    preferences::Preferences,
+    ui::CurrentPlayer,
+};
+use bevy::prelude::*;
+
+/// 预走的格子，最多一个
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Premove {
+    position: Option<u8>,
+}
+
+impl Premove {
+    /// 预走的格子
+    pub fn position(&self) -> Option<u8> {
+        self.position
+    }
+
+    /// 预走指定的格子，再次选择同一格时取消
+    pub fn toggle(&mut self, position: u8) {
+        self.position = (self.position != Some(position)).then_some(position);
+    }
+
+    /// 取消预走
+    pub fn cancel(&mut self) {
+        self.position = None;
+    }
+}
+
+/// 轮到玩家时走出预走的格子，不合法时丢弃；关闭预走设置时取消已有的预走
+///
+/// 走法和点击落子一样经过输入闸门，去抖未通过时保留预走到下一帧
+pub fn play_premove(
+    mut premove: ResMut<Premove>,
+    preferences: Res<Preferences>,
+    log_query: Query<&GameLog>,
+    ai_query: Query<&AiPlayer>,
+    current_player: Res<CurrentPlayer>,
+    mut input_gate: ResMut<InputGate>,
+    time: Res<Time<Real>>,
+    mut move_events: EventWriter<PlayerMoveEvent>,
+) {
+    let Some(position) = premove.position else {
+        return;
+    };
+    if !preferences.input.premove {
+        premove.cancel();
+        return;
+    }
+
+    let ai_turn = ai_query
+        .single()
+        .is_ok_and(|ai_player| ai_player.color == current_player.0);
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+    if ai_turn || log.to_move() != current_player.0 {
+        return;
+    }
+
+    let legal = log
+        .validate(&log.next_command(MoveAction::Place(position)))
+        .is_ok();
+    if !legal {
+        premove.cancel();
+    } else if input_gate.admit(TurnState::of(log), time.elapsed(), true) {
+        premove.cancel();
+        move_events.write(PlayerMoveEvent { position });
+    }
+}
@@ -0,0 +1,129 @@
+// 输入来源 - 每个座位（执黑、执白）由谁走棋：本机玩家、AI、脚本或网络对手
+//
+// 开始新对局时按对局模式分配本机玩家和AI的座位；预先指定为脚本或网络的座位保持不变。
+// 脚本座位按给定的走法列表逐步落子，走法与玩家和AI一样转换为走棋命令，
+// 可以用来在真实的事件流程中自动下完整盘棋（例如界面集成测试）
+
+use crate::game::{GameLog, MoveAction, MoveCommand, PlayerColor};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+use super::TurnState;
+
+/// 脚本座位的走法列表
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScriptedMoves {
+    /// 还没有走出的落子位置，无棋可走时自动跳过，不需要写入列表
+    moves: VecDeque<u8>,
+    /// 已经走过一步的回合，同一回合只走一步
+    played_turn: Option<TurnState>,
+}
+
+impl ScriptedMoves {
+    pub fn new(moves: impl IntoIterator<Item = u8>) -> Self {
+        Self {
+            moves: moves.into_iter().collect(),
+            played_turn: None,
+        }
+    }
+
+    /// 还没有走出的步数
+    pub fn remaining(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// 轮到脚本时的下一条走棋命令，本回合已经走过或列表已经走完时返回None
+    ///
+    /// 取出的走法不合法时同样占用本回合，对局停在这里而不是跳到后面的走法
+    pub fn next_command(&mut self, log: &GameLog) -> Option<MoveCommand> {
+        let turn = TurnState::of(log);
+        if self.played_turn == Some(turn) {
+            return None;
+        }
+        let position = self.moves.pop_front()?;
+        self.played_turn = Some(turn);
+        Some(log.next_command(MoveAction::Place(position)))
+    }
+}
+
+/// 一个座位的输入来源
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// 本机玩家（鼠标、触摸、手柄、键盘和棋谱输入）
+    #[default]
+    Human,
+    /// 本机AI
+    Ai,
+    /// 按走法列表自动落子
+    Script(ScriptedMoves),
+    /// 网络对手，走棋命令由传输层写入
+    Network,
+}
+
+impl InputSource {
+    /// 按走法列表自动落子的来源
+    pub fn script(moves: impl IntoIterator<Item = u8>) -> Self {
+        Self::Script(ScriptedMoves::new(moves))
+    }
+
+    /// 是否接受本机玩家的棋盘输入
+    pub fn is_human(&self) -> bool {
+        matches!(self, Self::Human)
+    }
+
+    /// 是否由本机AI思考
+    pub fn is_ai(&self) -> bool {
+        matches!(self, Self::Ai)
+    }
+
+    /// 无棋可走时是否自动跳过；本机玩家点击跳过按钮确认，网络对手自己发送跳过
+    pub fn passes_automatically(&self) -> bool {
+        matches!(self, Self::Ai | Self::Script(_))
+    }
+}
+
+/// 双方座位的输入来源
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerSeats {
+    black: InputSource,
+    white: InputSource,
+}
+
+impl PlayerSeats {
+    /// 指定颜色的输入来源
+    pub fn source(&self, color: PlayerColor) -> &InputSource {
+        match color {
+            PlayerColor::Black => &self.black,
+            PlayerColor::White => &self.white,
+        }
+    }
+
+    /// 指定颜色的输入来源（可修改）
+    pub fn source_mut(&mut self, color: PlayerColor) -> &mut InputSource {
+        match color {
+            PlayerColor::Black => &mut self.black,
+            PlayerColor::White => &mut self.white,
+        }
+    }
+
+    /// 设置指定颜色的输入来源
+    pub fn assign(&mut self, color: PlayerColor, source: InputSource) {
+        *self.source_mut(color) = source;
+    }
+
+    /// 开始新对局时分配座位：AI执`ai_color`，其余座位由本机玩家操作
+    ///
+    /// 预先指定为脚本或网络的座位保持不变，脚本座位占据AI的颜色时AI不再思考
+    pub fn reset_for_game(&mut self, ai_color: Option<PlayerColor>) {
+        for color in [PlayerColor::Black, PlayerColor::White] {
+            let seat = self.source_mut(color);
+            if matches!(seat, InputSource::Human | InputSource::Ai) {
+                *seat = if ai_color == Some(color) {
+                    InputSource::Ai
+                } else {
+                    InputSource::Human
+                };
+            }
+        }
+    }
+}
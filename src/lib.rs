@@ -1,7 +1,74 @@
+//! 黑白棋（Reversi）游戏和引擎
+//!
+//! 本crate可以按两种方式嵌入其他项目：
+//!
+//! - **只使用引擎**：[`Board`]、[`GameLog`]等规则和命令类型，以及[`AiDifficulty`]
+//!   驱动的AI搜索，不需要创建Bevy应用
+//! - **嵌入完整游戏**：在添加`DefaultPlugins`之后添加[`ReversiPlugin`]，
+//!   游戏系统按[`GameSystems`]分组执行，宿主应用可以据此排列自己的系统
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use reversi::ReversiPlugin;
+//!
+//! App::new()
+//!     .add_plugins(DefaultPlugins)
+//!     .add_plugins(ReversiPlugin)
+//!     .run();
+//! ```
+//!
+//! # 版本兼容
+//!
+//! 根模块和[`prelude`]中重新导出的类型是稳定的公开接口，遵循语义化版本；
+//! 各子模块中的其他项（具体的系统函数、界面组件等）主要供游戏内部使用，
+//! 可能在次版本中调整
+
+pub mod accessibility;
 pub mod ai;
+pub mod analysis;
+pub mod assist;
 pub mod audio;
+pub mod banter;
+pub mod boot;
+pub mod clock;
+pub mod coach;
+pub mod config_assets;
+pub mod demo;
+pub mod display;
 pub mod fonts;
 pub mod game;
+pub mod history;
+pub mod input;
 pub mod localization;
+pub mod net;
+pub mod platform;
+pub mod plugin;
+pub mod practice;
+pub mod preferences;
+pub mod record;
+pub mod sandbox;
+pub mod series;
+pub mod state;
+pub mod stats;
 pub mod systems;
 pub mod ui;
+pub mod undo;
+
+// 引擎：棋盘规则和走棋命令
+pub use game::{Board, CommandError, GameLog, Move, MoveAction, MoveCommand, PlayerColor};
+
+// 引擎：AI对手
+pub use ai::{evaluation::EvalConfig, AiDifficulty};
+
+// 游戏插件及其公开的状态、系统集和事件
+pub use plugin::{AiMoveEvent, AiSearchEvent, PlayerMoveEvent, ReversiPlugin, SelectedDifficulty};
+pub use state::{GameState, GameTransition};
+pub use systems::GameSystems;
+
+/// 常用类型，`use reversi::prelude::*;`即可使用
+pub mod prelude {
+    pub use crate::{
+        AiDifficulty, Board, GameLog, GameState, GameSystems, Move, MoveAction, MoveCommand,
+        PlayerColor, ReversiPlugin,
+    };
+}
@@ -0,0 +1,177 @@
+// 数字和日期格式 - 统计、历史对局和用时界面中的数字、时长和日期按当前语言显示
+//
+// 每种语言的格式规则挂在LocalizedTexts上。界面以TextArg::Number等参数传入原始数值，
+// 解析文本键时才格式化为字符串，切换语言后自动按新的格式重新显示
+
+use std::time::Duration;
+
+/// 一种语言的数字和日期格式
+#[derive(Debug)]
+pub struct LocaleFormat {
+    /// 千位分隔符
+    pub thousands_separator: &'static str,
+    /// 小数点
+    pub decimal_separator: &'static str,
+    /// 简写大数时使用的单位，从大到小排列
+    pub compact_units: &'static [(u64, &'static str)],
+    /// 一分钟以内的时长，参数为秒数
+    pub seconds_format: &'static str,
+    /// 日期格式，"{year}"、"{month}"和"{day}"分别替换为年、月、日
+    pub date_format: &'static str,
+    /// 月份名称
+    pub month_names: [&'static str; 12],
+}
+
+/// 公历日期和时间（UTC）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: i64,
+    /// 1到12
+    pub month: u32,
+    /// 1到31
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+}
+
+impl DateTime {
+    /// 由Unix时间戳（秒）推算公历日期和时间
+    pub fn from_unix_secs(secs: u64) -> Self {
+        let days = (secs / 86_400) as i64;
+        let minutes_of_day = (secs % 86_400 / 60) as u32;
+
+        // 由1970-01-01起的天数推算公历日期
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Self {
+            year,
+            month: month as u32,
+            day: day as u32,
+            hour: minutes_of_day / 60,
+            minute: minutes_of_day % 60,
+        }
+    }
+}
+
+impl LocaleFormat {
+    /// 整数，每三位插入千位分隔符
+    pub fn number(&self, value: u64) -> String {
+        let digits = value.to_string();
+        let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index) % 3 == 0 {
+                formatted.push_str(self.thousands_separator);
+            }
+            formatted.push(digit);
+        }
+        formatted
+    }
+
+    /// 保留指定位数的小数
+    pub fn decimal(&self, value: f32, digits: usize) -> String {
+        let formatted = format!("{value:.digits$}");
+        let (sign, unsigned) = formatted
+            .strip_prefix('-')
+            .map_or(("", formatted.as_str()), |unsigned| ("-", unsigned));
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let integer = integer
+            .parse()
+            .map_or_else(|_| integer.to_string(), |integer| self.number(integer));
+        if fraction.is_empty() {
+            format!("{sign}{integer}")
+        } else {
+            format!("{sign}{integer}{}{fraction}", self.decimal_separator)
+        }
+    }
+
+    /// 简写的大数，例如"2.1M"或"210万"；不到最小单位时与number相同
+    ///
+    /// 不到100个单位时保留一位小数（末尾的0省略），否则取整
+    pub fn compact(&self, value: u64) -> String {
+        let Some(&(unit, suffix)) = self.compact_units.iter().find(|(unit, _)| value >= *unit)
+        else {
+            return self.number(value);
+        };
+        let scaled = value as f64 / unit as f64;
+        let digits = if scaled < 100.0 { 1 } else { 0 };
+        let formatted = self.decimal(scaled as f32, digits);
+        let trimmed = formatted
+            .strip_suffix(&format!("{}0", self.decimal_separator))
+            .unwrap_or(&formatted);
+        format!("{trimmed}{suffix}")
+    }
+
+    /// 时长，一分钟以内显示一位小数的秒数，否则显示为"1:23"
+    pub fn duration(&self, duration: Duration) -> String {
+        let secs = duration.as_secs_f32();
+        if secs < 60.0 {
+            self.seconds_format
+                .replacen("{}", &self.decimal(secs, 1), 1)
+        } else {
+            let total = duration.as_secs();
+            format!("{}:{:02}", self.number(total / 60), total % 60)
+        }
+    }
+
+    /// 日期
+    pub fn date(&self, date_time: &DateTime) -> String {
+        let month = date_time
+            .month
+            .checked_sub(1)
+            .and_then(|index| self.month_names.get(index as usize))
+            .copied()
+            .unwrap_or_default();
+        self.date_format
+            .replace("{year}", &date_time.year.to_string())
+            .replace("{month}", month)
+            .replace("{day}", &date_time.day.to_string())
+    }
+
+    /// Unix时间戳（秒）对应的日期和24小时制时间（UTC）
+    pub fn timestamp(&self, secs: u64) -> String {
+        let date_time = DateTime::from_unix_secs(secs);
+        format!(
+            "{} {:02}:{:02}",
+            self.date(&date_time),
+            date_time.hour,
+            date_time.minute
+        )
+    }
+}
+
+/// 英文格式，例如"1,234"、"2.1M"、"4.2s"和"Nov 14, 2023"
+pub const ENGLISH_FORMAT: LocaleFormat = LocaleFormat {
+    thousands_separator: ",",
+    decimal_separator: ".",
+    compact_units: &[(1_000_000_000, "B"), (1_000_000, "M"), (1_000, "K")],
+    seconds_format: "{}s",
+    date_format: "{month} {day}, {year}",
+    month_names: [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ],
+};
+
+/// 中文格式，例如"1,234"、"210万"、"4.2秒"和"2023年11月14日"
+pub const CHINESE_FORMAT: LocaleFormat = LocaleFormat {
+    thousands_separator: ",",
+    decimal_separator: ".",
+    compact_units: &[(100_000_000, "亿"), (10_000, "万")],
+    seconds_format: "{}秒",
+    date_format: "{year}年{month}月{day}日",
+    month_names: [
+        "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+    ],
+};
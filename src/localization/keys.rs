@@ -0,0 +1,558 @@
+// 文本键 - 界面文本以键的形式挂在实体上，由本地化系统解析为当前语言的字符串
+//
+// 语言切换或参数变化时自动重新解析，界面代码不再直接写入翻译后的文本
+
+use super::{LanguageSettings, LocalizedTexts};
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// 界面文本键，对应LocalizedTexts中的一个字段
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextKey {
+    LanguageSelectionTitle,
+    LanguageEnglish,
+    LanguageChinese,
+    AiDifficultyFormat,
+    YourTurn,
+    AiTurn,
+    GameInProgress,
+    ClickToRestart,
+    GameResultFormat,
+    BlackWins,
+    WhiteWins,
+    Draw,
+    PassTurnFormat,
+    TimeForfeitFormat,
+    HistoryPreviewFormat,
+    DifficultyChaos,
+    DifficultyEasy,
+    DifficultyMedium,
+    DifficultyHard,
+    DifficultyExpert,
+    DifficultyExpertPlus,
+    DifficultyAdaptive,
+    AdaptiveLevelFormat,
+    RulesTitle,
+    RulesClose,
+    RulesContent,
+    RulesExampleFormat,
+    BanterPlayerCorner,
+    BanterAiCorner,
+    BanterPlayerBigFlip,
+    BanterAiBigFlip,
+    BanterLongThink,
+    LoadingText,
+    SelectDifficulty,
+    BackToDifficulty,
+    BlackName,
+    WhiteName,
+    SettingsTitle,
+    CornerWarningFormat,
+    CornerWarningPlay,
+    CornerWarningCancel,
+    CornerWarningDisable,
+    TimeStatsTitle,
+    TimeStatsFormat,
+    FlipStatsTitle,
+    FlipStatsFormat,
+    FlipRecordsFormat,
+    ToastFlipChainRecord,
+    TranscriptExportHint,
+    ReportExportHint,
+    TranscriptExported,
+    ValueOn,
+    ValueOff,
+    DangerZonesToggle,
+    ZoneCorner,
+    ZoneCSquare,
+    ZoneXSquare,
+    AnalysisHint,
+    AnalysisStatus,
+    AnalysisMainLine,
+    AnalysisVariation,
+    AnalysisControls,
+    PositionInputFormat,
+    PositionInputHint,
+    PositionLoad,
+    PositionCopy,
+    PositionLoaded,
+    PositionInvalid,
+    PositionCopied,
+    PositionClipboardFailed,
+    PositionPastePrompt,
+    AnalysisWinChance,
+    BrushFillRow,
+    BrushFillColumn,
+    BrushMirror,
+    BrushClear,
+    BrushSwapColors,
+    BrushFillRowHint,
+    BrushFillColumnHint,
+    PositionTooFewDiscs,
+    PositionEmptyCenter,
+    PositionDisconnected,
+    AnalysisWinChanceUnavailable,
+    PauseTitle,
+    PauseResume,
+    PauseRestart,
+    PauseMenu,
+    PauseAiStrengthFormat,
+    ArchiveTitle,
+    ArchiveEmpty,
+    ArchiveEntry,
+    OutcomeWin,
+    OutcomeLoss,
+    OutcomeDraw,
+    BackgroundLoading,
+    AssetWarningTitle,
+    AssetWarningHint,
+    CoachTookCorner,
+    CoachConcedesCorner,
+    CoachRiskyXSquare,
+    CoachBetterMove,
+    CoachLimitedOpponent,
+    CoachKeptMobility,
+    CoachSolid,
+    HistoryLivePreview,
+    OpeningPracticeFormat,
+    TimeControlFormat,
+    TimeControlUnlimited,
+    TimeControlBlitz,
+    TimeControlRapid,
+    TimeControlCasual,
+    SandboxButton,
+    SandboxLabel,
+    SandboxNotCounted,
+    IntroBlackFirst,
+    PhaseMobilityFormat,
+    PhaseStableFormat,
+    PhaseEmptiesFormat,
+    ParityOdd,
+    ParityEven,
+    DifficultyBadgeFormat,
+    DifficultySubtitleFormat,
+    DifficultyChaosSubtitleFormat,
+    DifficultyRecommended,
+    SoundTestTitle,
+    SoundTestRowFormat,
+    SoundTestVolumeFormat,
+    SoundTestPlay,
+    SoundPiecePlace,
+    SoundPieceFlip,
+    SoundVictory,
+    SoundDefeat,
+    SoundInvalidMove,
+    SoundPass,
+    SoundLowTime,
+    SoundCornerCapture,
+    SoundEdgeComplete,
+    SoundWipeoutThreat,
+    SaveConflictFormat,
+    SaveDataPreferences,
+    SaveDataStats,
+    SaveDataArchive,
+    SaveDataOther,
+    ToastPassFormat,
+    ToastGameSaved,
+    ToastSaveFailedFormat,
+    ToastExportFailed,
+    ToastReportExported,
+    ToastReportExportFailed,
+    AssetLoading,
+    AssetLoaded,
+    AssetFailed,
+    UndoButton,
+    PassButton,
+    UndoRemainingFormat,
+    UndoDisabledRated,
+    UndoLimitReached,
+    UndoNothing,
+    UndoWaitTurn,
+    NotationInputFormat,
+    InsightsTitle,
+    InsightsDisabled,
+    InsightsEmpty,
+    InsightsGamesFormat,
+    InsightsAverageFormat,
+    InsightsFeaturesHeader,
+    InsightsFeatureFormat,
+    InsightsClear,
+    UsageUndo,
+    UsageDangerZones,
+    UsageHistoryReview,
+    UsageAnalysis,
+    UsageReplay,
+    UsageSandbox,
+    UsageOpeningPractice,
+    UsageRules,
+    MatchScoreFormat,
+    MatchWonFormat,
+    MatchLostFormat,
+    MatchDrawnFormat,
+    ArchiveSeriesFormat,
+    ShortcutsTitle,
+    ShortcutRowFormat,
+    ShortcutKeysFormat,
+    ShortcutsCloseHint,
+    KeySpace,
+    KeyEnter,
+    ShortcutToggleHelp,
+    ShortcutToggleSound,
+    ShortcutToggleAnnouncements,
+    ShortcutToggleFullscreen,
+    ShortcutCycleWindowSize,
+    ShortcutPause,
+    ShortcutUndo,
+    ShortcutHint,
+    ShortcutToggleSquareLabels,
+    ShortcutToggleDebugOverlay,
+    ShortcutTogglePerformanceOverlay,
+    ShortcutStepBack,
+    ShortcutStepForward,
+    ShortcutRestart,
+    ShortcutAnalyze,
+    ShortcutExportTranscript,
+    ShortcutExportReport,
+    ShortcutPreviousVariation,
+    ShortcutNextVariation,
+    ShortcutMainLine,
+    ShortcutLeaveAnalysis,
+    NetDesyncFormat,
+    NetReasonInvalidMove,
+    NetReasonChecksum,
+    NetReasonHistory,
+    NetResyncedFormat,
+    NetResyncFailedFormat,
+    SpectatorConnecting,
+    SpectatorStatus,
+    SpectatorControls,
+    DebugTableFormat,
+    DebugNoSearch,
+    PerformanceOverlayFormat,
+    CommentaryTitle,
+    CommentaryMoveFormat,
+    CommentaryPassFormat,
+    CommentaryCornerFormat,
+    CommentarySwingFormat,
+    CommentaryEdgeFormat,
+    CommentaryMobilityFormat,
+    CommentaryGameOverFormat,
+    SearchCaptionFormat,
+}
+
+/// 格式化文本的参数
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextArg {
+    /// 不需要翻译的值，例如数字或棋谱坐标
+    Value(String),
+    /// 需要翻译的文本键
+    Key(TextKey),
+    /// 带参数的文本键，用于嵌套格式
+    Format(TextKey, Vec<TextArg>),
+    /// 按当前语言加千位分隔符的整数
+    Number(u64),
+    /// 按当前语言简写的大数，例如"2.1M"或"210万"
+    CompactNumber(u64),
+    /// 按当前语言格式化的时长
+    Duration(Duration),
+    /// 按当前语言格式化的日期和时间，参数为Unix时间戳（秒）
+    Timestamp(u64),
+}
+
+/// 文本键的格式参数，按顺序替换文本中的"{}"
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct TextArgs(pub Vec<TextArg>);
+
+impl LocalizedTexts {
+    /// 获取文本键对应的字符串
+    pub fn get(&self, key: TextKey) -> &'static str {
+        match key {
+            TextKey::LanguageSelectionTitle => self.language_selection_title,
+            TextKey::LanguageEnglish => self.language_english,
+            TextKey::LanguageChinese => self.language_chinese,
+            TextKey::AiDifficultyFormat => self.ai_difficulty_format,
+            TextKey::YourTurn => self.your_turn,
+            TextKey::AiTurn => self.ai_turn,
+            TextKey::GameInProgress => self.game_in_progress,
+            TextKey::ClickToRestart => self.click_to_restart,
+            TextKey::GameResultFormat => self.game_result_format,
+            TextKey::BlackWins => self.black_wins,
+            TextKey::WhiteWins => self.white_wins,
+            TextKey::Draw => self.draw,
+            TextKey::PassTurnFormat => self.pass_turn_format,
+            TextKey::TimeForfeitFormat => self.time_forfeit_format,
+            TextKey::HistoryPreviewFormat => self.history_preview_format,
+            TextKey::DifficultyEasy => self.difficulty_easy,
+            TextKey::DifficultyMedium => self.difficulty_medium,
+            TextKey::DifficultyHard => self.difficulty_hard,
+            TextKey::DifficultyExpert => self.difficulty_expert,
+            TextKey::DifficultyExpertPlus => self.difficulty_expert_plus,
+            TextKey::DifficultyAdaptive => self.difficulty_adaptive,
+            TextKey::DifficultyChaos => self.difficulty_chaos,
+            TextKey::AdaptiveLevelFormat => self.adaptive_level_format,
+            TextKey::RulesTitle => self.rules_title,
+            TextKey::RulesClose => self.rules_close,
+            TextKey::RulesContent => self.rules_content,
+            TextKey::RulesExampleFormat => self.rules_example_format,
+            TextKey::BanterPlayerCorner => self.banter_player_corner,
+            TextKey::BanterAiCorner => self.banter_ai_corner,
+            TextKey::BanterPlayerBigFlip => self.banter_player_big_flip,
+            TextKey::BanterAiBigFlip => self.banter_ai_big_flip,
+            TextKey::BanterLongThink => self.banter_long_think,
+            TextKey::LoadingText => self.loading_text,
+            TextKey::SelectDifficulty => self.select_difficulty,
+            TextKey::BackToDifficulty => self.back_to_difficulty,
+            TextKey::BlackName => self.black_name,
+            TextKey::WhiteName => self.white_name,
+            TextKey::SettingsTitle => self.settings_title,
+            TextKey::CornerWarningFormat => self.corner_warning_format,
+            TextKey::CornerWarningPlay => self.corner_warning_play,
+            TextKey::CornerWarningCancel => self.corner_warning_cancel,
+            TextKey::CornerWarningDisable => self.corner_warning_disable,
+            TextKey::TimeStatsTitle => self.time_stats_title,
+            TextKey::TimeStatsFormat => self.time_stats_format,
+            TextKey::FlipStatsTitle => self.flip_stats_title,
+            TextKey::FlipStatsFormat => self.flip_stats_format,
+            TextKey::FlipRecordsFormat => self.flip_records_format,
+            TextKey::ToastFlipChainRecord => self.toast_flip_chain_record,
+            TextKey::TranscriptExportHint => self.transcript_export_hint,
+            TextKey::ReportExportHint => self.report_export_hint,
+            TextKey::TranscriptExported => self.transcript_exported,
+            TextKey::ValueOn => self.value_on,
+            TextKey::ValueOff => self.value_off,
+            TextKey::DangerZonesToggle => self.danger_zones_toggle,
+            TextKey::ZoneCorner => self.zone_corner,
+            TextKey::ZoneCSquare => self.zone_c_square,
+            TextKey::ZoneXSquare => self.zone_x_square,
+            TextKey::AnalysisHint => self.analysis_hint,
+            TextKey::AnalysisStatus => self.analysis_status,
+            TextKey::AnalysisMainLine => self.analysis_main_line,
+            TextKey::AnalysisVariation => self.analysis_variation,
+            TextKey::AnalysisControls => self.analysis_controls,
+            TextKey::PositionInputFormat => self.position_input_format,
+            TextKey::PositionInputHint => self.position_input_hint,
+            TextKey::PositionLoad => self.position_load,
+            TextKey::PositionCopy => self.position_copy,
+            TextKey::PositionLoaded => self.position_loaded,
+            TextKey::PositionInvalid => self.position_invalid,
+            TextKey::PositionCopied => self.position_copied,
+            TextKey::PositionClipboardFailed => self.position_clipboard_failed,
+            TextKey::PositionPastePrompt => self.position_paste_prompt,
+            TextKey::AnalysisWinChance => self.analysis_win_chance,
+            TextKey::BrushFillRow => self.brush_fill_row,
+            TextKey::BrushFillColumn => self.brush_fill_column,
+            TextKey::BrushMirror => self.brush_mirror,
+            TextKey::BrushClear => self.brush_clear,
+            TextKey::BrushSwapColors => self.brush_swap_colors,
+            TextKey::BrushFillRowHint => self.brush_fill_row_hint,
+            TextKey::BrushFillColumnHint => self.brush_fill_column_hint,
+            TextKey::PositionTooFewDiscs => self.position_too_few_discs,
+            TextKey::PositionEmptyCenter => self.position_empty_center,
+            TextKey::PositionDisconnected => self.position_disconnected,
+            TextKey::AnalysisWinChanceUnavailable => self.analysis_win_chance_unavailable,
+            TextKey::PauseTitle => self.pause_title,
+            TextKey::PauseResume => self.pause_resume,
+            TextKey::PauseRestart => self.pause_restart,
+            TextKey::PauseMenu => self.pause_menu,
+            TextKey::PauseAiStrengthFormat => self.pause_ai_strength_format,
+            TextKey::ArchiveTitle => self.archive_title,
+            TextKey::ArchiveEmpty => self.archive_empty,
+            TextKey::ArchiveEntry => self.archive_entry,
+            TextKey::OutcomeWin => self.outcome_win,
+            TextKey::OutcomeLoss => self.outcome_loss,
+            TextKey::OutcomeDraw => self.outcome_draw,
+            TextKey::BackgroundLoading => self.background_loading,
+            TextKey::AssetWarningTitle => self.asset_warning_title,
+            TextKey::AssetWarningHint => self.asset_warning_hint,
+            TextKey::CoachTookCorner => self.coach_took_corner,
+            TextKey::CoachConcedesCorner => self.coach_concedes_corner,
+            TextKey::CoachRiskyXSquare => self.coach_risky_x_square,
+            TextKey::CoachBetterMove => self.coach_better_move,
+            TextKey::CoachLimitedOpponent => self.coach_limited_opponent,
+            TextKey::CoachKeptMobility => self.coach_kept_mobility,
+            TextKey::CoachSolid => self.coach_solid,
+            TextKey::HistoryLivePreview => self.history_live_preview,
+            TextKey::OpeningPracticeFormat => self.opening_practice_format,
+            TextKey::TimeControlFormat => self.time_control_format,
+            TextKey::TimeControlUnlimited => self.time_control_unlimited,
+            TextKey::TimeControlBlitz => self.time_control_blitz,
+            TextKey::TimeControlRapid => self.time_control_rapid,
+            TextKey::TimeControlCasual => self.time_control_casual,
+            TextKey::SandboxButton => self.sandbox_button,
+            TextKey::SandboxLabel => self.sandbox_label,
+            TextKey::SandboxNotCounted => self.sandbox_not_counted,
+            TextKey::IntroBlackFirst => self.intro_black_first,
+            TextKey::PhaseMobilityFormat => self.phase_mobility_format,
+            TextKey::PhaseStableFormat => self.phase_stable_format,
+            TextKey::PhaseEmptiesFormat => self.phase_empties_format,
+            TextKey::ParityOdd => self.parity_odd,
+            TextKey::ParityEven => self.parity_even,
+            TextKey::DifficultyBadgeFormat => self.difficulty_badge_format,
+            TextKey::DifficultySubtitleFormat => self.difficulty_subtitle_format,
+            TextKey::DifficultyChaosSubtitleFormat => self.difficulty_chaos_subtitle_format,
+            TextKey::DifficultyRecommended => self.difficulty_recommended,
+            TextKey::SoundTestTitle => self.sound_test_title,
+            TextKey::SoundTestRowFormat => self.sound_test_row_format,
+            TextKey::SoundTestVolumeFormat => self.sound_test_volume_format,
+            TextKey::SoundTestPlay => self.sound_test_play,
+            TextKey::SoundPiecePlace => self.sound_piece_place,
+            TextKey::SoundPieceFlip => self.sound_piece_flip,
+            TextKey::SoundVictory => self.sound_victory,
+            TextKey::SoundDefeat => self.sound_defeat,
+            TextKey::SoundInvalidMove => self.sound_invalid_move,
+            TextKey::SoundPass => self.sound_pass,
+            TextKey::SoundLowTime => self.sound_low_time,
+            TextKey::SoundCornerCapture => self.sound_corner_capture,
+            TextKey::SoundEdgeComplete => self.sound_edge_complete,
+            TextKey::SoundWipeoutThreat => self.sound_wipeout_threat,
+            TextKey::SaveConflictFormat => self.save_conflict_format,
+            TextKey::SaveDataPreferences => self.save_data_preferences,
+            TextKey::SaveDataStats => self.save_data_stats,
+            TextKey::SaveDataArchive => self.save_data_archive,
+            TextKey::SaveDataOther => self.save_data_other,
+            // 跳过回合的提示与屏幕阅读器的播报共用同一句
+            TextKey::ToastPassFormat => self.announce_pass,
+            TextKey::ToastGameSaved => self.toast_game_saved,
+            TextKey::ToastSaveFailedFormat => self.toast_save_failed_format,
+            TextKey::ToastExportFailed => self.toast_export_failed,
+            TextKey::ToastReportExported => self.toast_report_exported,
+            TextKey::ToastReportExportFailed => self.toast_report_export_failed,
+            TextKey::AssetLoading => self.asset_loading,
+            TextKey::AssetLoaded => self.asset_loaded,
+            TextKey::AssetFailed => self.asset_failed,
+            TextKey::UndoButton => self.undo_button,
+            TextKey::PassButton => self.pass_button,
+            TextKey::UndoRemainingFormat => self.undo_remaining_format,
+            TextKey::UndoDisabledRated => self.undo_disabled_rated,
+            TextKey::UndoLimitReached => self.undo_limit_reached,
+            TextKey::UndoNothing => self.undo_nothing,
+            TextKey::UndoWaitTurn => self.undo_wait_turn,
+            TextKey::NotationInputFormat => self.notation_input_format,
+            TextKey::InsightsTitle => self.insights_title,
+            TextKey::InsightsDisabled => self.insights_disabled,
+            TextKey::InsightsEmpty => self.insights_empty,
+            TextKey::InsightsGamesFormat => self.insights_games_format,
+            TextKey::InsightsAverageFormat => self.insights_average_format,
+            TextKey::InsightsFeaturesHeader => self.insights_features_header,
+            TextKey::InsightsFeatureFormat => self.insights_feature_format,
+            TextKey::InsightsClear => self.insights_clear,
+            TextKey::UsageUndo => self.usage_undo,
+            TextKey::UsageDangerZones => self.usage_danger_zones,
+            TextKey::UsageHistoryReview => self.usage_history_review,
+            TextKey::UsageAnalysis => self.usage_analysis,
+            TextKey::UsageReplay => self.usage_replay,
+            TextKey::UsageSandbox => self.usage_sandbox,
+            TextKey::UsageOpeningPractice => self.usage_opening_practice,
+            TextKey::UsageRules => self.usage_rules,
+            TextKey::MatchScoreFormat => self.match_score_format,
+            TextKey::MatchWonFormat => self.match_won_format,
+            TextKey::MatchLostFormat => self.match_lost_format,
+            TextKey::MatchDrawnFormat => self.match_drawn_format,
+            TextKey::ArchiveSeriesFormat => self.archive_series_format,
+            TextKey::ShortcutsTitle => self.shortcuts_title,
+            TextKey::ShortcutRowFormat => self.shortcut_row_format,
+            TextKey::ShortcutKeysFormat => self.shortcut_keys_format,
+            TextKey::ShortcutsCloseHint => self.shortcuts_close_hint,
+            TextKey::KeySpace => self.key_space,
+            TextKey::KeyEnter => self.key_enter,
+            TextKey::ShortcutToggleHelp => self.shortcut_toggle_help,
+            TextKey::ShortcutToggleSound => self.shortcut_toggle_sound,
+            TextKey::ShortcutToggleAnnouncements => self.shortcut_toggle_announcements,
+            TextKey::ShortcutToggleFullscreen => self.shortcut_toggle_fullscreen,
+            TextKey::ShortcutCycleWindowSize => self.shortcut_cycle_window_size,
+            TextKey::ShortcutPause => self.shortcut_pause,
+            TextKey::ShortcutUndo => self.shortcut_undo,
+            TextKey::ShortcutHint => self.shortcut_hint,
+            TextKey::ShortcutToggleSquareLabels => self.shortcut_toggle_square_labels,
+            TextKey::ShortcutToggleDebugOverlay => self.shortcut_toggle_debug_overlay,
+            TextKey::ShortcutTogglePerformanceOverlay => self.shortcut_toggle_performance_overlay,
+            TextKey::ShortcutStepBack => self.shortcut_step_back,
+            TextKey::ShortcutStepForward => self.shortcut_step_forward,
+            TextKey::ShortcutRestart => self.shortcut_restart,
+            TextKey::ShortcutAnalyze => self.shortcut_analyze,
+            TextKey::ShortcutExportTranscript => self.shortcut_export_transcript,
+            TextKey::ShortcutExportReport => self.shortcut_export_report,
+            TextKey::ShortcutPreviousVariation => self.shortcut_previous_variation,
+            TextKey::ShortcutNextVariation => self.shortcut_next_variation,
+            TextKey::ShortcutMainLine => self.shortcut_main_line,
+            TextKey::ShortcutLeaveAnalysis => self.shortcut_leave_analysis,
+            TextKey::NetDesyncFormat => self.net_desync_format,
+            TextKey::NetReasonInvalidMove => self.net_reason_invalid_move,
+            TextKey::NetReasonChecksum => self.net_reason_checksum,
+            TextKey::NetReasonHistory => self.net_reason_history,
+            TextKey::NetResyncedFormat => self.net_resynced_format,
+            TextKey::NetResyncFailedFormat => self.net_resync_failed_format,
+            TextKey::SpectatorConnecting => self.spectator_connecting,
+            TextKey::SpectatorStatus => self.spectator_status,
+            TextKey::SpectatorControls => self.spectator_controls,
+            TextKey::DebugTableFormat => self.debug_table_format,
+            TextKey::DebugNoSearch => self.debug_no_search,
+            TextKey::PerformanceOverlayFormat => self.performance_overlay_format,
+            TextKey::CommentaryTitle => self.commentary_title,
+            TextKey::CommentaryMoveFormat => self.commentary_move_format,
+            TextKey::CommentaryPassFormat => self.commentary_pass_format,
+            TextKey::CommentaryCornerFormat => self.commentary_corner_format,
+            TextKey::CommentarySwingFormat => self.commentary_swing_format,
+            TextKey::CommentaryEdgeFormat => self.commentary_edge_format,
+            TextKey::CommentaryMobilityFormat => self.commentary_mobility_format,
+            TextKey::CommentaryGameOverFormat => self.commentary_game_over_format,
+            TextKey::SearchCaptionFormat => self.search_caption_format,
+        }
+    }
+
+    /// 解析文本键，并依次用参数替换文本中的"{}"
+    pub fn format(&self, key: TextKey, args: &[TextArg]) -> String {
+        args.iter().fold(self.get(key).to_string(), |text, arg| {
+            let value = match arg {
+                TextArg::Value(value) => value.clone(),
+                TextArg::Key(key) => self.get(*key).to_string(),
+                TextArg::Format(key, args) => self.format(*key, args),
+                TextArg::Number(value) => self.locale_format.number(*value),
+                TextArg::CompactNumber(value) => self.locale_format.compact(*value),
+                TextArg::Duration(duration) => self.locale_format.duration(*duration),
+                TextArg::Timestamp(secs) => self.locale_format.timestamp(*secs),
+            };
+            text.replacen("{}", &value, 1)
+        })
+    }
+}
+
+/// 语言切换或文本键、参数变化时，重新解析实体上的文本（界面文本和棋盘上的2D文本）
+pub fn resolve_text_keys(
+    language_settings: Res<LanguageSettings>,
+    mut text_query: Query<(Ref<TextKey>, Option<Ref<TextArgs>>, &mut Text)>,
+    mut text2d_query: Query<(Ref<TextKey>, Option<Ref<TextArgs>>, &mut Text2d)>,
+) {
+    let texts = language_settings.get_texts();
+    let language_changed = language_settings.is_changed();
+
+    for (key, args, mut text) in text_query.iter_mut() {
+        if let Some(resolved) = resolve(texts, language_changed, &key, args.as_ref()) {
+            if text.0 != resolved {
+                text.0 = resolved;
+            }
+        }
+    }
+
+    for (key, args, mut text) in text2d_query.iter_mut() {
+        if let Some(resolved) = resolve(texts, language_changed, &key, args.as_ref()) {
+            if text.0 != resolved {
+                text.0 = resolved;
+            }
+        }
+    }
+}
+
+/// 需要重新解析时返回解析后的文本
+fn resolve(
+    texts: &LocalizedTexts,
+    language_changed: bool,
+    key: &Ref<TextKey>,
+    args: Option<&Ref<TextArgs>>,
+) -> Option<String> {
+    let args_changed = args.is_some_and(|args| args.is_changed());
+    if !language_changed && !key.is_changed() && !args_changed {
+        return None;
+    }
+    Some(texts.format(**key, args.map_or(&[], |args| &args.0)))
+}
@@ -1,18 +1,35 @@
+pub mod format;
+pub mod keys;
 pub mod texts;
 
 use bevy::prelude::*;
+pub use format::*;
+pub use keys::*;
 pub use texts::*;
 
 /// 支持的语言枚举
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Language {
+    #[default]
     English,
     Chinese,
 }
 
-impl Default for Language {
-    fn default() -> Self {
-        Self::English
+impl Language {
+    /// 循环切换到下一种语言
+    pub fn next(&self) -> Self {
+        match self {
+            Self::English => Self::Chinese,
+            Self::Chinese => Self::English,
+        }
+    }
+
+    /// 语言名称对应的文本键
+    pub fn name_key(&self) -> TextKey {
+        match self {
+            Self::English => TextKey::LanguageEnglish,
+            Self::Chinese => TextKey::LanguageChinese,
+        }
     }
 }
 
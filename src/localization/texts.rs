@@ -1,3 +1,5 @@
+use super::{LocaleFormat, CHINESE_FORMAT, ENGLISH_FORMAT};
+
 /// 本地化文本结构
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -8,29 +10,40 @@ pub struct LocalizedTexts {
     pub language_chinese: &'static str,
 
     // UI 文本
-    pub score_format: &'static str,
     pub ai_difficulty_format: &'static str,
     pub game_in_progress: &'static str,
     pub click_to_restart: &'static str,
+    /// 终局结果后接重新开始的提示
+    pub game_result_format: &'static str,
     pub your_turn: &'static str,
     pub ai_turn: &'static str,
 
     // 难度级别
+    pub difficulty_chaos: &'static str,
     pub difficulty_easy: &'static str,
     pub difficulty_medium: &'static str,
     pub difficulty_hard: &'static str,
     pub difficulty_expert: &'static str,
+    pub difficulty_expert_plus: &'static str,
+    pub difficulty_adaptive: &'static str,
+    pub adaptive_level_format: &'static str,
 
     // 游戏状态
     pub black_wins: &'static str,
     pub white_wins: &'static str,
     pub draw: &'static str,
-    pub pass_turn: &'static str,
+    pub pass_turn_format: &'static str,
 
     // 规则文本
     pub rules_title: &'static str,
     pub rules_close: &'static str,
     pub rules_content: &'static str,
+    pub rules_example_format: &'static str,
+    pub banter_player_corner: &'static str,
+    pub banter_ai_corner: &'static str,
+    pub banter_player_big_flip: &'static str,
+    pub banter_ai_big_flip: &'static str,
+    pub banter_long_think: &'static str,
 
     // 调试信息
     pub ai_difficulty_changed: &'static str,
@@ -42,6 +55,302 @@ pub struct LocalizedTexts {
     pub loading_text: &'static str,
     pub select_difficulty: &'static str,
     pub back_to_difficulty: &'static str,
+
+    // 无障碍播报
+    pub black_name: &'static str,
+    pub white_name: &'static str,
+    pub announce_move: &'static str,
+    pub announce_move_single: &'static str,
+    pub announce_pass: &'static str,
+    pub announce_result: &'static str,
+
+    // 设置面板
+    pub settings_title: &'static str,
+    pub setting_fullscreen: &'static str,
+    pub setting_window_size: &'static str,
+    pub setting_sound: &'static str,
+    pub setting_announcements: &'static str,
+    pub setting_language: &'static str,
+    pub setting_reduced_motion: &'static str,
+    pub setting_text_size: &'static str,
+    pub text_size_small: &'static str,
+    pub text_size_normal: &'static str,
+    pub text_size_large: &'static str,
+    pub text_size_extra_large: &'static str,
+    pub setting_assist_level: &'static str,
+    pub assist_level_hardcore: &'static str,
+    pub assist_level_off: &'static str,
+    pub assist_level_legal_moves: &'static str,
+    pub assist_level_flip_counts: &'static str,
+    pub assist_level_hints: &'static str,
+    pub setting_assist_mode: &'static str,
+    pub setting_corner_warning: &'static str,
+    pub setting_custom_cursor: &'static str,
+    pub setting_notation_input: &'static str,
+    pub setting_premove: &'static str,
+    pub setting_coach: &'static str,
+    pub setting_banter: &'static str,
+    pub setting_performance_overlay: &'static str,
+    pub setting_search_caption: &'static str,
+    pub setting_sound_test: &'static str,
+    pub setting_theme: &'static str,
+    pub theme_light: &'static str,
+    pub theme_dark: &'static str,
+    pub theme_auto: &'static str,
+    pub value_on: &'static str,
+    pub value_off: &'static str,
+
+    // 历史浏览
+    pub history_preview_format: &'static str,
+    pub history_live_preview: &'static str,
+
+    // 让出角位提醒
+    pub corner_warning_format: &'static str,
+    pub corner_warning_play: &'static str,
+    pub corner_warning_cancel: &'static str,
+    pub corner_warning_disable: &'static str,
+
+    // 用时统计
+    pub time_stats_title: &'static str,
+    pub time_stats_format: &'static str,
+    pub flip_stats_title: &'static str,
+    pub flip_stats_format: &'static str,
+    pub flip_records_format: &'static str,
+    pub toast_flip_chain_record: &'static str,
+    pub transcript_export_hint: &'static str,
+    pub transcript_exported: &'static str,
+    pub report_export_hint: &'static str,
+
+    // 危险区域标注
+    pub danger_zones_toggle: &'static str,
+    pub zone_corner: &'static str,
+    pub zone_c_square: &'static str,
+    pub zone_x_square: &'static str,
+
+    // 复盘分析
+    pub analysis_hint: &'static str,
+    pub analysis_status: &'static str,
+    pub analysis_main_line: &'static str,
+    pub analysis_variation: &'static str,
+    pub analysis_controls: &'static str,
+
+    // 局面字符串输入
+    pub position_input_format: &'static str,
+    pub position_input_hint: &'static str,
+    pub position_load: &'static str,
+    pub position_copy: &'static str,
+    pub position_loaded: &'static str,
+    pub position_invalid: &'static str,
+    pub position_copied: &'static str,
+    pub position_clipboard_failed: &'static str,
+    pub position_paste_prompt: &'static str,
+    pub analysis_win_chance: &'static str,
+    pub brush_fill_row: &'static str,
+    pub brush_fill_column: &'static str,
+    pub brush_mirror: &'static str,
+    pub brush_clear: &'static str,
+    pub brush_swap_colors: &'static str,
+    pub brush_fill_row_hint: &'static str,
+    pub brush_fill_column_hint: &'static str,
+    pub position_too_few_discs: &'static str,
+    pub position_empty_center: &'static str,
+    pub position_disconnected: &'static str,
+    pub analysis_win_chance_unavailable: &'static str,
+
+    // 暂停菜单
+    pub pause_title: &'static str,
+    pub pause_resume: &'static str,
+    pub pause_restart: &'static str,
+    pub pause_menu: &'static str,
+    pub pause_ai_strength_format: &'static str,
+
+    // 历史对局
+    pub archive_title: &'static str,
+    pub archive_empty: &'static str,
+    pub archive_entry: &'static str,
+    pub outcome_win: &'static str,
+    pub outcome_loss: &'static str,
+    pub outcome_draw: &'static str,
+
+    // 启动加载
+    pub background_loading: &'static str,
+    pub asset_warning_title: &'static str,
+    pub asset_warning_hint: &'static str,
+
+    // 教练模式
+    pub coach_took_corner: &'static str,
+    pub coach_concedes_corner: &'static str,
+    pub coach_risky_x_square: &'static str,
+    pub coach_better_move: &'static str,
+    pub coach_limited_opponent: &'static str,
+    pub coach_kept_mobility: &'static str,
+    pub coach_solid: &'static str,
+
+    // 开局练习
+    pub opening_practice_format: &'static str,
+
+    // 自由落子沙盒
+    pub sandbox_button: &'static str,
+    pub sandbox_label: &'static str,
+    pub sandbox_not_counted: &'static str,
+    pub intro_black_first: &'static str,
+    pub phase_mobility_format: &'static str,
+    pub phase_stable_format: &'static str,
+    pub phase_empties_format: &'static str,
+    pub parity_odd: &'static str,
+    pub parity_even: &'static str,
+
+    // 难度按钮上的战绩徽标
+    pub difficulty_badge_format: &'static str,
+    pub difficulty_subtitle_format: &'static str,
+    pub difficulty_chaos_subtitle_format: &'static str,
+    pub difficulty_recommended: &'static str,
+
+    // 音效测试
+    pub sound_test_title: &'static str,
+    pub sound_test_row_format: &'static str,
+    pub sound_test_volume_format: &'static str,
+    pub sound_test_play: &'static str,
+    pub sound_piece_place: &'static str,
+    pub sound_piece_flip: &'static str,
+    pub sound_victory: &'static str,
+    pub sound_defeat: &'static str,
+    pub sound_invalid_move: &'static str,
+    pub sound_pass: &'static str,
+    pub sound_low_time: &'static str,
+    pub sound_corner_capture: &'static str,
+    pub sound_edge_complete: &'static str,
+    pub sound_wipeout_threat: &'static str,
+    pub save_conflict_format: &'static str,
+    pub save_data_preferences: &'static str,
+    pub save_data_stats: &'static str,
+    pub save_data_archive: &'static str,
+    pub save_data_other: &'static str,
+    pub toast_game_saved: &'static str,
+    pub toast_save_failed_format: &'static str,
+    pub toast_export_failed: &'static str,
+    pub toast_report_exported: &'static str,
+    pub toast_report_export_failed: &'static str,
+    pub asset_loading: &'static str,
+    pub asset_loaded: &'static str,
+    pub asset_failed: &'static str,
+
+    // 悔棋
+    pub undo_button: &'static str,
+    pub pass_button: &'static str,
+    pub undo_remaining_format: &'static str,
+    pub undo_disabled_rated: &'static str,
+    pub undo_limit_reached: &'static str,
+    pub undo_nothing: &'static str,
+    pub undo_wait_turn: &'static str,
+    pub notation_input_format: &'static str,
+    pub insights_title: &'static str,
+    pub insights_disabled: &'static str,
+    pub insights_empty: &'static str,
+    pub insights_games_format: &'static str,
+    pub insights_average_format: &'static str,
+    pub insights_features_header: &'static str,
+    pub insights_feature_format: &'static str,
+    pub insights_clear: &'static str,
+    pub usage_undo: &'static str,
+    pub usage_danger_zones: &'static str,
+    pub usage_history_review: &'static str,
+    pub usage_analysis: &'static str,
+    pub usage_replay: &'static str,
+    pub usage_sandbox: &'static str,
+    pub usage_opening_practice: &'static str,
+    pub usage_rules: &'static str,
+    pub setting_insights: &'static str,
+
+    // 自定义对局规则
+    pub setting_victory: &'static str,
+    pub victory_standard: &'static str,
+    pub victory_margin_format: &'static str,
+    pub victory_first_to_format: &'static str,
+
+    // 多局比赛
+    pub setting_match: &'static str,
+    pub match_single: &'static str,
+    pub match_best_of_format: &'static str,
+    pub match_score_format: &'static str,
+    pub match_won_format: &'static str,
+    pub match_lost_format: &'static str,
+    pub match_drawn_format: &'static str,
+    pub archive_series_format: &'static str,
+
+    // 对局计时
+    pub setting_time_control: &'static str,
+    pub time_control_format: &'static str,
+    pub time_control_unlimited: &'static str,
+    pub time_control_blitz: &'static str,
+    pub time_control_rapid: &'static str,
+    pub time_control_casual: &'static str,
+    pub time_forfeit_format: &'static str,
+
+    // 快捷键帮助
+    pub shortcuts_title: &'static str,
+    pub shortcut_row_format: &'static str,
+    pub shortcut_keys_format: &'static str,
+    pub shortcuts_close_hint: &'static str,
+    pub key_space: &'static str,
+    pub key_enter: &'static str,
+    pub shortcut_toggle_help: &'static str,
+    pub shortcut_toggle_sound: &'static str,
+    pub shortcut_toggle_announcements: &'static str,
+    pub shortcut_toggle_fullscreen: &'static str,
+    pub shortcut_cycle_window_size: &'static str,
+    pub shortcut_pause: &'static str,
+    pub shortcut_undo: &'static str,
+    pub shortcut_hint: &'static str,
+    pub shortcut_toggle_square_labels: &'static str,
+    pub shortcut_toggle_debug_overlay: &'static str,
+    pub shortcut_toggle_performance_overlay: &'static str,
+    pub shortcut_step_back: &'static str,
+    pub shortcut_step_forward: &'static str,
+    pub shortcut_restart: &'static str,
+    pub shortcut_analyze: &'static str,
+    pub shortcut_export_transcript: &'static str,
+    pub shortcut_export_report: &'static str,
+    pub shortcut_previous_variation: &'static str,
+    pub shortcut_next_variation: &'static str,
+    pub shortcut_main_line: &'static str,
+    pub shortcut_leave_analysis: &'static str,
+
+    // 联机同步提示
+    pub net_desync_format: &'static str,
+    pub net_reason_invalid_move: &'static str,
+    pub net_reason_checksum: &'static str,
+    pub net_reason_history: &'static str,
+    pub net_resynced_format: &'static str,
+    pub net_resync_failed_format: &'static str,
+
+    // 联机观战
+    pub spectator_connecting: &'static str,
+    pub spectator_status: &'static str,
+    pub spectator_controls: &'static str,
+
+    // 搜索设置和调试信息
+    pub setting_search_memory: &'static str,
+    pub search_memory_format: &'static str,
+    pub debug_table_format: &'static str,
+    pub debug_no_search: &'static str,
+    pub performance_overlay_format: &'static str,
+
+    // 演示对局解说
+    pub commentary_title: &'static str,
+    pub commentary_move_format: &'static str,
+    pub commentary_pass_format: &'static str,
+    pub commentary_corner_format: &'static str,
+    pub commentary_swing_format: &'static str,
+    pub commentary_edge_format: &'static str,
+    pub commentary_mobility_format: &'static str,
+    pub commentary_game_over_format: &'static str,
+
+    // AI搜索说明
+    pub search_caption_format: &'static str,
+
+    // 数字和日期格式
+    pub locale_format: LocaleFormat,
 }
 
 /// 英文文本
@@ -52,29 +361,39 @@ pub const ENGLISH_TEXTS: LocalizedTexts = LocalizedTexts {
     language_chinese: "中文",
 
     // UI 文本
-    score_format: "B:{} W:{}",
     ai_difficulty_format: "AI: {}",
     game_in_progress: "Game in progress",
     click_to_restart: "Click to restart",
+    game_result_format: "{} {}",
     your_turn: "Your turn.",
     ai_turn: "Bill's turn.",
 
     // 难度级别
+    difficulty_chaos: "Chaos",
     difficulty_easy: "Easy",
     difficulty_medium: "Medium",
     difficulty_hard: "Hard",
     difficulty_expert: "Expert",
+    difficulty_expert_plus: "Expert+",
+    difficulty_adaptive: "Adaptive",
+    adaptive_level_format: "{} (Lv.{})",
 
     // 游戏状态
     black_wins: "Black wins!",
     white_wins: "White wins!",
     draw: "Draw!",
-    pass_turn: "has no valid moves. Pass turn.",
+    pass_turn_format: "{} has no valid moves. Pass turn.",
 
     // 规则文本
     rules_title: "Reversi Rules",
     rules_close: "Close",
-    rules_content: "OBJECTIVE:\nCapture the most pieces by the end of the game.\n\nHOW TO PLAY:\n• Players alternate placing pieces\n• Black always goes first\n• Place pieces to trap opponent's pieces\n• Trapped pieces flip to your color\n• Must make a valid move if possible\n• Game ends when board is full or no moves available\n\nVALID MOVES:\n• Must trap at least one opponent piece\n• Pieces are trapped in straight lines (horizontal, vertical, diagonal)\n• All trapped pieces between your new piece and existing piece flip\n\nCONTROLS:\n• Click/tap to place pieces\n• M: Toggle sound",
+    rules_content: "OBJECTIVE:\nCapture the most pieces by the end of the game.\n\nHOW TO PLAY:\n• Players alternate placing pieces\n• Black always goes first\n• Place pieces to trap opponent's pieces\n• Trapped pieces flip to your color\n• Must make a valid move if possible\n• Game ends when board is full or no moves available\n\nVALID MOVES:\n• Must trap at least one opponent piece\n• Pieces are trapped in straight lines (horizontal, vertical, diagonal)\n• All trapped pieces between your new piece and existing piece flip\n\nCONTROLS:\n• Click/tap to place pieces\n• Touch: drag off the square before lifting to cancel\n• Swipe, scroll sideways or ←/→: Review earlier moves\n• M: Toggle sound\n• N: Toggle move announcements\n• F10: Cycle window size\n• F11: Toggle fullscreen",
+    rules_example_format: "Example from the current board: {} plays at {} (ring) and flips {} (outlined)",
+    banter_player_corner: "Nice corner!",
+    banter_ai_corner: "That corner is mine now.",
+    banter_player_big_flip: "I didn't see that coming!",
+    banter_ai_big_flip: "How do you like that?",
+    banter_long_think: "Phew, that one took some thinking.",
 
     // 调试信息
     ai_difficulty_changed: "AI difficulty changed to:",
@@ -86,6 +405,302 @@ pub const ENGLISH_TEXTS: LocalizedTexts = LocalizedTexts {
     loading_text: "Loading...",
     select_difficulty: "Select Difficulty",
     back_to_difficulty: "← Back",
+
+    // 无障碍播报
+    black_name: "Black",
+    white_name: "White",
+    announce_move: "{} plays {}, flips {} discs",
+    announce_move_single: "{} plays {}, flips {} disc",
+    announce_pass: "{} has no valid moves and passes",
+    announce_result: "Game over. {} Black {}, White {}",
+
+    // 设置面板
+    settings_title: "Settings",
+    setting_fullscreen: "Fullscreen",
+    setting_window_size: "Window size",
+    setting_sound: "Sound",
+    setting_announcements: "Move announcements",
+    setting_language: "Language",
+    setting_reduced_motion: "Reduced motion",
+    setting_text_size: "Text size",
+    text_size_small: "Small",
+    text_size_normal: "Normal",
+    text_size_large: "Large",
+    text_size_extra_large: "Extra large",
+    setting_assist_level: "Assist level",
+    assist_level_hardcore: "Hardcore",
+    assist_level_off: "None",
+    assist_level_legal_moves: "Legal moves",
+    assist_level_flip_counts: "Flip counts",
+    assist_level_hints: "Hints allowed",
+    setting_assist_mode: "Assisted mode",
+    setting_corner_warning: "Corner warning",
+    setting_custom_cursor: "Custom cursor",
+    setting_notation_input: "Move entry box",
+    setting_premove: "Premove during AI turn",
+    setting_coach: "Coach",
+    setting_banter: "AI chatter",
+    setting_performance_overlay: "Performance info",
+    setting_search_caption: "AI search info",
+    setting_sound_test: "Sound test...",
+    setting_theme: "Board theme",
+    theme_light: "Light",
+    theme_dark: "Dark",
+    theme_auto: "Auto",
+    value_on: "On",
+    value_off: "Off",
+
+    // 历史浏览
+    history_preview_format: "Move {} of {} · tap to return",
+    history_live_preview: "Current position",
+
+    // 让出角位提醒
+    corner_warning_format: "This lets {} take a corner ({}) — play anyway?",
+    corner_warning_play: "Play anyway",
+    corner_warning_cancel: "Cancel",
+    corner_warning_disable: "Play and don't warn again",
+
+    // 用时统计
+    time_stats_title: "Thinking time",
+    time_stats_format: "{}: total {} · avg {}",
+    flip_stats_title: "Discs flipped",
+    flip_stats_format: "{}: {} flipped · best move {} · {} corners",
+    flip_records_format: "All games: {} flipped · best move {} · {} corners",
+    toast_flip_chain_record: "New record: {} discs flipped in one move!",
+    transcript_export_hint: "Press E to export the transcript",
+    transcript_exported: "Transcript saved",
+    report_export_hint: "Press R to export an analysis report",
+
+    // 危险区域标注
+    danger_zones_toggle: "Danger zones: {}",
+    zone_corner: "Corner",
+    zone_c_square: "C",
+    zone_x_square: "X",
+
+    // 复盘分析
+    analysis_hint: "Press A to analyze the game",
+    analysis_status: "Analysis · move {} · {}",
+    analysis_main_line: "main line",
+    analysis_variation: "variation {}/{}",
+    analysis_controls: "Click: try a move · ←/→: step · ↑/↓: switch variation · Home: main line · Esc: exit",
+
+    // 局面字符串输入
+    position_input_format: "Position: {}",
+    position_input_hint: "Type or paste (Ctrl+V) a position string, Enter to load · Ctrl+C: copy",
+    position_load: "Load",
+    position_copy: "Copy",
+    position_loaded: "Position loaded",
+    position_invalid: "Not a valid position: 64 squares of X/O/- then a space and X or O to move",
+    position_copied: "Copied the current position",
+    position_clipboard_failed: "Could not access the clipboard",
+    position_paste_prompt: "Paste a position string",
+    analysis_win_chance: "Win chance (Black): {}%",
+    brush_fill_row: "Row",
+    brush_fill_column: "Column",
+    brush_mirror: "Mirror",
+    brush_clear: "Clear",
+    brush_swap_colors: "Swap",
+    brush_fill_row_hint: "Click a square to fill its row with the side to move",
+    brush_fill_column_hint: "Click a square to fill its column with the side to move",
+    position_too_few_discs: "Impossible position: fewer than 4 discs",
+    position_empty_center: "Impossible position: the four center squares must be occupied",
+    position_disconnected: "Impossible position: every disc must connect to the center group",
+    analysis_win_chance_unavailable: "Win chance: — (fix the position first)",
+
+    // 暂停菜单
+    pause_title: "Paused",
+    pause_resume: "Resume",
+    pause_restart: "Restart",
+    pause_menu: "Main menu",
+    pause_ai_strength_format: "AI strength: {}",
+
+    // 历史对局
+    archive_title: "History",
+    archive_empty: "No finished games yet",
+    archive_entry: "{} · {} · {} · {}:{}",
+    outcome_win: "Win",
+    outcome_loss: "Loss",
+    outcome_draw: "Draw",
+
+    // 启动加载
+    background_loading: "Loading assets {}/{}",
+    asset_warning_title: "Missing game files",
+    asset_warning_hint: "The game keeps running without them. Check that these files were packaged under assets/:",
+
+    // 教练模式
+    coach_took_corner: "Great: you took a corner",
+    coach_concedes_corner: "Careful: your opponent can now take {}",
+    coach_risky_x_square: "Careful: X-square next to an empty corner",
+    coach_better_move: "Inaccurate: {} was stronger",
+    coach_limited_opponent: "Good: your opponent has few replies left",
+    coach_kept_mobility: "Good: you kept more mobility",
+    coach_solid: "Solid move",
+
+    // 开局练习
+    opening_practice_format: "Opening practice: {}",
+
+    // 自由落子沙盒
+    sandbox_button: "Sandbox (free placement)",
+    sandbox_label: "Sandbox: any empty square",
+    sandbox_not_counted: "Not counted in stats",
+    intro_black_first: "Black moves first",
+    phase_mobility_format: "Mobility {}–{}",
+    phase_stable_format: "Stable {}–{}",
+    phase_empties_format: "Empties {} ({})",
+    parity_odd: "odd",
+    parity_even: "even",
+
+    // 难度按钮上的战绩徽标
+    difficulty_badge_format: "Streak {} (best {}) · Best win +{}",
+    difficulty_subtitle_format: "Depth {} · avg {} per move · ~{} Elo",
+    difficulty_chaos_subtitle_format: "Random moves · instant · ~{} Elo",
+    difficulty_recommended: "Recommended for you",
+
+    // 音效测试
+    sound_test_title: "Sound Test",
+    sound_test_row_format: "{} · {}",
+    sound_test_volume_format: "Volume: {}%",
+    sound_test_play: "Play",
+    sound_piece_place: "Place piece",
+    sound_piece_flip: "Flip pieces",
+    sound_victory: "Victory",
+    sound_defeat: "Defeat",
+    sound_invalid_move: "Invalid move",
+    sound_pass: "Pass",
+    sound_low_time: "Low time",
+    sound_corner_capture: "Corner captured",
+    sound_edge_complete: "Edge completed",
+    sound_wipeout_threat: "Wipeout threat",
+    save_conflict_format: "Another open copy of the game also saved {} · kept this window's version",
+    save_data_preferences: "settings",
+    save_data_stats: "stats",
+    save_data_archive: "game history",
+    save_data_other: "game data",
+    toast_game_saved: "Game saved to history",
+    toast_save_failed_format: "Could not save {}",
+    toast_export_failed: "Could not export the transcript",
+    toast_report_exported: "Analysis report saved (Markdown and HTML)",
+    toast_report_export_failed: "Could not export the analysis report",
+    asset_loading: "loading",
+    asset_loaded: "ok",
+    asset_failed: "missing",
+
+    // 悔棋
+    undo_button: "Undo",
+    pass_button: "Pass",
+    undo_remaining_format: "Undo ({})",
+    undo_disabled_rated: "No undo in rated games",
+    undo_limit_reached: "No undos left this game",
+    undo_nothing: "Nothing to undo yet",
+    undo_wait_turn: "Wait for your turn",
+    notation_input_format: "Move (e.g. f5): {}",
+    insights_title: "Insights",
+    insights_disabled: "Local insights are off. Turn them on in Settings to record games and feature usage on this device.",
+    insights_empty: "Nothing recorded yet",
+    insights_games_format: "{}: {} games",
+    insights_average_format: "Average game: {} moves, {}",
+    insights_features_header: "Feature usage",
+    insights_feature_format: "{}: {}",
+    insights_clear: "Clear",
+    usage_undo: "Undo",
+    usage_danger_zones: "Danger zones",
+    usage_history_review: "History review",
+    usage_analysis: "Analysis",
+    usage_replay: "Replays",
+    usage_sandbox: "Sandbox games",
+    usage_opening_practice: "Opening practice",
+    usage_rules: "Rules",
+    setting_insights: "Local insights",
+
+    // 自定义对局规则
+    setting_victory: "Victory",
+    victory_standard: "Standard",
+    victory_margin_format: "Win by {}+",
+    victory_first_to_format: "First to {}",
+
+    // 多局比赛
+    setting_match: "Match",
+    match_single: "Single game",
+    match_best_of_format: "Best of {}",
+    match_score_format: "Game {}/{} · You {}–{} AI",
+    match_won_format: "You won the match {}–{}",
+    match_lost_format: "AI won the match {}–{}",
+    match_drawn_format: "Match drawn {}–{}",
+    archive_series_format: "{} · G{}/{}",
+
+    // 对局计时
+    setting_time_control: "Time control",
+    time_control_format: "Time control: {}",
+    time_control_unlimited: "Untimed",
+    time_control_blitz: "Blitz 3+2",
+    time_control_rapid: "Rapid 10+5",
+    time_control_casual: "Casual 30+0",
+    time_forfeit_format: "{} ran out of time.",
+
+    // 快捷键帮助
+    shortcuts_title: "Keyboard Shortcuts",
+    shortcut_row_format: "{}: {}",
+    shortcut_keys_format: "{} / {}",
+    shortcuts_close_hint: "Press {} again to close",
+    key_space: "Space",
+    key_enter: "Enter",
+    shortcut_toggle_help: "Show/hide this help",
+    shortcut_toggle_sound: "Mute/unmute sound",
+    shortcut_toggle_announcements: "Toggle move announcements",
+    shortcut_toggle_fullscreen: "Toggle fullscreen",
+    shortcut_cycle_window_size: "Cycle window size",
+    shortcut_pause: "Pause/resume",
+    shortcut_undo: "Undo",
+    shortcut_hint: "Show the best move",
+    shortcut_toggle_square_labels: "Show square labels",
+    shortcut_toggle_debug_overlay: "Show search statistics",
+    shortcut_toggle_performance_overlay: "Show performance info",
+    shortcut_step_back: "Step back",
+    shortcut_step_forward: "Step forward",
+    shortcut_restart: "Next game",
+    shortcut_analyze: "Analyze game",
+    shortcut_export_transcript: "Export transcript",
+    shortcut_export_report: "Export analysis report",
+    shortcut_previous_variation: "Previous variation",
+    shortcut_next_variation: "Next variation",
+    shortcut_main_line: "Back to main line",
+    shortcut_leave_analysis: "Leave analysis",
+
+    // 联机同步提示
+    net_desync_format: "Out of sync at move {} ({}), resyncing…",
+    net_reason_invalid_move: "invalid move",
+    net_reason_checksum: "position mismatch",
+    net_reason_history: "invalid history",
+    net_resynced_format: "Back in sync after move {} ({} squares corrected)",
+    net_resync_failed_format: "Resync failed after move {}, please reconnect",
+
+    // 联机观战
+    spectator_connecting: "Joining the match as a spectator…",
+    spectator_status: "Spectating · move {} · {} to move",
+    spectator_controls: "Read-only · Esc: leave",
+
+    // 搜索设置和调试信息
+    setting_search_memory: "Search memory",
+    search_memory_format: "{} MB",
+    debug_table_format: "Transposition table {} MB · hit rate {} · {} probes · {} stores",
+    debug_no_search: "No AI search in this game",
+    performance_overlay_format: "FPS {} · {} ms\nEntities {}\nAI move {} (avg {})",
+
+    // 演示对局解说
+    commentary_title: "Commentary",
+    commentary_move_format: "{}. {} plays {}, flipping {}",
+    commentary_pass_format: "{} has no moves and passes",
+    commentary_corner_format: "{} takes the {} corner!",
+    commentary_swing_format: "Big swing toward {}: winning chances now {}%",
+    commentary_edge_format: "{} gains ground ({}% to win)",
+    commentary_mobility_format: "{} is down to {} moves",
+    commentary_game_over_format: "Game over: Black {} – White {}",
+
+    // AI搜索说明
+    search_caption_format: "Searched {} plies in {}, {} nodes",
+
+    // 数字和日期格式
+    locale_format: ENGLISH_FORMAT,
 };
 
 /// 中文文本
@@ -96,29 +711,39 @@ pub const CHINESE_TEXTS: LocalizedTexts = LocalizedTexts {
     language_chinese: "中文",
 
     // UI 文本
-    score_format: "黑:{} 白:{}",
     ai_difficulty_format: "AI: {}",
     game_in_progress: "游戏进行中",
     click_to_restart: "点击重新开始",
+    game_result_format: "{} {}",
     your_turn: "轮到你了。",
     ai_turn: "AI回合。",
 
     // 难度级别
+    difficulty_chaos: "混乱",
     difficulty_easy: "简单",
     difficulty_medium: "中等",
     difficulty_hard: "困难",
     difficulty_expert: "专家",
+    difficulty_expert_plus: "专家+",
+    difficulty_adaptive: "自适应",
+    adaptive_level_format: "{}（{}级）",
 
     // 游戏状态
     black_wins: "黑棋获胜！",
     white_wins: "白棋获胜！",
     draw: "平局！",
-    pass_turn: "无可用走法，跳过回合。",
+    pass_turn_format: "{}无可用走法，跳过回合。",
 
     // 规则文本
     rules_title: "黑白棋规则",
     rules_close: "关闭",
-    rules_content: "游戏目标：\n在游戏结束时获得最多的棋子。\n\n游戏玩法：\n• 玩家轮流放置棋子\n• 黑棋先手\n• 放置棋子以夹住对手棋子\n• 被夹住的棋子翻转为己方颜色\n• 有合法走法时必须走棋\n• 棋盘填满或无合法走法时游戏结束\n\n合法走法：\n• 必须至少夹住一个对手棋子\n• 棋子在直线上被夹住（水平、垂直、对角线）\n• 新棋子与已有棋子之间的所有对手棋子都会翻转\n\n操作控制：\n• 点击/触摸放置棋子\n• M：切换音效",
+    rules_content: "游戏目标：\n在游戏结束时获得最多的棋子。\n\n游戏玩法：\n• 玩家轮流放置棋子\n• 黑棋先手\n• 放置棋子以夹住对手棋子\n• 被夹住的棋子翻转为己方颜色\n• 有合法走法时必须走棋\n• 棋盘填满或无合法走法时游戏结束\n\n合法走法：\n• 必须至少夹住一个对手棋子\n• 棋子在直线上被夹住（水平、垂直、对角线）\n• 新棋子与已有棋子之间的所有对手棋子都会翻转\n\n操作控制：\n• 点击/触摸放置棋子\n• 触摸时拖出该格再抬起可取消落子\n• 左右滑动、横向滚动或←/→：回看之前的局面\n• M：切换音效\n• N：切换走棋播报\n• F10：切换窗口尺寸\n• F11：切换全屏",
+    rules_example_format: "当前局面示例：{}下在{}（圆环），翻转{}（描边）",
+    banter_player_corner: "好角！",
+    banter_ai_corner: "这个角归我了。",
+    banter_player_big_flip: "这一步我可没想到！",
+    banter_ai_big_flip: "这一手怎么样？",
+    banter_long_think: "呼，这步想得可真久。",
 
     // 调试信息
     ai_difficulty_changed: "AI难度已改为：",
@@ -130,4 +755,300 @@ pub const CHINESE_TEXTS: LocalizedTexts = LocalizedTexts {
     loading_text: "加载中...",
     select_difficulty: "选择难度",
     back_to_difficulty: "← 返回",
+
+    // 无障碍播报
+    black_name: "黑棋",
+    white_name: "白棋",
+    announce_move: "{}落子{}，翻转{}枚棋子",
+    announce_move_single: "{}落子{}，翻转{}枚棋子",
+    announce_pass: "{}无棋可走，跳过回合",
+    announce_result: "游戏结束。{} 黑{}，白{}",
+
+    // 设置面板
+    settings_title: "设置",
+    setting_fullscreen: "全屏",
+    setting_window_size: "窗口尺寸",
+    setting_sound: "音效",
+    setting_announcements: "走棋播报",
+    setting_language: "语言",
+    setting_reduced_motion: "减少动效",
+    setting_text_size: "文字大小",
+    text_size_small: "小",
+    text_size_normal: "标准",
+    text_size_large: "大",
+    text_size_extra_large: "特大",
+    setting_assist_level: "辅助等级",
+    assist_level_hardcore: "硬核",
+    assist_level_off: "无",
+    assist_level_legal_moves: "可落子位置",
+    assist_level_flip_counts: "翻转数",
+    assist_level_hints: "允许提示",
+    setting_assist_mode: "辅助模式",
+    setting_corner_warning: "让角提醒",
+    setting_custom_cursor: "自定义光标",
+    setting_notation_input: "棋谱输入框",
+    setting_premove: "AI回合中预走",
+    setting_coach: "教练点评",
+    setting_banter: "AI闲聊",
+    setting_performance_overlay: "性能信息",
+    setting_search_caption: "AI搜索信息",
+    setting_sound_test: "音效测试…",
+    setting_theme: "棋盘主题",
+    theme_light: "浅色",
+    theme_dark: "深色",
+    theme_auto: "自动",
+    value_on: "开",
+    value_off: "关",
+
+    // 历史浏览
+    history_preview_format: "第{}步 / 共{}步 · 点击返回",
+    history_live_preview: "当前局面",
+
+    // 让出角位提醒
+    corner_warning_format: "这步棋会让{}占到角（{}）—— 仍然要下吗？",
+    corner_warning_play: "仍然落子",
+    corner_warning_cancel: "取消",
+    corner_warning_disable: "落子并不再提醒",
+
+    // 用时统计
+    time_stats_title: "思考用时",
+    time_stats_format: "{}：共{} · 平均{}",
+    flip_stats_title: "翻转棋子",
+    flip_stats_format: "{}：翻转{}枚 · 单步最多{}枚 · 占角{}个",
+    flip_records_format: "累计：翻转{}枚 · 单步最多{}枚 · 占角{}个",
+    toast_flip_chain_record: "新纪录：一步翻转{}枚棋子！",
+    transcript_export_hint: "按E导出棋谱",
+    transcript_exported: "棋谱已保存",
+    report_export_hint: "按R导出分析报告",
+
+    // 危险区域标注
+    danger_zones_toggle: "危险区域：{}",
+    zone_corner: "角",
+    zone_c_square: "C位",
+    zone_x_square: "X位",
+
+    // 复盘分析
+    analysis_hint: "按A进入复盘分析",
+    analysis_status: "复盘分析 · 第{}步 · {}",
+    analysis_main_line: "主线",
+    analysis_variation: "变着 {}/{}",
+    analysis_controls: "点击：尝试走法 · ←/→：前后移动 · ↑/↓：切换变着 · Home：回到主线 · Esc：退出",
+
+    // 局面字符串输入
+    position_input_format: "局面：{}",
+    position_input_hint: "键入或粘贴（Ctrl+V）局面字符串，回车载入 · Ctrl+C：复制",
+    position_load: "载入",
+    position_copy: "复制",
+    position_loaded: "已载入局面",
+    position_invalid: "局面无效：应为64格X/O/-，加一个空格和轮到的一方X或O",
+    position_copied: "已复制当前局面",
+    position_clipboard_failed: "无法访问剪贴板",
+    position_paste_prompt: "粘贴局面字符串",
+    analysis_win_chance: "胜率（黑方）：{}%",
+    brush_fill_row: "整行",
+    brush_fill_column: "整列",
+    brush_mirror: "镜像",
+    brush_clear: "清空",
+    brush_swap_colors: "交换颜色",
+    brush_fill_row_hint: "点击一格，用轮到的一方填满这一行",
+    brush_fill_column_hint: "点击一格，用轮到的一方填满这一列",
+    position_too_few_discs: "不可能的局面：棋子少于4枚",
+    position_empty_center: "不可能的局面：中央四格必须有棋子",
+    position_disconnected: "不可能的局面：所有棋子都必须与中央的棋子相连",
+    analysis_win_chance_unavailable: "胜率：—（请先修正局面）",
+
+    // 暂停菜单
+    pause_title: "已暂停",
+    pause_resume: "继续",
+    pause_restart: "重新开始",
+    pause_menu: "返回菜单",
+    pause_ai_strength_format: "AI强度：{}",
+
+    // 历史对局
+    archive_title: "历史对局",
+    archive_empty: "还没有完成的对局",
+    archive_entry: "{} · {} · {} · {}:{}",
+    outcome_win: "胜",
+    outcome_loss: "负",
+    outcome_draw: "平",
+
+    // 启动加载
+    background_loading: "正在加载资源 {}/{}",
+    asset_warning_title: "缺少游戏文件",
+    asset_warning_hint: "游戏会在缺少这些文件的情况下继续运行，请检查打包时assets/目录中是否包含：",
+
+    // 教练模式
+    coach_took_corner: "好棋：你占到了角",
+    coach_concedes_corner: "注意：对手现在可以占据{}",
+    coach_risky_x_square: "注意：你下在了空角旁边的X位",
+    coach_better_move: "欠佳：{}更好",
+    coach_limited_opponent: "好棋：对手可走的棋不多了",
+    coach_kept_mobility: "好棋：你保持了更多的行动力",
+    coach_solid: "稳健的一步",
+
+    // 开局练习
+    opening_practice_format: "开局练习：{}",
+
+    // 自由落子沙盒
+    sandbox_button: "沙盒（自由落子）",
+    sandbox_label: "沙盒：任意空格均可落子",
+    sandbox_not_counted: "不计入战绩",
+    intro_black_first: "黑棋先行",
+    phase_mobility_format: "行动力 {}–{}",
+    phase_stable_format: "稳定子 {}–{}",
+    phase_empties_format: "空位 {}（{}）",
+    parity_odd: "奇数",
+    parity_even: "偶数",
+
+    // 难度按钮上的战绩徽标
+    difficulty_badge_format: "连胜{}（最高{}）· 最大胜差+{}",
+    difficulty_subtitle_format: "深度{} · 每步平均{} · 约{}等级分",
+    difficulty_chaos_subtitle_format: "随机走棋 · 立即落子 · 约{}等级分",
+    difficulty_recommended: "为你推荐",
+
+    // 音效测试
+    sound_test_title: "音效测试",
+    sound_test_row_format: "{} · {}",
+    sound_test_volume_format: "音量：{}%",
+    sound_test_play: "播放",
+    sound_piece_place: "落子",
+    sound_piece_flip: "翻转",
+    sound_victory: "胜利",
+    sound_defeat: "失败",
+    sound_invalid_move: "无效落子",
+    sound_pass: "跳过回合",
+    sound_low_time: "时间不足",
+    sound_corner_capture: "占角",
+    sound_edge_complete: "占满一条边",
+    sound_wipeout_threat: "即将全灭",
+    save_conflict_format: "另一个游戏窗口也保存了{} · 已保留本窗口的版本",
+    save_data_preferences: "设置",
+    save_data_stats: "战绩",
+    save_data_archive: "历史对局",
+    save_data_other: "游戏数据",
+    toast_game_saved: "对局已保存到历史对局",
+    toast_save_failed_format: "无法保存{}",
+    toast_export_failed: "无法导出棋谱",
+    toast_report_exported: "分析报告已保存（Markdown和HTML）",
+    toast_report_export_failed: "无法导出分析报告",
+    asset_loading: "加载中",
+    asset_loaded: "正常",
+    asset_failed: "缺失",
+
+    // 悔棋
+    undo_button: "悔棋",
+    pass_button: "跳过",
+    undo_remaining_format: "悔棋（{}）",
+    undo_disabled_rated: "计分对局不能悔棋",
+    undo_limit_reached: "本局悔棋次数已用完",
+    undo_nothing: "还没有可以撤销的落子",
+    undo_wait_turn: "请等待轮到你",
+    notation_input_format: "走法（例如f5）：{}",
+    insights_title: "使用统计",
+    insights_disabled: "使用统计未开启。在设置中开启后，对局和功能使用情况只记录在本机",
+    insights_empty: "还没有记录",
+    insights_games_format: "{}：{}局",
+    insights_average_format: "平均每局：{}步，{}",
+    insights_features_header: "功能使用",
+    insights_feature_format: "{}：{}次",
+    insights_clear: "清空",
+    usage_undo: "悔棋",
+    usage_danger_zones: "危险区域",
+    usage_history_review: "回看历史",
+    usage_analysis: "复盘分析",
+    usage_replay: "回放对局",
+    usage_sandbox: "沙盒对局",
+    usage_opening_practice: "开局练习",
+    usage_rules: "游戏规则",
+    setting_insights: "使用统计",
+
+    // 自定义对局规则
+    setting_victory: "胜负规则",
+    victory_standard: "标准",
+    victory_margin_format: "领先{}子获胜",
+    victory_first_to_format: "先到{}子获胜",
+
+    // 多局比赛
+    setting_match: "赛制",
+    match_single: "单局",
+    match_best_of_format: "{}局{}胜",
+    match_score_format: "第{}/{}局 · 你 {}:{} AI",
+    match_won_format: "你赢得了比赛 {}:{}",
+    match_lost_format: "AI赢得了比赛 {}:{}",
+    match_drawn_format: "比赛战平 {}:{}",
+    archive_series_format: "{} · 第{}/{}局",
+
+    // 对局计时
+    setting_time_control: "计时",
+    time_control_format: "计时：{}",
+    time_control_unlimited: "不计时",
+    time_control_blitz: "快棋 3+2",
+    time_control_rapid: "中速 10+5",
+    time_control_casual: "休闲 30+0",
+    time_forfeit_format: "{}超时。",
+
+    // 快捷键帮助
+    shortcuts_title: "键盘快捷键",
+    shortcut_row_format: "{}：{}",
+    shortcut_keys_format: "{} / {}",
+    shortcuts_close_hint: "再按{}关闭",
+    key_space: "空格",
+    key_enter: "回车",
+    shortcut_toggle_help: "显示/隐藏快捷键帮助",
+    shortcut_toggle_sound: "开关音效",
+    shortcut_toggle_announcements: "开关走棋播报",
+    shortcut_toggle_fullscreen: "切换全屏",
+    shortcut_cycle_window_size: "切换窗口尺寸",
+    shortcut_pause: "暂停/继续",
+    shortcut_undo: "悔棋",
+    shortcut_hint: "显示最佳走法",
+    shortcut_toggle_square_labels: "显示格子编号",
+    shortcut_toggle_debug_overlay: "显示搜索统计",
+    shortcut_toggle_performance_overlay: "显示性能信息",
+    shortcut_step_back: "后退一步",
+    shortcut_step_forward: "前进一步",
+    shortcut_restart: "开始下一局",
+    shortcut_analyze: "复盘分析",
+    shortcut_export_transcript: "导出棋谱",
+    shortcut_export_report: "导出分析报告",
+    shortcut_previous_variation: "上一个变着",
+    shortcut_next_variation: "下一个变着",
+    shortcut_main_line: "回到主线",
+    shortcut_leave_analysis: "退出分析",
+
+    // 联机同步提示
+    net_desync_format: "第{}步与对方不同步（{}），正在重新同步…",
+    net_reason_invalid_move: "走法不合法",
+    net_reason_checksum: "局面不一致",
+    net_reason_history: "历史记录无效",
+    net_resynced_format: "已在第{}步后重新同步（纠正了{}个格子）",
+    net_resync_failed_format: "第{}步后重新同步失败，请重新连接",
+
+    // 联机观战
+    spectator_connecting: "正在以观战者身份加入对局…",
+    spectator_status: "观战中 · 第{}步 · 轮到{}",
+    spectator_controls: "只读 · Esc：退出观战",
+
+    // 搜索设置和调试信息
+    setting_search_memory: "搜索内存",
+    search_memory_format: "{} MB",
+    debug_table_format: "置换表 {} MB · 命中率{} · 查询{}次 · 写入{}次",
+    debug_no_search: "本局没有AI搜索",
+    performance_overlay_format: "帧率 {} · {} 毫秒\n实体 {}\nAI每步 {}（平均 {}）",
+
+    // 演示对局解说
+    commentary_title: "解说",
+    commentary_move_format: "{}. {}下在{}，翻转{}枚",
+    commentary_pass_format: "{}无处可下，跳过回合",
+    commentary_corner_format: "{}占据了{}角！",
+    commentary_swing_format: "局势大幅倒向{}：胜率升至{}%",
+    commentary_edge_format: "{}稍占上风（胜率{}%）",
+    commentary_mobility_format: "{}只剩{}步可走",
+    commentary_game_over_format: "对局结束：黑{} 白{}",
+
+    // AI搜索说明
+    search_caption_format: "搜索了{}步，用时{}，{}个节点",
+
+    // 数字和日期格式
+    locale_format: CHINESE_FORMAT,
 };
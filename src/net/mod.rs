@@ -0,0 +1,178 @@
+// 联机协议 - 带版本握手的消息定义，与传输层无关
+//
+// 客户端连接后先发送Hello声明协议版本和身份（对弈者或观战者），
+// 服务器校验版本后回复Welcome，其中包含初始局面、已有的全部走棋命令和建局时确定的对局规则，
+// 之后双方只交换按序号校验的走棋命令；观战者只接收命令流，不能走棋。
+// 服务器广播每条命令时附带走完这步后的局面哈希，客户端在本地棋盘上校验并应用命令后回报自己的哈希；
+// 任一方发现命令不合法或哈希不一致时，服务器发送完整的局面字符串和命令历史让客户端重新同步（见sync）。
+//
+// 消息以RON文本编码，每条消息占一帧，具体的传输方式（WebSocket、TCP等）由上层决定
+
+pub mod relay;
+pub mod spectator;
+pub mod sync;
+
+pub use relay::*;
+pub use spectator::*;
+pub use sync::*;
+
+use crate::assist::MatchRules;
+use crate::game::{position_hash, Board, CommandError, GameLog, MoveCommand, PlayerColor};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
+
+/// 当前协议版本，消息格式有不兼容的改动时递增
+///
+/// 版本2：广播的命令附带局面哈希，新增哈希回报和重新同步消息
+/// 版本3：Welcome附带对局规则，旧客户端无法执行辅助功能的限制，不再兼容
+pub const PROTOCOL_VERSION: u16 = 3;
+
+/// 仍然兼容的最低协议版本
+pub const MIN_PROTOCOL_VERSION: u16 = 3;
+
+/// 客户端在对局中的身份
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Role {
+    /// 对弈者，占据一个颜色的座位
+    Player,
+    /// 观战者，只读接收命令流
+    Spectator,
+}
+
+/// 客户端发往服务器的消息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// 握手：协议版本和请求的身份
+    Hello { version: u16, role: Role },
+    /// 走棋命令
+    Move(MoveCommand),
+    /// 应用服务器广播的命令后回报本地局面的哈希
+    Checksum(PositionChecksum),
+    /// 本地日志与服务器不一致，请求重新同步
+    RequestResync,
+    /// 离开对局
+    Leave,
+}
+
+/// 服务器发往客户端的消息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServerMessage {
+    /// 握手成功，附带对局的完整状态，加入时对局可能已经开始
+    Welcome {
+        version: u16,
+        role: Role,
+        /// 对弈者分配到的颜色，观战者为None
+        seat: Option<PlayerColor>,
+        initial: Board,
+        first_player: PlayerColor,
+        history: Vec<MoveCommand>,
+        /// 对局中允许使用的辅助功能，对弈者和观战者都需要遵守
+        rules: MatchRules,
+    },
+    /// 握手失败
+    Rejected(HandshakeError),
+    /// 已被接受的走棋命令和走完这步后的局面哈希，广播给所有对弈者和观战者
+    Command {
+        command: MoveCommand,
+        checksum: PositionChecksum,
+    },
+    /// 重新同步：完整的命令历史和当前局面字符串，客户端重放历史后用局面字符串核对
+    Resync {
+        initial: Board,
+        first_player: PlayerColor,
+        history: Vec<MoveCommand>,
+        position: String,
+    },
+    /// 发送的走棋命令被拒绝
+    MoveRejected(MoveRejection),
+    /// 有对弈者离开
+    PlayerLeft(PlayerColor),
+}
+
+/// 某一步之后的局面哈希
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionChecksum {
+    /// 已应用的命令数，即下一条命令的序号
+    pub sequence: u32,
+    /// 局面哈希，包含棋盘和轮到的一方
+    pub hash: u64,
+}
+
+impl PositionChecksum {
+    /// 命令日志当前局面的哈希
+    pub fn of(log: &GameLog) -> Self {
+        Self {
+            sequence: log.next_sequence(),
+            hash: position_hash(&log.board(), log.to_move()),
+        }
+    }
+}
+
+/// 握手失败的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HandshakeError {
+    /// 客户端协议版本不兼容，附带服务器支持的版本范围
+    UnsupportedVersion { min: u16, max: u16 },
+    /// 两个对弈者座位都已占满
+    SeatsFull,
+    /// 已经完成过握手
+    AlreadyJoined,
+}
+
+impl fmt::Display for HandshakeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion { min, max } => {
+                write!(f, "unsupported protocol version, expected {min}..={max}")
+            }
+            Self::SeatsFull => write!(f, "both player seats are taken"),
+            Self::AlreadyJoined => write!(f, "already joined"),
+        }
+    }
+}
+
+/// 走棋命令被拒绝的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MoveRejection {
+    /// 还没有完成握手
+    NotJoined,
+    /// 观战者不能走棋
+    ReadOnly,
+    /// 命令不是以自己的颜色发出的
+    NotYourSeat,
+    /// 命令未通过日志校验
+    Invalid(CommandError),
+}
+
+impl fmt::Display for MoveRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotJoined => write!(f, "handshake required"),
+            Self::ReadOnly => write!(f, "spectators cannot move"),
+            Self::NotYourSeat => write!(f, "not your seat"),
+            Self::Invalid(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+/// 检查客户端的协议版本是否兼容
+pub fn check_version(version: u16) -> Result<(), HandshakeError> {
+    if (MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&version) {
+        Ok(())
+    } else {
+        Err(HandshakeError::UnsupportedVersion {
+            min: MIN_PROTOCOL_VERSION,
+            max: PROTOCOL_VERSION,
+        })
+    }
+}
+
+/// 将消息编码为一帧RON文本
+pub fn encode<T: Serialize>(message: &T) -> Result<String, String> {
+    ron::to_string(message).map_err(|err| err.to_string())
+}
+
+/// 解码一帧RON文本
+pub fn decode<T: DeserializeOwned>(frame: &str) -> Result<T, String> {
+    ron::from_str(frame).map_err(|err| err.to_string())
+}
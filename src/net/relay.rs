@@ -0,0 +1,205 @@
+// 对局转发 - 服务器端维护权威命令日志，并把命令流转发给对弈者和观战者
+//
+// 转发器不关心传输方式：每收到一条客户端消息就返回需要发送的消息列表。
+// 对局规则在大厅建局时确定，握手时发给每个加入的客户端。
+// 客户端回报的局面哈希与权威日志在同一步的哈希不一致时，直接向该客户端发送重新同步消息
+
+use super::{
+    check_version, ClientMessage, HandshakeError, MoveRejection, PositionChecksum, Role,
+    ServerMessage, PROTOCOL_VERSION,
+};
+use crate::{
+    assist::MatchRules,
+    game::{position_string, Board, GameLog, PlayerColor},
+};
+use std::collections::BTreeMap;
+
+/// 连接标识，由传输层分配
+pub type ClientId = u64;
+
+/// 需要发送给某个客户端的消息
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outgoing {
+    pub to: ClientId,
+    pub message: ServerMessage,
+}
+
+/// 已完成握手的客户端
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Participant {
+    role: Role,
+    seat: Option<PlayerColor>,
+}
+
+/// 一局联机对局的转发器
+#[derive(Debug, Clone)]
+pub struct MatchRelay {
+    log: GameLog,
+    rules: MatchRules,
+    participants: BTreeMap<ClientId, Participant>,
+}
+
+impl MatchRelay {
+    /// 从初始局面创建对局，黑棋先行，使用默认的对局规则
+    pub fn new(initial: Board) -> Self {
+        Self {
+            log: GameLog::new(initial, PlayerColor::Black),
+            rules: MatchRules::default(),
+            participants: BTreeMap::new(),
+        }
+    }
+
+    /// 使用大厅中约定的对局规则
+    pub fn with_rules(mut self, rules: MatchRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// 权威命令日志
+    pub fn log(&self) -> &GameLog {
+        &self.log
+    }
+
+    /// 对局规则
+    pub fn rules(&self) -> MatchRules {
+        self.rules
+    }
+
+    /// 当前观战者数量
+    pub fn spectator_count(&self) -> usize {
+        self.participants
+            .values()
+            .filter(|participant| participant.role == Role::Spectator)
+            .count()
+    }
+
+    /// 处理一条客户端消息，返回需要发送的消息
+    pub fn handle(&mut self, from: ClientId, message: ClientMessage) -> Vec<Outgoing> {
+        match message {
+            ClientMessage::Hello { version, role } => self.handle_hello(from, version, role),
+            ClientMessage::Move(command) => {
+                let Some(participant) = self.participants.get(&from) else {
+                    return reject_move(from, MoveRejection::NotJoined);
+                };
+                let Some(seat) = participant.seat else {
+                    return reject_move(from, MoveRejection::ReadOnly);
+                };
+                if command.player != seat {
+                    return reject_move(from, MoveRejection::NotYourSeat);
+                }
+
+                match self.log.apply(command) {
+                    Ok(()) => self.broadcast(ServerMessage::Command {
+                        command,
+                        checksum: PositionChecksum::of(&self.log),
+                    }),
+                    Err(err) => reject_move(from, MoveRejection::Invalid(err)),
+                }
+            }
+            ClientMessage::Checksum(reported) => {
+                if !self.participants.contains_key(&from)
+                    || self.checksum_at(reported.sequence) == Some(reported)
+                {
+                    return Vec::new();
+                }
+                self.resync(from)
+            }
+            ClientMessage::RequestResync => {
+                if !self.participants.contains_key(&from) {
+                    return Vec::new();
+                }
+                self.resync(from)
+            }
+            ClientMessage::Leave => self.disconnect(from),
+        }
+    }
+
+    /// 客户端断开连接，对弈者离开时通知其他人
+    pub fn disconnect(&mut self, client: ClientId) -> Vec<Outgoing> {
+        match self.participants.remove(&client) {
+            Some(Participant {
+                seat: Some(seat), ..
+            }) => self.broadcast(ServerMessage::PlayerLeft(seat)),
+            _ => Vec::new(),
+        }
+    }
+
+    fn handle_hello(&mut self, from: ClientId, version: u16, role: Role) -> Vec<Outgoing> {
+        let result = check_version(version).and_then(|()| {
+            if self.participants.contains_key(&from) {
+                return Err(HandshakeError::AlreadyJoined);
+            }
+            match role {
+                Role::Player => self.free_seat().map(Some).ok_or(HandshakeError::SeatsFull),
+                Role::Spectator => Ok(None),
+            }
+        });
+
+        let message = match result {
+            Ok(seat) => {
+                self.participants.insert(from, Participant { role, seat });
+                ServerMessage::Welcome {
+                    version: PROTOCOL_VERSION,
+                    role,
+                    seat,
+                    initial: self.log.initial(),
+                    first_player: self.log.first_player(),
+                    history: self.log.commands().to_vec(),
+                    rules: self.rules,
+                }
+            }
+            Err(err) => ServerMessage::Rejected(err),
+        };
+        vec![Outgoing { to: from, message }]
+    }
+
+    /// 权威日志应用了指定数量的命令后的局面哈希，序号超出已有命令时为None
+    pub fn checksum_at(&self, sequence: u32) -> Option<PositionChecksum> {
+        let commands = self.log.commands().get(..sequence as usize)?;
+        GameLog::replay(self.log.initial(), self.log.first_player(), commands)
+            .ok()
+            .map(|log| PositionChecksum::of(&log))
+    }
+
+    /// 向客户端发送完整的命令历史和当前局面字符串
+    fn resync(&self, to: ClientId) -> Vec<Outgoing> {
+        vec![Outgoing {
+            to,
+            message: ServerMessage::Resync {
+                initial: self.log.initial(),
+                first_player: self.log.first_player(),
+                history: self.log.commands().to_vec(),
+                position: position_string(&self.log.board(), self.log.to_move()),
+            },
+        }]
+    }
+
+    /// 黑棋座位优先分配
+    fn free_seat(&self) -> Option<PlayerColor> {
+        [PlayerColor::Black, PlayerColor::White]
+            .into_iter()
+            .find(|color| {
+                !self
+                    .participants
+                    .values()
+                    .any(|participant| participant.seat == Some(*color))
+            })
+    }
+
+    fn broadcast(&self, message: ServerMessage) -> Vec<Outgoing> {
+        self.participants
+            .keys()
+            .map(|&to| Outgoing {
+                to,
+                message: message.clone(),
+            })
+            .collect()
+    }
+}
+
+fn reject_move(to: ClientId, reason: MoveRejection) -> Vec<Outgoing> {
+    vec![Outgoing {
+        to,
+        message: ServerMessage::MoveRejected(reason),
+    }]
+}
@@ -0,0 +1,118 @@
+// 观战客户端 - 根据服务器的命令流维护本地命令日志
+//
+// 中途加入时用Welcome中的历史命令重放出当前局面，之后逐条校验并应用广播的命令，
+// 与服务器失步时自动重新同步（见sync）；
+// 得到的GameLog与本地对局使用的相同，可以直接交给棋盘界面渲染。
+//
+// 游戏本身不包含传输层，观战是提供给宿主应用的接口：宿主应用建立连接后，
+// 在难度选择界面通过GameTransitions请求Spectate切换，并在SpectatorLink和连接之间搬运消息帧
+// （用进程内转发器演示的完整流程见examples/embed_in_bevy_app.rs）。观战状态（Spectating）中：
+// - 进入时创建SpectatorView并排队发送Hello握手
+// - 每帧解码收到的消息帧交给SpectatorView，回复排队发送，同步事件交给界面提示
+// - 退出时排队发送Leave，由传输层发出后断开连接
+// 棋盘的渲染和状态面板见ui::spectator_ui
+
+use super::{
+    decode, encode, ClientMessage, ClientSync, Role, ServerMessage, SyncEvent, SyncStep,
+    PROTOCOL_VERSION,
+};
+use crate::{assist::MatchRules, game::GameLog};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// 观战客户端的本地状态，观战状态中作为资源存在
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SpectatorView {
+    sync: ClientSync,
+}
+
+impl SpectatorView {
+    /// 握手消息
+    pub fn hello() -> ClientMessage {
+        ClientMessage::Hello {
+            version: PROTOCOL_VERSION,
+            role: Role::Spectator,
+        }
+    }
+
+    /// 当前同步到的命令日志，握手完成前为None
+    pub fn log(&self) -> Option<&GameLog> {
+        self.sync.log()
+    }
+
+    /// 握手时收到的对局规则，观战者同样不能看到被规则关闭的辅助信息
+    pub fn rules(&self) -> Option<MatchRules> {
+        self.sync.rules()
+    }
+
+    /// 处理一条服务器消息，返回需要回复的消息和同步状态的变化
+    pub fn receive(&mut self, message: &ServerMessage) -> SyncStep {
+        self.sync.receive(message)
+    }
+}
+
+/// 观战连接的消息帧队列，由传输层填充和取走
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SpectatorLink {
+    /// 收到的服务器消息帧，按到达顺序处理
+    pub incoming: VecDeque<String>,
+    /// 等待发送给服务器的消息帧
+    pub outgoing: Vec<String>,
+}
+
+impl SpectatorLink {
+    /// 编码一条消息并排队发送
+    fn send(&mut self, message: &ClientMessage) {
+        match encode(message) {
+            Ok(frame) => self.outgoing.push(frame),
+            Err(err) => warn!("Failed to encode spectator message: {err}"),
+        }
+    }
+}
+
+/// 进入观战：创建本地状态并发送握手
+pub fn start_spectating(mut commands: Commands, mut link: ResMut<SpectatorLink>) {
+    commands.insert_resource(SpectatorView::default());
+    link.send(&SpectatorView::hello());
+}
+
+/// 处理收到的消息帧，无法解码的帧记录警告后丢弃
+pub fn receive_spectator_frames(
+    mut link: ResMut<SpectatorLink>,
+    mut view: ResMut<SpectatorView>,
+    mut sync_events: EventWriter<SyncEvent>,
+) {
+    if link.incoming.is_empty() {
+        return;
+    }
+
+    // 只在确实收到消息时标记变化，避免每帧重绘棋盘
+    let mut received = false;
+    while let Some(frame) = link.incoming.pop_front() {
+        let message = match decode::<ServerMessage>(&frame) {
+            Ok(message) => message,
+            Err(err) => {
+                warn!("Failed to decode server message: {err}");
+                continue;
+            }
+        };
+        let step = view.bypass_change_detection().receive(&message);
+        received = true;
+        if let Some(reply) = step.reply {
+            link.send(&reply);
+        }
+        if let Some(event) = step.event {
+            sync_events.write(event);
+        }
+    }
+    if received {
+        view.set_changed();
+    }
+}
+
+/// 退出观战：移除本地状态并通知服务器离开
+pub fn finish_spectating(mut commands: Commands, mut link: ResMut<SpectatorLink>) {
+    commands.remove_resource::<SpectatorView>();
+    link.incoming.clear();
+    link.send(&ClientMessage::Leave);
+}
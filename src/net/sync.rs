@@ -0,0 +1,161 @@
+// 客户端同步 - 校验收到的每条命令和局面哈希，不一致时自动重新同步
+//
+// 对弈者和观战者都用它维护本地命令日志：收到的命令先在本地棋盘上校验再应用，
+// 应用后把本地局面哈希与服务器附带的哈希比对并回报给服务器。
+// 命令不合法或哈希不一致时请求重新同步，等待期间忽略后续命令（它们会包含在重新同步的历史中）；
+// 收到重新同步消息后重放历史，并用局面字符串核对重放结果。
+// 握手时收到的对局规则也保存在这里，由传输层写入ActiveMatchRules供辅助系统使用。
+// 每次失步和重新同步都产生SyncEvent，由界面提示玩家
+
+use super::{ClientMessage, PositionChecksum, ServerMessage};
+use crate::{
+    assist::MatchRules,
+    game::{position_string, BoardDiff, CommandError, GameLog},
+};
+use bevy::prelude::*;
+
+/// 本地日志与服务器不一致的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DesyncReason {
+    /// 收到的命令在本地局面上不合法
+    InvalidCommand(CommandError),
+    /// 应用命令后本地局面哈希与服务器不同
+    ChecksumMismatch { expected: u64, actual: u64 },
+    /// 握手时收到的历史无法在本地重放
+    InvalidHistory(CommandError),
+}
+
+/// 同步状态的变化，用于在界面中提示
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncEvent {
+    /// 在指定序号处发现失步，已请求重新同步
+    Desync { sequence: u32, reason: DesyncReason },
+    /// 重新同步完成，本地日志已追上服务器；diff为本地局面被纠正的格子
+    Resynced { sequence: u32, diff: BoardDiff },
+    /// 重新同步的数据无法重放或与局面字符串不符，需要重新连接
+    ResyncFailed { sequence: u32 },
+}
+
+/// 处理一条服务器消息的结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncStep {
+    /// 需要回复给服务器的消息
+    pub reply: Option<ClientMessage>,
+    /// 同步状态的变化
+    pub event: Option<SyncEvent>,
+}
+
+/// 客户端的本地命令日志和同步状态
+#[derive(Debug, Clone, Default)]
+pub struct ClientSync {
+    log: Option<GameLog>,
+    rules: Option<MatchRules>,
+    awaiting_resync: bool,
+}
+
+impl ClientSync {
+    /// 当前同步到的命令日志，握手完成前为None
+    pub fn log(&self) -> Option<&GameLog> {
+        self.log.as_ref()
+    }
+
+    /// 握手时收到的对局规则，握手完成前为None
+    pub fn rules(&self) -> Option<MatchRules> {
+        self.rules
+    }
+
+    /// 是否已经请求重新同步，还在等待服务器回复
+    pub fn is_awaiting_resync(&self) -> bool {
+        self.awaiting_resync
+    }
+
+    /// 处理一条服务器消息
+    pub fn receive(&mut self, message: &ServerMessage) -> SyncStep {
+        match message {
+            ServerMessage::Welcome {
+                initial,
+                first_player,
+                history,
+                rules,
+                ..
+            } => {
+                // 历史无法重放时规则同样生效
+                self.rules = Some(*rules);
+                match GameLog::replay(*initial, *first_player, history) {
+                    Ok(log) => {
+                        self.log = Some(log);
+                        self.awaiting_resync = false;
+                        SyncStep::default()
+                    }
+                    Err(err) => {
+                        self.desync(history.len() as u32, DesyncReason::InvalidHistory(err))
+                    }
+                }
+            }
+            ServerMessage::Command { command, checksum } => {
+                if self.awaiting_resync {
+                    return SyncStep::default();
+                }
+                let Some(log) = self.log.as_mut() else {
+                    return SyncStep::default();
+                };
+                if let Err(err) = log.apply(*command) {
+                    return self.desync(command.sequence, DesyncReason::InvalidCommand(err));
+                }
+
+                let local = PositionChecksum::of(log);
+                if local != *checksum {
+                    return self.desync(
+                        command.sequence,
+                        DesyncReason::ChecksumMismatch {
+                            expected: checksum.hash,
+                            actual: local.hash,
+                        },
+                    );
+                }
+                SyncStep {
+                    reply: Some(ClientMessage::Checksum(local)),
+                    event: None,
+                }
+            }
+            ServerMessage::Resync {
+                initial,
+                first_player,
+                history,
+                position,
+            } => {
+                let sequence = history.len() as u32;
+                let log = GameLog::replay(*initial, *first_player, history)
+                    .ok()
+                    .filter(|log| position_string(&log.board(), log.to_move()) == *position);
+                let event = match log {
+                    Some(log) => {
+                        let diff = self
+                            .log
+                            .as_ref()
+                            .map_or(log.initial(), GameLog::board)
+                            .diff(&log.board());
+                        self.log = Some(log);
+                        self.awaiting_resync = false;
+                        SyncEvent::Resynced { sequence, diff }
+                    }
+                    None => SyncEvent::ResyncFailed { sequence },
+                };
+                SyncStep {
+                    reply: None,
+                    event: Some(event),
+                }
+            }
+            _ => SyncStep::default(),
+        }
+    }
+
+    /// 发现失步：请求重新同步，等待期间不再应用命令
+    fn desync(&mut self, sequence: u32, reason: DesyncReason) -> SyncStep {
+        self.awaiting_resync = true;
+        SyncStep {
+            reply: Some(ClientMessage::RequestResync),
+            event: Some(SyncEvent::Desync { sequence, reason }),
+        }
+    }
+}
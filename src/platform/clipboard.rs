@@ -0,0 +1,56 @@
+// 剪贴板 - 复制和粘贴文本，用于复盘分析中的局面字符串
+//
+// 桌面版直接读写系统剪贴板。浏览器只允许异步读取剪贴板，Web版复制时写入剪贴板、
+// 粘贴时弹出浏览器的输入框，由玩家在其中粘贴。移动端没有实现，总是返回错误
+
+/// 把文本复制到剪贴板
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+pub fn copy_text(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|err| err.to_string())
+}
+
+/// 读取剪贴板中的文本，`prompt`只在Web版中使用
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+pub fn paste_text(_prompt: &str) -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|err| err.to_string())
+}
+
+/// 把文本复制到剪贴板（Web版），写入是异步的，不等待结果
+#[cfg(target_arch = "wasm32")]
+pub fn copy_text(text: &str) -> Result<(), String> {
+    let window = web_sys::window().ok_or_else(|| "window is unavailable".to_string())?;
+    let _ = window.navigator().clipboard().write_text(text);
+    Ok(())
+}
+
+/// 弹出浏览器的输入框，返回玩家粘贴的文本（Web版），取消时返回错误
+#[cfg(target_arch = "wasm32")]
+pub fn paste_text(prompt: &str) -> Result<String, String> {
+    web_sys::window()
+        .ok_or_else(|| "window is unavailable".to_string())?
+        .prompt_with_message(prompt)
+        .map_err(|err| format!("{err:?}"))?
+        .ok_or_else(|| "cancelled".to_string())
+}
+
+/// 移动端没有剪贴板支持
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(target_os = "android", target_os = "ios")
+))]
+pub fn copy_text(_text: &str) -> Result<(), String> {
+    Err("clipboard is unavailable".to_string())
+}
+
+/// 移动端没有剪贴板支持
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(target_os = "android", target_os = "ios")
+))]
+pub fn paste_text(_prompt: &str) -> Result<String, String> {
+    Err("clipboard is unavailable".to_string())
+}
@@ -0,0 +1,172 @@
+// 平台配置 - 启动时按运行平台选择资源档次
+//
+// 桌面平台加载完整品质的资源，网页和移动平台使用精简档次。
+// 精简资源与完整资源同名，放在同一目录下的compact子目录中（例如fonts/compact/），
+// 只有列在精简资源清单中的文件才会按精简路径加载，其余资源逐个回退到完整资源；
+// 打包了自己的精简资源的宿主应用可以在添加ReversiPlugin之前插入带有自己清单的PlatformProfile
+
+pub mod clipboard;
+
+use crate::{
+    audio::{AudioAssets, SoundType},
+    boot::BootAssets,
+    fonts::{FontAssets, CHINESE_FONT_PATH},
+};
+use bevy::{asset::LoadState, prelude::*};
+
+/// 运行平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Desktop,
+    Web,
+    Mobile,
+}
+
+impl Platform {
+    /// 编译目标对应的平台
+    pub fn current() -> Self {
+        if cfg!(any(target_arch = "wasm32", target_family = "wasm")) {
+            Self::Web
+        } else if cfg!(any(target_os = "android", target_os = "ios")) {
+            Self::Mobile
+        } else {
+            Self::Desktop
+        }
+    }
+}
+
+/// 资源档次
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetTier {
+    /// 完整品质的字体和音效
+    Full,
+    /// 压缩后的小字体和低码率音效
+    Compact,
+}
+
+impl AssetTier {
+    /// 资源在本档次中的路径
+    pub fn path(self, path: &str) -> String {
+        match self {
+            Self::Full => path.to_string(),
+            Self::Compact => match path.rsplit_once('/') {
+                Some((directory, file)) => format!("{directory}/compact/{file}"),
+                None => format!("compact/{path}"),
+            },
+        }
+    }
+}
+
+/// assets/中提供了精简版本的资源（完整品质路径），目前还没有
+pub const SHIPPED_COMPACT_ASSETS: &[&str] = &[];
+
+/// 平台配置资源，启动时决定加载哪一档资源
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlatformProfile {
+    pub platform: Platform,
+    pub asset_tier: AssetTier,
+    /// 有精简版本的资源（完整品质路径），不在清单中的资源总是加载完整品质
+    pub compact_assets: &'static [&'static str],
+}
+
+impl PlatformProfile {
+    /// 平台默认的配置，网页和移动平台使用精简档次
+    pub fn new(platform: Platform) -> Self {
+        let asset_tier = match platform {
+            Platform::Desktop => AssetTier::Full,
+            Platform::Web | Platform::Mobile => AssetTier::Compact,
+        };
+        Self {
+            platform,
+            asset_tier,
+            compact_assets: SHIPPED_COMPACT_ASSETS,
+        }
+    }
+
+    /// 使用指定资源档次的配置
+    pub fn with_asset_tier(self, asset_tier: AssetTier) -> Self {
+        Self { asset_tier, ..self }
+    }
+
+    /// 使用指定精简资源清单的配置，供打包了自己的精简资源的宿主应用使用
+    pub fn with_compact_assets(self, compact_assets: &'static [&'static str]) -> Self {
+        Self {
+            compact_assets,
+            ..self
+        }
+    }
+
+    /// 资源按当前档次的路径，没有精简版本的资源使用完整品质路径
+    pub fn asset_path(&self, path: &str) -> String {
+        if self.compact_assets.contains(&path) {
+            self.asset_tier.path(path)
+        } else {
+            AssetTier::Full.path(path)
+        }
+    }
+}
+
+impl Default for PlatformProfile {
+    fn default() -> Self {
+        Self::new(Platform::current())
+    }
+}
+
+/// 清单中的精简资源加载失败时改为加载完整资源，并替换加载画面中跟踪的句柄
+///
+/// 需要在检查加载画面是否完成之前运行，避免中文字体回退前就离开加载画面
+pub fn fall_back_to_full_quality_assets(
+    asset_server: Res<AssetServer>,
+    profile: Res<PlatformProfile>,
+    mut font_assets: ResMut<FontAssets>,
+    audio_assets: Option<ResMut<AudioAssets>>,
+    mut boot_assets: ResMut<BootAssets>,
+) {
+    if profile.asset_tier == AssetTier::Full {
+        return;
+    }
+
+    if let Some(full) = full_quality_fallback(
+        &font_assets.chinese_font,
+        CHINESE_FONT_PATH,
+        &asset_server,
+        &mut boot_assets,
+    ) {
+        font_assets.chinese_font = full;
+    }
+
+    let Some(mut audio_assets) = audio_assets else {
+        return;
+    };
+    for sound_type in SoundType::ALL {
+        if let Some(full) = full_quality_fallback(
+            audio_assets.handle(sound_type),
+            sound_type.asset_path(),
+            &asset_server,
+            &mut boot_assets,
+        ) {
+            *audio_assets.handle_mut(sound_type) = full;
+        }
+    }
+}
+
+/// 句柄加载失败且不是完整资源时，开始加载完整资源并返回新的句柄
+fn full_quality_fallback<A: Asset>(
+    handle: &Handle<A>,
+    full_path: &'static str,
+    asset_server: &AssetServer,
+    boot_assets: &mut BootAssets,
+) -> Option<Handle<A>> {
+    if !matches!(asset_server.load_state(handle.id()), LoadState::Failed(_)) {
+        return None;
+    }
+    let path = handle.path()?;
+    if path.path() == std::path::Path::new(full_path) {
+        return None;
+    }
+
+    warn!("Failed to load {path}, falling back to {full_path}");
+    let full = asset_server.load(full_path);
+    boot_assets.replace(handle.id().untyped(), full.clone().untyped());
+    Some(full)
+}
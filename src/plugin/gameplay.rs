@@ -0,0 +1,771 @@
+// 对局流程 - 输入、走棋命令、AI回合、终局判定和重新开始
+//
+// 棋盘只通过MoveCommand事件改变：玩家和AI的落子都先转换为命令，再由apply_move_commands按序应用
+
+use super::{
+    AiMoveEvent, AiSearchEvent, BackToDifficultyEvent, GameSession, NewGameRequest,
+    PlayerMoveEvent, SelectedDifficulty,
+};
+use crate::{
+    accessibility::AnnouncementEvent,
+    ai::{endgame::GameOutcome, AiPlayer, AiScheduler, SearchPriority},
+    assist::{corner_conceded, ActiveMatchRules, CornerWarningState, PendingCornerWarning},
+    audio::{MoveOutcome, PlaySoundEvent, SoundType},
+    clock::GameClock,
+    coach::CoachState,
+    config_assets::ActiveEvalConfig,
+    game::{Board, GameLog, LegalMovesCache, MoveAction, MoveCommand, PlacementRule, PlayerColor},
+    history::{HistoryPreview, SWIPE_MIN_DISTANCE},
+    input::{
+        BoardCursor, GamepadAction, GamepadActionEvent, InputAction, InputGate, InputMap,
+        InputSource, PlayerSeats, Premove, TurnState,
+    },
+    localization::{TextArg, TextKey},
+    practice::{OpeningAutoplay, SelectedOpening},
+    preferences::Preferences,
+    sandbox::SandboxMode,
+    series::MatchSeries,
+    state::{GameState, GameTransition, GameTransitions},
+    stats::{GameFinishedEvent, PlayerStats},
+    ui::{
+        board_square_at, BackToDifficultyButton, BoardUI, CurrentPlayer, FlipChainEvent, GameUI,
+        RestartGameEvent, RulesPanel, ToDelete, ToastEvent, ToggleRulesEvent, UiFocus, UiState,
+    },
+    undo::{UndoPolicy, UndoState},
+};
+use bevy::prelude::*;
+
+/// 进入对局界面时是否开始新对局：有明确的新对局请求，或者还没有对局
+pub fn starts_new_game(
+    new_game: Res<NewGameRequest>,
+    sessions: Query<(), With<GameSession>>,
+) -> bool {
+    new_game.0 || sessions.is_empty()
+}
+
+/// 进入对局界面时准备对局
+///
+/// 只有收到新对局请求或者还没有对局时才创建棋盘、命令日志和AI（替换掉旧对局），
+/// 否则继续原来的对局，棋盘、悔棋次数和点评都保持不变，
+/// 并把棋盘标记为已变化，让新建的界面按当前局面刷新
+pub fn setup_game(
+    mut commands: Commands,
+    mut new_game: ResMut<NewGameRequest>,
+    mut sessions: Query<(Entity, &mut Board, &mut GameLog), With<GameSession>>,
+    selected_difficulty: Res<SelectedDifficulty>,
+    selected_opening: Res<SelectedOpening>,
+    sandbox: Res<SandboxMode>,
+    player_stats: Res<PlayerStats>,
+    preferences: Res<Preferences>,
+    series: Res<MatchSeries>,
+    mut corner_warning: ResMut<CornerWarningState>,
+    // 系统参数数量有上限，输入闸门、预走状态和座位合并为一个参数
+    (mut input_gate, mut premove, mut seats): (
+        ResMut<InputGate>,
+        ResMut<Premove>,
+        ResMut<PlayerSeats>,
+    ),
+    mut ui_state: ResMut<UiState>,
+    mut coach: ResMut<CoachState>,
+    mut undo: ResMut<UndoState>,
+    #[cfg(feature = "remote_engine")] remote_engine: Option<Res<crate::ai::remote::RemoteEngine>>,
+) {
+    // 丢弃未确认的走法（对话框已经随界面删除），并取消暂停
+    corner_warning.pending = None;
+    *input_gate = InputGate::default();
+    ui_state.paused = false;
+
+    if !std::mem::take(&mut new_game.0) && !sessions.is_empty() {
+        for (_, mut board, mut log) in sessions.iter_mut() {
+            board.set_changed();
+            log.set_changed();
+        }
+        return;
+    }
+
+    // 开始新对局：删除旧对局，丢弃上一局的点评和预走
+    for (entity, _, _) in sessions.iter() {
+        commands.entity(entity).despawn();
+    }
+    coach.comment = None;
+    premove.cancel();
+
+    // 按对局模式确定本局的悔棋规则，重新开始时次数也重置
+    *undo = UndoState::new(UndoPolicy::for_game(
+        sandbox.0,
+        selected_opening.0.is_some(),
+        selected_difficulty.0,
+    ));
+
+    // 沙盒中没有AI，玩家轮流替双方自由落子
+    if sandbox.0 {
+        seats.reset_for_game(None);
+        let board = Board::new();
+        let log = GameLog::new(board, PlayerColor::Black).with_rule(sandbox.placement_rule());
+        commands.spawn((board, log, GameSession));
+        return;
+    }
+
+    // 棋盘只通过命令日志改变，黑棋先行；开局库中的开局都从标准初始局面开始
+    let board = if selected_opening.0.is_some() {
+        Board::new_standard()
+    } else {
+        Board::new()
+    };
+    // 自定义的胜负条件在开局时写入命令日志，对局中修改设置只影响下一局
+    let log = GameLog::new(board, PlayerColor::Black).with_victory(preferences.rules.victory);
+    let session = commands.spawn((board, log, GameSession)).id();
+
+    // 使用用户选择的难度创建AI，自适应难度使用战绩中保存的当前强度；
+    // 多局比赛中每局交换颜色，玩家执白时AI执黑先行
+    let difficulty = player_stats.resolve_difficulty(selected_difficulty.0);
+    let ai_player = AiPlayer::new(difficulty, series.player_color.opposite());
+    seats.reset_for_game(Some(ai_player.color));
+    // 命令行指定了外部引擎时由外部引擎走棋
+    #[cfg(feature = "remote_engine")]
+    let ai_player = match remote_engine {
+        Some(engine) => ai_player.with_remote_engine(engine.clone()),
+        None => ai_player,
+    };
+    commands.spawn((ai_player, ChildOf(session)));
+}
+
+pub fn handle_input(
+    mut move_events: EventWriter<PlayerMoveEvent>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    touch_input: Res<Touches>,
+    mut gamepad_actions: EventReader<GamepadActionEvent>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    current_player: Res<CurrentPlayer>,
+    seats: Res<PlayerSeats>,
+    mut history_preview: ResMut<HistoryPreview>,
+    corner_warning: Res<CornerWarningState>,
+    board_cursor: Res<BoardCursor>,
+    ui_focus: Res<UiFocus>,
+    ui_state: Res<UiState>,
+    log_query: Query<&GameLog>,
+    // 系统参数数量有上限，输入闸门、预走状态和设置合并为一个参数
+    (mut input_gate, mut premove, preferences): (
+        ResMut<InputGate>,
+        ResMut<Premove>,
+        Res<Preferences>,
+    ),
+    time: Res<Time<Real>>,
+) {
+    // 难度现在在游戏开始前选择，不再支持游戏中切换
+
+    // 右键随时取消预走
+    if mouse_input.just_pressed(MouseButton::Right) {
+        premove.cancel();
+    }
+
+    // 手柄确认键在棋盘光标处落子（焦点在按钮上时由焦点系统处理）；
+    // 每帧都读取事件，避免暂停期间的按键在恢复后被当作落子
+    let gamepad_confirm = gamepad_actions
+        .read()
+        .any(|GamepadActionEvent(action)| *action == GamepadAction::Confirm)
+        && ui_focus.on_board();
+
+    // 暂停或让角提醒对话框打开时不接受棋盘输入
+    if ui_state.paused || corner_warning.pending.is_some() {
+        return;
+    }
+
+    // 检查是否有输入事件（鼠标点击、触摸或手柄），记录按下和抬起的位置
+    let input = if mouse_input.just_pressed(MouseButton::Left) {
+        // 鼠标输入 - 按下时立即落子
+        let Ok(window) = windows.single() else {
+            return;
+        };
+        window
+            .cursor_position()
+            .map(|position| BoardInput::Pointer(position, position))
+    } else if let Some(touch) = touch_input
+        .iter_just_released()
+        .find(|touch| touch.distance().length() < SWIPE_MIN_DISTANCE)
+    {
+        // 触摸输入 - 抬起时才确认落子，滑动手势留给历史浏览
+        Some(BoardInput::Pointer(
+            touch.start_position(),
+            touch.position(),
+        ))
+    } else if gamepad_confirm {
+        Some(BoardInput::Cursor(board_cursor.position))
+    } else {
+        // 没有输入事件
+        return;
+    };
+
+    // 正在浏览历史时，新的输入只回到实时局面
+    if history_preview.is_previewing() {
+        history_preview.snap_to_live();
+        return;
+    }
+
+    // 不是本机玩家的回合（AI、脚本或网络对手）时，点击只在开启预走时记下，否则忽略
+    let ai_turn = !seats.source(current_player.0).is_human();
+    if ai_turn && !preferences.input.premove {
+        return;
+    }
+
+    let position = match input {
+        Some(BoardInput::Pointer(press_position, release_position)) => {
+            let Ok((camera, camera_transform)) = camera_query.single() else {
+                return;
+            };
+            let press_square = board_square_at(camera, camera_transform, press_position);
+            let release_square = board_square_at(camera, camera_transform, release_position);
+
+            // 只有在按下的同一格抬起才落子，拖出该格后抬起即取消
+            press_square.filter(|_| press_square == release_square)
+        }
+        Some(BoardInput::Cursor(position)) => Some(position),
+        None => None,
+    };
+    let Some(position) = position else {
+        return;
+    };
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+
+    // 预走只能选空格，轮到玩家时再检查是否合法
+    if ai_turn {
+        if log.board().get_piece(position).is_none() {
+            premove.toggle(position);
+        }
+        return;
+    }
+
+    // 去抖，并且同一回合只接受一次合法落子
+    let legal = log
+        .validate(&log.next_command(MoveAction::Place(position)))
+        .is_ok();
+    if input_gate.admit(TurnState::of(log), time.elapsed(), legal) {
+        move_events.write(PlayerMoveEvent { position });
+    }
+}
+
+/// 棋盘输入的来源
+enum BoardInput {
+    /// 鼠标或触摸：按下和抬起时的屏幕位置
+    Pointer(Vec2, Vec2),
+    /// 手柄光标所在的格子
+    Cursor(u8),
+}
+
+/// 手柄方向键移动棋盘光标
+pub fn move_board_cursor(
+    mut gamepad_actions: EventReader<GamepadActionEvent>,
+    ui_focus: Res<UiFocus>,
+    mut board_cursor: ResMut<BoardCursor>,
+) {
+    for GamepadActionEvent(action) in gamepad_actions.read() {
+        if let GamepadAction::Move(direction) = *action {
+            if ui_focus.on_board() {
+                board_cursor.step(direction);
+            }
+        }
+    }
+}
+
+pub fn handle_player_move(
+    mut move_events: EventReader<PlayerMoveEvent>,
+    log_query: Query<&GameLog>,
+    mut move_commands: EventWriter<MoveCommand>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+    preferences: Res<Preferences>,
+    match_rules: Res<ActiveMatchRules>,
+    mut corner_warning: ResMut<CornerWarningState>,
+    autoplay: Res<OpeningAutoplay>,
+) {
+    // 自动走开局期间忽略玩家落子
+    if autoplay.is_playing() {
+        move_events.clear();
+        return;
+    }
+
+    for event in move_events.read() {
+        if let Ok(log) = log_query.single() {
+            let command = log.next_command(MoveAction::Place(event.position));
+            if log.validate(&command).is_ok() {
+                // 辅助模式下，让对手下一步占角的走法需要先确认（对局规则允许时）
+                let conceded = preferences
+                    .assist
+                    .permitted(match_rules.allowed())
+                    .warns_about_corners()
+                    .then(|| corner_conceded(&log.board(), event.position, command.player))
+                    .flatten();
+
+                if let Some(corner) = conceded {
+                    corner_warning.pending = Some(PendingCornerWarning {
+                        position: event.position,
+                        corner,
+                        opponent: command.player.opposite(),
+                    });
+                } else {
+                    move_commands.write(command);
+                }
+            } else {
+                // 播放无效落子音效
+                sound_events.write(PlaySoundEvent {
+                    sound_type: SoundType::InvalidMove,
+                    actor: None,
+                });
+            }
+        }
+    }
+}
+
+pub fn ai_system(
+    mut ai_query: Query<&mut AiPlayer>,
+    board_query: Query<&Board>,
+    current_player: Res<CurrentPlayer>,
+    mut ai_move_events: EventWriter<AiMoveEvent>,
+    mut ai_search_events: EventWriter<AiSearchEvent>,
+    active_eval: Res<ActiveEvalConfig>,
+    scheduler: Res<AiScheduler>,
+    preferences: Res<Preferences>,
+    seats: Res<PlayerSeats>,
+    time: Res<Time>,
+) {
+    if let Ok(mut ai_player) = ai_query.single_mut() {
+        // 座位改由脚本或网络对手走棋时AI不思考
+        if ai_player.color != current_player.0 || !seats.source(ai_player.color).is_ai() {
+            return;
+        }
+
+        // 如果AI正在异步思考，检查是否完成
+        if ai_player.is_thinking {
+            if let Some(result) = ai_player.check_thinking_result() {
+                if let Some(ai_move) = result {
+                    ai_move_events.write(AiMoveEvent { ai_move });
+                    if let Some(summary) = ai_player.last_search() {
+                        ai_search_events.write(AiSearchEvent { summary });
+                    }
+                }
+                // 重置计时器准备下次思考
+                ai_player.thinking_timer.reset();
+            }
+            return;
+        }
+
+        // 开局库中的走法不需要思考，直接走出
+        if let Some(ai_move) = board_query
+            .single()
+            .ok()
+            .and_then(|board| ai_player.instant_move(board))
+        {
+            ai_move_events.write(AiMoveEvent { ai_move });
+            ai_player.thinking_timer.reset();
+            return;
+        }
+
+        ai_player.thinking_timer.tick(time.delta());
+
+        // 计时器完成且没有在思考时，开始AI计算；走棋优先于其他后台搜索
+        if ai_player.thinking_timer.finished() {
+            // 置换表大小的设置在下一次计算时生效
+            let table_size_mb = preferences.search.table_size_mb;
+            if ai_player.table_size_mb() != table_size_mb {
+                ai_player.set_table_size_mb(table_size_mb);
+            }
+            if let Ok(board) = board_query.single() {
+                ai_player.start_scheduled_thinking(
+                    board,
+                    active_eval.0.clone(),
+                    &scheduler,
+                    SearchPriority::Move,
+                );
+            }
+        }
+    }
+}
+
+/// 脚本座位落子：轮到脚本座位时按走法列表写入下一条走棋命令
+///
+/// 不合法的走法不写入命令，对局停在这一步，方便发现脚本与局面不一致
+pub fn play_scripted_moves(
+    mut seats: ResMut<PlayerSeats>,
+    log_query: Query<&GameLog>,
+    mut move_commands: EventWriter<MoveCommand>,
+) {
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+    if log.is_game_over() {
+        return;
+    }
+    let InputSource::Script(script) = seats.source_mut(log.to_move()) else {
+        return;
+    };
+    let Some(command) = script.next_command(log) else {
+        return;
+    };
+    match log.validate(&command) {
+        Ok(()) => {
+            move_commands.write(command);
+        }
+        Err(err) => warn!("Scripted move {command:?} is not playable: {err}"),
+    }
+}
+
+pub fn handle_ai_move(
+    mut ai_move_events: EventReader<AiMoveEvent>,
+    log_query: Query<&GameLog>,
+    mut move_commands: EventWriter<MoveCommand>,
+) {
+    for event in ai_move_events.read() {
+        if let Ok(log) = log_query.single() {
+            move_commands.write(log.next_command(MoveAction::Place(event.ai_move.position)));
+        }
+    }
+}
+
+/// 按序应用走棋命令 - 棋盘状态唯一的修改入口
+pub fn apply_move_commands(
+    mut move_commands: EventReader<MoveCommand>,
+    mut board_query: Query<(&mut Board, &mut GameLog)>,
+    mut current_player: ResMut<CurrentPlayer>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+    mut announcement_events: EventWriter<AnnouncementEvent>,
+    mut flip_events: EventWriter<FlipChainEvent>,
+    mut outcome_events: EventWriter<MoveOutcome>,
+    mut toast_events: EventWriter<ToastEvent>,
+    seats: Res<PlayerSeats>,
+) {
+    let Ok((mut board, mut log)) = board_query.single_mut() else {
+        return;
+    };
+
+    for command in move_commands.read() {
+        let board_before = log.board();
+        if let Err(err) = log.apply(*command) {
+            // 序号过期或不合法的命令直接丢弃
+            warn!("Rejected move command {command:?}: {err}");
+            continue;
+        }
+
+        if command.action == MoveAction::Pass {
+            announce_pass(
+                command.player,
+                &mut sound_events,
+                &mut announcement_events,
+                &mut toast_events,
+            );
+        } else if let MoveAction::Place(position) = command.action {
+            // 播报落子
+            announcement_events.write(AnnouncementEvent::Move {
+                color: command.player,
+                position,
+                flipped: board_before.diff(&log.board()).flipped.count_ones(),
+            });
+
+            // 播放落子音效
+            sound_events.write(PlaySoundEvent {
+                sound_type: SoundType::PiecePlace,
+                actor: Some(command.player),
+            });
+
+            // 播放翻转音效
+            sound_events.write(PlaySoundEvent {
+                sound_type: SoundType::PieceFlip,
+                actor: Some(command.player),
+            });
+
+            // 翻转连锁动画
+            flip_events.write(FlipChainEvent {
+                position,
+                player: command.player,
+                rays: board_before.flipped_rays(position, command.player),
+            });
+
+            // 交给局面事件分类播放占角等提示音
+            outcome_events.write(MoveOutcome {
+                player: command.player,
+                position,
+                before: board_before,
+                after: log.board(),
+            });
+        }
+
+        // AI或脚本无棋可走但对局未结束时自动跳过，玩家需要点击跳过按钮确认
+        let next_player = log.to_move();
+        if seats.source(next_player).passes_automatically() && log.must_pass() {
+            let pass = log.next_command(MoveAction::Pass);
+            if log.apply(pass).is_ok() {
+                announce_pass(
+                    next_player,
+                    &mut sound_events,
+                    &mut announcement_events,
+                    &mut toast_events,
+                );
+            }
+        }
+    }
+
+    // 同步棋盘和当前玩家，只在变化时写入以免触发无谓的变更检测
+    if log.board() != *board {
+        *board = log.board();
+    }
+    if current_player.0 != log.to_move() {
+        current_player.0 = log.to_move();
+    }
+}
+
+/// 跳过回合的播报、提示和音效
+fn announce_pass(
+    color: PlayerColor,
+    sound_events: &mut EventWriter<PlaySoundEvent>,
+    announcement_events: &mut EventWriter<AnnouncementEvent>,
+    toast_events: &mut EventWriter<ToastEvent>,
+) {
+    announcement_events.write(AnnouncementEvent::Pass { color });
+    let name = match color {
+        PlayerColor::Black => TextKey::BlackName,
+        PlayerColor::White => TextKey::WhiteName,
+    };
+    toast_events
+        .write(ToastEvent::info(TextKey::ToastPassFormat).with_args(vec![TextArg::Key(name)]));
+    sound_events.write(PlaySoundEvent {
+        sound_type: SoundType::Pass,
+        actor: None,
+    });
+}
+
+pub fn check_game_over(
+    board_query: Query<(&Board, &GameLog)>,
+    legal_moves: Res<LegalMovesCache>,
+    mut transitions: GameTransitions,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+    mut announcement_events: EventWriter<AnnouncementEvent>,
+    mut finished_events: EventWriter<GameFinishedEvent>,
+    ai_query: Query<&AiPlayer>,
+    clock: Res<GameClock>,
+) {
+    // 只在Playing状态下检查游戏结束
+    if transitions.current() != GameState::Playing {
+        return;
+    }
+
+    if let Ok((board, log)) = board_query.single() {
+        // 自由落子的沙盒中棋盘下满才结束，自定义规则下达到目标子数时提前结束，
+        // 计时对局中一方时间用完时立即结束
+        let game_over = clock.flagged().is_some()
+            || match log.rule() {
+                PlacementRule::Standard => {
+                    legal_moves.is_game_over(board) || log.victory().ends_early(board)
+                }
+                PlacementRule::Free => log.is_game_over(),
+            };
+        if game_over {
+            // 超时的一方判负，否则按胜负条件判断胜者，领先不足规定子数时算平局
+            let winner = match clock.flagged() {
+                Some(flagged) => Some(flagged.opposite()),
+                None => log.victory().winner(board),
+            };
+
+            // 播报对局结果
+            announcement_events.write(AnnouncementEvent::Result {
+                winner,
+                black: board.count_pieces(PlayerColor::Black),
+                white: board.count_pieces(PlayerColor::White),
+            });
+
+            // 记录玩家视角的对局结果，沙盒中没有AI，不计入战绩和历史对局
+            if let Ok(ai_player) = ai_query.single() {
+                let player_color = ai_player.color.opposite();
+                let score = board.count_pieces(player_color) as i32
+                    - board.count_pieces(ai_player.color) as i32;
+                let outcome = match winner {
+                    Some(color) if color == player_color => GameOutcome::Win,
+                    Some(_) => GameOutcome::Loss,
+                    None => GameOutcome::Draw,
+                };
+                finished_events.write(GameFinishedEvent {
+                    outcome,
+                    difficulty: ai_player.difficulty,
+                    margin: score,
+                });
+            }
+
+            // 播放游戏结束音效
+            if let Some(winner) = winner {
+                // 如果有AI玩家，判断是玩家胜利还是AI胜利
+                if let Ok(ai_player) = ai_query.single() {
+                    if winner == ai_player.color {
+                        // AI胜利，玩家失败
+                        sound_events.write(PlaySoundEvent {
+                            sound_type: SoundType::Defeat,
+                            actor: None,
+                        });
+                    } else {
+                        // 玩家胜利
+                        sound_events.write(PlaySoundEvent {
+                            sound_type: SoundType::Victory,
+                            actor: None,
+                        });
+                    }
+                } else {
+                    // 没有AI，根据黑棋结果判断（玩家是黑棋）
+                    if winner == PlayerColor::Black {
+                        sound_events.write(PlaySoundEvent {
+                            sound_type: SoundType::Victory,
+                            actor: None,
+                        });
+                    } else {
+                        sound_events.write(PlaySoundEvent {
+                            sound_type: SoundType::Defeat,
+                            actor: None,
+                        });
+                    }
+                }
+            } else {
+                // 平局，播放胜利音效（因为没有输）
+                sound_events.write(PlaySoundEvent {
+                    sound_type: SoundType::Victory,
+                    actor: None,
+                });
+            }
+
+            transitions.request(GameTransition::EndGame);
+        }
+    }
+}
+
+pub fn handle_game_over_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    touch_input: Res<Touches>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut restart_events: EventWriter<RestartGameEvent>,
+) {
+    // 键盘输入（桌面端）
+    let keyboard_restart = input_map.just_pressed(InputAction::Restart, &keyboard_input);
+
+    // 触摸输入（移动端）
+    let touch_restart = touch_input.any_just_pressed();
+
+    // 鼠标输入（桌面端备用）
+    let mouse_restart = mouse_input.just_pressed(MouseButton::Left);
+
+    if keyboard_restart || touch_restart || mouse_restart {
+        restart_events.write(RestartGameEvent);
+    }
+}
+
+pub fn restart_game(
+    mut restart_events: EventReader<RestartGameEvent>,
+    mut transitions: GameTransitions,
+) {
+    for _event in restart_events.read() {
+        // 通过状态切换来重新创建UI，清理由切换本身完成（见begin_restart）
+        // 切换到Restarting状态，然后会自动切换回Playing
+        transitions.request(GameTransition::Restart);
+    }
+}
+
+/// 进入Restarting状态：删除对局界面并请求新对局
+///
+/// 只标记界面根实体和棋盘根实体，棋子等作为子实体一起删除；
+/// 旧对局在回到对局界面时被新对局替换
+pub fn begin_restart(
+    mut commands: Commands,
+    mut current_player: ResMut<CurrentPlayer>,
+    mut new_game: ResMut<NewGameRequest>,
+    screen_roots: Query<Entity, Or<(With<GameUI>, With<BoardUI>)>>,
+) {
+    for entity in screen_roots.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+    new_game.0 = true;
+    current_player.0 = PlayerColor::Black;
+}
+
+#[derive(Resource)]
+pub struct RestartTimer {
+    timer: Timer,
+}
+
+impl Default for RestartTimer {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(0.1, TimerMode::Once), // 100ms延迟
+        }
+    }
+}
+
+pub fn setup_restart_timer(mut restart_timer: ResMut<RestartTimer>) {
+    restart_timer.timer.reset();
+}
+
+pub fn handle_restart_state(
+    mut restart_timer: ResMut<RestartTimer>,
+    mut transitions: GameTransitions,
+    time: Res<Time>,
+) {
+    restart_timer.timer.tick(time.delta());
+
+    if restart_timer.timer.finished() {
+        transitions.request(GameTransition::FinishRestart);
+    }
+}
+
+pub fn handle_rules_toggle(
+    mut rules_events: EventReader<ToggleRulesEvent>,
+    mut ui_state: ResMut<UiState>,
+) {
+    for _event in rules_events.read() {
+        ui_state.show_rules = !ui_state.show_rules;
+    }
+}
+
+// 处理返回难度选择按钮点击
+pub fn handle_back_to_difficulty_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<BackToDifficultyButton>)>,
+    mut back_events: EventWriter<BackToDifficultyEvent>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            back_events.write(BackToDifficultyEvent);
+        }
+    }
+}
+
+// 处理返回难度选择事件
+pub fn handle_back_to_difficulty_event(
+    mut back_events: EventReader<BackToDifficultyEvent>,
+    mut transitions: GameTransitions,
+) {
+    for _event in back_events.read() {
+        // 切换到难度选择状态，清理由切换本身完成（见leave_game_screen）
+        transitions.request(GameTransition::BackToMenu);
+    }
+}
+
+/// 从对局或结果界面返回菜单时删除整个对局界面和对局
+///
+/// 在BackToMenu切换中运行，无论由哪个系统发出请求都会清理
+pub fn leave_game_screen(
+    mut commands: Commands,
+    screen_roots: Query<Entity, Or<(With<GameUI>, With<BoardUI>, With<GameSession>)>>,
+    rules_panel_entities: Query<Entity, With<RulesPanel>>,
+    mut current_player: ResMut<CurrentPlayer>,
+    mut ui_state: ResMut<UiState>,
+) {
+    // 规则面板是独立的弹出层，单独删除
+    for entity in rules_panel_entities.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+
+    // 界面、棋盘和对局都只删除根实体，子实体随之递归删除
+    for entity in screen_roots.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+
+    // 重置游戏状态
+    current_player.0 = PlayerColor::Black;
+    ui_state.show_rules = false; // 重置规则面板状态
+    ui_state.paused = false;
+}
@@ -0,0 +1,742 @@
+// 菜单界面 - 加载画面、语言选择和难度选择（含计时规则和历史对局列表入口）
+
+use super::{NewGameRequest, SelectedDifficulty};
+use crate::{
+    accessibility::MotionSettings,
+    ai::{adaptive::AdaptiveLevel, AiDifficulty},
+    analysis::{AnalysisSession, GameTree},
+    boot::{spawn_boot_progress, BootAssets},
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    localization::{ChangeLanguageEvent, Language, LanguageSettings, TextArgs, TextKey},
+    practice::SelectedOpening,
+    preferences::Preferences,
+    record::GameArchive,
+    sandbox::SandboxMode,
+    state::{GameTransition, GameTransitions},
+    stats::PlayerStats,
+    ui::{
+        self, spawn_button, spawn_label, ArchiveEntryButton, ButtonColors, ButtonStyle, ToDelete,
+        UiState,
+    },
+};
+use bevy::prelude::*;
+use rand::random;
+
+// UI组件定义
+#[derive(Component)]
+pub struct LoadingScreenUI;
+
+#[derive(Component)]
+pub struct LoadingText {
+    timer: Timer,
+}
+
+#[derive(Component)]
+pub struct FadeIn {
+    timer: Timer,
+    duration: f32,
+}
+
+impl Default for LoadingText {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+        }
+    }
+}
+
+impl FadeIn {
+    fn new(duration: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+            duration,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct LanguageSelectionUI;
+
+#[derive(Component)]
+pub struct LanguageButton {
+    language: Language,
+}
+
+#[derive(Component)]
+pub struct DifficultySelectionUI;
+
+#[derive(Component)]
+pub struct DifficultyButton {
+    difficulty: AiDifficulty,
+}
+
+/// 切换开局练习的按钮
+#[derive(Component)]
+pub struct OpeningPracticeButton;
+
+/// 开局练习按钮上的文字
+#[derive(Component)]
+pub struct OpeningPracticeText;
+
+/// 切换对局计时规则的按钮
+#[derive(Component)]
+pub struct TimeControlButton;
+
+/// 计时按钮上的文字
+#[derive(Component)]
+pub struct TimeControlText;
+
+/// 进入自由落子沙盒的按钮
+#[derive(Component)]
+pub struct SandboxButton;
+
+// Loading Screen 相关函数
+pub fn setup_loading_screen(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    language_settings: Res<LanguageSettings>,
+    boot_assets: Res<BootAssets>,
+) {
+    let font = get_font_for_language(&language_settings, &font_assets);
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            LoadingScreenUI,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                TextKey::LoadingText,
+                TextFont {
+                    font: font.clone(),
+                    font_size: 36.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                LocalizedText,
+                LoadingText::default(),
+            ));
+
+            // 进度和资源列表使用内置字体，不需要等待中文字体即可显示
+            spawn_boot_progress(parent, &boot_assets, &font_assets.default_font);
+        });
+}
+
+pub fn check_loading_complete(
+    asset_server: Res<AssetServer>,
+    boot_assets: Res<BootAssets>,
+    mut transitions: GameTransitions,
+    loading_ui_query: Query<Entity, With<LoadingScreenUI>>,
+    mut commands: Commands,
+) {
+    // 必需的资源（中文字体）结束加载后即进入菜单，音效继续在后台加载
+    if boot_assets.required_ready(&asset_server) {
+        // 清理Loading UI
+        for entity in loading_ui_query.iter() {
+            commands.entity(entity).insert(ToDelete);
+        }
+        // 切换到语言选择
+        transitions.request(GameTransition::FinishLoading);
+    }
+}
+
+// 语言选择状态
+pub fn setup_language_selection(
+    commands: Commands,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    setup_language_selection_ui(commands, language_settings, font_assets);
+}
+
+pub fn setup_language_selection_ui(
+    mut commands: Commands,
+    _language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    // 总是使用中文字体以确保"中文"按钮能正确显示
+    let font = font_assets.chinese_font.clone();
+
+    // 语言选择界面
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
+            LanguageSelectionUI,
+            FadeIn::new(0.5),
+        ))
+        .with_children(|parent| {
+            // 标题
+            parent.spawn((
+                Text::default(),
+                TextKey::LanguageSelectionTitle,
+                TextFont {
+                    font: font.clone(),
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(50.0)),
+                    ..default()
+                },
+                LocalizedText,
+            ));
+
+            // 按钮容器
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                })
+                .with_children(|buttons| {
+                    // English 按钮
+                    let english_style = ButtonStyle {
+                        height: Val::Px(60.0),
+                        color: Color::srgb(0.2, 0.2, 0.8),
+                        radius: 10.0,
+                        font_size: 24.0,
+                        ..default()
+                    };
+                    spawn_button(
+                        buttons,
+                        &english_style,
+                        &font,
+                        TextKey::LanguageEnglish,
+                        LanguageButton {
+                            language: Language::English,
+                        },
+                    );
+
+                    // 中文 按钮
+                    let chinese_style = ButtonStyle {
+                        color: Color::srgb(0.8, 0.2, 0.2),
+                        ..english_style
+                    };
+                    spawn_button(
+                        buttons,
+                        &chinese_style,
+                        &font,
+                        TextKey::LanguageChinese,
+                        LanguageButton {
+                            language: Language::Chinese,
+                        },
+                    );
+                });
+        });
+}
+
+pub fn handle_language_selection(
+    interaction_query: Query<
+        (&Interaction, &LanguageButton),
+        (Changed<Interaction>, With<LanguageButton>),
+    >,
+    mut language_events: EventWriter<ChangeLanguageEvent>,
+    mut language_settings: ResMut<LanguageSettings>,
+    mut transitions: GameTransitions,
+    mut commands: Commands,
+    ui_query: Query<Entity, With<LanguageSelectionUI>>,
+) {
+    for (interaction, language_button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            // 设置语言
+            language_settings.set_language(language_button.language);
+
+            // 发送语言切换事件
+            language_events.write(ChangeLanguageEvent {
+                language: language_button.language,
+            });
+
+            // 标记语言选择UI为删除
+            for entity in ui_query.iter() {
+                commands.entity(entity).insert(ToDelete);
+            }
+
+            // 切换到难度选择状态
+            transitions.request(GameTransition::ChooseLanguage);
+        }
+    }
+}
+
+pub fn handle_language_change(
+    mut language_events: EventReader<ChangeLanguageEvent>,
+    mut language_settings: ResMut<LanguageSettings>,
+) {
+    for event in language_events.read() {
+        language_settings.set_language(event.language);
+    }
+}
+
+// Difficulty Selection 相关函数
+pub fn setup_difficulty_selection(
+    mut commands: Commands,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    selected_opening: Res<SelectedOpening>,
+    stats: Res<PlayerStats>,
+    preferences: Res<Preferences>,
+) {
+    let font = get_font_for_language(&language_settings, &font_assets);
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
+            DifficultySelectionUI,
+            FadeIn::new(0.5),
+        ))
+        .with_children(|parent| {
+            // 标题
+            parent.spawn((
+                Text::default(),
+                TextKey::SelectDifficulty,
+                TextFont {
+                    font: font.clone(),
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                },
+                LocalizedText,
+            ));
+
+            // 难度按钮容器
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(10.0),
+                    ..default()
+                })
+                .with_children(|buttons| {
+                    // 创建随机走棋的混乱难度、四个固定难度按钮和自适应难度按钮，启用nn-eval特性时增加专家+难度
+                    let mut difficulties = vec![
+                        (
+                            AiDifficulty::Chaos(random()),
+                            TextKey::DifficultyChaos,
+                            Color::srgb(0.75, 0.35, 0.6),
+                        ),
+                        (
+                            AiDifficulty::Beginner,
+                            TextKey::DifficultyEasy,
+                            Color::srgb(0.2, 0.7, 0.2),
+                        ),
+                        (
+                            AiDifficulty::Intermediate,
+                            TextKey::DifficultyMedium,
+                            Color::srgb(0.2, 0.2, 0.7),
+                        ),
+                        (
+                            AiDifficulty::Advanced,
+                            TextKey::DifficultyHard,
+                            Color::srgb(0.7, 0.5, 0.2),
+                        ),
+                        (
+                            AiDifficulty::Expert,
+                            TextKey::DifficultyExpert,
+                            Color::srgb(0.7, 0.2, 0.2),
+                        ),
+                        (
+                            AiDifficulty::Adaptive(AdaptiveLevel::default()),
+                            TextKey::DifficultyAdaptive,
+                            Color::srgb(0.5, 0.2, 0.7),
+                        ),
+                    ];
+                    if cfg!(feature = "nn-eval") {
+                        difficulties.insert(
+                            5,
+                            (
+                                AiDifficulty::ExpertPlus,
+                                TextKey::DifficultyExpertPlus,
+                                Color::srgb(0.55, 0.1, 0.15),
+                            ),
+                        );
+                    }
+
+                    let recommended = stats.recommended_difficulty();
+                    for (difficulty, text_key, color) in difficulties {
+                        let record = stats.record_for(difficulty);
+                        // 推荐的难度加白色边框
+                        let style = ButtonStyle {
+                            width: Val::Px(280.0),
+                            height: Val::Auto,
+                            padding: UiRect::vertical(Val::Px(4.0)),
+                            color,
+                            border_width: if difficulty == recommended { 2.0 } else { 0.0 },
+                            border_color: Color::WHITE,
+                            radius: 10.0,
+                            font_size: 22.0,
+                            ..default()
+                        };
+                        let mut button =
+                            buttons.spawn((style.bundle(), DifficultyButton { difficulty }));
+                        // 名称、说明和战绩纵向排列
+                        button.entry::<Node>().and_modify(|mut node| {
+                            node.flex_direction = FlexDirection::Column;
+                            node.min_height = Val::Px(56.0);
+                        });
+                        button.with_children(|button| {
+                            spawn_label(button, &style.label_style(), &font, text_key, ());
+
+                            // 搜索深度、每步时限和估计的等级分
+                            button.spawn((
+                                Text::default(),
+                                stats.difficulty_subtitle_key(difficulty),
+                                stats.difficulty_subtitle_args(difficulty),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 12.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgba(1.0, 1.0, 1.0, 0.85)),
+                                LocalizedText,
+                            ));
+
+                            // 根据战绩推荐的难度在右上角显示标签
+                            if difficulty == recommended {
+                                button.spawn((
+                                    Text::default(),
+                                    TextKey::DifficultyRecommended,
+                                    TextFont {
+                                        font: font.clone(),
+                                        font_size: 10.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::BLACK),
+                                    Node {
+                                        position_type: PositionType::Absolute,
+                                        top: Val::Px(-8.0),
+                                        right: Val::Px(-8.0),
+                                        padding: UiRect::axes(Val::Px(6.0), Val::Px(1.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(Color::srgb(1.0, 0.84, 0.2)),
+                                    BorderRadius::all(Val::Px(6.0)),
+                                    LocalizedText,
+                                ));
+                            }
+
+                            // 赢过该难度后显示连胜和最大胜差
+                            if let Some(badge_args) = record.badge_args() {
+                                button.spawn((
+                                    Text::default(),
+                                    TextKey::DifficultyBadgeFormat,
+                                    badge_args,
+                                    TextFont {
+                                        font: font.clone(),
+                                        font_size: 11.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::srgba(1.0, 1.0, 1.0, 0.8)),
+                                    LocalizedText,
+                                ));
+                            }
+                        });
+                    }
+
+                    // 计时按钮，点击依次切换计时规则，开始新对局时生效
+                    let option_style = ButtonStyle {
+                        width: Val::Px(280.0),
+                        radius: 10.0,
+                        font_size: 16.0,
+                        ..default()
+                    };
+                    let time_control_style = ButtonStyle {
+                        color: Color::srgba(0.2, 0.35, 0.4, 0.8),
+                        ..option_style.clone()
+                    };
+                    buttons
+                        .spawn((time_control_style.bundle(), TimeControlButton))
+                        .with_children(|button| {
+                            spawn_label(
+                                button,
+                                &time_control_style.label_style(),
+                                &font,
+                                TextKey::TimeControlFormat,
+                                (preferences.rules.time_control.text_args(), TimeControlText),
+                            );
+                        });
+
+                    // 开局练习按钮，点击依次切换开局库中的开局
+                    let practice_style = ButtonStyle {
+                        color: Color::srgba(0.3, 0.3, 0.3, 0.8),
+                        ..option_style.clone()
+                    };
+                    buttons
+                        .spawn((practice_style.bundle(), OpeningPracticeButton))
+                        .with_children(|button| {
+                            spawn_label(
+                                button,
+                                &practice_style.label_style(),
+                                &font,
+                                TextKey::OpeningPracticeFormat,
+                                (selected_opening.text_args(), OpeningPracticeText),
+                            );
+                        });
+
+                    // 沙盒按钮，直接开始没有AI的自由落子对局
+                    let sandbox_style = ButtonStyle {
+                        color: Color::srgba(0.45, 0.35, 0.15, 0.8),
+                        ..option_style
+                    };
+                    spawn_button(
+                        buttons,
+                        &sandbox_style,
+                        &font,
+                        TextKey::SandboxButton,
+                        SandboxButton,
+                    );
+                });
+
+            // 帮助、设置、历史对局和使用统计按钮，窄屏时换行
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(10.0),
+                    row_gap: Val::Px(10.0),
+                    max_width: Val::Px(360.0),
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                })
+                .with_children(|row| {
+                    let style = ButtonStyle {
+                        width: Val::Px(110.0),
+                        color: Color::srgba(0.3, 0.3, 0.3, 0.8),
+                        font_size: 16.0,
+                        ..default()
+                    };
+                    spawn_button(row, &style, &font, TextKey::RulesTitle, ui::RulesButton);
+                    spawn_button(
+                        row,
+                        &style,
+                        &font,
+                        TextKey::SettingsTitle,
+                        ui::SettingsButton,
+                    );
+                    spawn_button(row, &style, &font, TextKey::ArchiveTitle, ui::ArchiveButton);
+                    spawn_button(
+                        row,
+                        &style,
+                        &font,
+                        TextKey::InsightsTitle,
+                        ui::InsightsButton,
+                    );
+                });
+        });
+}
+
+pub fn handle_difficulty_selection(
+    interaction_query: Query<
+        (&Interaction, &DifficultyButton),
+        (Changed<Interaction>, With<DifficultyButton>),
+    >,
+    mut selected_difficulty: ResMut<SelectedDifficulty>,
+    mut sandbox: ResMut<SandboxMode>,
+    mut new_game: ResMut<NewGameRequest>,
+    mut transitions: GameTransitions,
+    mut commands: Commands,
+    ui_query: Query<Entity, With<DifficultySelectionUI>>,
+) {
+    for (interaction, difficulty_button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            // 设置选中的难度，离开沙盒
+            selected_difficulty.0 = difficulty_button.difficulty;
+            sandbox.0 = false;
+
+            // 清理难度选择UI
+            for entity in ui_query.iter() {
+                commands.entity(entity).insert(ToDelete);
+            }
+
+            // 切换到游戏状态，开始新对局
+            new_game.0 = true;
+            transitions.request(GameTransition::StartGame);
+        }
+    }
+}
+
+/// 点击开局练习按钮切换到开局库中的下一个开局
+pub fn handle_opening_practice_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<OpeningPracticeButton>)>,
+    mut selected_opening: ResMut<SelectedOpening>,
+    mut text_query: Query<&mut TextArgs, With<OpeningPracticeText>>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        selected_opening.cycle();
+        for mut args in text_query.iter_mut() {
+            *args = selected_opening.text_args();
+        }
+    }
+}
+
+/// 点击计时按钮切换到下一个计时规则
+pub fn handle_time_control_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<TimeControlButton>)>,
+    mut preferences: ResMut<Preferences>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            preferences.rules.time_control = preferences.rules.time_control.next();
+        }
+    }
+}
+
+/// 计时规则变化时（包括在设置面板中修改）更新计时按钮的文字
+pub fn update_time_control_text(
+    preferences: Res<Preferences>,
+    mut text_query: Query<&mut TextArgs, With<TimeControlText>>,
+) {
+    if !preferences.is_changed() {
+        return;
+    }
+    let args = preferences.rules.time_control.text_args();
+    for mut text_args in text_query.iter_mut() {
+        text_args.set_if_neq(args.clone());
+    }
+}
+
+/// 点击沙盒按钮开始自由落子对局
+pub fn handle_sandbox_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SandboxButton>)>,
+    mut sandbox: ResMut<SandboxMode>,
+    mut new_game: ResMut<NewGameRequest>,
+    mut transitions: GameTransitions,
+    mut commands: Commands,
+    ui_query: Query<Entity, With<DifficultySelectionUI>>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        sandbox.0 = true;
+        for entity in ui_query.iter() {
+            commands.entity(entity).insert(ToDelete);
+        }
+        new_game.0 = true;
+        transitions.request(GameTransition::StartGame);
+    }
+}
+
+/// 从历史对局列表选择一局：用存档回放出命令日志，在分析查看器中打开
+pub fn handle_archive_selection(
+    interaction_query: Query<(&Interaction, &ArchiveEntryButton), Changed<Interaction>>,
+    archive: Res<GameArchive>,
+    mut ui_state: ResMut<UiState>,
+    mut transitions: GameTransitions,
+    mut commands: Commands,
+    ui_query: Query<Entity, With<DifficultySelectionUI>>,
+) {
+    for (interaction, ArchiveEntryButton(index)) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(game) = archive.games.get(*index) else {
+            continue;
+        };
+        let log = match game.replay() {
+            Ok(log) => log,
+            Err(err) => {
+                warn!("Failed to replay archived game: {err}");
+                continue;
+            }
+        };
+
+        commands.insert_resource(AnalysisSession {
+            tree: GameTree::from_log(&log),
+        });
+        ui_state.show_archive = false;
+        for entity in ui_query.iter() {
+            commands.entity(entity).insert(ToDelete);
+        }
+        transitions.request(GameTransition::OpenReplay);
+        return;
+    }
+}
+
+// 通用按钮交互效果
+pub fn update_button_interactions(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor, &ButtonColors),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut background_color, button_colors) in button_query.iter_mut() {
+        *background_color = match *interaction {
+            Interaction::Pressed => button_colors.pressed.into(),
+            Interaction::Hovered => button_colors.hovered.into(),
+            Interaction::None => button_colors.normal.into(),
+        };
+    }
+}
+
+// 加载文本动画
+pub fn animate_loading_text(
+    mut loading_query: Query<(&mut LoadingText, &mut TextColor)>,
+    motion_settings: Res<MotionSettings>,
+    time: Res<Time>,
+) {
+    for (mut loading_text, mut text_color) in loading_query.iter_mut() {
+        // 减少动效时文字保持不透明，不再闪烁
+        if motion_settings.reduced_motion {
+            text_color.0 = Color::WHITE;
+            continue;
+        }
+
+        loading_text.timer.tick(time.delta());
+
+        // 计算脉冲效果的透明度
+        let alpha = (loading_text.timer.elapsed_secs() * std::f32::consts::PI).sin() * 0.3 + 0.7;
+        text_color.0 = Color::srgba(1.0, 1.0, 1.0, alpha);
+    }
+}
+
+// 淡入效果更新
+pub fn update_fade_in_effects(
+    mut fade_query: Query<(&mut FadeIn, &mut BackgroundColor)>,
+    motion_settings: Res<MotionSettings>,
+    time: Res<Time>,
+) {
+    for (mut fade_in, mut background_color) in fade_query.iter_mut() {
+        fade_in.timer.tick(time.delta());
+
+        // 减少动效时直接显示最终状态
+        let alpha = if motion_settings.reduced_motion {
+            1.0
+        } else {
+            (fade_in.timer.elapsed_secs() / fade_in.duration).min(1.0)
+        };
+
+        // 透明背景渐现效果
+        background_color.0 = Color::srgba(0.0, 0.0, 0.0, alpha * 0.3);
+    }
+}
@@ -0,0 +1,750 @@
+// 游戏插件 - 把整个黑白棋游戏（菜单、棋盘、AI对手、设置）注册到Bevy应用中
+//
+// ReversiPlugin需要在DefaultPlugins之后添加，它会创建自己的2D相机。
+// 偏好设置会在插件构建时读取，宿主应用也可以提前插入Preferences资源来覆盖
+
+pub mod gameplay;
+mod menus;
+
+use crate::{
+    accessibility::{
+        post_announcements, sync_motion_preferences, toggle_announcements_system,
+        AccessibilitySettings, AnnouncementEvent,
+    },
+    ai::{AiDifficulty, AiScheduler, SearchSummary},
+    analysis::{
+        analysis_input, cleanup_replay_board, enter_analysis_input, finish_analysis,
+        handle_brush_buttons, handle_position_input, render_analysis_board, start_analysis,
+        start_replay, update_analysis_status, update_brush_toolbar, update_position_input_text,
+        PositionInput,
+    },
+    assist::{refresh_hint_cache, ActiveMatchRules, CornerWarningState, HintCache},
+    audio::{
+        classify_game_events, load_audio_assets, play_sound_system, toggle_audio_system,
+        update_audio_ducking, AudioSettings, MoveOutcome, PlaySoundEvent,
+    },
+    banter::{trigger_banter, BanterState},
+    boot::{
+        spawn_background_loading_indicator, track_boot_assets, update_background_loading_indicator,
+        update_boot_progress, validate_boot_assets, BootAssets, MissingAssets,
+    },
+    clock::{tick_game_clock, GameClock},
+    coach::{coach_player_moves, CoachState},
+    config_assets::{
+        apply_eval_config_changes, apply_theme_changes, load_config_assets, recolor_board,
+        update_active_theme, ActiveEvalConfig, ActiveTheme, BoardTheme, EvalConfigAsset,
+        RonAssetLoader,
+    },
+    demo::{
+        advance_demo_game, advance_menu_backdrop, manage_commentary_panel, reset_attract_mode,
+        spawn_menu_backdrop, update_attract_mode, update_demo_commentary, AttractMode,
+        CommentaryFeed, MenuBackdrop,
+    },
+    display::{
+        apply_display_events, display_shortcuts_system, track_window_geometry, update_board_layout,
+        BoardLayout, CycleWindowPresetEvent, ToggleFullscreenEvent,
+    },
+    fonts::{load_font_assets, update_chinese_text_fonts, FontAssets},
+    game::{
+        assert_board_invariants, invariant_checks_enabled, refresh_legal_moves_cache,
+        LegalMovesCache, Move, MoveCommand, PlayerColor,
+    },
+    history::{history_gesture_system, record_history_snapshots, HistoryPreview, MoveHistory},
+    input::{
+        handle_notation_input, hide_board_cursor_on_pointer, play_premove, read_gamepad_actions,
+        BoardCursor, GamepadActionEvent, InputGate, InputMap, NotationInput, PlayerSeats, Premove,
+    },
+    localization::{resolve_text_keys, ChangeLanguageEvent, LanguageSettings},
+    net::{
+        finish_spectating, receive_spectator_frames, start_spectating, SpectatorLink, SyncEvent,
+    },
+    platform::{fall_back_to_full_quality_assets, PlatformProfile},
+    practice::{
+        autoplay_opening_moves, opening_autoplay_finished, start_opening_autoplay,
+        update_move_grades, MoveQualityGrades, OpeningAutoplay, SelectedOpening,
+    },
+    preferences::{persist_preferences, storage::StorageLocation, Preferences},
+    record::{archive_finished_games, record_move_times, GameArchive, GameRecord},
+    sandbox::SandboxMode,
+    series::{prepare_match_series, record_match_results, reset_match_series, MatchSeries},
+    state::GameState,
+    stats::{
+        record_flip_stats, record_game_insights, record_game_results, track_feature_usage,
+        GameFinishedEvent, PlayerStats,
+    },
+    systems::GameSystems,
+    ui::{
+        animate_confetti, animate_piece_flips, animate_ripples, animate_score_flourish,
+        animate_turn_change, apply_ui_focus, cleanup_marked_entities, fade_in_defeat_tint,
+        game_intro_finished, game_not_paused, handle_archive_button, handle_asset_warning_close,
+        handle_corner_warning_buttons, handle_danger_zone_button, handle_hint_input,
+        handle_insights_button, handle_insights_clear_button, handle_pass_button,
+        handle_pass_requests, handle_pause_difficulty_buttons, handle_pause_input,
+        handle_pause_resume_button, handle_report_export, handle_restart_button,
+        handle_rules_button, handle_settings_button, handle_settings_options,
+        handle_settings_toggle, handle_sound_test_buttons, handle_transcript_export,
+        handle_undo_button, leave_spectating_input, manage_archive_panel,
+        manage_corner_warning_dialog, manage_danger_zone_overlay, manage_debug_overlay,
+        manage_insights_panel, manage_notation_input_box, manage_pause_menu,
+        manage_performance_overlay, manage_rules_panel, manage_settings_panel,
+        manage_shortcuts_overlay, manage_sound_test_panel, manage_square_labels, play_game_intro,
+        pulse_active_avatar, queue_toasts, reclaim_mini_boards, render_spectator_board,
+        restyle_board, scroll_archive_list, setup_board_ui, setup_game_ui, setup_time_stats_panel,
+        shake_camera, show_asset_warning_panel, show_save_conflicts, show_sync_notices,
+        spawn_input_ripples, spawn_spectator_panel, start_end_game_effects, start_game_intro,
+        start_turn_change_animation, stop_camera_shake, sync_mini_boards, tick_toasts,
+        tint_move_indicators, toggle_debug_overlay, toggle_performance_overlay,
+        toggle_shortcuts_overlay, toggle_square_labels, update_ai_thinking_indicator,
+        update_banter_bubble, update_board_cursor_highlight, update_coach_ticker,
+        update_current_player_text, update_custom_cursor, update_debug_overlay,
+        update_difficulty_text, update_flip_counts, update_game_status_text, update_hint_marker,
+        update_history_live_preview, update_hover_highlight, update_hud_visibility,
+        update_match_score_text, update_notation_input_text, update_pass_button,
+        update_performance_overlay, update_phase_info, update_pieces, update_player_clocks,
+        update_premove_marker, update_score_projections, update_score_widget,
+        update_search_caption, update_settings_option_texts, update_sound_test_texts,
+        update_toast_stack, update_undo_button, update_valid_moves, BoardColors, CurrentPlayer,
+        FlipChainEvent, GameIntro, HintRequest, MiniBoardPool, PassRequestEvent, RestartGameEvent,
+        ToastEvent, ToastQueue, ToggleRulesEvent, ToggleSettingsEvent, UiFocus, UiState,
+    },
+    undo::{apply_undo_requests, UndoRequestEvent, UndoState},
+};
+use bevy::{
+    diagnostic::{EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+    ui::UiSystem,
+};
+use gameplay::*;
+use menus::*;
+
+/// 玩家点击棋盘产生的落子请求，校验后转换为走棋命令
+#[derive(Event)]
+pub struct PlayerMoveEvent {
+    pub position: u8,
+}
+
+/// AI思考完成后给出的落子
+#[derive(Event)]
+pub struct AiMoveEvent {
+    pub ai_move: Move,
+}
+
+/// AI经过搜索走出一步后的搜索统计，在同一帧的AiMoveEvent之后写入
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AiSearchEvent {
+    pub summary: SearchSummary,
+}
+
+/// 从对局返回难度选择界面
+#[derive(Event)]
+pub struct BackToDifficultyEvent;
+
+/// 难度选择界面中选中的AI难度
+#[derive(Resource)]
+pub struct SelectedDifficulty(pub AiDifficulty);
+
+impl Default for SelectedDifficulty {
+    fn default() -> Self {
+        Self(AiDifficulty::Intermediate)
+    }
+}
+
+/// 对局实体：棋盘和命令日志所在的实体，AI是它的子实体
+///
+/// 对局与界面分开保存，离开对局界面再回来（暂停、读档、回放共用对局界面）时继续原来的对局
+#[derive(Component)]
+pub struct GameSession;
+
+/// 下次进入对局界面时开始新对局；没有请求且已有对局时继续原来的对局
+#[derive(Resource, Default)]
+pub struct NewGameRequest(pub bool);
+
+/// 完整的黑白棋游戏插件
+///
+/// 注册游戏状态、事件、资源和全部系统，系统按GameSystems分组依次执行，
+/// 宿主应用可以用这些系统集排列自己的系统
+#[derive(Default)]
+pub struct ReversiPlugin;
+
+impl Plugin for ReversiPlugin {
+    fn build(&self, app: &mut App) {
+        // 宿主应用可以提前插入存档位置，否则使用平台配置目录
+        let storage = app
+            .world()
+            .get_resource::<StorageLocation>()
+            .cloned()
+            .unwrap_or_default();
+
+        // 宿主应用没有提前插入偏好设置时，从存储中读取
+        let preferences = app
+            .world()
+            .get_resource::<Preferences>()
+            .cloned()
+            .unwrap_or_else(|| Preferences::load(&storage));
+
+        app.insert_resource(preferences.motion.clone())
+            .insert_resource(preferences)
+            .insert_resource(PlayerStats::load(&storage))
+            .insert_resource(GameArchive::load(&storage))
+            .insert_resource(storage)
+            .init_state::<GameState>()
+            .add_event::<PlayerMoveEvent>()
+            .add_event::<AiMoveEvent>()
+            .add_event::<AiSearchEvent>()
+            .add_event::<MoveCommand>()
+            .add_event::<PlaySoundEvent>()
+            .add_event::<MoveOutcome>()
+            .add_event::<RestartGameEvent>()
+            .add_event::<ToggleRulesEvent>()
+            .add_event::<ChangeLanguageEvent>()
+            .add_event::<BackToDifficultyEvent>()
+            .add_event::<AnnouncementEvent>()
+            .add_event::<ToggleSettingsEvent>()
+            .add_event::<ToggleFullscreenEvent>()
+            .add_event::<CycleWindowPresetEvent>()
+            .add_event::<GameFinishedEvent>()
+            .add_event::<GamepadActionEvent>()
+            .add_event::<FlipChainEvent>()
+            .add_event::<UndoRequestEvent>()
+            .add_event::<SyncEvent>()
+            .add_event::<PassRequestEvent>()
+            .add_event::<ToastEvent>()
+            .init_asset::<EvalConfigAsset>()
+            .init_asset::<BoardTheme>()
+            .register_asset_loader(RonAssetLoader::<EvalConfigAsset>::new(&["eval.ron"]))
+            .register_asset_loader(RonAssetLoader::<BoardTheme>::new(&["theme.ron"]))
+            .init_resource::<ActiveEvalConfig>()
+            .init_resource::<AiScheduler>()
+            .init_resource::<SpectatorLink>()
+            .init_resource::<ActiveTheme>()
+            .init_resource::<NotationInput>()
+            .init_resource::<CoachState>()
+            .init_resource::<BanterState>()
+            .init_resource::<GameClock>()
+            .init_resource::<PositionInput>()
+            .init_resource::<LegalMovesCache>()
+            .init_resource::<MiniBoardPool>()
+            .init_resource::<PlatformProfile>()
+            .init_resource::<BootAssets>()
+            .init_resource::<MissingAssets>()
+            .init_resource::<BoardColors>()
+            .init_resource::<SelectedDifficulty>()
+            .init_resource::<NewGameRequest>()
+            .init_resource::<SelectedOpening>()
+            .init_resource::<SandboxMode>()
+            .init_resource::<MatchSeries>()
+            .init_resource::<OpeningAutoplay>()
+            .init_resource::<GameIntro>()
+            .init_resource::<UndoState>()
+            .init_resource::<AudioSettings>()
+            .init_resource::<AccessibilitySettings>()
+            .init_resource::<UiState>()
+            .init_resource::<InputMap>()
+            .init_resource::<LanguageSettings>()
+            .init_resource::<FontAssets>()
+            .init_resource::<RestartTimer>()
+            .init_resource::<BoardLayout>()
+            // 无界面插件的测试环境中同样需要界面缩放资源
+            .init_resource::<UiScale>()
+            .init_resource::<MoveHistory>()
+            .init_resource::<HistoryPreview>()
+            .init_resource::<CornerWarningState>()
+            .init_resource::<ActiveMatchRules>()
+            .init_resource::<InputGate>()
+            .init_resource::<Premove>()
+            .init_resource::<PlayerSeats>()
+            .init_resource::<ToastQueue>()
+            .init_resource::<MoveQualityGrades>()
+            .init_resource::<HintCache>()
+            .init_resource::<HintRequest>()
+            .init_resource::<BoardCursor>()
+            .init_resource::<UiFocus>()
+            .init_resource::<GameRecord>()
+            .init_resource::<AttractMode>()
+            .init_resource::<CommentaryFeed>()
+            .init_resource::<MenuBackdrop>()
+            .insert_resource(CurrentPlayer(PlayerColor::Black))
+            .insert_resource(ClearColor(Color::srgb(0.18, 0.58, 0.18)))
+            // 启动时开始加载字体和音效，登记后由加载画面显示进度
+            .add_systems(
+                Startup,
+                (
+                    (
+                        load_audio_assets,
+                        load_font_assets,
+                        load_config_assets,
+                        setup_camera,
+                    ),
+                    track_boot_assets,
+                )
+                    .chain(),
+            )
+            // 手柄输入在界面交互之后转换为焦点导航和按下，Update中的按钮系统即可响应
+            .add_systems(
+                PreUpdate,
+                (read_gamepad_actions, apply_ui_focus)
+                    .chain()
+                    .after(UiSystem::Focus),
+            )
+            // Loading Screen 状态系统
+            .add_systems(OnEnter(GameState::LoadingScreen), setup_loading_screen)
+            .add_systems(
+                Update,
+                (
+                    update_boot_progress,
+                    check_loading_complete,
+                    animate_loading_text,
+                )
+                    .run_if(in_state(GameState::LoadingScreen)),
+            )
+            // 精简资源加载失败时改为完整资源，在离开加载画面的检查之前运行
+            .add_systems(
+                Update,
+                fall_back_to_full_quality_assets.before(check_loading_complete),
+            )
+            // 全部资源结束加载后检查一次缺失的文件，在所有状态下显示警告面板
+            .add_systems(
+                Update,
+                (
+                    validate_boot_assets.after(fall_back_to_full_quality_assets),
+                    (show_asset_warning_panel, handle_asset_warning_close)
+                        .in_set(GameSystems::Common),
+                )
+                    .chain(),
+            )
+            .add_systems(
+                OnExit(GameState::LoadingScreen),
+                spawn_background_loading_indicator,
+            )
+            // 语言选择状态系统
+            .add_systems(
+                OnEnter(GameState::LanguageSelection),
+                (setup_language_selection, spawn_menu_backdrop::<GameState>),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_language_selection,
+                    update_button_interactions,
+                    update_fade_in_effects,
+                )
+                    .run_if(in_state(GameState::LanguageSelection)),
+            )
+            // 菜单背景棋盘在语言选择和难度选择界面中持续对弈，缩略棋盘在通用系统中重绘
+            .add_systems(
+                Update,
+                advance_menu_backdrop
+                    .run_if(
+                        in_state(GameState::LanguageSelection)
+                            .or(in_state(GameState::DifficultySelection)),
+                    )
+                    .in_set(GameSystems::UI),
+            )
+            // 难度选择状态系统
+            .add_systems(
+                OnEnter(GameState::DifficultySelection),
+                (
+                    setup_difficulty_selection,
+                    reset_attract_mode,
+                    reset_match_series,
+                    spawn_menu_backdrop::<GameState>,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_difficulty_selection,
+                    (
+                        handle_opening_practice_button,
+                        handle_time_control_button,
+                        update_time_control_text,
+                    ),
+                    handle_sandbox_button,
+                    handle_rules_button,
+                    manage_rules_panel,
+                    handle_danger_zone_button,
+                    handle_settings_button,
+                    manage_settings_panel,
+                    handle_settings_options,
+                    update_settings_option_texts,
+                    (
+                        manage_sound_test_panel,
+                        handle_sound_test_buttons,
+                        update_sound_test_texts,
+                    ),
+                    update_button_interactions,
+                    update_fade_in_effects,
+                    update_attract_mode::<GameState>,
+                    (
+                        advance_demo_game::<GameState>,
+                        update_demo_commentary,
+                        manage_commentary_panel::<GameState>,
+                    )
+                        .chain(),
+                    handle_archive_button,
+                    manage_archive_panel,
+                    scroll_archive_list,
+                    handle_archive_selection,
+                    (
+                        handle_insights_button,
+                        handle_insights_clear_button,
+                        manage_insights_panel,
+                    ),
+                )
+                    .run_if(in_state(GameState::DifficultySelection)),
+            )
+            .add_systems(
+                OnEnter(GameState::Playing),
+                // 比赛和开局练习的自动走棋只在开始新对局时准备，继续原来的对局时保持不变；
+                // 界面按比赛中玩家的执子颜色布置头像，所以先准备比赛
+                (
+                    prepare_match_series.run_if(starts_new_game),
+                    setup_board_ui,
+                    setup_game_ui,
+                    (start_opening_autoplay, start_game_intro).run_if(starts_new_game),
+                    setup_game,
+                    update_pieces,
+                )
+                    .chain(),
+            )
+            // 游戏进行状态系统
+            .add_systems(
+                Update,
+                (
+                    // 游戏核心逻辑
+                    (
+                        history_gesture_system.run_if(game_not_paused),
+                        move_board_cursor,
+                        (
+                            (handle_input, handle_notation_input),
+                            play_premove
+                                .run_if(game_not_paused)
+                                .run_if(opening_autoplay_finished),
+                            play_scripted_moves
+                                .run_if(game_not_paused)
+                                .run_if(opening_autoplay_finished),
+                        )
+                            .chain()
+                            .run_if(game_intro_finished),
+                        handle_player_move,
+                        handle_ai_move,
+                        coach_player_moves,
+                        autoplay_opening_moves.run_if(game_intro_finished),
+                        apply_undo_requests,
+                        handle_pass_requests,
+                        (apply_move_commands, classify_game_events).chain(),
+                        refresh_legal_moves_cache,
+                        assert_board_invariants.run_if(invariant_checks_enabled),
+                        record_move_times,
+                        record_history_snapshots,
+                        ai_system
+                            .run_if(game_not_paused)
+                            .run_if(opening_autoplay_finished)
+                            .run_if(game_intro_finished),
+                        tick_game_clock
+                            .run_if(game_not_paused)
+                            .run_if(opening_autoplay_finished)
+                            .run_if(game_intro_finished),
+                        check_game_over,
+                    )
+                        .chain() // 确保顺序执行
+                        .in_set(GameSystems::Gameplay),
+                    // UI更新
+                    (
+                        (update_pieces, play_game_intro).chain(),
+                        (
+                            (update_valid_moves, update_move_grades, tint_move_indicators).chain(),
+                            update_coach_ticker,
+                            update_score_projections,
+                            update_flip_counts,
+                        ),
+                        update_hover_highlight,
+                        spawn_input_ripples,
+                        animate_ripples,
+                        (
+                            update_score_widget,
+                            update_player_clocks,
+                            update_phase_info,
+                            update_hud_visibility,
+                        ),
+                        update_current_player_text,
+                        (update_game_status_text, update_history_live_preview),
+                        (
+                            start_turn_change_animation,
+                            animate_turn_change,
+                            pulse_active_avatar,
+                        )
+                            .chain(),
+                        update_difficulty_text,
+                        (
+                            update_ai_thinking_indicator,
+                            (trigger_banter, update_banter_bubble).chain(),
+                            update_search_caption,
+                        ),
+                        handle_restart_button,
+                        handle_back_to_difficulty_button,
+                        (
+                            handle_undo_button,
+                            update_undo_button,
+                            handle_pass_button,
+                            update_pass_button,
+                            manage_notation_input_box,
+                            update_notation_input_text,
+                        ),
+                        manage_corner_warning_dialog,
+                        handle_corner_warning_buttons,
+                        (
+                            manage_danger_zone_overlay,
+                            toggle_square_labels,
+                            manage_square_labels,
+                            (
+                                toggle_debug_overlay,
+                                manage_debug_overlay,
+                                update_debug_overlay,
+                            )
+                                .chain(),
+                        ),
+                        (
+                            update_board_cursor_highlight,
+                            update_premove_marker,
+                            (refresh_hint_cache, handle_hint_input, update_hint_marker).chain(),
+                        ),
+                        (
+                            handle_pause_input,
+                            handle_pause_resume_button,
+                            manage_pause_menu,
+                            handle_pause_difficulty_buttons,
+                        ),
+                        update_button_interactions,
+                    )
+                        .in_set(GameSystems::UI),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            // 游戏结束状态系统
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                (setup_time_stats_panel, update_hud_visibility),
+            )
+            // 终局特效只在对局刚结束时播放，从复盘分析返回时不重播
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Playing,
+                    entered: GameState::GameOver,
+                },
+                start_end_game_effects,
+            )
+            .add_systems(OnExit(GameState::GameOver), stop_camera_shake)
+            .add_systems(
+                Update,
+                (
+                    handle_game_over_input,
+                    handle_transcript_export,
+                    handle_report_export,
+                    enter_analysis_input,
+                    (
+                        animate_confetti,
+                        animate_score_flourish,
+                        fade_in_defeat_tint,
+                        shake_camera,
+                    ),
+                )
+                    .run_if(in_state(GameState::GameOver)),
+            )
+            // 复盘分析状态系统
+            .add_systems(OnEnter(GameState::Analysis), start_analysis)
+            .add_systems(OnExit(GameState::Analysis), finish_analysis)
+            // 历史对局回放使用同一个查看器，另外需要自己的棋盘
+            .add_systems(OnEnter(GameState::Replay), (setup_board_ui, start_replay))
+            .add_systems(
+                OnExit(GameState::Replay),
+                (finish_analysis, cleanup_replay_board),
+            )
+            .add_systems(
+                Update,
+                (
+                    (handle_brush_buttons, analysis_input, handle_position_input)
+                        .chain()
+                        .in_set(GameSystems::Gameplay),
+                    (
+                        render_analysis_board,
+                        update_analysis_status,
+                        update_position_input_text,
+                        update_brush_toolbar,
+                    )
+                        .in_set(GameSystems::UI),
+                )
+                    .run_if(in_state(GameState::Analysis).or(in_state(GameState::Replay))),
+            )
+            // 观战状态：只读棋盘跟随传输层转发的命令流
+            .add_systems(
+                OnEnter(GameState::Spectating),
+                (setup_board_ui, spawn_spectator_panel, start_spectating),
+            )
+            .add_systems(
+                OnExit(GameState::Spectating),
+                (finish_spectating, cleanup_replay_board),
+            )
+            .add_systems(
+                Update,
+                (
+                    (leave_spectating_input, receive_spectator_frames)
+                        .in_set(GameSystems::Gameplay),
+                    render_spectator_board.in_set(GameSystems::UI),
+                )
+                    .run_if(in_state(GameState::Spectating)),
+            )
+            // 重新开始状态处理，进入时清理旧的对局界面
+            .add_systems(
+                OnEnter(GameState::Restarting),
+                (setup_restart_timer, begin_restart),
+            )
+            // 返回菜单的切换负责清理对局界面和对局
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Playing,
+                    entered: GameState::DifficultySelection,
+                },
+                leave_game_screen,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::GameOver,
+                    entered: GameState::DifficultySelection,
+                },
+                leave_game_screen,
+            )
+            .add_systems(
+                Update,
+                handle_restart_state.run_if(in_state(GameState::Restarting)),
+            )
+            // 通用系统 - 在所有状态下运行
+            .add_systems(
+                Update,
+                (
+                    (play_sound_system, update_audio_ducking).chain(),
+                    toggle_audio_system,
+                    toggle_announcements_system,
+                    post_announcements,
+                    restart_game,
+                    handle_rules_toggle,
+                    handle_settings_toggle,
+                    handle_language_change,
+                    handle_back_to_difficulty_event,
+                    update_chinese_text_fonts,
+                    resolve_text_keys,
+                    display_shortcuts_system,
+                    apply_display_events,
+                    track_window_geometry,
+                    update_board_layout,
+                    sync_motion_preferences,
+                    update_custom_cursor,
+                    hide_board_cursor_on_pointer,
+                    persist_preferences,
+                    (
+                        (record_game_insights, record_flip_stats, record_game_results).chain(),
+                        (record_match_results, archive_finished_games).chain(),
+                        update_match_score_text,
+                        track_feature_usage,
+                    ),
+                )
+                    .in_set(GameSystems::Common),
+            )
+            .add_systems(
+                Update,
+                update_background_loading_indicator.in_set(GameSystems::Common),
+            )
+            // 性能信息层在所有状态下可用
+            .add_systems(
+                Update,
+                (
+                    toggle_performance_overlay,
+                    manage_performance_overlay,
+                    update_performance_overlay,
+                )
+                    .chain()
+                    .in_set(GameSystems::Common),
+            )
+            // 快捷键帮助在所有状态下可用，内容随状态变化
+            .add_systems(
+                Update,
+                (toggle_shortcuts_overlay, manage_shortcuts_overlay)
+                    .chain()
+                    .in_set(GameSystems::Common),
+            )
+            // 通知提示在所有状态下显示：联机同步提示由传输层发送的SyncEvent触发，
+            // 存档冲突由存储后端在保存时发现
+            .add_systems(
+                Update,
+                (
+                    (show_sync_notices, show_save_conflicts),
+                    queue_toasts,
+                    tick_toasts,
+                    update_toast_stack,
+                )
+                    .chain()
+                    .in_set(GameSystems::Common),
+            )
+            // 评估配置和棋盘主题文件加载或修改后立即生效
+            .add_systems(
+                Update,
+                (
+                    apply_eval_config_changes,
+                    (
+                        update_active_theme,
+                        apply_theme_changes,
+                        recolor_board,
+                        restyle_board,
+                    )
+                        .chain(),
+                )
+                    .in_set(GameSystems::Common),
+            )
+            // 翻转动画在对局结束后也要播放完，不限制状态
+            .add_systems(
+                Update,
+                animate_piece_flips
+                    .after(update_pieces)
+                    .in_set(GameSystems::UI),
+            )
+            // 缩略棋盘用于难度选择界面的历史对局列表和对局中的历史浏览，不限制状态
+            .add_systems(Update, sync_mini_boards.in_set(GameSystems::Common))
+            // 宿主被删除前回收缩略棋盘的实体组
+            .add_systems(
+                Update,
+                reclaim_mini_boards
+                    .after(GameSystems::Common)
+                    .before(cleanup_marked_entities),
+            )
+            // 清理系统单独运行，确保在所有其他系统之后
+            .add_systems(Update, cleanup_marked_entities.after(GameSystems::Common))
+            // 配置系统依赖关系
+            .configure_sets(
+                Update,
+                (GameSystems::Gameplay, GameSystems::UI, GameSystems::Common).chain(), // 按顺序执行
+            );
+
+        // 性能信息层的帧率和实体数，宿主应用已经添加时不重复添加
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+        if !app.is_plugin_added::<EntityCountDiagnosticsPlugin>() {
+            app.add_plugins(EntityCountDiagnosticsPlugin);
+        }
+
+        // 专家+难度的网络权重，缺失时使用启发式评估
+        #[cfg(feature = "nn-eval")]
+        app.init_asset::<crate::config_assets::NetworkWeights>()
+            .register_asset_loader(crate::config_assets::NetworkWeightsLoader)
+            .add_systems(Startup, crate::config_assets::load_network_weights)
+            .add_systems(
+                Update,
+                crate::config_assets::install_network_weights.in_set(GameSystems::Common),
+            );
+    }
+}
+
+fn setup_camera(mut commands: Commands) {
+    // 创建共享的2D相机
+    commands.spawn(Camera2d);
+}
@@ -0,0 +1,162 @@
+// 开局练习 - 自动替双方走完选定的开局，再把对局交给玩家对抗AI
+//
+// 在难度选择界面选择开局后开始对局：从标准初始局面开始，按开局库每隔一段时间自动走一步，
+// 走完前plies步后玩家和AI正常对弈。自动走棋期间不接受棋盘输入，AI也不开始思考。
+// 轮到玩家时可落子指示器按走法质量着色（见move_quality）
+
+pub mod move_quality;
+
+pub use move_quality::*;
+
+use crate::{
+    ai::opening_book::{Opening, OPENING_BOOK},
+    game::{GameLog, MoveAction, MoveCommand},
+    localization::{TextArg, TextArgs, TextKey},
+    sandbox::SandboxMode,
+    ui::UiState,
+};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// 自动走棋每步之间的间隔（秒）
+const AUTOPLAY_INTERVAL_SECS: f32 = 0.6;
+
+/// 选择的开局练习
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpeningPractice {
+    /// 开局在开局库中的序号
+    pub opening: usize,
+    /// 自动走的步数，不超过开局的步数
+    pub plies: usize,
+}
+
+impl OpeningPractice {
+    /// 自动走完整个开局的练习，序号超出开局库时返回None
+    pub fn new(opening: usize) -> Option<Self> {
+        OPENING_BOOK.get(opening).map(|book| Self {
+            opening,
+            plies: book.plies(),
+        })
+    }
+
+    /// 练习的开局
+    pub fn opening(&self) -> &'static Opening {
+        &OPENING_BOOK[self.opening]
+    }
+
+    /// 自动走棋的位置
+    pub fn positions(&self) -> Vec<u8> {
+        let mut positions = self.opening().positions().unwrap_or_default();
+        positions.truncate(self.plies);
+        positions
+    }
+}
+
+/// 难度选择界面中选择的开局练习，None表示普通对局
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SelectedOpening(pub Option<OpeningPractice>);
+
+impl SelectedOpening {
+    /// 依次切换到开局库中的下一个开局，最后一个之后回到普通对局
+    pub fn cycle(&mut self) {
+        let next = self.0.map_or(0, |practice| practice.opening + 1);
+        self.0 = OpeningPractice::new(next);
+    }
+
+    /// 开局练习文本的参数：开局名称，普通对局时显示"关"
+    pub fn text_args(&self) -> TextArgs {
+        TextArgs(vec![self
+            .0
+            .map_or(TextArg::Key(TextKey::ValueOff), |practice| {
+                TextArg::Value(practice.opening().name.to_string())
+            })])
+    }
+}
+
+/// 开局自动走棋的进度
+#[derive(Resource, Debug)]
+pub struct OpeningAutoplay {
+    /// 还没有走的开局走法
+    remaining: VecDeque<u8>,
+    /// 每步之间的间隔
+    timer: Timer,
+}
+
+impl Default for OpeningAutoplay {
+    fn default() -> Self {
+        Self {
+            remaining: VecDeque::new(),
+            timer: Timer::from_seconds(AUTOPLAY_INTERVAL_SECS, TimerMode::Once),
+        }
+    }
+}
+
+impl OpeningAutoplay {
+    /// 准备自动走棋
+    pub fn new(positions: impl IntoIterator<Item = u8>) -> Self {
+        Self {
+            remaining: positions.into_iter().collect(),
+            ..default()
+        }
+    }
+
+    /// 是否还在自动走开局
+    pub fn is_playing(&self) -> bool {
+        !self.remaining.is_empty()
+    }
+}
+
+/// 自动走棋结束后AI才开始思考
+pub fn opening_autoplay_finished(autoplay: Res<OpeningAutoplay>) -> bool {
+    !autoplay.is_playing()
+}
+
+/// 对局开始时按选择的开局准备自动走棋，普通对局和沙盒中清空
+pub fn start_opening_autoplay(
+    selected_opening: Res<SelectedOpening>,
+    sandbox: Res<SandboxMode>,
+    mut autoplay: ResMut<OpeningAutoplay>,
+) {
+    *autoplay = selected_opening
+        .0
+        .filter(|_| !sandbox.0)
+        .map_or_else(OpeningAutoplay::default, |practice| {
+            OpeningAutoplay::new(practice.positions())
+        });
+}
+
+/// 每隔一段时间替当前一方走开局的下一步
+///
+/// 需要在教练点评之后、应用走棋命令之前运行：命令在同一帧应用，教练不会点评开局库中的走法
+pub fn autoplay_opening_moves(
+    mut autoplay: ResMut<OpeningAutoplay>,
+    log_query: Query<&GameLog>,
+    mut move_commands: EventWriter<MoveCommand>,
+    ui_state: Res<UiState>,
+    time: Res<Time>,
+) {
+    if !autoplay.is_playing() || ui_state.paused {
+        return;
+    }
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+
+    autoplay.timer.tick(time.delta());
+    if !autoplay.timer.finished() {
+        return;
+    }
+    autoplay.timer.reset();
+
+    let Some(position) = autoplay.remaining.pop_front() else {
+        return;
+    };
+    let command = log.next_command(MoveAction::Place(position));
+    if let Err(err) = log.validate(&command) {
+        // 开局库与局面不符（例如宿主应用改了初始局面）时放弃剩余的开局
+        warn!("Opening move {command:?} is not playable: {err}");
+        autoplay.remaining.clear();
+        return;
+    }
+    move_commands.write(command);
+}
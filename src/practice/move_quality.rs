@@ -0,0 +1,167 @@
+// 走法质量 - 开局练习中按两层搜索把玩家的每个合法走法分为好、一般和差三档
+//
+// 轮到玩家走棋时在后台线程中为每个走法搜索两层（自己落子和对手的最佳应手），
+// 与最佳走法的差距决定档位。结果只用于给可落子指示器着色（见ui::move_quality_ui），
+// 不指出具体该走哪里，初学者可以在没有明确提示的情况下得到温和的引导
+
+use super::{OpeningAutoplay, SelectedOpening};
+use crate::{
+    ai::{evaluation::EvalConfig, minimax::minimax, Evaluator},
+    config_assets::ActiveEvalConfig,
+    game::{Board, PlayerColor},
+    input::PlayerSeats,
+    sandbox::SandboxMode,
+    ui::CurrentPlayer,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use futures_lite::future;
+use std::sync::Arc;
+
+/// 比最佳走法少于该评估分的走法为好棋
+pub const DUBIOUS_MARGIN: i32 = 40;
+
+/// 比最佳走法少该评估分及以上的走法为差棋
+pub const BAD_MARGIN: i32 = 120;
+
+/// 走法的质量档位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveQuality {
+    Good,
+    Dubious,
+    Bad,
+}
+
+impl MoveQuality {
+    /// 按与最佳走法的评估分差距分档
+    pub fn from_loss(loss: i32) -> Self {
+        if loss < DUBIOUS_MARGIN {
+            Self::Good
+        } else if loss < BAD_MARGIN {
+            Self::Dubious
+        } else {
+            Self::Bad
+        }
+    }
+
+    /// 指示器的颜色：好绿、一般黄、差红
+    pub fn color(self) -> Color {
+        match self {
+            Self::Good => Color::srgb(0.3, 0.9, 0.35),
+            Self::Dubious => Color::srgb(1.0, 0.85, 0.25),
+            Self::Bad => Color::srgb(1.0, 0.35, 0.3),
+        }
+    }
+}
+
+/// 每个合法走法的两层搜索评估分：落子后让对手应一步，取对手最佳应手后的评估分
+pub fn score_moves(
+    board: &Board,
+    player: PlayerColor,
+    eval: &(impl Evaluator + ?Sized),
+) -> Vec<(u8, i32)> {
+    board
+        .get_valid_moves_list(player)
+        .into_iter()
+        .map(|candidate| {
+            let mut after = *board;
+            after.make_move(candidate.position, player);
+            let score = minimax(&after, 1, i32::MIN, i32::MAX, false, player, eval);
+            (candidate.position, score)
+        })
+        .collect()
+}
+
+/// 为每个合法走法分档：两层搜索的评估分与最佳走法比较
+pub fn grade_moves(
+    board: &Board,
+    player: PlayerColor,
+    eval: &(impl Evaluator + ?Sized),
+) -> Vec<(u8, MoveQuality)> {
+    let scores = score_moves(board, player, eval);
+    let Some(best) = scores.iter().map(|&(_, score)| score).max() else {
+        return Vec::new();
+    };
+    scores
+        .into_iter()
+        .map(|(position, score)| (position, MoveQuality::from_loss(best - score)))
+        .collect()
+}
+
+/// 当前局面各走法的档位，以及正在进行的后台分档任务
+#[derive(Resource, Default)]
+pub struct MoveQualityGrades {
+    /// 分档对应的局面和轮到的一方
+    position: Option<(Board, PlayerColor)>,
+    grades: Vec<(u8, MoveQuality)>,
+    task: Option<Task<Vec<(u8, MoveQuality)>>>,
+}
+
+impl MoveQualityGrades {
+    /// 走法的档位，还没有算完或不是当前局面的走法时返回None
+    pub fn quality(&self, position: u8) -> Option<MoveQuality> {
+        self.grades
+            .iter()
+            .find(|(candidate, _)| *candidate == position)
+            .map(|&(_, quality)| quality)
+    }
+
+    /// 是否正在后台分档
+    pub fn is_pending(&self) -> bool {
+        self.task.is_some()
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// 开局练习中轮到玩家走棋时，局面变化后在后台重新分档，完成后取回结果
+///
+/// 开局自动走棋期间、沙盒和普通对局中不分档；局面在分档完成前又变化时丢弃旧任务
+pub fn update_move_grades(
+    board_query: Query<&Board>,
+    current_player: Res<CurrentPlayer>,
+    seats: Res<PlayerSeats>,
+    selected_opening: Res<SelectedOpening>,
+    autoplay: Res<OpeningAutoplay>,
+    sandbox: Res<SandboxMode>,
+    eval: Res<ActiveEvalConfig>,
+    mut grades: ResMut<MoveQualityGrades>,
+) {
+    let player = current_player.0;
+    let board = board_query.single().ok().filter(|_| {
+        selected_opening.0.is_some()
+            && !sandbox.0
+            && !autoplay.is_playing()
+            && seats.source(player).is_human()
+    });
+    let Some(&board) = board else {
+        if grades.position.is_some() {
+            grades.clear();
+        }
+        return;
+    };
+
+    if grades.position != Some((board, player)) {
+        let eval: Arc<EvalConfig> = eval.0.clone();
+        let task = AsyncComputeTaskPool::get()
+            .spawn(async move { grade_moves(&board, player, eval.as_ref()) });
+        *grades = MoveQualityGrades {
+            position: Some((board, player)),
+            grades: Vec::new(),
+            task: Some(task),
+        };
+        return;
+    }
+
+    let Some(task) = grades.bypass_change_detection().task.as_mut() else {
+        return;
+    };
+    if let Some(result) = future::block_on(future::poll_once(task)) {
+        grades.task = None;
+        grades.grades = result;
+    }
+}
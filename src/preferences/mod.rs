@@ -0,0 +1,88 @@
+// 偏好设置模块 - 跨会话保存用户设置
+//
+// 偏好设置以RON格式通过存储后端持久化，启动时读取，修改后自动保存
+
+pub mod save_guard;
+pub mod storage;
+
+use crate::{
+    accessibility::MotionSettings, ai::SearchPreferences, assist::AssistPreferences,
+    banter::BanterPreferences, config_assets::ThemeMode, display::DisplayPreferences,
+    game::RulesConfig, input::InputPreferences, localization::TextKey, ui::ToastEvent,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use storage::StorageLocation;
+
+/// 偏好设置在存储中的键名
+pub const PREFERENCES_KEY: &str = "preferences.ron";
+
+/// 修改后延迟保存的时间（秒），避免拖动窗口时频繁写入
+const SAVE_DELAY_SECS: f32 = 0.5;
+
+/// 用户偏好设置资源
+///
+/// 新增字段需要提供默认值，以兼容旧版本保存的文件
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Preferences {
+    /// 显示设置（窗口尺寸、位置、全屏）
+    pub display: DisplayPreferences,
+    /// 辅助模式设置
+    pub assist: AssistPreferences,
+    /// 动效设置
+    pub motion: MotionSettings,
+    /// 输入设置
+    pub input: InputPreferences,
+    /// 棋盘主题选项
+    pub theme: ThemeMode,
+    /// 是否在本地记录使用统计，默认关闭
+    pub insights: bool,
+    /// 自定义对局规则，开始新对局时生效
+    pub rules: RulesConfig,
+    /// 搜索设置（置换表大小）
+    pub search: SearchPreferences,
+    /// AI闲聊设置
+    pub banter: BanterPreferences,
+}
+
+impl Preferences {
+    /// 从存储中读取偏好设置，读取或解析失败时使用默认值
+    pub fn load(storage: &StorageLocation) -> Self {
+        storage
+            .read(PREFERENCES_KEY)
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// 将偏好设置写入存储
+    pub fn save(&self, storage: &StorageLocation) -> Result<(), String> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())?;
+        storage.write(PREFERENCES_KEY, &contents)
+    }
+}
+
+/// 偏好设置变化后延迟保存
+pub fn persist_preferences(
+    preferences: Res<Preferences>,
+    storage: Res<StorageLocation>,
+    mut save_timer: Local<Option<Timer>>,
+    time: Res<Time>,
+    mut toast_events: EventWriter<ToastEvent>,
+) {
+    if preferences.is_changed() && !preferences.is_added() {
+        *save_timer = Some(Timer::from_seconds(SAVE_DELAY_SECS, TimerMode::Once));
+    }
+
+    if let Some(timer) = save_timer.as_mut() {
+        timer.tick(time.delta());
+        if timer.finished() {
+            if let Err(err) = preferences.save(&storage) {
+                warn!("Failed to save preferences: {err}");
+                toast_events.write(ToastEvent::save_failed(TextKey::SaveDataPreferences));
+            }
+            *save_timer = None;
+        }
+    }
+}
@@ -0,0 +1,130 @@
+// 存档冲突保护 - 同时运行多个游戏实例时检测彼此覆盖存档的情况
+//
+// 每次写入存储时，在数据旁边另存一个修订号（保存时间和实例编号）。
+// 实例记住自己最后读到或写入的修订号；写入前发现存储中的修订号已经变化，
+// 说明另一个实例在此期间保存过同一份数据。此时仍按"最后写入者为准"保存本实例的数据，
+// 同时记录一次冲突，由界面提示玩家。没有读写过的键不检查冲突
+
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+
+/// 一次保存的修订号
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveRevision {
+    /// 保存时间（Unix毫秒）
+    pub saved_at: u64,
+    /// 保存的实例编号
+    pub instance: u64,
+}
+
+impl SaveRevision {
+    /// 解析存储中的修订号文本"保存时间 实例编号"
+    pub fn parse(text: &str) -> Option<Self> {
+        let (saved_at, instance) = text.trim().split_once(' ')?;
+        Some(Self {
+            saved_at: saved_at.parse().ok()?,
+            instance: instance.parse().ok()?,
+        })
+    }
+
+    /// 存储中保存的文本
+    pub fn to_text(self) -> String {
+        format!("{} {}", self.saved_at, self.instance)
+    }
+}
+
+/// 一次存档冲突：另一个实例在本实例上次读写之后保存过同一个键
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveConflict {
+    /// 存储中的键名
+    pub key: String,
+    /// 另一个实例的修订号
+    pub other: SaveRevision,
+}
+
+/// 本实例读写过的每个键的修订号
+#[derive(Debug)]
+pub struct SaveLedger {
+    instance: u64,
+    seen: HashMap<String, Option<SaveRevision>>,
+}
+
+impl SaveLedger {
+    pub fn new(instance: u64) -> Self {
+        Self {
+            instance,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// 记住读取时存储中的修订号
+    pub fn observe(&mut self, key: &str, current: Option<SaveRevision>) {
+        self.seen.insert(key.to_string(), current);
+    }
+
+    /// 准备写入：返回这次保存的修订号，存储中的修订号与上次读写时不同则同时返回冲突
+    ///
+    /// 新的修订号晚于存储中的修订号，两个实例的时钟不一致时也能区分先后
+    pub fn prepare_write(
+        &self,
+        key: &str,
+        current: Option<SaveRevision>,
+        now: u64,
+    ) -> (SaveRevision, Option<SaveConflict>) {
+        let conflict = match (self.seen.get(key), current) {
+            (Some(&seen), Some(other)) if seen != Some(other) => Some(SaveConflict {
+                key: key.to_string(),
+                other,
+            }),
+            _ => None,
+        };
+        let revision = SaveRevision {
+            saved_at: current.map_or(now, |current| now.max(current.saved_at + 1)),
+            instance: self.instance,
+        };
+        (revision, conflict)
+    }
+
+    /// 写入成功后记住新的修订号
+    pub fn commit(&mut self, key: &str, revision: SaveRevision) {
+        self.seen.insert(key.to_string(), Some(revision));
+    }
+}
+
+/// 本进程的修订号记录，实例编号在启动时随机生成
+pub(super) static LEDGER: LazyLock<Mutex<SaveLedger>> =
+    LazyLock::new(|| Mutex::new(SaveLedger::new(rand::random())));
+
+/// 还没有提示的冲突
+static CONFLICTS: Mutex<Vec<SaveConflict>> = Mutex::new(Vec::new());
+
+/// 记录一次冲突
+pub(super) fn report_conflict(conflict: SaveConflict) {
+    if let Ok(mut conflicts) = CONFLICTS.lock() {
+        conflicts.push(conflict);
+    }
+}
+
+/// 取出还没有提示的冲突
+pub fn take_save_conflicts() -> Vec<SaveConflict> {
+    CONFLICTS
+        .lock()
+        .map(|mut conflicts| std::mem::take(&mut *conflicts))
+        .unwrap_or_default()
+}
+
+/// 当前的Unix时间（毫秒）
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+/// 当前的Unix时间（毫秒），浏览器中不能使用SystemTime
+#[cfg(target_arch = "wasm32")]
+pub(super) fn now_millis() -> u64 {
+    js_sys::Date::now() as u64
+}
@@ -0,0 +1,198 @@
+// 本地存储后端 - 按键读写文本和二进制数据
+//
+// 桌面版写入数据目录下的文件，Web版使用浏览器的localStorage。
+// localStorage只能保存字符串，二进制数据在Web版中以Base64文本保存。
+// 每个键旁边另存修订号（键名加".rev"），用于发现同时运行的其他实例覆盖了存档（见save_guard）；
+// 桌面版先写入临时文件再改名，另一个实例不会读到写了一半的文件。
+// 数据目录由StorageLocation资源决定，宿主应用可以在添加ReversiPlugin之前插入它，
+// 把桌面版的数据写到指定目录，例如测试使用临时目录
+
+use super::save_guard::{now_millis, report_conflict, SaveRevision, LEDGER};
+use bevy::prelude::Resource;
+use std::path::PathBuf;
+
+/// 存档位置：桌面版的数据目录，Web版不使用
+///
+/// 默认为平台配置目录下的reversi目录，找不到配置目录时为当前目录
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct StorageLocation(pub PathBuf);
+
+impl Default for StorageLocation {
+    fn default() -> Self {
+        Self(backend::default_data_dir())
+    }
+}
+
+impl StorageLocation {
+    /// 读取指定键对应的内容，不存在或读取失败时返回None
+    pub fn read(&self, key: &str) -> Option<String> {
+        self.observe_revision(key);
+        backend::read(self, key)
+    }
+
+    /// 写入指定键对应的内容
+    pub fn write(&self, key: &str, contents: &str) -> Result<(), String> {
+        self.guarded_write(key, || backend::write(self, key, contents))
+    }
+
+    /// 读取指定键对应的二进制内容，不存在或读取失败时返回None
+    pub fn read_bytes(&self, key: &str) -> Option<Vec<u8>> {
+        self.observe_revision(key);
+        backend::read_bytes(self, key)
+    }
+
+    /// 写入指定键对应的二进制内容
+    pub fn write_bytes(&self, key: &str, contents: &[u8]) -> Result<(), String> {
+        self.guarded_write(key, || backend::write_bytes(self, key, contents))
+    }
+
+    /// 修订号记录中的键名，不同数据目录中的同名键分开记录
+    fn ledger_key(&self, key: &str) -> String {
+        self.0.join(key).to_string_lossy().into_owned()
+    }
+
+    /// 存储中的修订号
+    fn stored_revision(&self, key: &str) -> Option<SaveRevision> {
+        backend::read(self, &revision_key(key)).and_then(|text| SaveRevision::parse(&text))
+    }
+
+    /// 读取时记住存储中的修订号
+    fn observe_revision(&self, key: &str) {
+        let current = self.stored_revision(key);
+        if let Ok(mut ledger) = LEDGER.lock() {
+            ledger.observe(&self.ledger_key(key), current);
+        }
+    }
+
+    /// 写入数据并更新修订号，另一个实例在此期间保存过同一个键时仍然写入并记录冲突
+    fn guarded_write(
+        &self,
+        key: &str,
+        write: impl FnOnce() -> Result<(), String>,
+    ) -> Result<(), String> {
+        let ledger_key = self.ledger_key(key);
+        let mut ledger = LEDGER.lock().map_err(|err| err.to_string())?;
+        let (revision, conflict) =
+            ledger.prepare_write(&ledger_key, self.stored_revision(key), now_millis());
+        write()?;
+        if let Some(mut conflict) = conflict {
+            // 界面按键名提示是哪一份数据
+            conflict.key = key.to_string();
+            report_conflict(conflict);
+        }
+        backend::write(self, &revision_key(key), &revision.to_text())?;
+        ledger.commit(&ledger_key, revision);
+        Ok(())
+    }
+}
+
+/// 修订号在存储中的键名
+fn revision_key(key: &str) -> String {
+    format!("{key}.rev")
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use super::StorageLocation;
+    use std::path::{Path, PathBuf};
+
+    pub fn read(location: &StorageLocation, key: &str) -> Option<String> {
+        std::fs::read_to_string(location.0.join(key)).ok()
+    }
+
+    pub fn write(location: &StorageLocation, key: &str, contents: &str) -> Result<(), String> {
+        write_bytes(location, key, contents.as_bytes())
+    }
+
+    pub fn read_bytes(location: &StorageLocation, key: &str) -> Option<Vec<u8>> {
+        std::fs::read(location.0.join(key)).ok()
+    }
+
+    /// 先写入临时文件再改名替换，改名是原子操作
+    pub fn write_bytes(
+        location: &StorageLocation,
+        key: &str,
+        contents: &[u8],
+    ) -> Result<(), String> {
+        let dir = &location.0;
+        std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        let path = dir.join(key);
+        let temp = temp_path(&path);
+        std::fs::write(&temp, contents).map_err(|err| err.to_string())?;
+        std::fs::rename(&temp, &path).map_err(|err| err.to_string())
+    }
+
+    /// 临时文件名带上进程号，两个实例同时写入时不会共用同一个临时文件
+    fn temp_path(path: &Path) -> PathBuf {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".{}.tmp", std::process::id()));
+        path.with_file_name(name)
+    }
+
+    /// 桌面版默认的数据目录：优先使用平台配置目录，找不到时回退到当前目录
+    pub fn default_data_dir() -> PathBuf {
+        use std::env;
+
+        let base = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        base.join("reversi")
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use super::StorageLocation;
+    use base64::Engine;
+    use std::path::PathBuf;
+
+    /// Web版不使用数据目录
+    pub fn default_data_dir() -> PathBuf {
+        PathBuf::new()
+    }
+
+    /// 读取指定键对应的内容（localStorage）
+    pub fn read(_location: &StorageLocation, key: &str) -> Option<String> {
+        local_storage()?.get_item(&storage_key(key)).ok().flatten()
+    }
+
+    /// 写入指定键对应的内容（localStorage）
+    pub fn write(_location: &StorageLocation, key: &str, contents: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or_else(|| "localStorage is unavailable".to_string())?
+            .set_item(&storage_key(key), contents)
+            .map_err(|err| format!("{err:?}"))
+    }
+
+    /// 读取指定键对应的二进制内容（Base64文本）
+    pub fn read_bytes(location: &StorageLocation, key: &str) -> Option<Vec<u8>> {
+        base64::engine::general_purpose::STANDARD
+            .decode(read(location, key)?)
+            .ok()
+    }
+
+    /// 写入指定键对应的二进制内容（Base64文本）
+    pub fn write_bytes(
+        location: &StorageLocation,
+        key: &str,
+        contents: &[u8],
+    ) -> Result<(), String> {
+        write(
+            location,
+            key,
+            &base64::engine::general_purpose::STANDARD.encode(contents),
+        )
+    }
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    /// 为键添加前缀，避免与同域名下的其他页面冲突
+    fn storage_key(key: &str) -> String {
+        format!("reversi/{key}")
+    }
+}
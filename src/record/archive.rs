@@ -0,0 +1,247 @@
+// 历史对局存档 - 保存最近完成的若干局对局记录
+//
+// 每局结束后把初始局面、走法记录和结果写入存档，超出上限时丢弃最旧的对局。
+// 存档以带版本号的二进制格式（见format模块）通过偏好设置的存储后端持久化，可以随时用命令日志重新回放。
+// 旧版本保存的RON存档在第一次读取时迁移为二进制格式
+
+use super::{format, GameRecord, RecordFormatError, RecordedMove};
+use crate::{
+    ai::{endgame::GameOutcome, AiDifficulty},
+    game::{Board, CommandError, GameLog, MoveCommand, PlayerColor},
+    localization::{DateTime, TextKey},
+    preferences::storage::StorageLocation,
+    series::{MatchSeries, SeriesGame},
+    stats::GameFinishedEvent,
+    ui::ToastEvent,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 历史对局存档在存储中的键名
+pub const ARCHIVE_KEY: &str = "game_archive.bin";
+
+/// 旧版本RON格式存档的键名，只用于迁移
+const LEGACY_ARCHIVE_KEY: &str = "game_archive.ron";
+
+/// 存档保留的对局数
+pub const ARCHIVE_LIMIT: usize = 20;
+
+/// 一局已完成的对局
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ArchivedGame {
+    /// 结束时间（Unix时间戳，秒）
+    pub finished_at: u64,
+
+    /// 玩家视角的结果
+    pub outcome: GameOutcome,
+
+    /// AI难度
+    pub difficulty: AiDifficulty,
+
+    /// 终局黑棋子数
+    pub black: u32,
+
+    /// 终局白棋子数
+    pub white: u32,
+
+    /// 初始局面
+    pub initial: Board,
+
+    /// 先行的玩家
+    pub first_player: PlayerColor,
+
+    /// 按顺序记录的走法和思考时间
+    pub moves: Vec<RecordedMove>,
+
+    /// 多局比赛中的第几局及当时的比分，单局对局为None
+    #[serde(default)]
+    pub series: Option<SeriesGame>,
+}
+
+impl ArchivedGame {
+    /// 从结束的对局创建存档条目
+    pub fn new(
+        log: &GameLog,
+        record: &GameRecord,
+        outcome: GameOutcome,
+        difficulty: AiDifficulty,
+        finished_at: u64,
+    ) -> Self {
+        let board = log.board();
+        Self {
+            finished_at,
+            outcome,
+            difficulty,
+            black: board.count_pieces(PlayerColor::Black),
+            white: board.count_pieces(PlayerColor::White),
+            initial: log.initial(),
+            first_player: log.first_player(),
+            moves: record.moves.clone(),
+            series: None,
+        }
+    }
+
+    /// 标记为多局比赛中的一局
+    pub fn with_series(mut self, series: Option<SeriesGame>) -> Self {
+        self.series = series;
+        self
+    }
+
+    /// 按走法记录重新回放出命令日志，存档被修改导致走法不合法时返回错误
+    pub fn replay(&self) -> Result<GameLog, CommandError> {
+        let commands: Vec<MoveCommand> = self
+            .moves
+            .iter()
+            .zip(0..)
+            .map(|(recorded, sequence)| MoveCommand {
+                sequence,
+                player: recorded.player,
+                action: recorded.action,
+            })
+            .collect();
+        GameLog::replay(self.initial, self.first_player, &commands)
+    }
+}
+
+/// 历史对局存档资源，最新的对局在前
+///
+/// 新增字段需要提供默认值，以兼容旧版本保存的文件
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct GameArchive {
+    pub games: Vec<ArchivedGame>,
+
+    /// 存储中的存档由不兼容的新版本写入，本次运行不覆盖它
+    #[serde(skip)]
+    read_only: bool,
+}
+
+impl GameArchive {
+    /// 从存储中读取存档，没有二进制存档时迁移旧版本的RON存档
+    pub fn load(storage: &StorageLocation) -> Self {
+        let binary = storage.read_bytes(ARCHIVE_KEY);
+        let legacy = binary
+            .is_none()
+            .then(|| storage.read(LEGACY_ARCHIVE_KEY))
+            .flatten();
+        let archive = Self::restore(binary.as_deref(), legacy.as_deref());
+        if legacy.is_some() {
+            if let Err(err) = archive.save(storage) {
+                warn!("Failed to migrate game archive: {err}");
+            }
+        }
+        archive
+    }
+
+    /// 由存储中的内容恢复存档：优先使用二进制存档，其次是旧版本的RON存档。
+    /// 解析失败时为空；二进制存档来自不兼容的新版本时同样为空，并且不再保存
+    pub fn restore(binary: Option<&[u8]>, legacy: Option<&str>) -> Self {
+        match binary.map(Self::from_bytes) {
+            Some(Ok(archive)) => archive,
+            Some(Err(err)) => {
+                warn!("Failed to read game archive: {err}");
+                Self {
+                    read_only: matches!(err, RecordFormatError::UnsupportedVersion { .. }),
+                    ..default()
+                }
+            }
+            None => legacy
+                .and_then(|contents| ron::from_str(contents).ok())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// 解码二进制存档
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RecordFormatError> {
+        format::decode(bytes)
+    }
+
+    /// 编码为二进制存档
+    pub fn to_bytes(&self) -> Result<Vec<u8>, RecordFormatError> {
+        format::encode(self)
+    }
+
+    /// 存储中的存档是否由不兼容的新版本写入
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// 将存档写入存储
+    pub fn save(&self, storage: &StorageLocation) -> Result<(), String> {
+        if self.read_only {
+            return Err("game archive was written by a newer version".to_string());
+        }
+        let bytes = self.to_bytes().map_err(|err| err.to_string())?;
+        storage.write_bytes(ARCHIVE_KEY, &bytes)
+    }
+
+    /// 添加一局对局，超出上限时丢弃最旧的对局
+    pub fn push(&mut self, game: ArchivedGame) {
+        self.games.insert(0, game);
+        self.games.truncate(ARCHIVE_LIMIT);
+    }
+}
+
+/// 对局结束时把本局记录加入存档并保存
+pub fn archive_finished_games(
+    mut finished_events: EventReader<GameFinishedEvent>,
+    log_query: Query<&GameLog>,
+    record: Res<GameRecord>,
+    series: Res<MatchSeries>,
+    mut archive: ResMut<GameArchive>,
+    storage: Res<StorageLocation>,
+    mut toast_events: EventWriter<ToastEvent>,
+) {
+    for event in finished_events.read() {
+        let Ok(log) = log_query.single() else {
+            continue;
+        };
+        // 比赛结果已经先记录（record_match_results），这里取最近一局的比分
+        archive.push(
+            ArchivedGame::new(
+                log,
+                &record,
+                event.outcome,
+                event.difficulty,
+                now_unix_secs(),
+            )
+            .with_series(series.last_game()),
+        );
+
+        match archive.save(&storage) {
+            Ok(()) => {
+                toast_events.write(ToastEvent::success(TextKey::ToastGameSaved));
+            }
+            Err(err) => {
+                warn!("Failed to save game archive: {err}");
+                toast_events.write(ToastEvent::save_failed(TextKey::SaveDataArchive));
+            }
+        }
+    }
+}
+
+/// 格式化Unix时间戳为"2024-05-01 13:45"（UTC），界面中按语言显示的日期见LocaleFormat::timestamp
+pub fn format_timestamp(secs: u64) -> String {
+    let DateTime {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+    } = DateTime::from_unix_secs(secs);
+    format!("{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}")
+}
+
+/// 当前的Unix时间戳（秒）
+#[cfg(not(target_arch = "wasm32"))]
+fn now_unix_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// 当前的Unix时间戳（秒），浏览器中不能使用SystemTime
+#[cfg(target_arch = "wasm32")]
+fn now_unix_secs() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
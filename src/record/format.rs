@@ -0,0 +1,125 @@
+// 存档二进制格式 - 带版本号的紧凑存档格式，用于历史对局存档和以后的云同步
+//
+// 布局：4字节魔数"RVSA"、2字节格式版本、2字节最低读取版本（均为小端），之后是CBOR编码的数据。
+// CBOR按字段名保存结构体，新版本增加的字段会被旧版本忽略，因此只要写入方声明的最低读取版本
+// 不高于本程序支持的版本就可以读取；格式发生不兼容的变化时提高最低读取版本，旧版本拒绝读取，
+// 不会把看不懂的数据当作空存档覆盖。第一版之前的RON文本存档由GameArchive::load迁移
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt;
+
+/// 文件开头的魔数
+pub const RECORD_MAGIC: [u8; 4] = *b"RVSA";
+
+/// 本程序写入的格式版本
+pub const FORMAT_VERSION: u16 = 1;
+
+/// 读取本程序写入的数据所需的最低版本
+pub const MIN_READER_VERSION: u16 = 1;
+
+/// 文件头长度：魔数和两个版本号
+const HEADER_LEN: usize = 8;
+
+/// 文件头中的版本信息
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordHeader {
+    /// 写入时的格式版本
+    pub version: u16,
+    /// 读取所需的最低版本
+    pub min_reader_version: u16,
+}
+
+impl RecordHeader {
+    /// 本程序写入的文件头
+    pub const CURRENT: Self = Self {
+        version: FORMAT_VERSION,
+        min_reader_version: MIN_READER_VERSION,
+    };
+
+    /// 解析文件头，不校验版本
+    pub fn parse(bytes: &[u8]) -> Result<Self, RecordFormatError> {
+        let header = bytes
+            .get(..HEADER_LEN)
+            .ok_or(RecordFormatError::Truncated)?;
+        if header[..4] != RECORD_MAGIC {
+            return Err(RecordFormatError::BadMagic);
+        }
+        Ok(Self {
+            version: u16::from_le_bytes([header[4], header[5]]),
+            min_reader_version: u16::from_le_bytes([header[6], header[7]]),
+        })
+    }
+
+    /// 写成字节
+    pub fn to_bytes(self) -> [u8; HEADER_LEN] {
+        let [v0, v1] = self.version.to_le_bytes();
+        let [m0, m1] = self.min_reader_version.to_le_bytes();
+        let [a, b, c, d] = RECORD_MAGIC;
+        [a, b, c, d, v0, v1, m0, m1]
+    }
+
+    /// 本程序能否读取该文件
+    pub fn is_readable(&self) -> bool {
+        self.min_reader_version <= FORMAT_VERSION
+    }
+}
+
+/// 编码或解码失败的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordFormatError {
+    /// 数据不完整
+    Truncated,
+
+    /// 不是存档格式的数据
+    BadMagic,
+
+    /// 由更新的版本写入、格式不兼容
+    UnsupportedVersion { required: u16, supported: u16 },
+
+    /// 编码失败
+    Encode(String),
+
+    /// CBOR数据损坏或与存档结构不符
+    Decode(String),
+}
+
+impl fmt::Display for RecordFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "record data is truncated"),
+            Self::BadMagic => write!(f, "not a reversi record"),
+            Self::UnsupportedVersion {
+                required,
+                supported,
+            } => write!(
+                f,
+                "record needs format version {required}, this build reads up to {supported}"
+            ),
+            Self::Encode(err) => write!(f, "failed to encode record: {err}"),
+            Self::Decode(err) => write!(f, "failed to decode record: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for RecordFormatError {}
+
+/// 编码为带文件头的二进制数据
+pub fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, RecordFormatError> {
+    let mut bytes = RecordHeader::CURRENT.to_bytes().to_vec();
+    ciborium::into_writer(value, &mut bytes)
+        .map_err(|err| RecordFormatError::Encode(err.to_string()))?;
+    Ok(bytes)
+}
+
+/// 校验文件头后解码，旧版本写入的数据中缺少的字段取默认值
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, RecordFormatError> {
+    let header = RecordHeader::parse(bytes)?;
+    if !header.is_readable() {
+        return Err(RecordFormatError::UnsupportedVersion {
+            required: header.min_reader_version,
+            supported: FORMAT_VERSION,
+        });
+    }
+    ciborium::from_reader(&bytes[HEADER_LEN..])
+        .map_err(|err| RecordFormatError::Decode(err.to_string()))
+}
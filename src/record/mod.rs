@@ -0,0 +1,155 @@
+// 对局记录模块 - 记录每一步棋和双方的思考时间
+//
+// 走棋记录由命令日志推导，每当日志中出现新命令时，
+// 记下从上一步结束到这一步之间经过的时间；对局结束后记录保存到历史对局存档，
+// 也可以导出为文本棋谱或带评注的分析报告（见report）
+
+pub mod archive;
+pub mod format;
+pub mod report;
+
+pub use archive::*;
+pub use format::{RecordFormatError, RecordHeader, FORMAT_VERSION};
+pub use report::{board_diagram, board_svg, AnalysisReport, AnnotatedMove, ReportFormat};
+
+use crate::{
+    game::{Board, GameLog, MoveAction, PlayerColor},
+    preferences::storage::StorageLocation,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 导出棋谱在存储中的键名
+const TRANSCRIPT_KEY: &str = "last_game.txt";
+
+/// 一步棋的记录
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMove {
+    /// 走棋的玩家
+    pub player: PlayerColor,
+
+    /// 走棋动作
+    pub action: MoveAction,
+
+    /// 思考时间
+    pub think_time: Duration,
+}
+
+/// 当前对局的记录
+#[derive(Resource, Debug, Default)]
+pub struct GameRecord {
+    /// 按顺序记录的走法
+    pub moves: Vec<RecordedMove>,
+
+    /// 当前回合开始的时间点
+    turn_started: Duration,
+}
+
+impl GameRecord {
+    /// 玩家落子（不含跳过回合）的记录
+    fn placed_moves(&self, player: PlayerColor) -> impl Iterator<Item = &RecordedMove> {
+        self.moves.iter().filter(move |recorded| {
+            recorded.player == player && matches!(recorded.action, MoveAction::Place(_))
+        })
+    }
+
+    /// 玩家的总思考时间
+    pub fn total_time(&self, player: PlayerColor) -> Duration {
+        self.placed_moves(player)
+            .map(|recorded| recorded.think_time)
+            .sum()
+    }
+
+    /// 玩家每步的平均思考时间，没有落子时返回None
+    pub fn average_time(&self, player: PlayerColor) -> Option<Duration> {
+        let count = self.placed_moves(player).count() as u32;
+        (count > 0).then(|| self.total_time(player) / count)
+    }
+
+    /// 生成文本棋谱，每步的思考时间以注释形式写在花括号中
+    pub fn to_transcript(&self) -> String {
+        let mut transcript = String::from("# Reversi\n");
+        for (index, recorded) in self.moves.iter().enumerate() {
+            let color = match recorded.player {
+                PlayerColor::Black => "Black",
+                PlayerColor::White => "White",
+            };
+            let action = match recorded.action {
+                MoveAction::Place(position) => Board::position_to_notation(position),
+                MoveAction::Pass => "pass".to_string(),
+            };
+            transcript.push_str(&format!(
+                "{}. {color} {action} {{{}}}\n",
+                index + 1,
+                format_duration(recorded.think_time)
+            ));
+        }
+
+        for player in [PlayerColor::Black, PlayerColor::White] {
+            let average = self
+                .average_time(player)
+                .map_or_else(|| "-".to_string(), format_duration);
+            transcript.push_str(&format!(
+                "# {player:?}: total {}, average {average}\n",
+                format_duration(self.total_time(player))
+            ));
+        }
+        transcript
+    }
+
+    /// 将棋谱导出到存储
+    pub fn export_transcript(&self, storage: &StorageLocation) -> Result<(), String> {
+        storage.write(TRANSCRIPT_KEY, &self.to_transcript())
+    }
+}
+
+/// 格式化时长，一分钟以内显示为"4.2s"，否则显示为"1:23"
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f32();
+    if secs < 60.0 {
+        format!("{secs:.1}s")
+    } else {
+        let total = duration.as_secs();
+        format!("{}:{:02}", total / 60, total % 60)
+    }
+}
+
+/// 根据命令日志记录新走法的思考时间
+pub fn record_move_times(
+    log_query: Query<Ref<GameLog>>,
+    mut record: ResMut<GameRecord>,
+    time: Res<Time<Real>>,
+) {
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+    let now = time.elapsed();
+
+    // 新对局开始，重新计时
+    if log.is_added() {
+        record.moves.clear();
+        record.turn_started = now;
+    }
+
+    if !log.is_changed() {
+        return;
+    }
+
+    // 悔棋后丢弃被撤销的走法，从悔棋时重新计时
+    if record.moves.len() > log.commands().len() {
+        record.moves.truncate(log.commands().len());
+        record.turn_started = now;
+    }
+
+    // 同一帧内的自动跳过回合不计时间
+    for command in log.commands().iter().skip(record.moves.len()) {
+        let think_time = now.saturating_sub(record.turn_started);
+        record.moves.push(RecordedMove {
+            player: command.player,
+            action: command.action,
+            think_time,
+        });
+        record.turn_started = now;
+    }
+}
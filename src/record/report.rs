@@ -0,0 +1,337 @@
+// 分析报告 - 对局结束后生成可以分享的Markdown或HTML报告
+//
+// 报告只由命令日志和对局记录生成，与界面无关：逐步列出走法、思考时间和评注，
+// 用开局练习相同的两层搜索（见practice::move_quality）与最佳走法比较，
+// 差距达到BAD_MARGIN的走法标为败着"??"，达到DUBIOUS_MARGIN的标为疑问手"?!"，并写出更好的走法。
+// 报告末尾附终局棋盘图：Markdown中为Unicode字符画，HTML中为内嵌的SVG图片
+
+use super::{format_duration, GameRecord};
+use crate::{
+    ai::Evaluator,
+    game::{Board, GameLog, MoveAction, PlayerColor},
+    practice::{score_moves, MoveQuality},
+    preferences::storage::StorageLocation,
+};
+use std::time::Duration;
+
+/// 报告的文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 2] = [ReportFormat::Markdown, ReportFormat::Html];
+
+    /// 导出报告在存储中的键名
+    pub fn storage_key(self) -> &'static str {
+        match self {
+            Self::Markdown => "analysis_report.md",
+            Self::Html => "analysis_report.html",
+        }
+    }
+}
+
+/// 报告中的一步棋
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotatedMove {
+    /// 从1开始的步数
+    pub number: usize,
+    pub player: PlayerColor,
+    pub action: MoveAction,
+    /// 思考时间，对局记录与日志不一致时为None
+    pub think_time: Option<Duration>,
+    /// 走法质量，跳过回合和不在合法走法中的落子（自由落子）为None
+    pub quality: Option<MoveQuality>,
+    /// 比最佳走法少的评估分
+    pub loss: i32,
+    /// 不是好棋时更好的走法
+    pub better: Option<u8>,
+}
+
+impl AnnotatedMove {
+    pub fn is_blunder(&self) -> bool {
+        self.quality == Some(MoveQuality::Bad)
+    }
+
+    /// 走法后面的评注符号
+    pub fn mark(&self) -> &'static str {
+        match self.quality {
+            Some(MoveQuality::Dubious) => "?!",
+            Some(MoveQuality::Bad) => "??",
+            _ => "",
+        }
+    }
+
+    /// 记法加评注符号，例如"B2??"或"pass"
+    fn move_text(&self) -> String {
+        match self.action {
+            MoveAction::Place(position) => {
+                format!("{}{}", Board::position_to_notation(position), self.mark())
+            }
+            MoveAction::Pass => "pass".to_string(),
+        }
+    }
+
+    /// 评注文字，好棋和跳过回合为空
+    fn note(&self) -> String {
+        let label = match self.quality {
+            Some(MoveQuality::Dubious) => "Dubious",
+            Some(MoveQuality::Bad) => "Blunder",
+            _ => return String::new(),
+        };
+        match self.better {
+            Some(better) => format!(
+                "{label}: {} was better by {}",
+                Board::position_to_notation(better),
+                self.loss
+            ),
+            None => label.to_string(),
+        }
+    }
+
+    fn time_text(&self) -> String {
+        self.think_time
+            .map_or_else(|| "-".to_string(), format_duration)
+    }
+}
+
+/// 一局棋的分析报告
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisReport {
+    pub moves: Vec<AnnotatedMove>,
+    pub final_board: Board,
+    pub winner: Option<PlayerColor>,
+}
+
+impl AnalysisReport {
+    /// 重放命令日志，为每一步落子打分；思考时间取自对局记录中对应的走法
+    pub fn build(log: &GameLog, record: &GameRecord, eval: &(impl Evaluator + ?Sized)) -> Self {
+        let mut board = log.initial();
+        let moves = log
+            .commands()
+            .iter()
+            .enumerate()
+            .map(|(index, command)| {
+                let think_time = record
+                    .moves
+                    .get(index)
+                    .filter(|recorded| {
+                        recorded.player == command.player && recorded.action == command.action
+                    })
+                    .map(|recorded| recorded.think_time);
+                let mut annotated = AnnotatedMove {
+                    number: index + 1,
+                    player: command.player,
+                    action: command.action,
+                    think_time,
+                    quality: None,
+                    loss: 0,
+                    better: None,
+                };
+
+                if let MoveAction::Place(position) = command.action {
+                    let scores = score_moves(&board, command.player, eval);
+                    let played = scores.iter().find(|&&(candidate, _)| candidate == position);
+                    let best = scores.iter().max_by_key(|&&(_, score)| score);
+                    if let (Some(&(_, played)), Some(&(best_position, best))) = (played, best) {
+                        let quality = MoveQuality::from_loss(best - played);
+                        annotated.quality = Some(quality);
+                        annotated.loss = best - played;
+                        annotated.better = (quality != MoveQuality::Good).then_some(best_position);
+                    }
+                    board.make_move(position, command.player);
+                }
+                annotated
+            })
+            .collect();
+
+        Self {
+            moves,
+            final_board: log.board(),
+            winner: log.winner(),
+        }
+    }
+
+    /// 所有败着
+    pub fn blunders(&self) -> impl Iterator<Item = &AnnotatedMove> {
+        self.moves.iter().filter(|annotated| annotated.is_blunder())
+    }
+
+    /// 按格式生成报告文本
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.to_markdown(),
+            ReportFormat::Html => self.to_html(),
+        }
+    }
+
+    /// 胜负和比分，例如"Black wins 40-24"
+    fn result_line(&self) -> String {
+        let black = self.final_board.count_pieces(PlayerColor::Black);
+        let white = self.final_board.count_pieces(PlayerColor::White);
+        match self.winner {
+            Some(winner) => format!("{winner:?} wins {black}-{white}"),
+            None => format!("Draw {black}-{white}"),
+        }
+    }
+
+    /// 各方败着数量，例如"2 (Black 1, White 1)"
+    fn blunder_summary(&self) -> String {
+        let count = |player| {
+            self.blunders()
+                .filter(|annotated| annotated.player == player)
+                .count()
+        };
+        format!(
+            "{} (Black {}, White {})",
+            self.blunders().count(),
+            count(PlayerColor::Black),
+            count(PlayerColor::White)
+        )
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("# Reversi analysis report\n\n");
+        markdown.push_str(&format!("- Result: {}\n", self.result_line()));
+        markdown.push_str(&format!("- Blunders: {}\n", self.blunder_summary()));
+
+        markdown
+            .push_str("\n## Moves\n\n| # | Player | Move | Time | Note |\n|---|---|---|---|---|\n");
+        for annotated in &self.moves {
+            let note = annotated.note();
+            let note = if annotated.is_blunder() {
+                format!("**{note}**")
+            } else {
+                note
+            };
+            markdown.push_str(&format!(
+                "| {} | {:?} | {} | {} | {note} |\n",
+                annotated.number,
+                annotated.player,
+                annotated.move_text(),
+                annotated.time_text()
+            ));
+        }
+
+        if self.blunders().next().is_some() {
+            markdown.push_str("\n## Blunders\n\n");
+            for annotated in self.blunders() {
+                markdown.push_str(&format!(
+                    "- {}. {:?} {} - {}\n",
+                    annotated.number,
+                    annotated.player,
+                    annotated.move_text(),
+                    annotated.note()
+                ));
+            }
+        }
+
+        markdown.push_str("\n## Final position\n\n```text\n");
+        markdown.push_str(&board_diagram(&self.final_board));
+        markdown.push_str("```\n");
+        markdown
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(concat!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n",
+            "<title>Reversi analysis report</title>\n<style>\n",
+            "body { font-family: sans-serif; margin: 2em; }\n",
+            "table { border-collapse: collapse; }\n",
+            "th, td { border: 1px solid #ccc; padding: 2px 8px; text-align: left; }\n",
+            "tr.dubious { background: #fff4cc; }\n",
+            "tr.blunder { background: #ffd6d1; font-weight: bold; }\n",
+            "</style>\n</head>\n<body>\n<h1>Reversi analysis report</h1>\n",
+        ));
+        html.push_str(&format!(
+            "<p>Result: {}<br>Blunders: {}</p>\n",
+            self.result_line(),
+            self.blunder_summary()
+        ));
+
+        html.push_str("<h2>Moves</h2>\n<table>\n<tr><th>#</th><th>Player</th><th>Move</th><th>Time</th><th>Note</th></tr>\n");
+        for annotated in &self.moves {
+            let class = match annotated.quality {
+                Some(MoveQuality::Dubious) => " class=\"dubious\"",
+                Some(MoveQuality::Bad) => " class=\"blunder\"",
+                _ => "",
+            };
+            html.push_str(&format!(
+                "<tr{class}><td>{}</td><td>{:?}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                annotated.number,
+                annotated.player,
+                annotated.move_text(),
+                annotated.time_text(),
+                annotated.note()
+            ));
+        }
+        html.push_str("</table>\n<h2>Final position</h2>\n");
+        html.push_str(&board_svg(&self.final_board));
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// 将Markdown和HTML两种格式的报告都导出到存储
+    pub fn export(&self, storage: &StorageLocation) -> Result<(), String> {
+        for format in ReportFormat::ALL {
+            storage.write(format.storage_key(), &self.render(format))?;
+        }
+        Ok(())
+    }
+}
+
+/// 棋盘的Unicode字符画：黑子●、白子○、空格·，带列字母和行号
+pub fn board_diagram(board: &Board) -> String {
+    let mut diagram = String::from("  A B C D E F G H\n");
+    for row in 0..8u8 {
+        diagram.push_str(&(row + 1).to_string());
+        for col in 0..8u8 {
+            let symbol = match board.get_piece(row * 8 + col) {
+                Some(PlayerColor::Black) => '●',
+                Some(PlayerColor::White) => '○',
+                None => '·',
+            };
+            diagram.push(' ');
+            diagram.push(symbol);
+        }
+        diagram.push('\n');
+    }
+    diagram
+}
+
+/// 棋盘的SVG图片，可以直接内嵌在HTML中
+pub fn board_svg(board: &Board) -> String {
+    const CELL: u32 = 40;
+    let size = CELL * 8;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" viewBox=\"0 0 {size} {size}\">\n\
+         <rect width=\"{size}\" height=\"{size}\" fill=\"#2e7d32\"/>\n"
+    );
+    for line in 1..8 {
+        let offset = line * CELL;
+        svg.push_str(&format!(
+            "<line x1=\"{offset}\" y1=\"0\" x2=\"{offset}\" y2=\"{size}\" stroke=\"#1b5e20\"/>\n\
+             <line x1=\"0\" y1=\"{offset}\" x2=\"{size}\" y2=\"{offset}\" stroke=\"#1b5e20\"/>\n"
+        ));
+    }
+    for position in 0..64u8 {
+        let Some(color) = board.get_piece(position) else {
+            continue;
+        };
+        let (row, col) = Board::position_to_coords(position);
+        let fill = match color {
+            PlayerColor::Black => "#111",
+            PlayerColor::White => "#f5f5f5",
+        };
+        svg.push_str(&format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{fill}\"/>\n",
+            col as u32 * CELL + CELL / 2,
+            row as u32 * CELL + CELL / 2,
+            CELL * 2 / 5
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
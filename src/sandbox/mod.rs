@@ -0,0 +1,22 @@
+// 自由落子沙盒 - 不检查落子是否合法的演示模式，与复盘分析分开
+//
+// 任意空格都可以落子，照常翻转被夹住的对方棋子，用于向初学者演示"如果下在这里会怎样"。
+// 沙盒中没有AI，玩家轮流替双方落子，棋盘下满时结束；沙盒对局不计入战绩和历史对局
+
+use crate::game::PlacementRule;
+use bevy::prelude::*;
+
+/// 难度选择界面中是否选择了沙盒，选择任一难度开始普通对局时关闭
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SandboxMode(pub bool);
+
+impl SandboxMode {
+    /// 对局使用的落子规则
+    pub fn placement_rule(&self) -> PlacementRule {
+        if self.0 {
+            PlacementRule::Free
+        } else {
+            PlacementRule::Standard
+        }
+    }
+}
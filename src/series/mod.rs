@@ -0,0 +1,211 @@
+// 多局比赛 - 三局两胜或五局三胜，每局交换执子颜色
+//
+// 赛制在设置面板中选择（RulesConfig::match_format），从难度选择界面开始对局时开始新的比赛，
+// 每局结束后记录玩家视角的结果，一方提前锁定胜局或打满局数时比赛结束，之后再开局开始新的比赛。
+// 第一局玩家执黑，之后每局交换颜色；开局练习和沙盒不计入比赛，玩家固定执黑
+
+use crate::{
+    ai::endgame::GameOutcome, game::PlayerColor, practice::SelectedOpening,
+    preferences::Preferences, sandbox::SandboxMode, stats::GameFinishedEvent,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 比赛的赛制
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchFormat {
+    /// 单局，不显示比分
+    #[default]
+    Single,
+    /// 三局两胜
+    BestOf3,
+    /// 五局三胜
+    BestOf5,
+}
+
+impl MatchFormat {
+    /// 最多进行的局数
+    pub fn games(self) -> u32 {
+        match self {
+            Self::Single => 1,
+            Self::BestOf3 => 3,
+            Self::BestOf5 => 5,
+        }
+    }
+
+    /// 锁定比赛胜利需要赢的局数
+    pub fn wins_needed(self) -> u32 {
+        self.games() / 2 + 1
+    }
+
+    /// 设置面板中依次切换的赛制
+    pub fn next(self) -> Self {
+        match self {
+            Self::Single => Self::BestOf3,
+            Self::BestOf3 => Self::BestOf5,
+            Self::BestOf5 => Self::Single,
+        }
+    }
+}
+
+/// 进行中的比赛，记录已完成各局的玩家视角结果和玩家本局执子的颜色
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct MatchSeries {
+    pub format: MatchFormat,
+    pub results: Vec<GameOutcome>,
+    /// 玩家本局执子的颜色，准备新的一局时确定，本局结束后保持不变
+    pub player_color: PlayerColor,
+}
+
+impl Default for MatchSeries {
+    fn default() -> Self {
+        Self::new(MatchFormat::default())
+    }
+}
+
+/// 比赛中一局的存档信息，随历史对局一起保存
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeriesGame {
+    /// 赛制
+    pub format: MatchFormat,
+    /// 本局是第几局，从1开始
+    pub game: u32,
+    /// 玩家本局执子的颜色
+    pub player_color: PlayerColor,
+    /// 本局结束后玩家赢的局数
+    pub wins: u32,
+    /// 本局结束后AI赢的局数
+    pub losses: u32,
+}
+
+impl MatchSeries {
+    pub fn new(format: MatchFormat) -> Self {
+        Self {
+            format,
+            results: Vec::new(),
+            player_color: PlayerColor::Black,
+        }
+    }
+
+    /// 是否是多局比赛
+    pub fn is_match(&self) -> bool {
+        self.format.games() > 1
+    }
+
+    /// 已完成的局数
+    pub fn played(&self) -> u32 {
+        self.results.len() as u32
+    }
+
+    fn count(&self, outcome: GameOutcome) -> u32 {
+        self.results.iter().filter(|r| **r == outcome).count() as u32
+    }
+
+    /// 玩家赢的局数
+    pub fn wins(&self) -> u32 {
+        self.count(GameOutcome::Win)
+    }
+
+    /// AI赢的局数
+    pub fn losses(&self) -> u32 {
+        self.count(GameOutcome::Loss)
+    }
+
+    /// 比赛是否已经结束：一方领先的局数超过剩余局数，或者已经打满
+    pub fn is_decided(&self) -> bool {
+        let remaining = self.format.games().saturating_sub(self.played());
+        remaining == 0
+            || self.wins() > self.losses() + remaining
+            || self.losses() > self.wins() + remaining
+    }
+
+    /// 比赛结束后玩家视角的结果，比赛未结束时为None
+    pub fn outcome(&self) -> Option<GameOutcome> {
+        if !self.is_decided() {
+            return None;
+        }
+        Some(match self.wins().cmp(&self.losses()) {
+            std::cmp::Ordering::Greater => GameOutcome::Win,
+            std::cmp::Ordering::Less => GameOutcome::Loss,
+            std::cmp::Ordering::Equal => GameOutcome::Draw,
+        })
+    }
+
+    /// 当前（或刚结束的）局是第几局，从1开始
+    pub fn current_game(&self) -> u32 {
+        if self.is_decided() {
+            self.played().max(1)
+        } else {
+            self.played() + 1
+        }
+    }
+
+    /// 第几局玩家执子的颜色：第一局执黑，之后每局交换
+    fn color_for_game(game: u32) -> PlayerColor {
+        if game % 2 == 1 {
+            PlayerColor::Black
+        } else {
+            PlayerColor::White
+        }
+    }
+
+    /// 记录一局的结果，比赛已经结束时忽略
+    pub fn record(&mut self, outcome: GameOutcome) {
+        if !self.is_decided() {
+            self.results.push(outcome);
+        }
+    }
+
+    /// 开始新的一局前调用：还没有开始或上一场比赛已经结束时按赛制开始新的比赛，
+    /// 并确定玩家本局执子的颜色
+    pub fn prepare(&mut self, format: MatchFormat) {
+        if self.results.is_empty() || self.is_decided() {
+            *self = Self::new(format);
+        }
+        self.player_color = Self::color_for_game(self.played() + 1);
+    }
+
+    /// 最近一局的存档信息，单局赛制时为None
+    pub fn last_game(&self) -> Option<SeriesGame> {
+        if !self.is_match() || self.results.is_empty() {
+            return None;
+        }
+        let game = self.played();
+        Some(SeriesGame {
+            format: self.format,
+            game,
+            player_color: Self::color_for_game(game),
+            wins: self.wins(),
+            losses: self.losses(),
+        })
+    }
+}
+
+/// 开始新对局前准备比赛：开局练习和沙盒只下单局，普通对局继续进行中的比赛或开始新的比赛
+pub fn prepare_match_series(
+    mut series: ResMut<MatchSeries>,
+    preferences: Res<Preferences>,
+    sandbox: Res<SandboxMode>,
+    selected_opening: Res<SelectedOpening>,
+) {
+    if sandbox.0 || selected_opening.0.is_some() {
+        *series = MatchSeries::new(MatchFormat::Single);
+    } else {
+        series.prepare(preferences.rules.match_format);
+    }
+}
+
+/// 对局结束时记录比赛中这一局的结果
+pub fn record_match_results(
+    mut finished_events: EventReader<GameFinishedEvent>,
+    mut series: ResMut<MatchSeries>,
+) {
+    for event in finished_events.read() {
+        series.record(event.outcome);
+    }
+}
+
+/// 回到难度选择界面时放弃进行中的比赛
+pub fn reset_match_series(mut series: ResMut<MatchSeries>) {
+    *series = MatchSeries::default();
+}
@@ -0,0 +1,142 @@
+// 游戏状态机 - 状态定义和集中的状态切换接口
+//
+// 状态切换图（切换名 => 起始状态 -> 目标状态）：
+//   FinishLoading  => LoadingScreen          -> LanguageSelection
+//   ChooseLanguage => LanguageSelection      -> DifficultySelection
+//   StartGame      => DifficultySelection    -> Playing
+//   EndGame        => Playing                -> GameOver
+//   Restart        => Playing | GameOver     -> Restarting
+//   FinishRestart  => Restarting             -> Playing
+//   BackToMenu     => Playing | GameOver     -> DifficultySelection
+//   Analyze        => GameOver               -> Analysis
+//   OpenReplay     => DifficultySelection    -> Replay
+//   Spectate       => DifficultySelection    -> Spectating
+//   LeaveAnalysis  => Analysis               -> GameOver
+//                     Replay | Spectating    -> DifficultySelection
+//
+// 系统不直接写入NextState，而是通过GameTransitions请求切换，
+// 不在切换图中的请求会被拒绝并记录警告；新增状态时只需扩展GameTransition。
+// Restart和BackToMenu离开对局界面时由切换本身清理界面实体（见plugin::gameplay），
+// 发出请求的系统不需要自己清理
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use std::fmt;
+
+/// 游戏状态
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[states(scoped_entities)]
+pub enum GameState {
+    /// 加载字体等资源
+    #[default]
+    LoadingScreen,
+    /// 首次选择界面语言
+    LanguageSelection,
+    /// 难度选择菜单
+    DifficultySelection,
+    /// 对局进行中
+    Playing,
+    /// 对局结束，显示结果
+    GameOver,
+    /// 重新开始的过渡状态，等待旧实体清理完成后回到Playing
+    Restarting,
+    /// 复盘分析，可以从任意一步尝试其他走法
+    Analysis,
+    /// 回放历史对局，与复盘分析使用同一个查看器
+    Replay,
+    /// 以观战者身份只读显示联机对局的命令流
+    Spectating,
+}
+
+/// 允许的状态切换
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameTransition {
+    /// 资源加载完成
+    FinishLoading,
+    /// 选择了界面语言
+    ChooseLanguage,
+    /// 选择难度后开始对局
+    StartGame,
+    /// 对局结束
+    EndGame,
+    /// 重新开始对局，进入Restarting时删除对局界面
+    Restart,
+    /// 重新开始的过渡结束
+    FinishRestart,
+    /// 返回难度选择菜单，切换时删除对局界面和对局
+    BackToMenu,
+    /// 对局结束后进入复盘分析
+    Analyze,
+    /// 从历史对局列表打开一局回放
+    OpenReplay,
+    /// 传输层连接服务器后开始观战
+    Spectate,
+    /// 结束复盘分析、回放或观战，回到进入前的界面
+    LeaveAnalysis,
+}
+
+/// 不在状态切换图中的请求
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionError {
+    pub from: GameState,
+    pub transition: GameTransition,
+}
+
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "transition {:?} is not allowed from {:?}",
+            self.transition, self.from
+        )
+    }
+}
+
+impl GameTransition {
+    /// 从指定状态执行该切换后到达的状态
+    pub fn target(self, from: GameState) -> Result<GameState, TransitionError> {
+        use GameState::*;
+
+        match (self, from) {
+            (Self::FinishLoading, LoadingScreen) => Ok(LanguageSelection),
+            (Self::ChooseLanguage, LanguageSelection) => Ok(DifficultySelection),
+            (Self::StartGame, DifficultySelection) => Ok(Playing),
+            (Self::EndGame, Playing) => Ok(GameOver),
+            (Self::Restart, Playing | GameOver) => Ok(Restarting),
+            (Self::FinishRestart, Restarting) => Ok(Playing),
+            (Self::BackToMenu, Playing | GameOver) => Ok(DifficultySelection),
+            (Self::Analyze, GameOver) => Ok(Analysis),
+            (Self::OpenReplay, DifficultySelection) => Ok(Replay),
+            (Self::Spectate, DifficultySelection) => Ok(Spectating),
+            (Self::LeaveAnalysis, Analysis) => Ok(GameOver),
+            (Self::LeaveAnalysis, Replay | Spectating) => Ok(DifficultySelection),
+            _ => Err(TransitionError {
+                from,
+                transition: self,
+            }),
+        }
+    }
+}
+
+/// 状态切换的系统参数，所有状态切换都通过它进行
+#[derive(SystemParam)]
+pub struct GameTransitions<'w> {
+    state: Res<'w, State<GameState>>,
+    next_state: ResMut<'w, NextState<GameState>>,
+}
+
+impl GameTransitions<'_> {
+    /// 当前状态
+    pub fn current(&self) -> GameState {
+        *self.state.get()
+    }
+
+    /// 请求状态切换，切换在本帧结束后生效，不允许的切换会被忽略并记录警告
+    ///
+    /// 同一帧内的多次合法请求以最后一次为准
+    pub fn request(&mut self, transition: GameTransition) {
+        match transition.target(self.current()) {
+            Ok(target) => self.next_state.set(target),
+            Err(err) => warn!("{err}"),
+        }
+    }
+}
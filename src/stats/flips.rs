@@ -0,0 +1,137 @@
+// 翻转统计 - 每局双方翻转的棋子总数、单步最多翻转数和占角数，以及跨对局累计的翻转记录
+//
+// 一局的统计由命令日志重放得到，与界面无关，结果界面直接显示双方的数据；
+// 对局结束后玩家一方的数据计入PlayerStats中的累计记录，打破单步翻转记录时发送通知提示
+
+use super::{GameFinishedEvent, PlayerStats};
+use crate::{
+    ai::AiPlayer,
+    game::{GameLog, MoveAction, PlayerColor, CORNER_MASK},
+    localization::{TextArg, TextArgs, TextKey},
+    ui::ToastEvent,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 一方在一局中的翻转统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SideFlipStats {
+    /// 翻转的棋子总数
+    pub discs_flipped: u32,
+    /// 单步最多翻转的棋子数
+    pub longest_chain: u32,
+    /// 占据的角数
+    pub corners: u32,
+}
+
+impl SideFlipStats {
+    /// 结果界面一行的参数：一方的名称、翻转总数、单步最多翻转数和占角数
+    pub fn text_args(&self, name: TextKey) -> TextArgs {
+        TextArgs(vec![
+            TextArg::Key(name),
+            TextArg::Number(self.discs_flipped.into()),
+            TextArg::Number(self.longest_chain.into()),
+            TextArg::Number(self.corners.into()),
+        ])
+    }
+}
+
+/// 一局中双方的翻转统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GameFlipStats {
+    pub black: SideFlipStats,
+    pub white: SideFlipStats,
+}
+
+impl GameFlipStats {
+    /// 从初始局面重放命令日志统计双方的翻转
+    pub fn from_log(log: &GameLog) -> Self {
+        let mut stats = Self::default();
+        let mut board = log.initial();
+        for command in log.commands() {
+            let MoveAction::Place(position) = command.action else {
+                continue;
+            };
+            let before = board;
+            board.make_move(position, command.player);
+            let flipped = before.diff(&board).flipped.count_ones();
+
+            let side = stats.side_mut(command.player);
+            side.discs_flipped += flipped;
+            side.longest_chain = side.longest_chain.max(flipped);
+            if CORNER_MASK & (1u64 << position) != 0 {
+                side.corners += 1;
+            }
+        }
+        stats
+    }
+
+    /// 一方的翻转统计
+    pub fn side(&self, player: PlayerColor) -> SideFlipStats {
+        match player {
+            PlayerColor::Black => self.black,
+            PlayerColor::White => self.white,
+        }
+    }
+
+    fn side_mut(&mut self, player: PlayerColor) -> &mut SideFlipStats {
+        match player {
+            PlayerColor::Black => &mut self.black,
+            PlayerColor::White => &mut self.white,
+        }
+    }
+}
+
+/// 玩家跨对局累计的翻转记录
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct FlipRecords {
+    /// 累计翻转的棋子数
+    pub total_flipped: u64,
+    /// 单步最多翻转的棋子数
+    pub best_chain: u32,
+    /// 累计占据的角数
+    pub corners_captured: u32,
+}
+
+impl FlipRecords {
+    /// 计入玩家一局的统计，返回是否打破了之前的单步翻转记录（第一次记录不算）
+    pub fn record(&mut self, side: SideFlipStats) -> bool {
+        let new_best = self.best_chain > 0 && side.longest_chain > self.best_chain;
+        self.total_flipped += u64::from(side.discs_flipped);
+        self.best_chain = self.best_chain.max(side.longest_chain);
+        self.corners_captured += side.corners;
+        new_best
+    }
+
+    /// 结果界面累计记录的参数：累计翻转数、单步最多翻转数和累计占角数
+    pub fn text_args(&self) -> TextArgs {
+        TextArgs(vec![
+            TextArg::Number(self.total_flipped),
+            TextArg::Number(self.best_chain.into()),
+            TextArg::Number(self.corners_captured.into()),
+        ])
+    }
+}
+
+/// 对局结束时把玩家一方的翻转统计计入累计记录，需要在保存玩家统计之前运行
+pub fn record_flip_stats(
+    mut finished_events: EventReader<GameFinishedEvent>,
+    log_query: Query<&GameLog>,
+    ai_query: Query<&AiPlayer>,
+    mut stats: ResMut<PlayerStats>,
+    mut toast_events: EventWriter<ToastEvent>,
+) {
+    for _ in finished_events.read() {
+        let (Ok(log), Ok(ai_player)) = (log_query.single(), ai_query.single()) else {
+            continue;
+        };
+        let side = GameFlipStats::from_log(log).side(ai_player.color.opposite());
+        if stats.flips.record(side) {
+            toast_events.write(
+                ToastEvent::success(TextKey::ToastFlipChainRecord)
+                    .with_args(vec![TextArg::Number(side.longest_chain.into())]),
+            );
+        }
+    }
+}
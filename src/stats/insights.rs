@@ -0,0 +1,220 @@
+// 使用统计 - 玩家自愿开启的本地统计，记录各难度的对局数、平均对局长度和功能使用次数
+//
+// 默认关闭，在设置中开启后才记录；数据只保存在本地的玩家统计中，不做任何网络请求，
+// 在难度选择界面的“使用统计”页面查看，也可以在该页面清空。
+// 功能使用通过观察已有的事件和界面状态得到，不需要在各功能中单独埋点
+
+use super::{DifficultyTier, GameFinishedEvent, PlayerStats};
+use crate::{
+    game::MoveAction,
+    history::HistoryPreview,
+    localization::TextKey,
+    practice::SelectedOpening,
+    preferences::{storage::StorageLocation, Preferences},
+    record::GameRecord,
+    sandbox::SandboxMode,
+    state::GameState,
+    ui::{ToastEvent, UiState},
+    undo::UndoRequestEvent,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Duration};
+
+/// 统计使用次数的功能
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum UsageFeature {
+    Undo,
+    DangerZones,
+    HistoryReview,
+    Analysis,
+    Replay,
+    Sandbox,
+    OpeningPractice,
+    Rules,
+}
+
+impl UsageFeature {
+    pub const ALL: [UsageFeature; 8] = [
+        UsageFeature::Undo,
+        UsageFeature::DangerZones,
+        UsageFeature::HistoryReview,
+        UsageFeature::Analysis,
+        UsageFeature::Replay,
+        UsageFeature::Sandbox,
+        UsageFeature::OpeningPractice,
+        UsageFeature::Rules,
+    ];
+}
+
+/// 本地使用统计，保存在玩家统计中
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct UsageInsights {
+    /// 各难度的对局数
+    pub games_by_difficulty: BTreeMap<DifficultyTier, u32>,
+
+    /// 记录的对局总数
+    pub games: u32,
+
+    /// 记录的对局中双方落子的总步数
+    pub total_moves: u64,
+
+    /// 记录的对局中双方思考时间的总和（秒）
+    pub total_play_secs: f64,
+
+    /// 各功能的使用次数
+    pub feature_usage: BTreeMap<UsageFeature, u32>,
+}
+
+impl UsageInsights {
+    /// 记录一局对局的难度、落子步数和用时
+    pub fn record_game(&mut self, difficulty: DifficultyTier, moves: u32, duration: Duration) {
+        *self.games_by_difficulty.entry(difficulty).or_default() += 1;
+        self.games += 1;
+        self.total_moves += u64::from(moves);
+        self.total_play_secs += duration.as_secs_f64();
+    }
+
+    /// 记录一次功能使用
+    pub fn record_feature(&mut self, feature: UsageFeature) {
+        *self.feature_usage.entry(feature).or_default() += 1;
+    }
+
+    /// 某个难度的对局数
+    pub fn games_at(&self, difficulty: DifficultyTier) -> u32 {
+        self.games_by_difficulty
+            .get(&difficulty)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// 某个功能的使用次数
+    pub fn uses_of(&self, feature: UsageFeature) -> u32 {
+        self.feature_usage.get(&feature).copied().unwrap_or(0)
+    }
+
+    /// 平均每局的落子步数（四舍五入）和用时，还没有记录对局时返回None
+    pub fn average_game(&self) -> Option<(u64, Duration)> {
+        (self.games > 0).then(|| {
+            let games = f64::from(self.games);
+            (
+                (self.total_moves as f64 / games).round() as u64,
+                Duration::from_secs_f64(self.total_play_secs / games),
+            )
+        })
+    }
+
+    /// 是否还没有任何记录
+    pub fn is_empty(&self) -> bool {
+        self.games == 0 && self.feature_usage.is_empty()
+    }
+}
+
+/// 开启使用统计时记录结束的对局，需要在保存玩家统计之前运行
+pub fn record_game_insights(
+    mut finished_events: EventReader<GameFinishedEvent>,
+    preferences: Res<Preferences>,
+    record: Res<GameRecord>,
+    mut stats: ResMut<PlayerStats>,
+) {
+    for event in finished_events.read() {
+        if !preferences.insights {
+            continue;
+        }
+        let moves = record
+            .moves
+            .iter()
+            .filter(|recorded| matches!(recorded.action, MoveAction::Place(_)))
+            .count() as u32;
+        let duration = record
+            .moves
+            .iter()
+            .map(|recorded| recorded.think_time)
+            .sum();
+        stats
+            .insights
+            .record_game(event.difficulty.into(), moves, duration);
+    }
+}
+
+/// 上一帧各功能是否处于使用中，进入使用状态时计一次
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeatureActivity {
+    danger_zones: bool,
+    history_review: bool,
+    analysis: bool,
+    replay: bool,
+    sandbox: bool,
+    opening_practice: bool,
+    rules: bool,
+}
+
+impl FeatureActivity {
+    /// 从未使用变为使用中的功能
+    fn started_since(self, previous: Self) -> impl Iterator<Item = UsageFeature> {
+        [
+            (
+                self.danger_zones && !previous.danger_zones,
+                UsageFeature::DangerZones,
+            ),
+            (
+                self.history_review && !previous.history_review,
+                UsageFeature::HistoryReview,
+            ),
+            (self.analysis && !previous.analysis, UsageFeature::Analysis),
+            (self.replay && !previous.replay, UsageFeature::Replay),
+            (self.sandbox && !previous.sandbox, UsageFeature::Sandbox),
+            (
+                self.opening_practice && !previous.opening_practice,
+                UsageFeature::OpeningPractice,
+            ),
+            (self.rules && !previous.rules, UsageFeature::Rules),
+        ]
+        .into_iter()
+        .filter_map(|(started, feature)| started.then_some(feature))
+    }
+}
+
+/// 开启使用统计时记录功能的使用次数，有新的记录时保存玩家统计
+pub fn track_feature_usage(
+    preferences: Res<Preferences>,
+    mut stats: ResMut<PlayerStats>,
+    storage: Res<StorageLocation>,
+    mut undo_events: EventReader<UndoRequestEvent>,
+    ui_state: Res<UiState>,
+    history_preview: Res<HistoryPreview>,
+    state: Res<State<GameState>>,
+    sandbox: Res<SandboxMode>,
+    selected_opening: Res<SelectedOpening>,
+    mut previous: Local<FeatureActivity>,
+    mut toast_events: EventWriter<ToastEvent>,
+) {
+    let undo_requests = undo_events.read().count();
+    let playing = *state.get() == GameState::Playing;
+    let activity = FeatureActivity {
+        danger_zones: ui_state.show_danger_zones,
+        history_review: history_preview.is_previewing(),
+        analysis: *state.get() == GameState::Analysis,
+        replay: *state.get() == GameState::Replay,
+        sandbox: playing && sandbox.0,
+        opening_practice: playing && selected_opening.0.is_some(),
+        rules: ui_state.show_rules,
+    };
+    let started: Vec<_> = activity.started_since(*previous).collect();
+    *previous = activity;
+
+    if !preferences.insights || (undo_requests == 0 && started.is_empty()) {
+        return;
+    }
+    for _ in 0..undo_requests {
+        stats.insights.record_feature(UsageFeature::Undo);
+    }
+    for feature in started {
+        stats.insights.record_feature(feature);
+    }
+    if let Err(err) = stats.save(&storage) {
+        warn!("Failed to save player stats: {err}");
+        toast_events.write(ToastEvent::save_failed(TextKey::SaveDataStats));
+    }
+}
@@ -0,0 +1,248 @@
+// 玩家统计模块 - 记录玩家的对局战绩
+//
+// 战绩以RON格式通过偏好设置的存储后端持久化，每局结束后立即保存。
+// 除总战绩外，还按难度记录连胜和胜差最大的一局，在难度选择按钮上显示；
+// 玩家开启使用统计后，同一份数据中还保存本地的使用统计（见insights）；
+// 翻转棋子数、单步最多翻转和占角数的累计记录见flips
+
+pub mod flips;
+pub mod insights;
+
+pub use flips::*;
+pub use insights::*;
+
+use crate::{
+    ai::{adaptive::AdaptiveState, endgame::GameOutcome, AiDifficulty},
+    localization::{TextArg, TextArgs, TextKey},
+    preferences::storage::StorageLocation,
+    ui::ToastEvent,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 玩家统计在存储中的键名
+pub const STATS_KEY: &str = "stats.ron";
+
+/// 在某个难度下达到该最长连胜后，不再向玩家推荐这个难度
+pub const RECOMMEND_MASTERY_STREAK: u32 = 3;
+
+/// 对局结束事件
+///
+/// 在对局结束时发送，结果为玩家视角
+#[derive(Event, Debug, Clone, Copy)]
+pub struct GameFinishedEvent {
+    /// 玩家的对局结果
+    pub outcome: GameOutcome,
+
+    /// 本局AI的难度
+    pub difficulty: AiDifficulty,
+
+    /// 玩家与AI的终局子数差，玩家领先为正
+    pub margin: i32,
+}
+
+/// 按难度分开统计时使用的难度级别，自适应难度不区分当时的强度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DifficultyTier {
+    Chaos,
+    Beginner,
+    Intermediate,
+    Advanced,
+    Expert,
+    ExpertPlus,
+    Adaptive,
+}
+
+impl From<AiDifficulty> for DifficultyTier {
+    fn from(difficulty: AiDifficulty) -> Self {
+        match difficulty {
+            AiDifficulty::Chaos(_) => Self::Chaos,
+            AiDifficulty::Beginner => Self::Beginner,
+            AiDifficulty::Intermediate => Self::Intermediate,
+            AiDifficulty::Advanced => Self::Advanced,
+            AiDifficulty::Expert => Self::Expert,
+            AiDifficulty::ExpertPlus => Self::ExpertPlus,
+            AiDifficulty::Adaptive(_) => Self::Adaptive,
+        }
+    }
+}
+
+/// 某个难度下的连胜和最佳胜局记录
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct DifficultyRecord {
+    /// 当前连胜局数，输棋或平局后归零
+    pub current_streak: u32,
+
+    /// 最长连胜局数
+    pub best_streak: u32,
+
+    /// 胜差最大的一局的子数差，还没有赢过时为None
+    pub best_win: Option<u32>,
+}
+
+impl DifficultyRecord {
+    /// 记录一局结果，`margin`为玩家领先的子数
+    pub fn record(&mut self, outcome: GameOutcome, margin: i32) {
+        match outcome {
+            GameOutcome::Win => {
+                self.current_streak += 1;
+                self.best_streak = self.best_streak.max(self.current_streak);
+                let margin = margin.unsigned_abs();
+                self.best_win = Some(self.best_win.map_or(margin, |best| best.max(margin)));
+            }
+            GameOutcome::Loss | GameOutcome::Draw => self.current_streak = 0,
+        }
+    }
+
+    /// 难度按钮徽标的参数：当前连胜、最长连胜和最大胜差，还没有赢过时返回None
+    pub fn badge_args(&self) -> Option<TextArgs> {
+        self.best_win.map(|best_win| {
+            TextArgs(vec![
+                TextArg::Number(self.current_streak.into()),
+                TextArg::Number(self.best_streak.into()),
+                TextArg::Number(best_win.into()),
+            ])
+        })
+    }
+}
+
+/// 玩家统计资源
+///
+/// 新增字段需要提供默认值，以兼容旧版本保存的文件
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct PlayerStats {
+    /// 总对局数
+    pub games_played: u32,
+
+    /// 胜局数
+    pub wins: u32,
+
+    /// 负局数
+    pub losses: u32,
+
+    /// 平局数
+    pub draws: u32,
+
+    /// 自适应难度状态
+    pub adaptive: AdaptiveState,
+
+    /// 各难度的连胜和最佳胜局
+    pub difficulty_records: BTreeMap<DifficultyTier, DifficultyRecord>,
+
+    /// 本地使用统计，只在玩家开启后记录
+    pub insights: UsageInsights,
+
+    /// 累计的翻转记录
+    pub flips: FlipRecords,
+}
+
+impl PlayerStats {
+    /// 从存储中读取玩家统计，读取或解析失败时使用默认值
+    pub fn load(storage: &StorageLocation) -> Self {
+        storage
+            .read(STATS_KEY)
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// 将玩家统计写入存储
+    pub fn save(&self, storage: &StorageLocation) -> Result<(), String> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())?;
+        storage.write(STATS_KEY, &contents)
+    }
+
+    /// 记录一局对局结果，自适应难度的对局同时调整下一局的AI强度
+    ///
+    /// `margin`为玩家与AI的终局子数差，用于记录胜差最大的一局
+    pub fn record_game(&mut self, outcome: GameOutcome, difficulty: AiDifficulty, margin: i32) {
+        self.games_played += 1;
+        match outcome {
+            GameOutcome::Win => self.wins += 1,
+            GameOutcome::Loss => self.losses += 1,
+            GameOutcome::Draw => self.draws += 1,
+        }
+        self.difficulty_records
+            .entry(difficulty.into())
+            .or_default()
+            .record(outcome, margin);
+
+        if matches!(difficulty, AiDifficulty::Adaptive(_)) {
+            self.adaptive.record_result(outcome);
+        }
+    }
+
+    /// 某个难度的连胜和最佳胜局，还没有下过该难度时为默认值
+    pub fn record_for(&self, difficulty: AiDifficulty) -> DifficultyRecord {
+        self.difficulty_records
+            .get(&difficulty.into())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// 向玩家推荐的难度：从初级开始，第一个还没有连胜过3局的固定难度，都连胜过时推荐专家
+    pub fn recommended_difficulty(&self) -> AiDifficulty {
+        [
+            AiDifficulty::Beginner,
+            AiDifficulty::Intermediate,
+            AiDifficulty::Advanced,
+        ]
+        .into_iter()
+        .find(|&difficulty| self.record_for(difficulty).best_streak < RECOMMEND_MASTERY_STREAK)
+        .unwrap_or(AiDifficulty::Expert)
+    }
+
+    /// 难度按钮副标题的文本键，随机走棋的难度不显示搜索深度和时限
+    pub fn difficulty_subtitle_key(&self, difficulty: AiDifficulty) -> TextKey {
+        if difficulty.get_search_params().random_moves {
+            TextKey::DifficultyChaosSubtitleFormat
+        } else {
+            TextKey::DifficultySubtitleFormat
+        }
+    }
+
+    /// 难度按钮副标题的参数：搜索深度、每步时限和估计的等级分，自适应难度按当前强度计算；
+    /// 随机走棋的难度只有估计的等级分
+    pub fn difficulty_subtitle_args(&self, difficulty: AiDifficulty) -> TextArgs {
+        let difficulty = self.resolve_difficulty(difficulty);
+        let params = difficulty.get_search_params();
+        if params.random_moves {
+            return TextArgs(vec![TextArg::Number(difficulty.estimated_elo().into())]);
+        }
+        TextArgs(vec![
+            TextArg::Number(params.max_depth.into()),
+            TextArg::Duration(params.time_limit),
+            TextArg::Number(difficulty.estimated_elo().into()),
+        ])
+    }
+
+    /// 根据选择的难度得到本局实际使用的难度
+    ///
+    /// 自适应难度使用统计中保存的当前强度
+    pub fn resolve_difficulty(&self, difficulty: AiDifficulty) -> AiDifficulty {
+        match difficulty {
+            AiDifficulty::Adaptive(_) => AiDifficulty::Adaptive(self.adaptive.level),
+            other => other,
+        }
+    }
+}
+
+/// 记录对局结果并保存玩家统计
+pub fn record_game_results(
+    mut finished_events: EventReader<GameFinishedEvent>,
+    mut stats: ResMut<PlayerStats>,
+    storage: Res<StorageLocation>,
+    mut toast_events: EventWriter<ToastEvent>,
+) {
+    for event in finished_events.read() {
+        stats.record_game(event.outcome, event.difficulty, event.margin);
+
+        if let Err(err) = stats.save(&storage) {
+            warn!("Failed to save player stats: {err}");
+            toast_events.write(ToastEvent::save_failed(TextKey::SaveDataStats));
+        }
+    }
+}
@@ -0,0 +1,267 @@
+// 历史对局列表 - 难度选择界面中浏览已保存的对局，选择一局在分析查看器中回放
+
+use super::{
+    difficulty_text_arg, spawn_button, spawn_label, ButtonStyle, FocusScope, MiniBoardView,
+    ToDelete, UiFocus, UiState,
+};
+use crate::{
+    ai::endgame::GameOutcome,
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    record::{ArchivedGame, GameArchive},
+    state::GameState,
+};
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+
+/// 列表条目的高度
+const ENTRY_HEIGHT: f32 = 44.0; // 触摸友好高度
+
+/// 列表条目的间距
+const ENTRY_GAP: f32 = 6.0;
+
+/// 列表可见区域的高度
+const LIST_HEIGHT: f32 = 340.0;
+
+/// 条目中终局缩略棋盘的格子边长
+const THUMBNAIL_CELL_SIZE: f32 = 5.0;
+
+/// 鼠标滚轮每行滚动的距离
+const WHEEL_LINE_HEIGHT: f32 = 24.0;
+
+#[derive(Component)]
+pub struct ArchiveButton;
+
+#[derive(Component)]
+pub struct ArchivePanel;
+
+/// 可滚动的对局列表
+#[derive(Component)]
+pub struct ArchiveList;
+
+/// 列表中的一局对局，值为在存档中的序号
+#[derive(Component)]
+pub struct ArchiveEntryButton(pub usize);
+
+/// 打开或关闭历史对局列表（关闭按钮复用同一组件）
+pub fn handle_archive_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ArchiveButton>)>,
+    mut ui_state: ResMut<UiState>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            ui_state.show_archive = !ui_state.show_archive;
+        }
+    }
+}
+
+pub fn manage_archive_panel(
+    mut commands: Commands,
+    ui_state: Res<UiState>,
+    panel_query: Query<Entity, With<ArchivePanel>>,
+    archive: Res<GameArchive>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    if !ui_state.is_changed() {
+        return;
+    }
+
+    let panel_open = !panel_query.is_empty();
+    if ui_state.show_archive == panel_open {
+        return;
+    }
+
+    for entity in panel_query.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+    if ui_state.show_archive {
+        spawn_archive_panel(&mut commands, &archive, &language_settings, &font_assets);
+    }
+}
+
+fn spawn_archive_panel(
+    commands: &mut Commands,
+    archive: &GameArchive,
+    language_settings: &LanguageSettings,
+    font_assets: &FontAssets,
+) {
+    let font = get_font_for_language(language_settings, font_assets);
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                padding: UiRect::all(Val::Px(15.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            BorderColor(Color::srgb(0.6, 0.6, 0.6)),
+            BorderRadius::all(Val::Px(10.0)),
+            ArchivePanel,
+            FocusScope,
+            StateScoped(GameState::DifficultySelection),
+        ))
+        .with_children(|panel| {
+            // 标题
+            panel.spawn((
+                Text::default(),
+                TextKey::ArchiveTitle,
+                TextFont {
+                    font: font.clone(),
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+                LocalizedText,
+            ));
+
+            if archive.games.is_empty() {
+                panel.spawn((
+                    Text::default(),
+                    TextKey::ArchiveEmpty,
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    LocalizedText,
+                ));
+            }
+
+            // 对局列表，超出可见区域时滚动
+            panel
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(ENTRY_GAP),
+                        max_height: Val::Px(LIST_HEIGHT),
+                        overflow: Overflow::scroll_y(),
+                        ..default()
+                    },
+                    ArchiveList,
+                ))
+                .with_children(|list| {
+                    let entry_style = ButtonStyle {
+                        width: Val::Px(300.0),
+                        height: Val::Px(ENTRY_HEIGHT),
+                        color: Color::srgb(0.25, 0.25, 0.3),
+                        font_size: 13.0,
+                        ..default()
+                    };
+                    for (index, game) in archive.games.iter().enumerate() {
+                        let mut entry =
+                            list.spawn((entry_style.bundle(), ArchiveEntryButton(index)));
+                        // 条目在滚动列表中不压缩，缩略棋盘和文本横向排列
+                        entry.entry::<Node>().and_modify(|mut node| {
+                            node.flex_shrink = 0.0;
+                            node.column_gap = Val::Px(8.0);
+                        });
+                        entry.with_children(|button| {
+                            // 终局缩略棋盘，存档无法回放时不显示
+                            if let Ok(log) = game.replay() {
+                                button.spawn((
+                                    Node::default(),
+                                    MiniBoardView::new(log.board(), THUMBNAIL_CELL_SIZE),
+                                ));
+                            }
+                            let (key, args) = entry_text(game);
+                            spawn_label(button, &entry_style.label_style(), &font, key, args);
+                        });
+                    }
+                });
+
+            // 关闭按钮
+            let close_style = ButtonStyle {
+                width: Val::Px(80.0),
+                radius: 5.0,
+                margin: UiRect::top(Val::Px(10.0)),
+                font_size: 14.0,
+                ..default()
+            };
+            spawn_button(
+                panel,
+                &close_style,
+                &font,
+                TextKey::RulesClose,
+                ArchiveButton,
+            );
+        });
+}
+
+/// 条目文本的键和参数：日期、结果、难度和比分，多局比赛中的对局再加上局数
+pub fn entry_text(game: &ArchivedGame) -> (TextKey, TextArgs) {
+    let outcome = match game.outcome {
+        GameOutcome::Win => TextKey::OutcomeWin,
+        GameOutcome::Loss => TextKey::OutcomeLoss,
+        GameOutcome::Draw => TextKey::OutcomeDraw,
+    };
+    let args = vec![
+        TextArg::Timestamp(game.finished_at),
+        TextArg::Key(outcome),
+        difficulty_text_arg(game.difficulty),
+        TextArg::Number(game.black.into()),
+        TextArg::Number(game.white.into()),
+    ];
+    match game.series {
+        Some(series) => (
+            TextKey::ArchiveSeriesFormat,
+            TextArgs(vec![
+                TextArg::Format(TextKey::ArchiveEntry, args),
+                TextArg::Number(series.game.into()),
+                TextArg::Number(series.format.games().into()),
+            ]),
+        ),
+        None => (TextKey::ArchiveEntry, TextArgs(args)),
+    }
+}
+
+/// 鼠标滚轮滚动列表；手柄焦点移到可见区域之外的条目时自动滚动到该条目
+pub fn scroll_archive_list(
+    mut wheel_events: EventReader<MouseWheel>,
+    mut list_query: Query<&mut ScrollPosition, With<ArchiveList>>,
+    entry_query: Query<&ArchiveEntryButton>,
+    focus: Res<UiFocus>,
+) {
+    let Ok(mut scroll) = list_query.single_mut() else {
+        wheel_events.clear();
+        return;
+    };
+
+    for event in wheel_events.read() {
+        let distance = match event.unit {
+            MouseScrollUnit::Line => event.y * WHEEL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        // 超出内容范围的滚动位置由布局系统限制
+        scroll.offset_y = (scroll.offset_y - distance).max(0.0);
+    }
+
+    if !focus.is_changed() {
+        return;
+    }
+    let Some(ArchiveEntryButton(index)) = focus.focused.and_then(|e| entry_query.get(e).ok())
+    else {
+        return;
+    };
+    let top = *index as f32 * (ENTRY_HEIGHT + ENTRY_GAP);
+    let bottom = top + ENTRY_HEIGHT;
+    if top < scroll.offset_y {
+        scroll.offset_y = top;
+    } else if bottom > scroll.offset_y + LIST_HEIGHT {
+        scroll.offset_y = bottom - LIST_HEIGHT;
+    }
+}
@@ -0,0 +1,136 @@
+// 缺失资源警告 - 启动检查发现字体或音效文件加载失败时，在屏幕顶部列出缺失的文件
+//
+// 缺失资源不影响游戏运行（字体回退到内置字体、音效跳过播放），面板只是提醒打包Web版本等场景下
+// 有文件没有放到资源目录中。文件路径使用内置字体显示，中文字体本身缺失时也能看清；
+// 标题和说明使用当前语言，中文字体缺失时同样改用内置字体。点击关闭后本次运行不再显示
+
+use super::{
+    spawn_button, spawn_label, spawn_panel, ButtonStyle, LabelStyle, PanelStyle, ToDelete,
+};
+use crate::{
+    boot::{BootStatus, MissingAssets},
+    fonts::{get_font_for_language, FontAssets},
+    localization::{Language, LanguageSettings, TextKey},
+};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct AssetWarningPanel;
+
+#[derive(Component)]
+pub struct AssetWarningCloseButton;
+
+/// 面板的标题和说明使用的字体，中文字体加载失败时使用内置字体
+fn warning_font(
+    language_settings: &LanguageSettings,
+    font_assets: &FontAssets,
+    asset_server: &AssetServer,
+) -> Handle<Font> {
+    let chinese_missing =
+        BootStatus::from_load_state(&asset_server.load_state(&font_assets.chinese_font))
+            == BootStatus::Failed;
+    if language_settings.current_language == Language::Chinese && chinese_missing {
+        font_assets.default_font.clone()
+    } else {
+        get_font_for_language(language_settings, font_assets)
+    }
+}
+
+/// 启动检查发现缺失资源后显示警告面板
+pub fn show_asset_warning_panel(
+    mut commands: Commands,
+    missing_assets: Res<MissingAssets>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    asset_server: Res<AssetServer>,
+) {
+    if !missing_assets.is_changed() || missing_assets.paths.is_empty() {
+        return;
+    }
+
+    let font = warning_font(&language_settings, &font_assets, &asset_server);
+    let root = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Px(48.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Pickable::IGNORE,
+            GlobalZIndex(50),
+            AssetWarningPanel,
+        ))
+        .id();
+    let panel_style = PanelStyle {
+        max_width: Val::Px(520.0),
+        background: Color::srgba(0.25, 0.12, 0.05, 0.92),
+        border_width: 1.0,
+        border_color: Color::srgb(0.95, 0.7, 0.2),
+        radius: 8.0,
+        padding: 12.0,
+        row_gap: 4.0,
+        ..default()
+    };
+    let panel = spawn_panel(&mut commands, &panel_style, ChildOf(root));
+    commands.entity(panel).with_children(|panel| {
+        let title_style = LabelStyle {
+            font_size: 18.0,
+            color: Color::srgb(0.95, 0.75, 0.3),
+            ..default()
+        };
+        spawn_label(panel, &title_style, &font, TextKey::AssetWarningTitle, ());
+        let hint_style = LabelStyle {
+            font_size: 13.0,
+            justify: JustifyText::Center,
+            ..default()
+        };
+        spawn_label(panel, &hint_style, &font, TextKey::AssetWarningHint, ());
+        for path in &missing_assets.paths {
+            panel.spawn((
+                Text::new(path.clone()),
+                TextFont {
+                    font: font_assets.default_font.clone(),
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.85, 0.85, 0.85)),
+            ));
+        }
+
+        let close_style = ButtonStyle {
+            width: Val::Px(80.0),
+            height: Val::Px(30.0),
+            margin: UiRect::top(Val::Px(6.0)),
+            border_width: 1.0,
+            radius: 5.0,
+            font_size: 14.0,
+            ..default()
+        };
+        spawn_button(
+            panel,
+            &close_style,
+            &font,
+            TextKey::RulesClose,
+            AssetWarningCloseButton,
+        );
+    });
+}
+
+/// 点击关闭按钮后移除警告面板
+pub fn handle_asset_warning_close(
+    mut commands: Commands,
+    close_query: Query<&Interaction, (Changed<Interaction>, With<AssetWarningCloseButton>)>,
+    panel_query: Query<Entity, With<AssetWarningPanel>>,
+) {
+    if !close_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        return;
+    }
+    for entity in panel_query.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+}
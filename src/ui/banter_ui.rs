@@ -0,0 +1,75 @@
+// AI闲聊气泡 - AI头像右侧的对话气泡，显示AI最近说的一句话
+
+use crate::{
+    banter::BanterState,
+    fonts::LocalizedText,
+    localization::{TextArgs, TextKey},
+};
+use bevy::prelude::*;
+
+/// 气泡的背景颜色
+const BUBBLE_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.92);
+
+/// 气泡中文字的颜色
+const BUBBLE_TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+
+/// 闲聊气泡
+#[derive(Component)]
+pub struct BanterBubble;
+
+/// 在AI头像中创建气泡，气泡绝对定位在头像右侧，不影响顶部区域的布局
+pub fn spawn_banter_bubble(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(60.0),
+                top: Val::Px(4.0),
+                max_width: Val::Px(180.0),
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(BUBBLE_COLOR),
+            BorderRadius::new(Val::Px(2.0), Val::Px(10.0), Val::Px(10.0), Val::Px(10.0)),
+            Visibility::Hidden,
+            BanterBubble,
+        ))
+        .with_children(|bubble| {
+            bubble.spawn((
+                Text::default(),
+                TextKey::BanterPlayerCorner,
+                TextArgs::default(),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(BUBBLE_TEXT_COLOR),
+                LocalizedText,
+            ));
+        });
+}
+
+/// 闲聊变化时更新气泡的文字和可见性
+pub fn update_banter_bubble(
+    banter: Res<BanterState>,
+    mut bubble_query: Query<(&mut Visibility, &Children), With<BanterBubble>>,
+    mut text_query: Query<&mut TextKey>,
+) {
+    if !banter.is_changed() {
+        return;
+    }
+
+    for (mut visibility, children) in bubble_query.iter_mut() {
+        let Some(line) = banter.line() else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        for &child in children {
+            if let Ok(mut key) = text_query.get_mut(child) {
+                key.set_if_neq(line.text_key());
+            }
+        }
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
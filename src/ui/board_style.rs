@@ -0,0 +1,292 @@
+// 棋盘质感 - 网格下方的绒布或木纹纹理、棋子的投影和柔化边缘的网格线
+//
+// 质感是棋盘主题的一部分（BoardTheme::style）。纹理在程序中生成，不需要额外的图片文件；
+// Flat风格不显示纹理和投影，网格线也不做柔化，精简资源档（Web和移动端）固定使用Flat
+
+use super::{BoardColors, BoardLine, BOARD_SIZE, PIECE_RADIUS};
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use serde::{Deserialize, Serialize};
+
+/// 纹理图片的边长（像素），拉伸到整个棋盘
+pub const BOARD_TEXTURE_SIZE: u32 = 128;
+
+/// 纹理的Z坐标 - 位于格子之上、危险区域标注和网格线之下
+const TEXTURE_Z: f32 = 0.05;
+
+/// 网格线的Z坐标，柔化边缘略低于线芯
+const LINE_Z: f32 = 1.0;
+const LINE_FEATHER_Z: f32 = 0.95;
+
+/// Flat风格的网格线宽度
+const FLAT_LINE_WIDTH: f32 = 1.5;
+
+/// 柔化网格线的线芯宽度，两侧各有半像素的半透明边缘，整体粗细与Flat相近
+const SOFT_LINE_WIDTH: f32 = 1.0;
+const LINE_FEATHER_WIDTH: f32 = SOFT_LINE_WIDTH + 1.0;
+
+/// 柔化边缘相对线芯的不透明度
+const LINE_FEATHER_ALPHA: f32 = 0.5;
+
+/// 投影相对棋子的偏移，光源在左上方；Z为负值使投影位于棋子之下
+const SHADOW_OFFSET: Vec3 = Vec3::new(PIECE_RADIUS * 0.08, -PIECE_RADIUS * 0.12, -0.05);
+
+/// 投影的颜色
+const SHADOW_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.28);
+
+/// 棋盘的质感风格
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BoardStyle {
+    /// 纯色格子，不显示纹理和投影，用于低端设备
+    Flat,
+    /// 绒布纹理，细密均匀的颗粒
+    #[default]
+    Felt,
+    /// 木纹纹理，横向起伏的纹路
+    Wood,
+}
+
+impl BoardStyle {
+    /// 是否显示纹理、投影和柔化网格线
+    pub fn is_textured(self) -> bool {
+        self != BoardStyle::Flat
+    }
+
+    /// 网格线线芯的宽度
+    pub fn line_width(self) -> f32 {
+        if self.is_textured() {
+            SOFT_LINE_WIDTH
+        } else {
+            FLAT_LINE_WIDTH
+        }
+    }
+
+    /// 纹理、投影等质感元素的可见性
+    pub fn detail_visibility(self) -> Visibility {
+        if self.is_textured() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        }
+    }
+}
+
+/// 棋盘纹理，记录生成图片时的风格
+#[derive(Component)]
+pub struct BoardTexture {
+    pub style: BoardStyle,
+}
+
+/// 网格线的半透明柔化边缘
+#[derive(Component)]
+pub struct BoardLineFeather;
+
+/// 棋子的投影，是棋子的子实体，随棋子一起翻转和删除
+#[derive(Component)]
+pub struct PieceShadow;
+
+/// 像素坐标的确定性噪声，范围0到1
+fn hash_noise(x: u32, y: u32, seed: u32) -> f32 {
+    let mut h = x.wrapping_mul(0x8da6_b343) ^ y.wrapping_mul(0xd816_3841) ^ seed;
+    h = (h ^ (h >> 13)).wrapping_mul(0x5bd1_e995);
+    h ^= h >> 15;
+    (h & 0xffff) as f32 / 65535.0
+}
+
+/// 一个像素的灰度和不透明度
+fn texture_texel(style: BoardStyle, x: u32, y: u32) -> (f32, f32) {
+    match style {
+        BoardStyle::Flat => (0.0, 0.0),
+        BoardStyle::Felt => {
+            // 两层不同尺度的颗粒叠加
+            let fine = hash_noise(x, y, 17);
+            let coarse = hash_noise(x / 4, y / 4, 29);
+            (0.35 + 0.3 * fine + 0.3 * coarse, 0.14)
+        }
+        BoardStyle::Wood => {
+            // 横向纹路随列缓慢起伏，再叠加细小的颗粒
+            let wobble = (x as f32 * 0.07).sin() * 3.0 + hash_noise(x / 16, 0, 41) * 4.0;
+            let ring = ((y as f32 + wobble) * 0.45).sin() * 0.5 + 0.5;
+            let grain = hash_noise(x, y, 53);
+            (0.25 + 0.5 * ring + 0.15 * grain, 0.2)
+        }
+    }
+}
+
+/// 生成棋盘纹理图片，Flat风格没有纹理
+pub fn board_texture_image(style: BoardStyle) -> Option<Image> {
+    if !style.is_textured() {
+        return None;
+    }
+
+    let mut data = Vec::with_capacity((BOARD_TEXTURE_SIZE * BOARD_TEXTURE_SIZE * 4) as usize);
+    for y in 0..BOARD_TEXTURE_SIZE {
+        for x in 0..BOARD_TEXTURE_SIZE {
+            let (value, alpha) = texture_texel(style, x, y);
+            let byte = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+            let gray = byte(value);
+            data.extend_from_slice(&[gray, gray, gray, byte(alpha)]);
+        }
+    }
+
+    Some(Image::new(
+        Extent3d {
+            width: BOARD_TEXTURE_SIZE,
+            height: BOARD_TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ))
+}
+
+/// 纹理精灵：Flat风格时隐藏，换成有纹理的风格时重新生成图片
+pub fn board_texture_sprite(style: BoardStyle, images: &mut Assets<Image>) -> Sprite {
+    Sprite {
+        image: board_texture_image(style)
+            .map(|image| images.add(image))
+            .unwrap_or_default(),
+        custom_size: Some(Vec2::splat(BOARD_SIZE)),
+        ..default()
+    }
+}
+
+/// 生成棋盘纹理，作为棋盘根实体的子实体
+pub fn spawn_board_texture(
+    parent: &mut ChildSpawnerCommands,
+    style: BoardStyle,
+    images: &mut Assets<Image>,
+) {
+    parent.spawn((
+        board_texture_sprite(style, images),
+        Transform::from_xyz(0.0, 0.0, TEXTURE_Z),
+        style.detail_visibility(),
+        BoardTexture { style },
+    ));
+}
+
+/// 柔化边缘的颜色
+pub fn line_feather_color(line_color: Color) -> Color {
+    line_color.with_alpha(line_color.alpha() * LINE_FEATHER_ALPHA)
+}
+
+/// 网格线精灵的尺寸，竖线和横线按宽度区分
+pub fn line_size(vertical: bool, width: f32) -> Vec2 {
+    if vertical {
+        Vec2::new(width, BOARD_SIZE)
+    } else {
+        Vec2::new(BOARD_SIZE, width)
+    }
+}
+
+/// 生成一条网格线：线芯和柔化边缘
+pub fn spawn_grid_line(
+    parent: &mut ChildSpawnerCommands,
+    vertical: bool,
+    offset: f32,
+    line_color: Color,
+    style: BoardStyle,
+) {
+    let position = |z: f32| {
+        if vertical {
+            Transform::from_xyz(offset, 0.0, z)
+        } else {
+            Transform::from_xyz(0.0, offset, z)
+        }
+    };
+
+    parent.spawn((
+        Sprite::from_color(line_color, line_size(vertical, style.line_width())),
+        position(LINE_Z),
+        BoardLine,
+    ));
+    parent.spawn((
+        Sprite::from_color(
+            line_feather_color(line_color),
+            line_size(vertical, LINE_FEATHER_WIDTH),
+        ),
+        position(LINE_FEATHER_Z),
+        style.detail_visibility(),
+        BoardLineFeather,
+    ));
+}
+
+/// 在棋子下方生成投影，Flat风格不生成
+pub fn spawn_piece_shadow(
+    commands: &mut Commands,
+    piece: Entity,
+    style: BoardStyle,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) {
+    if !style.is_textured() {
+        return;
+    }
+    commands.spawn((
+        Mesh2d(meshes.add(Circle::new(PIECE_RADIUS))),
+        MeshMaterial2d(materials.add(ColorMaterial::from(SHADOW_COLOR))),
+        Transform::from_translation(SHADOW_OFFSET),
+        PieceShadow,
+        ChildOf(piece),
+    ));
+}
+
+/// 棋盘主题变化后更新已经生成的纹理、网格线和投影
+///
+/// 与recolor_board配合：格子、线芯和棋子的颜色由recolor_board更新，
+/// 这里处理质感风格相关的部分，之后生成的棋子按新风格决定是否带投影
+pub fn restyle_board(
+    colors: Res<BoardColors>,
+    mut texture_query: Query<
+        (&mut BoardTexture, &mut Sprite, &mut Visibility),
+        (Without<BoardLine>, Without<BoardLineFeather>),
+    >,
+    mut line_query: Query<&mut Sprite, (With<BoardLine>, Without<BoardLineFeather>)>,
+    mut feather_query: Query<(&mut Sprite, &mut Visibility), With<BoardLineFeather>>,
+    mut shadow_query: Query<
+        &mut Visibility,
+        (
+            With<PieceShadow>,
+            Without<BoardTexture>,
+            Without<BoardLineFeather>,
+        ),
+    >,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if !colors.is_changed() || colors.is_added() {
+        return;
+    }
+    let style = colors.style;
+    let visibility = style.detail_visibility();
+
+    for (mut texture, mut sprite, mut texture_visibility) in texture_query.iter_mut() {
+        if texture.style != style {
+            // 换成其他纹理时重新生成图片，Flat只隐藏
+            if style.is_textured() {
+                *sprite = board_texture_sprite(style, &mut images);
+            }
+            texture.style = style;
+        }
+        texture_visibility.set_if_neq(visibility);
+    }
+
+    for mut sprite in line_query.iter_mut() {
+        if let Some(size) = sprite.custom_size {
+            let vertical = size.x < size.y;
+            sprite.custom_size = Some(line_size(vertical, style.line_width()));
+        }
+    }
+    for (mut sprite, mut feather_visibility) in feather_query.iter_mut() {
+        sprite.color = line_feather_color(colors.line_color);
+        feather_visibility.set_if_neq(visibility);
+    }
+
+    for mut shadow_visibility in shadow_query.iter_mut() {
+        shadow_visibility.set_if_neq(visibility);
+    }
+}
@@ -1,29 +1,54 @@
-use crate::game::{Board, PlayerColor};
+use super::{
+    spawn_board_texture, spawn_grid_line, spawn_piece_shadow, BoardStyle, FlipAnimation,
+    FlipChainEvent, HintMarker,
+};
+use crate::{
+    accessibility::MotionSettings,
+    assist::{ActiveMatchRules, CornerWarningState},
+    game::{Board, BoardDiff, LegalMovesCache, PlayerColor},
+    history::{HistoryPreview, MoveHistory},
+    input::{BoardCursor, Premove},
+    preferences::Preferences,
+};
 use bevy::prelude::*;
 
 #[derive(Component)]
 pub struct BoardSquare {
-    #[allow(dead_code)]
     pub position: u8,
 }
 
+/// 棋盘网格线
+#[derive(Component)]
+pub struct BoardLine;
+
 #[derive(Component)]
 pub struct Piece {
-    #[allow(dead_code)]
     pub color: PlayerColor,
-    #[allow(dead_code)]
     pub position: u8,
 }
 
 #[derive(Component)]
 pub struct ValidMoveIndicator {
-    #[allow(dead_code)]
     pub position: u8,
 }
 
+/// 棋盘的根实体：格子、网格线、棋子和可落子指示器都是它的后代，
+/// 离开棋盘界面时只需删除根实体
 #[derive(Component)]
 pub struct BoardUI;
 
+/// 鼠标悬停（或触摸按住）格子的高亮
+#[derive(Component)]
+pub struct HoverHighlight;
+
+/// 手柄棋盘光标的高亮
+#[derive(Component)]
+pub struct BoardCursorHighlight;
+
+/// 预走格子的标记
+#[derive(Component)]
+pub struct PremoveMarker;
+
 #[derive(Component)]
 pub struct ToDelete;
 
@@ -35,8 +60,11 @@ pub struct BoardColors {
     pub black_piece_color: bevy::prelude::Color,
     pub white_piece_color: bevy::prelude::Color,
     pub valid_move_color: bevy::prelude::Color,
-    #[allow(dead_code)]
     pub hover_color: bevy::prelude::Color,
+    /// 悬停在不能落子的格子上时的颜色
+    pub illegal_hover_color: bevy::prelude::Color,
+    /// 纹理、投影和网格线的质感风格
+    pub style: BoardStyle,
 }
 
 impl Default for BoardColors {
@@ -49,6 +77,8 @@ impl Default for BoardColors {
             white_piece_color: bevy::prelude::Color::srgb(0.98, 0.98, 0.98),
             valid_move_color: bevy::prelude::Color::srgba(1.0, 1.0, 1.0, 0.4),
             hover_color: bevy::prelude::Color::srgba(1.0, 1.0, 1.0, 0.3),
+            illegal_hover_color: bevy::prelude::Color::srgba(0.0, 0.0, 0.0, 0.15),
+            style: BoardStyle::default(),
         }
     }
 }
@@ -57,90 +87,373 @@ pub const BOARD_SIZE: f32 = 320.0; // 减小棋盘尺寸为手机优化
 pub const SQUARE_SIZE: f32 = BOARD_SIZE / 8.0;
 pub const PIECE_RADIUS: f32 = SQUARE_SIZE * 0.35;
 
-pub fn setup_board_ui(mut commands: Commands, colors: Res<BoardColors>) {
-    let _board_transform = Transform::from_xyz(0.0, 0.0, 0.0);
+/// 悬停高亮的Z坐标 - 位于危险区域标注之上、网格线之下
+const HOVER_Z: f32 = 0.8;
 
-    for row in 0..8 {
-        for col in 0..8 {
-            let position = (row * 8 + col) as u8;
-            let x = (col as f32 - 3.5) * SQUARE_SIZE;
-            let y = (3.5 - row as f32) * SQUARE_SIZE;
+/// 手柄光标的Z坐标 - 位于悬停高亮之上、网格线之下
+const BOARD_CURSOR_Z: f32 = 0.9;
 
-            let square_color = if (row + col) % 2 == 0 {
-                colors.board_color
-            } else {
-                colors.square_color
-            };
+/// 手柄光标的颜色
+const BOARD_CURSOR_COLOR: Color = Color::srgba(1.0, 0.85, 0.2, 0.45);
+
+/// 预走标记的Z坐标 - 位于悬停高亮和手柄光标之间
+const PREMOVE_Z: f32 = 0.85;
+
+/// 预走标记的颜色
+const PREMOVE_COLOR: Color = Color::srgba(0.3, 0.6, 1.0, 0.45);
+
+/// 最佳走法提示标记的Z坐标 - 位于悬停高亮和预走标记之间
+const HINT_Z: f32 = 0.83;
+
+/// 最佳走法提示标记的颜色
+const HINT_COLOR: Color = Color::srgba(0.3, 0.9, 0.4, 0.5);
+
+/// 将屏幕坐标转换为棋盘格位置，不在棋盘上时返回None
+pub fn board_square_at(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    screen_position: Vec2,
+) -> Option<u8> {
+    let world_position = camera
+        .viewport_to_world_2d(camera_transform, screen_position)
+        .ok()?;
+    square_at_world(world_position)
+}
+
+/// 将棋盘世界坐标转换为棋盘格位置，不在棋盘上时返回None
+///
+/// 棋盘中心位于原点，A1在左上角；格子的左边和上边属于该格
+pub fn square_at_world(world_position: Vec2) -> Option<u8> {
+    let col = ((world_position.x + SQUARE_SIZE * 4.0) / SQUARE_SIZE).floor() as i32;
+    let row = ((SQUARE_SIZE * 4.0 - world_position.y) / SQUARE_SIZE).floor() as i32;
+
+    ((0..8).contains(&col) && (0..8).contains(&row)).then(|| (row * 8 + col) as u8)
+}
+
+/// 创建棋盘根实体和静态的棋盘格子、纹理、网格线和高亮
+///
+/// 根实体位于原点且不缩放，子实体的局部坐标就是世界坐标
+pub fn setup_board_ui(
+    mut commands: Commands,
+    colors: Res<BoardColors>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    commands
+        .spawn((Transform::default(), Visibility::default(), BoardUI))
+        .with_children(|board| {
+            for row in 0..8 {
+                for col in 0..8 {
+                    let position = (row * 8 + col) as u8;
+                    let x = (col as f32 - 3.5) * SQUARE_SIZE;
+                    let y = (3.5 - row as f32) * SQUARE_SIZE;
+
+                    let square_color = if (row + col) % 2 == 0 {
+                        colors.board_color
+                    } else {
+                        colors.square_color
+                    };
+
+                    board.spawn((
+                        Sprite::from_color(square_color, Vec2::new(SQUARE_SIZE, SQUARE_SIZE)),
+                        Transform::from_xyz(x, y, 0.0),
+                        BoardSquare { position },
+                    ));
+                }
+            }
 
-            commands.spawn((
-                Sprite::from_color(square_color, Vec2::new(SQUARE_SIZE, SQUARE_SIZE)),
-                Transform::from_xyz(x, y, 0.0),
-                BoardSquare { position },
-                BoardUI,
+            spawn_board_texture(board, colors.style, &mut images);
+
+            for i in 0..9 {
+                let offset = (i as f32 - 4.0) * SQUARE_SIZE;
+                for vertical in [true, false] {
+                    spawn_grid_line(board, vertical, offset, colors.line_color, colors.style);
+                }
+            }
+
+            board.spawn((
+                Sprite::from_color(colors.hover_color, Vec2::splat(SQUARE_SIZE)),
+                Transform::from_xyz(0.0, 0.0, HOVER_Z),
+                Visibility::Hidden,
+                HoverHighlight,
             ));
-        }
+
+            board.spawn((
+                Sprite::from_color(BOARD_CURSOR_COLOR, Vec2::splat(SQUARE_SIZE)),
+                Transform::from_xyz(0.0, 0.0, BOARD_CURSOR_Z),
+                Visibility::Hidden,
+                BoardCursorHighlight,
+            ));
+
+            board.spawn((
+                Sprite::from_color(HINT_COLOR, Vec2::splat(SQUARE_SIZE)),
+                Transform::from_xyz(0.0, 0.0, HINT_Z),
+                Visibility::Hidden,
+                HintMarker,
+            ));
+
+            board.spawn((
+                Sprite::from_color(PREMOVE_COLOR, Vec2::splat(SQUARE_SIZE)),
+                Transform::from_xyz(0.0, 0.0, PREMOVE_Z),
+                Visibility::Hidden,
+                PremoveMarker,
+            ));
+        });
+}
+
+/// 有预走时标记预走的格子
+pub fn update_premove_marker(
+    premove: Res<Premove>,
+    mut marker_query: Query<(&mut Transform, &mut Visibility), With<PremoveMarker>>,
+) {
+    let Ok((mut transform, mut visibility)) = marker_query.single_mut() else {
+        return;
+    };
+
+    let Some(position) = premove.position() else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    let (row, col) = Board::position_to_coords(position);
+    transform.translation.x = (col as f32 - 3.5) * SQUARE_SIZE;
+    transform.translation.y = (3.5 - row as f32) * SQUARE_SIZE;
+    visibility.set_if_neq(Visibility::Inherited);
+}
+
+/// 手柄光标显示时高亮光标所在的格子
+pub fn update_board_cursor_highlight(
+    board_cursor: Res<BoardCursor>,
+    mut highlight_query: Query<(&mut Transform, &mut Visibility), With<BoardCursorHighlight>>,
+) {
+    let Ok((mut transform, mut visibility)) = highlight_query.single_mut() else {
+        return;
+    };
+
+    if !board_cursor.visible {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
     }
 
-    for i in 0..9 {
-        let offset = (i as f32 - 4.0) * SQUARE_SIZE;
+    let (row, col) = Board::position_to_coords(board_cursor.position);
+    transform.translation.x = (col as f32 - 3.5) * SQUARE_SIZE;
+    transform.translation.y = (3.5 - row as f32) * SQUARE_SIZE;
+    visibility.set_if_neq(Visibility::Inherited);
+}
 
-        commands.spawn((
-            Sprite::from_color(colors.line_color, Vec2::new(1.5, BOARD_SIZE)),
-            Transform::from_xyz(offset, 0.0, 1.0),
-            BoardUI,
-        ));
+/// 高亮指针下的格子，可以落子和不能落子的格子使用不同颜色
+///
+/// 鼠标跟随光标；触摸只在手指仍停留在按下的格子上时高亮，
+/// 与抬起时才确认落子的规则一致，拖出该格后高亮消失表示将取消
+pub fn update_hover_highlight(
+    mut highlight_query: Query<
+        (&mut Transform, &mut Sprite, &mut Visibility),
+        With<HoverHighlight>,
+    >,
+    board_query: Query<&Board>,
+    legal_moves: Res<LegalMovesCache>,
+    ai_query: Query<&crate::ai::AiPlayer>,
+    current_player: Res<CurrentPlayer>,
+    history_preview: Res<HistoryPreview>,
+    corner_warning: Res<CornerWarningState>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    touch_input: Res<Touches>,
+    colors: Res<BoardColors>,
+) {
+    let Ok((mut transform, mut sprite, mut visibility)) = highlight_query.single_mut() else {
+        return;
+    };
 
-        commands.spawn((
-            Sprite::from_color(colors.line_color, Vec2::new(BOARD_SIZE, 1.5)),
-            Transform::from_xyz(0.0, offset, 1.0),
-            BoardUI,
-        ));
+    let players_turn = ai_query
+        .single()
+        .map_or(true, |ai_player| ai_player.color != current_player.0);
+    let accepts_input =
+        players_turn && !history_preview.is_previewing() && corner_warning.pending.is_none();
+
+    let hovered = camera_query
+        .single()
+        .ok()
+        .filter(|_| accepts_input)
+        .and_then(|(camera, camera_transform)| {
+            if let Some(touch) = touch_input.iter().next() {
+                let pressed = board_square_at(camera, camera_transform, touch.start_position());
+                let current = board_square_at(camera, camera_transform, touch.position());
+                pressed.filter(|_| pressed == current)
+            } else {
+                let cursor = windows.single().ok()?.cursor_position()?;
+                board_square_at(camera, camera_transform, cursor)
+            }
+        });
+
+    let Some(position) = hovered else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    let legal = board_query
+        .single()
+        .is_ok_and(|board| legal_moves.is_legal(board, position, current_player.0));
+    let color = if legal {
+        colors.hover_color
+    } else {
+        colors.illegal_hover_color
+    };
+    if sprite.color != color {
+        sprite.color = color;
+    }
+
+    let (row, col) = Board::position_to_coords(position);
+    let translation = Vec3::new(
+        (col as f32 - 3.5) * SQUARE_SIZE,
+        (3.5 - row as f32) * SQUARE_SIZE,
+        HOVER_Z,
+    );
+    if transform.translation != translation {
+        transform.translation = translation;
     }
+    visibility.set_if_neq(Visibility::Inherited);
 }
 
 pub fn update_pieces(
     mut commands: Commands,
-    board_query: Query<&Board, Changed<Board>>,
-    piece_query: Query<Entity, With<Piece>>,
+    board_query: Query<Ref<Board>>,
+    board_root: Query<Entity, With<BoardUI>>,
+    piece_query: Query<(Entity, &Piece, &ChildOf), Without<ToDelete>>,
+    history: Res<MoveHistory>,
+    history_preview: Res<HistoryPreview>,
     colors: Res<BoardColors>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    mut flip_events: EventReader<FlipChainEvent>,
+    motion_settings: Res<MotionSettings>,
 ) {
-    // 只有当Board组件发生变化时才执行更新
-    if let Ok(board) = board_query.single() {
-        // 标记旧棋子为删除
-        for entity in piece_query.iter() {
+    let flip_chain = flip_events.read().last().cloned();
+    let (Ok(live_board), Ok(root)) = (board_query.single(), board_root.single()) else {
+        return;
+    };
+
+    // 只有当Board组件或历史浏览位置发生变化时才执行更新
+    if live_board.is_changed() || history_preview.is_changed() {
+        // 浏览历史时显示快照局面
+        let board = history_preview.displayed_board(&history, &live_board);
+
+        let pieces = sync_pieces(
+            &mut commands,
+            root,
+            &piece_query,
+            &board,
+            &colors,
+            &mut meshes,
+            &mut materials,
+        );
+
+        // 刚落子时被翻转的棋子依次播放翻转动画，浏览历史时不播放
+        let Some(flip_chain) = flip_chain.filter(|_| !history_preview.is_previewing()) else {
+            return;
+        };
+        for (position, delay) in flip_chain.delays() {
+            let Some(entity) = pieces[position as usize] else {
+                continue;
+            };
+            if let Some(animation) =
+                FlipAnimation::for_piece(flip_chain.player, delay, &colors, &motion_settings)
+            {
+                commands.entity(entity).insert(animation);
+            }
+        }
+    }
+}
+
+/// 让棋盘根实体下的棋子与局面一致，返回按位置索引的棋子实体
+///
+/// 根据现有棋子还原出正在显示的局面，只替换与目标局面差异中新放、移除和变色的格子，
+/// 其余棋子保留不动；变色的格子重新生成，以便加上翻转动画
+pub fn sync_pieces(
+    commands: &mut Commands,
+    root: Entity,
+    piece_query: &Query<(Entity, &Piece, &ChildOf), Without<ToDelete>>,
+    board: &Board,
+    colors: &BoardColors,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) -> [Option<Entity>; 64] {
+    let mut pieces = [None; 64];
+    let mut shown = Board { black: 0, white: 0 };
+    for (entity, piece, child_of) in piece_query.iter() {
+        // 即将随旧根实体删除的棋子不算在内；同一格出现重复棋子时只保留一个
+        if child_of.parent() != root {
+            continue;
+        }
+        let slot = &mut pieces[piece.position as usize];
+        if slot.is_some() {
             commands.entity(entity).insert(ToDelete);
+            continue;
+        }
+        *slot = Some(entity);
+        match piece.color {
+            PlayerColor::Black => shown.black |= 1 << piece.position,
+            PlayerColor::White => shown.white |= 1 << piece.position,
         }
+    }
 
-        for position in 0..64 {
-            if let Some(color) = board.get_piece(position) {
-                let (row, col) = Board::position_to_coords(position);
-                let x = (col as f32 - 3.5) * SQUARE_SIZE;
-                let y = (3.5 - row as f32) * SQUARE_SIZE;
-
-                let piece_color = match color {
-                    PlayerColor::Black => colors.black_piece_color,
-                    PlayerColor::White => colors.white_piece_color,
-                };
-
-                commands.spawn((
-                    Mesh2d(meshes.add(Circle::new(PIECE_RADIUS))),
-                    MeshMaterial2d(materials.add(ColorMaterial::from(piece_color))),
-                    Transform::from_xyz(x, y, 2.0),
-                    Piece { color, position },
-                    BoardUI,
-                ));
-            }
+    let diff = shown.diff(board);
+    for position in BoardDiff::squares(diff.removed | diff.flipped) {
+        if let Some(entity) = pieces[position as usize].take() {
+            commands.entity(entity).insert(ToDelete);
+        }
+    }
+    for position in BoardDiff::squares(diff.placed | diff.flipped) {
+        if let Some(color) = board.get_piece(position) {
+            pieces[position as usize] = Some(spawn_piece(
+                commands, root, position, color, colors, meshes, materials,
+            ));
         }
     }
+    pieces
+}
+
+/// 在棋盘根实体下生成一枚棋子及其投影
+fn spawn_piece(
+    commands: &mut Commands,
+    root: Entity,
+    position: u8,
+    color: PlayerColor,
+    colors: &BoardColors,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) -> Entity {
+    let (row, col) = Board::position_to_coords(position);
+    let x = (col as f32 - 3.5) * SQUARE_SIZE;
+    let y = (3.5 - row as f32) * SQUARE_SIZE;
+
+    let piece_color = match color {
+        PlayerColor::Black => colors.black_piece_color,
+        PlayerColor::White => colors.white_piece_color,
+    };
+
+    let entity = commands
+        .spawn((
+            Mesh2d(meshes.add(Circle::new(PIECE_RADIUS))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(piece_color))),
+            Transform::from_xyz(x, y, 2.0),
+            Piece { color, position },
+            ChildOf(root),
+        ))
+        .id();
+    spawn_piece_shadow(commands, entity, colors.style, meshes, materials);
+    entity
 }
 
 pub fn update_valid_moves(
     mut commands: Commands,
-    board_query: Query<&Board, Or<(Changed<Board>, Added<Board>)>>,
+    board_query: Query<Ref<Board>>,
+    board_root: Query<Entity, With<BoardUI>>,
+    legal_moves: Res<LegalMovesCache>,
     current_player: Res<CurrentPlayer>,
     ai_query: Query<&crate::ai::AiPlayer>,
+    history_preview: Res<HistoryPreview>,
+    match_rules: Res<ActiveMatchRules>,
+    preferences: Res<Preferences>,
     valid_move_query: Query<Entity, With<ValidMoveIndicator>>,
     colors: Res<BoardColors>,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -154,7 +467,19 @@ pub fn update_valid_moves(
         false
     };
 
-    if board_query.single().is_ok() || current_player.is_changed() || ai_state_changed {
+    // Board变化、从历史浏览返回、对局规则或辅助等级变化时需要重新生成指示器
+    let board_changed = board_query.single().is_ok_and(|board| {
+        board.is_changed()
+            || history_preview.is_changed()
+            || match_rules.is_changed()
+            || preferences.is_changed()
+    });
+
+    if board_changed
+        || current_player.is_changed()
+        || history_preview.is_changed()
+        || ai_state_changed
+    {
         // 标记旧的有效移动指示器为删除
         for entity in valid_move_query.iter() {
             commands.entity(entity).insert(ToDelete);
@@ -167,40 +492,64 @@ pub fn update_valid_moves(
             false
         };
 
-        // 只有在非AI思考状态下才显示有效移动指示器
-        if !ai_is_thinking {
-            if let Ok(board) = board_query.single() {
-                let valid_moves = board.get_valid_moves_list(current_player.0);
-
-                for move_option in valid_moves {
-                    let (row, col) = Board::position_to_coords(move_option.position);
-                    let x = (col as f32 - 3.5) * SQUARE_SIZE;
-                    let y = (3.5 - row as f32) * SQUARE_SIZE;
-
-                    commands.spawn((
-                        Mesh2d(meshes.add(Circle::new(PIECE_RADIUS * 0.6))),
-                        MeshMaterial2d(materials.add(ColorMaterial::from(colors.valid_move_color))),
-                        Transform::from_xyz(x, y, 1.5),
-                        ValidMoveIndicator {
-                            position: move_option.position,
-                        },
-                    ));
-                }
+        // 只有在非AI思考状态、未浏览历史且对局规则和辅助等级都允许时才显示有效移动指示器
+        if !ai_is_thinking
+            && !history_preview.is_previewing()
+            && preferences
+                .assist
+                .allowed(match_rules.allowed())
+                .legal_move_hints
+        {
+            let board = board_query.single().ok().filter(|_| board_changed);
+            if let (Some(board), Ok(root)) = (board, board_root.single()) {
+                spawn_valid_move_indicators(
+                    &mut commands,
+                    root,
+                    legal_moves.moves(&board, current_player.0),
+                    &colors,
+                    &mut meshes,
+                    &mut materials,
+                );
             }
         }
     }
 }
 
+/// 在棋盘根实体下为合法走法位掩码中的每个位置生成可落子指示器
+pub fn spawn_valid_move_indicators(
+    commands: &mut Commands,
+    root: Entity,
+    moves: u64,
+    colors: &BoardColors,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) {
+    for position in (0..64u8).filter(|position| moves & (1u64 << position) != 0) {
+        let (row, col) = Board::position_to_coords(position);
+        let x = (col as f32 - 3.5) * SQUARE_SIZE;
+        let y = (3.5 - row as f32) * SQUARE_SIZE;
+
+        commands.spawn((
+            Mesh2d(meshes.add(Circle::new(PIECE_RADIUS * 0.6))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(colors.valid_move_color))),
+            Transform::from_xyz(x, y, 1.5),
+            ValidMoveIndicator { position },
+            ChildOf(root),
+        ));
+    }
+}
+
 #[derive(Resource)]
 pub struct CurrentPlayer(pub PlayerColor);
 
+/// 删除标记的实体及其所有后代
+///
+/// 祖先和后代可能在同一帧都被标记，祖先先被删除时后代已经不存在，因此使用try_despawn
 pub fn cleanup_marked_entities(
     mut commands: Commands,
     marked_entities: Query<Entity, With<ToDelete>>,
 ) {
     for entity in marked_entities.iter() {
-        if let Ok(mut entity_commands) = commands.get_entity(entity) {
-            entity_commands.despawn();
-        }
+        commands.entity(entity).try_despawn();
     }
 }
@@ -0,0 +1,93 @@
+// 对局时钟 - 计时对局中在双方头像左侧显示剩余时间
+//
+// 不计时的对局不显示；轮到走棋的一方时间不足时时钟红白闪烁，减少动效时保持红色不闪烁，
+// 超时的一方时钟停在0:00并显示为红色
+
+use crate::{
+    accessibility::MotionSettings,
+    clock::{clock_text, GameClock},
+    game::{GameLog, PlayerColor},
+};
+use bevy::prelude::*;
+
+/// 时钟文字的正常颜色
+const CLOCK_COLOR: Color = Color::WHITE;
+
+/// 时间不足和超时时的颜色
+const CLOCK_LOW_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+
+/// 时间不足时闪烁一次的周期（秒）
+const CLOCK_FLASH_PERIOD_SECS: f32 = 0.5;
+
+/// 一方的时钟
+#[derive(Component)]
+pub struct PlayerClock(pub PlayerColor);
+
+/// 在头像中创建时钟，时钟绝对定位在头像左侧，不影响顶部和底部区域的布局
+pub fn spawn_player_clock(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    color: PlayerColor,
+) {
+    parent.spawn((
+        Text::new("0:00"),
+        TextFont {
+            font: font.clone(),
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(CLOCK_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(60.0),
+            top: Val::Px(12.0),
+            ..default()
+        },
+        Visibility::Hidden,
+        PlayerClock(color),
+    ));
+}
+
+/// 某一时刻时间不足的时钟是否显示为红色，减少动效时一直是红色
+pub fn clock_flash_on(seconds: f32, reduced_motion: bool) -> bool {
+    reduced_motion || (seconds / CLOCK_FLASH_PERIOD_SECS).fract() < 0.5
+}
+
+/// 按剩余时间更新双方的时钟
+pub fn update_player_clocks(
+    clock: Res<GameClock>,
+    log_query: Query<&GameLog>,
+    motion_settings: Res<MotionSettings>,
+    time: Res<Time>,
+    mut clock_query: Query<(&PlayerClock, &mut Text, &mut TextColor, &mut Visibility)>,
+) {
+    let to_move = log_query
+        .single()
+        .ok()
+        .filter(|log| !log.is_game_over())
+        .map(GameLog::to_move);
+    let flash_on = clock_flash_on(time.elapsed_secs(), motion_settings.reduced_motion);
+
+    for (player_clock, mut text, mut text_color, mut visibility) in clock_query.iter_mut() {
+        if !clock.is_timed() {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        visibility.set_if_neq(Visibility::Inherited);
+
+        let color = player_clock.0;
+        let label = clock_text(clock.remaining(color));
+        // 只在显示的秒数变化时写入，避免每帧触发文本重新布局
+        if text.0 != label {
+            text.0 = label;
+        }
+
+        let warning = clock.flagged() == Some(color)
+            || (clock.is_low(color) && to_move == Some(color) && flash_on);
+        text_color.set_if_neq(TextColor(if warning {
+            CLOCK_LOW_COLOR
+        } else {
+            CLOCK_COLOR
+        }));
+    }
+}
@@ -0,0 +1,65 @@
+// 教练提示栏 - 棋盘下方显示最近一步的点评
+
+use crate::{
+    assist::ActiveMatchRules,
+    coach::CoachState,
+    fonts::LocalizedText,
+    localization::{TextArgs, TextKey},
+    preferences::Preferences,
+};
+use bevy::prelude::*;
+
+/// 点评文本的颜色
+const COACH_TEXT_COLOR: Color = Color::srgb(1.0, 0.92, 0.6);
+
+#[derive(Component)]
+pub struct CoachTicker;
+
+/// 在棋盘下方的区域中创建提示栏，没有点评时隐藏但保留位置，避免布局跳动
+pub fn spawn_coach_ticker(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
+    parent.spawn((
+        Text::default(),
+        TextKey::CoachSolid,
+        TextArgs::default(),
+        TextFont {
+            font: font.clone(),
+            font_size: 13.0,
+            ..default()
+        },
+        TextColor(COACH_TEXT_COLOR),
+        Node {
+            height: Val::Px(18.0), // 固定高度
+            margin: UiRect::bottom(Val::Px(4.0)),
+            ..default()
+        },
+        Visibility::Hidden,
+        CoachTicker,
+        LocalizedText,
+    ));
+}
+
+/// 点评、教练模式开关或对局规则变化时更新提示栏
+pub fn update_coach_ticker(
+    coach: Res<CoachState>,
+    preferences: Res<Preferences>,
+    match_rules: Res<ActiveMatchRules>,
+    mut ticker_query: Query<(&mut TextKey, &mut TextArgs, &mut Visibility), With<CoachTicker>>,
+) {
+    if !coach.is_changed() && !preferences.is_changed() && !match_rules.is_changed() {
+        return;
+    }
+
+    let comment = coach
+        .comment
+        .filter(|_| preferences.assist.permitted(match_rules.allowed()).coach);
+    for (mut key, mut args, mut visibility) in ticker_query.iter_mut() {
+        let Some(comment) = comment else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        let (comment_key, comment_args) = comment.text();
+        key.set_if_neq(comment_key);
+        args.set_if_neq(TextArgs(comment_args));
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
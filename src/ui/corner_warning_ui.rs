@@ -0,0 +1,227 @@
+use super::{spawn_button, ButtonStyle, FocusScope, GameUI, ToDelete};
+use crate::{
+    assist::{CornerWarningState, PendingCornerWarning},
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    game::{Board, GameLog, MoveAction, MoveCommand, PlayerColor},
+    input::InputGate,
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    preferences::Preferences,
+};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct CornerWarningDialog;
+
+/// 让出角位提醒对话框中的选项
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CornerWarningChoice {
+    /// 仍然落子
+    PlayAnyway,
+    /// 取消，重新选择走法
+    Cancel,
+    /// 落子并不再提醒
+    DontWarnAgain,
+}
+
+#[derive(Component)]
+pub struct CornerWarningButton(pub CornerWarningChoice);
+
+pub fn manage_corner_warning_dialog(
+    mut commands: Commands,
+    warning_state: Res<CornerWarningState>,
+    dialog_query: Query<Entity, With<CornerWarningDialog>>,
+    ui_root: Query<Entity, With<GameUI>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    if warning_state.is_changed() {
+        // 标记现有的对话框为删除
+        for entity in dialog_query.iter() {
+            commands.entity(entity).insert(ToDelete);
+        }
+
+        // 有待确认的走法时创建新的对话框
+        if let (Some(pending), Ok(root)) = (warning_state.pending, ui_root.single()) {
+            spawn_corner_warning_dialog(
+                &mut commands,
+                root,
+                &language_settings,
+                &font_assets,
+                pending,
+            );
+        }
+    }
+}
+
+fn spawn_corner_warning_dialog(
+    commands: &mut Commands,
+    root: Entity,
+    language_settings: &LanguageSettings,
+    font_assets: &FontAssets,
+    pending: PendingCornerWarning,
+) {
+    let font = get_font_for_language(language_settings, font_assets);
+    let opponent_name = match pending.opponent {
+        PlayerColor::Black => TextKey::BlackName,
+        PlayerColor::White => TextKey::WhiteName,
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+            CornerWarningDialog,
+            FocusScope,
+            ChildOf(root),
+        ))
+        .with_children(|overlay| {
+            overlay
+                .spawn((
+                    Node {
+                        width: Val::Px(300.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(10.0),
+                        padding: UiRect::all(Val::Px(15.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+                    BorderColor(Color::srgb(0.6, 0.6, 0.6)),
+                    BorderRadius::all(Val::Px(10.0)),
+                ))
+                .with_children(|dialog| {
+                    // 提醒文本
+                    dialog.spawn((
+                        Text::default(),
+                        TextKey::CornerWarningFormat,
+                        TextArgs(vec![
+                            TextArg::Key(opponent_name),
+                            TextArg::Value(Board::position_to_notation(pending.corner)),
+                        ]),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        Node {
+                            margin: UiRect::bottom(Val::Px(10.0)),
+                            ..default()
+                        },
+                        LocalizedText,
+                    ));
+
+                    let choices = [
+                        (
+                            CornerWarningChoice::PlayAnyway,
+                            TextKey::CornerWarningPlay,
+                            Color::srgb(0.7, 0.4, 0.2),
+                        ),
+                        (
+                            CornerWarningChoice::Cancel,
+                            TextKey::CornerWarningCancel,
+                            Color::srgb(0.25, 0.25, 0.3),
+                        ),
+                        (
+                            CornerWarningChoice::DontWarnAgain,
+                            TextKey::CornerWarningDisable,
+                            Color::srgb(0.3, 0.3, 0.3),
+                        ),
+                    ];
+
+                    for (choice, text_key, color) in choices {
+                        let style = ButtonStyle {
+                            width: Val::Px(220.0),
+                            color,
+                            font_size: 16.0,
+                            ..default()
+                        };
+                        spawn_button(dialog, &style, &font, text_key, CornerWarningButton(choice));
+                    }
+                });
+        });
+}
+
+/// 对话框按钮在松开时生效
+///
+/// 触摸落子在抬起时触发，若按下时就关闭对话框，抬起的同一次触摸会落到下方的棋盘上。
+/// 鼠标或手指按下按钮时记下该按钮，松开时按松开的位置判断是否仍在按钮上，移出按钮后松开视为放弃；
+/// 手指抬起时按钮直接从Pressed变为None，因此不能用Interaction判断松开的位置。
+/// 手柄确认按下按钮时没有按住的指针，立即生效
+pub fn handle_corner_warning_buttons(
+    interaction_query: Query<
+        (Entity, &Interaction),
+        (Changed<Interaction>, With<CornerWarningButton>),
+    >,
+    button_query: Query<(&CornerWarningButton, &ComputedNode, &GlobalTransform)>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    touch_input: Res<Touches>,
+    windows: Query<&Window>,
+    mut armed_button: Local<Option<Entity>>,
+    mut warning_state: ResMut<CornerWarningState>,
+    mut preferences: ResMut<Preferences>,
+    log_query: Query<&GameLog>,
+    mut move_commands: EventWriter<MoveCommand>,
+    mut input_gate: ResMut<InputGate>,
+) {
+    let pointer_down =
+        mouse_input.pressed(MouseButton::Left) || touch_input.iter().next().is_some();
+    let mut chosen = None;
+    for (entity, interaction) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            if pointer_down {
+                *armed_button = Some(entity);
+            } else {
+                chosen = Some(entity);
+            }
+        }
+    }
+
+    // 指针松开时，松开的位置仍在按下的按钮上才生效
+    if mouse_input.just_released(MouseButton::Left) || touch_input.any_just_released() {
+        let window = windows.single().ok();
+        let release_position = touch_input
+            .iter_just_released()
+            .next()
+            .map(|touch| touch.position())
+            .or_else(|| window?.cursor_position());
+        let scale_factor = window.map_or(1.0, Window::scale_factor);
+        if let (Some(entity), Some(position)) = (armed_button.take(), release_position) {
+            let released_over = button_query.get(entity).is_ok_and(|(_, node, transform)| {
+                Rect::from_center_size(transform.translation().truncate(), node.size())
+                    .contains(position * scale_factor)
+            });
+            if released_over {
+                chosen = Some(entity);
+            }
+        }
+    }
+
+    let Some((button, ..)) = chosen.and_then(|entity| button_query.get(entity).ok()) else {
+        return;
+    };
+    let Some(pending) = warning_state.pending.take() else {
+        return;
+    };
+
+    match button.0 {
+        // 取消后允许本回合重新选择落子位置
+        CornerWarningChoice::Cancel => input_gate.release(),
+        CornerWarningChoice::PlayAnyway | CornerWarningChoice::DontWarnAgain => {
+            if button.0 == CornerWarningChoice::DontWarnAgain {
+                preferences.assist.corner_warning = false;
+            }
+            if let Ok(log) = log_query.single() {
+                move_commands.write(log.next_command(MoveAction::Place(pending.position)));
+            }
+        }
+    }
+}
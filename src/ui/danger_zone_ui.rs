@@ -0,0 +1,126 @@
+use super::{BoardUI, ToDelete, UiState, SQUARE_SIZE};
+use crate::{
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    localization::{LanguageSettings, TextKey},
+};
+use bevy::prelude::*;
+
+/// 危险区域标注层的Z坐标 - 位于棋盘格之上、棋子之下
+const OVERLAY_Z: f32 = 0.5;
+
+#[derive(Component)]
+pub struct DangerZoneButton;
+
+#[derive(Component)]
+pub struct DangerZoneOverlay;
+
+/// 棋盘上需要特别注意的区域
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SquareZone {
+    /// 角 - 占据后永远不会被翻转
+    Corner,
+    /// C位 - 与角相邻的边上格子
+    CSquare,
+    /// X位 - 与角斜向相邻的格子，过早占据容易让出角
+    XSquare,
+}
+
+impl SquareZone {
+    /// 获取位置所属的区域
+    pub fn of(position: u8) -> Option<Self> {
+        match position {
+            0 | 7 | 56 | 63 => Some(Self::Corner),
+            1 | 6 | 8 | 15 | 48 | 55 | 57 | 62 => Some(Self::CSquare),
+            9 | 14 | 49 | 54 => Some(Self::XSquare),
+            _ => None,
+        }
+    }
+
+    /// 区域的标注颜色
+    pub fn color(&self) -> Color {
+        match self {
+            Self::Corner => Color::srgba(0.95, 0.8, 0.1, 0.45),
+            Self::CSquare => Color::srgba(0.95, 0.55, 0.1, 0.4),
+            Self::XSquare => Color::srgba(0.9, 0.15, 0.15, 0.45),
+        }
+    }
+
+    /// 区域的简短标签
+    pub fn label_key(&self) -> TextKey {
+        match self {
+            Self::Corner => TextKey::ZoneCorner,
+            Self::CSquare => TextKey::ZoneCSquare,
+            Self::XSquare => TextKey::ZoneXSquare,
+        }
+    }
+}
+
+pub fn handle_danger_zone_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<DangerZoneButton>)>,
+    mut ui_state: ResMut<UiState>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            ui_state.show_danger_zones = !ui_state.show_danger_zones;
+        }
+    }
+}
+
+/// 根据开关显示或移除危险区域标注，棋盘重建后自动重新生成
+pub fn manage_danger_zone_overlay(
+    mut commands: Commands,
+    ui_state: Res<UiState>,
+    overlay_query: Query<Entity, With<DangerZoneOverlay>>,
+    board_root: Query<Entity, With<BoardUI>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    if !ui_state.show_danger_zones {
+        for entity in overlay_query.iter() {
+            commands.entity(entity).insert(ToDelete);
+        }
+        return;
+    }
+
+    if !overlay_query.is_empty() {
+        return;
+    }
+    // 重新开始时棋盘根实体重建之前不生成
+    let Ok(root) = board_root.single() else {
+        return;
+    };
+
+    let font = get_font_for_language(&language_settings, &font_assets);
+    for position in 0..64u8 {
+        let Some(zone) = SquareZone::of(position) else {
+            continue;
+        };
+
+        let row = (position / 8) as f32;
+        let col = (position % 8) as f32;
+        let x = (col - 3.5) * SQUARE_SIZE;
+        let y = (3.5 - row) * SQUARE_SIZE;
+
+        commands
+            .spawn((
+                Sprite::from_color(zone.color(), Vec2::splat(SQUARE_SIZE)),
+                Transform::from_xyz(x, y, OVERLAY_Z),
+                DangerZoneOverlay,
+                ChildOf(root),
+            ))
+            .with_children(|square| {
+                square.spawn((
+                    Text2d::default(),
+                    zone.label_key(),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 9.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    Transform::from_xyz(0.0, -SQUARE_SIZE * 0.3, 0.1),
+                    LocalizedText,
+                ));
+            });
+    }
+}
@@ -0,0 +1,107 @@
+// 搜索统计调试层 - F4开关，在屏幕左下角显示对局AI置换表的大小、命中率和读写次数
+//
+// 用于在手机和Web上确认置换表的内存上限是否合适：命中率明显偏低时可以在设置中调大搜索内存
+
+use super::{GameUI, ToDelete, UiState};
+use crate::{
+    ai::AiPlayer,
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    input::{InputAction, InputMap},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    state::GameState,
+};
+use bevy::prelude::*;
+
+/// 调试层的文本颜色
+const DEBUG_TEXT_COLOR: Color = Color::srgb(0.6, 1.0, 0.6);
+
+#[derive(Component)]
+pub struct DebugOverlay;
+
+/// 统计文本的键和参数，本局没有AI时显示提示；置换表还没有创建时各项计数为0
+pub fn debug_overlay_text(ai_player: Option<&AiPlayer>) -> (TextKey, TextArgs) {
+    let Some(ai_player) = ai_player else {
+        return (TextKey::DebugNoSearch, TextArgs::default());
+    };
+    let stats = ai_player.table_stats().unwrap_or_default();
+    (
+        TextKey::DebugTableFormat,
+        TextArgs(vec![
+            TextArg::Number(ai_player.table_size_mb().into()),
+            TextArg::Value(format!("{:.1}%", stats.hit_rate() * 100.0)),
+            TextArg::Number(stats.probes),
+            TextArg::Number(stats.stores),
+        ]),
+    )
+}
+
+/// 默认F4切换搜索统计显示
+pub fn toggle_debug_overlay(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut ui_state: ResMut<UiState>,
+) {
+    if input_map.just_pressed(InputAction::ToggleDebugOverlay, &keyboard_input) {
+        ui_state.show_debug_overlay = !ui_state.show_debug_overlay;
+    }
+}
+
+/// 根据开关显示或移除调试层，对局界面重建后自动重新生成
+pub fn manage_debug_overlay(
+    mut commands: Commands,
+    ui_state: Res<UiState>,
+    overlay_query: Query<Entity, With<DebugOverlay>>,
+    ui_root: Query<Entity, With<GameUI>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    if !ui_state.show_debug_overlay {
+        for entity in overlay_query.iter() {
+            commands.entity(entity).insert(ToDelete);
+        }
+        return;
+    }
+
+    if !overlay_query.is_empty() {
+        return;
+    }
+    let Ok(root) = ui_root.single() else {
+        return;
+    };
+
+    commands.spawn((
+        Text::default(),
+        TextKey::DebugNoSearch,
+        TextArgs::default(),
+        TextFont {
+            font: get_font_for_language(&language_settings, &font_assets),
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(DEBUG_TEXT_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(8.0),
+            bottom: Val::Px(8.0),
+            padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        DebugOverlay,
+        LocalizedText,
+        ChildOf(root),
+        StateScoped(GameState::Playing),
+    ));
+}
+
+/// 每帧刷新统计文本，只在内容变化时修改
+pub fn update_debug_overlay(
+    ai_query: Query<&AiPlayer>,
+    mut overlay_query: Query<(&mut TextKey, &mut TextArgs), With<DebugOverlay>>,
+) {
+    let (key, args) = debug_overlay_text(ai_query.single().ok());
+    for (mut overlay_key, mut overlay_args) in overlay_query.iter_mut() {
+        overlay_key.set_if_neq(key);
+        overlay_args.set_if_neq(args.clone());
+    }
+}
@@ -0,0 +1,302 @@
+// 终局特效 - 胜利时彩纸飞散、分数闪动，失败时镜头短暂震动、画面蒙上灰色
+//
+// 只在对局刚结束（从Playing进入GameOver）时播放一次，从复盘分析回到结果界面不再重播。
+// 特效实体带有StateScoped组件，离开结果界面时自动清理；镜头震动结束或离开结果界面时镜头回到原点。
+// 减少动效时不生成彩纸、不震动镜头、分数不闪动，失败的灰色蒙层直接以最终透明度显示
+
+use super::{ScoreEntry, BOARD_SIZE};
+use crate::{
+    accessibility::MotionSettings,
+    ai::{endgame::GameOutcome, AiPlayer},
+    game::Board,
+    state::GameState,
+};
+use bevy::prelude::*;
+use rand::Rng;
+
+/// 彩纸数量
+const CONFETTI_COUNT: usize = 80;
+
+/// 彩纸持续时间（秒）
+const CONFETTI_DURATION_SECS: f32 = 2.5;
+
+/// 彩纸的重力加速度（世界坐标/秒²）
+const CONFETTI_GRAVITY: f32 = 420.0;
+
+/// 彩纸的Z坐标 - 位于棋盘所有元素之上
+const CONFETTI_Z: f32 = 8.0;
+
+/// 彩纸颜色
+const CONFETTI_COLORS: [Color; 5] = [
+    Color::srgb(0.95, 0.3, 0.3),
+    Color::srgb(0.95, 0.8, 0.2),
+    Color::srgb(0.3, 0.8, 0.4),
+    Color::srgb(0.3, 0.6, 0.95),
+    Color::srgb(0.85, 0.4, 0.9),
+];
+
+/// 分数闪动持续时间（秒）
+const FLOURISH_DURATION_SECS: f32 = 1.2;
+
+/// 分数闪动的次数
+const FLOURISH_PULSES: f32 = 3.0;
+
+/// 分数闪动时的最大放大比例
+const FLOURISH_SCALE: f32 = 0.3;
+
+/// 胜利后玩家分数的边框颜色
+const FLOURISH_BORDER: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// 镜头震动持续时间（秒）
+const SHAKE_DURATION_SECS: f32 = 0.4;
+
+/// 镜头震动的最大偏移（世界坐标）
+const SHAKE_INTENSITY: f32 = 8.0;
+
+/// 失败蒙层淡入时间（秒）
+const TINT_FADE_SECS: f32 = 0.6;
+
+/// 失败蒙层的颜色和最终透明度
+const TINT_COLOR: Color = Color::srgba(0.25, 0.25, 0.3, 0.45);
+
+/// 对局结果对应的终局特效
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndGameEffect {
+    Victory,
+    Defeat,
+}
+
+impl EndGameEffect {
+    /// 玩家视角的对局结果对应的特效，平局没有特效
+    pub fn for_outcome(outcome: GameOutcome) -> Option<Self> {
+        match outcome {
+            GameOutcome::Win => Some(Self::Victory),
+            GameOutcome::Loss => Some(Self::Defeat),
+            GameOutcome::Draw => None,
+        }
+    }
+}
+
+/// 一片彩纸
+#[derive(Component)]
+pub struct Confetti {
+    velocity: Vec2,
+    spin: f32,
+    timer: Timer,
+}
+
+/// 胜利后闪动的分数条目
+#[derive(Component)]
+pub struct ScoreFlourish {
+    timer: Timer,
+}
+
+/// 失败后的灰色蒙层
+#[derive(Component)]
+pub struct DefeatTint {
+    timer: Timer,
+}
+
+/// 正在进行的镜头震动
+#[derive(Resource)]
+pub struct CameraShake {
+    timer: Timer,
+}
+
+impl CameraShake {
+    /// 当前的最大偏移，随时间线性衰减到0
+    pub fn intensity(&self) -> f32 {
+        SHAKE_INTENSITY * (1.0 - self.timer.fraction())
+    }
+}
+
+/// 对局结束时按玩家视角的结果播放特效，没有AI对手（沙盒）时不播放
+pub fn start_end_game_effects(
+    mut commands: Commands,
+    board_query: Query<&Board>,
+    ai_query: Query<&AiPlayer>,
+    entry_query: Query<(Entity, &ScoreEntry)>,
+    motion_settings: Res<MotionSettings>,
+) {
+    let (Ok(board), Ok(ai_player)) = (board_query.single(), ai_query.single()) else {
+        return;
+    };
+    let player_color = ai_player.color.opposite();
+    let score =
+        board.count_pieces(player_color) as i32 - board.count_pieces(ai_player.color) as i32;
+    let Some(effect) = EndGameEffect::for_outcome(GameOutcome::from_score(score)) else {
+        return;
+    };
+    let reduced_motion = motion_settings.reduced_motion;
+
+    match effect {
+        EndGameEffect::Victory => {
+            for (entity, ScoreEntry(color)) in entry_query.iter() {
+                if *color != player_color {
+                    continue;
+                }
+                commands.entity(entity).insert(BorderColor(FLOURISH_BORDER));
+                if !reduced_motion {
+                    commands.entity(entity).insert(ScoreFlourish {
+                        timer: Timer::from_seconds(FLOURISH_DURATION_SECS, TimerMode::Once),
+                    });
+                }
+            }
+            if !reduced_motion {
+                spawn_confetti(&mut commands);
+            }
+        }
+        EndGameEffect::Defeat => {
+            let mut tint = TINT_COLOR;
+            if !reduced_motion {
+                tint.set_alpha(0.0);
+                commands.insert_resource(CameraShake {
+                    timer: Timer::from_seconds(SHAKE_DURATION_SECS, TimerMode::Once),
+                });
+            }
+            // 蒙层位于其他界面之下，只让棋盘变暗，结果和用时统计仍然清晰
+            commands.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(tint),
+                GlobalZIndex(-1),
+                Pickable::IGNORE,
+                DefeatTint {
+                    timer: Timer::from_seconds(TINT_FADE_SECS, TimerMode::Once),
+                },
+                StateScoped(GameState::GameOver),
+            ));
+        }
+    }
+}
+
+/// 从棋盘上方中间向两侧喷出彩纸
+fn spawn_confetti(commands: &mut Commands) {
+    let mut rng = rand::thread_rng();
+    for index in 0..CONFETTI_COUNT {
+        let color = CONFETTI_COLORS[index % CONFETTI_COLORS.len()];
+        let origin = Vec2::new(rng.gen_range(-0.2..0.2) * BOARD_SIZE, BOARD_SIZE * 0.35);
+        let velocity = Vec2::new(rng.gen_range(-260.0..260.0), rng.gen_range(120.0..420.0));
+        commands.spawn((
+            Sprite::from_color(color, Vec2::new(6.0, 10.0)),
+            Transform::from_translation(origin.extend(CONFETTI_Z)).with_rotation(
+                Quat::from_rotation_z(rng.gen_range(0.0..std::f32::consts::TAU)),
+            ),
+            Confetti {
+                velocity,
+                spin: rng.gen_range(-8.0..8.0),
+                timer: Timer::from_seconds(
+                    CONFETTI_DURATION_SECS * rng.gen_range(0.7..1.0),
+                    TimerMode::Once,
+                ),
+            },
+            StateScoped(GameState::GameOver),
+        ));
+    }
+}
+
+/// 彩纸受重力下落、旋转并逐渐淡出，结束后删除
+pub fn animate_confetti(
+    mut commands: Commands,
+    mut confetti_query: Query<(Entity, &mut Confetti, &mut Transform, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut confetti, mut transform, mut sprite) in confetti_query.iter_mut() {
+        confetti.timer.tick(time.delta());
+        if confetti.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        confetti.velocity.y -= CONFETTI_GRAVITY * delta;
+        transform.translation += (confetti.velocity * delta).extend(0.0);
+        transform.rotate_z(confetti.spin * delta);
+        sprite
+            .color
+            .set_alpha(1.0 - confetti.timer.fraction().powi(2));
+    }
+}
+
+/// 胜利方的分数条目放大闪动几次后复原
+pub fn animate_score_flourish(
+    mut commands: Commands,
+    mut flourish_query: Query<(Entity, &mut ScoreFlourish, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut flourish, mut transform) in flourish_query.iter_mut() {
+        flourish.timer.tick(time.delta());
+        if flourish.timer.finished() {
+            transform.scale = Vec3::ONE;
+            commands.entity(entity).remove::<ScoreFlourish>();
+            continue;
+        }
+
+        let progress = flourish.timer.fraction();
+        let pulse = (progress * FLOURISH_PULSES * std::f32::consts::PI)
+            .sin()
+            .abs();
+        transform.scale = Vec3::splat(1.0 + FLOURISH_SCALE * pulse * (1.0 - progress));
+    }
+}
+
+/// 失败蒙层淡入到最终透明度
+pub fn fade_in_defeat_tint(
+    mut tint_query: Query<(&mut DefeatTint, &mut BackgroundColor)>,
+    time: Res<Time>,
+) {
+    for (mut tint, mut background) in tint_query.iter_mut() {
+        if tint.timer.finished() {
+            continue;
+        }
+        tint.timer.tick(time.delta());
+        background
+            .0
+            .set_alpha(TINT_COLOR.alpha() * tint.timer.fraction());
+    }
+}
+
+/// 镜头在原点附近随机偏移，幅度逐渐衰减，结束后回到原点
+pub fn shake_camera(
+    mut commands: Commands,
+    shake: Option<ResMut<CameraShake>>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+    time: Res<Time>,
+) {
+    let Some(mut shake) = shake else {
+        return;
+    };
+    shake.timer.tick(time.delta());
+    let offset = if shake.timer.finished() {
+        commands.remove_resource::<CameraShake>();
+        Vec2::ZERO
+    } else {
+        let mut rng = rand::thread_rng();
+        let intensity = shake.intensity();
+        Vec2::new(
+            rng.gen_range(-intensity..=intensity),
+            rng.gen_range(-intensity..=intensity),
+        )
+    };
+
+    for mut transform in camera_query.iter_mut() {
+        transform.translation.x = offset.x;
+        transform.translation.y = offset.y;
+    }
+}
+
+/// 离开结果界面时停止震动，镜头回到原点，棋盘输入的坐标换算依赖镜头位于原点
+pub fn stop_camera_shake(
+    mut commands: Commands,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    commands.remove_resource::<CameraShake>();
+    for mut transform in camera_query.iter_mut() {
+        transform.translation.x = 0.0;
+        transform.translation.y = 0.0;
+    }
+}
@@ -0,0 +1,169 @@
+use super::SQUARE_SIZE;
+use crate::{accessibility::MotionSettings, preferences::Preferences, state::GameState};
+use bevy::prelude::*;
+
+/// 点击波纹持续时间（秒）
+const RIPPLE_DURATION_SECS: f32 = 0.35;
+
+/// 波纹初始半径
+const RIPPLE_RADIUS: f32 = SQUARE_SIZE * 0.25;
+
+/// 波纹结束时相对初始大小的缩放
+const RIPPLE_MAX_SCALE: f32 = 2.5;
+
+/// 波纹初始不透明度
+const RIPPLE_ALPHA: f32 = 0.6;
+
+/// 波纹的Z坐标 - 位于棋子之上
+const RIPPLE_Z: f32 = 5.0;
+
+/// 自定义光标的Z坐标 - 位于所有棋盘元素之上
+const CURSOR_Z: f32 = 10.0;
+
+/// 点击或触摸位置的波纹
+#[derive(Component)]
+pub struct Ripple {
+    timer: Timer,
+}
+
+/// 自定义光标
+#[derive(Component)]
+pub struct CustomCursor;
+
+/// 屏幕坐标转换为世界坐标
+fn screen_to_world(
+    camera_query: &Query<(&Camera, &GlobalTransform)>,
+    screen_position: Vec2,
+) -> Option<Vec2> {
+    let (camera, camera_transform) = camera_query.single().ok()?;
+    camera
+        .viewport_to_world_2d(camera_transform, screen_position)
+        .ok()
+}
+
+/// 在点击和触摸位置生成波纹，无论该位置能否落子都立即给出反馈
+pub fn spawn_input_ripples(
+    mut commands: Commands,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    touch_input: Res<Touches>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let mut positions: Vec<Vec2> = touch_input
+        .iter_just_pressed()
+        .map(|touch| touch.position())
+        .collect();
+    if mouse_input.just_pressed(MouseButton::Left) {
+        positions.extend(windows.single().ok().and_then(Window::cursor_position));
+    }
+
+    for screen_position in positions {
+        let Some(world_position) = screen_to_world(&camera_query, screen_position) else {
+            continue;
+        };
+
+        commands.spawn((
+            Mesh2d(meshes.add(Annulus::new(RIPPLE_RADIUS * 0.8, RIPPLE_RADIUS))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgba(
+                1.0,
+                1.0,
+                1.0,
+                RIPPLE_ALPHA,
+            )))),
+            Transform::from_translation(world_position.extend(RIPPLE_Z)),
+            Ripple {
+                timer: Timer::from_seconds(RIPPLE_DURATION_SECS, TimerMode::Once),
+            },
+            StateScoped(GameState::Playing),
+        ));
+    }
+}
+
+/// 波纹扩散并淡出，结束后删除；减少动效时只短暂显示，不做动画
+pub fn animate_ripples(
+    mut commands: Commands,
+    mut ripple_query: Query<(
+        Entity,
+        &mut Ripple,
+        &mut Transform,
+        &MeshMaterial2d<ColorMaterial>,
+    )>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    motion_settings: Res<MotionSettings>,
+    time: Res<Time>,
+) {
+    for (entity, mut ripple, mut transform, material) in ripple_query.iter_mut() {
+        ripple.timer.tick(time.delta());
+        if ripple.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if motion_settings.reduced_motion {
+            continue;
+        }
+
+        let progress = ripple.timer.fraction();
+        transform.scale = Vec3::splat(1.0 + (RIPPLE_MAX_SCALE - 1.0) * progress);
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.color.set_alpha(RIPPLE_ALPHA * (1.0 - progress));
+        }
+    }
+}
+
+/// 桌面端可选的自定义光标：开启后隐藏系统光标，用跟随指针的圆环代替
+pub fn update_custom_cursor(
+    mut commands: Commands,
+    preferences: Res<Preferences>,
+    mut windows: Query<&mut Window>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    touch_input: Res<Touches>,
+    mut cursor_query: Query<(Entity, &mut Transform), With<CustomCursor>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let Ok(mut window) = windows.single_mut() else {
+        return;
+    };
+
+    // 触摸操作时没有光标，始终使用系统设置
+    let cursor_world = window
+        .cursor_position()
+        .filter(|_| preferences.input.custom_cursor && touch_input.iter().next().is_none())
+        .and_then(|position| screen_to_world(&camera_query, position));
+
+    let show_system_cursor = cursor_world.is_none();
+    if window.cursor_options.visible != show_system_cursor {
+        window.cursor_options.visible = show_system_cursor;
+    }
+
+    match (cursor_world, cursor_query.single_mut()) {
+        (Some(position), Ok((_, mut transform))) => {
+            transform.translation = position.extend(CURSOR_Z);
+        }
+        (Some(position), Err(_)) => {
+            commands
+                .spawn((
+                    Mesh2d(meshes.add(Annulus::new(5.0, 8.0))),
+                    MeshMaterial2d(materials.add(ColorMaterial::from(Color::WHITE))),
+                    Transform::from_translation(position.extend(CURSOR_Z)),
+                    CustomCursor,
+                ))
+                .with_children(|cursor| {
+                    cursor.spawn((
+                        Mesh2d(meshes.add(Circle::new(2.0))),
+                        MeshMaterial2d(
+                            materials.add(ColorMaterial::from(Color::srgb(0.05, 0.05, 0.05))),
+                        ),
+                        Transform::from_xyz(0.0, 0.0, 0.1),
+                    ));
+                });
+        }
+        (None, Ok((entity, _))) => {
+            commands.entity(entity).despawn();
+        }
+        (None, Err(_)) => {}
+    }
+}
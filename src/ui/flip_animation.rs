@@ -0,0 +1,109 @@
+// 翻转连锁动画 - 落子后被夹住的棋子由近到远依次翻转，形成波纹效果
+//
+// 走棋命令应用成功后发送FlipChainEvent，包含按方向分组、按距离排列的翻转棋子。
+// 重新生成棋子时为这些棋子加上FlipAnimation：先显示翻转前的颜色，
+// 按与落子位置的距离错开开始时间，横向压扁后换成新颜色再展开
+
+use super::BoardColors;
+use crate::{accessibility::MotionSettings, game::PlayerColor};
+use bevy::prelude::*;
+
+/// 相邻距离的棋子开始翻转的间隔（秒）
+const FLIP_STAGGER_SECS: f32 = 0.06;
+
+/// 单个棋子压扁或展开的时长（秒），完整翻转为两倍
+const FLIP_HALF_SECS: f32 = 0.09;
+
+/// 一次落子翻转的棋子
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct FlipChainEvent {
+    pub position: u8,
+    pub player: PlayerColor,
+    /// 按方向分组的翻转棋子，每组由近到远排列
+    pub rays: Vec<Vec<u8>>,
+}
+
+impl FlipChainEvent {
+    /// 每个翻转棋子开始翻转前的等待时间
+    pub fn delays(&self) -> impl Iterator<Item = (u8, f32)> + '_ {
+        self.rays.iter().flat_map(|ray| {
+            ray.iter()
+                .enumerate()
+                .map(|(distance, &position)| (position, distance as f32 * FLIP_STAGGER_SECS))
+        })
+    }
+}
+
+/// 正在翻转的棋子
+#[derive(Component, Debug)]
+pub struct FlipAnimation {
+    /// 开始翻转前的等待时间
+    pub delay: f32,
+    /// 已经经过的时间
+    pub elapsed: f32,
+    /// 翻转前的颜色
+    pub from: Color,
+    /// 翻转后的颜色
+    pub to: Color,
+}
+
+impl FlipAnimation {
+    /// 翻转为指定颜色的动画；减少动效时不生成动画
+    pub fn for_piece(
+        color: PlayerColor,
+        delay: f32,
+        colors: &BoardColors,
+        motion_settings: &MotionSettings,
+    ) -> Option<Self> {
+        if motion_settings.reduced_motion {
+            return None;
+        }
+        let piece_color = |color| match color {
+            PlayerColor::Black => colors.black_piece_color,
+            PlayerColor::White => colors.white_piece_color,
+        };
+        Some(Self {
+            delay,
+            elapsed: 0.0,
+            from: piece_color(color.opposite()),
+            to: piece_color(color),
+        })
+    }
+}
+
+/// 推进翻转动画，结束后移除动画组件
+pub fn animate_piece_flips(
+    mut commands: Commands,
+    mut piece_query: Query<(
+        Entity,
+        &mut FlipAnimation,
+        &mut Transform,
+        &MeshMaterial2d<ColorMaterial>,
+    )>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    for (entity, mut animation, mut transform, material) in piece_query.iter_mut() {
+        animation.elapsed += time.delta_secs();
+        let progress = (animation.elapsed - animation.delay) / FLIP_HALF_SECS;
+
+        // 前半段以旧颜色压扁，后半段以新颜色展开
+        let (width, color) = if progress >= 2.0 {
+            commands.entity(entity).remove::<FlipAnimation>();
+            (1.0, animation.to)
+        } else if progress >= 1.0 {
+            (progress - 1.0, animation.to)
+        } else {
+            (1.0 - progress.max(0.0), animation.from)
+        };
+
+        if transform.scale.x != width {
+            transform.scale.x = width;
+        }
+        if let Some(material) = materials.get_mut(&material.0) {
+            if material.color != color {
+                material.color = color;
+            }
+        }
+    }
+}
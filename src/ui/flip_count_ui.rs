@@ -0,0 +1,53 @@
+// 翻转数标注 - 辅助等级为"显示翻转数"及以上时，在每个可落子指示器右下角标注该步翻转的棋子数
+//
+// 标注作为可落子指示器的子实体生成，指示器重建时随之移除；
+// 只标注右下角，不与残局比分预测在指示器中央的标注重叠
+
+use super::{CurrentPlayer, ValidMoveIndicator, PIECE_RADIUS};
+use crate::{game::Board, preferences::Preferences};
+use bevy::prelude::*;
+
+/// 标注相对指示器的偏移
+const LABEL_OFFSET: Vec3 = Vec3::new(PIECE_RADIUS, -PIECE_RADIUS, 0.1);
+
+/// 翻转数标注的字号
+const LABEL_FONT_SIZE: f32 = 10.0;
+
+/// 可落子指示器上的翻转数标注
+#[derive(Component)]
+pub struct FlipCountLabel {
+    pub count: u32,
+}
+
+/// 可落子指示器重建时，按辅助等级为每个指示器标注翻转的棋子数
+pub fn update_flip_counts(
+    mut commands: Commands,
+    indicator_query: Query<(Entity, &ValidMoveIndicator), Added<ValidMoveIndicator>>,
+    board_query: Query<&Board>,
+    current_player: Res<CurrentPlayer>,
+    preferences: Res<Preferences>,
+) {
+    if indicator_query.is_empty() || !preferences.assist.level.shows_flip_counts() {
+        return;
+    }
+    let Ok(board) = board_query.single() else {
+        return;
+    };
+
+    for (entity, indicator) in indicator_query.iter() {
+        let count = board.flip_count(indicator.position, current_player.0);
+        if count == 0 {
+            continue;
+        }
+        commands.entity(entity).with_child((
+            Text2d::new(count.to_string()),
+            TextFont {
+                font_size: LABEL_FONT_SIZE,
+                ..default()
+            },
+            TextColor(Color::srgba(1.0, 1.0, 1.0, 0.9)),
+            Transform::from_translation(LABEL_OFFSET),
+            FlipCountLabel { count },
+        ));
+    }
+}
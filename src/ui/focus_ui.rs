@@ -0,0 +1,191 @@
+// 界面焦点 - 用手柄在按钮之间导航
+//
+// 所有可见的按钮都可以获得焦点，焦点按钮显示描边。打开模态面板（带FocusScope的实体）时，
+// 焦点只在面板内的按钮之间移动；对局中没有焦点且没有模态面板时，方向和确认操作交给棋盘光标。
+// 十字键移动到该方向上最近的按钮，肩键按从上到下、从左到右的顺序循环，A键按下焦点按钮
+
+use crate::{
+    input::{GamepadAction, GamepadActionEvent},
+    state::GameState,
+};
+use bevy::prelude::*;
+
+/// 焦点描边颜色
+const FOCUS_OUTLINE_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// 模态面板，存在时焦点限制在面板内
+#[derive(Component)]
+pub struct FocusScope;
+
+/// 当前焦点
+#[derive(Resource, Debug, Default)]
+pub struct UiFocus {
+    /// 获得焦点的按钮
+    pub focused: Option<Entity>,
+    /// 对局中且没有打开模态面板，未聚焦按钮时手柄操作棋盘
+    board_active: bool,
+    /// 上一帧由手柄按下、需要复位的按钮
+    pressed: Option<Entity>,
+}
+
+impl UiFocus {
+    /// 手柄的方向和确认操作是否作用于棋盘
+    pub fn on_board(&self) -> bool {
+        self.focused.is_none() && self.board_active
+    }
+}
+
+/// 可以获得焦点的按钮
+struct Candidate {
+    entity: Entity,
+    center: Vec2,
+}
+
+/// 处理焦点导航和按下，需要在界面交互系统之后运行，以免手柄按下被指针状态覆盖
+pub fn apply_ui_focus(
+    mut commands: Commands,
+    mut actions: EventReader<GamepadActionEvent>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut focus: ResMut<UiFocus>,
+    mut button_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &ComputedNode,
+            &InheritedVisibility,
+            &mut Interaction,
+        ),
+        With<Button>,
+    >,
+    scope_query: Query<(), With<FocusScope>>,
+    parent_query: Query<&ChildOf>,
+    state: Res<State<GameState>>,
+) {
+    // 复位上一帧由手柄按下的按钮
+    if let Some(entity) = focus.pressed.take() {
+        if let Ok((.., mut interaction)) = button_query.get_mut(entity) {
+            interaction.set_if_neq(Interaction::None);
+        }
+    }
+
+    let previous = focus.focused;
+    let modal = !scope_query.is_empty();
+    focus.board_active = *state.get() == GameState::Playing && !modal;
+
+    // 在模态面板内的按钮
+    let in_scope = |entity: Entity| {
+        !modal
+            || parent_query
+                .iter_ancestors(entity)
+                .any(|ancestor| scope_query.contains(ancestor))
+    };
+    let mut candidates: Vec<Candidate> = button_query
+        .iter()
+        .filter(|(entity, _, node, visibility, _)| {
+            visibility.get() && node.size().cmpgt(Vec2::ZERO).all() && in_scope(*entity)
+        })
+        .map(|(entity, transform, ..)| Candidate {
+            entity,
+            center: transform.translation().truncate(),
+        })
+        .collect();
+    // 从上到下、从左到右排序，作为循环切换的顺序
+    candidates.sort_by(|a, b| {
+        a.center
+            .y
+            .total_cmp(&b.center.y)
+            .then(a.center.x.total_cmp(&b.center.x))
+    });
+
+    // 焦点按钮被移除、隐藏或不在模态面板内时失去焦点
+    if focus
+        .focused
+        .is_some_and(|entity| !candidates.iter().any(|c| c.entity == entity))
+    {
+        focus.focused = None;
+    }
+
+    // 使用鼠标时清除焦点
+    if cursor_moved.read().count() > 0 {
+        focus.focused = None;
+    }
+
+    for GamepadActionEvent(action) in actions.read() {
+        match *action {
+            GamepadAction::Move(direction) => {
+                if let Some(current) = focus.focused {
+                    focus.focused = Some(nearest_in_direction(&candidates, current, direction));
+                } else if !focus.board_active {
+                    focus.focused = candidates.first().map(|c| c.entity);
+                }
+            }
+            GamepadAction::CycleFocus(offset) => {
+                focus.focused = cycle(&candidates, focus.focused, offset);
+            }
+            GamepadAction::Confirm => {
+                if let Some(entity) = focus.focused {
+                    if let Ok((.., mut interaction)) = button_query.get_mut(entity) {
+                        *interaction = Interaction::Pressed;
+                        focus.pressed = Some(entity);
+                    }
+                } else if !focus.board_active {
+                    focus.focused = candidates.first().map(|c| c.entity);
+                }
+            }
+            GamepadAction::Pause => {}
+        }
+    }
+
+    // 更新焦点描边
+    if focus.focused != previous {
+        if let Some(entity) = previous {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.remove::<Outline>();
+            }
+        }
+        if let Some(entity) = focus.focused {
+            commands.entity(entity).insert(Outline::new(
+                Val::Px(3.0),
+                Val::Px(2.0),
+                FOCUS_OUTLINE_COLOR,
+            ));
+        }
+    }
+}
+
+/// 当前按钮在指定方向上最近的按钮，没有时保持不变
+fn nearest_in_direction(candidates: &[Candidate], current: Entity, direction: IVec2) -> Entity {
+    let Some(origin) = candidates.iter().find(|c| c.entity == current) else {
+        return current;
+    };
+    // 界面坐标与手柄方向一致，y向下为正
+    let direction = direction.as_vec2();
+
+    candidates
+        .iter()
+        .filter(|c| c.entity != current)
+        .filter_map(|c| {
+            let offset = c.center - origin.center;
+            let along = offset.dot(direction);
+            // 偏离方向轴的距离加倍计入，优先选择正对方向的按钮
+            (along > 0.0).then(|| (c.entity, along + offset.perp_dot(direction).abs() * 2.0))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(current, |(entity, _)| entity)
+}
+
+/// 按顺序循环切换焦点，没有焦点时从第一个（或最后一个）开始
+fn cycle(candidates: &[Candidate], current: Option<Entity>, offset: i32) -> Option<Entity> {
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let count = candidates.len() as i32;
+    let index = match current.and_then(|entity| candidates.iter().position(|c| c.entity == entity))
+    {
+        Some(index) => (index as i32 + offset).rem_euclid(count),
+        None if offset >= 0 => 0,
+        None => count - 1,
+    };
+    Some(candidates[index as usize].entity)
+}
@@ -1,14 +1,41 @@
-use super::{ButtonColors, CurrentPlayer, RestartGameEvent, ToggleRulesEvent, UiState};
+use super::{
+    avatar_colors, avatar_glow, spawn_banter_bubble, spawn_button, spawn_coach_ticker, spawn_label,
+    spawn_match_score, spawn_pass_button, spawn_phase_info, spawn_player_clock,
+    spawn_rules_example, spawn_search_caption, spawn_undo_button, turn_text_key, BoardColors,
+    ButtonColors, ButtonStyle, CurrentPlayer, DangerZoneButton, FocusScope, MiniBoardView,
+    RestartGameEvent, RulesExample, ToastEvent, ToggleRulesEvent, TurnChangeAnimation, UiState,
+};
 use crate::{
+    accessibility::{announcement_accessibility_node, AnnouncementText},
     ai::{AiDifficulty, AiPlayer},
+    clock::GameClock,
+    config_assets::ActiveEvalConfig,
     fonts::{get_font_for_language, FontAssets, LocalizedText},
-    game::{Board, PlayerColor},
-    localization::LanguageSettings,
+    game::{Board, GameLog, LegalMovesCache, PlacementRule, PlayerColor},
+    history::{HistoryPreview, MoveHistory},
+    input::{InputAction, InputMap},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    practice::SelectedOpening,
+    preferences::storage::StorageLocation,
+    record::{AnalysisReport, GameRecord},
+    sandbox::SandboxMode,
+    series::MatchSeries,
+    state::GameState,
+    stats::{GameFlipStats, PlayerStats},
 };
 use bevy::prelude::*;
 
+/// 分数组件：黑白棋子图标加子数，轮到走棋的一方有强调边框
+#[derive(Component)]
+pub struct ScoreWidget;
+
+/// 分数组件中一方的条目
+#[derive(Component)]
+pub struct ScoreEntry(pub PlayerColor);
+
+/// 一方的子数文本
 #[derive(Component)]
-pub struct ScoreText;
+pub struct ScoreCount(pub PlayerColor);
 
 #[derive(Component)]
 pub struct CurrentPlayerText;
@@ -18,7 +45,6 @@ pub struct GameStatusText;
 
 #[derive(Component)]
 pub struct PlayerAvatar {
-    #[allow(dead_code)]
     pub player_color: PlayerColor,
 }
 
@@ -43,21 +69,38 @@ pub struct RestartButton;
 #[derive(Component)]
 pub struct RulesPanel;
 
+/// 对局界面的UI根节点，界面上的其他UI节点都是它的后代，离开对局界面时只需删除根节点
 #[derive(Component)]
 pub struct GameUI;
 
 #[derive(Component)]
 pub struct AiThinkingIndicator;
 
+#[derive(Component)]
+pub struct TranscriptHintText;
+
+/// 浏览历史局面时显示实时局面缩略图的面板
+#[derive(Component)]
+pub struct HistoryLivePreview;
+
+/// 实时局面缩略图的格子边长
+const LIVE_PREVIEW_CELL_SIZE: f32 = 8.0;
+
 pub fn setup_game_ui(
     mut commands: Commands,
     language_settings: Res<LanguageSettings>,
     font_assets: Res<FontAssets>,
+    selected_opening: Res<SelectedOpening>,
+    sandbox: Res<SandboxMode>,
+    series: Res<MatchSeries>,
 ) {
     let font = get_font_for_language(&language_settings, &font_assets);
-    let texts = language_settings.get_texts();
-    // 创建根UI容器
-    commands
+    // 多局比赛中玩家每局交换颜色，底部始终是玩家，顶部是AI
+    let player_color = series.player_color;
+    let (player_background, player_border) = avatar_colors(player_color);
+    let (ai_background, ai_border) = avatar_colors(player_color.opposite());
+    // 创建根UI容器，对局界面的其他UI节点都挂在它下面
+    let root = commands
         .spawn((
             Node {
                 width: Val::Percent(100.0),
@@ -81,22 +124,29 @@ pub fn setup_game_ui(
                     ..default()
                 },))
                 .with_children(|top_parent| {
-                    // Bill头像 - 白棋玩家
-                    top_parent.spawn((
-                        Node {
-                            width: Val::Px(50.0),
-                            height: Val::Px(50.0),
-                            border: UiRect::all(Val::Px(2.0)),
-                            margin: UiRect::bottom(Val::Px(8.0)),
-                            ..default()
-                        },
-                        BorderRadius::all(Val::Px(25.0)),
-                        BackgroundColor(Color::srgb(0.98, 0.98, 0.98)), // 白色头像
-                        BorderColor(Color::srgb(0.6, 0.6, 0.6)),        // 灰色边框以便识别
-                        PlayerAvatar {
-                            player_color: PlayerColor::White,
-                        },
-                    ));
+                    // Bill头像 - AI
+                    top_parent
+                        .spawn((
+                            Node {
+                                width: Val::Px(50.0),
+                                height: Val::Px(50.0),
+                                border: UiRect::all(Val::Px(2.0)),
+                                margin: UiRect::bottom(Val::Px(8.0)),
+                                ..default()
+                            },
+                            BorderRadius::all(Val::Px(25.0)),
+                            BackgroundColor(ai_background),
+                            BorderColor(ai_border),
+                            PlayerAvatar {
+                                player_color: player_color.opposite(),
+                            },
+                            avatar_glow(),
+                        ))
+                        .with_children(|avatar| {
+                            spawn_banter_bubble(avatar, &font);
+                            spawn_player_clock(avatar, &font, player_color.opposite());
+                            spawn_search_caption(avatar, &font);
+                        });
 
                     // Bill名称
                     top_parent.spawn((
@@ -108,7 +158,7 @@ pub fn setup_game_ui(
                         },
                         TextColor(Color::WHITE),
                         PlayerNameText {
-                            player_color: PlayerColor::White,
+                            player_color: player_color.opposite(),
                         },
                         LocalizedText,
                     ));
@@ -150,9 +200,13 @@ pub fn setup_game_ui(
                     ..default()
                 },))
                 .with_children(|bottom_parent| {
+                    // 教练点评提示栏，紧贴棋盘下方
+                    spawn_coach_ticker(bottom_parent, &font);
+
                     // Your turn文本
                     bottom_parent.spawn((
-                        Text::new(texts.your_turn),
+                        Text::default(),
+                        turn_text_key(PlayerColor::Black, player_color, sandbox.0),
                         TextFont {
                             font: font.clone(),
                             font_size: 18.0,
@@ -164,66 +218,55 @@ pub fn setup_game_ui(
                             ..default()
                         },
                         TurnIndicator,
+                        TurnChangeAnimation::default(),
                         LocalizedText,
                     ));
 
-                    // You头像 - 黑棋玩家
-                    bottom_parent.spawn((
-                        Node {
-                            width: Val::Px(50.0),
-                            height: Val::Px(50.0),
-                            border: UiRect::all(Val::Px(2.0)),
-                            ..default()
-                        },
-                        BorderRadius::all(Val::Px(25.0)),
-                        BackgroundColor(Color::srgb(0.05, 0.05, 0.05)), // 黑色头像
-                        BorderColor(Color::WHITE),                      // 白色边框以便识别
-                        PlayerAvatar {
-                            player_color: PlayerColor::Black,
-                        },
-                    ));
+                    // You头像 - 玩家
+                    bottom_parent
+                        .spawn((
+                            Node {
+                                width: Val::Px(50.0),
+                                height: Val::Px(50.0),
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BorderRadius::all(Val::Px(25.0)),
+                            BackgroundColor(player_background),
+                            BorderColor(player_border),
+                            PlayerAvatar { player_color },
+                            avatar_glow(),
+                        ))
+                        .with_children(|avatar| spawn_player_clock(avatar, &font, player_color));
                 });
-        });
+        })
+        .id();
 
     // 返回按钮 - 左上角
-    let back_normal = Color::srgba(0.2, 0.2, 0.2, 0.8);
-    commands
-        .spawn((
-            Button,
-            Node {
-                position_type: PositionType::Absolute,
-                left: Val::Px(8.0),
-                top: Val::Px(8.0),
-                width: Val::Px(44.0),  // 手机触摸友好的最小尺寸
-                height: Val::Px(44.0), // Apple推荐的44px最小触摸目标
-                padding: UiRect::all(Val::Px(8.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            BackgroundColor(back_normal),
-            BorderColor(Color::srgb(0.6, 0.6, 0.6)),
-            BorderRadius::all(Val::Px(6.0)),
+    let back_style = ButtonStyle {
+        colors: Some(ButtonColors {
+            normal: Color::srgba(0.2, 0.2, 0.2, 0.8),
+            hovered: Color::srgba(0.3, 0.3, 0.3, 0.9),
+            pressed: Color::srgba(0.1, 0.1, 0.1, 0.9),
+        }),
+        ..ButtonStyle::corner(8.0)
+    };
+
+    commands.entity(root).with_children(|root| {
+        spawn_button(
+            root,
+            &back_style,
+            &font,
+            TextKey::BackToDifficulty,
             super::BackToDifficultyButton,
-            ButtonColors {
-                normal: back_normal,
-                hovered: Color::srgba(0.3, 0.3, 0.3, 0.9),
-                pressed: Color::srgba(0.1, 0.1, 0.1, 0.9),
-            },
-            GameUI,
-        ))
-        .with_children(|button| {
-            button.spawn((
-                Text::new(texts.back_to_difficulty),
-                TextFont {
-                    font: font.clone(),
-                    font_size: 14.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-                LocalizedText,
-            ));
-        });
+        );
+
+        // 悔棋按钮 - 返回按钮下方
+        spawn_undo_button(root, &font);
+
+        // 跳过按钮 - 悔棋按钮下方
+        spawn_pass_button(root, &font);
+    });
 
     // 游戏信息面板 - 右上角
     commands
@@ -241,34 +284,38 @@ pub fn setup_game_ui(
             },
             BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
             BorderRadius::all(Val::Px(6.0)),
-            GameUI,
+            ChildOf(root),
         ))
         .with_children(|parent| {
             // 分数显示
-            parent.spawn((
-                Text::new(
-                    texts
-                        .score_format
-                        .replacen("{}", "2", 1)
-                        .replacen("{}", "2", 1),
-                ),
-                TextFont {
-                    font: font.clone(),
-                    font_size: 14.0, // 手机优化尺寸
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-                ScoreText,
-                LocalizedText,
-            ));
+            spawn_score_widget(parent, &font);
+
+            // 按对局阶段显示行动力、稳定子或剩余空位
+            spawn_phase_info(parent, &font);
+
+            // 沙盒中没有AI，醒目地标明自由落子且不计入战绩
+            if sandbox.0 {
+                for text_key in [TextKey::SandboxLabel, TextKey::SandboxNotCounted] {
+                    parent.spawn((
+                        Text::default(),
+                        text_key,
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 0.8, 0.35)),
+                        LocalizedText,
+                    ));
+                }
+                return;
+            }
 
             // AI难度显示
             parent.spawn((
-                Text::new(
-                    texts
-                        .ai_difficulty_format
-                        .replace("{}", texts.difficulty_medium),
-                ),
+                Text::default(),
+                TextKey::AiDifficultyFormat,
+                TextArgs(vec![TextArg::Key(TextKey::DifficultyMedium)]),
                 TextFont {
                     font: font.clone(),
                     font_size: 12.0, // 手机优化尺寸
@@ -278,6 +325,25 @@ pub fn setup_game_ui(
                 DifficultyText,
                 LocalizedText,
             ));
+
+            // 多局比赛的局数和比分
+            spawn_match_score(parent, &font, &series);
+
+            // 开局练习时显示练习的开局
+            if selected_opening.0.is_some() {
+                parent.spawn((
+                    Text::default(),
+                    TextKey::OpeningPracticeFormat,
+                    selected_opening.text_args(),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                    LocalizedText,
+                ));
+            }
         });
 
     // 游戏状态信息 - 右下角
@@ -292,7 +358,9 @@ pub fn setup_game_ui(
         },
         BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
         BorderRadius::all(Val::Px(6.0)),
-        Text::new(texts.game_in_progress),
+        Text::default(),
+        TextKey::GameInProgress,
+        TextArgs::default(),
         TextFont {
             font: font.clone(),
             font_size: 12.0, // 手机优化尺寸
@@ -301,23 +369,158 @@ pub fn setup_game_ui(
         TextColor(Color::WHITE),
         GameStatusText,
         LocalizedText,
-        GameUI,
+        ChildOf(root),
     ));
+
+    // 走棋播报实时区域 - 左下角
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(8.0),
+            bottom: Val::Px(8.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            max_width: Val::Px(180.0), // 限制最大宽度适应手机屏幕
+            ..default()
+        },
+        Text::new(" "), // 使用空格占位，避免完全空文本
+        TextFont {
+            font: font.clone(),
+            font_size: 12.0, // 手机优化尺寸
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+        announcement_accessibility_node(),
+        AnnouncementText,
+        LocalizedText,
+        ChildOf(root),
+    ));
+
+    // 实时局面缩略图 - 返回按钮下方，只在浏览历史时显示
+    commands
+        .spawn((
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                left: Val::Px(8.0),
+                top: Val::Px(60.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(6.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            BorderRadius::all(Val::Px(6.0)),
+            HistoryLivePreview,
+            ChildOf(root),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                TextKey::HistoryLivePreview,
+                TextFont {
+                    font: font.clone(),
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                LocalizedText,
+            ));
+            parent.spawn((
+                Node::default(),
+                MiniBoardView::new(Board::new_standard(), LIVE_PREVIEW_CELL_SIZE),
+            ));
+        });
+}
+
+/// 分数条目的强调边框颜色（轮到走棋的一方）
+const SCORE_ACTIVE_BORDER: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// 在父节点下创建分数组件，子数由update_score_widget更新
+pub fn spawn_score_widget(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
+    parent
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(4.0),
+                ..default()
+            },
+            ScoreWidget,
+        ))
+        .with_children(|widget| {
+            for color in [PlayerColor::Black, PlayerColor::White] {
+                let (disc_color, disc_border) = match color {
+                    PlayerColor::Black => (Color::BLACK, Color::srgb(0.5, 0.5, 0.5)),
+                    PlayerColor::White => (Color::WHITE, Color::srgb(0.6, 0.6, 0.6)),
+                };
+
+                widget
+                    .spawn((
+                        Node {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(4.0),
+                            padding: UiRect::axes(Val::Px(4.0), Val::Px(2.0)),
+                            border: UiRect::all(Val::Px(1.0)),
+                            ..default()
+                        },
+                        BorderColor(Color::NONE),
+                        BorderRadius::all(Val::Px(4.0)),
+                        ScoreEntry(color),
+                    ))
+                    .with_children(|entry| {
+                        // 棋子图标
+                        entry.spawn((
+                            Node {
+                                width: Val::Px(12.0),
+                                height: Val::Px(12.0),
+                                border: UiRect::all(Val::Px(1.0)),
+                                ..default()
+                            },
+                            BackgroundColor(disc_color),
+                            BorderColor(disc_border),
+                            BorderRadius::MAX,
+                        ));
+                        entry.spawn((
+                            Text::new("2"),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 14.0, // 手机优化尺寸
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            ScoreCount(color),
+                            LocalizedText,
+                        ));
+                    });
+            }
+        });
 }
 
-pub fn update_score_text(
-    mut score_query: Query<&mut Text, With<ScoreText>>,
+/// 用棋盘子数更新分数组件，并强调轮到走棋的一方
+pub fn update_score_widget(
+    mut count_query: Query<(&mut Text, &ScoreCount)>,
+    mut entry_query: Query<(&mut BorderColor, &ScoreEntry)>,
     board_query: Query<&Board>,
-    language_settings: Res<LanguageSettings>,
+    current_player: Res<CurrentPlayer>,
 ) {
-    if let (Ok(mut text), Ok(board)) = (score_query.single_mut(), board_query.single()) {
-        let black_count = board.count_pieces(PlayerColor::Black);
-        let white_count = board.count_pieces(PlayerColor::White);
-        let texts = language_settings.get_texts();
-        **text = texts
-            .score_format
-            .replacen("{}", &black_count.to_string(), 1) // 只替换第一个{}
-            .replacen("{}", &white_count.to_string(), 1); // 再替换下一个{}
+    if let Ok(board) = board_query.single() {
+        for (mut text, ScoreCount(color)) in count_query.iter_mut() {
+            let count = board.count_pieces(*color).to_string();
+            // 只在分数变化时写入，避免每帧触发文本重新布局
+            if text.0 != count {
+                text.0 = count;
+            }
+        }
+    }
+
+    for (mut border, ScoreEntry(color)) in entry_query.iter_mut() {
+        let emphasis = if *color == current_player.0 {
+            SCORE_ACTIVE_BORDER
+        } else {
+            Color::NONE
+        };
+        border.set_if_neq(BorderColor(emphasis));
     }
 }
 
@@ -332,67 +535,135 @@ pub fn update_current_player_text(
     }
 }
 
-pub fn update_game_status_text(
-    mut status_query: Query<&mut Text, With<GameStatusText>>,
-    board_query: Query<&Board>,
-    current_player: Res<CurrentPlayer>,
-    language_settings: Res<LanguageSettings>,
+/// 浏览历史时显示实时局面缩略图，回到实时局面后隐藏
+pub fn update_history_live_preview(
+    history_preview: Res<HistoryPreview>,
+    board_query: Query<Ref<Board>>,
+    mut panel_query: Query<(&mut Node, &Children), With<HistoryLivePreview>>,
+    mut view_query: Query<&mut MiniBoardView>,
 ) {
-    if let (Ok(mut text), Ok(board)) = (status_query.single_mut(), board_query.single()) {
-        let texts = language_settings.get_texts();
+    let Ok(board) = board_query.single() else {
+        return;
+    };
+    if !history_preview.is_changed() && !board.is_changed() {
+        return;
+    }
 
-        if board.is_game_over() {
-            if let Some(winner) = board.get_winner() {
-                **text = format!(
-                    "{} {}",
-                    match winner {
-                        PlayerColor::Black => texts.black_wins,
-                        PlayerColor::White => texts.white_wins,
-                    },
-                    texts.click_to_restart
-                );
-            } else {
-                **text = format!("{} {}", texts.draw, texts.click_to_restart);
-            }
-        } else if !board.has_valid_moves(current_player.0) {
-            **text = format!("{:?} {}", current_player.0, texts.pass_turn);
+    for (mut node, children) in panel_query.iter_mut() {
+        let display = if history_preview.is_previewing() {
+            Display::Flex
         } else {
-            **text = texts.game_in_progress.to_string();
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+        let mut views = view_query.iter_many_mut(children);
+        while let Some(mut view) = views.fetch_next() {
+            if view.board != *board {
+                view.board = *board;
+            }
         }
     }
 }
 
-pub fn update_turn_indicator(
-    mut turn_query: Query<&mut Text, With<TurnIndicator>>,
+pub fn update_game_status_text(
+    mut status_query: Query<(&mut TextKey, &mut TextArgs), With<GameStatusText>>,
+    board_query: Query<(&Board, &GameLog)>,
+    legal_moves: Res<LegalMovesCache>,
     current_player: Res<CurrentPlayer>,
-    language_settings: Res<LanguageSettings>,
+    history: Res<MoveHistory>,
+    history_preview: Res<HistoryPreview>,
+    clock: Res<GameClock>,
 ) {
-    if current_player.is_changed() {
-        if let Ok(mut text) = turn_query.single_mut() {
-            let texts = language_settings.get_texts();
-            match current_player.0 {
-                PlayerColor::Black => **text = texts.your_turn.to_string(),
-                PlayerColor::White => **text = texts.ai_turn.to_string(),
-            }
-        }
+    if let (Ok((mut key, mut args)), Ok((board, log))) =
+        (status_query.single_mut(), board_query.single())
+    {
+        let free_placement = log.rule() == PlacementRule::Free;
+
+        let (status_key, status_args) = if let Some(index) = history_preview.index {
+            (
+                TextKey::HistoryPreviewFormat,
+                vec![
+                    TextArg::Number(index as u64),
+                    TextArg::Number(history.move_count() as u64),
+                ],
+            )
+        } else if let Some(flagged) = clock.flagged() {
+            // 超时判负，对方获胜
+            (
+                TextKey::GameResultFormat,
+                vec![
+                    TextArg::Format(TextKey::TimeForfeitFormat, vec![side_name(flagged)]),
+                    TextArg::Format(
+                        TextKey::GameResultFormat,
+                        game_result_args(Some(flagged.opposite())),
+                    ),
+                ],
+            )
+        } else if free_placement && !log.is_game_over() {
+            (TextKey::GameInProgress, Vec::new())
+        } else if free_placement || legal_moves.is_game_over(board) || log.is_game_over() {
+            (
+                TextKey::GameResultFormat,
+                game_result_args(log.victory().winner(board)),
+            )
+        } else if !legal_moves.has_moves(board, current_player.0) {
+            (TextKey::PassTurnFormat, vec![side_name(current_player.0)])
+        } else {
+            (TextKey::GameInProgress, Vec::new())
+        };
+
+        // 只在内容变化时写入，避免每帧重新解析文本
+        key.set_if_neq(status_key);
+        args.set_if_neq(TextArgs(status_args));
+    }
+}
+
+/// 一方的本地化名称
+fn side_name(player: PlayerColor) -> TextArg {
+    TextArg::Key(match player {
+        PlayerColor::Black => TextKey::BlackName,
+        PlayerColor::White => TextKey::WhiteName,
+    })
+}
+
+/// 终局结果（没有胜者即平局）和重新开始的提示
+fn game_result_args(winner: Option<PlayerColor>) -> Vec<TextArg> {
+    let result = match winner {
+        Some(PlayerColor::Black) => TextKey::BlackWins,
+        Some(PlayerColor::White) => TextKey::WhiteWins,
+        None => TextKey::Draw,
+    };
+    vec![TextArg::Key(result), TextArg::Key(TextKey::ClickToRestart)]
+}
+/// AI难度对应的显示文本
+pub fn difficulty_text_arg(difficulty: AiDifficulty) -> TextArg {
+    match difficulty {
+        AiDifficulty::Chaos(_) => TextArg::Key(TextKey::DifficultyChaos),
+        AiDifficulty::Beginner => TextArg::Key(TextKey::DifficultyEasy),
+        AiDifficulty::Intermediate => TextArg::Key(TextKey::DifficultyMedium),
+        AiDifficulty::Advanced => TextArg::Key(TextKey::DifficultyHard),
+        AiDifficulty::Expert => TextArg::Key(TextKey::DifficultyExpert),
+        AiDifficulty::ExpertPlus => TextArg::Key(TextKey::DifficultyExpertPlus),
+        // 自适应难度显示当前搜索深度作为等级
+        AiDifficulty::Adaptive(level) => TextArg::Format(
+            TextKey::AdaptiveLevelFormat,
+            vec![
+                TextArg::Key(TextKey::DifficultyAdaptive),
+                TextArg::Value(level.max_depth.to_string()),
+            ],
+        ),
     }
 }
 
 pub fn update_difficulty_text(
-    mut difficulty_query: Query<&mut Text, With<DifficultyText>>,
+    mut difficulty_query: Query<&mut TextArgs, With<DifficultyText>>,
     ai_query: Query<&AiPlayer, Changed<AiPlayer>>,
-    language_settings: Res<LanguageSettings>,
 ) {
     if let Ok(ai_player) = ai_query.single() {
-        if let Ok(mut text) = difficulty_query.single_mut() {
-            let texts = language_settings.get_texts();
-            let difficulty_name = match ai_player.difficulty {
-                AiDifficulty::Beginner => texts.difficulty_easy,
-                AiDifficulty::Intermediate => texts.difficulty_medium,
-                AiDifficulty::Advanced => texts.difficulty_hard,
-                AiDifficulty::Expert => texts.difficulty_expert,
-            };
-            **text = texts.ai_difficulty_format.replace("{}", difficulty_name);
+        if let Ok(mut args) = difficulty_query.single_mut() {
+            args.set_if_neq(TextArgs(vec![difficulty_text_arg(ai_player.difficulty)]));
         }
     }
 }
@@ -408,10 +679,234 @@ pub fn handle_rules_button(
     }
 }
 
+/// 对局结束时显示双方的思考用时统计
+pub fn setup_time_stats_panel(
+    mut commands: Commands,
+    record: Res<GameRecord>,
+    log_query: Query<&GameLog>,
+    stats: Res<PlayerStats>,
+    ui_root: Query<Entity, With<GameUI>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    let Ok(root) = ui_root.single() else {
+        return;
+    };
+    let font = get_font_for_language(&language_settings, &font_assets);
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                right: Val::Px(0.0),
+                top: Val::Px(60.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ChildOf(root),
+            // 进入复盘分析时移除，回到结果界面时重新生成
+            StateScoped(GameState::GameOver),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(4.0),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                    BorderRadius::all(Val::Px(6.0)),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::default(),
+                        TextKey::TimeStatsTitle,
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        LocalizedText,
+                    ));
+
+                    for (player, name) in [
+                        (PlayerColor::Black, TextKey::BlackName),
+                        (PlayerColor::White, TextKey::WhiteName),
+                    ] {
+                        let average = record
+                            .average_time(player)
+                            .map_or_else(|| TextArg::Value("-".to_string()), TextArg::Duration);
+                        panel.spawn((
+                            Text::default(),
+                            TextKey::TimeStatsFormat,
+                            TextArgs(vec![
+                                TextArg::Key(name),
+                                TextArg::Duration(record.total_time(player)),
+                                average,
+                            ]),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                            LocalizedText,
+                        ));
+                    }
+
+                    // 双方的翻转统计和玩家的累计记录
+                    let flips = log_query
+                        .single()
+                        .map(GameFlipStats::from_log)
+                        .unwrap_or_default();
+                    panel.spawn((
+                        Text::default(),
+                        TextKey::FlipStatsTitle,
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        LocalizedText,
+                    ));
+                    for (player, name) in [
+                        (PlayerColor::Black, TextKey::BlackName),
+                        (PlayerColor::White, TextKey::WhiteName),
+                    ] {
+                        panel.spawn((
+                            Text::default(),
+                            TextKey::FlipStatsFormat,
+                            flips.side(player).text_args(name),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                            LocalizedText,
+                        ));
+                    }
+                    panel.spawn((
+                        Text::default(),
+                        TextKey::FlipRecordsFormat,
+                        stats.flips.text_args(),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        LocalizedText,
+                    ));
+
+                    panel.spawn((
+                        Text::default(),
+                        TextKey::TranscriptExportHint,
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        TranscriptHintText,
+                        LocalizedText,
+                    ));
+
+                    panel.spawn((
+                        Text::default(),
+                        TextKey::ReportExportHint,
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        LocalizedText,
+                    ));
+
+                    panel.spawn((
+                        Text::default(),
+                        TextKey::AnalysisHint,
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        LocalizedText,
+                    ));
+                });
+        });
+}
+
+/// 对局结束后按E（默认）导出带思考时间注释的棋谱
+pub fn handle_transcript_export(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    record: Res<GameRecord>,
+    storage: Res<StorageLocation>,
+    mut hint_query: Query<&mut TextKey, With<TranscriptHintText>>,
+    mut toast_events: EventWriter<ToastEvent>,
+) {
+    if !input_map.just_pressed(InputAction::ExportTranscript, &keyboard_input) {
+        return;
+    }
+
+    match record.export_transcript(&storage) {
+        Ok(()) => {
+            if let Ok(mut key) = hint_query.single_mut() {
+                *key = TextKey::TranscriptExported;
+            }
+        }
+        Err(err) => {
+            warn!("Failed to export transcript: {err}");
+            toast_events.write(ToastEvent::error(TextKey::ToastExportFailed));
+        }
+    }
+}
+
+/// 对局结束后按R（默认）导出带评注和终局棋盘图的分析报告（Markdown和HTML各一份）
+pub fn handle_report_export(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    log_query: Query<&GameLog>,
+    record: Res<GameRecord>,
+    eval: Res<ActiveEvalConfig>,
+    storage: Res<StorageLocation>,
+    mut toast_events: EventWriter<ToastEvent>,
+) {
+    if !input_map.just_pressed(InputAction::ExportReport, &keyboard_input) {
+        return;
+    }
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+
+    let report = AnalysisReport::build(log, &record, eval.0.as_ref());
+    match report.export(&storage) {
+        Ok(()) => {
+            toast_events.write(ToastEvent::success(TextKey::ToastReportExported));
+        }
+        Err(err) => {
+            warn!("Failed to export analysis report: {err}");
+            toast_events.write(ToastEvent::error(TextKey::ToastReportExportFailed));
+        }
+    }
+}
+
 pub fn manage_rules_panel(
     mut commands: Commands,
     ui_state: Res<UiState>,
     rules_panel_query: Query<Entity, With<RulesPanel>>,
+    log_query: Query<&GameLog>,
+    colors: Res<BoardColors>,
     language_settings: Res<LanguageSettings>,
     font_assets: Res<FontAssets>,
 ) {
@@ -422,8 +917,16 @@ pub fn manage_rules_panel(
         }
 
         // 如果需要显示规则，创建新的面板
+        // 示例走法按打开时的局面生成
         if ui_state.show_rules {
-            spawn_rules_panel(&mut commands, &language_settings, &font_assets);
+            spawn_rules_panel(
+                &mut commands,
+                &language_settings,
+                &font_assets,
+                ui_state.show_danger_zones,
+                &RulesExample::for_game(log_query.single().ok()),
+                &colors,
+            );
         }
     }
 }
@@ -432,8 +935,10 @@ fn spawn_rules_panel(
     commands: &mut Commands,
     language_settings: &LanguageSettings,
     font_assets: &FontAssets,
+    show_danger_zones: bool,
+    example: &RulesExample,
+    colors: &BoardColors,
 ) {
-    let texts = language_settings.get_texts();
     let font = get_font_for_language(language_settings, font_assets);
     commands
         .spawn((
@@ -452,11 +957,13 @@ fn spawn_rules_panel(
             BorderColor(Color::srgb(0.6, 0.6, 0.6)),
             BorderRadius::all(Val::Px(10.0)),
             RulesPanel,
+            FocusScope,
         ))
         .with_children(|panel| {
             // 标题
             panel.spawn((
-                Text::new(texts.rules_title),
+                Text::default(),
+                TextKey::RulesTitle,
                 TextFont {
                     font: font.clone(),
                     font_size: 20.0,
@@ -481,7 +988,8 @@ fn spawn_rules_panel(
                 },))
                 .with_children(|content| {
                     content.spawn((
-                        Text::new(texts.rules_content),
+                        Text::default(),
+                        TextKey::RulesContent,
                         TextFont {
                             font: font.clone(),
                             font_size: 12.0,
@@ -492,40 +1000,48 @@ fn spawn_rules_panel(
                     ));
                 });
 
-            // 关闭按钮
-            let close_normal = Color::srgb(0.3, 0.3, 0.3);
+            // 当前局面的示例走法
+            spawn_rules_example(panel, example, &font, colors);
+
+            // 底部按钮行：危险区域标注开关和关闭按钮
             panel
-                .spawn((
-                    Button,
-                    Node {
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    let close_style = ButtonStyle {
                         width: Val::Px(80.0),
-                        height: Val::Px(44.0), // 增加到44px触摸友好高度
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        align_self: AlignSelf::Center,
+                        radius: 5.0,
+                        font_size: 14.0,
                         ..default()
-                    },
-                    BackgroundColor(close_normal),
-                    BorderColor(Color::srgb(0.6, 0.6, 0.6)),
-                    BorderRadius::all(Val::Px(5.0)),
-                    RulesButton, // 复用按钮组件来关闭
-                    ButtonColors {
-                        normal: close_normal,
-                        hovered: Color::srgb(0.4, 0.4, 0.4),
-                        pressed: Color::srgb(0.2, 0.2, 0.2),
-                    },
-                ))
-                .with_children(|button| {
-                    button.spawn((
-                        Text::new(texts.rules_close),
-                        TextFont {
-                            font: font.clone(),
-                            font_size: 14.0,
-                            ..default()
-                        },
-                        TextColor(Color::WHITE),
-                        LocalizedText,
-                    ));
+                    };
+                    let toggle_style = ButtonStyle {
+                        width: Val::Auto,
+                        min_width: Val::Px(80.0),
+                        padding: UiRect::horizontal(Val::Px(10.0)),
+                        ..close_style.clone()
+                    };
+                    let state_key = if show_danger_zones {
+                        TextKey::ValueOn
+                    } else {
+                        TextKey::ValueOff
+                    };
+                    row.spawn((toggle_style.bundle(), DangerZoneButton))
+                        .with_children(|button| {
+                            spawn_label(
+                                button,
+                                &toggle_style.label_style(),
+                                &font,
+                                TextKey::DangerZonesToggle,
+                                TextArgs(vec![TextArg::Key(state_key)]),
+                            );
+                        });
+
+                    // 关闭按钮，复用规则按钮组件来关闭
+                    spawn_button(row, &close_style, &font, TextKey::RulesClose, RulesButton);
                 });
         });
 }
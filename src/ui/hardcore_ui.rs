@@ -0,0 +1,25 @@
+// 硬核模式 - 辅助等级为"硬核"时，对局中隐藏可落子指示器、子数和阶段信息，终局后再显示
+//
+// 用于练习读盘：可落子指示器和所有基于局面评估的提示由辅助等级的规则关闭，
+// 分数组件中的子数和阶段信息在这里隐藏，分数组件的棋子图标和轮到走棋的强调边框保留
+
+use super::{PhaseInfoText, ScoreCount};
+use crate::{preferences::Preferences, state::GameState};
+use bevy::prelude::*;
+
+/// 对局中按辅助等级隐藏子数和阶段信息，离开对局状态（终局）后显示
+pub fn update_hud_visibility(
+    preferences: Res<Preferences>,
+    state: Res<State<GameState>>,
+    mut hud_query: Query<&mut Visibility, Or<(With<ScoreCount>, With<PhaseInfoText>)>>,
+) {
+    let hidden = preferences.assist.level.hides_score() && *state.get() == GameState::Playing;
+    let visibility = if hidden {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    for mut hud_visibility in hud_query.iter_mut() {
+        hud_visibility.set_if_neq(visibility);
+    }
+}
@@ -0,0 +1,78 @@
+// 最佳走法提示界面 - 按提示键（默认T）在棋盘上标出后台算好的最佳走法
+//
+// 走法来自assist::HintCache，按键时不再开始新的搜索：缓存已有结果时立即显示，
+// 还没有结果时等第一个结果出来再显示。标记只对按键时的局面有效，局面变化后自动消失，再按一次提示键隐藏
+
+use super::{CurrentPlayer, NotationInputBox, SQUARE_SIZE};
+use crate::{
+    assist::HintCache,
+    game::{Board, PlayerColor},
+    input::{InputAction, InputMap},
+};
+use bevy::prelude::*;
+
+/// 棋盘上的最佳走法标记
+#[derive(Component)]
+pub struct HintMarker;
+
+/// 请求提示时的局面，局面变化后请求失效
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HintRequest(pub Option<(Board, PlayerColor)>);
+
+/// 按提示键时为当前局面请求提示，已经请求过时取消
+///
+/// 棋谱输入框显示时字母键用于输入棋谱，不触发提示
+pub fn handle_hint_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    box_query: Query<(), With<NotationInputBox>>,
+    board_query: Query<&Board>,
+    current_player: Res<CurrentPlayer>,
+    mut request: ResMut<HintRequest>,
+) {
+    let typing = !box_query.is_empty();
+    if !input_map.just_pressed_unless_typing(InputAction::Hint, &keyboard_input, typing) {
+        return;
+    }
+    let Ok(board) = board_query.single() else {
+        return;
+    };
+    let position = Some((*board, current_player.0));
+    request.0 = if request.0 == position {
+        None
+    } else {
+        position
+    };
+}
+
+/// 请求的局面有缓存的最佳走法时显示标记，局面变化后清除请求
+pub fn update_hint_marker(
+    mut request: ResMut<HintRequest>,
+    cache: Res<HintCache>,
+    board_query: Query<&Board>,
+    current_player: Res<CurrentPlayer>,
+    mut marker_query: Query<(&mut Transform, &mut Visibility), With<HintMarker>>,
+) {
+    let Ok((mut transform, mut visibility)) = marker_query.single_mut() else {
+        return;
+    };
+    let current = board_query
+        .single()
+        .ok()
+        .map(|board| (*board, current_player.0));
+    if request.0.is_some() && request.0 != current {
+        request.0 = None;
+    }
+    let best = request
+        .0
+        .and_then(|(board, player)| cache.best_move(&board, player));
+    let Some(position) = best else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    let (row, col) = Board::position_to_coords(position);
+    transform.translation.x = (col as f32 - 3.5) * SQUARE_SIZE;
+    transform.translation.y = (3.5 - row as f32) * SQUARE_SIZE;
+    visibility.set_if_neq(Visibility::Inherited);
+}
@@ -0,0 +1,280 @@
+// 使用统计页面 - 难度选择界面中查看本地记录的对局数、平均对局长度和功能使用次数
+
+use super::{spawn_button, ButtonStyle, FocusScope, ToDelete, ToastEvent, UiState};
+use crate::{
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    preferences::{storage::StorageLocation, Preferences},
+    state::GameState,
+    stats::{DifficultyTier, PlayerStats, UsageFeature, UsageInsights},
+};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct InsightsButton;
+
+#[derive(Component)]
+pub struct InsightsPanel;
+
+#[derive(Component)]
+pub struct InsightsClearButton;
+
+/// 按对局数显示的难度，与难度选择按钮的顺序相同
+const TIERS: [DifficultyTier; 7] = [
+    DifficultyTier::Chaos,
+    DifficultyTier::Beginner,
+    DifficultyTier::Intermediate,
+    DifficultyTier::Advanced,
+    DifficultyTier::Expert,
+    DifficultyTier::ExpertPlus,
+    DifficultyTier::Adaptive,
+];
+
+/// 难度级别的名称
+pub fn tier_text_key(tier: DifficultyTier) -> TextKey {
+    match tier {
+        DifficultyTier::Chaos => TextKey::DifficultyChaos,
+        DifficultyTier::Beginner => TextKey::DifficultyEasy,
+        DifficultyTier::Intermediate => TextKey::DifficultyMedium,
+        DifficultyTier::Advanced => TextKey::DifficultyHard,
+        DifficultyTier::Expert => TextKey::DifficultyExpert,
+        DifficultyTier::ExpertPlus => TextKey::DifficultyExpertPlus,
+        DifficultyTier::Adaptive => TextKey::DifficultyAdaptive,
+    }
+}
+
+/// 功能的名称
+pub fn usage_feature_key(feature: UsageFeature) -> TextKey {
+    match feature {
+        UsageFeature::Undo => TextKey::UsageUndo,
+        UsageFeature::DangerZones => TextKey::UsageDangerZones,
+        UsageFeature::HistoryReview => TextKey::UsageHistoryReview,
+        UsageFeature::Analysis => TextKey::UsageAnalysis,
+        UsageFeature::Replay => TextKey::UsageReplay,
+        UsageFeature::Sandbox => TextKey::UsageSandbox,
+        UsageFeature::OpeningPractice => TextKey::UsageOpeningPractice,
+        UsageFeature::Rules => TextKey::UsageRules,
+    }
+}
+
+/// 页面中的统计行：各难度的对局数、平均对局长度和各功能的使用次数，没有记录的项不显示
+pub fn insights_lines(insights: &UsageInsights) -> Vec<(TextKey, TextArgs)> {
+    let mut lines: Vec<_> = TIERS
+        .into_iter()
+        .filter(|tier| insights.games_at(*tier) > 0)
+        .map(|tier| {
+            (
+                TextKey::InsightsGamesFormat,
+                TextArgs(vec![
+                    TextArg::Key(tier_text_key(tier)),
+                    TextArg::Number(insights.games_at(tier).into()),
+                ]),
+            )
+        })
+        .collect();
+    if let Some((moves, duration)) = insights.average_game() {
+        lines.push((
+            TextKey::InsightsAverageFormat,
+            TextArgs(vec![TextArg::Number(moves), TextArg::Duration(duration)]),
+        ));
+    }
+
+    let features: Vec<_> = UsageFeature::ALL
+        .into_iter()
+        .filter(|feature| insights.uses_of(*feature) > 0)
+        .collect();
+    if !features.is_empty() {
+        lines.push((TextKey::InsightsFeaturesHeader, TextArgs::default()));
+    }
+    lines.extend(features.into_iter().map(|feature| {
+        (
+            TextKey::InsightsFeatureFormat,
+            TextArgs(vec![
+                TextArg::Key(usage_feature_key(feature)),
+                TextArg::Number(insights.uses_of(feature).into()),
+            ]),
+        )
+    }));
+    lines
+}
+
+/// 打开或关闭使用统计页面（关闭按钮复用同一组件）
+pub fn handle_insights_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<InsightsButton>)>,
+    mut ui_state: ResMut<UiState>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            ui_state.show_insights = !ui_state.show_insights;
+        }
+    }
+}
+
+/// 清空本地使用统计
+pub fn handle_insights_clear_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<InsightsClearButton>)>,
+    mut stats: ResMut<PlayerStats>,
+    storage: Res<StorageLocation>,
+    mut toast_events: EventWriter<ToastEvent>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed && !stats.insights.is_empty() {
+            stats.insights = UsageInsights::default();
+            if let Err(err) = stats.save(&storage) {
+                warn!("Failed to save player stats: {err}");
+                toast_events.write(ToastEvent::save_failed(TextKey::SaveDataStats));
+            }
+        }
+    }
+}
+
+/// 打开、关闭页面，统计被清空或开关变化时重新生成页面
+pub fn manage_insights_panel(
+    mut commands: Commands,
+    ui_state: Res<UiState>,
+    panel_query: Query<Entity, With<InsightsPanel>>,
+    stats: Res<PlayerStats>,
+    preferences: Res<Preferences>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    let panel_open = !panel_query.is_empty();
+    let outdated = stats.is_changed() || preferences.is_changed();
+    if ui_state.show_insights == panel_open && !(panel_open && outdated) {
+        return;
+    }
+
+    for entity in panel_query.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+    if ui_state.show_insights {
+        let font = get_font_for_language(&language_settings, &font_assets);
+        spawn_insights_panel(&mut commands, &font, preferences.insights, &stats.insights);
+    }
+}
+
+fn spawn_insights_panel(
+    commands: &mut Commands,
+    font: &Handle<Font>,
+    enabled: bool,
+    insights: &UsageInsights,
+) {
+    let text_font = |font_size: f32| TextFont {
+        font: font.clone(),
+        font_size,
+        ..default()
+    };
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::all(Val::Px(15.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            BorderColor(Color::srgb(0.6, 0.6, 0.6)),
+            BorderRadius::all(Val::Px(10.0)),
+            InsightsPanel,
+            FocusScope,
+            StateScoped(GameState::DifficultySelection),
+        ))
+        .with_children(|panel| {
+            // 标题
+            panel.spawn((
+                Text::default(),
+                TextKey::InsightsTitle,
+                text_font(20.0),
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+                LocalizedText,
+            ));
+
+            // 未开启时只说明如何开启，已经记录的数据仍然显示
+            if !enabled {
+                panel.spawn((
+                    Text::default(),
+                    TextKey::InsightsDisabled,
+                    text_font(14.0),
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    TextLayout::new_with_justify(JustifyText::Center),
+                    Node {
+                        max_width: Val::Px(300.0),
+                        ..default()
+                    },
+                    LocalizedText,
+                ));
+            }
+
+            let lines = insights_lines(insights);
+            if lines.is_empty() {
+                panel.spawn((
+                    Text::default(),
+                    TextKey::InsightsEmpty,
+                    text_font(14.0),
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    LocalizedText,
+                ));
+            }
+            for (key, args) in lines {
+                let header = key == TextKey::InsightsFeaturesHeader;
+                panel.spawn((
+                    Text::default(),
+                    key,
+                    args,
+                    text_font(if header { 16.0 } else { 14.0 }),
+                    TextColor(if header {
+                        Color::srgb(1.0, 0.85, 0.5)
+                    } else {
+                        Color::WHITE
+                    }),
+                    Node {
+                        margin: UiRect::top(Val::Px(if header { 8.0 } else { 0.0 })),
+                        ..default()
+                    },
+                    LocalizedText,
+                ));
+            }
+
+            // 清空和关闭按钮
+            panel
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(10.0),
+                    margin: UiRect::top(Val::Px(10.0)),
+                    ..default()
+                })
+                .with_children(|row| {
+                    let button_style = |color| ButtonStyle {
+                        width: Val::Px(80.0),
+                        color,
+                        radius: 5.0,
+                        font_size: 14.0,
+                        ..default()
+                    };
+                    spawn_button(
+                        row,
+                        &button_style(Color::srgb(0.45, 0.2, 0.2)),
+                        font,
+                        TextKey::InsightsClear,
+                        InsightsClearButton,
+                    );
+                    spawn_button(
+                        row,
+                        &button_style(Color::srgb(0.3, 0.3, 0.3)),
+                        font,
+                        TextKey::RulesClose,
+                        InsightsButton,
+                    );
+                });
+        });
+}
@@ -0,0 +1,199 @@
+// 开场动画 - 新对局开始时起始棋子逐个落下并播放落子音效，随后短暂显示"黑棋先行"横幅
+//
+// 动画期间不接受落子，AI、开局自动走棋和对局计时都等到动画结束后才开始；
+// 点击、触摸或按任意键立即跳过，减少动效时不播放。继续原对局时不播放
+
+use super::{BoardUI, Piece, ToDelete};
+use crate::{
+    accessibility::MotionSettings,
+    audio::{PlaySoundEvent, SoundType},
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    localization::{LanguageSettings, TextKey},
+    state::GameState,
+};
+use bevy::prelude::*;
+
+/// 相邻两枚起始棋子落下的间隔（秒）
+pub const INTRO_DISC_INTERVAL_SECS: f32 = 0.3;
+
+/// 一枚棋子从无到有放大的时长（秒）
+const INTRO_DISC_POP_SECS: f32 = 0.15;
+
+/// 全部棋子落下后横幅显示的时长（秒）
+pub const INTRO_BANNER_SECS: f32 = 1.0;
+
+/// 横幅在结束前淡出的时长（秒）
+const INTRO_BANNER_FADE_SECS: f32 = 0.3;
+
+/// 开场动画的进度
+#[derive(Resource, Debug, Default)]
+pub struct GameIntro {
+    /// 是否正在播放
+    playing: bool,
+    /// 已播放的时长（秒）
+    elapsed: f32,
+    /// 已经落下的棋子数，用于每枚棋子只播放一次音效
+    revealed: usize,
+}
+
+impl GameIntro {
+    /// 开始播放，减少动效时不播放
+    pub fn start(&mut self, reduced_motion: bool) {
+        *self = Self {
+            playing: !reduced_motion,
+            ..default()
+        };
+    }
+
+    /// 跳过剩余的动画
+    pub fn skip(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// 播放一段时间，`discs`为起始棋子数，返回这段时间内新落下的棋子数；
+    /// 横幅显示完后动画结束
+    pub fn advance(&mut self, delta: f32, discs: usize) -> usize {
+        if !self.playing {
+            return 0;
+        }
+        self.elapsed += delta;
+        let revealed = ((self.elapsed / INTRO_DISC_INTERVAL_SECS) as usize + 1).min(discs);
+        let new_discs = revealed.saturating_sub(self.revealed);
+        self.revealed = self.revealed.max(revealed);
+        if self.elapsed >= discs as f32 * INTRO_DISC_INTERVAL_SECS + INTRO_BANNER_SECS {
+            self.playing = false;
+        }
+        new_discs
+    }
+
+    /// 第`index`枚棋子的缩放，还没落下时为0；动画结束后都为1
+    pub fn disc_scale(&self, index: usize) -> f32 {
+        if !self.playing {
+            return 1.0;
+        }
+        let since = self.elapsed - index as f32 * INTRO_DISC_INTERVAL_SECS;
+        (since / INTRO_DISC_POP_SECS).clamp(0.0, 1.0)
+    }
+
+    /// 横幅的不透明度，`discs`枚棋子都落下后出现，结束前淡出
+    pub fn banner_alpha(&self, discs: usize) -> f32 {
+        let shown = self.elapsed - discs as f32 * INTRO_DISC_INTERVAL_SECS;
+        if !self.playing || shown < 0.0 {
+            return 0.0;
+        }
+        ((INTRO_BANNER_SECS - shown) / INTRO_BANNER_FADE_SECS).clamp(0.0, 1.0)
+    }
+}
+
+/// 开场动画的横幅
+#[derive(Component)]
+pub struct IntroBanner;
+
+/// 开场动画结束，用作落子输入、AI和对局计时的运行条件
+pub fn game_intro_finished(intro: Res<GameIntro>) -> bool {
+    !intro.is_playing()
+}
+
+/// 新对局开始时播放开场动画
+pub fn start_game_intro(mut intro: ResMut<GameIntro>, motion_settings: Res<MotionSettings>) {
+    intro.start(motion_settings.reduced_motion);
+}
+
+/// 推进开场动画：按位置顺序逐个显示起始棋子，全部落下后显示横幅，点击、触摸或按键跳过
+///
+/// 触摸在抬起时才落子，因此在抬起时跳过，避免同一次触摸在动画结束后又被当作落子
+pub fn play_game_intro(
+    mut commands: Commands,
+    mut intro: ResMut<GameIntro>,
+    time: Res<Time>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    touch_input: Res<Touches>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    board_root: Query<Entity, With<BoardUI>>,
+    mut piece_query: Query<(&Piece, &ChildOf, &mut Transform, &mut Visibility)>,
+    mut banner_query: Query<(Entity, &mut TextColor), With<IntroBanner>>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+    (language_settings, font_assets): (Res<LanguageSettings>, Res<FontAssets>),
+) {
+    // 动画结束后不再改动棋子，以免覆盖翻转动画的缩放
+    if !intro.is_playing() && !intro.is_changed() {
+        return;
+    }
+    if intro.is_playing()
+        && (mouse_input.get_just_pressed().next().is_some()
+            || touch_input.any_just_released()
+            || keyboard_input.get_just_pressed().next().is_some())
+    {
+        intro.skip();
+    }
+
+    let Ok(root) = board_root.single() else {
+        return;
+    };
+    let mut pieces: Vec<_> = piece_query
+        .iter_mut()
+        .filter(|(_, child_of, _, _)| child_of.parent() == root)
+        .collect();
+    pieces.sort_by_key(|(piece, _, _, _)| piece.position);
+    let discs = pieces.len();
+
+    // 棋子还没生成时等待下一帧
+    if intro.is_playing() && discs > 0 {
+        for _ in 0..intro.advance(time.delta_secs(), discs) {
+            sound_events.write(PlaySoundEvent {
+                sound_type: SoundType::PiecePlace,
+                actor: None,
+            });
+        }
+    }
+
+    for (index, (_, _, transform, visibility)) in pieces.iter_mut().enumerate() {
+        let scale = intro.disc_scale(index);
+        if transform.scale.x != scale {
+            transform.scale = Vec3::new(scale, scale, 1.0);
+        }
+        visibility.set_if_neq(if scale > 0.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+
+    let alpha = intro.banner_alpha(discs);
+    if !intro.is_playing() {
+        for (entity, _) in banner_query.iter() {
+            commands.entity(entity).insert(ToDelete);
+        }
+        return;
+    }
+    if let Ok((_, mut color)) = banner_query.single_mut() {
+        color.set_if_neq(TextColor(Color::WHITE.with_alpha(alpha)));
+    } else if alpha > 0.0 {
+        commands.spawn((
+            Text::default(),
+            TextKey::IntroBlackFirst,
+            TextFont {
+                font: get_font_for_language(&language_settings, &font_assets),
+                font_size: 32.0,
+                ..default()
+            },
+            TextColor(Color::WHITE.with_alpha(alpha)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(45.0),
+                left: Val::Px(0.0),
+                right: Val::Px(0.0),
+                ..default()
+            },
+            TextLayout::new_with_justify(JustifyText::Center),
+            GlobalZIndex(10),
+            IntroBanner,
+            LocalizedText,
+            StateScoped(GameState::Playing),
+        ));
+    }
+}
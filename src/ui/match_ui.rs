@@ -0,0 +1,96 @@
+// 比赛比分 - 多局比赛时在右上角信息面板中显示局数和比分，比赛结束后显示比赛结果
+//
+// 单局赛制不显示；头像按玩家本局的执子颜色布置，玩家执白时玩家头像显示为白色
+
+use crate::{
+    ai::endgame::GameOutcome,
+    fonts::LocalizedText,
+    game::PlayerColor,
+    localization::{TextArg, TextArgs, TextKey},
+    series::MatchSeries,
+};
+use bevy::prelude::*;
+
+/// 比赛比分文本
+#[derive(Component)]
+pub struct MatchScoreText;
+
+/// 比分文本的键和参数：进行中显示第几局和比分，结束后显示比赛结果和最终比分
+pub fn match_score_text(series: &MatchSeries) -> (TextKey, TextArgs) {
+    let score = [
+        TextArg::Number(series.wins().into()),
+        TextArg::Number(series.losses().into()),
+    ];
+    match series.outcome() {
+        None => (
+            TextKey::MatchScoreFormat,
+            TextArgs(
+                [
+                    TextArg::Number(series.current_game().into()),
+                    TextArg::Number(series.format.games().into()),
+                ]
+                .into_iter()
+                .chain(score)
+                .collect(),
+            ),
+        ),
+        Some(outcome) => (
+            match outcome {
+                GameOutcome::Win => TextKey::MatchWonFormat,
+                GameOutcome::Loss => TextKey::MatchLostFormat,
+                GameOutcome::Draw => TextKey::MatchDrawnFormat,
+            },
+            TextArgs(score.to_vec()),
+        ),
+    }
+}
+
+/// 头像的背景和边框颜色
+pub fn avatar_colors(color: PlayerColor) -> (Color, Color) {
+    match color {
+        // 黑色头像加白色边框以便识别
+        PlayerColor::Black => (Color::srgb(0.05, 0.05, 0.05), Color::WHITE),
+        // 白色头像加灰色边框以便识别
+        PlayerColor::White => (Color::srgb(0.98, 0.98, 0.98), Color::srgb(0.6, 0.6, 0.6)),
+    }
+}
+
+/// 在信息面板中创建比分文本，单局赛制不创建
+pub fn spawn_match_score(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    series: &MatchSeries,
+) {
+    if !series.is_match() {
+        return;
+    }
+    let (key, args) = match_score_text(series);
+    parent.spawn((
+        Text::default(),
+        key,
+        args,
+        TextFont {
+            font: font.clone(),
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.85, 0.35)),
+        MatchScoreText,
+        LocalizedText,
+    ));
+}
+
+/// 比赛记录了新的结果后更新比分文本
+pub fn update_match_score_text(
+    series: Res<MatchSeries>,
+    mut text_query: Query<(&mut TextKey, &mut TextArgs), With<MatchScoreText>>,
+) {
+    if !series.is_changed() {
+        return;
+    }
+    let (key, args) = match_score_text(&series);
+    for (mut text_key, mut text_args) in text_query.iter_mut() {
+        text_key.set_if_neq(key);
+        text_args.set_if_neq(args.clone());
+    }
+}
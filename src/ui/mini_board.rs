@@ -0,0 +1,214 @@
+// 缩略棋盘 - 用可复用的UI实体组绘制任意局面的小棋盘
+//
+// 需要显示缩略棋盘的UI节点加上MiniBoardView，由sync_mini_boards分配一组实体（底板和64个棋子）
+// 作为它的子节点并按局面着色。宿主节点被标记删除或移除MiniBoardView时，实体组从宿主上摘下、
+// 隐藏后放回MiniBoardPool，下次打开历史对局列表时直接复用，不再重新生成上百个节点。
+// 缩略棋盘只使用自己的实体，不会影响对局棋盘的格子和棋子
+
+use super::{BoardColors, ToDelete};
+use crate::game::{Board, PlayerColor};
+use bevy::prelude::*;
+
+/// 缩略棋盘四周的边距
+const MINI_BOARD_PADDING: f32 = 1.0;
+
+/// 需要显示缩略棋盘的UI节点
+#[derive(Component, Debug, Clone)]
+pub struct MiniBoardView {
+    pub board: Board,
+    /// 每格的边长
+    pub cell_size: f32,
+    /// 分配到的实体组
+    set: Option<Entity>,
+}
+
+impl MiniBoardView {
+    pub fn new(board: Board, cell_size: f32) -> Self {
+        Self {
+            board,
+            cell_size,
+            set: None,
+        }
+    }
+
+    /// 当前分配到的实体组
+    pub fn set(&self) -> Option<Entity> {
+        self.set
+    }
+}
+
+/// 一组缩略棋盘实体的底板，棋子按格子序号排列
+#[derive(Component, Debug)]
+pub struct MiniBoardSet {
+    discs: [Entity; 64],
+    /// 已经绘制的局面，与宿主的局面相同时不重新着色
+    drawn: Option<Board>,
+}
+
+/// 缩略棋盘中的一枚棋子，空格为透明
+#[derive(Component)]
+pub struct MiniBoardDisc;
+
+/// 空闲的缩略棋盘实体组
+#[derive(Resource, Debug, Default)]
+pub struct MiniBoardPool {
+    free: Vec<Entity>,
+}
+
+impl MiniBoardPool {
+    /// 空闲的实体组数
+    pub fn available(&self) -> usize {
+        self.free.len()
+    }
+}
+
+/// 底板的布局，隐藏时不参与布局
+fn mini_board_node(cell_size: f32, display: Display) -> Node {
+    Node {
+        display,
+        width: Val::Px(cell_size * 8.0 + MINI_BOARD_PADDING * 2.0),
+        height: Val::Px(cell_size * 8.0 + MINI_BOARD_PADDING * 2.0),
+        flex_shrink: 0.0,
+        padding: UiRect::all(Val::Px(MINI_BOARD_PADDING)),
+        grid_template_columns: RepeatedGridTrack::flex(8, 1.0),
+        grid_template_rows: RepeatedGridTrack::flex(8, 1.0),
+        ..default()
+    }
+}
+
+/// 格子中棋子的颜色
+fn disc_color(board: &Board, position: u8, colors: &BoardColors) -> Color {
+    match board.get_piece(position) {
+        Some(PlayerColor::Black) => colors.black_piece_color,
+        Some(PlayerColor::White) => colors.white_piece_color,
+        None => Color::NONE,
+    }
+}
+
+/// 为没有实体组的宿主分配实体组，并在局面或棋盘颜色变化后重新着色
+///
+/// 优先复用池中的实体组，池为空时生成新的一组
+pub fn sync_mini_boards(
+    mut commands: Commands,
+    mut pool: ResMut<MiniBoardPool>,
+    mut view_query: Query<(Entity, &mut MiniBoardView)>,
+    mut set_query: Query<
+        (&mut MiniBoardSet, &mut Node, &mut BackgroundColor),
+        Without<MiniBoardDisc>,
+    >,
+    mut disc_query: Query<&mut BackgroundColor, (With<MiniBoardDisc>, Without<MiniBoardSet>)>,
+    colors: Res<BoardColors>,
+) {
+    for (host, mut view) in view_query.iter_mut() {
+        // 实体组随其他实体一起被删除时重新分配
+        if view.set.is_some_and(|set| !set_query.contains(set)) {
+            view.set = None;
+        }
+
+        if view.set.is_none() {
+            // 池中的实体组也可能已经被删除（例如随宿主的状态一起清理）
+            while let Some(set) = pool.free.pop() {
+                let Ok((mut mini_board, mut node, _)) = set_query.get_mut(set) else {
+                    continue;
+                };
+                *node = mini_board_node(view.cell_size, Display::Grid);
+                mini_board.drawn = None;
+                commands.entity(set).insert(ChildOf(host));
+                view.set = Some(set);
+                break;
+            }
+        }
+
+        let Some(set) = view.set else {
+            view.set = Some(spawn_mini_board_set(&mut commands, host, &view, &colors));
+            continue;
+        };
+
+        let Ok((mut mini_board, _, mut background)) = set_query.get_mut(set) else {
+            // 本帧刚分配、尚未生成的实体组
+            continue;
+        };
+        if mini_board.drawn == Some(view.board) && !colors.is_changed() {
+            continue;
+        }
+        background.0 = colors.board_color;
+        for (position, &disc) in mini_board.discs.iter().enumerate() {
+            if let Ok(mut disc_background) = disc_query.get_mut(disc) {
+                disc_background.0 = disc_color(&view.board, position as u8, &colors);
+            }
+        }
+        mini_board.drawn = Some(view.board);
+    }
+}
+
+/// 生成一组已经按局面着色的缩略棋盘实体
+fn spawn_mini_board_set(
+    commands: &mut Commands,
+    host: Entity,
+    view: &MiniBoardView,
+    colors: &BoardColors,
+) -> Entity {
+    let discs = std::array::from_fn(|position| {
+        commands
+            .spawn((
+                Node {
+                    width: Val::Percent(80.0),
+                    height: Val::Percent(80.0),
+                    justify_self: JustifySelf::Center,
+                    align_self: AlignSelf::Center,
+                    ..default()
+                },
+                BackgroundColor(disc_color(&view.board, position as u8, colors)),
+                BorderRadius::MAX,
+                MiniBoardDisc,
+            ))
+            .id()
+    });
+
+    commands
+        .spawn((
+            mini_board_node(view.cell_size, Display::Grid),
+            BackgroundColor(colors.board_color),
+            BorderRadius::all(Val::Px(2.0)),
+            MiniBoardSet {
+                discs,
+                drawn: Some(view.board),
+            },
+            ChildOf(host),
+        ))
+        .add_children(&discs)
+        .id()
+}
+
+/// 宿主被标记删除或不再需要缩略棋盘时，把实体组摘下并放回池中
+///
+/// 需要在标记删除之后、cleanup_marked_entities之前运行，避免实体组随宿主一起被删除
+pub fn reclaim_mini_boards(
+    mut commands: Commands,
+    mut pool: ResMut<MiniBoardPool>,
+    mut set_query: Query<(Entity, &ChildOf, &mut Node), With<MiniBoardSet>>,
+    mut view_query: Query<&mut MiniBoardView>,
+    parent_query: Query<&ChildOf>,
+    marked_query: Query<(), With<ToDelete>>,
+) {
+    for (set, child_of, mut node) in set_query.iter_mut() {
+        let host = child_of.parent();
+        let still_shown = view_query.get(host).is_ok_and(|view| view.set == Some(set));
+        let host_deleted = marked_query.contains(host)
+            || parent_query
+                .iter_ancestors(host)
+                .any(|ancestor| marked_query.contains(ancestor));
+        if still_shown && !host_deleted {
+            continue;
+        }
+
+        if let Ok(mut view) = view_query.get_mut(host) {
+            if view.set == Some(set) {
+                view.set = None;
+            }
+        }
+        node.display = Display::None;
+        commands.entity(set).remove::<ChildOf>();
+        pool.free.push(set);
+    }
+}
@@ -1,14 +1,110 @@
+pub mod archive_ui;
+pub mod asset_warning_ui;
+pub mod banter_ui;
+pub mod board_style;
 pub mod board_ui;
+pub mod clock_ui;
+pub mod coach_ui;
+pub mod corner_warning_ui;
+pub mod danger_zone_ui;
+pub mod debug_overlay_ui;
+pub mod end_game_effects;
+pub mod feedback_ui;
+pub mod flip_animation;
+pub mod flip_count_ui;
+pub mod focus_ui;
 pub mod game_ui;
+pub mod hardcore_ui;
+pub mod hint_ui;
+pub mod insights_ui;
+pub mod intro_ui;
+pub mod match_ui;
+pub mod mini_board;
+pub mod move_quality_ui;
+pub mod net_sync_ui;
+pub mod notation_input_ui;
+pub mod pass_ui;
+pub mod pause_ui;
+pub mod performance_ui;
+pub mod phase_info_ui;
+pub mod rules_example_ui;
+pub mod save_conflict_ui;
+pub mod score_projection_ui;
+pub mod search_caption_ui;
+pub mod settings_ui;
+pub mod shortcuts_ui;
+pub mod sound_test_ui;
+pub mod spectator_ui;
+pub mod square_label_ui;
+pub mod toast_ui;
+pub mod turn_animation_ui;
+pub mod undo_ui;
+pub mod widgets;
 
+pub use archive_ui::*;
+pub use asset_warning_ui::*;
+pub use banter_ui::*;
+pub use board_style::*;
 pub use board_ui::*;
+pub use clock_ui::*;
+pub use coach_ui::*;
+pub use corner_warning_ui::*;
+pub use danger_zone_ui::*;
+pub use debug_overlay_ui::*;
+pub use end_game_effects::*;
+pub use feedback_ui::*;
+pub use flip_animation::*;
+pub use flip_count_ui::*;
+pub use focus_ui::*;
 pub use game_ui::*;
+pub use hardcore_ui::*;
+pub use hint_ui::*;
+pub use insights_ui::*;
+pub use intro_ui::*;
+pub use match_ui::*;
+pub use mini_board::*;
+pub use move_quality_ui::*;
+pub use net_sync_ui::*;
+pub use notation_input_ui::*;
+pub use pass_ui::*;
+pub use pause_ui::*;
+pub use performance_ui::*;
+pub use phase_info_ui::*;
+pub use rules_example_ui::*;
+pub use save_conflict_ui::*;
+pub use score_projection_ui::*;
+pub use search_caption_ui::*;
+pub use settings_ui::*;
+pub use shortcuts_ui::*;
+pub use sound_test_ui::*;
+pub use spectator_ui::*;
+pub use square_label_ui::*;
+pub use toast_ui::*;
+pub use turn_animation_ui::*;
+pub use undo_ui::*;
+pub use widgets::*;
 
 use bevy::prelude::*;
 
 #[derive(Resource, Default)]
 pub struct UiState {
     pub show_rules: bool,
+    pub show_settings: bool,
+    pub show_danger_zones: bool,
+    /// 调试用：在每个格子上显示序号和棋谱记法
+    pub show_square_labels: bool,
+    /// 调试用：显示对局AI置换表的统计数据
+    pub show_debug_overlay: bool,
+    /// 显示历史对局列表
+    pub show_archive: bool,
+    /// 显示音效测试面板
+    pub show_sound_test: bool,
+    /// 显示使用统计页面
+    pub show_insights: bool,
+    /// 显示快捷键帮助
+    pub show_shortcuts: bool,
+    /// 对局暂停，显示暂停菜单
+    pub paused: bool,
 }
 
 #[derive(Event)]
@@ -20,9 +116,20 @@ pub struct RestartGameEvent;
 #[derive(Component)]
 pub struct BackToDifficultyButton;
 
-#[derive(Component)]
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
 pub struct ButtonColors {
     pub normal: Color,
     pub hovered: Color,
     pub pressed: Color,
 }
+
+impl ButtonColors {
+    /// 以正常颜色为基础，悬停时稍亮、按下时稍暗
+    pub fn for_color(normal: Color) -> Self {
+        Self {
+            normal,
+            hovered: normal.lighter(0.1),
+            pressed: normal.darker(0.1),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// 走法质量着色 - 开局练习中按走法质量把可落子指示器染成绿、黄、红色
+//
+// 档位由practice::move_quality在后台算出，算完之前和其他模式下指示器保持主题颜色；
+// 透明度沿用主题中可落子指示器的透明度，只改变色相，保持提示的克制
+
+use super::{BoardColors, ValidMoveIndicator};
+use crate::practice::MoveQualityGrades;
+use bevy::prelude::*;
+
+/// 档位更新、指示器重建或主题变化时重新为指示器着色
+pub fn tint_move_indicators(
+    grades: Res<MoveQualityGrades>,
+    colors: Res<BoardColors>,
+    added_query: Query<(), Added<ValidMoveIndicator>>,
+    indicator_query: Query<(&ValidMoveIndicator, &MeshMaterial2d<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !grades.is_changed() && !colors.is_changed() && added_query.is_empty() {
+        return;
+    }
+    let alpha = colors.valid_move_color.alpha();
+    for (indicator, material) in indicator_query.iter() {
+        let color = grades
+            .quality(indicator.position)
+            .map_or(colors.valid_move_color, |quality| {
+                quality.color().with_alpha(alpha)
+            });
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.color = color;
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// 联机同步提示 - 与服务器失步、重新同步完成或失败时短暂显示提示
+//
+// 提示由传输层转发的SyncEvent触发，通过通知提示显示（见toast_ui）
+
+use super::{ToastEvent, ToastKind};
+use crate::{
+    localization::{TextArg, TextArgs, TextKey},
+    net::{DesyncReason, SyncEvent},
+};
+use bevy::prelude::*;
+
+/// 提示文本的键和参数，步数从1开始
+pub fn sync_notice_text(event: &SyncEvent) -> (TextKey, TextArgs) {
+    match event {
+        SyncEvent::Desync { sequence, reason } => {
+            let reason = match reason {
+                DesyncReason::InvalidCommand(_) => TextKey::NetReasonInvalidMove,
+                DesyncReason::ChecksumMismatch { .. } => TextKey::NetReasonChecksum,
+                DesyncReason::InvalidHistory(_) => TextKey::NetReasonHistory,
+            };
+            (
+                TextKey::NetDesyncFormat,
+                TextArgs(vec![
+                    TextArg::Number(u64::from(*sequence) + 1),
+                    TextArg::Key(reason),
+                ]),
+            )
+        }
+        SyncEvent::Resynced { sequence, diff } => (
+            TextKey::NetResyncedFormat,
+            TextArgs(vec![
+                TextArg::Number((*sequence).into()),
+                TextArg::Number(diff.changed().count_ones().into()),
+            ]),
+        ),
+        SyncEvent::ResyncFailed { sequence } => (
+            TextKey::NetResyncFailedFormat,
+            TextArgs(vec![TextArg::Number((*sequence).into())]),
+        ),
+    }
+}
+
+/// 提示的类别：失步是警告，重新同步失败是错误
+pub fn sync_notice_kind(event: &SyncEvent) -> ToastKind {
+    match event {
+        SyncEvent::Desync { .. } => ToastKind::Warning,
+        SyncEvent::Resynced { .. } => ToastKind::Success,
+        SyncEvent::ResyncFailed { .. } => ToastKind::Error,
+    }
+}
+
+/// 收到同步事件时发送提示
+pub fn show_sync_notices(
+    mut sync_events: EventReader<SyncEvent>,
+    mut toast_events: EventWriter<ToastEvent>,
+) {
+    for event in sync_events.read() {
+        let (key, args) = sync_notice_text(event);
+        toast_events.write(ToastEvent {
+            kind: sync_notice_kind(event),
+            key,
+            args,
+        });
+    }
+}
@@ -0,0 +1,127 @@
+// 棋谱输入框 - 桌面端开启后显示在棋盘下方，显示正在键入的走法，无效时标红
+
+use super::GameUI;
+use crate::{
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    input::NotationInput,
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    platform::{Platform, PlatformProfile},
+    preferences::Preferences,
+};
+use bevy::prelude::*;
+
+/// 正常输入时的文本颜色
+const NOTATION_TEXT_COLOR: Color = Color::WHITE;
+
+/// 记法无效或走法不合法时的文本颜色
+const NOTATION_REJECTED_COLOR: Color = Color::srgb(1.0, 0.45, 0.4);
+
+#[derive(Component)]
+pub struct NotationInputBox;
+
+#[derive(Component)]
+pub struct NotationInputText;
+
+/// 输入框中显示的文本参数，没有输入时显示光标占位
+fn notation_args(input: &NotationInput) -> TextArgs {
+    TextArgs(vec![TextArg::Value(format!("{}_", input.text))])
+}
+
+/// 按平台和偏好设置显示或移除输入框，只在桌面端显示
+pub fn manage_notation_input_box(
+    mut commands: Commands,
+    preferences: Res<Preferences>,
+    platform_profile: Res<PlatformProfile>,
+    box_query: Query<Entity, With<NotationInputBox>>,
+    ui_root: Query<Entity, With<GameUI>>,
+    mut notation_input: ResMut<NotationInput>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    let enabled =
+        preferences.input.notation_input && platform_profile.platform == Platform::Desktop;
+    match (enabled, box_query.single()) {
+        (true, Err(_)) => {
+            let Ok(root) = ui_root.single() else {
+                return;
+            };
+            *notation_input = NotationInput::default();
+            let font = get_font_for_language(&language_settings, &font_assets);
+            spawn_notation_input_box(&mut commands, root, &font, &notation_input);
+        }
+        (false, Ok(entity)) => {
+            commands.entity(entity).despawn();
+        }
+        _ => {}
+    }
+}
+
+/// 在棋盘下方居中创建输入框
+fn spawn_notation_input_box(
+    commands: &mut Commands,
+    root: Entity,
+    font: &Handle<Font>,
+    input: &NotationInput,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                right: Val::Px(0.0),
+                bottom: Val::Px(56.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Pickable::IGNORE,
+            NotationInputBox,
+            ChildOf(root),
+        ))
+        .with_children(|row| {
+            row.spawn((
+                Node {
+                    min_width: Val::Px(120.0),
+                    padding: UiRect::axes(Val::Px(10.0), Val::Px(6.0)),
+                    border: UiRect::all(Val::Px(1.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                BorderColor(Color::srgb(0.6, 0.6, 0.6)),
+                BorderRadius::all(Val::Px(6.0)),
+            ))
+            .with_children(|field| {
+                field.spawn((
+                    Text::default(),
+                    TextKey::NotationInputFormat,
+                    notation_args(input),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(NOTATION_TEXT_COLOR),
+                    LocalizedText,
+                    NotationInputText,
+                ));
+            });
+        });
+}
+
+/// 输入变化后更新输入框的文本和颜色
+pub fn update_notation_input_text(
+    notation_input: Res<NotationInput>,
+    mut text_query: Query<(&mut TextArgs, &mut TextColor), With<NotationInputText>>,
+) {
+    if !notation_input.is_changed() {
+        return;
+    }
+    for (mut args, mut color) in text_query.iter_mut() {
+        args.set_if_neq(notation_args(&notation_input));
+        color.set_if_neq(TextColor(if notation_input.rejected {
+            NOTATION_REJECTED_COLOR
+        } else {
+            NOTATION_TEXT_COLOR
+        }));
+    }
+}
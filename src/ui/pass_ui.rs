@@ -0,0 +1,141 @@
+// 跳过按钮 - 位于悔棋按钮下方，只有轮到的玩家确实无棋可走时才可点击
+//
+// 玩家无棋可走时不再自动跳过，需要点击按钮确认，跳过时播放音效并播报
+
+use super::{spawn_label, ButtonColors, ButtonStyle};
+use crate::{
+    ai::AiPlayer,
+    game::{GameLog, MoveAction, MoveCommand},
+    localization::TextKey,
+    practice::OpeningAutoplay,
+};
+use bevy::prelude::*;
+
+/// 可以跳过时的按钮颜色，比悔棋按钮更醒目以提示玩家
+const PASS_ENABLED_COLORS: ButtonColors = ButtonColors {
+    normal: Color::srgba(0.55, 0.35, 0.1, 0.9),
+    hovered: Color::srgba(0.65, 0.45, 0.15, 0.95),
+    pressed: Color::srgba(0.45, 0.28, 0.08, 0.95),
+};
+
+/// 不能跳过时的按钮颜色，悬停时不变亮
+const PASS_DISABLED_COLORS: ButtonColors = ButtonColors {
+    normal: Color::srgba(0.15, 0.15, 0.15, 0.5),
+    hovered: Color::srgba(0.15, 0.15, 0.15, 0.5),
+    pressed: Color::srgba(0.15, 0.15, 0.15, 0.5),
+};
+
+/// 玩家请求跳过回合，是否执行由`pass_available`决定
+#[derive(Event)]
+pub struct PassRequestEvent;
+
+#[derive(Component)]
+pub struct PassButton;
+
+#[derive(Component)]
+pub struct PassButtonText;
+
+/// 玩家能否跳过回合：轮到玩家（不是AI）且没有任何合法走法
+pub fn pass_available(log: &GameLog, ai_player: Option<&AiPlayer>) -> bool {
+    log.must_pass() && ai_player.is_none_or(|ai| ai.color != log.to_move())
+}
+
+/// 在悔棋按钮下方创建跳过按钮
+pub fn spawn_pass_button(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
+    let style = ButtonStyle {
+        colors: Some(PASS_DISABLED_COLORS),
+        text_color: Color::srgb(0.55, 0.55, 0.55),
+        ..ButtonStyle::corner(112.0)
+    };
+    parent
+        .spawn((style.bundle(), PassButton))
+        .with_children(|button| {
+            spawn_label(
+                button,
+                &style.label_style(),
+                font,
+                TextKey::PassButton,
+                PassButtonText,
+            );
+        });
+}
+
+/// 点击跳过按钮时请求跳过回合
+pub fn handle_pass_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<PassButton>)>,
+    mut pass_events: EventWriter<PassRequestEvent>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            pass_events.write(PassRequestEvent);
+        }
+    }
+}
+
+/// 玩家点击跳过按钮 - 确实无棋可走时转换为跳过回合的命令
+pub fn handle_pass_requests(
+    mut pass_events: EventReader<PassRequestEvent>,
+    log_query: Query<&GameLog>,
+    ai_query: Query<&AiPlayer>,
+    autoplay: Res<OpeningAutoplay>,
+    mut move_commands: EventWriter<MoveCommand>,
+) {
+    // 同一帧多次点击只跳过一次
+    if pass_events.read().count() == 0 || autoplay.is_playing() {
+        return;
+    }
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+    if pass_available(log, ai_query.single().ok()) {
+        move_commands.write(log.next_command(MoveAction::Pass));
+    }
+}
+
+/// 按当前能否跳过更新按钮的颜色
+pub fn update_pass_button(
+    mut button_query: Query<
+        (
+            &Interaction,
+            &mut ButtonColors,
+            &mut BackgroundColor,
+            &Children,
+        ),
+        With<PassButton>,
+    >,
+    mut label_query: Query<&mut TextColor, With<PassButtonText>>,
+    log_query: Query<&GameLog>,
+    ai_query: Query<&AiPlayer>,
+) {
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+    let available = pass_available(log, ai_query.single().ok());
+
+    for (interaction, mut colors, mut background, children) in button_query.iter_mut() {
+        let target = if available {
+            PASS_ENABLED_COLORS
+        } else {
+            PASS_DISABLED_COLORS
+        };
+        if colors.normal != target.normal {
+            background.0 = match interaction {
+                Interaction::None => target.normal,
+                Interaction::Hovered => target.hovered,
+                Interaction::Pressed => target.pressed,
+            };
+            *colors = target;
+        }
+
+        for child in children.iter() {
+            if let Ok(mut color) = label_query.get_mut(child) {
+                let text_color = if available {
+                    Color::WHITE
+                } else {
+                    Color::srgb(0.55, 0.55, 0.55)
+                };
+                color.set_if_neq(TextColor(text_color));
+            }
+        }
+    }
+}
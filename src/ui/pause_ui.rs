@@ -0,0 +1,305 @@
+// 暂停菜单 - 对局中按Esc暂停，可以继续、重新开始或回到主菜单
+//
+// 开局练习中菜单里还有AI强度滑块，按PRACTICE_LADDER逐档调整AI的难度，
+// 之后的走棋立即使用新难度的搜索参数，不需要重新开始（见AiPlayer::set_difficulty）
+
+use super::{
+    difficulty_text_arg, spawn_button, spawn_label, spawn_panel, BackToDifficultyButton,
+    ButtonStyle, FocusScope, GameUI, LabelStyle, PanelStyle, RestartButton, ToDelete, UiState,
+};
+use crate::{
+    ai::{AiDifficulty, AiPlayer},
+    fonts::{get_font_for_language, FontAssets},
+    input::{GamepadAction, GamepadActionEvent, InputAction, InputMap},
+    localization::{LanguageSettings, TextArgs, TextKey},
+    practice::SelectedOpening,
+    state::GameState,
+};
+use bevy::prelude::*;
+
+/// 滑块中当前档及更弱各档的颜色
+const PIP_FILLED: Color = Color::srgb(0.85, 0.65, 0.2);
+
+/// 滑块中更强各档的颜色
+const PIP_EMPTY: Color = Color::srgb(0.35, 0.35, 0.35);
+
+#[derive(Component)]
+pub struct PauseMenu;
+
+#[derive(Component)]
+pub struct PauseResumeButton;
+
+/// 调整AI强度的按钮，值为调整的档数（正数变强）
+#[derive(Component)]
+pub struct PauseDifficultyButton(pub i32);
+
+/// AI当前难度的文本
+#[derive(Component)]
+pub struct PauseDifficultyText;
+
+/// 滑块中的一档，值为在PRACTICE_LADDER中的序号
+#[derive(Component)]
+pub struct PauseDifficultyPip(pub usize);
+
+/// 开局练习中AI的当前难度，普通对局和沙盒中没有强度滑块
+pub fn practice_difficulty(
+    selected_opening: &SelectedOpening,
+    ai_player: Option<&AiPlayer>,
+) -> Option<AiDifficulty> {
+    selected_opening
+        .0
+        .and(ai_player)
+        .map(|ai_player| ai_player.difficulty)
+}
+
+fn difficulty_text_args(difficulty: AiDifficulty) -> TextArgs {
+    TextArgs(vec![difficulty_text_arg(difficulty)])
+}
+
+/// 滑块中一档的颜色：当前难度及更弱的档为填充色，不在阶梯中的难度不填充
+fn pip_color(difficulty: AiDifficulty, pip: usize) -> Color {
+    let current = AiDifficulty::PRACTICE_LADDER
+        .iter()
+        .position(|&rung| rung == difficulty);
+    if current.is_some_and(|current| pip <= current) {
+        PIP_FILLED
+    } else {
+        PIP_EMPTY
+    }
+}
+
+/// 对局未暂停，用作对局逻辑系统的运行条件
+pub fn game_not_paused(ui_state: Res<UiState>) -> bool {
+    !ui_state.paused
+}
+
+/// Esc键或手柄B键、Start键切换暂停
+pub fn handle_pause_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut gamepad_actions: EventReader<GamepadActionEvent>,
+    mut ui_state: ResMut<UiState>,
+) {
+    let gamepad_pause = gamepad_actions
+        .read()
+        .any(|GamepadActionEvent(action)| *action == GamepadAction::Pause);
+
+    if input_map.just_pressed(InputAction::Pause, &keyboard_input) || gamepad_pause {
+        ui_state.paused = !ui_state.paused;
+    }
+}
+
+pub fn handle_pause_resume_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<PauseResumeButton>)>,
+    mut ui_state: ResMut<UiState>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            ui_state.paused = false;
+        }
+    }
+}
+
+/// 开局练习中调整AI强度，之后的走棋使用新的难度
+pub fn handle_pause_difficulty_buttons(
+    interaction_query: Query<(&Interaction, &PauseDifficultyButton), Changed<Interaction>>,
+    selected_opening: Res<SelectedOpening>,
+    mut ai_query: Query<&mut AiPlayer>,
+    mut text_query: Query<&mut TextArgs, With<PauseDifficultyText>>,
+    mut pip_query: Query<(&PauseDifficultyPip, &mut BackgroundColor)>,
+) {
+    if selected_opening.0.is_none() {
+        return;
+    }
+    let Ok(mut ai_player) = ai_query.single_mut() else {
+        return;
+    };
+    for (interaction, PauseDifficultyButton(steps)) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let difficulty = ai_player.difficulty.stepped(*steps);
+        ai_player.set_difficulty(difficulty);
+
+        for mut args in text_query.iter_mut() {
+            args.set_if_neq(difficulty_text_args(difficulty));
+        }
+        for (PauseDifficultyPip(pip), mut background) in pip_query.iter_mut() {
+            background.0 = pip_color(difficulty, *pip);
+        }
+    }
+}
+
+pub fn manage_pause_menu(
+    mut commands: Commands,
+    ui_state: Res<UiState>,
+    menu_query: Query<Entity, With<PauseMenu>>,
+    ui_root: Query<Entity, With<GameUI>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    selected_opening: Res<SelectedOpening>,
+    ai_query: Query<&AiPlayer>,
+) {
+    if !ui_state.is_changed() {
+        return;
+    }
+
+    let menu_open = !menu_query.is_empty();
+    if ui_state.paused == menu_open {
+        return;
+    }
+
+    for entity in menu_query.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+    if let (true, Ok(root)) = (ui_state.paused, ui_root.single()) {
+        let difficulty = practice_difficulty(&selected_opening, ai_query.single().ok());
+        spawn_pause_menu(
+            &mut commands,
+            root,
+            &language_settings,
+            &font_assets,
+            difficulty,
+        );
+    }
+}
+
+fn spawn_pause_menu(
+    commands: &mut Commands,
+    root: Entity,
+    language_settings: &LanguageSettings,
+    font_assets: &FontAssets,
+    practice_difficulty: Option<AiDifficulty>,
+) {
+    let font = get_font_for_language(language_settings, font_assets);
+
+    let overlay = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+            PauseMenu,
+            FocusScope,
+            ChildOf(root),
+            StateScoped(GameState::Playing),
+        ))
+        .id();
+    let menu_style = PanelStyle {
+        width: Val::Px(260.0),
+        ..default()
+    };
+    let menu = spawn_panel(commands, &menu_style, ChildOf(overlay));
+    commands.entity(menu).with_children(|menu| {
+        let title_style = LabelStyle {
+            font_size: 22.0,
+            ..default()
+        };
+        spawn_label(
+            menu,
+            &title_style,
+            &font,
+            TextKey::PauseTitle,
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        );
+
+        let button_style = |color| ButtonStyle { color, ..default() };
+        spawn_button(
+            menu,
+            &button_style(Color::srgb(0.2, 0.5, 0.2)),
+            &font,
+            TextKey::PauseResume,
+            PauseResumeButton,
+        );
+
+        if let Some(difficulty) = practice_difficulty {
+            spawn_difficulty_slider(menu, difficulty, &font);
+        }
+
+        spawn_button(
+            menu,
+            &button_style(Color::srgb(0.25, 0.25, 0.3)),
+            &font,
+            TextKey::PauseRestart,
+            RestartButton,
+        );
+        spawn_button(
+            menu,
+            &button_style(Color::srgb(0.3, 0.3, 0.3)),
+            &font,
+            TextKey::PauseMenu,
+            BackToDifficultyButton,
+        );
+    });
+}
+
+/// AI强度滑块：难度名称、减弱和增强按钮，以及表示各档的圆点
+fn spawn_difficulty_slider(
+    menu: &mut ChildSpawnerCommands,
+    difficulty: AiDifficulty,
+    font: &Handle<Font>,
+) {
+    let text_style = LabelStyle {
+        font_size: 16.0,
+        ..default()
+    };
+    spawn_label(
+        menu,
+        &text_style,
+        font,
+        TextKey::PauseAiStrengthFormat,
+        (difficulty_text_args(difficulty), PauseDifficultyText),
+    );
+
+    menu.spawn(Node {
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(8.0),
+        ..default()
+    })
+    .with_children(|row| {
+        let step_style = ButtonStyle {
+            width: Val::Px(36.0),
+            height: Val::Px(36.0),
+            color: Color::srgb(0.25, 0.25, 0.3),
+            ..default()
+        };
+        for steps in [-1, 1] {
+            // 按钮上是不需要翻译的符号，不使用spawn_button
+            row.spawn((step_style.bundle(), PauseDifficultyButton(steps)))
+                .with_children(|button| {
+                    button.spawn((
+                        Text::new(if steps < 0 { "-" } else { "+" }),
+                        TextFont {
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
+            // 圆点放在两个按钮之间
+            if steps < 0 {
+                for pip in 0..AiDifficulty::PRACTICE_LADDER.len() {
+                    row.spawn((
+                        Node {
+                            width: Val::Px(12.0),
+                            height: Val::Px(12.0),
+                            ..default()
+                        },
+                        BackgroundColor(pip_color(difficulty, pip)),
+                        BorderRadius::MAX,
+                        PauseDifficultyPip(pip),
+                    ));
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,156 @@
+// 性能信息层 - F2或设置中开关，在屏幕右下角显示帧率、帧时间、实体数和AI每步用时
+//
+// 用于诊断手机和Web上的卡顿：帧率和实体数来自Bevy的诊断插件，AI用时来自对局AI的计算统计，
+// 不含模拟思考的等待时间。开关随偏好设置保存，在所有界面中显示，每0.5秒刷新一次以免频繁重新布局
+
+use super::ToDelete;
+use crate::{
+    ai::AiPlayer,
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    input::{InputAction, InputMap},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    preferences::Preferences,
+};
+use bevy::{
+    diagnostic::{DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+use std::time::Duration;
+
+/// 信息层的文本颜色
+const PERFORMANCE_TEXT_COLOR: Color = Color::srgb(1.0, 0.95, 0.6);
+
+/// 刷新间隔（秒）
+const PERFORMANCE_REFRESH_SECS: f32 = 0.5;
+
+#[derive(Component)]
+pub struct PerformanceOverlay;
+
+/// 一次采样的性能数据，还没有数据的项为None
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PerformanceSample {
+    /// 平滑后的帧率
+    pub fps: Option<f64>,
+    /// 平滑后的帧时间（毫秒）
+    pub frame_time_ms: Option<f64>,
+    /// 实体数
+    pub entities: Option<f64>,
+    /// AI最近一步的计算用时
+    pub ai_last: Option<Duration>,
+    /// AI本局每步的平均计算用时
+    pub ai_average: Option<Duration>,
+}
+
+impl PerformanceSample {
+    /// 从诊断数据和对局AI读取，没有诊断插件或AI时对应的项为None
+    pub fn read(diagnostics: Option<&DiagnosticsStore>, ai_player: Option<&AiPlayer>) -> Self {
+        let smoothed = |path| {
+            diagnostics
+                .and_then(|store| store.get(path))
+                .and_then(|diagnostic| diagnostic.smoothed())
+        };
+        Self {
+            fps: smoothed(&FrameTimeDiagnosticsPlugin::FPS),
+            frame_time_ms: smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME),
+            entities: diagnostics
+                .and_then(|store| store.get(&EntityCountDiagnosticsPlugin::ENTITY_COUNT))
+                .and_then(|diagnostic| diagnostic.value()),
+            ai_last: ai_player.and_then(AiPlayer::last_search_time),
+            ai_average: ai_player.and_then(AiPlayer::average_search_time),
+        }
+    }
+
+    /// 信息层文本的参数
+    pub fn text_args(&self) -> TextArgs {
+        let value = |value: Option<String>| TextArg::Value(value.unwrap_or_else(|| "-".into()));
+        let millis = |duration: Option<Duration>| {
+            value(duration.map(|duration| format!("{} ms", duration.as_millis())))
+        };
+        TextArgs(vec![
+            value(self.fps.map(|fps| format!("{fps:.0}"))),
+            value(self.frame_time_ms.map(|ms| format!("{ms:.1}"))),
+            self.entities
+                .map_or_else(|| value(None), |count| TextArg::Number(count as u64)),
+            millis(self.ai_last),
+            millis(self.ai_average),
+        ])
+    }
+}
+
+/// 默认F2切换性能信息层，开关保存到偏好设置
+pub fn toggle_performance_overlay(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut preferences: ResMut<Preferences>,
+) {
+    if input_map.just_pressed(InputAction::TogglePerformanceOverlay, &keyboard_input) {
+        preferences.display.performance_overlay = !preferences.display.performance_overlay;
+    }
+}
+
+/// 根据开关显示或移除信息层
+pub fn manage_performance_overlay(
+    mut commands: Commands,
+    preferences: Res<Preferences>,
+    overlay_query: Query<Entity, With<PerformanceOverlay>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    if !preferences.display.performance_overlay {
+        for entity in overlay_query.iter() {
+            commands.entity(entity).insert(ToDelete);
+        }
+        return;
+    }
+
+    if !overlay_query.is_empty() {
+        return;
+    }
+
+    commands.spawn((
+        Text::default(),
+        TextKey::PerformanceOverlayFormat,
+        PerformanceSample::default().text_args(),
+        TextFont {
+            font: get_font_for_language(&language_settings, &font_assets),
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(PERFORMANCE_TEXT_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(8.0),
+            bottom: Val::Px(8.0),
+            padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        // 显示在所有界面之上
+        GlobalZIndex(i32::MAX),
+        PerformanceOverlay,
+        LocalizedText,
+    ));
+}
+
+/// 每隔一段时间刷新信息层，只在内容变化时修改
+pub fn update_performance_overlay(
+    diagnostics: Option<Res<DiagnosticsStore>>,
+    ai_query: Query<&AiPlayer>,
+    time: Res<Time<Real>>,
+    mut since_refresh: Local<f32>,
+    mut overlay_query: Query<&mut TextArgs, With<PerformanceOverlay>>,
+) {
+    if overlay_query.is_empty() {
+        return;
+    }
+    *since_refresh += time.delta_secs();
+    if *since_refresh < PERFORMANCE_REFRESH_SECS {
+        return;
+    }
+    *since_refresh = 0.0;
+
+    let args = PerformanceSample::read(diagnostics.as_deref(), ai_query.single().ok()).text_args();
+    for mut overlay_args in overlay_query.iter_mut() {
+        overlay_args.set_if_neq(args.clone());
+    }
+}
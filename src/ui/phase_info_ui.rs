@@ -0,0 +1,82 @@
+// 阶段信息 - 分数下方按对局阶段轮换显示的局面信息
+//
+// 开局显示双方的行动力，中局显示双方的稳定子数，残局显示剩余空位和奇偶性；
+// 数据来自评估分解（ai::evaluation::EvalBreakdown），每次走棋或悔棋改变棋盘后更新
+
+use crate::{
+    ai::evaluation::{EvalBreakdown, GamePhase},
+    fonts::LocalizedText,
+    game::{Board, PlayerColor},
+    localization::{TextArg, TextArgs, TextKey},
+};
+use bevy::prelude::*;
+
+/// 阶段信息文本
+#[derive(Component)]
+pub struct PhaseInfoText;
+
+/// 局面对应的阶段信息，双方的数值按黑、白的顺序排列，与分数组件一致
+pub fn phase_info(board: &Board) -> (TextKey, TextArgs) {
+    let breakdown = EvalBreakdown::of(board);
+    let pair = |count: fn(&EvalBreakdown, PlayerColor) -> u32| {
+        TextArgs(vec![
+            TextArg::Number(count(&breakdown, PlayerColor::Black).into()),
+            TextArg::Number(count(&breakdown, PlayerColor::White).into()),
+        ])
+    };
+    match GamePhase::of_board(board) {
+        GamePhase::Opening => (
+            TextKey::PhaseMobilityFormat,
+            pair(EvalBreakdown::mobility_of),
+        ),
+        GamePhase::Midgame => (TextKey::PhaseStableFormat, pair(EvalBreakdown::stable_of)),
+        GamePhase::Endgame => (
+            TextKey::PhaseEmptiesFormat,
+            TextArgs(vec![
+                TextArg::Number(breakdown.empties.into()),
+                TextArg::Key(if breakdown.odd_parity() {
+                    TextKey::ParityOdd
+                } else {
+                    TextKey::ParityEven
+                }),
+            ]),
+        ),
+    }
+}
+
+/// 在信息面板中创建阶段信息文本
+pub fn spawn_phase_info(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
+    let (key, args) = phase_info(&Board::new_standard());
+    parent.spawn((
+        Text::default(),
+        key,
+        args,
+        TextFont {
+            font: font.clone(),
+            font_size: 11.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.75, 0.75, 0.75)),
+        LocalizedText,
+        PhaseInfoText,
+    ));
+}
+
+/// 棋盘变化后更新阶段信息
+pub fn update_phase_info(
+    board_query: Query<Ref<Board>>,
+    mut text_query: Query<(&mut TextKey, &mut TextArgs), With<PhaseInfoText>>,
+) {
+    let Ok(board) = board_query.single() else {
+        return;
+    };
+    if !board.is_changed() {
+        return;
+    }
+
+    let (info_key, info_args) = phase_info(&board);
+    for (mut key, mut args) in text_query.iter_mut() {
+        key.set_if_neq(info_key);
+        args.set_if_neq(info_args.clone());
+    }
+}
@@ -0,0 +1,181 @@
+// 规则示例 - 规则面板中用当前局面演示一步合法走法和它翻转的棋子
+//
+// 每次打开规则面板时按实时局面生成：取轮到走棋一方翻转棋子最多的合法走法（一样多时取序号最小的），
+// 在小棋盘上用圆环标出落子位置、用描边标出将被翻转的棋子，旁边的文字说明同一步棋。
+// 轮到的一方无棋可走时改用另一方；还没有对局或已经终局时使用标准开局局面
+
+use super::BoardColors;
+use crate::{
+    fonts::LocalizedText,
+    game::{Board, GameLog, PlayerColor},
+    localization::{TextArg, TextArgs, TextKey},
+};
+use bevy::prelude::*;
+
+/// 示例棋盘每格的边长
+const EXAMPLE_CELL_SIZE: f32 = 14.0;
+
+/// 落子位置和被翻转棋子的标记颜色
+const EXAMPLE_HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// 规则面板中的示例
+#[derive(Component)]
+pub struct RulesExampleView;
+
+/// 一步示例走法
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RulesExample {
+    /// 落子前的局面
+    pub board: Board,
+    /// 走棋的一方
+    pub player: PlayerColor,
+    /// 落子位置
+    pub position: u8,
+    /// 将被翻转的棋子，按序号排列
+    pub flipped: Vec<u8>,
+}
+
+impl RulesExample {
+    /// 局面中轮到的一方（无棋可走时为另一方）翻转棋子最多的走法，双方都无棋可走时返回None
+    pub fn for_position(board: &Board, to_move: PlayerColor) -> Option<Self> {
+        [to_move, to_move.opposite()]
+            .into_iter()
+            .find_map(|player| {
+                board
+                    .get_valid_moves_list(player)
+                    .into_iter()
+                    .map(|valid| {
+                        let mut flipped: Vec<u8> = board
+                            .flipped_rays(valid.position, player)
+                            .into_iter()
+                            .flatten()
+                            .collect();
+                        flipped.sort_unstable();
+                        (valid.position, flipped)
+                    })
+                    // 翻转一样多时保留序号较小的走法
+                    .rev()
+                    .max_by_key(|(_, flipped)| flipped.len())
+                    .map(|(position, flipped)| Self {
+                        board: *board,
+                        player,
+                        position,
+                        flipped,
+                    })
+            })
+    }
+
+    /// 当前对局的示例，没有对局或已经终局时使用标准开局局面
+    pub fn for_game(log: Option<&GameLog>) -> Self {
+        log.and_then(|log| Self::for_position(&log.board(), log.to_move()))
+            .or_else(|| Self::for_position(&Board::new_standard(), PlayerColor::Black))
+            .expect("the standard opening has legal moves")
+    }
+
+    /// 说明文字的键和参数
+    pub fn text(&self) -> (TextKey, TextArgs) {
+        let player = match self.player {
+            PlayerColor::Black => TextKey::BlackName,
+            PlayerColor::White => TextKey::WhiteName,
+        };
+        let flipped = self
+            .flipped
+            .iter()
+            .map(|&position| Board::position_to_notation(position))
+            .collect::<Vec<_>>()
+            .join(", ");
+        (
+            TextKey::RulesExampleFormat,
+            TextArgs(vec![
+                TextArg::Key(player),
+                TextArg::Value(Board::position_to_notation(self.position)),
+                TextArg::Value(flipped),
+            ]),
+        )
+    }
+}
+
+/// 在规则面板中生成示例棋盘和说明文字
+pub fn spawn_rules_example(
+    parent: &mut ChildSpawnerCommands,
+    example: &RulesExample,
+    font: &Handle<Font>,
+    colors: &BoardColors,
+) {
+    let (key, args) = example.text();
+    parent
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(10.0),
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            RulesExampleView,
+        ))
+        .with_children(|row| {
+            row.spawn((
+                Node {
+                    width: Val::Px(EXAMPLE_CELL_SIZE * 8.0 + 2.0),
+                    height: Val::Px(EXAMPLE_CELL_SIZE * 8.0 + 2.0),
+                    flex_shrink: 0.0,
+                    padding: UiRect::all(Val::Px(1.0)),
+                    grid_template_columns: RepeatedGridTrack::flex(8, 1.0),
+                    grid_template_rows: RepeatedGridTrack::flex(8, 1.0),
+                    ..default()
+                },
+                BackgroundColor(colors.board_color),
+                BorderRadius::all(Val::Px(2.0)),
+            ))
+            .with_children(|grid| {
+                for position in 0..64u8 {
+                    grid.spawn(example_cell(example, position, colors));
+                }
+            });
+
+            row.spawn((
+                Text::default(),
+                key,
+                args,
+                TextFont {
+                    font: font.clone(),
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                Node {
+                    flex_shrink: 1.0,
+                    ..default()
+                },
+                LocalizedText,
+            ));
+        });
+}
+
+/// 示例棋盘中的一格：棋子按局面着色，落子位置显示空心圆环，将被翻转的棋子加描边
+fn example_cell(example: &RulesExample, position: u8, colors: &BoardColors) -> impl Bundle {
+    let disc = match example.board.get_piece(position) {
+        Some(PlayerColor::Black) => colors.black_piece_color,
+        Some(PlayerColor::White) => colors.white_piece_color,
+        None => Color::NONE,
+    };
+    let marked = position == example.position || example.flipped.contains(&position);
+    (
+        Node {
+            width: Val::Percent(80.0),
+            height: Val::Percent(80.0),
+            justify_self: JustifySelf::Center,
+            align_self: AlignSelf::Center,
+            border: UiRect::all(Val::Px(if marked { 2.0 } else { 0.0 })),
+            ..default()
+        },
+        BackgroundColor(disc),
+        BorderColor(if marked {
+            EXAMPLE_HIGHLIGHT_COLOR
+        } else {
+            Color::NONE
+        }),
+        BorderRadius::MAX,
+    )
+}
@@ -0,0 +1,37 @@
+// 存档冲突提示 - 同时运行的另一个游戏实例保存过同一份数据时，在屏幕底部短暂提示
+//
+// 冲突由存储后端在写入时发现（见preferences::save_guard），本实例的数据已经按最后写入者为准保存，
+// 提示只是让玩家知道另一个窗口的修改被覆盖了。提示通过通知提示显示（见toast_ui）
+
+use super::ToastEvent;
+use crate::{
+    localization::{TextArg, TextKey},
+    preferences::{save_guard::take_save_conflicts, PREFERENCES_KEY},
+    record::ARCHIVE_KEY,
+    stats::STATS_KEY,
+};
+use bevy::prelude::*;
+
+/// 存储键对应的数据名称
+pub fn save_data_name_key(key: &str) -> TextKey {
+    match key {
+        PREFERENCES_KEY => TextKey::SaveDataPreferences,
+        STATS_KEY => TextKey::SaveDataStats,
+        ARCHIVE_KEY => TextKey::SaveDataArchive,
+        _ => TextKey::SaveDataOther,
+    }
+}
+
+/// 存储后端发现冲突时发送警告提示，每份数据提示一次
+pub fn show_save_conflicts(mut toast_events: EventWriter<ToastEvent>) {
+    for conflict in take_save_conflicts() {
+        warn!(
+            "{} was also saved by another instance at {} ms; kept this instance's version",
+            conflict.key, conflict.other.saved_at
+        );
+        toast_events.write(
+            ToastEvent::warning(TextKey::SaveConflictFormat)
+                .with_args(vec![TextArg::Key(save_data_name_key(&conflict.key))]),
+        );
+    }
+}
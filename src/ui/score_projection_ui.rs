@@ -0,0 +1,103 @@
+// 残局比分预测 - 开局练习进入最后10个空位后，在每个可落子指示器上标注该步的精确终局比分
+//
+// 比分由残局求解器在双方完美下法下算出，是玩家视角的终局子数差（"+4"表示赢4子）。
+// 标注作为可落子指示器的子实体生成，指示器重建时随之移除
+
+use super::{CurrentPlayer, ValidMoveIndicator};
+use crate::{
+    ai::{endgame::solve_each_move, AiPlayer},
+    assist::ActiveMatchRules,
+    game::Board,
+    practice::SelectedOpening,
+    preferences::Preferences,
+    sandbox::SandboxMode,
+};
+use bevy::prelude::*;
+
+/// 剩余空位不超过该数时显示比分预测
+pub const PROJECTION_MAX_EMPTIES: u32 = 10;
+
+/// 标注相对指示器的Z偏移
+const LABEL_Z: f32 = 0.1;
+
+/// 比分标注的字号
+const LABEL_FONT_SIZE: f32 = 11.0;
+
+/// 可落子指示器上的比分标注
+#[derive(Component)]
+pub struct ScoreProjectionLabel {
+    pub score: i32,
+}
+
+/// 比分标注文本：赢为"+4"，输为"-2"，平局为"0"
+pub fn projection_label(score: i32) -> String {
+    if score > 0 {
+        format!("+{score}")
+    } else {
+        score.to_string()
+    }
+}
+
+/// 比分标注的颜色：赢绿、输红、平局灰
+fn projection_color(score: i32) -> Color {
+    match score {
+        s if s > 0 => Color::srgb(0.4, 1.0, 0.4),
+        s if s < 0 => Color::srgb(1.0, 0.45, 0.4),
+        _ => Color::srgb(0.85, 0.85, 0.85),
+    }
+}
+
+/// 可落子指示器重建时，在开局练习的残局中为每个指示器标注终局比分，对局规则或辅助等级不允许时不标注
+pub fn update_score_projections(
+    mut commands: Commands,
+    indicator_query: Query<(Entity, &ValidMoveIndicator), Added<ValidMoveIndicator>>,
+    board_query: Query<&Board>,
+    current_player: Res<CurrentPlayer>,
+    ai_query: Query<&AiPlayer>,
+    selected_opening: Res<SelectedOpening>,
+    sandbox: Res<SandboxMode>,
+    match_rules: Res<ActiveMatchRules>,
+    preferences: Res<Preferences>,
+) {
+    if indicator_query.is_empty()
+        || selected_opening.0.is_none()
+        || sandbox.0
+        || !preferences
+            .assist
+            .allowed(match_rules.allowed())
+            .score_projection
+    {
+        return;
+    }
+    let Ok(board) = board_query.single() else {
+        return;
+    };
+    let player = current_player.0;
+    if board.get_empty_squares().count_ones() > PROJECTION_MAX_EMPTIES
+        || ai_query
+            .single()
+            .is_ok_and(|ai_player| ai_player.color == player)
+    {
+        return;
+    }
+
+    let scores = solve_each_move(board, player);
+    for (entity, indicator) in indicator_query.iter() {
+        let Some(&(_, score)) = scores
+            .iter()
+            .find(|(position, _)| *position == indicator.position)
+        else {
+            continue;
+        };
+        commands.entity(entity).with_child((
+            Text2d::new(projection_label(score)),
+            TextFont {
+                font_size: LABEL_FONT_SIZE,
+                ..default()
+            },
+            TextColor(projection_color(score)),
+            Transform::from_xyz(0.0, 0.0, LABEL_Z),
+            ScoreProjectionLabel { score },
+        ));
+    }
+}
@@ -0,0 +1,121 @@
+// AI搜索说明 - AI走棋后在头像左侧显示这一步看了多远，例如"搜索了8步，用时1.4秒，210万个节点"
+//
+// 数据来自AiSearchEvent，只有经过搜索的走法才有；开局库和随机走法不显示。
+// 说明显示一会儿后淡出，减少动效时不淡出、到时直接隐藏。在设置中默认关闭
+
+use crate::{
+    accessibility::MotionSettings,
+    ai::SearchSummary,
+    fonts::LocalizedText,
+    localization::{TextArg, TextArgs, TextKey},
+    plugin::AiSearchEvent,
+    preferences::Preferences,
+};
+use bevy::prelude::*;
+
+/// 说明完全显示的时间（秒）
+pub const SEARCH_CAPTION_HOLD_SECS: f32 = 2.0;
+
+/// 说明淡出的时间（秒）
+pub const SEARCH_CAPTION_FADE_SECS: f32 = 1.0;
+
+/// 说明文字的颜色
+const CAPTION_COLOR: Color = Color::srgb(0.75, 0.75, 0.75);
+
+/// AI头像旁的搜索说明
+#[derive(Component, Default)]
+pub struct SearchCaption {
+    /// 说明已显示的时间（秒），隐藏时为None
+    shown_secs: Option<f32>,
+}
+
+/// 说明文本的参数：深度、用时和简写的节点数
+pub fn search_caption_args(summary: &SearchSummary) -> TextArgs {
+    TextArgs(vec![
+        TextArg::Number(summary.depth.into()),
+        TextArg::Duration(summary.elapsed),
+        TextArg::CompactNumber(summary.nodes),
+    ])
+}
+
+/// 显示了一段时间后说明的透明度，为0时隐藏
+pub fn search_caption_alpha(shown_secs: f32, reduced_motion: bool) -> f32 {
+    let fading = shown_secs - SEARCH_CAPTION_HOLD_SECS;
+    if fading <= 0.0 {
+        1.0
+    } else if reduced_motion || fading >= SEARCH_CAPTION_FADE_SECS {
+        0.0
+    } else {
+        1.0 - fading / SEARCH_CAPTION_FADE_SECS
+    }
+}
+
+/// 在AI头像中创建说明，绝对定位在头像左侧时钟的下方，不影响顶部区域的布局
+pub fn spawn_search_caption(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
+    parent.spawn((
+        Text::default(),
+        TextKey::SearchCaptionFormat,
+        search_caption_args(&SearchSummary {
+            depth: 0,
+            nodes: 0,
+            elapsed: default(),
+        }),
+        TextFont {
+            font: font.clone(),
+            font_size: 11.0,
+            ..default()
+        },
+        TextColor(CAPTION_COLOR),
+        TextLayout::new_with_justify(JustifyText::Right),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(60.0),
+            top: Val::Px(36.0),
+            width: Val::Px(150.0),
+            ..default()
+        },
+        Visibility::Hidden,
+        LocalizedText,
+        SearchCaption::default(),
+    ));
+}
+
+/// AI搜索完成时显示说明，之后逐渐淡出；设置关闭时立即隐藏
+pub fn update_search_caption(
+    mut search_events: EventReader<AiSearchEvent>,
+    preferences: Res<Preferences>,
+    motion_settings: Res<MotionSettings>,
+    time: Res<Time>,
+    mut caption_query: Query<(
+        &mut SearchCaption,
+        &mut TextArgs,
+        &mut TextColor,
+        &mut Visibility,
+    )>,
+) {
+    let enabled = preferences.display.search_caption;
+    let latest = search_events.read().last().filter(|_| enabled);
+
+    for (mut caption, mut args, mut color, mut visibility) in caption_query.iter_mut() {
+        if let Some(event) = latest {
+            *args = search_caption_args(&event.summary);
+            caption.shown_secs = Some(0.0);
+        } else if let Some(shown_secs) = caption.shown_secs.as_mut() {
+            *shown_secs += time.delta_secs();
+        }
+
+        let alpha = match caption.shown_secs {
+            Some(shown_secs) if enabled => {
+                search_caption_alpha(shown_secs, motion_settings.reduced_motion)
+            }
+            _ => 0.0,
+        };
+        if alpha <= 0.0 {
+            caption.shown_secs = None;
+            visibility.set_if_neq(Visibility::Hidden);
+        } else {
+            color.0 = CAPTION_COLOR.with_alpha(alpha);
+            visibility.set_if_neq(Visibility::Inherited);
+        }
+    }
+}
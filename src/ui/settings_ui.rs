@@ -0,0 +1,533 @@
+use super::{spawn_button, ButtonStyle, FocusScope, ToDelete, UiState};
+use crate::{
+    accessibility::{AccessibilitySettings, MotionSettings},
+    assist::AssistLevel,
+    audio::AudioSettings,
+    config_assets::ThemeMode,
+    display::{CycleWindowPresetEvent, TextSize, ToggleFullscreenEvent},
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    game::VictoryCondition,
+    localization::{ChangeLanguageEvent, LanguageSettings, LocalizedTexts, TextKey},
+    preferences::Preferences,
+    series::MatchFormat,
+};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct SettingsButton;
+
+#[derive(Component)]
+pub struct SettingsPanel;
+
+#[derive(Event)]
+pub struct ToggleSettingsEvent;
+
+/// 设置面板中的可调整选项
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingsOption {
+    Fullscreen,
+    WindowSize,
+    Sound,
+    Announcements,
+    ReducedMotion,
+    /// 文字大小，缩放整个界面
+    TextSize,
+    Language,
+    AssistLevel,
+    AssistMode,
+    CornerWarning,
+    Coach,
+    /// AI头像旁的闲聊气泡
+    Banter,
+    CustomCursor,
+    NotationInput,
+    /// AI回合中点击的格子记为预走
+    Premove,
+    Theme,
+    Insights,
+    /// 自定义对局的胜负条件，开始新对局时生效
+    Victory,
+    /// 多局比赛的赛制，开始新比赛时生效
+    Match,
+    /// 对局计时规则，开始新对局时生效
+    TimeControl,
+    /// 对局AI置换表的大小，下一次AI计算时生效
+    SearchMemory,
+    /// 帧率、实体数和AI用时的性能信息层
+    PerformanceOverlay,
+    /// AI走棋后在头像旁显示的搜索说明
+    SearchCaption,
+    /// 打开音效测试面板，没有开关值
+    SoundTest,
+}
+
+impl SettingsOption {
+    pub const ALL: [SettingsOption; 24] = [
+        SettingsOption::Fullscreen,
+        SettingsOption::WindowSize,
+        SettingsOption::Sound,
+        SettingsOption::Announcements,
+        SettingsOption::ReducedMotion,
+        SettingsOption::TextSize,
+        SettingsOption::Language,
+        SettingsOption::AssistLevel,
+        SettingsOption::AssistMode,
+        SettingsOption::CornerWarning,
+        SettingsOption::Coach,
+        SettingsOption::Banter,
+        SettingsOption::CustomCursor,
+        SettingsOption::NotationInput,
+        SettingsOption::Premove,
+        SettingsOption::Theme,
+        SettingsOption::Insights,
+        SettingsOption::Victory,
+        SettingsOption::Match,
+        SettingsOption::TimeControl,
+        SettingsOption::SearchMemory,
+        SettingsOption::PerformanceOverlay,
+        SettingsOption::SearchCaption,
+        SettingsOption::SoundTest,
+    ];
+}
+
+#[derive(Component)]
+pub struct SettingsOptionButton(pub SettingsOption);
+
+#[derive(Component)]
+pub struct SettingsOptionText(pub SettingsOption);
+
+/// 设置选项当前值的快照，用于生成按钮文本
+pub struct SettingsValues<'a> {
+    pub preferences: &'a Preferences,
+    pub audio: &'a AudioSettings,
+    pub accessibility: &'a AccessibilitySettings,
+    pub language: &'a LanguageSettings,
+}
+
+impl SettingsValues<'_> {
+    /// 生成"名称: 值"形式的选项文本
+    pub fn label(&self, texts: &LocalizedTexts, option: SettingsOption) -> String {
+        let on_off = |enabled: bool| {
+            if enabled {
+                texts.value_on
+            } else {
+                texts.value_off
+            }
+        };
+
+        match option {
+            SettingsOption::Fullscreen => format!(
+                "{}: {}",
+                texts.setting_fullscreen,
+                on_off(self.preferences.display.fullscreen)
+            ),
+            SettingsOption::WindowSize => format!(
+                "{}: {}",
+                texts.setting_window_size,
+                self.preferences.display.preset.label()
+            ),
+            SettingsOption::Sound => {
+                format!("{}: {}", texts.setting_sound, on_off(self.audio.enabled))
+            }
+            SettingsOption::Announcements => format!(
+                "{}: {}",
+                texts.setting_announcements,
+                on_off(self.accessibility.announcements_enabled)
+            ),
+            SettingsOption::ReducedMotion => format!(
+                "{}: {}",
+                texts.setting_reduced_motion,
+                on_off(self.preferences.motion.reduced_motion)
+            ),
+            SettingsOption::TextSize => format!(
+                "{}: {}",
+                texts.setting_text_size,
+                match self.preferences.display.text_size {
+                    TextSize::Small => texts.text_size_small,
+                    TextSize::Normal => texts.text_size_normal,
+                    TextSize::Large => texts.text_size_large,
+                    TextSize::ExtraLarge => texts.text_size_extra_large,
+                }
+            ),
+            SettingsOption::Language => format!(
+                "{}: {}",
+                texts.setting_language,
+                texts.get(self.language.current_language.name_key())
+            ),
+            SettingsOption::AssistLevel => format!(
+                "{}: {}",
+                texts.setting_assist_level,
+                match self.preferences.assist.level {
+                    AssistLevel::Hardcore => texts.assist_level_hardcore,
+                    AssistLevel::Off => texts.assist_level_off,
+                    AssistLevel::LegalMoves => texts.assist_level_legal_moves,
+                    AssistLevel::FlipCounts => texts.assist_level_flip_counts,
+                    AssistLevel::Hints => texts.assist_level_hints,
+                }
+            ),
+            SettingsOption::AssistMode => format!(
+                "{}: {}",
+                texts.setting_assist_mode,
+                on_off(self.preferences.assist.enabled)
+            ),
+            SettingsOption::CornerWarning => format!(
+                "{}: {}",
+                texts.setting_corner_warning,
+                on_off(self.preferences.assist.corner_warning)
+            ),
+            SettingsOption::Coach => format!(
+                "{}: {}",
+                texts.setting_coach,
+                on_off(self.preferences.assist.coach)
+            ),
+            SettingsOption::Banter => format!(
+                "{}: {}",
+                texts.setting_banter,
+                on_off(self.preferences.banter.enabled)
+            ),
+            SettingsOption::PerformanceOverlay => format!(
+                "{}: {}",
+                texts.setting_performance_overlay,
+                on_off(self.preferences.display.performance_overlay)
+            ),
+            SettingsOption::SearchCaption => format!(
+                "{}: {}",
+                texts.setting_search_caption,
+                on_off(self.preferences.display.search_caption)
+            ),
+            SettingsOption::CustomCursor => format!(
+                "{}: {}",
+                texts.setting_custom_cursor,
+                on_off(self.preferences.input.custom_cursor)
+            ),
+            SettingsOption::NotationInput => format!(
+                "{}: {}",
+                texts.setting_notation_input,
+                on_off(self.preferences.input.notation_input)
+            ),
+            SettingsOption::Premove => format!(
+                "{}: {}",
+                texts.setting_premove,
+                on_off(self.preferences.input.premove)
+            ),
+            SettingsOption::Theme => format!(
+                "{}: {}",
+                texts.setting_theme,
+                match self.preferences.theme {
+                    ThemeMode::Light => texts.theme_light,
+                    ThemeMode::Dark => texts.theme_dark,
+                    ThemeMode::Auto => texts.theme_auto,
+                }
+            ),
+            SettingsOption::Insights => format!(
+                "{}: {}",
+                texts.setting_insights,
+                on_off(self.preferences.insights)
+            ),
+            SettingsOption::Victory => {
+                format!(
+                    "{}: {}",
+                    texts.setting_victory,
+                    match self.preferences.rules.victory {
+                        VictoryCondition::Standard => texts.victory_standard.to_string(),
+                        VictoryCondition::WinByMargin(margin) => texts
+                            .victory_margin_format
+                            .replacen("{}", &texts.locale_format.number(margin as u64), 1),
+                        VictoryCondition::FirstTo(target) => texts
+                            .victory_first_to_format
+                            .replacen("{}", &texts.locale_format.number(target as u64), 1),
+                    }
+                )
+            }
+            SettingsOption::Match => format!(
+                "{}: {}",
+                texts.setting_match,
+                match self.preferences.rules.match_format {
+                    MatchFormat::Single => texts.match_single.to_string(),
+                    // 参数依次为局数和获胜所需局数，英文只用到局数
+                    format => texts
+                        .match_best_of_format
+                        .replacen("{}", &format.games().to_string(), 1)
+                        .replacen("{}", &format.wins_needed().to_string(), 1),
+                }
+            ),
+            SettingsOption::TimeControl => format!(
+                "{}: {}",
+                texts.setting_time_control,
+                texts.get(self.preferences.rules.time_control.name_key())
+            ),
+            SettingsOption::SearchMemory => format!(
+                "{}: {}",
+                texts.setting_search_memory,
+                texts.search_memory_format.replacen(
+                    "{}",
+                    &self.preferences.search.table_size_mb.to_string(),
+                    1
+                )
+            ),
+            SettingsOption::SoundTest => texts.setting_sound_test.to_string(),
+        }
+    }
+}
+
+pub fn handle_settings_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SettingsButton>)>,
+    mut settings_events: EventWriter<ToggleSettingsEvent>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            settings_events.write(ToggleSettingsEvent);
+        }
+    }
+}
+
+pub fn handle_settings_toggle(
+    mut settings_events: EventReader<ToggleSettingsEvent>,
+    mut ui_state: ResMut<UiState>,
+) {
+    for _event in settings_events.read() {
+        ui_state.show_settings = !ui_state.show_settings;
+    }
+}
+
+pub fn manage_settings_panel(
+    mut commands: Commands,
+    ui_state: Res<UiState>,
+    settings_panel_query: Query<Entity, With<SettingsPanel>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    preferences: Res<Preferences>,
+    audio_settings: Res<AudioSettings>,
+    accessibility_settings: Res<AccessibilitySettings>,
+) {
+    if ui_state.is_changed() {
+        // 标记现有的设置面板为删除
+        for entity in settings_panel_query.iter() {
+            commands.entity(entity).insert(ToDelete);
+        }
+
+        // 如果需要显示设置，创建新的面板
+        if ui_state.show_settings {
+            let values = SettingsValues {
+                preferences: &preferences,
+                audio: &audio_settings,
+                accessibility: &accessibility_settings,
+                language: &language_settings,
+            };
+            spawn_settings_panel(&mut commands, &language_settings, &font_assets, &values);
+        }
+    }
+}
+
+fn spawn_settings_panel(
+    commands: &mut Commands,
+    language_settings: &LanguageSettings,
+    font_assets: &FontAssets,
+    values: &SettingsValues,
+) {
+    let texts = language_settings.get_texts();
+    let font = get_font_for_language(language_settings, font_assets);
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                padding: UiRect::all(Val::Px(15.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            BorderColor(Color::srgb(0.6, 0.6, 0.6)),
+            BorderRadius::all(Val::Px(10.0)),
+            SettingsPanel,
+            FocusScope,
+        ))
+        .with_children(|panel| {
+            // 标题
+            panel.spawn((
+                Text::default(),
+                TextKey::SettingsTitle,
+                TextFont {
+                    font: font.clone(),
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+                LocalizedText,
+            ));
+
+            // 选项按钮
+            // 选项文本由当前的值拼接而成，不使用spawn_button
+            let option_style = ButtonStyle {
+                width: Val::Px(260.0),
+                color: Color::srgb(0.25, 0.25, 0.3),
+                ..default()
+            };
+            for option in SettingsOption::ALL {
+                panel
+                    .spawn((option_style.bundle(), SettingsOptionButton(option)))
+                    .with_children(|button| {
+                        button.spawn((
+                            Text::new(values.label(texts, option)),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 16.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            SettingsOptionText(option),
+                            LocalizedText,
+                        ));
+                    });
+            }
+
+            // 关闭按钮
+            let close_style = ButtonStyle {
+                width: Val::Px(80.0),
+                margin: UiRect::top(Val::Px(10.0)),
+                radius: 5.0,
+                font_size: 14.0,
+                ..default()
+            };
+            // 复用设置按钮组件来关闭
+            spawn_button(
+                panel,
+                &close_style,
+                &font,
+                TextKey::RulesClose,
+                SettingsButton,
+            );
+        });
+}
+
+pub fn handle_settings_options(
+    interaction_query: Query<(&Interaction, &SettingsOptionButton), Changed<Interaction>>,
+    mut fullscreen_events: EventWriter<ToggleFullscreenEvent>,
+    mut preset_events: EventWriter<CycleWindowPresetEvent>,
+    mut audio_settings: ResMut<AudioSettings>,
+    mut accessibility_settings: ResMut<AccessibilitySettings>,
+    mut preferences: ResMut<Preferences>,
+    language_settings: Res<LanguageSettings>,
+    mut language_events: EventWriter<ChangeLanguageEvent>,
+    mut motion_settings: ResMut<MotionSettings>,
+    mut ui_state: ResMut<UiState>,
+) {
+    for (interaction, option_button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match option_button.0 {
+            SettingsOption::Fullscreen => {
+                fullscreen_events.write(ToggleFullscreenEvent);
+            }
+            SettingsOption::WindowSize => {
+                preset_events.write(CycleWindowPresetEvent);
+            }
+            SettingsOption::Sound => {
+                audio_settings.enabled = !audio_settings.enabled;
+            }
+            SettingsOption::Announcements => {
+                accessibility_settings.announcements_enabled =
+                    !accessibility_settings.announcements_enabled;
+            }
+            SettingsOption::ReducedMotion => {
+                motion_settings.reduced_motion = !motion_settings.reduced_motion;
+            }
+            SettingsOption::TextSize => {
+                preferences.display.text_size = preferences.display.text_size.next();
+            }
+            SettingsOption::Language => {
+                // 运行时切换语言，界面文本由文本键自动重新解析
+                language_events.write(ChangeLanguageEvent {
+                    language: language_settings.current_language.next(),
+                });
+            }
+            SettingsOption::AssistLevel => {
+                preferences.assist.level = preferences.assist.level.next();
+            }
+            SettingsOption::AssistMode => {
+                preferences.assist.enabled = !preferences.assist.enabled;
+            }
+            SettingsOption::CornerWarning => {
+                preferences.assist.corner_warning = !preferences.assist.corner_warning;
+            }
+            SettingsOption::Coach => {
+                preferences.assist.coach = !preferences.assist.coach;
+            }
+            SettingsOption::Banter => {
+                preferences.banter.enabled = !preferences.banter.enabled;
+            }
+            SettingsOption::PerformanceOverlay => {
+                preferences.display.performance_overlay = !preferences.display.performance_overlay;
+            }
+            SettingsOption::SearchCaption => {
+                preferences.display.search_caption = !preferences.display.search_caption;
+            }
+            SettingsOption::CustomCursor => {
+                preferences.input.custom_cursor = !preferences.input.custom_cursor;
+            }
+            SettingsOption::NotationInput => {
+                preferences.input.notation_input = !preferences.input.notation_input;
+            }
+            SettingsOption::Premove => {
+                preferences.input.premove = !preferences.input.premove;
+            }
+            SettingsOption::Theme => {
+                preferences.theme = preferences.theme.next();
+            }
+            SettingsOption::Insights => {
+                preferences.insights = !preferences.insights;
+            }
+            SettingsOption::Victory => {
+                preferences.rules.victory = preferences.rules.victory.next();
+            }
+            SettingsOption::Match => {
+                preferences.rules.match_format = preferences.rules.match_format.next();
+            }
+            SettingsOption::TimeControl => {
+                preferences.rules.time_control = preferences.rules.time_control.next();
+            }
+            SettingsOption::SearchMemory => {
+                preferences.search.next_table_size();
+            }
+            SettingsOption::SoundTest => {
+                // 音效测试面板代替设置面板显示，关闭后回到设置面板
+                ui_state.show_settings = false;
+                ui_state.show_sound_test = true;
+            }
+        }
+    }
+}
+
+pub fn update_settings_option_texts(
+    mut text_query: Query<(&mut Text, &SettingsOptionText)>,
+    language_settings: Res<LanguageSettings>,
+    preferences: Res<Preferences>,
+    audio_settings: Res<AudioSettings>,
+    accessibility_settings: Res<AccessibilitySettings>,
+) {
+    let texts = language_settings.get_texts();
+    let values = SettingsValues {
+        preferences: &preferences,
+        audio: &audio_settings,
+        accessibility: &accessibility_settings,
+        language: &language_settings,
+    };
+
+    for (mut text, option_text) in text_query.iter_mut() {
+        let label = values.label(texts, option_text.0);
+        if text.0 != label {
+            text.0 = label;
+        }
+    }
+}
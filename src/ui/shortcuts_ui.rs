@@ -0,0 +1,194 @@
+// 快捷键帮助 - 按?或H键显示当前界面可用的键盘快捷键
+//
+// 列表按InputMap的绑定和当前游戏状态生成，修改绑定或切换界面后自动重建；
+// 按键名称和动作说明都通过文本键显示，切换语言后随之更新
+
+use super::{NotationInputBox, ToDelete, UiState};
+use crate::{
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    input::{key_symbol, InputAction, InputMap},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    state::GameState,
+};
+use bevy::prelude::*;
+
+/// 快捷键帮助面板
+#[derive(Component)]
+pub struct ShortcutsOverlay;
+
+/// 快捷键动作的说明文本
+pub fn shortcut_text_key(action: InputAction) -> TextKey {
+    match action {
+        InputAction::ToggleHelp => TextKey::ShortcutToggleHelp,
+        InputAction::ToggleSound => TextKey::ShortcutToggleSound,
+        InputAction::ToggleAnnouncements => TextKey::ShortcutToggleAnnouncements,
+        InputAction::ToggleFullscreen => TextKey::ShortcutToggleFullscreen,
+        InputAction::CycleWindowSize => TextKey::ShortcutCycleWindowSize,
+        InputAction::Pause => TextKey::ShortcutPause,
+        InputAction::Undo => TextKey::ShortcutUndo,
+        InputAction::Hint => TextKey::ShortcutHint,
+        InputAction::ToggleSquareLabels => TextKey::ShortcutToggleSquareLabels,
+        InputAction::ToggleDebugOverlay => TextKey::ShortcutToggleDebugOverlay,
+        InputAction::TogglePerformanceOverlay => TextKey::ShortcutTogglePerformanceOverlay,
+        InputAction::StepBack => TextKey::ShortcutStepBack,
+        InputAction::StepForward => TextKey::ShortcutStepForward,
+        InputAction::Restart => TextKey::ShortcutRestart,
+        InputAction::Analyze => TextKey::ShortcutAnalyze,
+        InputAction::ExportTranscript => TextKey::ShortcutExportTranscript,
+        InputAction::ExportReport => TextKey::ShortcutExportReport,
+        InputAction::PreviousVariation => TextKey::ShortcutPreviousVariation,
+        InputAction::NextVariation => TextKey::ShortcutNextVariation,
+        InputAction::MainLine => TextKey::ShortcutMainLine,
+        InputAction::LeaveAnalysis => TextKey::ShortcutLeaveAnalysis,
+    }
+}
+
+/// 按键名称，空格和回车需要翻译
+pub fn key_text_arg(key: KeyCode) -> TextArg {
+    match key_symbol(key) {
+        Some(symbol) => TextArg::Value(symbol),
+        None if key == KeyCode::Enter => TextArg::Key(TextKey::KeyEnter),
+        None => TextArg::Key(TextKey::KeySpace),
+    }
+}
+
+/// 一个动作绑定的所有按键，多个按键用"/"分隔
+pub fn keys_text_arg(keys: &[KeyCode]) -> TextArg {
+    keys.iter()
+        .map(|key| key_text_arg(*key))
+        .reduce(|joined, next| TextArg::Format(TextKey::ShortcutKeysFormat, vec![joined, next]))
+        .unwrap_or_else(|| TextArg::Value(String::new()))
+}
+
+/// 当前游戏状态下可用的快捷键，每行为"按键：说明"
+pub fn shortcut_rows(input_map: &InputMap, state: GameState) -> Vec<TextArgs> {
+    input_map
+        .active_bindings(state)
+        .map(|(action, keys)| {
+            TextArgs(vec![
+                keys_text_arg(keys),
+                TextArg::Key(shortcut_text_key(action)),
+            ])
+        })
+        .collect()
+}
+
+/// 按帮助键（默认?或H）显示或关闭快捷键帮助，棋谱输入框显示时只响应非字母键
+pub fn toggle_shortcuts_overlay(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    box_query: Query<(), With<NotationInputBox>>,
+    mut ui_state: ResMut<UiState>,
+) {
+    let typing = !box_query.is_empty();
+    if input_map.just_pressed_unless_typing(InputAction::ToggleHelp, &keyboard_input, typing) {
+        ui_state.show_shortcuts = !ui_state.show_shortcuts;
+    }
+}
+
+/// 根据开关显示快捷键帮助，修改绑定或切换游戏状态后重建列表
+pub fn manage_shortcuts_overlay(
+    mut commands: Commands,
+    ui_state: Res<UiState>,
+    input_map: Res<InputMap>,
+    state: Res<State<GameState>>,
+    overlay_query: Query<Entity, With<ShortcutsOverlay>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    if !ui_state.is_changed() && !input_map.is_changed() && !state.is_changed() {
+        return;
+    }
+
+    for entity in overlay_query.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+
+    if ui_state.show_shortcuts {
+        let font = get_font_for_language(&language_settings, &font_assets);
+        spawn_shortcuts_overlay(&mut commands, &font, &input_map, *state.get());
+    }
+}
+
+fn spawn_shortcuts_overlay(
+    commands: &mut Commands,
+    font: &Handle<Font>,
+    input_map: &InputMap,
+    state: GameState,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(50.0),
+                top: Val::Px(60.0),
+                width: Val::Px(360.0),
+                margin: UiRect {
+                    left: Val::Px(-180.0),
+                    ..default()
+                },
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(15.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            BorderColor(Color::srgb(0.6, 0.6, 0.6)),
+            BorderRadius::all(Val::Px(10.0)),
+            ShortcutsOverlay,
+        ))
+        .with_children(|panel| {
+            // 标题
+            panel.spawn((
+                Text::default(),
+                TextKey::ShortcutsTitle,
+                TextFont {
+                    font: font.clone(),
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(8.0)),
+                    align_self: AlignSelf::Center,
+                    ..default()
+                },
+                LocalizedText,
+            ));
+
+            for args in shortcut_rows(input_map, state) {
+                panel.spawn((
+                    Text::default(),
+                    TextKey::ShortcutRowFormat,
+                    args,
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    LocalizedText,
+                ));
+            }
+
+            // 关闭提示
+            panel.spawn((
+                Text::default(),
+                TextKey::ShortcutsCloseHint,
+                TextArgs(vec![keys_text_arg(input_map.keys(InputAction::ToggleHelp))]),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                Node {
+                    margin: UiRect::top(Val::Px(8.0)),
+                    align_self: AlignSelf::Center,
+                    ..default()
+                },
+                LocalizedText,
+            ));
+        });
+}
@@ -0,0 +1,345 @@
+// 音效测试面板 - 从设置面板打开，逐个试听音效并调整音量，检查音频设备和音效文件是否正常
+//
+// 每行显示一个音效的名称和加载状态（加载中、正常、缺失），点击播放按钮直接试听。
+// 试听不经过混音层，关闭音效时也能播放，方便确认声音是否从设备正常输出。
+// 调整音量后立即用落子音效试听新的音量。以后加入背景音乐时，在音轨列表中追加即可
+
+use super::{spawn_button, ButtonStyle, FocusScope, ToDelete, UiState};
+use crate::{
+    audio::{AudioAssets, AudioSettings, SoundType},
+    boot::BootStatus,
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    state::GameState,
+};
+use bevy::{audio::Volume, prelude::*};
+
+/// 每次调整音量的幅度
+pub const VOLUME_STEP: f32 = 0.1;
+
+/// 音量调整后用于试听的音效
+const VOLUME_PREVIEW_SOUND: SoundType = SoundType::PiecePlace;
+
+#[derive(Component)]
+pub struct SoundTestPanel;
+
+/// 试听某个音效的按钮
+#[derive(Component)]
+pub struct SoundTestPlayButton(pub SoundType);
+
+/// 音效名称和加载状态的文本
+#[derive(Component)]
+pub struct SoundTestStatusText(pub SoundType);
+
+/// 调整音量的按钮，值为调整的档数（正数调高）
+#[derive(Component)]
+pub struct SoundTestVolumeButton(pub i32);
+
+#[derive(Component)]
+pub struct SoundTestVolumeText;
+
+/// 关闭音效测试，回到设置面板
+#[derive(Component)]
+pub struct SoundTestCloseButton;
+
+/// 正在试听的音效，播放结束后自动删除
+#[derive(Component)]
+pub struct SoundTestPreview;
+
+/// 音效的本地化名称
+pub fn sound_name_key(sound_type: SoundType) -> TextKey {
+    match sound_type {
+        SoundType::PiecePlace => TextKey::SoundPiecePlace,
+        SoundType::PieceFlip => TextKey::SoundPieceFlip,
+        SoundType::Victory => TextKey::SoundVictory,
+        SoundType::Defeat => TextKey::SoundDefeat,
+        SoundType::InvalidMove => TextKey::SoundInvalidMove,
+        SoundType::Pass => TextKey::SoundPass,
+        SoundType::LowTime => TextKey::SoundLowTime,
+        SoundType::CornerCapture => TextKey::SoundCornerCapture,
+        SoundType::EdgeComplete => TextKey::SoundEdgeComplete,
+        SoundType::WipeoutThreat => TextKey::SoundWipeoutThreat,
+    }
+}
+
+/// 按档调整音量，结果对齐到整档并限制在0到1之间
+pub fn step_volume(volume: f32, steps: i32) -> f32 {
+    let max_steps = (1.0 / VOLUME_STEP).round() as i32;
+    let current = (volume / VOLUME_STEP).round() as i32;
+    (current + steps).clamp(0, max_steps) as f32 * VOLUME_STEP
+}
+
+/// 音量的百分比
+pub fn volume_percent(volume: f32) -> u64 {
+    (volume.clamp(0.0, 1.0) * 100.0).round() as u64
+}
+
+/// 音效行的文本参数：音效名称和加载状态
+fn status_text_args(sound_type: SoundType, status: BootStatus) -> TextArgs {
+    TextArgs(vec![
+        TextArg::Key(sound_name_key(sound_type)),
+        TextArg::Key(status.text_key()),
+    ])
+}
+
+fn volume_text_args(audio_settings: &AudioSettings) -> TextArgs {
+    TextArgs(vec![TextArg::Number(volume_percent(audio_settings.volume))])
+}
+
+/// 音效的加载状态，音效资源还没有创建时视为加载中
+fn sound_status(
+    audio_assets: Option<&AudioAssets>,
+    asset_server: &AssetServer,
+    sound_type: SoundType,
+) -> BootStatus {
+    audio_assets.map_or(BootStatus::Loading, |audio_assets| {
+        BootStatus::from_load_state(&asset_server.load_state(audio_assets.handle(sound_type)))
+    })
+}
+
+pub fn manage_sound_test_panel(
+    mut commands: Commands,
+    ui_state: Res<UiState>,
+    panel_query: Query<Entity, With<SoundTestPanel>>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+    audio_settings: Res<AudioSettings>,
+    audio_assets: Option<Res<AudioAssets>>,
+    asset_server: Res<AssetServer>,
+) {
+    if !ui_state.is_changed() {
+        return;
+    }
+
+    let panel_open = !panel_query.is_empty();
+    if ui_state.show_sound_test == panel_open {
+        return;
+    }
+
+    for entity in panel_query.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+    if ui_state.show_sound_test {
+        let font = get_font_for_language(&language_settings, &font_assets);
+        let status = |sound_type| sound_status(audio_assets.as_deref(), &asset_server, sound_type);
+        spawn_sound_test_panel(&mut commands, font, &audio_settings, status);
+    }
+}
+
+fn spawn_sound_test_panel(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    audio_settings: &AudioSettings,
+    status: impl Fn(SoundType) -> BootStatus,
+) {
+    let play_style = ButtonStyle {
+        width: Val::Px(80.0),
+        color: Color::srgb(0.25, 0.25, 0.3),
+        font_size: 14.0,
+        ..default()
+    };
+    let volume_style = ButtonStyle {
+        width: Val::Px(44.0),
+        font_size: 20.0,
+        ..play_style.clone()
+    };
+    let close_style = ButtonStyle {
+        width: Val::Px(80.0),
+        radius: 5.0,
+        margin: UiRect::top(Val::Px(10.0)),
+        font_size: 14.0,
+        ..default()
+    };
+    let text_font = |font_size: f32| TextFont {
+        font: font.clone(),
+        font_size,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                padding: UiRect::all(Val::Px(15.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            BorderColor(Color::srgb(0.6, 0.6, 0.6)),
+            BorderRadius::all(Val::Px(10.0)),
+            SoundTestPanel,
+            FocusScope,
+            StateScoped(GameState::DifficultySelection),
+        ))
+        .with_children(|panel| {
+            // 标题
+            panel.spawn((
+                Text::default(),
+                TextKey::SoundTestTitle,
+                text_font(20.0),
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+                LocalizedText,
+            ));
+
+            // 每个音效一行：名称和加载状态，右侧为播放按钮
+            for sound_type in SoundType::ALL {
+                panel
+                    .spawn(Node {
+                        width: Val::Px(300.0),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceBetween,
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn((
+                            Text::default(),
+                            TextKey::SoundTestRowFormat,
+                            status_text_args(sound_type, status(sound_type)),
+                            text_font(15.0),
+                            TextColor(Color::WHITE),
+                            LocalizedText,
+                            SoundTestStatusText(sound_type),
+                        ));
+                        spawn_button(
+                            row,
+                            &play_style,
+                            &font,
+                            TextKey::SoundTestPlay,
+                            SoundTestPlayButton(sound_type),
+                        );
+                    });
+            }
+
+            // 音量调整
+            panel
+                .spawn(Node {
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(12.0),
+                    margin: UiRect::top(Val::Px(10.0)),
+                    ..default()
+                })
+                .with_children(|row| {
+                    for steps in [Some(-1), None, Some(1)] {
+                        let Some(steps) = steps else {
+                            row.spawn((
+                                Text::default(),
+                                TextKey::SoundTestVolumeFormat,
+                                volume_text_args(audio_settings),
+                                text_font(16.0),
+                                TextColor(Color::WHITE),
+                                LocalizedText,
+                                SoundTestVolumeText,
+                            ));
+                            continue;
+                        };
+                        // 加减号不随语言变化，不使用本地化文本
+                        row.spawn((volume_style.bundle(), SoundTestVolumeButton(steps)))
+                            .with_children(|button| {
+                                button.spawn((
+                                    Text::new(if steps < 0 { "-" } else { "+" }),
+                                    text_font(volume_style.font_size),
+                                    TextColor(volume_style.text_color),
+                                ));
+                            });
+                    }
+                });
+
+            // 关闭按钮，回到设置面板
+            spawn_button(
+                panel,
+                &close_style,
+                &font,
+                TextKey::RulesClose,
+                SoundTestCloseButton,
+            );
+        });
+}
+
+/// 直接播放一个音效用于试听，音效还没有加载完成或加载失败时不播放
+pub fn play_sound_preview(
+    commands: &mut Commands,
+    audio_assets: &AudioAssets,
+    audio_sources: &Assets<AudioSource>,
+    sound_type: SoundType,
+    volume: f32,
+) {
+    let handle = audio_assets.handle(sound_type);
+    if !audio_sources.contains(handle) {
+        return;
+    }
+    commands.spawn((
+        AudioPlayer::new(handle.clone()),
+        PlaybackSettings::DESPAWN
+            .with_volume(Volume::Linear(volume))
+            .with_speed(sound_type.playback_speed()),
+        SoundTestPreview,
+    ));
+}
+
+pub fn handle_sound_test_buttons(
+    mut commands: Commands,
+    play_query: Query<(&Interaction, &SoundTestPlayButton), Changed<Interaction>>,
+    volume_query: Query<(&Interaction, &SoundTestVolumeButton), Changed<Interaction>>,
+    close_query: Query<&Interaction, (Changed<Interaction>, With<SoundTestCloseButton>)>,
+    mut audio_settings: ResMut<AudioSettings>,
+    audio_assets: Option<Res<AudioAssets>>,
+    audio_sources: Res<Assets<AudioSource>>,
+    mut ui_state: ResMut<UiState>,
+) {
+    let mut preview = None;
+    for (interaction, SoundTestPlayButton(sound_type)) in play_query.iter() {
+        if *interaction == Interaction::Pressed {
+            preview = Some(*sound_type);
+        }
+    }
+    for (interaction, SoundTestVolumeButton(steps)) in volume_query.iter() {
+        if *interaction == Interaction::Pressed {
+            audio_settings.volume = step_volume(audio_settings.volume, *steps);
+            preview = Some(VOLUME_PREVIEW_SOUND);
+        }
+    }
+    if let (Some(sound_type), Some(audio_assets)) = (preview, audio_assets) {
+        play_sound_preview(
+            &mut commands,
+            &audio_assets,
+            &audio_sources,
+            sound_type,
+            audio_settings.volume,
+        );
+    }
+
+    if close_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        ui_state.show_sound_test = false;
+        ui_state.show_settings = true;
+    }
+}
+
+/// 更新音效的加载状态和音量文本
+pub fn update_sound_test_texts(
+    mut status_query: Query<(&mut TextArgs, &SoundTestStatusText), Without<SoundTestVolumeText>>,
+    mut volume_query: Query<&mut TextArgs, With<SoundTestVolumeText>>,
+    audio_settings: Res<AudioSettings>,
+    audio_assets: Option<Res<AudioAssets>>,
+    asset_server: Res<AssetServer>,
+) {
+    for (mut args, SoundTestStatusText(sound_type)) in status_query.iter_mut() {
+        let status = sound_status(audio_assets.as_deref(), &asset_server, *sound_type);
+        args.set_if_neq(status_text_args(*sound_type, status));
+    }
+    for mut args in volume_query.iter_mut() {
+        args.set_if_neq(volume_text_args(&audio_settings));
+    }
+}
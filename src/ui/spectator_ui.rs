@@ -0,0 +1,139 @@
+// 观战界面 - 在普通棋盘上只读显示联机对局的当前局面
+//
+// 观战状态进入时创建棋盘（与回放相同）和顶部的状态面板，
+// 每次收到服务器消息后把棋盘同步为命令日志的当前局面；
+// 观战者不能走棋，不显示可落子提示，Esc退出观战回到菜单
+
+use super::{
+    spawn_label, spawn_panel, sync_pieces, BoardColors, BoardUI, LabelStyle, PanelStyle, Piece,
+    ToDelete,
+};
+use crate::{
+    fonts::{get_font_for_language, FontAssets},
+    game::PlayerColor,
+    input::{InputAction, InputMap},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+    net::SpectatorView,
+    state::{GameState, GameTransition, GameTransitions},
+};
+use bevy::prelude::*;
+
+/// 观战状态文本（步数和轮到的一方），握手完成前显示正在加入
+#[derive(Component)]
+pub struct SpectatorStatusText;
+
+/// 状态文本的键和参数，步数为已走的命令数
+pub fn spectator_status_text(view: &SpectatorView) -> (TextKey, TextArgs) {
+    let Some(log) = view.log() else {
+        return (TextKey::SpectatorConnecting, TextArgs::default());
+    };
+    let to_move = match log.to_move() {
+        PlayerColor::Black => TextKey::BlackName,
+        PlayerColor::White => TextKey::WhiteName,
+    };
+    (
+        TextKey::SpectatorStatus,
+        TextArgs(vec![
+            TextArg::Number(log.next_sequence().into()),
+            TextArg::Key(to_move),
+        ]),
+    )
+}
+
+/// 创建观战状态面板，离开观战时自动清理
+pub fn spawn_spectator_panel(
+    mut commands: Commands,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    let font = get_font_for_language(&language_settings, &font_assets);
+    let root = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                right: Val::Px(0.0),
+                top: Val::Px(60.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            StateScoped(GameState::Spectating),
+        ))
+        .id();
+    let panel_style = PanelStyle {
+        background: Color::srgba(0.0, 0.0, 0.0, 0.7),
+        radius: 6.0,
+        padding: 8.0,
+        row_gap: 4.0,
+        ..default()
+    };
+    let panel = spawn_panel(&mut commands, &panel_style, ChildOf(root));
+    commands.entity(panel).with_children(|panel| {
+        spawn_label(
+            panel,
+            &LabelStyle::default(),
+            &font,
+            TextKey::SpectatorConnecting,
+            (TextArgs::default(), SpectatorStatusText),
+        );
+        let controls_style = LabelStyle {
+            font_size: 12.0,
+            color: Color::srgb(0.7, 0.7, 0.7),
+            ..default()
+        };
+        spawn_label(
+            panel,
+            &controls_style,
+            &font,
+            TextKey::SpectatorControls,
+            (),
+        );
+    });
+}
+
+/// 收到服务器消息后把棋盘同步为当前局面，并更新状态文本
+pub fn render_spectator_board(
+    mut commands: Commands,
+    view: Res<SpectatorView>,
+    piece_query: Query<(Entity, &Piece, &ChildOf), Without<ToDelete>>,
+    board_root: Query<Entity, With<BoardUI>>,
+    mut status_query: Query<(&mut TextKey, &mut TextArgs), With<SpectatorStatusText>>,
+    colors: Res<BoardColors>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !view.is_changed() {
+        return;
+    }
+
+    let (key, args) = spectator_status_text(&view);
+    for (mut status_key, mut status_args) in status_query.iter_mut() {
+        status_key.set_if_neq(key);
+        status_args.set_if_neq(args.clone());
+    }
+
+    let (Some(log), Ok(root)) = (view.log(), board_root.single()) else {
+        return;
+    };
+    sync_pieces(
+        &mut commands,
+        root,
+        &piece_query,
+        &log.board(),
+        &colors,
+        &mut meshes,
+        &mut materials,
+    );
+}
+
+/// 按Esc（默认）退出观战
+pub fn leave_spectating_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut transitions: GameTransitions,
+) {
+    if input_map.just_pressed(InputAction::LeaveAnalysis, &keyboard_input) {
+        transitions.request(GameTransition::LeaveAnalysis);
+    }
+}
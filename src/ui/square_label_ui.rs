@@ -0,0 +1,83 @@
+// 格子编号调试层 - F3开关，在每个格子左上角显示序号和棋谱记法
+//
+// 用于核对屏幕坐标到棋盘格的映射：点击某格后落子的位置应与该格显示的序号一致
+
+use super::{BoardUI, ToDelete, UiState, SQUARE_SIZE};
+use crate::{
+    game::Board,
+    input::{InputAction, InputMap},
+};
+use bevy::{prelude::*, sprite::Anchor};
+
+/// 格子编号的Z坐标 - 位于棋子之上、点击波纹之下
+const LABEL_Z: f32 = 3.0;
+
+/// 格子编号距格子边缘的距离
+const LABEL_INSET: f32 = 2.0;
+
+/// 格子编号的颜色
+const LABEL_COLOR: Color = Color::srgba(1.0, 0.9, 0.3, 0.85);
+
+#[derive(Component)]
+pub struct SquareLabel {
+    pub position: u8,
+}
+
+/// 格子编号文本：序号和棋谱记法，例如"37 F5"
+pub fn square_label_text(position: u8) -> String {
+    format!("{position} {}", Board::position_to_notation(position))
+}
+
+/// 默认F3切换格子编号显示
+pub fn toggle_square_labels(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut ui_state: ResMut<UiState>,
+) {
+    if input_map.just_pressed(InputAction::ToggleSquareLabels, &keyboard_input) {
+        ui_state.show_square_labels = !ui_state.show_square_labels;
+    }
+}
+
+/// 根据开关显示或移除格子编号，棋盘重建后自动重新生成
+pub fn manage_square_labels(
+    mut commands: Commands,
+    ui_state: Res<UiState>,
+    label_query: Query<Entity, With<SquareLabel>>,
+    board_root: Query<Entity, With<BoardUI>>,
+) {
+    if !ui_state.show_square_labels {
+        for entity in label_query.iter() {
+            commands.entity(entity).insert(ToDelete);
+        }
+        return;
+    }
+
+    if !label_query.is_empty() {
+        return;
+    }
+    // 重新开始时棋盘根实体重建之前不生成
+    let Ok(root) = board_root.single() else {
+        return;
+    };
+
+    for position in 0..64u8 {
+        let row = (position / 8) as f32;
+        let col = (position % 8) as f32;
+        let x = (col - 4.0) * SQUARE_SIZE + LABEL_INSET;
+        let y = (4.0 - row) * SQUARE_SIZE - LABEL_INSET;
+
+        commands.spawn((
+            Text2d::new(square_label_text(position)),
+            TextFont {
+                font_size: 8.0,
+                ..default()
+            },
+            TextColor(LABEL_COLOR),
+            Anchor::TopLeft,
+            Transform::from_xyz(x, y, LABEL_Z),
+            SquareLabel { position },
+            ChildOf(root),
+        ));
+    }
+}
@@ -0,0 +1,237 @@
+// 通知提示 - 跳过回合、联机状态、自动保存和出错信息等短暂提示的队列和显示
+//
+// 各模块发送ToastEvent，提示先进入队列，屏幕顶部同时最多显示MAX_VISIBLE_TOASTS条，
+// 新提示排在下面，到时后自动消失，排队的提示依次补上。与正在显示或排队中的提示相同的提示不重复加入。
+// 提示文本以文本键和参数表示，切换语言时跟随更新
+
+use super::ToDelete;
+use crate::{
+    fonts::{get_font_for_language, FontAssets, LocalizedText},
+    localization::{LanguageSettings, TextArg, TextArgs, TextKey},
+};
+use bevy::prelude::*;
+use std::{collections::VecDeque, time::Duration};
+
+/// 同时显示的提示数量
+pub const MAX_VISIBLE_TOASTS: usize = 3;
+
+/// 排队等待显示的提示数量，超出时丢弃最早的
+pub const MAX_PENDING_TOASTS: usize = 8;
+
+/// 提示的类别，决定颜色和显示时长
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    pub fn color(self) -> Color {
+        match self {
+            Self::Info => Color::srgb(0.9, 0.9, 0.9),
+            Self::Success => Color::srgb(0.55, 0.9, 0.55),
+            Self::Warning => Color::srgb(1.0, 0.85, 0.35),
+            Self::Error => Color::srgb(1.0, 0.45, 0.4),
+        }
+    }
+
+    /// 显示时长，警告和错误停留得更久
+    pub fn duration(self) -> Duration {
+        match self {
+            Self::Info | Self::Success => Duration::from_secs(3),
+            Self::Warning => Duration::from_secs(5),
+            Self::Error => Duration::from_secs(6),
+        }
+    }
+}
+
+/// 请求显示一条提示
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ToastEvent {
+    pub kind: ToastKind,
+    pub key: TextKey,
+    pub args: TextArgs,
+}
+
+impl ToastEvent {
+    pub fn new(kind: ToastKind, key: TextKey) -> Self {
+        Self {
+            kind,
+            key,
+            args: TextArgs::default(),
+        }
+    }
+
+    pub fn info(key: TextKey) -> Self {
+        Self::new(ToastKind::Info, key)
+    }
+
+    pub fn success(key: TextKey) -> Self {
+        Self::new(ToastKind::Success, key)
+    }
+
+    pub fn warning(key: TextKey) -> Self {
+        Self::new(ToastKind::Warning, key)
+    }
+
+    pub fn error(key: TextKey) -> Self {
+        Self::new(ToastKind::Error, key)
+    }
+
+    /// 保存失败的错误提示，`data`为数据名称（见save_data_name_key）
+    pub fn save_failed(data: TextKey) -> Self {
+        Self::error(TextKey::ToastSaveFailedFormat).with_args(vec![TextArg::Key(data)])
+    }
+
+    /// 设置文本键的格式参数
+    pub fn with_args(mut self, args: Vec<TextArg>) -> Self {
+        self.args = TextArgs(args);
+        self
+    }
+}
+
+/// 正在显示的提示和剩余时间
+#[derive(Debug, Clone)]
+struct ActiveToast {
+    toast: ToastEvent,
+    timer: Timer,
+}
+
+/// 提示队列：正在显示的提示和排队中的提示
+#[derive(Resource, Debug, Default)]
+pub struct ToastQueue {
+    visible: Vec<ActiveToast>,
+    pending: VecDeque<ToastEvent>,
+}
+
+impl ToastQueue {
+    /// 加入一条提示，有空位时立即显示；与正在显示或排队中的提示相同时忽略，返回是否加入
+    pub fn push(&mut self, toast: ToastEvent) -> bool {
+        let duplicate = self.visible.iter().any(|active| active.toast == toast)
+            || self.pending.contains(&toast);
+        if duplicate {
+            return false;
+        }
+        if self.pending.len() == MAX_PENDING_TOASTS {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(toast);
+        self.fill();
+        true
+    }
+
+    /// 推进显示时间，移除到时的提示并补上排队的提示，返回显示的提示是否变化
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        let before = self.visible.len();
+        for active in &mut self.visible {
+            active.timer.tick(delta);
+        }
+        self.visible.retain(|active| !active.timer.finished());
+        let expired = self.visible.len() != before;
+        self.fill() || expired
+    }
+
+    /// 从上到下正在显示的提示
+    pub fn visible(&self) -> impl Iterator<Item = &ToastEvent> {
+        self.visible.iter().map(|active| &active.toast)
+    }
+
+    /// 排队中的提示数量
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// 把排队的提示移到空位上，返回是否有提示开始显示
+    fn fill(&mut self) -> bool {
+        let mut filled = false;
+        while self.visible.len() < MAX_VISIBLE_TOASTS {
+            let Some(toast) = self.pending.pop_front() else {
+                break;
+            };
+            self.visible.push(ActiveToast {
+                timer: Timer::new(toast.kind.duration(), TimerMode::Once),
+                toast,
+            });
+            filled = true;
+        }
+        filled
+    }
+}
+
+/// 屏幕顶部的提示栏，显示的提示变化时整体重建
+#[derive(Component)]
+pub struct ToastStack;
+
+/// 收到的提示加入队列
+pub fn queue_toasts(mut toast_events: EventReader<ToastEvent>, mut queue: ResMut<ToastQueue>) {
+    for toast in toast_events.read() {
+        queue.push(toast.clone());
+    }
+}
+
+/// 推进提示的显示时间，只在显示的提示变化时标记队列已修改
+pub fn tick_toasts(time: Res<Time>, mut queue: ResMut<ToastQueue>) {
+    if queue.bypass_change_detection().tick(time.delta()) {
+        queue.set_changed();
+    }
+}
+
+/// 按队列重建提示栏
+pub fn update_toast_stack(
+    mut commands: Commands,
+    queue: Res<ToastQueue>,
+    stack_query: Query<Entity, (With<ToastStack>, Without<ToDelete>)>,
+    language_settings: Res<LanguageSettings>,
+    font_assets: Res<FontAssets>,
+) {
+    if !queue.is_changed() {
+        return;
+    }
+    for entity in stack_query.iter() {
+        commands.entity(entity).insert(ToDelete);
+    }
+    if queue.visible().next().is_none() {
+        return;
+    }
+
+    let font = get_font_for_language(&language_settings, &font_assets);
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(8.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            Pickable::IGNORE,
+            GlobalZIndex(100),
+            ToastStack,
+        ))
+        .with_children(|stack| {
+            for toast in queue.visible() {
+                stack.spawn((
+                    Text::default(),
+                    toast.key,
+                    toast.args.clone(),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(toast.kind.color()),
+                    Node {
+                        padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.85)),
+                    BorderRadius::all(Val::Px(6.0)),
+                    LocalizedText,
+                ));
+            }
+        });
+}
@@ -0,0 +1,178 @@
+// 回合切换动画 - 轮到的一方头像发出柔和的脉动光晕，回合提示文本在换手时滑入
+//
+// 只在命令日志的回合状态变化时更新提示文本并重新开始滑入动画，不再每帧重设文本。
+// 减少动效时文本直接出现，光晕保持常亮不脉动
+
+use super::{PlayerAvatar, TurnIndicator};
+use crate::{
+    accessibility::MotionSettings,
+    ai::AiPlayer,
+    game::{GameLog, PlayerColor},
+    input::TurnState,
+    localization::TextKey,
+    sandbox::SandboxMode,
+};
+use bevy::prelude::*;
+
+/// 回合提示文本滑入的时长（秒）
+pub const TURN_SLIDE_DURATION_SECS: f32 = 0.35;
+
+/// 回合提示文本滑入的起始水平偏移
+const TURN_SLIDE_DISTANCE: f32 = 40.0;
+
+/// 头像光晕脉动一次的周期（秒）
+const GLOW_PERIOD_SECS: f32 = 1.6;
+
+/// 光晕的颜色，透明度由脉动决定
+const GLOW_COLOR: Color = Color::srgb(1.0, 0.85, 0.35);
+
+/// 光晕透明度的范围
+const GLOW_ALPHA_MIN: f32 = 0.35;
+const GLOW_ALPHA_MAX: f32 = 0.8;
+
+/// 光晕的扩散和模糊半径
+const GLOW_SPREAD: f32 = 3.0;
+const GLOW_BLUR: f32 = 12.0;
+
+/// 回合提示文本的换手动画，记录上次看到的回合状态，回合状态变化时重新播放
+#[derive(Component, Debug, Default)]
+pub struct TurnChangeAnimation {
+    /// 上次看到的回合状态，还没看到过时为None
+    pub turn: Option<TurnState>,
+    /// 本次滑入已经播放的时间（秒）
+    pub elapsed: f32,
+}
+
+impl TurnChangeAnimation {
+    /// 滑入进度，0为刚开始，1为播放完毕
+    pub fn progress(&self) -> f32 {
+        (self.elapsed / TURN_SLIDE_DURATION_SECS).clamp(0.0, 1.0)
+    }
+
+    /// 滑入是否播放完毕
+    pub fn is_finished(&self) -> bool {
+        self.progress() >= 1.0
+    }
+}
+
+/// 回合提示文本：普通对局中区分玩家和AI（玩家执子颜色为player_color），沙盒中显示轮到的颜色
+pub fn turn_text_key(to_move: PlayerColor, player_color: PlayerColor, sandbox: bool) -> TextKey {
+    match (to_move, sandbox) {
+        (PlayerColor::Black, true) => TextKey::BlackName,
+        (PlayerColor::White, true) => TextKey::WhiteName,
+        (color, false) if color == player_color => TextKey::YourTurn,
+        (_, false) => TextKey::AiTurn,
+    }
+}
+
+/// 缓出曲线，滑入开始快、结束慢
+fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// 滑入进度对应的水平偏移和透明度
+pub fn turn_slide_pose(progress: f32) -> (f32, f32) {
+    let eased = ease_out_cubic(progress.clamp(0.0, 1.0));
+    (TURN_SLIDE_DISTANCE * (1.0 - eased), eased)
+}
+
+/// 光晕在某一时刻的透明度，减少动效时保持最亮
+pub fn glow_alpha(seconds: f32, reduced_motion: bool) -> f32 {
+    if reduced_motion {
+        return GLOW_ALPHA_MAX;
+    }
+    let wave = (seconds * std::f32::consts::TAU / GLOW_PERIOD_SECS).sin() * 0.5 + 0.5;
+    GLOW_ALPHA_MIN + (GLOW_ALPHA_MAX - GLOW_ALPHA_MIN) * wave
+}
+
+/// 头像光晕，初始不可见
+pub fn avatar_glow() -> BoxShadow {
+    BoxShadow::new(
+        GLOW_COLOR.with_alpha(0.0),
+        Val::Px(0.0),
+        Val::Px(0.0),
+        Val::Px(GLOW_SPREAD),
+        Val::Px(GLOW_BLUR),
+    )
+}
+
+/// 回合状态变化时更新提示文本并重新开始滑入
+pub fn start_turn_change_animation(
+    log_query: Query<&GameLog>,
+    ai_query: Query<&AiPlayer>,
+    sandbox: Res<SandboxMode>,
+    mut indicator_query: Query<(&mut TurnChangeAnimation, &mut TextKey), With<TurnIndicator>>,
+) {
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+    let turn = TurnState::of(log);
+    // 玩家执子颜色跟随AI，没有AI时按执黑处理
+    let player_color = ai_query
+        .single()
+        .map_or(PlayerColor::Black, |ai| ai.color.opposite());
+
+    for (mut animation, mut key) in indicator_query.iter_mut() {
+        if animation.turn == Some(turn) {
+            continue;
+        }
+        // 换手时才滑入，同一方连续走棋（对方跳过）只更新记录的回合状态
+        let switched = animation
+            .turn
+            .is_none_or(|last| last.to_move != turn.to_move);
+        animation.turn = Some(turn);
+        if switched {
+            animation.elapsed = 0.0;
+            key.set_if_neq(turn_text_key(turn.to_move, player_color, sandbox.0));
+        }
+    }
+}
+
+/// 播放回合提示文本的滑入，播放完毕后不再修改节点
+pub fn animate_turn_change(
+    time: Res<Time>,
+    motion_settings: Res<MotionSettings>,
+    mut indicator_query: Query<(&mut TurnChangeAnimation, &mut Node, &mut TextColor)>,
+) {
+    for (mut animation, mut node, mut color) in indicator_query.iter_mut() {
+        if animation.is_finished() {
+            continue;
+        }
+        animation.elapsed = if motion_settings.reduced_motion {
+            TURN_SLIDE_DURATION_SECS
+        } else {
+            animation.elapsed + time.delta_secs()
+        };
+
+        let (offset, alpha) = turn_slide_pose(animation.progress());
+        node.left = Val::Px(offset);
+        color.0 = color.0.with_alpha(alpha);
+    }
+}
+
+/// 轮到的一方头像光晕脉动，另一方和终局后不发光
+pub fn pulse_active_avatar(
+    time: Res<Time>,
+    motion_settings: Res<MotionSettings>,
+    log_query: Query<&GameLog>,
+    mut avatar_query: Query<(&PlayerAvatar, &mut BoxShadow)>,
+) {
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+    let active = (!log.is_game_over()).then(|| log.to_move());
+    let alpha = glow_alpha(time.elapsed_secs(), motion_settings.reduced_motion);
+
+    for (avatar, mut shadow) in avatar_query.iter_mut() {
+        let target = if active == Some(avatar.player_color) {
+            alpha
+        } else {
+            0.0
+        };
+        for style in shadow.0.iter_mut() {
+            if style.color.alpha() != target {
+                style.color.set_alpha(target);
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// 悔棋按钮 - 位于返回按钮下方，显示剩余次数，不能悔棋时变暗并在悬停时提示原因
+
+use super::{spawn_label, ButtonColors, ButtonStyle, NotationInputBox};
+use crate::{
+    ai::AiPlayer,
+    fonts::LocalizedText,
+    game::GameLog,
+    input::{InputAction, InputMap},
+    localization::{TextArg, TextArgs, TextKey},
+    practice::OpeningAutoplay,
+    undo::{undo_availability, UndoBlock, UndoRequestEvent, UndoState},
+};
+use bevy::prelude::*;
+
+/// 可以悔棋时的按钮颜色
+const UNDO_ENABLED_COLORS: ButtonColors = ButtonColors {
+    normal: Color::srgba(0.2, 0.2, 0.2, 0.8),
+    hovered: Color::srgba(0.3, 0.3, 0.3, 0.9),
+    pressed: Color::srgba(0.1, 0.1, 0.1, 0.9),
+};
+
+/// 不能悔棋时的按钮颜色，悬停时不变亮
+const UNDO_DISABLED_COLORS: ButtonColors = ButtonColors {
+    normal: Color::srgba(0.15, 0.15, 0.15, 0.5),
+    hovered: Color::srgba(0.15, 0.15, 0.15, 0.5),
+    pressed: Color::srgba(0.15, 0.15, 0.15, 0.5),
+};
+
+#[derive(Component)]
+pub struct UndoButton;
+
+#[derive(Component)]
+pub struct UndoButtonText;
+
+/// 悬停在不可用的悔棋按钮上时显示的原因
+#[derive(Component)]
+pub struct UndoTooltip;
+
+impl UndoBlock {
+    /// 提示中显示的原因
+    pub fn text_key(self) -> TextKey {
+        match self {
+            Self::Disabled => TextKey::UndoDisabledRated,
+            Self::LimitReached => TextKey::UndoLimitReached,
+            Self::NothingToUndo => TextKey::UndoNothing,
+            Self::NotYourTurn => TextKey::UndoWaitTurn,
+        }
+    }
+}
+
+/// 按钮文本的参数：有次数限制时显示剩余次数
+fn undo_label(undo: &UndoState) -> (TextKey, TextArgs) {
+    match undo.remaining() {
+        Some(remaining) => (
+            TextKey::UndoRemainingFormat,
+            TextArgs(vec![TextArg::Number(remaining.into())]),
+        ),
+        None => (TextKey::UndoButton, TextArgs::default()),
+    }
+}
+
+/// 在返回按钮下方创建悔棋按钮
+pub fn spawn_undo_button(parent: &mut ChildSpawnerCommands, font: &Handle<Font>) {
+    let style = ButtonStyle {
+        colors: Some(UNDO_ENABLED_COLORS),
+        ..ButtonStyle::corner(60.0)
+    };
+    parent
+        .spawn((style.bundle(), UndoButton))
+        .with_children(|button| {
+            spawn_label(
+                button,
+                &style.label_style(),
+                font,
+                TextKey::UndoButton,
+                (TextArgs::default(), UndoButtonText),
+            );
+
+            // 提示显示在按钮右侧
+            button
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Percent(100.0),
+                        margin: UiRect::left(Val::Px(6.0)),
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+                    BorderRadius::all(Val::Px(4.0)),
+                    Visibility::Hidden,
+                    Pickable::IGNORE,
+                    UndoTooltip,
+                ))
+                .with_children(|tooltip| {
+                    tooltip.spawn((
+                        Text::default(),
+                        TextKey::UndoNothing,
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                        LocalizedText,
+                    ));
+                });
+        });
+}
+
+/// 点击悔棋按钮或按悔棋键（默认U）时请求悔棋，是否执行由悔棋规则决定
+///
+/// 棋谱输入框显示时字母键用于输入棋谱，不触发悔棋
+pub fn handle_undo_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<UndoButton>)>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    box_query: Query<(), With<NotationInputBox>>,
+    mut undo_events: EventWriter<UndoRequestEvent>,
+) {
+    let clicked = interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+    let typing = !box_query.is_empty();
+    if clicked || input_map.just_pressed_unless_typing(InputAction::Undo, &keyboard_input, typing) {
+        undo_events.write(UndoRequestEvent);
+    }
+}
+
+/// 按当前能否悔棋更新按钮的文本、颜色和提示
+pub fn update_undo_button(
+    mut button_query: Query<
+        (
+            &Interaction,
+            &mut ButtonColors,
+            &mut BackgroundColor,
+            &Children,
+        ),
+        With<UndoButton>,
+    >,
+    mut label_query: Query<(&mut TextKey, &mut TextArgs, &mut TextColor), With<UndoButtonText>>,
+    mut tooltip_query: Query<(&mut Visibility, &Children), With<UndoTooltip>>,
+    mut tooltip_text_query: Query<&mut TextKey, Without<UndoButtonText>>,
+    undo: Res<UndoState>,
+    log_query: Query<&GameLog>,
+    ai_query: Query<&AiPlayer>,
+    autoplay: Res<OpeningAutoplay>,
+) {
+    let Ok(log) = log_query.single() else {
+        return;
+    };
+    let availability = undo_availability(&undo, log, ai_query.single().ok(), &autoplay);
+    let (label_key, label_args) = undo_label(&undo);
+
+    for (interaction, mut colors, mut background, children) in button_query.iter_mut() {
+        let target = if availability.is_ok() {
+            UNDO_ENABLED_COLORS
+        } else {
+            UNDO_DISABLED_COLORS
+        };
+        if colors.normal != target.normal {
+            background.0 = match interaction {
+                Interaction::None => target.normal,
+                Interaction::Hovered => target.hovered,
+                Interaction::Pressed => target.pressed,
+            };
+            *colors = target;
+        }
+
+        for child in children.iter() {
+            if let Ok((mut key, mut args, mut color)) = label_query.get_mut(child) {
+                key.set_if_neq(label_key);
+                args.set_if_neq(label_args.clone());
+                let text_color = if availability.is_ok() {
+                    Color::WHITE
+                } else {
+                    Color::srgb(0.55, 0.55, 0.55)
+                };
+                color.set_if_neq(TextColor(text_color));
+            }
+
+            let Ok((mut visibility, tooltip_children)) = tooltip_query.get_mut(child) else {
+                continue;
+            };
+            // 只在悬停于不可用的按钮上时显示原因
+            let reason = availability
+                .err()
+                .filter(|_| *interaction != Interaction::None);
+            visibility.set_if_neq(if reason.is_some() {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+            if let Some(reason) = reason {
+                for tooltip_child in tooltip_children.iter() {
+                    if let Ok(mut key) = tooltip_text_query.get_mut(tooltip_child) {
+                        key.set_if_neq(reason.text_key());
+                    }
+                }
+            }
+        }
+    }
+}
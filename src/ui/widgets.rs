@@ -0,0 +1,240 @@
+// 界面部件 - 按钮、面板和文本标签的统一生成函数
+//
+// 按钮由Button、居中内容的Node、背景和边框、圆角、悬停和按下颜色（ButtonColors）以及一个本地化文本子节点组成；
+// 面板是纵向排列、带内边距和圆角的半透明背景；标签是跟随语言切换的文本。
+// 外观由ButtonStyle、PanelStyle和LabelStyle描述（按钮可以绝对定位，用于对局界面四周的小按钮），生成函数返回新实体的id，
+// 标记组件和格式参数通过extra参数一起插入。所有按钮都由这里生成，保持外观一致；
+// 按钮中除文本外还有其他内容时，用ButtonStyle::bundle生成按钮节点再自行添加子节点
+
+use super::ButtonColors;
+use crate::{fonts::LocalizedText, localization::TextKey};
+use bevy::prelude::*;
+
+/// 按钮样式
+#[derive(Debug, Clone, PartialEq)]
+pub struct ButtonStyle {
+    pub width: Val,
+    /// 宽度随文本变化的按钮（width为Auto）的最小宽度
+    pub min_width: Val,
+    pub height: Val,
+    pub padding: UiRect,
+    /// 绝对定位时相对父节点的left/right/top/bottom（不用的边为Auto），为None时参与正常布局
+    pub position: Option<UiRect>,
+    /// 正常状态的背景颜色，悬停和按下时分别变亮和变暗
+    pub color: Color,
+    /// 自定义的三种状态颜色，设置后代替由color生成的颜色
+    pub colors: Option<ButtonColors>,
+    pub border_width: f32,
+    pub border_color: Color,
+    pub radius: f32,
+    pub margin: UiRect,
+    pub font_size: f32,
+    pub text_color: Color,
+}
+
+impl Default for ButtonStyle {
+    fn default() -> Self {
+        Self {
+            width: Val::Px(200.0),
+            min_width: Val::Auto,
+            height: Val::Px(44.0), // 触摸友好高度
+            padding: UiRect::ZERO,
+            position: None,
+            color: Color::srgb(0.3, 0.3, 0.3),
+            colors: None,
+            border_width: 0.0,
+            border_color: Color::srgb(0.6, 0.6, 0.6),
+            radius: 8.0,
+            margin: UiRect::ZERO,
+            font_size: 18.0,
+            text_color: Color::WHITE,
+        }
+    }
+}
+
+impl ButtonStyle {
+    /// 按钮节点的组件，不含文本
+    pub fn bundle(&self) -> impl Bundle {
+        let colors = self
+            .colors
+            .unwrap_or_else(|| ButtonColors::for_color(self.color));
+        let mut node = Node {
+            width: self.width,
+            min_width: self.min_width,
+            height: self.height,
+            padding: self.padding,
+            margin: self.margin,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            border: UiRect::all(Val::Px(self.border_width)),
+            ..default()
+        };
+        if let Some(position) = self.position {
+            node.position_type = PositionType::Absolute;
+            node.left = position.left;
+            node.right = position.right;
+            node.top = position.top;
+            node.bottom = position.bottom;
+        }
+        (
+            Button,
+            node,
+            BackgroundColor(colors.normal),
+            BorderColor(self.border_color),
+            BorderRadius::all(Val::Px(self.radius)),
+            colors,
+        )
+    }
+
+    /// 对局界面左上角一列的小按钮：宽度随文本变化，距左边8px、距顶部`top`
+    pub fn corner(top: f32) -> Self {
+        Self {
+            width: Val::Auto,
+            min_width: Val::Px(44.0), // 手机触摸友好的最小尺寸
+            padding: UiRect::horizontal(Val::Px(8.0)),
+            position: Some(UiRect::new(
+                Val::Px(8.0),
+                Val::Auto,
+                Val::Px(top),
+                Val::Auto,
+            )),
+            radius: 6.0,
+            font_size: 14.0,
+            ..default()
+        }
+    }
+
+    /// 按钮中文本的样式
+    pub fn label_style(&self) -> LabelStyle {
+        LabelStyle {
+            font_size: self.font_size,
+            color: self.text_color,
+            ..default()
+        }
+    }
+}
+
+/// 面板样式
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanelStyle {
+    pub width: Val,
+    pub max_width: Val,
+    pub background: Color,
+    pub border_width: f32,
+    pub border_color: Color,
+    pub radius: f32,
+    pub padding: f32,
+    /// 子节点之间的间距
+    pub row_gap: f32,
+}
+
+impl Default for PanelStyle {
+    fn default() -> Self {
+        Self {
+            width: Val::Auto,
+            max_width: Val::Auto,
+            background: Color::srgba(0.1, 0.1, 0.1, 0.95),
+            border_width: 0.0,
+            border_color: Color::srgb(0.6, 0.6, 0.6),
+            radius: 10.0,
+            padding: 15.0,
+            row_gap: 10.0,
+        }
+    }
+}
+
+impl PanelStyle {
+    /// 面板节点的组件，子节点纵向居中排列
+    pub fn bundle(&self) -> impl Bundle {
+        (
+            Node {
+                width: self.width,
+                max_width: self.max_width,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(self.row_gap),
+                padding: UiRect::all(Val::Px(self.padding)),
+                border: UiRect::all(Val::Px(self.border_width)),
+                ..default()
+            },
+            BackgroundColor(self.background),
+            BorderColor(self.border_color),
+            BorderRadius::all(Val::Px(self.radius)),
+        )
+    }
+}
+
+/// 文本标签样式
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelStyle {
+    pub font_size: f32,
+    pub color: Color,
+    /// 多行文本的对齐方式
+    pub justify: JustifyText,
+}
+
+impl Default for LabelStyle {
+    fn default() -> Self {
+        Self {
+            font_size: 14.0,
+            color: Color::WHITE,
+            justify: JustifyText::Left,
+        }
+    }
+}
+
+impl LabelStyle {
+    /// 标签的组件，文本内容由文本键按当前语言生成
+    pub fn bundle(&self, font: &Handle<Font>, key: TextKey) -> impl Bundle {
+        (
+            Text::default(),
+            key,
+            TextFont {
+                font: font.clone(),
+                font_size: self.font_size,
+                ..default()
+            },
+            TextColor(self.color),
+            TextLayout::new_with_justify(self.justify),
+            LocalizedText,
+        )
+    }
+}
+
+/// 生成带本地化文本的按钮，返回按钮实体
+///
+/// `extra`为按钮上的标记组件等，不需要时传入`()`
+pub fn spawn_button(
+    parent: &mut ChildSpawnerCommands,
+    style: &ButtonStyle,
+    font: &Handle<Font>,
+    key: TextKey,
+    extra: impl Bundle,
+) -> Entity {
+    parent
+        .spawn((style.bundle(), extra))
+        .with_children(|button| {
+            button.spawn(style.label_style().bundle(font, key));
+        })
+        .id()
+}
+
+/// 生成面板，返回面板实体，调用方再向其中添加子节点
+///
+/// 面板可以是根节点，也可以通过在`extra`中加入`ChildOf`放到其他节点中
+pub fn spawn_panel(commands: &mut Commands, style: &PanelStyle, extra: impl Bundle) -> Entity {
+    commands.spawn((style.bundle(), extra)).id()
+}
+
+/// 生成本地化文本标签，返回标签实体
+///
+/// `extra`为格式参数（TextArgs）、额外的Node或标记组件等，不需要时传入`()`
+pub fn spawn_label(
+    parent: &mut ChildSpawnerCommands,
+    style: &LabelStyle,
+    font: &Handle<Font>,
+    key: TextKey,
+    extra: impl Bundle,
+) -> Entity {
+    parent.spawn((style.bundle(font, key), extra)).id()
+}
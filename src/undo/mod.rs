@@ -0,0 +1,182 @@
+// 悔棋 - 按对局模式限制悔棋次数，保证对局公平
+//
+// 开局练习和沙盒不限次数；普通对局每局最多悔棋3次；自适应难度的对局结果会调整AI强度，
+// 相当于计分对局，不能悔棋。悔棋撤销玩家上一次落子以及AI随后的应手和跳过回合，
+// 只能在轮到玩家、AI没有在思考且没有自动走开局时进行
+
+use crate::{
+    ai::{AiDifficulty, AiPlayer},
+    assist::CornerWarningState,
+    coach::CoachState,
+    game::{Board, GameLog, MoveAction, PlayerColor},
+    input::InputGate,
+    practice::OpeningAutoplay,
+    ui::{CurrentPlayer, UiState},
+};
+use bevy::prelude::*;
+
+/// 普通对局每局可以悔棋的次数
+pub const NORMAL_UNDO_LIMIT: u32 = 3;
+
+/// 一局对局的悔棋规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoPolicy {
+    /// 不限次数
+    Unlimited,
+    /// 每局最多悔棋的次数
+    PerGame(u32),
+    /// 不能悔棋
+    Disabled,
+}
+
+impl Default for UndoPolicy {
+    fn default() -> Self {
+        Self::PerGame(NORMAL_UNDO_LIMIT)
+    }
+}
+
+impl UndoPolicy {
+    /// 对局模式对应的悔棋规则：练习不限次数，计分对局不能悔棋，其余为普通对局
+    pub fn for_game(sandbox: bool, opening_practice: bool, difficulty: AiDifficulty) -> Self {
+        if sandbox || opening_practice {
+            Self::Unlimited
+        } else if matches!(difficulty, AiDifficulty::Adaptive(_)) {
+            Self::Disabled
+        } else {
+            Self::PerGame(NORMAL_UNDO_LIMIT)
+        }
+    }
+
+    /// 已经悔棋`used`次后剩余的次数，不限次数时返回None
+    pub fn remaining(self, used: u32) -> Option<u32> {
+        match self {
+            Self::Unlimited => None,
+            Self::PerGame(limit) => Some(limit.saturating_sub(used)),
+            Self::Disabled => Some(0),
+        }
+    }
+}
+
+/// 当前不能悔棋的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoBlock {
+    /// 本局的规则不允许悔棋
+    Disabled,
+    /// 本局的悔棋次数已经用完
+    LimitReached,
+    /// 玩家还没有落过子
+    NothingToUndo,
+    /// 没有轮到玩家，或者正在自动走开局
+    NotYourTurn,
+}
+
+/// 本局的悔棋规则和已经使用的次数，每局开始时重置
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UndoState {
+    pub policy: UndoPolicy,
+    pub used: u32,
+}
+
+impl UndoState {
+    pub fn new(policy: UndoPolicy) -> Self {
+        Self { policy, used: 0 }
+    }
+
+    /// 本局剩余的悔棋次数，不限次数时返回None
+    pub fn remaining(&self) -> Option<u32> {
+        self.policy.remaining(self.used)
+    }
+
+    /// 检查规则和次数是否允许悔棋，允许时返回需要撤销的命令数
+    ///
+    /// `player`为悔棋的玩家，沙盒中双方都由玩家落子，传入None撤销最后一次落子
+    pub fn check(&self, log: &GameLog, player: Option<PlayerColor>) -> Result<usize, UndoBlock> {
+        if self.policy == UndoPolicy::Disabled {
+            return Err(UndoBlock::Disabled);
+        }
+        if self.remaining() == Some(0) {
+            return Err(UndoBlock::LimitReached);
+        }
+        undo_depth(log, player).ok_or(UndoBlock::NothingToUndo)
+    }
+}
+
+/// 撤销到玩家上一次落子之前需要撤销的命令数，玩家还没有落过子时返回None
+pub fn undo_depth(log: &GameLog, player: Option<PlayerColor>) -> Option<usize> {
+    log.commands()
+        .iter()
+        .rev()
+        .position(|command| {
+            matches!(command.action, MoveAction::Place(_))
+                && player.is_none_or(|player| command.player == player)
+        })
+        .map(|index| index + 1)
+}
+
+/// 玩家请求悔棋
+#[derive(Event, Debug, Clone, Copy)]
+pub struct UndoRequestEvent;
+
+/// 悔棋的玩家，沙盒中没有AI时为None
+pub fn undo_player(ai_player: Option<&AiPlayer>) -> Option<PlayerColor> {
+    ai_player.map(|ai_player| ai_player.color.opposite())
+}
+
+/// 当前能否悔棋，在规则和次数之外还要求轮到玩家、AI没有在思考、没有自动走开局
+pub fn undo_availability(
+    undo: &UndoState,
+    log: &GameLog,
+    ai_player: Option<&AiPlayer>,
+    autoplay: &OpeningAutoplay,
+) -> Result<usize, UndoBlock> {
+    let depth = undo.check(log, undo_player(ai_player))?;
+    let ai_turn = ai_player
+        .is_some_and(|ai_player| ai_player.is_thinking || ai_player.color == log.to_move());
+    if ai_turn || autoplay.is_playing() {
+        return Err(UndoBlock::NotYourTurn);
+    }
+    Ok(depth)
+}
+
+/// 按悔棋规则执行悔棋请求，撤销命令后同步棋盘和当前玩家
+///
+/// 需要在应用走棋命令之前运行，同一帧内排队的落子命令因序号过期被丢弃
+pub fn apply_undo_requests(
+    mut undo_events: EventReader<UndoRequestEvent>,
+    mut undo: ResMut<UndoState>,
+    mut board_query: Query<(&mut Board, &mut GameLog)>,
+    ai_query: Query<&AiPlayer>,
+    autoplay: Res<OpeningAutoplay>,
+    mut current_player: ResMut<CurrentPlayer>,
+    mut input_gate: ResMut<InputGate>,
+    mut corner_warning: ResMut<CornerWarningState>,
+    mut coach: ResMut<CoachState>,
+    ui_state: Res<UiState>,
+) {
+    // 一帧内多次请求只悔棋一次
+    if undo_events.read().count() == 0 || ui_state.paused {
+        return;
+    }
+    let Ok((mut board, mut log)) = board_query.single_mut() else {
+        return;
+    };
+
+    let depth = match undo_availability(&undo, &log, ai_query.single().ok(), &autoplay) {
+        Ok(depth) => depth,
+        Err(block) => {
+            info!("Undo rejected: {block:?}");
+            return;
+        }
+    };
+    for _ in 0..depth {
+        log.undo();
+    }
+    undo.used += 1;
+
+    *board = log.board();
+    current_player.0 = log.to_move();
+    // 回到之前的回合后允许重新落子，丢弃针对撤销前局面的提醒和点评
+    input_gate.release();
+    corner_warning.pending = None;
+    coach.comment = None;
+}
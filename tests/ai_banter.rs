@@ -0,0 +1,121 @@
+// AI闲聊测试 - 触发事件的优先级、显示时间和冷却、关闭设置以及两种语言的文本
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    ai::AiPlayer,
+    banter::{
+        banter_for_move, trigger_banter, Banter, BanterState, BANTER_COOLDOWN, BANTER_DISPLAY,
+        BIG_FLIP, LONG_THINK,
+    },
+    game::{MoveAction, PlayerColor},
+    localization::{CHINESE_TEXTS, ENGLISH_TEXTS},
+    preferences::Preferences,
+    record::{GameRecord, RecordedMove},
+    ui::FlipChainEvent,
+    AiDifficulty,
+};
+use std::time::Duration;
+
+#[test]
+fn events_are_checked_in_order() {
+    let quick = Duration::from_millis(500);
+    // 角优先于大翻转
+    assert_eq!(
+        banter_for_move(0, BIG_FLIP, false, quick),
+        Some(Banter::PlayerCorner)
+    );
+    assert_eq!(
+        banter_for_move(63, 1, true, LONG_THINK),
+        Some(Banter::AiCorner)
+    );
+    assert_eq!(
+        banter_for_move(20, BIG_FLIP, false, quick),
+        Some(Banter::PlayerBigFlip)
+    );
+    assert_eq!(
+        banter_for_move(20, BIG_FLIP, true, LONG_THINK),
+        Some(Banter::AiBigFlip)
+    );
+    assert_eq!(
+        banter_for_move(20, 2, true, LONG_THINK),
+        Some(Banter::LongThink)
+    );
+
+    // 玩家想得久、普通的一步都不说话
+    assert_eq!(banter_for_move(20, 2, false, LONG_THINK * 3), None);
+    assert_eq!(banter_for_move(20, BIG_FLIP - 1, true, quick), None);
+}
+
+#[test]
+fn lines_expire_and_respect_the_cooldown() {
+    let start = Duration::from_secs(100);
+    let mut state = BanterState::default();
+    assert!(state.say(Banter::AiCorner, start));
+    assert_eq!(state.line(), Some(Banter::AiCorner));
+
+    state.expire(start + BANTER_DISPLAY / 2);
+    assert_eq!(state.line(), Some(Banter::AiCorner));
+    state.expire(start + BANTER_DISPLAY);
+    assert_eq!(state.line(), None);
+
+    // 冷却期间不说新的话
+    assert!(!state.say(Banter::LongThink, start + BANTER_COOLDOWN / 2));
+    assert_eq!(state.line(), None);
+    assert!(state.say(Banter::LongThink, start + BANTER_COOLDOWN));
+    assert_eq!(state.line(), Some(Banter::LongThink));
+}
+
+/// AI执白，记录中最后一步是AI在C4想了很久的落子
+fn banter_world(enabled: bool) -> World {
+    let mut world = World::new();
+    let mut preferences = Preferences::default();
+    preferences.banter.enabled = enabled;
+    world.insert_resource(preferences);
+    world.init_resource::<BanterState>();
+    world.init_resource::<Time>();
+    world.init_resource::<Events<FlipChainEvent>>();
+    let mut record = GameRecord::default();
+    record.moves.push(RecordedMove {
+        player: PlayerColor::White,
+        action: MoveAction::Place(26),
+        think_time: LONG_THINK + Duration::from_secs(1),
+    });
+    world.insert_resource(record);
+    world.spawn(AiPlayer::new(AiDifficulty::Expert, PlayerColor::White));
+    world.send_event(FlipChainEvent {
+        position: 26,
+        player: PlayerColor::White,
+        rays: vec![vec![27]],
+    });
+    world
+}
+
+#[test]
+fn long_ai_thinks_trigger_a_line() {
+    let mut world = banter_world(true);
+    world.run_system_once(trigger_banter).unwrap();
+    assert_eq!(
+        world.resource::<BanterState>().line(),
+        Some(Banter::LongThink)
+    );
+
+    // 关闭设置时不说话
+    let mut world = banter_world(false);
+    world.run_system_once(trigger_banter).unwrap();
+    assert_eq!(world.resource::<BanterState>().line(), None);
+}
+
+#[test]
+fn every_line_is_localized() {
+    for banter in [
+        Banter::PlayerCorner,
+        Banter::AiCorner,
+        Banter::PlayerBigFlip,
+        Banter::AiBigFlip,
+        Banter::LongThink,
+    ] {
+        let key = banter.text_key();
+        assert!(!ENGLISH_TEXTS.format(key, &[]).is_empty());
+        assert!(!CHINESE_TEXTS.format(key, &[]).is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+// AI开局库走法测试 - 对称等价的开局库走法，以及按难度的开局库步数直接走棋
+
+use reversi::{
+    ai::{opening_book::book_moves, AiDifficulty, BOOK_PLIES},
+    game::{Board, PlayerColor},
+};
+
+fn notations(moves: &[u8]) -> Vec<String> {
+    moves
+        .iter()
+        .map(|&position| Board::position_to_notation(position))
+        .collect()
+}
+
+fn after(moves: &[&str]) -> Board {
+    let mut board = Board::new_standard();
+    let mut player = PlayerColor::Black;
+    for notation in moves {
+        let position = Board::notation_to_position(notation).unwrap();
+        assert!(board.is_valid_move(position, player), "{notation}");
+        board.make_move(position, player);
+        player = player.opposite();
+    }
+    board
+}
+
+#[test]
+fn first_move_includes_every_symmetric_equivalent() {
+    let moves = book_moves(&Board::new_standard(), PlayerColor::Black);
+    let mut names = notations(&moves);
+    names.sort();
+    assert_eq!(names, ["C4", "D3", "E6", "F5"]);
+
+    // 轮到白棋时初始局面不在开局库中
+    assert!(book_moves(&Board::new_standard(), PlayerColor::White).is_empty());
+}
+
+#[test]
+fn replies_follow_the_transformed_first_move() {
+    let board = after(&["F5"]);
+    let replies = notations(&book_moves(&board, PlayerColor::White));
+    for reply in ["D6", "F6", "F4"] {
+        assert!(replies.contains(&reply.to_string()), "{replies:?}");
+    }
+
+    // 黑棋先走对称的E6时，回应也按同一变换映射
+    let board = after(&["E6"]);
+    let replies = book_moves(&board, PlayerColor::White);
+    assert!(!replies.is_empty());
+    for position in replies {
+        assert!(board.is_valid_move(position, PlayerColor::White));
+    }
+}
+
+#[test]
+fn book_move_only_for_the_first_plies_of_book_difficulties() {
+    let start = Board::new_standard();
+    let expert = AiDifficulty::Expert;
+    assert_eq!(expert.get_search_params().book_plies, BOOK_PLIES);
+
+    let first = expert.book_move(&start, PlayerColor::Black).unwrap();
+    assert!(book_moves(&start, PlayerColor::Black).contains(&first.position));
+    let reply = expert
+        .book_move(&after(&["F5"]), PlayerColor::White)
+        .unwrap();
+    assert!(after(&["F5"]).is_valid_move(reply.position, PlayerColor::White));
+
+    // 第三步虽然仍在开局库中，也交给搜索
+    let third = after(&["F5", "D6"]);
+    assert!(!book_moves(&third, PlayerColor::Black).is_empty());
+    assert!(expert.book_move(&third, PlayerColor::Black).is_none());
+
+    // 不使用开局库的难度总是搜索
+    assert_eq!(AiDifficulty::Beginner.get_search_params().book_plies, 0);
+    assert!(AiDifficulty::Beginner
+        .book_move(&start, PlayerColor::Black)
+        .is_none());
+}
+
+#[test]
+fn expert_opens_instantly_from_the_book() {
+    let start = Board::new_standard();
+    let book = book_moves(&start, PlayerColor::Black);
+    let chosen = AiDifficulty::Expert
+        .get_ai_move(&start, PlayerColor::Black)
+        .unwrap();
+    assert!(book.contains(&chosen.position));
+}
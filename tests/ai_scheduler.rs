@@ -0,0 +1,131 @@
+// 搜索调度测试 - 并发预算、优先级、取消后台搜索以及被取消的搜索提前返回
+
+use bevy::tasks::{AsyncComputeTaskPool, TaskPool};
+use reversi::ai::{
+    evaluation::EvalConfig, minimax::find_best_move_with_time_limit, AiDifficulty, AiPlayer,
+    AiScheduler, SearchPriority,
+};
+use reversi::game::{Board, PlayerColor};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+#[test]
+fn budget_limits_concurrent_searches() {
+    let scheduler = AiScheduler::new(2);
+    let first = scheduler.request(SearchPriority::Background).unwrap();
+    let _second = scheduler.request(SearchPriority::Background).unwrap();
+    assert!(scheduler.request(SearchPriority::Background).is_none());
+    assert_eq!(scheduler.active(), 2);
+
+    // 许可销毁后归还预算
+    drop(first);
+    assert_eq!(scheduler.active(), 1);
+    assert!(scheduler.request(SearchPriority::Background).is_some());
+}
+
+#[test]
+fn budget_is_at_least_one() {
+    let scheduler = AiScheduler::new(0);
+    assert_eq!(scheduler.budget(), 1);
+    assert!(scheduler.request(SearchPriority::Move).is_some());
+}
+
+#[test]
+fn move_search_cancels_background_searches() {
+    let scheduler = AiScheduler::new(1);
+    let background = scheduler.request(SearchPriority::Background).unwrap();
+
+    // 被取消的后台搜索真正结束之前仍然占用预算
+    assert!(scheduler.request(SearchPriority::Move).is_none());
+    assert!(background.is_cancelled());
+    assert_eq!(scheduler.active(), 1);
+    drop(background);
+
+    let search = scheduler.request(SearchPriority::Move).unwrap();
+    assert!(!search.is_cancelled());
+    assert_eq!(search.priority(), SearchPriority::Move);
+    assert_eq!(scheduler.active(), 1);
+
+    // 走棋搜索进行时不开始后台搜索
+    assert!(scheduler.request(SearchPriority::Background).is_none());
+    drop(search);
+    assert!(scheduler.request(SearchPriority::Background).is_some());
+}
+
+#[test]
+fn cancelled_search_returns_early() {
+    let board = Board::new_standard();
+    let eval = EvalConfig::standard();
+    let cancel = AtomicBool::new(true);
+
+    let started = Instant::now();
+    let result = find_best_move_with_time_limit(
+        &board,
+        Duration::from_secs(60),
+        20,
+        PlayerColor::Black,
+        eval,
+        None,
+        Some(&cancel),
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(!result.completed);
+    assert!(result.depth_reached < 20);
+
+    // 许可被取消后，传给搜索的标志同样被设置
+    let scheduler = AiScheduler::new(2);
+    let background = scheduler.request(SearchPriority::Background).unwrap();
+    let flag = background.cancel_flag();
+    let _search = scheduler.request(SearchPriority::Move).unwrap();
+    assert!(flag.load(Ordering::Relaxed));
+}
+
+#[test]
+fn cancelled_ai_player_stops_thinking() {
+    AsyncComputeTaskPool::get_or_init(TaskPool::new);
+    let scheduler = AiScheduler::default();
+    // 离开开局库的中局局面，专家难度的搜索用满时间预算，取消前不会自行结束
+    let mut board = Board::new_standard();
+    let mut player = PlayerColor::Black;
+    for _ in 0..20 {
+        let moves = board.get_valid_moves(player);
+        board.make_move(moves.trailing_zeros() as u8, player);
+        if board.get_valid_moves(player.opposite()) != 0 {
+            player = player.opposite();
+        }
+    }
+    let eval = Arc::new(EvalConfig::standard().clone());
+
+    let mut background = AiPlayer::new(AiDifficulty::Expert, player);
+    assert!(background.start_scheduled_thinking(
+        &board,
+        eval.clone(),
+        &scheduler,
+        SearchPriority::Background,
+    ));
+    assert!(background.is_thinking);
+
+    let _search = scheduler.request(SearchPriority::Move).unwrap();
+    assert_eq!(background.check_thinking_result(), None);
+    assert!(!background.is_thinking);
+
+    // 被取消的搜索很快返回并归还预算
+    let started = Instant::now();
+    while scheduler.active() > 1 {
+        assert!(started.elapsed() < Duration::from_secs(5));
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    // 走棋搜索结束前不能重新开始
+    assert!(!background.start_scheduled_thinking(
+        &board,
+        eval,
+        &scheduler,
+        SearchPriority::Background,
+    ));
+}
@@ -0,0 +1,43 @@
+// AI看门狗测试 - 硬性时限和备用走法
+
+use reversi::ai::{
+    evaluation::EvalConfig, AiDifficulty, WATCHDOG_MIN_DEADLINE, WATCHDOG_TIME_FACTOR,
+};
+use reversi::game::{Board, PlayerColor};
+
+const DIFFICULTIES: [AiDifficulty; 4] = [
+    AiDifficulty::Beginner,
+    AiDifficulty::Intermediate,
+    AiDifficulty::Advanced,
+    AiDifficulty::Expert,
+];
+
+#[test]
+fn deadline_is_a_multiple_of_the_time_limit() {
+    for difficulty in DIFFICULTIES {
+        let deadline = difficulty.watchdog_deadline();
+        assert!(deadline >= difficulty.get_search_params().time_limit * WATCHDOG_TIME_FACTOR);
+        assert!(deadline >= WATCHDOG_MIN_DEADLINE);
+    }
+}
+
+#[test]
+fn fallback_move_is_legal() {
+    let board = Board::new_standard();
+    for difficulty in DIFFICULTIES {
+        let fallback = difficulty
+            .fallback_move(&board, PlayerColor::Black, EvalConfig::standard())
+            .expect("opening position has legal moves");
+        assert!(board.is_valid_move(fallback.position, PlayerColor::Black));
+    }
+
+    // 无棋可走时没有备用走法
+    let full = Board {
+        black: u64::MAX,
+        white: 0,
+    };
+    assert_eq!(
+        AiDifficulty::Beginner.fallback_move(&full, PlayerColor::White, EvalConfig::standard()),
+        None
+    );
+}
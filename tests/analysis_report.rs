@@ -0,0 +1,93 @@
+// 分析报告测试 - 败着和疑问手的评注、思考时间、终局棋盘图，以及Markdown和HTML两种格式
+
+use reversi::{
+    ai::evaluation::EvalConfig,
+    game::{parse_position_string, Board},
+    input::InputAction,
+    practice::MoveQuality,
+    record::{board_diagram, AnalysisReport, GameRecord, RecordedMove, ReportFormat},
+    GameLog, GameState, MoveAction, PlayerColor,
+};
+use std::time::Duration;
+
+/// 与走法质量测试相同的局面：黑方下C5是败着，D6是好棋
+fn log_after(notation: &str) -> GameLog {
+    let (board, player) = parse_position_string(concat!(
+        "--------",
+        "-X------",
+        "--XO----",
+        "---XO---",
+        "---OX---",
+        "--------",
+        "--------",
+        "-------- X",
+    ))
+    .unwrap();
+    let mut log = GameLog::new(board, player);
+    let action = MoveAction::Place(Board::notation_to_position(notation).unwrap());
+    log.apply(log.next_command(action)).unwrap();
+    log
+}
+
+#[test]
+fn blunders_are_marked_with_a_better_move() {
+    let log = log_after("C5");
+    let report = AnalysisReport::build(&log, &GameRecord::default(), EvalConfig::standard());
+
+    let blunder = &report.moves[0];
+    assert_eq!(blunder.quality, Some(MoveQuality::Bad));
+    assert_eq!(blunder.mark(), "??");
+    assert!(blunder.better.is_some());
+    assert_eq!(report.blunders().count(), 1);
+
+    let markdown = report.render(ReportFormat::Markdown);
+    assert!(markdown.contains("| 1 | Black | C5?? | - | **Blunder: "));
+    assert!(markdown.contains("## Blunders"));
+    let html = report.render(ReportFormat::Html);
+    assert!(html.contains("<tr class=\"blunder\"><td>1</td><td>Black</td><td>C5??</td>"));
+    assert!(html.contains("<svg"));
+}
+
+#[test]
+fn good_moves_have_no_annotation() {
+    let log = log_after("D6");
+    let mut record = GameRecord::default();
+    record.moves.push(RecordedMove {
+        player: PlayerColor::Black,
+        action: log.commands()[0].action,
+        think_time: Duration::from_millis(4200),
+    });
+    let report = AnalysisReport::build(&log, &record, EvalConfig::standard());
+
+    assert_eq!(report.moves[0].quality, Some(MoveQuality::Good));
+    assert_eq!(report.moves[0].better, None);
+    let markdown = report.to_markdown();
+    assert!(markdown.contains("| 1 | Black | D6 | 4.2s |  |"));
+    assert!(!markdown.contains("## Blunders"));
+    assert!(markdown.contains("- Blunders: 0 (Black 0, White 0)"));
+}
+
+#[test]
+fn final_board_is_drawn_as_unicode_art() {
+    let diagram = board_diagram(&Board::new_standard());
+    let lines: Vec<_> = diagram.lines().collect();
+    assert_eq!(lines.len(), 9);
+    assert_eq!(lines[0], "  A B C D E F G H");
+    assert_eq!(lines[4], "4 · · · ○ ● · · ·");
+    assert_eq!(lines[5], "5 · · · ● ○ · · ·");
+
+    let report = AnalysisReport::build(
+        &GameLog::new(Board::new_standard(), PlayerColor::Black),
+        &GameRecord::default(),
+        EvalConfig::standard(),
+    );
+    assert!(report.to_markdown().contains(&diagram));
+}
+
+#[test]
+fn report_export_is_bound_to_r_after_the_game() {
+    let key = InputAction::ExportReport.default_keys();
+    assert_eq!(key, &[bevy::prelude::KeyCode::KeyR]);
+    assert!(InputAction::ExportReport.is_active(GameState::GameOver));
+    assert!(!InputAction::ExportReport.is_active(GameState::Playing));
+}
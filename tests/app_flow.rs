@@ -0,0 +1,271 @@
+// 应用流程测试 - 用最小插件组构建完整的无窗口应用，模拟落子事件和时间推进，
+// 检查棋盘状态、状态切换，以及重新开始和返回菜单之后的实体清理
+
+mod common;
+
+use bevy::{
+    input::InputPlugin,
+    prelude::*,
+    state::app::StatesPlugin,
+    time::TimeUpdateStrategy,
+    window::{ExitCondition, WindowPlugin},
+};
+use common::{count, session};
+use reversi::{
+    ai::AiPlayer,
+    game::{parse_position_string, Board, GameLog},
+    plugin::{BackToDifficultyEvent, GameSession, NewGameRequest, PlayerMoveEvent},
+    preferences::{storage::StorageLocation, Preferences},
+    ui::{BoardUI, GameUI, Piece, RestartGameEvent},
+    AiDifficulty, GameState, PlayerColor, ReversiPlugin, SelectedDifficulty,
+};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+/// 每次更新推进的时间
+const FRAME: Duration = Duration::from_millis(100);
+
+/// 等待异步事件（AI思考、状态切换）的最多帧数
+const MAX_FRAMES: usize = 300;
+
+/// 每个应用使用单独的临时数据目录，并行运行的测试不会读写彼此的存档
+fn temporary_storage() -> StorageLocation {
+    static NEXT_APP: AtomicUsize = AtomicUsize::new(0);
+    let app = NEXT_APP.fetch_add(1, Ordering::Relaxed);
+    StorageLocation(
+        std::env::temp_dir().join(format!("reversi-app-flow-{}-{app}", std::process::id())),
+    )
+}
+
+/// 无窗口、无渲染和音频输出的完整应用，跳过加载画面直接进入难度选择
+///
+/// 存档写入临时目录，测试不会改动用户的设置和战绩
+fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        AssetPlugin::default(),
+        InputPlugin,
+        WindowPlugin {
+            primary_window: None,
+            exit_condition: ExitCondition::DontExit,
+            close_when_requested: false,
+        },
+    ))
+    // 渲染、文字和音频插件没有加入，界面用到的资源类型需要单独注册
+    .init_asset::<Font>()
+    .init_asset::<AudioSource>()
+    .init_asset::<Image>()
+    .init_asset::<Mesh>()
+    .init_asset::<ColorMaterial>()
+    .init_asset::<TextureAtlasLayout>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+    .insert_resource(Preferences::default())
+    .insert_resource(temporary_storage())
+    .add_plugins(ReversiPlugin);
+
+    app.update();
+    set_state(&mut app, GameState::DifficultySelection);
+    app
+}
+
+fn set_state(app: &mut App, state: GameState) {
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(state);
+    app.update();
+}
+
+fn state(app: &App) -> GameState {
+    *app.world().resource::<State<GameState>>().get()
+}
+
+/// 与难度选择按钮相同：选择难度，请求新对局并进入对局
+fn start_game(app: &mut App) {
+    app.insert_resource(SelectedDifficulty(AiDifficulty::Beginner));
+    app.world_mut().resource_mut::<NewGameRequest>().0 = true;
+    set_state(app, GameState::Playing);
+}
+
+/// 推进时间直到条件满足，超过最多帧数时测试失败
+fn run_until(app: &mut App, what: &str, done: impl Fn(&mut App) -> bool) {
+    for _ in 0..MAX_FRAMES {
+        if done(app) {
+            return;
+        }
+        app.update();
+    }
+    panic!("timed out waiting for {what}");
+}
+
+fn log(app: &mut App) -> GameLog {
+    app.world_mut()
+        .query::<&GameLog>()
+        .single(app.world())
+        .unwrap()
+        .clone()
+}
+
+fn board(app: &mut App) -> Board {
+    *app.world_mut()
+        .query::<&Board>()
+        .single(app.world())
+        .unwrap()
+}
+
+/// 玩家（执黑）走第一个合法位置
+fn play_first_move(app: &mut App) -> u8 {
+    let position = log(app).board().get_valid_moves_list(PlayerColor::Black)[0].position;
+    app.world_mut().send_event(PlayerMoveEvent { position });
+    app.update();
+    position
+}
+
+/// 点击重新开始：经过Restarting过渡回到对局，等新界面创建完成
+fn restart(app: &mut App) {
+    app.world_mut().send_event(RestartGameEvent);
+    run_until(app, "the restart transition", |app| {
+        state(app) == GameState::Restarting
+    });
+    run_until(app, "the new game", |app| state(app) == GameState::Playing);
+    app.update();
+}
+
+/// 对局界面的实体各只有一份，棋子与棋盘上的棋子一一对应
+fn assert_single_screen(app: &mut App) {
+    assert_eq!(count::<GameSession>(app.world_mut()), 1);
+    assert_eq!(count::<AiPlayer>(app.world_mut()), 1);
+    assert_eq!(count::<GameUI>(app.world_mut()), 1);
+    assert_eq!(count::<BoardUI>(app.world_mut()), 1);
+    let board = board(app);
+    let discs = board.count_pieces(PlayerColor::Black) + board.count_pieces(PlayerColor::White);
+    assert_eq!(count::<Piece>(app.world_mut()), discs as usize);
+}
+
+#[test]
+fn starting_a_game_builds_one_session_and_screen() {
+    let mut app = headless_app();
+    assert_eq!(state(&app), GameState::DifficultySelection);
+
+    start_game(&mut app);
+    app.update();
+
+    assert_eq!(state(&app), GameState::Playing);
+    assert_single_screen(&mut app);
+    assert!(log(&mut app).commands().is_empty());
+}
+
+#[test]
+fn player_move_updates_the_board_and_ai_replies() {
+    let mut app = headless_app();
+    start_game(&mut app);
+
+    let position = play_first_move(&mut app);
+    assert_eq!(log(&mut app).commands().len(), 1);
+    assert_eq!(
+        board(&mut app).get_piece(position),
+        Some(PlayerColor::Black)
+    );
+
+    run_until(&mut app, "the AI reply", |app| {
+        log(app).to_move() == PlayerColor::Black && log(app).commands().len() >= 2
+    });
+    assert_eq!(board(&mut app), log(&mut app).board());
+    app.update();
+    assert_single_screen(&mut app);
+}
+
+#[test]
+fn illegal_player_move_leaves_the_board_unchanged() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    let before = board(&mut app);
+    let occupied = (0..64).find(|&square| before.get_piece(square).is_some());
+
+    app.world_mut().send_event(PlayerMoveEvent {
+        position: occupied.unwrap(),
+    });
+    app.update();
+
+    assert!(log(&mut app).commands().is_empty());
+    assert_eq!(board(&mut app), before);
+}
+
+#[test]
+fn restart_replaces_the_session_and_cleans_up_the_screen() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    let first = session(app.world_mut());
+    play_first_move(&mut app);
+
+    restart(&mut app);
+
+    assert_ne!(session(app.world_mut()), first);
+    assert!(log(&mut app).commands().is_empty());
+    assert_single_screen(&mut app);
+}
+
+#[test]
+fn restarting_twice_does_not_leak_entities() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    app.update();
+    let entities = app.world().entities().len();
+
+    for _ in 0..2 {
+        restart(&mut app);
+        assert_single_screen(&mut app);
+    }
+
+    // 重新开始后对局界面完整重建，实体数量回到第一次进入对局时的数量
+    assert_eq!(app.world().entities().len(), entities);
+}
+
+#[test]
+fn back_to_menu_removes_the_game_and_a_new_game_starts_fresh() {
+    let mut app = headless_app();
+    start_game(&mut app);
+    play_first_move(&mut app);
+
+    app.world_mut().send_event(BackToDifficultyEvent);
+    app.update();
+    app.update();
+
+    assert_eq!(state(&app), GameState::DifficultySelection);
+    assert_eq!(count::<GameSession>(app.world_mut()), 0);
+    assert_eq!(count::<AiPlayer>(app.world_mut()), 0);
+    assert_eq!(count::<GameUI>(app.world_mut()), 0);
+    assert_eq!(count::<BoardUI>(app.world_mut()), 0);
+    assert_eq!(count::<Piece>(app.world_mut()), 0);
+
+    start_game(&mut app);
+    app.update();
+    assert!(log(&mut app).commands().is_empty());
+    assert_single_screen(&mut app);
+}
+
+#[test]
+fn final_move_ends_the_game() {
+    let mut app = headless_app();
+    start_game(&mut app);
+
+    // 只剩A1一个空格，黑方在A1落子翻转B1后棋盘下满
+    let squares = format!("-O{}", "X".repeat(62));
+    let (position, _) = parse_position_string(&format!("{squares} X")).unwrap();
+    let entity = session(app.world_mut());
+    app.world_mut()
+        .entity_mut(entity)
+        .insert((position, GameLog::new(position, PlayerColor::Black)));
+    app.update();
+
+    app.world_mut().send_event(PlayerMoveEvent { position: 0 });
+    run_until(&mut app, "the game over screen", |app| {
+        state(app) == GameState::GameOver
+    });
+
+    assert_eq!(board(&mut app).count_pieces(PlayerColor::White), 0);
+    assert_eq!(log(&mut app).winner(), Some(PlayerColor::Black));
+}
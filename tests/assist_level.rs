@@ -0,0 +1,108 @@
+// 辅助等级测试 - 等级决定可落子指示器、翻转数标注和基于局面评估的提示，并与对局规则共同生效
+
+mod common;
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use common::insert_board_assets;
+use reversi::{
+    assist::{ActiveMatchRules, AssistLevel, AssistPreferences, MatchRules},
+    game::{Board, LegalMovesCache, PlayerColor},
+    history::HistoryPreview,
+    preferences::Preferences,
+    ui::{
+        update_flip_counts, update_valid_moves, BoardUI, CurrentPlayer, FlipCountLabel,
+        ValidMoveIndicator,
+    },
+};
+
+#[test]
+fn levels_are_cumulative() {
+    assert_eq!(AssistLevel::default(), AssistLevel::Hints);
+    assert_eq!(AssistLevel::Hints.rules(), MatchRules::UNRESTRICTED);
+
+    let off = AssistLevel::Off.rules();
+    assert!(!off.legal_move_hints && !off.coach);
+    let legal = AssistLevel::LegalMoves.rules();
+    assert!(legal.legal_move_hints && !legal.corner_warning && !legal.score_projection);
+    assert!(!AssistLevel::LegalMoves.shows_flip_counts());
+    assert!(AssistLevel::FlipCounts.shows_flip_counts());
+    assert_eq!(AssistLevel::FlipCounts.rules(), legal);
+
+    // 设置中循环切换
+    assert_eq!(AssistLevel::Hints.next(), AssistLevel::Hardcore);
+    assert_eq!(AssistLevel::Hardcore.next(), AssistLevel::Off);
+}
+
+#[test]
+fn level_and_match_rules_both_apply() {
+    let preferences = AssistPreferences {
+        level: AssistLevel::LegalMoves,
+        enabled: true,
+        coach: true,
+        ..AssistPreferences::default()
+    };
+    let permitted = preferences.permitted(MatchRules::UNRESTRICTED);
+    assert!(!permitted.warns_about_corners());
+    assert!(!permitted.coach);
+    assert_eq!(permitted.level, AssistLevel::LegalMoves);
+
+    // 辅助等级允许但对局规则不允许时同样关闭
+    let hints = AssistPreferences {
+        level: AssistLevel::Hints,
+        ..preferences
+    };
+    assert!(!hints.allowed(MatchRules::default()).coach);
+    assert!(hints.allowed(MatchRules::default()).legal_move_hints);
+}
+
+fn board_world(level: AssistLevel) -> World {
+    let mut world = World::new();
+    let mut preferences = Preferences::default();
+    preferences.assist.level = level;
+    world.insert_resource(preferences);
+    world.init_resource::<ActiveMatchRules>();
+    world.insert_resource(CurrentPlayer(PlayerColor::Black));
+    world.init_resource::<LegalMovesCache>();
+    insert_board_assets(&mut world);
+    world.init_resource::<HistoryPreview>();
+    world.spawn(BoardUI);
+    world.spawn(Board::new_standard());
+    world
+}
+
+fn labels(world: &mut World) -> (usize, Vec<u32>) {
+    world.run_system_once(update_valid_moves).unwrap();
+    world.run_system_once(update_flip_counts).unwrap();
+    let indicators = world.query::<&ValidMoveIndicator>().iter(world).count();
+    let counts = world
+        .query::<&FlipCountLabel>()
+        .iter(world)
+        .map(|label| label.count)
+        .collect();
+    (indicators, counts)
+}
+
+#[test]
+fn off_hides_the_legal_move_dots() {
+    let mut world = board_world(AssistLevel::Off);
+    assert_eq!(labels(&mut world), (0, Vec::new()));
+
+    // 调高等级后重新显示
+    world.resource_mut::<Preferences>().assist.level = AssistLevel::LegalMoves;
+    assert_eq!(labels(&mut world), (4, Vec::new()));
+}
+
+#[test]
+fn flip_counts_label_each_indicator() {
+    let mut world = board_world(AssistLevel::FlipCounts);
+    // 开局的4个走法都只翻转1枚棋子
+    assert_eq!(labels(&mut world), (4, vec![1; 4]));
+
+    let mut board = Board::new_standard();
+    board.make_move(19, PlayerColor::Black);
+    assert_eq!(board.flip_count(19, PlayerColor::Black), 0);
+    let flips = board.get_valid_moves_list(PlayerColor::White);
+    assert!(flips
+        .iter()
+        .all(|chess_move| board.flip_count(chess_move.position, PlayerColor::White) >= 1));
+}
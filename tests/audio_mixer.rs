@@ -0,0 +1,113 @@
+// 音频混音测试 - 声道复用、上限和胜负音效抢占，AI落子音效升调
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    ai::AiPlayer,
+    audio::{
+        choose_voice, play_sound_system, voice_speed, AudioAssets, AudioSettings, PlaySoundEvent,
+        SoundType, VoiceChoice, VoiceSlot, AI_PLACEMENT_SPEED, MAX_VOICES,
+    },
+    game::PlayerColor,
+    AiDifficulty,
+};
+use std::sync::Arc;
+
+fn slot(sting: bool, started_at: f32, busy: bool) -> VoiceSlot {
+    VoiceSlot {
+        sting,
+        started_at,
+        busy,
+    }
+}
+
+#[test]
+fn idle_voice_is_reused_before_spawning() {
+    let slots = [slot(false, 0.0, true), slot(false, 1.0, false)];
+    assert_eq!(choose_voice(&slots, false), VoiceChoice::Reuse(1));
+    assert_eq!(choose_voice(&slots[..1], false), VoiceChoice::Spawn);
+}
+
+#[test]
+fn full_mixer_drops_sfx_and_lets_stings_steal_oldest_sfx() {
+    let mut slots: Vec<VoiceSlot> = (0..MAX_VOICES)
+        .map(|i| slot(false, 1.0 + i as f32, true))
+        .collect();
+    slots[0].sting = true;
+    slots[0].started_at = 0.0;
+
+    assert_eq!(choose_voice(&slots, false), VoiceChoice::Drop);
+    assert_eq!(choose_voice(&slots, true), VoiceChoice::Steal(1));
+
+    for slot in &mut slots {
+        slot.sting = true;
+    }
+    assert_eq!(choose_voice(&slots, true), VoiceChoice::Steal(0));
+}
+
+#[test]
+fn ai_placements_are_pitched_up() {
+    assert_eq!(voice_speed(SoundType::PiecePlace, false), 1.0);
+    assert_eq!(voice_speed(SoundType::PiecePlace, true), AI_PLACEMENT_SPEED);
+    assert_eq!(voice_speed(SoundType::PieceFlip, true), AI_PLACEMENT_SPEED);
+    // 与落子无关的音效不区分玩家和AI
+    for sound_type in [SoundType::Victory, SoundType::Pass, SoundType::InvalidMove] {
+        assert_eq!(
+            voice_speed(sound_type, true),
+            voice_speed(sound_type, false)
+        );
+    }
+}
+
+/// AI执白的世界，所有音效都已加载
+fn mixer_world() -> World {
+    let mut world = World::new();
+    let mut sources = Assets::<AudioSource>::default();
+    let mut load = || {
+        sources.add(AudioSource {
+            bytes: Arc::from(Vec::new()),
+        })
+    };
+    let assets = AudioAssets {
+        piece_place: load(),
+        piece_flip: load(),
+        victory: load(),
+        defeat: load(),
+        invalid_move: load(),
+        pass: load(),
+        low_time: load(),
+        corner_capture: load(),
+        edge_complete: load(),
+        wipeout_threat: load(),
+    };
+    world.insert_resource(sources);
+    world.insert_resource(assets);
+    world.init_resource::<AudioSettings>();
+    world.init_resource::<Time>();
+    world.init_resource::<Events<PlaySoundEvent>>();
+    world.spawn(AiPlayer::new(AiDifficulty::Beginner, PlayerColor::White));
+    world
+}
+
+fn played_speeds(world: &mut World) -> Vec<f32> {
+    let mut speeds: Vec<f32> = world
+        .query::<&PlaybackSettings>()
+        .iter(world)
+        .map(|settings| settings.speed)
+        .collect();
+    speeds.sort_by(f32::total_cmp);
+    speeds
+}
+
+#[test]
+fn mixer_tells_player_and_ai_placements_apart() {
+    let mut world = mixer_world();
+    for actor in [PlayerColor::Black, PlayerColor::White, PlayerColor::White] {
+        world.send_event(PlaySoundEvent {
+            sound_type: SoundType::PiecePlace,
+            actor: Some(actor),
+        });
+    }
+    world.run_system_once(play_sound_system).unwrap();
+    // 同一方的相同音效只播放一次，玩家和AI的落子各占一个声道
+    assert_eq!(played_speeds(&mut world), vec![1.0, AI_PLACEMENT_SPEED]);
+}
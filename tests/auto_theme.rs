@@ -0,0 +1,97 @@
+// 自动主题测试 - 优先使用系统的深色模式设置，否则按本地时间在夜间切换到深色主题
+
+use bevy::{
+    ecs::system::RunSystemOnce,
+    prelude::*,
+    window::{PrimaryWindow, WindowTheme, WindowThemeChanged},
+};
+use reversi::{
+    config_assets::{update_active_theme, ActiveTheme, ThemeMode, ThemePalette},
+    preferences::Preferences,
+};
+
+#[test]
+fn fixed_modes_ignore_hints() {
+    assert_eq!(
+        ThemeMode::Light.palette(Some(WindowTheme::Dark), Some(23)),
+        ThemePalette::Light
+    );
+    assert_eq!(
+        ThemeMode::Dark.palette(Some(WindowTheme::Light), Some(12)),
+        ThemePalette::Dark
+    );
+}
+
+#[test]
+fn auto_prefers_os_hint_over_local_time() {
+    assert_eq!(
+        ThemeMode::Auto.palette(Some(WindowTheme::Dark), Some(12)),
+        ThemePalette::Dark
+    );
+    assert_eq!(
+        ThemeMode::Auto.palette(Some(WindowTheme::Light), Some(23)),
+        ThemePalette::Light
+    );
+    assert_eq!(ThemeMode::Auto.palette(None, Some(23)), ThemePalette::Dark);
+    assert_eq!(ThemeMode::Auto.palette(None, None), ThemePalette::Light);
+}
+
+#[test]
+fn night_hours_use_dark_palette() {
+    assert_eq!(ThemePalette::for_hour(6), ThemePalette::Dark);
+    assert_eq!(ThemePalette::for_hour(7), ThemePalette::Light);
+    assert_eq!(ThemePalette::for_hour(18), ThemePalette::Light);
+    assert_eq!(ThemePalette::for_hour(19), ThemePalette::Dark);
+    assert_eq!(ThemePalette::for_hour(0), ThemePalette::Dark);
+}
+
+#[test]
+fn modes_cycle_through_all_options() {
+    assert_eq!(ThemeMode::Light.next(), ThemeMode::Dark);
+    assert_eq!(ThemeMode::Dark.next(), ThemeMode::Auto);
+    assert_eq!(ThemeMode::Auto.next(), ThemeMode::Light);
+}
+
+fn theme_world(mode: ThemeMode) -> World {
+    let mut world = World::new();
+    world.insert_resource(Preferences {
+        theme: mode,
+        ..default()
+    });
+    world.init_resource::<ActiveTheme>();
+    world.init_resource::<Events<WindowThemeChanged>>();
+    world.init_resource::<Time<Real>>();
+    world
+}
+
+#[test]
+fn os_theme_change_switches_auto_theme() {
+    let mut world = theme_world(ThemeMode::Auto);
+    let window = world
+        .spawn((
+            Window {
+                window_theme: Some(WindowTheme::Light),
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id();
+    world.run_system_once(update_active_theme).unwrap();
+    assert_eq!(world.resource::<ActiveTheme>().palette, ThemePalette::Light);
+
+    world.send_event(WindowThemeChanged {
+        window,
+        theme: WindowTheme::Dark,
+    });
+    world.run_system_once(update_active_theme).unwrap();
+    let active = world.resource::<ActiveTheme>();
+    assert_eq!(active.os_hint, Some(WindowTheme::Dark));
+    assert_eq!(active.palette, ThemePalette::Dark);
+}
+
+#[test]
+fn dark_mode_applies_without_window() {
+    let mut world = theme_world(ThemeMode::Dark);
+    world.run_system_once(update_active_theme).unwrap();
+    assert_eq!(world.resource::<ActiveTheme>().palette, ThemePalette::Dark);
+}
@@ -0,0 +1,240 @@
+// 最佳走法提示测试 - 后台逐层加深、刷新频率限制、让位给AI搜索、局面变化时失效，以及按提示键显示标记
+
+use bevy::{prelude::*, tasks::AsyncComputeTaskPool, tasks::TaskPool};
+use reversi::{
+    ai::{evaluation::EvalConfig, minimax::find_best_move, AiScheduler, SearchPriority},
+    assist::{
+        hints_available, ActiveMatchRules, AssistLevel, HintCache, MatchRules, HINT_MAX_DEPTH,
+        HINT_MIN_DEPTH, HINT_REFRESH_SECS,
+    },
+    game::Board,
+    input::{InputAction, InputMap, InputSource, PlayerSeats},
+    preferences::Preferences,
+    sandbox::SandboxMode,
+    ui::{handle_hint_input, update_hint_marker, CurrentPlayer, HintMarker, HintRequest},
+    PlayerColor,
+};
+use std::{sync::Arc, time::Duration};
+
+const REFRESH: Duration = Duration::from_millis((HINT_REFRESH_SECS * 1000.0) as u64);
+
+fn eval() -> Arc<EvalConfig> {
+    AsyncComputeTaskPool::get_or_init(TaskPool::default);
+    Arc::new(EvalConfig::standard().clone())
+}
+
+/// 反复刷新直到后台搜索完成
+fn finish_search(cache: &mut HintCache, board: &Board, now: Duration, scheduler: &AiScheduler) {
+    let eval = eval();
+    cache.refresh(board, PlayerColor::Black, now, scheduler, &eval);
+    for _ in 0..5000 {
+        if !cache.is_searching() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+        cache.refresh(board, PlayerColor::Black, now, scheduler, &eval);
+    }
+    panic!("hint search did not finish");
+}
+
+#[test]
+fn cache_deepens_step_by_step_within_the_refresh_limit() {
+    let board = Board::new_standard();
+    let scheduler = AiScheduler::default();
+    let mut cache = HintCache::default();
+
+    finish_search(&mut cache, &board, Duration::ZERO, &scheduler);
+    assert_eq!(cache.depth(), Some(HINT_MIN_DEPTH));
+    let first = cache.best_move(&board, PlayerColor::Black).unwrap();
+    assert!(board.is_valid_move(first, PlayerColor::Black));
+
+    // 间隔不够时不开始下一层
+    cache.refresh(&board, PlayerColor::Black, REFRESH / 2, &scheduler, &eval());
+    assert!(!cache.is_searching());
+
+    let mut now = Duration::ZERO;
+    while cache.depth() != Some(HINT_MAX_DEPTH) {
+        now += REFRESH;
+        finish_search(&mut cache, &board, now, &scheduler);
+    }
+    let deepest = find_best_move(&board, HINT_MAX_DEPTH, PlayerColor::Black, eval().as_ref())
+        .best_move
+        .map(|best| best.position);
+    assert_eq!(cache.best_move(&board, PlayerColor::Black), deepest);
+
+    // 到最大深度后不再搜索
+    cache.refresh(
+        &board,
+        PlayerColor::Black,
+        now + REFRESH,
+        &scheduler,
+        &eval(),
+    );
+    assert!(!cache.is_searching());
+}
+
+#[test]
+fn ai_searches_take_priority_over_hints() {
+    let board = Board::new_standard();
+    let scheduler = AiScheduler::default();
+    let mut cache = HintCache::default();
+
+    let ai_search = scheduler.request(SearchPriority::Move).unwrap();
+    cache.refresh(
+        &board,
+        PlayerColor::Black,
+        Duration::ZERO,
+        &scheduler,
+        &eval(),
+    );
+    assert!(!cache.is_searching());
+    drop(ai_search);
+
+    cache.refresh(
+        &board,
+        PlayerColor::Black,
+        Duration::ZERO,
+        &scheduler,
+        &eval(),
+    );
+    assert!(cache.is_searching());
+    // AI开始思考时取消提示搜索
+    let _ai_search = scheduler.request(SearchPriority::Move).unwrap();
+    cache.refresh(
+        &board,
+        PlayerColor::Black,
+        Duration::ZERO,
+        &scheduler,
+        &eval(),
+    );
+    assert!(!cache.is_searching());
+    assert_eq!(cache.best_move(&board, PlayerColor::Black), None);
+}
+
+#[test]
+fn a_new_position_invalidates_the_cache() {
+    let board = Board::new_standard();
+    let scheduler = AiScheduler::default();
+    let mut cache = HintCache::default();
+    finish_search(&mut cache, &board, Duration::ZERO, &scheduler);
+
+    let mut next = board;
+    next.make_move(
+        cache.best_move(&board, PlayerColor::Black).unwrap(),
+        PlayerColor::Black,
+    );
+    cache.refresh(
+        &next,
+        PlayerColor::White,
+        Duration::ZERO,
+        &scheduler,
+        &eval(),
+    );
+    assert_eq!(cache.best_move(&board, PlayerColor::Black), None);
+    assert_eq!(cache.depth(), None);
+}
+
+#[test]
+fn hints_need_a_local_player_and_permission() {
+    let seats = PlayerSeats::default();
+    let preferences = Preferences::default();
+    let local = ActiveMatchRules::default();
+    assert!(hints_available(
+        PlayerColor::Black,
+        &seats,
+        &SandboxMode(false),
+        &preferences,
+        &local
+    ));
+    assert!(!hints_available(
+        PlayerColor::Black,
+        &seats,
+        &SandboxMode(true),
+        &preferences,
+        &local
+    ));
+
+    let mut ai_seat = PlayerSeats::default();
+    ai_seat.assign(PlayerColor::Black, InputSource::Ai);
+    assert!(!hints_available(
+        PlayerColor::Black,
+        &ai_seat,
+        &SandboxMode(false),
+        &preferences,
+        &local
+    ));
+
+    // 联机对局默认不允许，辅助等级低于"允许提示"时也不允许
+    let online = ActiveMatchRules(Some(MatchRules::default()));
+    assert!(!hints_available(
+        PlayerColor::Black,
+        &seats,
+        &SandboxMode(false),
+        &preferences,
+        &online
+    ));
+    let mut flip_counts = Preferences::default();
+    flip_counts.assist.level = AssistLevel::FlipCounts;
+    assert!(!hints_available(
+        PlayerColor::Black,
+        &seats,
+        &SandboxMode(false),
+        &flip_counts,
+        &local
+    ));
+}
+
+#[test]
+fn pressing_the_hint_key_shows_the_cached_move() {
+    let board = Board::new_standard();
+    let scheduler = AiScheduler::default();
+    let mut cache = HintCache::default();
+    finish_search(&mut cache, &board, Duration::ZERO, &scheduler);
+    let best = cache.best_move(&board, PlayerColor::Black).unwrap();
+
+    let mut app = App::new();
+    app.insert_resource(cache)
+        .init_resource::<HintRequest>()
+        .init_resource::<InputMap>()
+        .init_resource::<ButtonInput<KeyCode>>()
+        .insert_resource(CurrentPlayer(PlayerColor::Black))
+        .add_systems(Update, (handle_hint_input, update_hint_marker).chain());
+    app.world_mut().spawn(board);
+    let marker = app
+        .world_mut()
+        .spawn((Transform::default(), Visibility::Hidden, HintMarker))
+        .id();
+    app.update();
+    assert_eq!(
+        app.world().get::<Visibility>(marker),
+        Some(&Visibility::Hidden)
+    );
+
+    let key = InputMap::default().keys(InputAction::Hint)[0];
+    assert_eq!(key, KeyCode::KeyT);
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(key);
+    app.update();
+    assert_eq!(
+        app.world().get::<Visibility>(marker),
+        Some(&Visibility::Inherited)
+    );
+    let (row, col) = Board::position_to_coords(best);
+    let translation = app.world().get::<Transform>(marker).unwrap().translation;
+    assert_eq!(
+        (translation.x > 0.0, translation.y > 0.0),
+        (col >= 4, row < 4)
+    );
+
+    // 再按一次隐藏
+    let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keyboard.release(key);
+    keyboard.clear();
+    keyboard.press(key);
+    app.update();
+    assert_eq!(
+        app.world().get::<Visibility>(marker),
+        Some(&Visibility::Hidden)
+    );
+}
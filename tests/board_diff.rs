@@ -0,0 +1,94 @@
+// 局面差异测试 - 落子、悔棋和相同局面的差异，以及棋子渲染只替换变化的格子
+
+mod common;
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use common::insert_board_assets;
+use reversi::{
+    accessibility::MotionSettings,
+    game::{Board, BoardDiff, PlayerColor},
+    history::{HistoryPreview, MoveHistory},
+    ui::{update_pieces, BoardUI, FlipChainEvent, Piece, ToDelete},
+};
+
+#[test]
+fn move_places_one_disc_and_flips_the_rest() {
+    let before = Board::new_standard();
+    let mut after = before;
+    after.make_move(19, PlayerColor::Black);
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.placed, 1 << 19);
+    assert_eq!(diff.removed, 0);
+    assert_eq!(BoardDiff::squares(diff.flipped).collect::<Vec<_>>(), [27]);
+    assert_eq!(diff.changed().count_ones(), 2);
+
+    // 悔棋方向：落子的格子被移除，翻转的格子变回原色
+    let undo = after.diff(&before);
+    assert_eq!(undo, diff.reversed());
+    assert_eq!(undo.removed, 1 << 19);
+    assert_eq!(undo.placed, 0);
+}
+
+#[test]
+fn identical_boards_have_no_diff() {
+    let board = Board::new_standard();
+    assert!(board.diff(&board).is_empty());
+    assert_eq!(board.diff(&board), BoardDiff::default());
+}
+
+fn piece_world(board: Board) -> World {
+    let mut world = World::new();
+    insert_board_assets(&mut world);
+    world.init_resource::<MoveHistory>();
+    world.init_resource::<HistoryPreview>();
+    world.init_resource::<MotionSettings>();
+    world.init_resource::<Events<FlipChainEvent>>();
+    world.spawn(BoardUI);
+    world.spawn(board);
+    world
+}
+
+/// 按位置索引的现有棋子
+fn live_pieces(world: &mut World) -> Vec<(u8, Entity)> {
+    let mut pieces: Vec<(u8, Entity)> = world
+        .query_filtered::<(Entity, &Piece), Without<ToDelete>>()
+        .iter(world)
+        .map(|(entity, piece)| (piece.position, entity))
+        .collect();
+    pieces.sort();
+    pieces
+}
+
+#[test]
+fn piece_rendering_only_replaces_changed_squares() {
+    let mut world = piece_world(Board::new_standard());
+    world.run_system_once(update_pieces).unwrap();
+    let before = live_pieces(&mut world);
+    assert_eq!(before.len(), 4);
+
+    let mut board = Board::new_standard();
+    board.make_move(19, PlayerColor::Black);
+    let diff = Board::new_standard().diff(&board);
+    let mut query = world.query::<&mut Board>();
+    *query.single_mut(&mut world).unwrap() = board;
+    world.run_system_once(update_pieces).unwrap();
+
+    let after = live_pieces(&mut world);
+    assert_eq!(after.len(), 5);
+    for (position, entity) in &before {
+        let kept = after.contains(&(*position, *entity));
+        assert_eq!(
+            kept,
+            diff.flipped & (1 << position) == 0,
+            "square {position}"
+        );
+    }
+
+    // 只有翻转的棋子被替换
+    let replaced = world
+        .query_filtered::<(), (With<Piece>, With<ToDelete>)>()
+        .iter(&world)
+        .count();
+    assert_eq!(replaced as u32, diff.flipped.count_ones());
+}
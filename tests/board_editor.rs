@@ -0,0 +1,109 @@
+// 局面编辑测试 - 整行和整列填充、镜像、清空、交换颜色，以及不可能局面的检查
+
+use reversi::{
+    analysis::{apply_brush, validate_position, BoardBrush, GameTree, PositionProblem},
+    game::{parse_position_string, Board, MoveAction},
+    PlayerColor,
+};
+
+fn position(squares: &str) -> Board {
+    parse_position_string(&format!("{squares} X")).unwrap().0
+}
+
+#[test]
+fn fill_brushes_overwrite_a_whole_line() {
+    let board = Board::new_standard();
+    // D4所在的第4行和D列
+    let d4 = Board::notation_to_position("D4").unwrap();
+
+    let row = BoardBrush::FillRow.apply(&board, d4, PlayerColor::Black);
+    for column in 0..8 {
+        assert_eq!(
+            row.get_piece(Board::coords_to_position(3, column)),
+            Some(PlayerColor::Black)
+        );
+    }
+    assert_eq!(row.get_piece(36), Some(PlayerColor::White));
+
+    let column = BoardBrush::FillColumn.apply(&board, d4, PlayerColor::White);
+    for row in 0..8 {
+        assert_eq!(
+            column.get_piece(Board::coords_to_position(row, 3)),
+            Some(PlayerColor::White)
+        );
+    }
+    assert_eq!(column.count_pieces(PlayerColor::Black), 1);
+}
+
+#[test]
+fn whole_board_brushes() {
+    let board = position(concat!(
+        "X-------", "--------", "--------", "---OX---", "---XO---", "--------", "--------",
+        "-------O",
+    ));
+
+    let mirrored = BoardBrush::Mirror.apply(&board, 0, PlayerColor::Black);
+    assert_eq!(mirrored.get_piece(7), Some(PlayerColor::Black));
+    assert_eq!(mirrored.get_piece(56), Some(PlayerColor::White));
+
+    let swapped = BoardBrush::SwapColors.apply(&board, 0, PlayerColor::Black);
+    assert_eq!(swapped.black, board.white);
+    assert_eq!(swapped.white, board.black);
+
+    let cleared = BoardBrush::Clear.apply(&board, 0, PlayerColor::Black);
+    assert_eq!(cleared.get_empty_squares(), u64::MAX);
+    assert!(BoardBrush::ALL.iter().all(|brush| brush.needs_square()
+        == matches!(brush, BoardBrush::FillRow | BoardBrush::FillColumn)));
+}
+
+#[test]
+fn brushes_start_a_new_tree_with_the_same_side_to_move() {
+    let mut tree = GameTree::new(Board::new_standard(), PlayerColor::Black);
+    let position = tree.board().get_valid_moves_list(PlayerColor::Black)[0].position;
+    tree.play(MoveAction::Place(position)).unwrap();
+
+    let edited = apply_brush(&tree, BoardBrush::SwapColors, 0);
+    assert_eq!(edited.ply(), 0);
+    assert_eq!(edited.to_move(), PlayerColor::White);
+    assert_eq!(edited.board().black, tree.board().white);
+}
+
+#[test]
+fn validator_rejects_impossible_positions() {
+    assert_eq!(validate_position(&Board::new_standard()), Ok(()));
+    assert_eq!(
+        validate_position(&Board { black: 0, white: 0 }),
+        Err(PositionProblem::TooFewDiscs)
+    );
+
+    // 中央缺一格
+    let missing_center = position(concat!(
+        "--------", "--------", "--------", "---OXX--", "----O---", "--------", "--------",
+        "--------",
+    ));
+    assert_eq!(
+        validate_position(&missing_center),
+        Err(PositionProblem::EmptyCenter)
+    );
+
+    // H8的棋子与中央不相连
+    let island = position(concat!(
+        "--------", "--------", "--------", "---OX---", "---XO---", "--------", "--------",
+        "-------X",
+    ));
+    assert_eq!(
+        validate_position(&island),
+        Err(PositionProblem::Disconnected)
+    );
+
+    // 沿对角线相连的棋子可以出现
+    let diagonal = position(concat!(
+        "--------", "--------", "--X-----", "---OX---", "---XO---", "--------", "--------",
+        "--------",
+    ));
+    assert_eq!(validate_position(&diagonal), Ok(()));
+
+    // 填满一整行后与中央相连，不算不可能的局面
+    let filled = BoardBrush::FillRow.apply(&Board::new_standard(), 24, PlayerColor::Black);
+    assert_eq!(validate_position(&filled), Ok(()));
+}
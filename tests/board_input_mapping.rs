@@ -0,0 +1,158 @@
+// 棋盘输入坐标测试 - 屏幕坐标经相机换算到棋盘格的映射，覆盖格子边界和缩放后的窗口
+
+use bevy::{prelude::*, render::camera::CameraProjection};
+use reversi::{
+    display::{ui_scale_for_window, BoardLayout, TextSize},
+    game::Board,
+    ui::{square_at_world, square_label_text, BOARD_SIZE, SQUARE_SIZE},
+};
+
+/// 格子中心的世界坐标
+fn square_center(position: u8) -> Vec2 {
+    let row = (position / 8) as f32;
+    let col = (position % 8) as f32;
+    Vec2::new((col - 3.5) * SQUARE_SIZE, (3.5 - row) * SQUARE_SIZE)
+}
+
+/// 窗口尺寸对应的棋盘布局
+fn layout_for_window(window_size: Vec2) -> BoardLayout {
+    let ui_scale = ui_scale_for_window(window_size.x, window_size.y, TextSize::Normal);
+    BoardLayout {
+        board_size: BoardLayout::board_size_for_window(window_size.x, window_size.y, ui_scale),
+    }
+}
+
+/// 按Bevy相机的viewport_to_world_2d换算：相机位于原点，正交投影按布局缩放
+fn bevy_screen_to_world(layout: &BoardLayout, screen_position: Vec2, window_size: Vec2) -> Vec2 {
+    let mut projection = OrthographicProjection {
+        scale: layout.camera_scale(),
+        ..OrthographicProjection::default_2d()
+    };
+    projection.update(window_size.x, window_size.y);
+    let ndc = Vec2::new(
+        screen_position.x / window_size.x * 2.0 - 1.0,
+        1.0 - screen_position.y / window_size.y * 2.0,
+    );
+    projection
+        .get_clip_from_view()
+        .inverse()
+        .project_point3(ndc.extend(1.0))
+        .truncate()
+}
+
+#[test]
+fn square_centers_map_to_their_position() {
+    for position in 0..64 {
+        assert_eq!(square_at_world(square_center(position)), Some(position));
+    }
+    // A1在左上角，H8在右下角
+    assert_eq!(square_at_world(Vec2::new(-150.0, 150.0)), Some(0));
+    assert_eq!(square_at_world(Vec2::new(150.0, -150.0)), Some(63));
+}
+
+#[test]
+fn square_edges_belong_to_the_right_square() {
+    let half = BOARD_SIZE / 2.0;
+    // 左上角属于A1，右边和下边不属于棋盘
+    assert_eq!(square_at_world(Vec2::new(-half, half)), Some(0));
+    assert_eq!(square_at_world(Vec2::new(half, 0.0)), None);
+    assert_eq!(square_at_world(Vec2::new(0.0, -half)), None);
+    assert_eq!(
+        square_at_world(Vec2::new(half - 0.01, -half + 0.01)),
+        Some(63)
+    );
+
+    // 格子之间的分界线属于右边和下边的格子
+    assert_eq!(square_at_world(Vec2::new(0.0, 1.0)), Some(28));
+    assert_eq!(square_at_world(Vec2::new(-0.01, 1.0)), Some(27));
+    assert_eq!(square_at_world(Vec2::new(-1.0, 0.0)), Some(35));
+    assert_eq!(square_at_world(Vec2::new(-1.0, 0.01)), Some(27));
+}
+
+#[test]
+fn points_off_the_board_have_no_square() {
+    let half = BOARD_SIZE / 2.0;
+    for point in [
+        Vec2::new(-half - 0.01, 0.0),
+        Vec2::new(0.0, half + 0.01),
+        Vec2::new(half + SQUARE_SIZE, 0.0),
+        Vec2::new(0.0, -half - SQUARE_SIZE),
+        Vec2::splat(1000.0),
+    ] {
+        assert_eq!(square_at_world(point), None, "{point}");
+    }
+}
+
+#[test]
+fn layout_mapping_matches_bevy_camera_in_resized_windows() {
+    for window_size in [
+        Vec2::new(400.0, 600.0),
+        Vec2::new(1280.0, 720.0),
+        Vec2::new(1920.0, 1080.0),
+        Vec2::new(360.0, 640.0),
+        Vec2::new(200.0, 200.0),
+    ] {
+        let layout = layout_for_window(window_size);
+        for screen_position in [
+            Vec2::ZERO,
+            window_size / 2.0,
+            window_size,
+            Vec2::new(window_size.x * 0.3, window_size.y * 0.7),
+        ] {
+            let expected = bevy_screen_to_world(&layout, screen_position, window_size);
+            let actual = layout.screen_to_world(screen_position, window_size);
+            assert!(
+                actual.abs_diff_eq(expected, 1e-3),
+                "{window_size}: {screen_position} -> {actual}, expected {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn clicking_square_centers_on_screen_hits_every_square() {
+    for window_size in [
+        Vec2::new(400.0, 600.0),
+        Vec2::new(1280.0, 720.0),
+        Vec2::new(360.0, 640.0),
+    ] {
+        let layout = layout_for_window(window_size);
+        let pixels_per_square = layout.board_size / 8.0;
+        let board_top_left = (window_size - Vec2::splat(layout.board_size)) / 2.0;
+
+        for position in 0..64u8 {
+            let row = (position / 8) as f32;
+            let col = (position % 8) as f32;
+            let center = board_top_left + (Vec2::new(col, row) + 0.5) * pixels_per_square;
+            let world = layout.screen_to_world(center, window_size);
+            assert_eq!(square_at_world(world), Some(position), "{window_size}");
+
+            // 离格子边缘一个像素以内仍在同一格
+            let inside = pixels_per_square / 2.0 - 1.0;
+            for offset in [Vec2::new(-inside, -inside), Vec2::new(inside, inside)] {
+                let world = layout.screen_to_world(center + offset, window_size);
+                assert_eq!(square_at_world(world), Some(position), "{window_size}");
+            }
+        }
+
+        // 棋盘外一个像素不对应任何格子
+        let outside = board_top_left - Vec2::ONE;
+        assert_eq!(
+            square_at_world(layout.screen_to_world(outside, window_size)),
+            None
+        );
+    }
+}
+
+#[test]
+fn square_labels_show_index_and_notation() {
+    assert_eq!(square_label_text(0), "0 A1");
+    assert_eq!(square_label_text(37), "37 F5");
+    assert_eq!(square_label_text(63), "63 H8");
+    for position in 0..64 {
+        let label = square_label_text(position);
+        let (index, notation) = label.split_once(' ').unwrap();
+        assert_eq!(index.parse::<u8>(), Ok(position));
+        assert_eq!(Board::notation_to_position(notation), Some(position));
+    }
+}
@@ -0,0 +1,135 @@
+// 局面不变量测试 - 参考翻转实现与位运算一致、各类错误局面被发现、复现信息可以解析，以及不一致时系统panic
+
+use bevy::prelude::*;
+use reversi::{
+    game::{
+        assert_board_invariants, check_log, check_move, parse_position_string, reference_flips,
+        reproduction, Board, GameLog, InvariantViolation, LegalMovesCache, MoveAction,
+    },
+    PlayerColor,
+};
+
+fn square(notation: &str) -> u8 {
+    Board::notation_to_position(notation).unwrap()
+}
+
+/// 从标准开局每次走编号最大的合法走法，直到终局
+fn play_out() -> GameLog {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    while !log.is_game_over() {
+        let action = match log.board().get_valid_moves(log.to_move()) {
+            0 => MoveAction::Pass,
+            moves => MoveAction::Place(63 - moves.leading_zeros() as u8),
+        };
+        log.apply(log.next_command(action)).unwrap();
+    }
+    log
+}
+
+#[test]
+fn bitboard_rules_match_the_reference_in_a_full_game() {
+    let log = play_out();
+    assert!(log.commands().len() > 20);
+    assert_eq!(check_log(&log), Ok(()));
+
+    let board = Board::new_standard();
+    for candidate in board.get_valid_moves_list(PlayerColor::Black) {
+        let expected: u64 = board
+            .flipped_rays(candidate.position, PlayerColor::Black)
+            .into_iter()
+            .flatten()
+            .map(|disc| 1u64 << disc)
+            .sum();
+        assert_eq!(
+            reference_flips(&board, candidate.position, PlayerColor::Black),
+            expected
+        );
+    }
+}
+
+#[test]
+fn broken_positions_are_reported() {
+    let before = Board::new_standard();
+    let mut after = before;
+    after.make_move(square("D3"), PlayerColor::Black);
+    let play = MoveAction::Place(square("D3"));
+    assert_eq!(
+        check_move(&before, &after, PlayerColor::Black, play),
+        Ok(())
+    );
+
+    let mut overlapping = after;
+    overlapping.black |= overlapping.white;
+    assert!(matches!(
+        check_move(&before, &overlapping, PlayerColor::Black, play),
+        Err(InvariantViolation::OverlappingDiscs { .. })
+    ));
+
+    // 翻转的棋子少了一枚
+    let mut unflipped = after;
+    let d4 = 1u64 << square("D4");
+    unflipped.black &= !d4;
+    unflipped.white |= d4;
+    assert_eq!(
+        check_move(&before, &unflipped, PlayerColor::Black, play),
+        Err(InvariantViolation::FlipMismatch {
+            expected: d4,
+            actual: 0
+        })
+    );
+
+    assert_eq!(
+        check_move(&before, &after, PlayerColor::Black, MoveAction::Pass),
+        Err(InvariantViolation::ChangedOnPass)
+    );
+    assert_eq!(
+        check_move(
+            &before,
+            &after,
+            PlayerColor::Black,
+            MoveAction::Place(square("D4"))
+        ),
+        Err(InvariantViolation::OccupiedSquare {
+            position: square("D4")
+        })
+    );
+}
+
+#[test]
+fn reproduction_can_be_parsed_back() {
+    let board = Board::new_standard();
+    let text = reproduction(&board, PlayerColor::White, MoveAction::Place(square("F5")));
+    assert!(text.ends_with("\" move F5"));
+
+    let position = text
+        .strip_prefix("position \"")
+        .and_then(|rest| rest.split('"').next())
+        .unwrap();
+    assert_eq!(
+        parse_position_string(position),
+        Some((board, PlayerColor::White))
+    );
+}
+
+#[test]
+fn consistent_games_pass_the_system_check() {
+    let mut app = App::new();
+    app.init_resource::<LegalMovesCache>()
+        .add_systems(Update, assert_board_invariants);
+    let log = play_out();
+    app.world_mut().spawn((log.board(), log));
+    app.update();
+}
+
+#[test]
+#[should_panic(expected = "Board invariant violated: board component differs from the game log")]
+fn out_of_sync_board_panics() {
+    let mut app = App::new();
+    app.init_resource::<LegalMovesCache>()
+        .add_systems(Update, assert_board_invariants);
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    log.apply(log.next_command(MoveAction::Place(square("D3"))))
+        .unwrap();
+    app.world_mut().spawn((Board::new_standard(), log));
+    app.update();
+}
@@ -0,0 +1,165 @@
+// 棋盘质感测试 - 主题中的风格、纹理生成、棋子投影，以及精简资源档回退到Flat
+
+mod common;
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use common::insert_board_assets;
+use reversi::{
+    config_assets::BoardTheme,
+    game::Board,
+    platform::AssetTier,
+    ui::{
+        board_texture_image, restyle_board, setup_board_ui, sync_pieces, BoardColors, BoardLine,
+        BoardLineFeather, BoardStyle, BoardTexture, BoardUI, Piece, PieceShadow, ToDelete,
+        BOARD_TEXTURE_SIZE,
+    },
+};
+
+fn board_world(style: BoardStyle) -> World {
+    let mut world = World::new();
+    insert_board_assets(&mut world);
+    world.insert_resource(BoardColors { style, ..default() });
+    world.run_system_once(setup_board_ui).unwrap();
+    world
+}
+
+fn spawn_standard_pieces(world: &mut World) {
+    world
+        .run_system_once(
+            |mut commands: Commands,
+             root: Query<Entity, With<BoardUI>>,
+             pieces: Query<(Entity, &Piece, &ChildOf), Without<ToDelete>>,
+             colors: Res<BoardColors>,
+             mut meshes: ResMut<Assets<Mesh>>,
+             mut materials: ResMut<Assets<ColorMaterial>>| {
+                sync_pieces(
+                    &mut commands,
+                    root.single().unwrap(),
+                    &pieces,
+                    &Board::new_standard(),
+                    &colors,
+                    &mut meshes,
+                    &mut materials,
+                );
+            },
+        )
+        .unwrap();
+}
+
+fn visible<C: Component>(world: &mut World) -> Vec<bool> {
+    world
+        .query_filtered::<&Visibility, With<C>>()
+        .iter(world)
+        .map(|visibility| *visibility != Visibility::Hidden)
+        .collect()
+}
+
+#[test]
+fn theme_style_defaults_to_felt_and_parses() {
+    assert_eq!(BoardTheme::default().style, BoardStyle::Felt);
+    let theme = BoardTheme::from_ron("(style: Wood)").unwrap();
+    assert_eq!(theme.style, BoardStyle::Wood);
+    assert_eq!(theme.colors().style, BoardStyle::Wood);
+}
+
+#[test]
+fn compact_tier_falls_back_to_flat() {
+    let theme = BoardTheme::from_ron("(style: Wood)").unwrap();
+    assert_eq!(theme.colors_for(AssetTier::Full).style, BoardStyle::Wood);
+    assert_eq!(theme.colors_for(AssetTier::Compact).style, BoardStyle::Flat);
+    // 回退只影响质感，不影响颜色
+    assert_eq!(
+        theme.colors_for(AssetTier::Compact).board_color,
+        theme.colors().board_color
+    );
+}
+
+#[test]
+fn textures_are_generated_for_textured_styles() {
+    assert!(board_texture_image(BoardStyle::Flat).is_none());
+
+    let felt = board_texture_image(BoardStyle::Felt).unwrap();
+    let wood = board_texture_image(BoardStyle::Wood).unwrap();
+    for image in [&felt, &wood] {
+        assert_eq!(image.width(), BOARD_TEXTURE_SIZE);
+        assert_eq!(image.height(), BOARD_TEXTURE_SIZE);
+        // 纹理半透明地叠加在格子上
+        let data = image.data.as_ref().unwrap();
+        assert!(data.chunks(4).all(|texel| texel[3] > 0 && texel[3] < 128));
+    }
+    assert_ne!(felt.data, wood.data);
+
+    // 纹理是确定的，每次生成相同
+    assert_eq!(
+        board_texture_image(BoardStyle::Wood).unwrap().data,
+        wood.data
+    );
+}
+
+#[test]
+fn textured_board_has_shadows_and_soft_lines() {
+    let mut world = board_world(BoardStyle::Felt);
+    spawn_standard_pieces(&mut world);
+
+    assert_eq!(visible::<BoardTexture>(&mut world), vec![true]);
+    assert_eq!(visible::<PieceShadow>(&mut world).len(), 4);
+    let feathers = visible::<BoardLineFeather>(&mut world);
+    assert_eq!(feathers.len(), 18);
+    assert!(feathers.iter().all(|visible| *visible));
+}
+
+#[test]
+fn flat_board_has_no_texture_or_shadows() {
+    let mut world = board_world(BoardStyle::Flat);
+    spawn_standard_pieces(&mut world);
+
+    assert_eq!(visible::<BoardTexture>(&mut world), vec![false]);
+    assert!(visible::<PieceShadow>(&mut world).is_empty());
+    assert!(visible::<BoardLineFeather>(&mut world)
+        .iter()
+        .all(|visible| !visible));
+}
+
+#[test]
+fn switching_style_restyles_the_board() {
+    let mut world = board_world(BoardStyle::Felt);
+    spawn_standard_pieces(&mut world);
+    let line_widths = |world: &mut World| -> Vec<f32> {
+        world
+            .query_filtered::<&Sprite, With<BoardLine>>()
+            .iter(world)
+            .map(|sprite| sprite.custom_size.unwrap().min_element())
+            .collect()
+    };
+    let textured_width = line_widths(&mut world)[0];
+    // 注册后先运行一次，之后的运行才能看到棋盘颜色的变化
+    let restyle = world.register_system(restyle_board);
+    world.run_system(restyle).unwrap();
+
+    world.resource_mut::<BoardColors>().style = BoardStyle::Flat;
+    world.run_system(restyle).unwrap();
+    assert_eq!(visible::<BoardTexture>(&mut world), vec![false]);
+    assert!(visible::<PieceShadow>(&mut world).iter().all(|v| !v));
+    assert!(visible::<BoardLineFeather>(&mut world).iter().all(|v| !v));
+    let flat_widths = line_widths(&mut world);
+    assert!(flat_widths.iter().all(|width| *width > textured_width));
+
+    // 换成木纹时重新生成纹理并恢复显示
+    world.resource_mut::<BoardColors>().style = BoardStyle::Wood;
+    world.run_system(restyle).unwrap();
+    assert_eq!(visible::<BoardTexture>(&mut world), vec![true]);
+    assert!(visible::<PieceShadow>(&mut world).iter().all(|v| *v));
+    let texture = world
+        .query::<(&BoardTexture, &Sprite)>()
+        .single(&world)
+        .unwrap();
+    assert_eq!(texture.0.style, BoardStyle::Wood);
+    let image = world
+        .resource::<Assets<Image>>()
+        .get(&texture.1.image)
+        .unwrap();
+    assert_eq!(
+        image.data,
+        board_texture_image(BoardStyle::Wood).unwrap().data
+    );
+}
@@ -0,0 +1,168 @@
+// 棋盘对称测试 - 8种变换逐格核对、群运算性质、规范形式和合法走法的对应关系
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use reversi::game::{Board, PlayerColor, Symmetry};
+
+/// 从标准初始局面随机走若干步得到的局面
+fn random_positions(count: usize) -> Vec<(Board, PlayerColor)> {
+    let mut rng = StdRng::seed_from_u64(446);
+    let mut positions = Vec::new();
+    while positions.len() < count {
+        let mut board = Board::new_standard();
+        let mut player = PlayerColor::Black;
+        for _ in 0..rng.gen_range(0..60) {
+            let moves = board.get_valid_moves_list(player);
+            if moves.is_empty() {
+                if board.is_game_over() {
+                    break;
+                }
+            } else {
+                board.make_move(moves[rng.gen_range(0..moves.len())].position, player);
+            }
+            player = player.opposite();
+        }
+        positions.push((board, player));
+    }
+    positions
+}
+
+#[test]
+fn bit_transforms_match_square_mapping() {
+    for symmetry in Symmetry::ALL {
+        for position in 0..64u8 {
+            assert_eq!(
+                symmetry.apply_bits(1 << position),
+                1 << symmetry.apply_position(position),
+                "{symmetry:?} at {}",
+                Board::position_to_notation(position)
+            );
+        }
+    }
+}
+
+#[test]
+fn transforms_are_distinct_permutations() {
+    let mut images = Vec::new();
+    for symmetry in Symmetry::ALL {
+        let mut targets: Vec<u8> = (0..64).map(|p| symmetry.apply_position(p)).collect();
+        images.push(targets.clone());
+        targets.sort_unstable();
+        assert_eq!(targets, (0..64).collect::<Vec<u8>>(), "{symmetry:?}");
+    }
+    images.sort();
+    images.dedup();
+    assert_eq!(images.len(), 8);
+}
+
+#[test]
+fn inverse_undoes_every_transform() {
+    for symmetry in Symmetry::ALL {
+        for position in 0..64u8 {
+            assert_eq!(
+                symmetry
+                    .inverse()
+                    .apply_position(symmetry.apply_position(position)),
+                position
+            );
+        }
+    }
+}
+
+#[test]
+fn transforms_are_closed_under_composition() {
+    for first in Symmetry::ALL {
+        for second in Symmetry::ALL {
+            let composed: Vec<u8> = (0..64)
+                .map(|p| second.apply_position(first.apply_position(p)))
+                .collect();
+            assert!(
+                Symmetry::ALL.into_iter().any(|symmetry| {
+                    (0..64).all(|p| symmetry.apply_position(p) == composed[p as usize])
+                }),
+                "{first:?} then {second:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn corners_map_to_corners() {
+    let corners = [0u8, 7, 56, 63];
+    for symmetry in Symmetry::ALL {
+        for corner in corners {
+            assert!(corners.contains(&symmetry.apply_position(corner)));
+        }
+    }
+    assert_eq!(Symmetry::Rotate90.apply_position(0), 7);
+    assert_eq!(Symmetry::FlipDiagonal.apply_position(1), 8);
+}
+
+#[test]
+fn opening_moves_are_symmetric() {
+    // 黑方的四个开局走法互为对称
+    let start = Board::new_standard();
+    let mut after: Vec<Board> = ["F5", "E6", "D3", "C4"]
+        .iter()
+        .map(|notation| {
+            let mut board = start;
+            board.make_move(
+                Board::notation_to_position(notation).unwrap(),
+                PlayerColor::Black,
+            );
+            board
+        })
+        .collect();
+    for board in &after {
+        assert!(board.is_symmetric_to(&after[0]));
+    }
+    after
+        .iter_mut()
+        .for_each(|board| *board = board.canonical_form());
+    after.dedup();
+    assert_eq!(after.len(), 1);
+
+    // 初始局面在4种变换下不变
+    let fixed = start
+        .symmetries()
+        .iter()
+        .filter(|board| **board == start)
+        .count();
+    assert_eq!(fixed, 4);
+}
+
+#[test]
+fn canonical_form_is_shared_by_all_symmetries() {
+    for (board, _) in random_positions(200) {
+        let canonical = board.canonical_form();
+        assert_eq!(board.transformed(board.canonical_symmetry()), canonical);
+        for transformed in board.symmetries() {
+            assert_eq!(transformed.canonical_form(), canonical);
+            assert_eq!(
+                transformed.count_pieces(PlayerColor::Black),
+                board.count_pieces(PlayerColor::Black)
+            );
+        }
+    }
+}
+
+#[test]
+fn legal_moves_follow_the_transform() {
+    for (board, player) in random_positions(200) {
+        for symmetry in Symmetry::ALL {
+            let transformed = board.transformed(symmetry);
+            assert_eq!(
+                transformed.get_valid_moves(player),
+                symmetry.apply_bits(board.get_valid_moves(player))
+            );
+
+            // 落子后再变换与变换后落子得到同一局面
+            for chess_move in board.get_valid_moves_list(player) {
+                let mut played = board;
+                played.make_move(chess_move.position, player);
+                let mut transformed_played = transformed;
+                transformed_played.make_move(symmetry.apply_position(chess_move.position), player);
+                assert_eq!(played.transformed(symmetry), transformed_played);
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// 混乱难度测试 - 按种子随机走合法的棋、同样的种子复现同样的对局、不搜索立即走出，以及名称解析和难度说明
+
+use reversi::{
+    ai::{seeded_random_move, AiPlayer},
+    localization::{TextKey, ENGLISH_TEXTS},
+    stats::{DifficultyTier, PlayerStats},
+    AiDifficulty, Board, GameLog, MoveAction, PlayerColor,
+};
+use std::collections::HashSet;
+
+/// 双方都用混乱难度下完一局
+fn play_game(seed: u64) -> GameLog {
+    let difficulty = AiDifficulty::Chaos(seed);
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    while !log.is_game_over() {
+        let action = difficulty
+            .get_ai_move(&log.board(), log.to_move())
+            .map_or(MoveAction::Pass, |chosen| {
+                MoveAction::Place(chosen.position)
+            });
+        log.apply(log.next_command(action)).unwrap();
+    }
+    log
+}
+
+#[test]
+fn moves_are_legal_and_reproducible_from_the_seed() {
+    let log = play_game(42);
+    assert!(log.is_game_over());
+    assert_eq!(play_game(42), log);
+    assert_ne!(play_game(43).commands(), log.commands());
+}
+
+#[test]
+fn every_legal_move_can_be_chosen() {
+    let board = Board::new_standard();
+    let chosen: HashSet<u8> = (0..200)
+        .filter_map(|seed| seeded_random_move(&board, PlayerColor::Black, seed))
+        .map(|chosen| chosen.position)
+        .collect();
+    assert_eq!(chosen.len(), 4);
+    assert!(chosen
+        .iter()
+        .all(|&position| board.is_valid_move(position, PlayerColor::Black)));
+
+    // 没有合法走法时不走棋
+    let full = Board {
+        black: u64::MAX,
+        white: 0,
+    };
+    assert_eq!(seeded_random_move(&full, PlayerColor::White, 1), None);
+}
+
+#[test]
+fn chaos_plays_instantly_without_searching() {
+    let difficulty = AiDifficulty::Chaos(7);
+    let params = difficulty.get_search_params();
+    assert!(params.random_moves);
+    assert_eq!(params.max_depth, 0);
+    assert!(!AiDifficulty::Beginner.get_search_params().random_moves);
+
+    let board = Board::new_standard();
+    let ai = AiPlayer::new(difficulty, PlayerColor::Black);
+    assert_eq!(
+        ai.instant_move(&board),
+        seeded_random_move(&board, PlayerColor::Black, 7)
+    );
+}
+
+#[test]
+fn chaos_is_named_and_described_below_beginner() {
+    assert_eq!(
+        AiDifficulty::from_name("Chaos:42"),
+        Some(AiDifficulty::Chaos(42))
+    );
+    assert!(matches!(
+        AiDifficulty::from_name("chaos"),
+        Some(AiDifficulty::Chaos(_))
+    ));
+    assert_eq!(AiDifficulty::from_name("chaos:many"), None);
+
+    let chaos = AiDifficulty::Chaos(0);
+    assert!(chaos.estimated_elo() < AiDifficulty::Beginner.estimated_elo());
+    assert_eq!(DifficultyTier::from(chaos), DifficultyTier::Chaos);
+
+    let stats = PlayerStats::default();
+    let key = stats.difficulty_subtitle_key(chaos);
+    assert_eq!(key, TextKey::DifficultyChaosSubtitleFormat);
+    assert_eq!(
+        ENGLISH_TEXTS.format(key, &stats.difficulty_subtitle_args(chaos).0),
+        "Random moves · instant · ~200 Elo"
+    );
+    assert_eq!(
+        stats.difficulty_subtitle_key(AiDifficulty::Beginner),
+        TextKey::DifficultySubtitleFormat
+    );
+}
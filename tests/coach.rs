@@ -0,0 +1,41 @@
+// 教练点评测试 - 占角、X位和不合法走法
+
+use reversi::coach::{coach_move, CoachComment};
+use reversi::game::{Board, PlayerColor};
+
+fn bit(row: usize, col: usize) -> u64 {
+    1u64 << Board::coords_to_position(row, col)
+}
+
+#[test]
+fn taking_a_corner_is_praised() {
+    let board = Board {
+        black: bit(4, 4),
+        white: bit(1, 1) | bit(2, 2) | bit(3, 3),
+    };
+    assert_eq!(
+        coach_move(&board, Board::coords_to_position(0, 0), PlayerColor::Black),
+        Some(CoachComment::TookCorner)
+    );
+}
+
+#[test]
+fn x_square_next_to_empty_corner_is_flagged() {
+    let board = Board {
+        black: bit(3, 3),
+        white: bit(2, 2),
+    };
+    assert_eq!(
+        coach_move(&board, Board::coords_to_position(1, 1), PlayerColor::Black),
+        Some(CoachComment::RiskyXSquare)
+    );
+}
+
+#[test]
+fn illegal_move_has_no_comment() {
+    let board = Board::new_standard();
+    assert_eq!(coach_move(&board, 0, PlayerColor::Black), None);
+
+    let opening = board.get_valid_moves_list(PlayerColor::Black)[0].position;
+    assert!(coach_move(&board, opening, PlayerColor::Black).is_some());
+}
@@ -0,0 +1,97 @@
+// 测试公共代码 - 无窗口测试共用的World初始化、进入对局界面和实体计数
+//
+// 各测试文件通过`mod common;`引入，只用到其中一部分函数，未使用的函数不报警告
+
+#![allow(dead_code)]
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    accessibility::MotionSettings,
+    assist::CornerWarningState,
+    coach::CoachState,
+    fonts::FontAssets,
+    history::{HistoryPreview, MoveHistory},
+    input::{InputGate, PlayerSeats, Premove},
+    localization::LanguageSettings,
+    plugin::{gameplay::setup_game, BackToDifficultyEvent, GameSession, NewGameRequest},
+    practice::SelectedOpening,
+    preferences::Preferences,
+    sandbox::SandboxMode,
+    series::MatchSeries,
+    stats::PlayerStats,
+    ui::{
+        setup_board_ui, setup_game_ui, update_pieces, BoardColors, CurrentPlayer, FlipChainEvent,
+        RestartGameEvent, UiState,
+    },
+    undo::UndoState,
+    AiDifficulty, GameState, PlayerColor, SelectedDifficulty,
+};
+
+/// 渲染棋盘需要的颜色和资源集合
+pub fn insert_board_assets(world: &mut World) {
+    world.init_resource::<BoardColors>();
+    world.init_resource::<Assets<Image>>();
+    world.init_resource::<Assets<Mesh>>();
+    world.init_resource::<Assets<ColorMaterial>>();
+}
+
+/// 生成界面文本需要的语言设置和字体（字体为空句柄）
+pub fn insert_fonts(world: &mut World) {
+    world.init_resource::<LanguageSettings>();
+    world.insert_resource(FontAssets {
+        default_font: Handle::default(),
+        chinese_font: Handle::default(),
+    });
+}
+
+/// 进入对局界面需要的全部资源，处于Playing状态
+pub fn game_world() -> World {
+    let mut world = World::new();
+    insert_board_assets(&mut world);
+    insert_fonts(&mut world);
+    world.init_resource::<NewGameRequest>();
+    world.init_resource::<SelectedOpening>();
+    world.init_resource::<SandboxMode>();
+    world.init_resource::<MatchSeries>();
+    world.insert_resource(SelectedDifficulty(AiDifficulty::Intermediate));
+    world.init_resource::<PlayerStats>();
+    world.init_resource::<Preferences>();
+    world.init_resource::<CornerWarningState>();
+    world.init_resource::<InputGate>();
+    world.init_resource::<Premove>();
+    world.init_resource::<PlayerSeats>();
+    world.init_resource::<UiState>();
+    world.init_resource::<CoachState>();
+    world.init_resource::<UndoState>();
+    world.init_resource::<MoveHistory>();
+    world.init_resource::<HistoryPreview>();
+    world.init_resource::<MotionSettings>();
+    world.insert_resource(CurrentPlayer(PlayerColor::Black));
+    world.insert_resource(State::new(GameState::Playing));
+    world.init_resource::<NextState<GameState>>();
+    world.init_resource::<Events<RestartGameEvent>>();
+    world.init_resource::<Events<BackToDifficultyEvent>>();
+    world.init_resource::<Events<FlipChainEvent>>();
+    world
+}
+
+/// 进入对局界面：按插件中的顺序创建棋盘根实体、界面、对局和棋子
+pub fn enter_game(world: &mut World) {
+    world.run_system_once(setup_board_ui).unwrap();
+    world.run_system_once(setup_game_ui).unwrap();
+    world.run_system_once(setup_game).unwrap();
+    world.run_system_once(update_pieces).unwrap();
+}
+
+/// 带有指定组件的实体数
+pub fn count<C: Component>(world: &mut World) -> usize {
+    world.query_filtered::<(), With<C>>().iter(world).count()
+}
+
+/// 当前的对局实体
+pub fn session(world: &mut World) -> Entity {
+    world
+        .query_filtered::<Entity, With<GameSession>>()
+        .single(world)
+        .unwrap()
+}
@@ -0,0 +1,35 @@
+// 配置资源测试 - 随游戏发布的评估配置和主题文件与内置默认值一致
+
+use reversi::ai::evaluation::EvalConfig;
+use reversi::config_assets::BoardTheme;
+
+#[test]
+fn bundled_eval_config_matches_default() {
+    let contents = include_str!("../assets/ai/standard.eval.ron");
+    assert_eq!(
+        EvalConfig::from_ron(contents).unwrap(),
+        EvalConfig::default()
+    );
+}
+
+#[test]
+fn bundled_theme_matches_default() {
+    let contents = include_str!("../assets/themes/classic.theme.ron");
+    assert_eq!(
+        BoardTheme::from_ron(contents).unwrap(),
+        BoardTheme::default()
+    );
+}
+
+#[test]
+fn theme_fields_fall_back_to_default() {
+    let theme = BoardTheme::from_ron("(line_color: (1.0, 0.0, 0.0, 1.0))").unwrap();
+    assert_eq!(theme.line_color, [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(theme.board_color, BoardTheme::default().board_color);
+}
+
+#[test]
+fn bundled_dark_theme_matches_builtin() {
+    let contents = include_str!("../assets/themes/dark.theme.ron");
+    assert_eq!(BoardTheme::from_ron(contents).unwrap(), BoardTheme::dark());
+}
@@ -0,0 +1,153 @@
+// 让角提醒测试 - 只提醒这步新让出的角，对话框按钮只在同一按钮上按下并松开时生效（手柄确认立即生效）
+
+use bevy::{
+    ecs::system::RunSystemOnce,
+    input::touch::{touch_screen_input_system, TouchPhase},
+    prelude::*,
+};
+use reversi::{
+    assist::{corner_conceded, CornerWarningState, PendingCornerWarning},
+    game::{
+        parse_position_string, Board, GameLog, MoveAction, MoveCommand, PlayerColor, CORNER_MASK,
+    },
+    input::InputGate,
+    preferences::Preferences,
+    ui::{handle_corner_warning_buttons, CornerWarningButton, CornerWarningChoice},
+};
+
+/// 白方走之前就能占a1；黑方走g8会让白方可以占h8
+const POSITION: &str = concat!(
+    "-XO-----",
+    "--------",
+    "--------",
+    "---OX---",
+    "---XO---",
+    "--------",
+    "--------",
+    "---OXO-- X",
+);
+
+#[test]
+fn warns_only_about_newly_opened_corners() {
+    let (board, player) = parse_position_string(POSITION).unwrap();
+    let corners = board.get_valid_moves(player.opposite()) & CORNER_MASK;
+    assert_eq!(corners, 1, "white can already take a1");
+
+    // 中央的走法不新让出角，对手原本就能占的a1不再提醒
+    assert!(board.is_valid_move(19, player));
+    assert_eq!(corner_conceded(&board, 19, player), None);
+
+    // g8让白方可以占h8
+    assert!(board.is_valid_move(62, player));
+    assert_eq!(corner_conceded(&board, 62, player), Some(63));
+}
+
+/// 按钮中心在窗口中的位置，窗口缩放比例为1
+const BUTTON_CENTER: Vec2 = Vec2::new(300.0, 200.0);
+
+fn dialog_world() -> (World, Entity) {
+    let mut world = World::new();
+    world.insert_resource(CornerWarningState {
+        pending: Some(PendingCornerWarning {
+            position: 19,
+            corner: 0,
+            opponent: PlayerColor::White,
+        }),
+    });
+    world.init_resource::<Preferences>();
+    world.init_resource::<InputGate>();
+    world.init_resource::<Events<MoveCommand>>();
+    world.init_resource::<ButtonInput<MouseButton>>();
+    world.init_resource::<Touches>();
+    world.init_resource::<Events<TouchInput>>();
+    world.spawn(Window::default());
+    world.spawn(GameLog::new(Board::new_standard(), PlayerColor::Black));
+    let button = world
+        .spawn((
+            Interaction::None,
+            ComputedNode {
+                size: Vec2::new(220.0, 40.0),
+                ..ComputedNode::DEFAULT
+            },
+            GlobalTransform::from_translation(BUTTON_CENTER.extend(0.0)),
+            CornerWarningButton(CornerWarningChoice::PlayAnyway),
+        ))
+        .id();
+    (world, button)
+}
+
+fn sent_moves(world: &World) -> Vec<MoveCommand> {
+    let events = world.resource::<Events<MoveCommand>>();
+    events.get_cursor().read(events).copied().collect()
+}
+
+/// 发送一个触摸事件并交给Bevy的触摸输入系统处理，处理后清空事件，避免下一次重复读取
+fn touch(world: &mut World, phase: TouchPhase, position: Vec2) {
+    let window = world
+        .query_filtered::<Entity, With<Window>>()
+        .single(world)
+        .unwrap();
+    world.send_event(TouchInput {
+        phase,
+        position,
+        window,
+        force: None,
+        id: 0,
+    });
+    world.run_system_once(touch_screen_input_system).unwrap();
+    world.resource_mut::<Events<TouchInput>>().clear();
+}
+
+#[test]
+fn dialog_buttons_fire_only_when_released_over_the_button() {
+    let (mut world, button) = dialog_world();
+    let handle = world.register_system(handle_corner_warning_buttons);
+    // 与UI焦点系统相同：手指按住时为Pressed，抬起后直接变为None
+    let interact = |world: &mut World, interaction: Interaction| {
+        *world.get_mut::<Interaction>(button).unwrap() = interaction;
+        world.run_system(handle).unwrap();
+    };
+
+    // 按下后移出按钮再抬起视为放弃
+    touch(&mut world, TouchPhase::Started, BUTTON_CENTER);
+    interact(&mut world, Interaction::Pressed);
+    touch(&mut world, TouchPhase::Moved, Vec2::new(600.0, 500.0));
+    touch(&mut world, TouchPhase::Ended, Vec2::new(600.0, 500.0));
+    interact(&mut world, Interaction::None);
+    assert!(world.resource::<CornerWarningState>().pending.is_some());
+    assert!(sent_moves(&world).is_empty());
+
+    // 在按钮上按下并抬起才落子
+    touch(&mut world, TouchPhase::Started, BUTTON_CENTER);
+    interact(&mut world, Interaction::Pressed);
+    assert!(world.resource::<CornerWarningState>().pending.is_some());
+    touch(
+        &mut world,
+        TouchPhase::Moved,
+        BUTTON_CENTER + Vec2::new(20.0, 5.0),
+    );
+    touch(
+        &mut world,
+        TouchPhase::Ended,
+        BUTTON_CENTER + Vec2::new(20.0, 5.0),
+    );
+    interact(&mut world, Interaction::None);
+    assert!(world.resource::<CornerWarningState>().pending.is_none());
+    let moves = sent_moves(&world);
+    assert_eq!(moves.len(), 1);
+    assert_eq!(moves[0].action, MoveAction::Place(19));
+}
+
+#[test]
+fn gamepad_confirm_fires_immediately() {
+    let (mut world, button) = dialog_world();
+
+    // 手柄确认只把按钮设为Pressed，没有按住的指针
+    *world.get_mut::<Interaction>(button).unwrap() = Interaction::Pressed;
+    world
+        .run_system_once(handle_corner_warning_buttons)
+        .unwrap();
+
+    assert!(world.resource::<CornerWarningState>().pending.is_none());
+    assert_eq!(sent_moves(&world).len(), 1);
+}
@@ -0,0 +1,102 @@
+// 演示对局解说测试 - 走棋、占角和终局的解说文本，跟随命令日志增量更新、换局时重置，以及只保留最新的几行
+
+use reversi::{
+    demo::{move_commentary, CommentaryFeed, COMMENTARY_LINES},
+    game::{parse_position_string, Board, GameLog, MoveAction, PlayerColor},
+    localization::{TextKey, CHINESE_TEXTS, ENGLISH_TEXTS},
+};
+
+/// 从标准开局按坐标依次走棋
+fn standard_game(moves: &[&str]) -> GameLog {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    for notation in moves {
+        let position = Board::notation_to_position(notation).unwrap();
+        log.apply(log.next_command(MoveAction::Place(position)))
+            .unwrap();
+    }
+    log
+}
+
+#[test]
+fn corner_move_is_described_in_both_languages() {
+    // 黑方在A1落子，翻转B1和C1两枚白子，白方没有棋子，对局结束
+    let (board, player) = parse_position_string(concat!(
+        "-OOX----",
+        "--------",
+        "--------",
+        "--------",
+        "--------",
+        "--------",
+        "--------",
+        "-------- X",
+    ))
+    .unwrap();
+    let log = GameLog::new(board, player);
+    let lines = move_commentary(&board, &log.next_command(MoveAction::Place(0)));
+    let english: Vec<_> = lines
+        .iter()
+        .map(|(key, args)| ENGLISH_TEXTS.format(*key, &args.0))
+        .collect();
+    assert_eq!(
+        english,
+        [
+            "1. Black plays A1, flipping 2",
+            "Black takes the A1 corner!",
+            "Game over: Black 4 – White 0",
+        ]
+    );
+    let (key, args) = &lines[1];
+    assert_eq!(CHINESE_TEXTS.format(*key, &args.0), "黑棋占据了A1角！");
+}
+
+#[test]
+fn feed_follows_the_log_and_restarts_with_a_new_game() {
+    let mut feed = CommentaryFeed::default();
+    assert!(feed.sync(&standard_game(&["D3", "C5"])));
+    assert!(!feed.sync(&standard_game(&["D3", "C5"])));
+    assert!(feed.sync(&standard_game(&["D3", "C5", "F6"])));
+    let moves: Vec<_> = feed
+        .lines()
+        .filter(|(key, _)| *key == TextKey::CommentaryMoveFormat)
+        .map(|(_, args)| ENGLISH_TEXTS.format(TextKey::CommentaryMoveFormat, &args.0))
+        .collect();
+    assert_eq!(
+        moves,
+        [
+            "1. Black plays D3, flipping 1",
+            "2. White plays C5, flipping 1",
+            "3. Black plays F6, flipping 1",
+        ]
+    );
+
+    // 命令变少说明换了一局，解说从头开始
+    assert!(feed.sync(&standard_game(&["F5"])));
+    let (key, args) = feed.lines().next().unwrap();
+    assert_eq!(
+        ENGLISH_TEXTS.format(*key, &args.0),
+        "1. Black plays F5, flipping 1"
+    );
+    assert!(feed.sync(&standard_game(&[])));
+    assert!(feed.is_empty());
+}
+
+#[test]
+fn feed_keeps_only_the_latest_lines() {
+    // 双方总是走第一个合法位置，直到终局
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    while !log.is_game_over() {
+        let action = if log.must_pass() {
+            MoveAction::Pass
+        } else {
+            let moves = log.board().get_valid_moves(log.to_move());
+            MoveAction::Place(moves.trailing_zeros() as u8)
+        };
+        log.apply(log.next_command(action)).unwrap();
+    }
+
+    let mut feed = CommentaryFeed::default();
+    assert!(feed.sync(&log));
+    assert_eq!(feed.lines().count(), COMMENTARY_LINES);
+    let (key, _) = feed.lines().last().unwrap();
+    assert_eq!(*key, TextKey::CommentaryGameOverFormat);
+}
@@ -0,0 +1,85 @@
+// 难度说明测试 - 难度按钮副标题中的搜索深度、时限和估计等级分，以及按战绩推荐难度
+
+use reversi::{
+    ai::{adaptive::AdaptiveLevel, endgame::GameOutcome},
+    localization::{TextKey, CHINESE_TEXTS, ENGLISH_TEXTS},
+    stats::PlayerStats,
+    AiDifficulty,
+};
+
+#[test]
+fn subtitle_shows_depth_time_and_elo() {
+    let stats = PlayerStats::default();
+    let args = stats.difficulty_subtitle_args(AiDifficulty::Beginner);
+    assert_eq!(
+        ENGLISH_TEXTS.format(TextKey::DifficultySubtitleFormat, &args.0),
+        "Depth 2 · avg 0.1s per move · ~800 Elo"
+    );
+    let args = stats.difficulty_subtitle_args(AiDifficulty::Expert);
+    assert_eq!(
+        CHINESE_TEXTS.format(TextKey::DifficultySubtitleFormat, &args.0),
+        "深度12 · 每步平均5.0秒 · 约2,000等级分"
+    );
+}
+
+#[test]
+fn estimated_elo_grows_with_strength() {
+    let fixed = [
+        AiDifficulty::Beginner,
+        AiDifficulty::Intermediate,
+        AiDifficulty::Advanced,
+        AiDifficulty::Expert,
+        AiDifficulty::ExpertPlus,
+    ];
+    assert!(fixed
+        .windows(2)
+        .all(|pair| pair[0].estimated_elo() < pair[1].estimated_elo()));
+
+    // 自适应难度按当前强度估计，最弱时也不低于400
+    let default = AiDifficulty::Adaptive(AdaptiveLevel::default()).estimated_elo();
+    let stronger = AiDifficulty::Adaptive(AdaptiveLevel::default().stronger()).estimated_elo();
+    assert!(default < stronger);
+    assert_eq!(default % 50, 0);
+    let weakest = AiDifficulty::Adaptive(AdaptiveLevel {
+        max_depth: 1,
+        mistake_probability: 0.4,
+    });
+    assert_eq!(weakest.estimated_elo(), 400);
+}
+
+#[test]
+fn adaptive_subtitle_uses_the_saved_level() {
+    let mut stats = PlayerStats::default();
+    stats.adaptive.level = AdaptiveLevel {
+        max_depth: 6,
+        mistake_probability: 0.0,
+    };
+    let args = stats.difficulty_subtitle_args(AiDifficulty::Adaptive(AdaptiveLevel::default()));
+    assert_eq!(
+        ENGLISH_TEXTS.format(TextKey::DifficultySubtitleFormat, &args.0),
+        "Depth 6 · avg 0.6s per move · ~1,500 Elo"
+    );
+}
+
+#[test]
+fn recommendation_moves_up_after_a_winning_streak() {
+    let mut stats = PlayerStats::default();
+    assert_eq!(stats.recommended_difficulty(), AiDifficulty::Beginner);
+
+    for _ in 0..3 {
+        stats.record_game(GameOutcome::Win, AiDifficulty::Beginner, 10);
+    }
+    assert_eq!(stats.recommended_difficulty(), AiDifficulty::Intermediate);
+
+    // 输棋不会取消已经达到的最长连胜
+    stats.record_game(GameOutcome::Loss, AiDifficulty::Beginner, -4);
+    stats.record_game(GameOutcome::Win, AiDifficulty::Intermediate, 2);
+    assert_eq!(stats.recommended_difficulty(), AiDifficulty::Intermediate);
+
+    for difficulty in [AiDifficulty::Intermediate, AiDifficulty::Advanced] {
+        for _ in 0..3 {
+            stats.record_game(GameOutcome::Win, difficulty, 2);
+        }
+    }
+    assert_eq!(stats.recommended_difficulty(), AiDifficulty::Expert);
+}
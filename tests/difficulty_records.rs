@@ -0,0 +1,90 @@
+// 分难度战绩测试 - 连胜、最长连胜和最大胜差按难度分别记录，旧存档缺少该字段时仍能读取
+
+use reversi::{
+    ai::{adaptive::AdaptiveLevel, endgame::GameOutcome},
+    localization::TextArg,
+    stats::{DifficultyRecord, PlayerStats},
+    AiDifficulty,
+};
+
+#[test]
+fn streak_grows_with_wins_and_resets_on_loss_or_draw() {
+    let mut record = DifficultyRecord::default();
+    record.record(GameOutcome::Win, 4);
+    record.record(GameOutcome::Win, 10);
+    record.record(GameOutcome::Win, 2);
+    assert_eq!(record.current_streak, 3);
+    assert_eq!(record.best_streak, 3);
+    assert_eq!(record.best_win, Some(10));
+
+    record.record(GameOutcome::Draw, 0);
+    assert_eq!(record.current_streak, 0);
+    record.record(GameOutcome::Win, 6);
+    record.record(GameOutcome::Loss, -20);
+    assert_eq!(record.current_streak, 0);
+    assert_eq!(record.best_streak, 3);
+    assert_eq!(record.best_win, Some(10));
+}
+
+#[test]
+fn records_are_kept_per_difficulty() {
+    let mut stats = PlayerStats::default();
+    stats.record_game(GameOutcome::Win, AiDifficulty::Beginner, 30);
+    stats.record_game(GameOutcome::Win, AiDifficulty::Beginner, 12);
+    stats.record_game(GameOutcome::Loss, AiDifficulty::Expert, -8);
+
+    let beginner = stats.record_for(AiDifficulty::Beginner);
+    assert_eq!(beginner.current_streak, 2);
+    assert_eq!(beginner.best_win, Some(30));
+
+    let expert = stats.record_for(AiDifficulty::Expert);
+    assert_eq!(expert.current_streak, 0);
+    assert_eq!(expert.best_win, None);
+
+    assert_eq!(
+        stats.record_for(AiDifficulty::Intermediate),
+        DifficultyRecord::default()
+    );
+    assert_eq!(stats.games_played, 3);
+}
+
+#[test]
+fn adaptive_games_share_one_record_across_levels() {
+    let mut stats = PlayerStats::default();
+    let easy = AdaptiveLevel::default();
+    stats.record_game(GameOutcome::Win, AiDifficulty::Adaptive(easy), 6);
+    let harder = stats.adaptive.level;
+    stats.record_game(GameOutcome::Win, AiDifficulty::Adaptive(harder), 4);
+
+    let record = stats.record_for(AiDifficulty::Adaptive(AdaptiveLevel::default()));
+    assert_eq!(record.current_streak, 2);
+    assert_eq!(record.best_win, Some(6));
+}
+
+#[test]
+fn badge_only_appears_after_a_win() {
+    let mut record = DifficultyRecord::default();
+    record.record(GameOutcome::Loss, -4);
+    assert_eq!(record.badge_args(), None);
+
+    record.record(GameOutcome::Win, 18);
+    let args = record.badge_args().unwrap();
+    assert_eq!(
+        args.0,
+        vec![TextArg::Number(1), TextArg::Number(1), TextArg::Number(18)]
+    );
+}
+
+#[test]
+fn old_saves_without_records_still_load() {
+    let stats: PlayerStats =
+        ron::from_str("(games_played: 5, wins: 3, losses: 2, draws: 0)").unwrap();
+    assert_eq!(stats.wins, 3);
+    assert!(stats.difficulty_records.is_empty());
+
+    let mut stats = stats;
+    stats.record_game(GameOutcome::Win, AiDifficulty::Advanced, 14);
+    let saved = ron::to_string(&stats).unwrap();
+    let loaded: PlayerStats = ron::from_str(&saved).unwrap();
+    assert_eq!(loaded, stats);
+}
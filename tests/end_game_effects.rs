@@ -0,0 +1,91 @@
+// 终局特效测试 - 胜负对应的特效，以及减少动效时只保留静态效果
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    accessibility::MotionSettings,
+    ai::{endgame::GameOutcome, AiPlayer},
+    game::Board,
+    ui::{
+        start_end_game_effects, CameraShake, Confetti, DefeatTint, EndGameEffect, ScoreEntry,
+        ScoreFlourish,
+    },
+    AiDifficulty, PlayerColor,
+};
+
+/// 玩家执黑，胜方填满棋盘后的结束局面
+fn finished_world(winner: PlayerColor, reduced_motion: bool) -> World {
+    let mut world = World::new();
+    world.insert_resource(MotionSettings { reduced_motion });
+    let mut finished = Board::new_standard();
+    for position in 0..64 {
+        finished.place_free(position, winner);
+    }
+    world.spawn(finished);
+    world.spawn(AiPlayer::new(AiDifficulty::Beginner, PlayerColor::White));
+    world.spawn(ScoreEntry(PlayerColor::Black));
+    world.spawn(ScoreEntry(PlayerColor::White));
+    world
+}
+
+fn count<C: Component>(world: &mut World) -> usize {
+    world.query::<&C>().iter(world).count()
+}
+
+#[test]
+fn outcomes_map_to_effects() {
+    assert_eq!(
+        EndGameEffect::for_outcome(GameOutcome::Win),
+        Some(EndGameEffect::Victory)
+    );
+    assert_eq!(
+        EndGameEffect::for_outcome(GameOutcome::Loss),
+        Some(EndGameEffect::Defeat)
+    );
+    assert_eq!(EndGameEffect::for_outcome(GameOutcome::Draw), None);
+}
+
+#[test]
+fn victory_bursts_confetti_and_flourishes_the_player_score() {
+    let mut world = finished_world(PlayerColor::Black, false);
+    world.run_system_once(start_end_game_effects).unwrap();
+
+    assert!(count::<Confetti>(&mut world) > 0);
+    let flourished: Vec<PlayerColor> = world
+        .query_filtered::<&ScoreEntry, With<ScoreFlourish>>()
+        .iter(&world)
+        .map(|ScoreEntry(color)| *color)
+        .collect();
+    assert_eq!(flourished, vec![PlayerColor::Black]);
+    assert_eq!(count::<DefeatTint>(&mut world), 0);
+    assert!(!world.contains_resource::<CameraShake>());
+}
+
+#[test]
+fn defeat_shakes_the_camera_and_tints_the_screen() {
+    let mut world = finished_world(PlayerColor::White, false);
+    world.run_system_once(start_end_game_effects).unwrap();
+
+    assert!(world.contains_resource::<CameraShake>());
+    assert_eq!(count::<DefeatTint>(&mut world), 1);
+    assert_eq!(count::<Confetti>(&mut world), 0);
+}
+
+#[test]
+fn reduced_motion_keeps_only_static_effects() {
+    let mut world = finished_world(PlayerColor::Black, true);
+    world.run_system_once(start_end_game_effects).unwrap();
+    assert_eq!(count::<Confetti>(&mut world), 0);
+    assert_eq!(count::<ScoreFlourish>(&mut world), 0);
+
+    let mut world = finished_world(PlayerColor::White, true);
+    world.run_system_once(start_end_game_effects).unwrap();
+    assert!(!world.contains_resource::<CameraShake>());
+    // 蒙层直接以最终透明度显示
+    let alpha = world
+        .query_filtered::<&BackgroundColor, With<DefeatTint>>()
+        .single(&world)
+        .unwrap()
+        .0
+        .alpha();
+    assert!(alpha > 0.0);
+}
@@ -0,0 +1,196 @@
+// 残局求解器强度测试 - 使用已知胜负和结果的残局局面防止求解和搜索退化，以及求解超时或取消时放弃并改用普通搜索
+
+use bevy::platform::time::Instant;
+use reversi::ai::{
+    endgame::{
+        solve_endgame, solve_endgame_within, GameOutcome, SolveLimits, ENDGAME_SOLVER_MAX_EMPTIES,
+    },
+    evaluation::EvalConfig,
+    AiDifficulty,
+};
+use reversi::game::{Board, PlayerColor};
+use std::{sync::atomic::AtomicBool, time::Duration};
+
+const CORPUS: &str = include_str!("data/endgame_wld.txt");
+
+struct WldPosition {
+    line: usize,
+    board: Board,
+    player: PlayerColor,
+    outcome: GameOutcome,
+    score: i32,
+}
+
+fn parse_bitboard(value: &str) -> u64 {
+    u64::from_str_radix(value.trim_start_matches("0x"), 16).expect("invalid bitboard")
+}
+
+fn load_corpus() -> Vec<WldPosition> {
+    CORPUS
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(index, line)| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            assert_eq!(fields.len(), 5, "line {}: expected 5 fields", index + 1);
+
+            let player = match fields[2] {
+                "B" => PlayerColor::Black,
+                "W" => PlayerColor::White,
+                other => panic!("line {}: unknown side to move {other}", index + 1),
+            };
+            let outcome = match fields[3] {
+                "W" => GameOutcome::Win,
+                "L" => GameOutcome::Loss,
+                "D" => GameOutcome::Draw,
+                other => panic!("line {}: unknown outcome {other}", index + 1),
+            };
+
+            WldPosition {
+                line: index + 1,
+                board: Board {
+                    black: parse_bitboard(fields[0]),
+                    white: parse_bitboard(fields[1]),
+                },
+                player,
+                outcome,
+                score: fields[4].parse().expect("invalid score"),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn corpus_positions_are_valid() {
+    let corpus = load_corpus();
+    assert!(!corpus.is_empty());
+
+    for position in &corpus {
+        let board = position.board;
+        assert_eq!(board.black & board.white, 0, "line {}", position.line);
+        assert!(
+            board.get_empty_squares().count_ones() <= ENDGAME_SOLVER_MAX_EMPTIES,
+            "line {}: too many empties",
+            position.line
+        );
+        assert!(
+            board.has_valid_moves(position.player),
+            "line {}: side to move must have a legal move",
+            position.line
+        );
+        assert_eq!(
+            GameOutcome::from_score(position.score),
+            position.outcome,
+            "line {}: outcome and score disagree",
+            position.line
+        );
+    }
+}
+
+#[test]
+fn solver_finds_expected_outcome_and_score() {
+    for position in load_corpus() {
+        let result = solve_endgame(&position.board, position.player);
+        assert_eq!(result.outcome(), position.outcome, "line {}", position.line);
+        assert_eq!(result.score, position.score, "line {}", position.line);
+    }
+}
+
+#[test]
+fn solver_best_move_preserves_score() {
+    for position in load_corpus() {
+        let result = solve_endgame(&position.board, position.player);
+        let best_move = result.best_move.expect("position has legal moves");
+
+        let mut board = position.board;
+        assert!(board.make_move(best_move.position, position.player));
+
+        // 走出最佳着法后，对手视角的完美结果应恰好相反
+        let reply = solve_endgame(&board, position.player.opposite());
+        assert_eq!(-reply.score, position.score, "line {}", position.line);
+    }
+}
+
+#[test]
+fn expert_ai_plays_perfect_endgame_moves() {
+    for position in load_corpus() {
+        let ai_move = AiDifficulty::Expert
+            .get_ai_move(&position.board, position.player)
+            .expect("position has legal moves");
+
+        let mut board = position.board;
+        assert!(board.make_move(ai_move.position, position.player));
+
+        let reply = solve_endgame(&board, position.player.opposite());
+        assert_eq!(
+            -reply.score,
+            position.score,
+            "line {}: expert played {} instead of a perfect move",
+            position.line,
+            Board::position_to_notation(ai_move.position)
+        );
+    }
+}
+
+/// 语料中空位最多的局面
+fn hardest_position() -> WldPosition {
+    load_corpus()
+        .into_iter()
+        .max_by_key(|position| position.board.get_empty_squares().count_ones())
+        .unwrap()
+}
+
+#[test]
+fn solver_gives_up_outside_its_limits() {
+    let position = hardest_position();
+    let expired = SolveLimits {
+        deadline: Some(Instant::now()),
+        cancel: None,
+    };
+    assert_eq!(
+        solve_endgame_within(&position.board, position.player, expired),
+        None
+    );
+
+    let cancel = AtomicBool::new(true);
+    let cancelled = SolveLimits {
+        deadline: None,
+        cancel: Some(&cancel),
+    };
+    assert_eq!(
+        solve_endgame_within(&position.board, position.player, cancelled),
+        None
+    );
+
+    let generous = SolveLimits {
+        deadline: Some(Instant::now() + Duration::from_secs(60)),
+        cancel: None,
+    };
+    assert_eq!(
+        solve_endgame_within(&position.board, position.player, generous),
+        Some(solve_endgame(&position.board, position.player))
+    );
+}
+
+#[test]
+fn ai_falls_back_to_search_when_the_solver_runs_out_of_time() {
+    let position = hardest_position();
+    let empties = position.board.get_empty_squares().count_ones() as u8;
+
+    // 没有时间求解时仍在看门狗时限内给出合法走法
+    let started = Instant::now();
+    let result = AiDifficulty::Expert.search_ai_move(
+        &position.board,
+        position.player,
+        EvalConfig::standard(),
+        None,
+        Duration::ZERO,
+        None,
+    );
+    assert!(started.elapsed() < AiDifficulty::Expert.watchdog_deadline());
+    let chosen = result.best_move.expect("position has legal moves");
+    assert!(position
+        .board
+        .is_valid_move(chosen.position, position.player));
+    assert!(result.depth_reached < empties);
+}
@@ -0,0 +1,80 @@
+// 评估器接口测试 - 默认启发式评估和自定义评估器接入搜索
+
+use reversi::ai::{
+    evaluation::{evaluate_board_with, EvalConfig},
+    minimax::find_best_move,
+    mistakes::rank_moves,
+    AiDifficulty, Evaluator, EvaluatorKind,
+};
+use reversi::game::{Board, PlayerColor};
+
+/// 只看某一格是否属于自己的评估器
+struct PreferSquare(u8);
+
+impl Evaluator for PreferSquare {
+    fn evaluate(&self, board: &Board, player: PlayerColor) -> i32 {
+        i32::from(board.get_piece(self.0) == Some(player))
+    }
+}
+
+#[test]
+fn heuristic_matches_the_evaluation_config() {
+    let board = Board::new_standard();
+    let config = EvalConfig::standard();
+    let evaluator = EvaluatorKind::Heuristic.evaluator(config);
+    for player in [PlayerColor::Black, PlayerColor::White] {
+        assert_eq!(
+            evaluator.evaluate(&board, player),
+            evaluate_board_with(&board, player, config)
+        );
+    }
+}
+
+#[test]
+fn difficulties_default_to_the_heuristic() {
+    for difficulty in [
+        AiDifficulty::Beginner,
+        AiDifficulty::Intermediate,
+        AiDifficulty::Advanced,
+        AiDifficulty::Expert,
+    ] {
+        assert_eq!(
+            difficulty.get_search_params().evaluator,
+            EvaluatorKind::Heuristic
+        );
+    }
+    assert_eq!(
+        EvaluatorKind::from_name("Heuristic"),
+        Some(EvaluatorKind::Heuristic)
+    );
+    assert_eq!(EvaluatorKind::from_name("oracle"), None);
+}
+
+#[test]
+fn custom_evaluator_drives_the_search() {
+    let board = Board::new_standard();
+    // 黑方开局的四个走法中只有f5(37)占到该格
+    let evaluator = PreferSquare(37);
+    let result = find_best_move(&board, 1, PlayerColor::Black, &evaluator);
+    assert_eq!(result.best_move.map(|chosen| chosen.position), Some(37));
+
+    let dynamic: &dyn Evaluator = &evaluator;
+    let ranked = rank_moves(&board, PlayerColor::Black, dynamic, None);
+    assert_eq!(ranked[0].0.position, 37);
+}
+
+#[test]
+fn network_falls_back_to_the_heuristic_without_weights() {
+    let board = Board::new_standard();
+    let config = EvalConfig::standard();
+    assert_eq!(
+        AiDifficulty::from_name("expert+"),
+        Some(AiDifficulty::ExpertPlus)
+    );
+    let kind = AiDifficulty::ExpertPlus.get_search_params().evaluator;
+    assert_eq!(kind, EvaluatorKind::Network);
+    assert_eq!(
+        kind.evaluator(config).evaluate(&board, PlayerColor::Black),
+        evaluate_board_with(&board, PlayerColor::Black, config)
+    );
+}
@@ -0,0 +1,69 @@
+// 翻转分组测试 - 按方向分组、由近到远排列并带有方向的翻转棋子
+
+use reversi::game::{Board, PlayerColor};
+
+#[test]
+fn opening_move_flips_one_disc() {
+    let board = Board::new_standard();
+    let position = board.get_valid_moves_list(PlayerColor::Black)[0].position;
+    let rays = board.flipped_rays(position, PlayerColor::Black);
+    assert_eq!(rays.len(), 1);
+    assert_eq!(rays[0].len(), 1);
+}
+
+#[test]
+fn rays_are_ordered_outward_and_match_make_move() {
+    // 黑方在A1落子，沿对角线夹住B2、C3、D4三枚白子
+    let bit = |row, col| 1u64 << Board::coords_to_position(row, col);
+    let board = Board {
+        black: bit(4, 4),
+        white: bit(1, 1) | bit(2, 2) | bit(3, 3),
+    };
+
+    let position = Board::coords_to_position(0, 0);
+    let rays = board.flipped_rays(position, PlayerColor::Black);
+    assert_eq!(
+        rays,
+        vec![vec![
+            Board::coords_to_position(1, 1),
+            Board::coords_to_position(2, 2),
+            Board::coords_to_position(3, 3),
+        ]]
+    );
+
+    let mut after = board;
+    assert!(after.make_move(position, PlayerColor::Black));
+    for &flipped in &rays[0] {
+        assert_eq!(after.get_piece(flipped), Some(PlayerColor::Black));
+    }
+    assert!(board.flipped_rays(63, PlayerColor::Black).is_empty());
+}
+
+#[test]
+fn rays_carry_their_direction() {
+    // 黑方在A1落子：沿第1行夹住B1、C1，沿对角线夹住B2
+    let bit = |row, col| 1u64 << Board::coords_to_position(row, col);
+    let board = Board {
+        black: bit(0, 3) | bit(2, 2),
+        white: bit(0, 1) | bit(0, 2) | bit(1, 1),
+    };
+
+    let rays = board.get_flipped_discs_by_direction(0, PlayerColor::Black);
+    assert_eq!(rays.len(), 2);
+
+    let row = rays.iter().find(|ray| ray.is_horizontal()).unwrap();
+    assert_eq!(row.direction, (0, 1));
+    assert_eq!(row.discs, vec![1, 2]);
+    assert_eq!(row.farthest(), Some(2));
+
+    let diagonal = rays.iter().find(|ray| ray.is_diagonal()).unwrap();
+    assert_eq!(diagonal.direction, (1, 1));
+    assert_eq!(diagonal.discs, vec![Board::coords_to_position(1, 1)]);
+    assert!(!rays.iter().any(|ray| ray.is_vertical()));
+
+    let discs: Vec<Vec<u8>> = rays.into_iter().map(|ray| ray.discs).collect();
+    assert_eq!(discs, board.flipped_rays(0, PlayerColor::Black));
+    assert!(board
+        .get_flipped_discs_by_direction(63, PlayerColor::Black)
+        .is_empty());
+}
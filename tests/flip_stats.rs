@@ -0,0 +1,134 @@
+// 翻转统计测试 - 每局双方的翻转数、单步最多翻转和占角数，累计记录、打破记录的提示和结果界面的文本
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    ai::{endgame::GameOutcome, AiDifficulty, AiPlayer},
+    game::{parse_position_string, Board, GameLog, MoveAction, PlayerColor},
+    localization::{TextKey, CHINESE_TEXTS, ENGLISH_TEXTS},
+    stats::{
+        record_flip_stats, FlipRecords, GameFinishedEvent, GameFlipStats, PlayerStats,
+        SideFlipStats,
+    },
+    ui::ToastEvent,
+};
+
+/// 黑方在A1落子，翻转B1和C1两枚白子并占角，白方没有棋子，对局结束
+fn corner_game() -> GameLog {
+    let (board, player) = parse_position_string(concat!(
+        "-OOX----",
+        "--------",
+        "--------",
+        "--------",
+        "--------",
+        "--------",
+        "--------",
+        "-------- X",
+    ))
+    .unwrap();
+    let mut log = GameLog::new(board, player);
+    log.apply(log.next_command(MoveAction::Place(0))).unwrap();
+    log
+}
+
+#[test]
+fn flips_and_corners_are_counted_per_side() {
+    let stats = GameFlipStats::from_log(&corner_game());
+    assert_eq!(
+        stats.side(PlayerColor::Black),
+        SideFlipStats {
+            discs_flipped: 2,
+            longest_chain: 2,
+            corners: 1
+        }
+    );
+    assert_eq!(stats.side(PlayerColor::White), SideFlipStats::default());
+
+    // 开局的每一步都只翻转一枚棋子
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    for notation in ["D3", "C5", "F6"] {
+        let position = Board::notation_to_position(notation).unwrap();
+        log.apply(log.next_command(MoveAction::Place(position)))
+            .unwrap();
+    }
+    let stats = GameFlipStats::from_log(&log);
+    assert_eq!(stats.black.discs_flipped, 2);
+    assert_eq!(stats.white.longest_chain, 1);
+    assert_eq!(stats.black.corners + stats.white.corners, 0);
+}
+
+#[test]
+fn records_accumulate_and_report_new_bests() {
+    let mut records = FlipRecords::default();
+    let game = |discs_flipped, longest_chain, corners| SideFlipStats {
+        discs_flipped,
+        longest_chain,
+        corners,
+    };
+    // 第一局的记录不算打破记录
+    assert!(!records.record(game(30, 5, 1)));
+    assert!(!records.record(game(20, 5, 0)));
+    assert!(records.record(game(25, 8, 2)));
+    assert_eq!(
+        records,
+        FlipRecords {
+            total_flipped: 75,
+            best_chain: 8,
+            corners_captured: 3
+        }
+    );
+
+    // 旧版本保存的统计没有翻转记录
+    let old: PlayerStats = ron::from_str("(games_played: 3)").unwrap();
+    assert_eq!(old.flips, FlipRecords::default());
+}
+
+#[test]
+fn player_side_is_recorded_when_the_game_ends() {
+    let mut world = World::new();
+    world.spawn(corner_game());
+    world.spawn(AiPlayer::new(AiDifficulty::Beginner, PlayerColor::White));
+    world.insert_resource(PlayerStats {
+        flips: FlipRecords {
+            total_flipped: 10,
+            best_chain: 1,
+            corners_captured: 0,
+        },
+        ..default()
+    });
+    world.init_resource::<Events<GameFinishedEvent>>();
+    world.init_resource::<Events<ToastEvent>>();
+    world.send_event(GameFinishedEvent {
+        outcome: GameOutcome::Win,
+        difficulty: AiDifficulty::Beginner,
+        margin: 4,
+    });
+    world.run_system_once(record_flip_stats).unwrap();
+
+    let flips = world.resource::<PlayerStats>().flips;
+    assert_eq!(flips.total_flipped, 12);
+    assert_eq!(flips.best_chain, 2);
+    assert_eq!(flips.corners_captured, 1);
+    assert_eq!(world.resource::<Events<ToastEvent>>().len(), 1);
+}
+
+#[test]
+fn game_over_texts_show_both_sides() {
+    let stats = GameFlipStats::from_log(&corner_game());
+    let args = stats.black.text_args(TextKey::BlackName);
+    assert_eq!(
+        ENGLISH_TEXTS.format(TextKey::FlipStatsFormat, &args.0),
+        "Black: 2 flipped · best move 2 · 1 corners"
+    );
+    assert_eq!(
+        CHINESE_TEXTS.format(TextKey::FlipStatsFormat, &args.0),
+        "黑棋：翻转2枚 · 单步最多2枚 · 占角1个"
+    );
+    let records = FlipRecords {
+        total_flipped: 1234,
+        best_chain: 9,
+        corners_captured: 40,
+    };
+    assert!(ENGLISH_TEXTS
+        .format(TextKey::FlipRecordsFormat, &records.text_args().0)
+        .starts_with("All games: 1,234 flipped"));
+}
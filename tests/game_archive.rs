@@ -0,0 +1,65 @@
+// 历史对局存档测试 - 上限、回放和时间格式
+
+use reversi::ai::{endgame::GameOutcome, AiDifficulty};
+use reversi::game::{Board, GameLog, MoveAction, PlayerColor};
+use reversi::record::{
+    format_timestamp, ArchivedGame, GameArchive, GameRecord, RecordedMove, ARCHIVE_LIMIT,
+};
+use std::time::Duration;
+
+/// 走了若干步的对局和对应的走法记录
+fn played_game(plies: usize) -> (GameLog, GameRecord) {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    let mut record = GameRecord::default();
+    for _ in 0..plies {
+        let position = log.board().get_valid_moves_list(log.to_move())[0].position;
+        let command = log.next_command(MoveAction::Place(position));
+        log.apply(command).unwrap();
+        record.moves.push(RecordedMove {
+            player: command.player,
+            action: command.action,
+            think_time: Duration::from_millis(1500),
+        });
+    }
+    (log, record)
+}
+
+#[test]
+fn archived_games_replay_to_the_same_position() {
+    let (log, record) = played_game(6);
+    let game = ArchivedGame::new(
+        &log,
+        &record,
+        GameOutcome::Win,
+        AiDifficulty::Expert,
+        1_700_000_000,
+    );
+
+    assert_eq!(game.replay().unwrap(), log);
+    assert_eq!(game.black + game.white, 10);
+
+    // 存档经过RON往返后不变
+    let mut archive = GameArchive::default();
+    archive.push(game);
+    let contents = ron::to_string(&archive).unwrap();
+    assert_eq!(ron::from_str::<GameArchive>(&contents).unwrap(), archive);
+}
+
+#[test]
+fn keeps_only_the_newest_games() {
+    let (log, record) = played_game(2);
+    let mut archive = GameArchive::default();
+    for finished_at in 0..(ARCHIVE_LIMIT as u64 + 5) {
+        archive.push(ArchivedGame::new(
+            &log,
+            &record,
+            GameOutcome::Draw,
+            AiDifficulty::Beginner,
+            finished_at,
+        ));
+    }
+
+    assert_eq!(archive.games.len(), ARCHIVE_LIMIT);
+    assert_eq!(archive.games[0].finished_at, ARCHIVE_LIMIT as u64 + 4);
+    assert_eq!(format_timestamp(1_700_000_000), "2023-11-14 22:13");
+}
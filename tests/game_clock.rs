@@ -0,0 +1,187 @@
+// 对局计时测试 - 计时预设、扣时和加秒、时间不足的提示音、超时判负以及没有AI时不计时
+
+use bevy::prelude::*;
+use reversi::{
+    ai::AiPlayer,
+    audio::{PlaySoundEvent, SoundType},
+    clock::{clock_text, tick_game_clock, ClockAlert, GameClock, TimeControl, LOW_TIME},
+    game::{Board, GameLog, MoveAction, PlayerColor},
+    localization::{CHINESE_TEXTS, ENGLISH_TEXTS},
+    preferences::Preferences,
+    ui::clock_flash_on,
+    AiDifficulty,
+};
+use std::time::Duration;
+
+#[test]
+fn presets_cycle_and_show_their_increment() {
+    let mut control = TimeControl::default();
+    assert!(!control.is_timed());
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        seen.push(control);
+        control = control.next();
+    }
+    assert_eq!(control, TimeControl::Unlimited);
+    assert_eq!(
+        seen,
+        [
+            TimeControl::Unlimited,
+            TimeControl::Blitz,
+            TimeControl::Rapid,
+            TimeControl::Casual,
+        ]
+    );
+
+    assert_eq!(TimeControl::Blitz.base(), Some(Duration::from_secs(180)));
+    assert_eq!(TimeControl::Blitz.increment(), Duration::from_secs(2));
+    assert_eq!(TimeControl::Casual.increment(), Duration::ZERO);
+    assert_eq!(
+        ENGLISH_TEXTS.format(TimeControl::Blitz.name_key(), &[]),
+        "Blitz 3+2"
+    );
+    assert_eq!(
+        CHINESE_TEXTS.format(TimeControl::Rapid.name_key(), &[]),
+        "中速 10+5"
+    );
+}
+
+#[test]
+fn clock_text_rounds_up_to_whole_seconds() {
+    assert_eq!(clock_text(Duration::from_secs(180)), "3:00");
+    assert_eq!(clock_text(Duration::from_millis(61_200)), "1:02");
+    assert_eq!(clock_text(Duration::from_millis(100)), "0:01");
+    assert_eq!(clock_text(Duration::ZERO), "0:00");
+
+    // 减少动效时时间不足的时钟一直是红色
+    assert!(clock_flash_on(0.1, false));
+    assert!(!clock_flash_on(0.3, false));
+    assert!(clock_flash_on(0.3, true));
+}
+
+#[test]
+fn warns_once_and_flags_at_zero() {
+    let mut clock = GameClock::new(TimeControl::Blitz);
+    let almost_low = TimeControl::Blitz.base().unwrap() - LOW_TIME - Duration::from_secs(1);
+    assert_eq!(clock.tick(PlayerColor::Black, almost_low), None);
+    assert!(!clock.is_low(PlayerColor::Black));
+
+    assert_eq!(
+        clock.tick(PlayerColor::Black, Duration::from_secs(2)),
+        Some(ClockAlert::LowTime(PlayerColor::Black))
+    );
+    assert!(clock.is_low(PlayerColor::Black));
+
+    // 加秒后回到警告线以上、再次跌破时不重复提示
+    clock.add_increment(PlayerColor::Black);
+    assert_eq!(
+        clock.remaining(PlayerColor::Black),
+        LOW_TIME + Duration::from_secs(1)
+    );
+    assert_eq!(clock.tick(PlayerColor::Black, Duration::from_secs(2)), None);
+
+    assert_eq!(
+        clock.tick(PlayerColor::Black, LOW_TIME),
+        Some(ClockAlert::Flagged(PlayerColor::Black))
+    );
+    assert_eq!(clock.flagged(), Some(PlayerColor::Black));
+    // 超时后时钟停止
+    assert_eq!(clock.tick(PlayerColor::White, Duration::from_secs(1)), None);
+    assert_eq!(
+        clock.remaining(PlayerColor::White),
+        TimeControl::Blitz.base().unwrap()
+    );
+}
+
+/// 快棋设置下的对局，玩家执黑；`with_ai`为false时相当于沙盒
+fn clock_world(with_ai: bool) -> (World, Schedule) {
+    let mut world = World::new();
+    let mut preferences = Preferences::default();
+    preferences.rules.time_control = TimeControl::Blitz;
+    world.insert_resource(preferences);
+    world.init_resource::<GameClock>();
+    world.init_resource::<Time>();
+    world.init_resource::<Events<PlaySoundEvent>>();
+    world.spawn(GameLog::new(Board::new_standard(), PlayerColor::Black));
+    if with_ai {
+        world.spawn(AiPlayer::new(AiDifficulty::Beginner, PlayerColor::White));
+    }
+    let mut schedule = Schedule::default();
+    schedule.add_systems(tick_game_clock);
+    (world, schedule)
+}
+
+/// 经过一段时间后运行一帧
+fn run_after(world: &mut World, schedule: &mut Schedule, elapsed: Duration) {
+    world.resource_mut::<Time>().advance_by(elapsed);
+    schedule.run(world);
+}
+
+fn low_time_sounds(world: &mut World) -> usize {
+    world
+        .resource_mut::<Events<PlaySoundEvent>>()
+        .drain()
+        .filter(|event| event.sound_type == SoundType::LowTime)
+        .count()
+}
+
+#[test]
+fn runs_the_side_to_move_and_adds_the_increment() {
+    let (mut world, mut schedule) = clock_world(true);
+    let base = TimeControl::Blitz.base().unwrap();
+    run_after(&mut world, &mut schedule, Duration::ZERO);
+    assert_eq!(world.resource::<GameClock>().control(), TimeControl::Blitz);
+
+    run_after(&mut world, &mut schedule, Duration::from_secs(5));
+    let clock = world.resource::<GameClock>();
+    assert_eq!(
+        clock.remaining(PlayerColor::Black),
+        base - Duration::from_secs(5)
+    );
+    assert_eq!(clock.remaining(PlayerColor::White), base);
+
+    // 黑棋落子后加2秒，之后扣白棋的时间
+    let mut log = world
+        .query::<&mut GameLog>()
+        .single_mut(&mut world)
+        .unwrap();
+    let command = log.next_command(MoveAction::Place(19));
+    log.apply(command).unwrap();
+    run_after(&mut world, &mut schedule, Duration::ZERO);
+    run_after(&mut world, &mut schedule, Duration::from_secs(1));
+    let clock = world.resource::<GameClock>();
+    assert_eq!(
+        clock.remaining(PlayerColor::Black),
+        base - Duration::from_secs(3)
+    );
+    assert_eq!(
+        clock.remaining(PlayerColor::White),
+        base - Duration::from_secs(1)
+    );
+}
+
+#[test]
+fn player_hears_the_low_time_warning_then_loses_on_time() {
+    let (mut world, mut schedule) = clock_world(true);
+    run_after(&mut world, &mut schedule, Duration::ZERO);
+    let base = TimeControl::Blitz.base().unwrap();
+    run_after(&mut world, &mut schedule, base - LOW_TIME);
+    assert_eq!(low_time_sounds(&mut world), 1);
+
+    run_after(&mut world, &mut schedule, LOW_TIME);
+    assert_eq!(
+        world.resource::<GameClock>().flagged(),
+        Some(PlayerColor::Black)
+    );
+    assert_eq!(low_time_sounds(&mut world), 0);
+}
+
+#[test]
+fn games_without_an_ai_are_untimed() {
+    let (mut world, mut schedule) = clock_world(false);
+    run_after(&mut world, &mut schedule, Duration::ZERO);
+    run_after(&mut world, &mut schedule, Duration::from_secs(600));
+    let clock = world.resource::<GameClock>();
+    assert!(!clock.is_timed());
+    assert_eq!(clock.flagged(), None);
+}
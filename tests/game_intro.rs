@@ -0,0 +1,156 @@
+// 开场动画测试 - 起始棋子逐个落下的时间和音效、横幅淡出、减少动效时不播放，以及点击跳过
+
+use bevy::prelude::*;
+use reversi::{
+    audio::{PlaySoundEvent, SoundType},
+    fonts::FontAssets,
+    game::PlayerColor,
+    localization::LanguageSettings,
+    ui::{
+        play_game_intro, BoardUI, GameIntro, IntroBanner, Piece, INTRO_BANNER_SECS,
+        INTRO_DISC_INTERVAL_SECS,
+    },
+};
+use std::time::Duration;
+
+#[test]
+fn discs_drop_one_by_one_then_the_banner_fades() {
+    let mut intro = GameIntro::default();
+    intro.start(false);
+    assert!(intro.is_playing());
+
+    // 第一枚棋子立即落下，之后每隔一段时间落下一枚
+    assert_eq!(intro.advance(0.0, 4), 1);
+    assert_eq!(intro.disc_scale(0), 0.0);
+    assert_eq!(intro.disc_scale(1), 0.0);
+    assert_eq!(intro.advance(INTRO_DISC_INTERVAL_SECS + 0.01, 4), 1);
+    assert_eq!(intro.disc_scale(0), 1.0);
+    assert_eq!(intro.banner_alpha(4), 0.0);
+
+    // 一帧跨过两枚棋子时都要播放音效
+    assert_eq!(intro.advance(INTRO_DISC_INTERVAL_SECS * 2.0, 4), 2);
+    assert_eq!(intro.advance(INTRO_DISC_INTERVAL_SECS, 4), 0);
+    assert_eq!(intro.banner_alpha(4), 1.0);
+
+    intro.advance(INTRO_BANNER_SECS, 4);
+    assert!(!intro.is_playing());
+    assert_eq!(intro.disc_scale(3), 1.0);
+    assert_eq!(intro.banner_alpha(4), 0.0);
+}
+
+#[test]
+fn reduced_motion_skips_the_intro() {
+    let mut intro = GameIntro::default();
+    intro.start(true);
+    assert!(!intro.is_playing());
+    assert_eq!(intro.advance(1.0, 4), 0);
+    assert_eq!(intro.disc_scale(0), 1.0);
+}
+
+/// 带4枚起始棋子的棋盘和刚开始的开场动画
+fn intro_world() -> (World, Schedule, Vec<Entity>) {
+    let mut world = World::new();
+    let mut intro = GameIntro::default();
+    intro.start(false);
+    world.insert_resource(intro);
+    world.init_resource::<Time>();
+    world.init_resource::<ButtonInput<MouseButton>>();
+    world.init_resource::<ButtonInput<KeyCode>>();
+    world.init_resource::<Touches>();
+    world.init_resource::<Events<PlaySoundEvent>>();
+    world.init_resource::<LanguageSettings>();
+    world.init_resource::<FontAssets>();
+    let root = world.spawn(BoardUI).id();
+    let pieces = [
+        (27, PlayerColor::White),
+        (28, PlayerColor::Black),
+        (35, PlayerColor::Black),
+        (36, PlayerColor::White),
+    ]
+    .into_iter()
+    .map(|(position, color)| {
+        world
+            .spawn((
+                Piece { color, position },
+                Transform::default(),
+                Visibility::default(),
+                ChildOf(root),
+            ))
+            .id()
+    })
+    .collect();
+    let mut schedule = Schedule::default();
+    schedule.add_systems(play_game_intro);
+    (world, schedule, pieces)
+}
+
+fn run_after(world: &mut World, schedule: &mut Schedule, secs: f32) {
+    world
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs_f32(secs));
+    schedule.run(world);
+}
+
+fn place_sounds(world: &mut World) -> usize {
+    world
+        .resource_mut::<Events<PlaySoundEvent>>()
+        .drain()
+        .filter(|event| event.sound_type == SoundType::PiecePlace)
+        .count()
+}
+
+#[test]
+fn pieces_appear_in_order_with_sounds() {
+    let (mut world, mut schedule, pieces) = intro_world();
+    run_after(&mut world, &mut schedule, 0.0);
+    assert_eq!(place_sounds(&mut world), 1);
+    assert_eq!(
+        world.get::<Visibility>(pieces[1]),
+        Some(&Visibility::Hidden)
+    );
+
+    run_after(&mut world, &mut schedule, INTRO_DISC_INTERVAL_SECS + 0.01);
+    assert_eq!(place_sounds(&mut world), 1);
+    assert_eq!(
+        world.get::<Visibility>(pieces[0]),
+        Some(&Visibility::Inherited)
+    );
+    assert_eq!(world.get::<Transform>(pieces[0]).unwrap().scale.x, 1.0);
+    assert_eq!(
+        world.get::<Visibility>(pieces[2]),
+        Some(&Visibility::Hidden)
+    );
+
+    // 全部落下后显示横幅
+    run_after(&mut world, &mut schedule, INTRO_DISC_INTERVAL_SECS * 3.0);
+    assert_eq!(place_sounds(&mut world), 2);
+    run_after(&mut world, &mut schedule, 0.0);
+    assert_eq!(
+        world
+            .query_filtered::<(), With<IntroBanner>>()
+            .iter(&world)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn click_skips_to_the_full_board() {
+    let (mut world, mut schedule, pieces) = intro_world();
+    run_after(&mut world, &mut schedule, 0.0);
+
+    world
+        .resource_mut::<ButtonInput<MouseButton>>()
+        .press(MouseButton::Left);
+    run_after(&mut world, &mut schedule, 0.0);
+    assert!(!world.resource::<GameIntro>().is_playing());
+    for &piece in &pieces {
+        assert_eq!(world.get::<Visibility>(piece), Some(&Visibility::Inherited));
+        assert_eq!(world.get::<Transform>(piece).unwrap().scale, Vec3::ONE);
+    }
+
+    // 结束后不再改动棋子的缩放，留给翻转动画
+    world.get_mut::<Transform>(pieces[0]).unwrap().scale.x = 0.5;
+    run_after(&mut world, &mut schedule, 0.1);
+    assert_eq!(world.get::<Transform>(pieces[0]).unwrap().scale.x, 0.5);
+}
@@ -0,0 +1,153 @@
+// 对局界面清理测试 - 界面、棋盘和对局各只有一个根实体，重新开始和返回菜单的状态切换只删除根实体，反复重开不残留实体
+
+mod common;
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use common::{count, enter_game, game_world, session};
+use reversi::{
+    ai::AiPlayer,
+    game::{Board, GameLog},
+    plugin::{
+        gameplay::{
+            begin_restart, handle_back_to_difficulty_event, leave_game_screen, restart_game,
+        },
+        BackToDifficultyEvent, GameSession, NewGameRequest,
+    },
+    ui::{
+        cleanup_marked_entities, manage_pause_menu, BoardUI, GameUI, Piece, RestartGameEvent,
+        UiState,
+    },
+    GameState,
+};
+
+const RESTART_CYCLES: usize = 100;
+
+/// 进入对局并打开暂停菜单
+fn enter_paused_game(world: &mut World) {
+    enter_game(world);
+    world.resource_mut::<UiState>().paused = true;
+    world.run_system_once(manage_pause_menu).unwrap();
+}
+
+/// 实体是否在指定根实体之下
+fn is_descendant_of(world: &World, mut entity: Entity, root: Entity) -> bool {
+    while let Some(parent) = world.get::<ChildOf>(entity) {
+        entity = parent.parent();
+        if entity == root {
+            return true;
+        }
+    }
+    false
+}
+
+#[test]
+fn game_screen_has_one_root_each() {
+    let mut world = game_world();
+    enter_paused_game(&mut world);
+
+    assert_eq!(count::<GameUI>(&mut world), 1);
+    assert_eq!(count::<BoardUI>(&mut world), 1);
+    assert_eq!(count::<GameSession>(&mut world), 1);
+
+    // 除三个根实体外，所有实体都挂在其中一个根实体下
+    let ui_root = world
+        .query_filtered::<Entity, With<GameUI>>()
+        .single(&world)
+        .unwrap();
+    let board_root = world
+        .query_filtered::<Entity, With<BoardUI>>()
+        .single(&world)
+        .unwrap();
+    let session = session(&mut world);
+    let entities: Vec<Entity> = world.query::<Entity>().iter(&world).collect();
+    for entity in entities {
+        if [ui_root, board_root, session].contains(&entity) {
+            continue;
+        }
+        assert!(
+            is_descendant_of(&world, entity, ui_root)
+                || is_descendant_of(&world, entity, board_root)
+                || is_descendant_of(&world, entity, session),
+            "{entity} is not under a screen root"
+        );
+    }
+
+    // 棋子在棋盘根实体下，AI在对局实体下
+    for entity in world.query_filtered::<Entity, With<Piece>>().iter(&world) {
+        assert!(is_descendant_of(&world, entity, board_root));
+    }
+    let ai = world
+        .query_filtered::<Entity, With<AiPlayer>>()
+        .single(&world)
+        .unwrap();
+    assert!(is_descendant_of(&world, ai, session));
+    assert!(world.get::<GameLog>(session).is_some());
+    // 对局实体是独立的根实体，不挂在棋盘或界面下
+    assert!(world.get::<ChildOf>(session).is_none());
+    assert_eq!(count::<Piece>(&mut world), 4);
+}
+
+#[test]
+fn restarts_do_not_leak_entities() {
+    let mut world = game_world();
+    let restart = world.register_system(restart_game);
+    let begin = world.register_system(begin_restart);
+    let cleanup = world.register_system(cleanup_marked_entities);
+    // 注册的系统本身也是实体
+    let empty_count = world.entities().len();
+
+    enter_paused_game(&mut world);
+    let entity_count = world.entities().len();
+
+    for cycle in 0..RESTART_CYCLES {
+        world.send_event(RestartGameEvent);
+        world.run_system(restart).unwrap();
+        // 请求本身不删除实体，进入Restarting时才清理
+        assert_eq!(count::<GameUI>(&mut world), 1);
+        assert!(matches!(
+            *world.resource::<NextState<GameState>>(),
+            NextState::Pending(GameState::Restarting)
+        ));
+        world.run_system(begin).unwrap();
+        world.run_system(cleanup).unwrap();
+        // 只剩下旧对局（对局实体和AI），回到对局界面时被新对局替换
+        assert_eq!(world.entities().len(), empty_count + 2, "cycle {cycle}");
+        assert_eq!(
+            count::<GameUI>(&mut world) + count::<BoardUI>(&mut world),
+            0
+        );
+        assert!(world.resource::<NewGameRequest>().0);
+
+        enter_paused_game(&mut world);
+        assert!(!world.resource::<NewGameRequest>().0);
+        assert_eq!(world.entities().len(), entity_count, "cycle {cycle}");
+        assert_eq!(count::<GameUI>(&mut world), 1);
+        assert_eq!(count::<BoardUI>(&mut world), 1);
+        assert_eq!(count::<Board>(&mut world), 1);
+        assert_eq!(count::<AiPlayer>(&mut world), 1);
+    }
+}
+
+#[test]
+fn back_to_menu_removes_the_whole_screen() {
+    let mut world = game_world();
+    let back = world.register_system(handle_back_to_difficulty_event);
+    let leave = world.register_system(leave_game_screen);
+    let cleanup = world.register_system(cleanup_marked_entities);
+    let empty_count = world.entities().len();
+
+    for cycle in 0..RESTART_CYCLES {
+        enter_paused_game(&mut world);
+        world.send_event(BackToDifficultyEvent);
+        world.run_system(back).unwrap();
+        assert!(matches!(
+            *world.resource::<NextState<GameState>>(),
+            NextState::Pending(GameState::DifficultySelection)
+        ));
+        // BackToMenu切换中运行的清理
+        world.run_system(leave).unwrap();
+        world.run_system(cleanup).unwrap();
+        assert_eq!(world.entities().len(), empty_count, "cycle {cycle}");
+        assert!(!world.resource::<UiState>().paused);
+    }
+}
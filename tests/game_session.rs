@@ -0,0 +1,152 @@
+// 对局会话测试 - 重新进入对局界面时继续原对局，只有新对局请求才替换棋盘、命令日志和AI，新对局使用设置中的胜负条件和比赛中的执子颜色
+
+mod common;
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use common::{count, enter_game, game_world, session};
+use reversi::{
+    ai::{endgame::GameOutcome, AiPlayer},
+    game::{Board, GameLog, MoveAction, VictoryCondition},
+    plugin::{gameplay::starts_new_game, GameSession, NewGameRequest},
+    preferences::Preferences,
+    series::{MatchFormat, MatchSeries},
+    ui::{avatar_colors, BoardUI, GameUI, MatchScoreText, Piece, PlayerAvatar},
+    undo::UndoState,
+    PlayerColor,
+};
+
+/// 离开对局界面：删除界面和棋盘根实体，对局保留
+fn leave_game(world: &mut World) {
+    let roots: Vec<Entity> = world
+        .query_filtered::<Entity, Or<(With<GameUI>, With<BoardUI>)>>()
+        .iter(world)
+        .collect();
+    for root in roots {
+        world.entity_mut(root).despawn();
+    }
+}
+
+/// 黑方走第一个合法的位置，同步更新棋盘（初始布局是随机的）
+fn play_first_move(world: &mut World) {
+    let entity = session(world);
+    let mut entity = world.entity_mut(entity);
+    let mut log = entity.get_mut::<GameLog>().unwrap();
+    let position = log.board().get_valid_moves_list(PlayerColor::Black)[0].position;
+    let command = log.next_command(MoveAction::Place(position));
+    log.apply(command).unwrap();
+    let board = log.board();
+    *entity.get_mut::<Board>().unwrap() = board;
+}
+
+#[test]
+fn new_game_only_without_session_or_with_request() {
+    let mut world = game_world();
+    assert!(world.run_system_once(starts_new_game).unwrap());
+
+    enter_game(&mut world);
+    assert!(!world.run_system_once(starts_new_game).unwrap());
+
+    world.resource_mut::<NewGameRequest>().0 = true;
+    assert!(world.run_system_once(starts_new_game).unwrap());
+}
+
+#[test]
+fn reentering_resumes_the_same_game() {
+    let mut world = game_world();
+    enter_game(&mut world);
+    let first = session(&mut world);
+    play_first_move(&mut world);
+    world.resource_mut::<UndoState>().used = 1;
+
+    leave_game(&mut world);
+    enter_game(&mut world);
+
+    // 对局、AI和悔棋次数都没有重建
+    assert_eq!(session(&mut world), first);
+    assert_eq!(count::<GameSession>(&mut world), 1);
+    assert_eq!(count::<Board>(&mut world), 1);
+    assert_eq!(count::<AiPlayer>(&mut world), 1);
+    assert_eq!(world.get::<GameLog>(first).unwrap().commands().len(), 1);
+    assert_eq!(world.resource::<UndoState>().used, 1);
+
+    // 新界面按当前局面生成棋子：开局4子加落子1枚、翻转不改变数量
+    assert_eq!(count::<Piece>(&mut world), 5);
+}
+
+#[test]
+fn new_game_request_replaces_the_session() {
+    let mut world = game_world();
+    enter_game(&mut world);
+    let first = session(&mut world);
+    play_first_move(&mut world);
+
+    leave_game(&mut world);
+    world.resource_mut::<NewGameRequest>().0 = true;
+    enter_game(&mut world);
+
+    let second = session(&mut world);
+    assert_ne!(second, first);
+    assert!(world.get_entity(first).is_err());
+    assert!(!world.resource::<NewGameRequest>().0);
+    assert_eq!(count::<Board>(&mut world), 1);
+    assert_eq!(count::<AiPlayer>(&mut world), 1);
+    assert!(world.get::<GameLog>(second).unwrap().commands().is_empty());
+    assert_eq!(count::<Piece>(&mut world), 4);
+}
+
+#[test]
+fn rule_changes_apply_to_the_next_game() {
+    let mut world = game_world();
+    enter_game(&mut world);
+    world.resource_mut::<Preferences>().rules.victory = VictoryCondition::FirstTo(40);
+
+    // 继续原对局时胜负条件不变
+    leave_game(&mut world);
+    enter_game(&mut world);
+    let first = session(&mut world);
+    assert_eq!(
+        world.get::<GameLog>(first).unwrap().victory(),
+        VictoryCondition::Standard
+    );
+
+    leave_game(&mut world);
+    world.resource_mut::<NewGameRequest>().0 = true;
+    enter_game(&mut world);
+    let second = session(&mut world);
+    assert_eq!(
+        world.get::<GameLog>(second).unwrap().victory(),
+        VictoryCondition::FirstTo(40)
+    );
+}
+
+#[test]
+fn second_match_game_swaps_colors() {
+    let mut world = game_world();
+    world.insert_resource(MatchSeries::new(MatchFormat::BestOf3));
+    world
+        .resource_mut::<MatchSeries>()
+        .prepare(MatchFormat::BestOf3);
+    enter_game(&mut world);
+    let ai_color = |world: &mut World| world.query::<&AiPlayer>().single(world).unwrap().color;
+    assert_eq!(ai_color(&mut world), PlayerColor::White);
+
+    // 第一局结束后开始第二局，玩家执白，AI执黑先行
+    world.resource_mut::<MatchSeries>().record(GameOutcome::Win);
+    world
+        .resource_mut::<MatchSeries>()
+        .prepare(MatchFormat::BestOf3);
+    leave_game(&mut world);
+    world.resource_mut::<NewGameRequest>().0 = true;
+    enter_game(&mut world);
+    assert_eq!(ai_color(&mut world), PlayerColor::Black);
+
+    // 头像的颜色跟随双方本局执子的颜色
+    for (avatar, background) in world
+        .query::<(&PlayerAvatar, &BackgroundColor)>()
+        .iter(&world)
+    {
+        assert_eq!(background.0, avatar_colors(avatar.player_color).0);
+    }
+    assert_eq!(count::<PlayerAvatar>(&mut world), 2);
+    assert_eq!(count::<MatchScoreText>(&mut world), 1);
+}
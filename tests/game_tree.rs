@@ -0,0 +1,67 @@
+// 变着树测试 - 分支、导航和回到主线
+
+use reversi::analysis::GameTree;
+use reversi::game::{Board, GameLog, MoveAction, PlayerColor};
+
+/// 由若干步合法走法组成的主线
+fn main_line(plies: usize) -> GameLog {
+    let mut log = GameLog::new(Board::new(), PlayerColor::Black);
+    for _ in 0..plies {
+        let position = log.board().get_valid_moves_list(log.to_move())[0].position;
+        log.apply(log.next_command(MoveAction::Place(position)))
+            .unwrap();
+    }
+    log
+}
+
+#[test]
+fn deviating_creates_a_variation_and_returns_to_the_main_line() {
+    let log = main_line(4);
+    let mut tree = GameTree::from_log(&log);
+    assert_eq!(tree.ply(), 4);
+    assert_eq!(tree.board(), log.board());
+
+    // 回到第2步，尝试主线之外的走法
+    assert!(tree.back() && tree.back());
+    let main_node = tree.children(tree.current())[0];
+    let alternative = tree
+        .board()
+        .get_valid_moves_list(tree.to_move())
+        .into_iter()
+        .map(|chess_move| chess_move.position)
+        .find(|&position| {
+            Some(tree.log().next_command(MoveAction::Place(position))) != tree.command(main_node)
+        })
+        .unwrap();
+    tree.play(MoveAction::Place(alternative)).unwrap();
+
+    assert!(!tree.is_main_line(tree.current()));
+    assert_eq!(tree.variation_index(), (1, 2));
+
+    // 在两个分支之间切换
+    assert!(tree.switch_variation(1));
+    assert_eq!(tree.current(), main_node);
+    assert!(tree.switch_variation(-1));
+    assert!(!tree.is_main_line(tree.current()));
+
+    // 回到主线后沿主线前进到对局结尾
+    assert!(tree.to_main_line());
+    assert_eq!(tree.ply(), 2);
+    while tree.forward() {}
+    assert_eq!(tree.log(), &log);
+}
+
+#[test]
+fn replaying_an_existing_move_follows_the_branch() {
+    let log = main_line(2);
+    let mut tree = GameTree::from_log(&log);
+    tree.go_to(GameTree::ROOT);
+
+    let first = log.commands()[0].action;
+    let node = tree.play(first).unwrap();
+    assert_eq!(tree.children(GameTree::ROOT), &[node]);
+
+    // 不合法的走法不改变当前位置
+    assert!(tree.play(MoveAction::Place(0)).is_err());
+    assert_eq!(tree.current(), node);
+}
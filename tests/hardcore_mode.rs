@@ -0,0 +1,75 @@
+// 硬核模式测试 - 对局中不显示可落子指示器和任何提示，子数和阶段信息在终局后才显示
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    assist::{AssistLevel, MatchRules},
+    preferences::Preferences,
+    state::GameState,
+    ui::{update_hud_visibility, PhaseInfoText, ScoreCount},
+    PlayerColor,
+};
+
+#[test]
+fn hardcore_disables_every_board_assist() {
+    let rules = AssistLevel::Hardcore.rules();
+    assert_eq!(
+        rules,
+        MatchRules {
+            legal_move_hints: false,
+            corner_warning: false,
+            coach: false,
+            score_projection: false,
+            best_move_hint: false,
+        }
+    );
+    assert!(!AssistLevel::Hardcore.shows_flip_counts());
+    assert!(AssistLevel::Hardcore.hides_score());
+    assert!(!AssistLevel::Off.hides_score());
+}
+
+fn hud_world(level: AssistLevel, state: GameState) -> (World, Vec<Entity>) {
+    let mut world = World::new();
+    let mut preferences = Preferences::default();
+    preferences.assist.level = level;
+    world.insert_resource(preferences);
+    world.insert_resource(State::new(state));
+    let hud = vec![
+        world
+            .spawn((ScoreCount(PlayerColor::Black), Visibility::default()))
+            .id(),
+        world
+            .spawn((ScoreCount(PlayerColor::White), Visibility::default()))
+            .id(),
+        world.spawn((PhaseInfoText, Visibility::default())).id(),
+    ];
+    (world, hud)
+}
+
+fn visibilities(world: &mut World, hud: &[Entity]) -> Vec<Visibility> {
+    world.run_system_once(update_hud_visibility).unwrap();
+    hud.iter()
+        .map(|&entity| *world.get::<Visibility>(entity).unwrap())
+        .collect()
+}
+
+#[test]
+fn score_is_hidden_until_the_game_ends() {
+    let (mut world, hud) = hud_world(AssistLevel::Hardcore, GameState::Playing);
+    assert_eq!(visibilities(&mut world, &hud), vec![Visibility::Hidden; 3]);
+
+    // 终局后显示
+    world.insert_resource(State::new(GameState::GameOver));
+    assert_eq!(
+        visibilities(&mut world, &hud),
+        vec![Visibility::Inherited; 3]
+    );
+}
+
+#[test]
+fn other_levels_keep_the_score_visible() {
+    let (mut world, hud) = hud_world(AssistLevel::Off, GameState::Playing);
+    assert_eq!(
+        visibilities(&mut world, &hud),
+        vec![Visibility::Inherited; 3]
+    );
+}
@@ -0,0 +1,52 @@
+// 输入闸门测试 - 模拟快速连点时的落子输入序列
+
+use reversi::{
+    game::{Board, GameLog, MoveAction, PlayerColor},
+    input::{InputGate, TurnState, INPUT_DEBOUNCE},
+};
+use std::time::Duration;
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn double_tap_queues_a_single_move() {
+    let log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    let turn = TurnState::of(&log);
+    let mut gate = InputGate::default();
+
+    assert!(gate.admit(turn, ms(1000), true));
+    // 双击的第二下落在去抖时间内
+    assert!(!gate.admit(turn, ms(1040), true));
+    // 去抖时间过后，同一回合仍然不再接受落子
+    assert!(!gate.admit(turn, ms(1000) + INPUT_DEBOUNCE * 4, true));
+}
+
+#[test]
+fn next_turn_unlocks_input() {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    let mut gate = InputGate::default();
+
+    assert!(gate.admit(TurnState::of(&log), ms(1000), true));
+    log.apply(log.next_command(MoveAction::Place(19))).unwrap();
+    log.apply(log.next_command(MoveAction::Place(18))).unwrap();
+
+    // 回到黑棋回合，但命令序号已经前进
+    assert!(gate.admit(TurnState::of(&log), ms(2000), true));
+}
+
+#[test]
+fn illegal_taps_do_not_lock_the_turn() {
+    let log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    let turn = TurnState::of(&log);
+    let mut gate = InputGate::default();
+
+    assert!(gate.admit(turn, ms(1000), false));
+    assert!(!gate.admit(turn, ms(1050), true));
+    assert!(gate.admit(turn, ms(1200), true));
+
+    // 已接受的落子被取消后可以重新选择
+    gate.release();
+    assert!(gate.admit(turn, ms(1400), true));
+}
@@ -0,0 +1,163 @@
+// 输入来源测试 - 新对局按模式分配本机玩家和AI的座位并保留脚本座位，脚本座位通过真实的走棋命令流程下完整盘棋
+
+use bevy::prelude::*;
+use reversi::{
+    accessibility::AnnouncementEvent,
+    audio::{MoveOutcome, PlaySoundEvent},
+    game::{Board, GameLog, MoveAction, MoveCommand},
+    input::{InputSource, PlayerSeats},
+    plugin::{
+        gameplay::{apply_move_commands, play_scripted_moves},
+        GameSession,
+    },
+    ui::{CurrentPlayer, FlipChainEvent, ToastEvent},
+    PlayerColor,
+};
+
+/// 从标准初始局面开始，双方总是走最后一个合法位置，返回双方的走法列表和终局局面
+fn scripted_game() -> (Vec<u8>, Vec<u8>, Board) {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    let (mut black, mut white) = (Vec::new(), Vec::new());
+    while !log.is_game_over() {
+        if log.must_pass() {
+            log.apply(log.next_command(MoveAction::Pass)).unwrap();
+            continue;
+        }
+        let player = log.to_move();
+        let position = log
+            .board()
+            .get_valid_moves_list(player)
+            .last()
+            .unwrap()
+            .position;
+        match player {
+            PlayerColor::Black => black.push(position),
+            PlayerColor::White => white.push(position),
+        }
+        log.apply(log.next_command(MoveAction::Place(position)))
+            .unwrap();
+    }
+    (black, white, log.board())
+}
+
+fn scripted_app(seats: PlayerSeats) -> App {
+    let mut app = App::new();
+    app.insert_resource(seats)
+        .insert_resource(CurrentPlayer(PlayerColor::Black))
+        .add_event::<MoveCommand>()
+        .add_event::<PlaySoundEvent>()
+        .add_event::<AnnouncementEvent>()
+        .add_event::<FlipChainEvent>()
+        .add_event::<MoveOutcome>()
+        .add_event::<ToastEvent>()
+        .add_systems(Update, (play_scripted_moves, apply_move_commands).chain());
+    let board = Board::new_standard();
+    app.world_mut()
+        .spawn((board, GameLog::new(board, PlayerColor::Black), GameSession));
+    app
+}
+
+fn log(app: &mut App) -> GameLog {
+    app.world_mut()
+        .query::<&GameLog>()
+        .single(app.world())
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn new_game_seats_human_and_ai_but_keeps_scripts() {
+    let mut seats = PlayerSeats::default();
+    seats.reset_for_game(Some(PlayerColor::White));
+    assert!(seats.source(PlayerColor::Black).is_human());
+    assert!(seats.source(PlayerColor::White).is_ai());
+
+    // 换色后AI改执黑，沙盒中双方都由本机玩家操作
+    seats.reset_for_game(Some(PlayerColor::Black));
+    assert!(seats.source(PlayerColor::Black).is_ai());
+    assert!(seats.source(PlayerColor::White).is_human());
+    seats.reset_for_game(None);
+    assert!(seats.source(PlayerColor::Black).is_human());
+
+    seats.assign(PlayerColor::White, InputSource::script([19, 18]));
+    seats.assign(PlayerColor::Black, InputSource::Network);
+    seats.reset_for_game(Some(PlayerColor::White));
+    assert_eq!(
+        seats.source(PlayerColor::White),
+        &InputSource::script([19, 18])
+    );
+    assert_eq!(seats.source(PlayerColor::Black), &InputSource::Network);
+}
+
+#[test]
+fn only_ai_and_scripts_pass_automatically() {
+    assert!(InputSource::Ai.passes_automatically());
+    assert!(InputSource::script([]).passes_automatically());
+    assert!(!InputSource::Human.passes_automatically());
+    assert!(!InputSource::Network.passes_automatically());
+}
+
+#[test]
+fn scripts_play_a_full_game_through_move_commands() {
+    let (black, white, final_board) = scripted_game();
+    let mut seats = PlayerSeats::default();
+    seats.assign(PlayerColor::Black, InputSource::script(black.clone()));
+    seats.assign(PlayerColor::White, InputSource::script(white.clone()));
+    let mut app = scripted_app(seats);
+
+    for _ in 0..=black.len() + white.len() {
+        app.update();
+    }
+
+    let log = log(&mut app);
+    assert!(log.is_game_over());
+    assert_eq!(log.board(), final_board);
+    let board = *app
+        .world_mut()
+        .query::<&Board>()
+        .single(app.world())
+        .unwrap();
+    assert_eq!(board, final_board);
+
+    let seats = app.world().resource::<PlayerSeats>();
+    for color in [PlayerColor::Black, PlayerColor::White] {
+        let InputSource::Script(script) = seats.source(color) else {
+            panic!("seat changed");
+        };
+        assert_eq!(script.remaining(), 0);
+    }
+}
+
+#[test]
+fn illegal_scripted_move_stalls_the_game() {
+    let mut seats = PlayerSeats::default();
+    // A1在初始局面不能落子
+    seats.assign(PlayerColor::Black, InputSource::script([0, 19]));
+    let mut app = scripted_app(seats);
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let log = log(&mut app);
+    assert!(log.commands().is_empty());
+    let InputSource::Script(script) = app
+        .world()
+        .resource::<PlayerSeats>()
+        .source(PlayerColor::Black)
+    else {
+        panic!("seat changed");
+    };
+    assert_eq!(script.remaining(), 1);
+}
+
+#[test]
+fn human_seat_waits_for_local_input() {
+    let mut seats = PlayerSeats::default();
+    seats.assign(PlayerColor::White, InputSource::script([18]));
+    let mut app = scripted_app(seats);
+
+    app.update();
+
+    assert!(log(&mut app).commands().is_empty());
+}
@@ -0,0 +1,40 @@
+// 合法走法缓存测试 - 缓存结果与直接计算一致，局面变化后重新计算
+
+use reversi::game::{Board, LegalMovesCache, PlayerColor};
+
+#[test]
+fn cached_moves_match_board() {
+    let mut board = Board::new_standard();
+    let mut cache = LegalMovesCache::default();
+    assert!(cache.update(&board));
+    assert!(!cache.update(&board));
+
+    for player in [PlayerColor::Black, PlayerColor::White] {
+        assert_eq!(cache.moves(&board, player), board.get_valid_moves(player));
+    }
+
+    let position = board.get_valid_moves_list(PlayerColor::Black)[0].position;
+    assert!(cache.is_legal(&board, position, PlayerColor::Black));
+    assert!(!cache.is_legal(&board, position, PlayerColor::White));
+
+    board.make_move(position, PlayerColor::Black);
+    // 缓存未刷新时直接计算新局面
+    assert_eq!(
+        cache.moves(&board, PlayerColor::White),
+        board.get_valid_moves(PlayerColor::White)
+    );
+    assert!(cache.update(&board));
+    assert!(!cache.is_game_over(&board));
+}
+
+#[test]
+fn full_board_is_game_over() {
+    let board = Board {
+        black: u64::MAX,
+        white: 0,
+    };
+    let mut cache = LegalMovesCache::default();
+    cache.update(&board);
+    assert!(!cache.has_moves(&board, PlayerColor::White));
+    assert!(cache.is_game_over(&board));
+}
@@ -0,0 +1,91 @@
+// 本地化格式测试 - 数字、时长和日期按语言格式化，格式化参数随文本键一起解析
+
+use reversi::localization::{
+    DateTime, TextArg, TextKey, CHINESE_FORMAT, CHINESE_TEXTS, ENGLISH_FORMAT, ENGLISH_TEXTS,
+};
+use std::time::Duration;
+
+#[test]
+fn numbers_use_thousands_separators() {
+    assert_eq!(ENGLISH_FORMAT.number(0), "0");
+    assert_eq!(ENGLISH_FORMAT.number(999), "999");
+    assert_eq!(ENGLISH_FORMAT.number(1_000), "1,000");
+    assert_eq!(ENGLISH_FORMAT.number(1_234_567), "1,234,567");
+    assert_eq!(ENGLISH_FORMAT.decimal(12_345.67, 1), "12,345.7");
+    assert_eq!(ENGLISH_FORMAT.decimal(-1_500.0, 0), "-1,500");
+}
+
+#[test]
+fn large_numbers_are_abbreviated_per_language() {
+    assert_eq!(ENGLISH_FORMAT.compact(950), "950");
+    assert_eq!(ENGLISH_FORMAT.compact(2_100_000), "2.1M");
+    assert_eq!(ENGLISH_FORMAT.compact(3_000), "3K");
+    assert_eq!(ENGLISH_FORMAT.compact(125_400), "125K");
+    assert_eq!(CHINESE_FORMAT.compact(9_999), "9,999");
+    assert_eq!(CHINESE_FORMAT.compact(2_100_000), "210万");
+    assert_eq!(CHINESE_FORMAT.compact(150_000_000), "1.5亿");
+}
+
+#[test]
+fn durations_follow_language() {
+    let short = Duration::from_millis(4_200);
+    let long = Duration::from_secs(83);
+    assert_eq!(ENGLISH_FORMAT.duration(short), "4.2s");
+    assert_eq!(CHINESE_FORMAT.duration(short), "4.2秒");
+    assert_eq!(ENGLISH_FORMAT.duration(long), "1:23");
+    assert_eq!(CHINESE_FORMAT.duration(long), "1:23");
+}
+
+#[test]
+fn dates_follow_language_order() {
+    assert_eq!(
+        DateTime::from_unix_secs(1_700_000_000),
+        DateTime {
+            year: 2023,
+            month: 11,
+            day: 14,
+            hour: 22,
+            minute: 13,
+        }
+    );
+    assert_eq!(
+        ENGLISH_FORMAT.timestamp(1_700_000_000),
+        "Nov 14, 2023 22:13"
+    );
+    assert_eq!(
+        CHINESE_FORMAT.timestamp(1_700_000_000),
+        "2023年11月14日 22:13"
+    );
+    assert_eq!(ENGLISH_FORMAT.timestamp(0), "Jan 1, 1970 00:00");
+}
+
+#[test]
+fn format_args_resolve_per_language() {
+    let args = [
+        TextArg::Key(TextKey::BlackName),
+        TextArg::Duration(Duration::from_secs(75)),
+        TextArg::Duration(Duration::from_millis(2_500)),
+    ];
+    assert_eq!(
+        ENGLISH_TEXTS.format(TextKey::TimeStatsFormat, &args),
+        "Black: total 1:15 · avg 2.5s"
+    );
+    assert_eq!(
+        CHINESE_TEXTS.format(TextKey::TimeStatsFormat, &args),
+        "黑棋：共1:15 · 平均2.5秒"
+    );
+
+    let args = [
+        TextArg::Timestamp(1_700_000_000),
+        TextArg::Key(TextKey::OutcomeWin),
+        TextArg::Key(TextKey::DifficultyHard),
+        TextArg::Number(40),
+        TextArg::Number(24),
+    ];
+    assert!(ENGLISH_TEXTS
+        .format(TextKey::ArchiveEntry, &args)
+        .starts_with("Nov 14, 2023 22:13 · "));
+    assert!(CHINESE_TEXTS
+        .format(TextKey::ArchiveEntry, &args)
+        .ends_with(" · 40:24"));
+}
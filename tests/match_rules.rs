@@ -0,0 +1,109 @@
+// 对局规则测试 - 握手时下发规则，客户端按规则关闭辅助功能，本地对局不受限制
+
+mod common;
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use common::insert_board_assets;
+use reversi::{
+    assist::{ActiveMatchRules, AssistPreferences, MatchRules},
+    game::{Board, LegalMovesCache, PlayerColor},
+    history::HistoryPreview,
+    net::{
+        ClientMessage, ClientSync, MatchRelay, Role, ServerMessage, SpectatorView, PROTOCOL_VERSION,
+    },
+    preferences::Preferences,
+    ui::{update_valid_moves, BoardUI, CurrentPlayer, ValidMoveIndicator},
+};
+
+fn hello(role: Role) -> ClientMessage {
+    ClientMessage::Hello {
+        version: PROTOCOL_VERSION,
+        role,
+    }
+}
+
+#[test]
+fn welcome_carries_the_lobby_rules() {
+    let rules = MatchRules {
+        coach: true,
+        ..MatchRules::default()
+    };
+    let mut relay = MatchRelay::new(Board::new_standard()).with_rules(rules);
+    assert_eq!(relay.rules(), rules);
+
+    let mut player = ClientSync::default();
+    assert_eq!(player.rules(), None);
+    let welcome = relay.handle(1, hello(Role::Player));
+    player.receive(&welcome[0].message);
+    assert_eq!(player.rules(), Some(rules));
+
+    // 观战者收到相同的规则
+    let mut spectator = SpectatorView::default();
+    let welcome = relay.handle(2, hello(Role::Spectator));
+    assert!(matches!(
+        welcome[0].message,
+        ServerMessage::Welcome { rules: sent, .. } if sent == rules
+    ));
+    spectator.receive(&welcome[0].message);
+    assert_eq!(spectator.rules(), Some(rules));
+}
+
+#[test]
+fn default_rules_disable_evaluation_assists() {
+    let rules = MatchRelay::new(Board::new_standard()).rules();
+    assert_eq!(rules, MatchRules::default());
+    assert!(rules.legal_move_hints);
+    assert!(!rules.corner_warning && !rules.coach && !rules.score_projection);
+
+    // 本地对局不受限制
+    assert_eq!(
+        ActiveMatchRules::default().allowed(),
+        MatchRules::UNRESTRICTED
+    );
+}
+
+#[test]
+fn preferences_are_restricted_without_being_changed() {
+    let preferences = AssistPreferences {
+        enabled: true,
+        corner_warning: true,
+        coach: true,
+        ..AssistPreferences::default()
+    };
+    assert_eq!(preferences.permitted(MatchRules::UNRESTRICTED), preferences);
+
+    let permitted = preferences.permitted(MatchRules::default());
+    assert!(permitted.enabled);
+    assert!(!permitted.warns_about_corners());
+    assert!(!permitted.coach);
+}
+
+fn indicator_world(rules: ActiveMatchRules) -> World {
+    let mut world = World::new();
+    world.insert_resource(rules);
+    world.insert_resource(CurrentPlayer(PlayerColor::Black));
+    world.init_resource::<LegalMovesCache>();
+    insert_board_assets(&mut world);
+    world.init_resource::<HistoryPreview>();
+    world.init_resource::<Preferences>();
+    world.spawn(BoardUI);
+    world.spawn(Board::new_standard());
+    world
+}
+
+fn indicator_count(world: &mut World) -> usize {
+    world.run_system_once(update_valid_moves).unwrap();
+    world.query::<&ValidMoveIndicator>().iter(world).count()
+}
+
+#[test]
+fn legal_move_hints_follow_the_rules() {
+    let mut world = indicator_world(ActiveMatchRules::default());
+    assert_eq!(indicator_count(&mut world), 4);
+
+    let mut world = indicator_world(ActiveMatchRules(Some(MatchRules {
+        legal_move_hints: false,
+        ..MatchRules::default()
+    })));
+    assert_eq!(indicator_count(&mut world), 0);
+}
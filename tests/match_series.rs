@@ -0,0 +1,137 @@
+// 多局比赛测试 - 提前锁定胜局、打满局数、每局交换执子颜色、比分文本和比赛信息随历史对局保存
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    ai::{endgame::GameOutcome, AiDifficulty},
+    game::{Board, GameLog, PlayerColor},
+    localization::{TextArg, TextKey},
+    practice::SelectedOpening,
+    preferences::Preferences,
+    record::{ArchivedGame, GameArchive, GameRecord},
+    sandbox::SandboxMode,
+    series::{prepare_match_series, MatchFormat, MatchSeries},
+    ui::{entry_text, match_score_text},
+};
+
+/// 依次准备并记录每一局的结果，返回每局玩家的执子颜色
+fn play_series(series: &mut MatchSeries, results: &[GameOutcome]) -> Vec<PlayerColor> {
+    results
+        .iter()
+        .map(|outcome| {
+            series.prepare(MatchFormat::BestOf3);
+            let color = series.player_color;
+            series.record(*outcome);
+            color
+        })
+        .collect()
+}
+
+#[test]
+fn best_of_three_ends_once_clinched() {
+    let mut series = MatchSeries::default();
+    play_series(&mut series, &[GameOutcome::Win, GameOutcome::Win]);
+    assert!(series.is_decided());
+    assert_eq!(series.outcome(), Some(GameOutcome::Win));
+    assert_eq!(series.current_game(), 2);
+
+    // 比赛结束后的结果不再记录
+    series.record(GameOutcome::Loss);
+    assert_eq!(series.played(), 2);
+}
+
+#[test]
+fn split_games_go_the_distance() {
+    let mut series = MatchSeries::default();
+    play_series(&mut series, &[GameOutcome::Win, GameOutcome::Loss]);
+    assert!(!series.is_decided());
+    assert_eq!(series.current_game(), 3);
+
+    // 平局不算胜局，打满三局后按胜局数判断
+    play_series(&mut series, &[GameOutcome::Draw]);
+    assert!(series.is_decided());
+    assert_eq!(series.outcome(), Some(GameOutcome::Draw));
+}
+
+#[test]
+fn colors_swap_every_game() {
+    let mut series = MatchSeries::default();
+    let colors = play_series(
+        &mut series,
+        &[GameOutcome::Loss, GameOutcome::Win, GameOutcome::Loss],
+    );
+    assert_eq!(
+        colors,
+        vec![PlayerColor::Black, PlayerColor::White, PlayerColor::Black]
+    );
+    assert_eq!(series.outcome(), Some(GameOutcome::Loss));
+
+    // 本局结束后颜色保持不变，下一局开始新的比赛，玩家重新执黑
+    assert_eq!(series.player_color, PlayerColor::Black);
+    series.prepare(MatchFormat::BestOf5);
+    assert_eq!(series, MatchSeries::new(MatchFormat::BestOf5));
+}
+
+#[test]
+fn practice_and_sandbox_are_single_games() {
+    let mut world = World::new();
+    let mut preferences = Preferences::default();
+    preferences.rules.match_format = MatchFormat::BestOf3;
+    world.insert_resource(preferences);
+    world.init_resource::<SelectedOpening>();
+    world.insert_resource(SandboxMode(true));
+    world.init_resource::<MatchSeries>();
+
+    world.run_system_once(prepare_match_series).unwrap();
+    assert!(!world.resource::<MatchSeries>().is_match());
+
+    world.insert_resource(SandboxMode(false));
+    world.run_system_once(prepare_match_series).unwrap();
+    assert_eq!(world.resource::<MatchSeries>().format, MatchFormat::BestOf3);
+}
+
+#[test]
+fn score_text_shows_progress_then_result() {
+    let mut series = MatchSeries::default();
+    play_series(&mut series, &[GameOutcome::Win]);
+    let (key, args) = match_score_text(&series);
+    assert_eq!(key, TextKey::MatchScoreFormat);
+    assert_eq!(
+        args.0,
+        [2, 3, 1, 0].map(TextArg::Number).to_vec(),
+        "game 2 of 3, 1-0"
+    );
+
+    play_series(&mut series, &[GameOutcome::Win]);
+    let (key, args) = match_score_text(&series);
+    assert_eq!(key, TextKey::MatchWonFormat);
+    assert_eq!(args.0, [2, 0].map(TextArg::Number).to_vec());
+}
+
+#[test]
+fn series_games_are_archived_with_their_score() {
+    let mut series = MatchSeries::default();
+    play_series(&mut series, &[GameOutcome::Win, GameOutcome::Loss]);
+    let last = series.last_game().unwrap();
+    assert_eq!(last.game, 2);
+    assert_eq!(last.player_color, PlayerColor::White);
+    assert_eq!((last.wins, last.losses), (1, 1));
+
+    let log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    let game = ArchivedGame::new(
+        &log,
+        &GameRecord::default(),
+        GameOutcome::Loss,
+        AiDifficulty::Advanced,
+        1_700_000_000,
+    )
+    .with_series(Some(last));
+    assert_eq!(entry_text(&game).0, TextKey::ArchiveSeriesFormat);
+
+    let mut archive = GameArchive::default();
+    archive.push(game);
+    let contents = ron::to_string(&archive).unwrap();
+    assert_eq!(ron::from_str::<GameArchive>(&contents).unwrap(), archive);
+
+    // 单局对局不带比赛信息
+    assert_eq!(MatchSeries::default().last_game(), None);
+}
@@ -0,0 +1,99 @@
+// 菜单背景棋盘测试 - 初级AI缓慢自我对弈并更新缩略棋盘、减少动效时停止、背景不拦截菜单输入并随菜单状态清理
+
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use reversi::{
+    accessibility::MotionSettings,
+    ai::AiScheduler,
+    demo::{
+        advance_menu_backdrop, spawn_menu_backdrop, AttractMode, MenuBackdrop, MenuBackdropBoard,
+        MenuBackdropUI,
+    },
+    ui::MiniBoardView,
+};
+use std::time::Duration;
+
+/// 等待AI走棋的最多帧数
+const MAX_FRAMES: usize = 400;
+
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+enum Menu {
+    #[default]
+    Shown,
+    Closed,
+}
+
+fn menu_app(reduced_motion: bool) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            500,
+        )))
+        .insert_resource(MotionSettings { reduced_motion })
+        .init_resource::<AiScheduler>()
+        .init_resource::<AttractMode>()
+        .init_resource::<MenuBackdrop>()
+        .init_state::<Menu>()
+        .enable_state_scoped_entities::<Menu>()
+        .add_systems(OnEnter(Menu::Shown), spawn_menu_backdrop::<Menu>)
+        .add_systems(Update, advance_menu_backdrop.run_if(in_state(Menu::Shown)));
+    app.update();
+    app
+}
+
+fn backdrop_view(app: &mut App) -> MiniBoardView {
+    let mut views = app
+        .world_mut()
+        .query_filtered::<&MiniBoardView, With<MenuBackdropBoard>>();
+    views.single(app.world()).unwrap().clone()
+}
+
+#[test]
+fn backdrop_board_plays_itself() {
+    let mut app = menu_app(false);
+    let start = app.world().resource::<MenuBackdrop>().board();
+    assert_eq!(backdrop_view(&mut app).board, start);
+
+    for _ in 0..MAX_FRAMES {
+        app.update();
+        if app.world().resource::<MenuBackdrop>().moves_played() >= 2 {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    let backdrop = app.world().resource::<MenuBackdrop>();
+    assert!(backdrop.moves_played() >= 2);
+    let board = backdrop.board();
+    assert_ne!(board, start);
+    assert_eq!(backdrop_view(&mut app).board, board);
+}
+
+#[test]
+fn reduced_motion_keeps_the_board_still() {
+    let mut app = menu_app(true);
+    let start = app.world().resource::<MenuBackdrop>().board();
+    for _ in 0..20 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<MenuBackdrop>().moves_played(), 0);
+    assert_eq!(backdrop_view(&mut app).board, start);
+}
+
+#[test]
+fn backdrop_stays_behind_menus_and_leaves_with_them() {
+    let mut app = menu_app(false);
+    let mut roots = app
+        .world_mut()
+        .query_filtered::<(&GlobalZIndex, &Pickable), With<MenuBackdropUI>>();
+    let (z_index, pickable) = roots.single(app.world()).unwrap();
+    assert!(z_index.0 < 0);
+    assert!(!pickable.should_block_lower && !pickable.is_hoverable);
+
+    app.world_mut()
+        .resource_mut::<NextState<Menu>>()
+        .set(Menu::Closed);
+    app.update();
+    let mut roots = app
+        .world_mut()
+        .query_filtered::<Entity, With<MenuBackdropUI>>();
+    assert_eq!(roots.iter(app.world()).count(), 0);
+}
@@ -0,0 +1,53 @@
+// 缩略棋盘实体池测试 - 宿主删除时回收实体组，新的宿主复用同一组实体
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    game::Board,
+    ui::{
+        cleanup_marked_entities, reclaim_mini_boards, sync_mini_boards, BoardColors, MiniBoardPool,
+        MiniBoardView, ToDelete,
+    },
+};
+
+fn assigned_set(world: &World, host: Entity) -> Option<Entity> {
+    world
+        .get::<MiniBoardView>(host)
+        .and_then(MiniBoardView::set)
+}
+
+#[test]
+fn sets_are_reused_after_host_is_deleted() {
+    let mut world = World::new();
+    world.init_resource::<MiniBoardPool>();
+    world.init_resource::<BoardColors>();
+
+    let first = world
+        .spawn((
+            Node::default(),
+            MiniBoardView::new(Board::new_standard(), 5.0),
+        ))
+        .id();
+    world.run_system_once(sync_mini_boards).unwrap();
+    let set = assigned_set(&world, first).expect("set spawned for host");
+    assert_eq!(world.get::<ChildOf>(set).map(ChildOf::parent), Some(first));
+
+    // 宿主被标记删除时实体组回到池中，不随宿主一起删除
+    world.entity_mut(first).insert(ToDelete);
+    world.run_system_once(reclaim_mini_boards).unwrap();
+    world.run_system_once(cleanup_marked_entities).unwrap();
+    assert!(world.get_entity(first).is_err());
+    assert!(world.get_entity(set).is_ok());
+    assert!(world.get::<ChildOf>(set).is_none());
+    assert_eq!(world.resource::<MiniBoardPool>().available(), 1);
+
+    let second = world
+        .spawn((
+            Node::default(),
+            MiniBoardView::new(Board::new_standard(), 8.0),
+        ))
+        .id();
+    world.run_system_once(sync_mini_boards).unwrap();
+    assert_eq!(assigned_set(&world, second), Some(set));
+    assert_eq!(world.get::<ChildOf>(set).map(ChildOf::parent), Some(second));
+    assert_eq!(world.resource::<MiniBoardPool>().available(), 0);
+}
@@ -0,0 +1,125 @@
+// 缺失资源检查测试 - 全部资源结束加载后列出加载失败的文件、共用文件只列一次，以及警告面板的显示和关闭
+
+use bevy::{prelude::*, state::app::StatesPlugin};
+use reversi::{
+    boot::{validate_boot_assets, BootAssets, MissingAssets},
+    fonts::FontAssets,
+    localization::{LanguageSettings, TextKey, CHINESE_TEXTS, ENGLISH_TEXTS},
+    ui::{
+        handle_asset_warning_close, show_asset_warning_panel, AssetWarningCloseButton,
+        AssetWarningPanel, ToDelete,
+    },
+};
+
+/// 等待资源加载结束的最多帧数
+const MAX_FRAMES: usize = 200;
+
+/// 没有音频插件，音效文件不存在或没有加载器时都会加载失败
+fn app_with_sounds(paths: &[&'static str]) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, AssetPlugin::default()))
+        .init_asset::<Font>()
+        .init_asset::<AudioSource>()
+        .init_resource::<BootAssets>()
+        .init_resource::<MissingAssets>()
+        .init_resource::<LanguageSettings>()
+        .init_resource::<FontAssets>()
+        .add_systems(
+            Update,
+            (
+                validate_boot_assets,
+                show_asset_warning_panel,
+                handle_asset_warning_close,
+            )
+                .chain(),
+        );
+
+    let asset_server = app.world().resource::<AssetServer>().clone();
+    let mut boot_assets = app.world_mut().resource_mut::<BootAssets>();
+    for path in paths {
+        boot_assets.track(asset_server.load::<AudioSource>(*path), false);
+    }
+    app
+}
+
+fn run_until_checked(app: &mut App) {
+    for _ in 0..MAX_FRAMES {
+        app.update();
+        if app.world().resource::<MissingAssets>().checked {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    panic!("boot assets never finished loading");
+}
+
+#[test]
+fn failed_assets_are_listed_once() {
+    let mut app = app_with_sounds(&[
+        "sounds/not_packaged.ogg",
+        "sounds/also_missing.ogg",
+        "sounds/not_packaged.ogg",
+    ]);
+    run_until_checked(&mut app);
+
+    let missing = app.world().resource::<MissingAssets>();
+    assert_eq!(
+        missing.paths,
+        ["sounds/also_missing.ogg", "sounds/not_packaged.ogg"]
+    );
+}
+
+#[test]
+fn nothing_is_shown_when_every_asset_loads() {
+    let mut app = app_with_sounds(&[]);
+    run_until_checked(&mut app);
+    app.update();
+
+    assert!(app.world().resource::<MissingAssets>().paths.is_empty());
+    let mut panels = app
+        .world_mut()
+        .query_filtered::<Entity, With<AssetWarningPanel>>();
+    assert_eq!(panels.iter(app.world()).count(), 0);
+}
+
+#[test]
+fn warning_panel_lists_files_and_can_be_closed() {
+    let mut app = app_with_sounds(&["sounds/not_packaged.ogg"]);
+    run_until_checked(&mut app);
+    app.update();
+
+    let mut texts = app.world_mut().query::<&Text>();
+    assert!(texts
+        .iter(app.world())
+        .any(|text| text.0 == "sounds/not_packaged.ogg"));
+    let mut panels = app
+        .world_mut()
+        .query_filtered::<Entity, With<AssetWarningPanel>>();
+    let panel = panels.single(app.world()).unwrap();
+
+    let mut buttons = app
+        .world_mut()
+        .query_filtered::<Entity, With<AssetWarningCloseButton>>();
+    let button = buttons.single(app.world()).unwrap();
+    app.world_mut()
+        .entity_mut(button)
+        .insert(Interaction::Pressed);
+    app.update();
+    assert!(app.world().entity(panel).contains::<ToDelete>());
+
+    // 面板只在检查结果变化时生成，关闭后不会重新出现
+    app.update();
+    assert_eq!(panels.iter(app.world()).count(), 1);
+}
+
+#[test]
+fn warning_texts_are_localized() {
+    assert_eq!(
+        ENGLISH_TEXTS.get(TextKey::AssetWarningTitle),
+        "Missing game files"
+    );
+    assert_eq!(
+        CHINESE_TEXTS.get(TextKey::AssetWarningTitle),
+        "缺少游戏文件"
+    );
+}
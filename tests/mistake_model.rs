@@ -0,0 +1,75 @@
+// 失误模型测试 - 按温度在前k个走法中抽样，低温时总是走最佳走法
+
+use reversi::{
+    ai::{
+        evaluation::EvalConfig,
+        mistakes::{rank_moves, sample_move, selection_probabilities},
+    },
+    game::{Board, Move},
+    AiDifficulty, PlayerColor,
+};
+
+#[test]
+fn zero_temperature_always_picks_the_first_move() {
+    assert_eq!(
+        selection_probabilities(&[10, 50, 30], 0.0, 3),
+        vec![1.0, 0.0, 0.0]
+    );
+    assert_eq!(selection_probabilities(&[10, 50, 30], 40.0, 1), vec![1.0]);
+}
+
+#[test]
+fn probabilities_cover_only_the_top_k_and_favor_better_scores() {
+    let probabilities = selection_probabilities(&[100, 80, 40, -200], 30.0, 3);
+    assert_eq!(probabilities.len(), 3);
+    assert!((probabilities.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+    assert!(probabilities[0] > probabilities[1]);
+    assert!(probabilities[1] > probabilities[2]);
+
+    // 温度越高分布越平
+    let hot = selection_probabilities(&[100, 80, 40], 300.0, 3);
+    assert!(hot[2] > probabilities[2]);
+    assert!(hot[0] < probabilities[0]);
+}
+
+#[test]
+fn sampling_follows_cumulative_probabilities() {
+    let ranked = [
+        (Move { position: 19 }, 100),
+        (Move { position: 26 }, 100),
+        (Move { position: 37 }, 100),
+    ];
+    // 分数相同时三个走法的概率均为1/3
+    assert_eq!(sample_move(&ranked, 50.0, 3, 0.0), Some(ranked[0].0));
+    assert_eq!(sample_move(&ranked, 50.0, 3, 0.5), Some(ranked[1].0));
+    assert_eq!(sample_move(&ranked, 50.0, 3, 0.99), Some(ranked[2].0));
+    // 超出前k个的走法永远不会被选中
+    assert_eq!(sample_move(&ranked, 50.0, 2, 0.99), Some(ranked[1].0));
+    assert_eq!(sample_move(&[], 50.0, 3, 0.5), None);
+}
+
+#[test]
+fn ranking_puts_the_preferred_move_first() {
+    let board = Board::new_standard();
+    let eval = EvalConfig::standard();
+    let ranked = rank_moves(&board, PlayerColor::Black, eval, None);
+    assert_eq!(ranked.len(), 4);
+    assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+    let last = ranked.last().unwrap().0;
+    let preferred = rank_moves(&board, PlayerColor::Black, eval, Some(last));
+    assert_eq!(preferred[0].0, last);
+    assert_eq!(preferred[0].1, ranked[0].1);
+    assert_eq!(preferred.len(), 4);
+}
+
+#[test]
+fn difficulties_expose_temperature_and_top_k() {
+    let beginner = AiDifficulty::Beginner.get_search_params();
+    let advanced = AiDifficulty::Advanced.get_search_params();
+    let expert = AiDifficulty::Expert.get_search_params();
+    assert!(beginner.mistake_temperature > advanced.mistake_temperature);
+    assert!(beginner.mistake_top_k > advanced.mistake_top_k);
+    assert_eq!(expert.mistake_temperature, 0.0);
+    assert_eq!(expert.mistake_top_k, 1);
+}
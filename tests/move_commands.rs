@@ -0,0 +1,78 @@
+// 走棋命令日志测试 - 校验命令按序应用、回放一致和悔棋
+
+use reversi::game::{Board, CommandError, GameLog, MoveAction, PlayerColor};
+
+/// 每次选择第一个合法走法，无棋可走时跳过，直到对局结束
+fn play_out(log: &mut GameLog) {
+    while !log.board().is_game_over() {
+        let action = match log.board().get_valid_moves_list(log.to_move()).first() {
+            Some(first) => MoveAction::Place(first.position),
+            None => MoveAction::Pass,
+        };
+        log.apply(log.next_command(action))
+            .expect("generated command is valid");
+    }
+}
+
+#[test]
+fn replay_reproduces_final_board() {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    play_out(&mut log);
+
+    let replayed = GameLog::replay(Board::new_standard(), PlayerColor::Black, log.commands())
+        .expect("recorded commands replay cleanly");
+    assert_eq!(replayed, log);
+    assert!(replayed.board().is_game_over());
+}
+
+#[test]
+fn rejects_out_of_order_and_illegal_commands() {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    let first = log.next_command(MoveAction::Place(19));
+    log.apply(first).expect("D3 is a legal opening move");
+
+    // 重复发送的旧命令被序号校验拒绝
+    assert_eq!(
+        log.apply(first),
+        Err(CommandError::OutOfSequence {
+            expected: 1,
+            actual: 0
+        })
+    );
+
+    // 轮到白棋时黑棋不能走
+    let mut wrong_player = log.next_command(MoveAction::Place(18));
+    wrong_player.player = PlayerColor::Black;
+    assert_eq!(
+        log.apply(wrong_player),
+        Err(CommandError::WrongPlayer {
+            expected: PlayerColor::White
+        })
+    );
+
+    // 有棋可走时不能跳过，也不能下在已占用的位置
+    assert_eq!(
+        log.apply(log.next_command(MoveAction::Pass)),
+        Err(CommandError::IllegalPass)
+    );
+    assert_eq!(
+        log.apply(log.next_command(MoveAction::Place(19))),
+        Err(CommandError::IllegalMove(19))
+    );
+    assert_eq!(log.next_sequence(), 1);
+}
+
+#[test]
+fn undo_restores_previous_position() {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    log.apply(log.next_command(MoveAction::Place(19)))
+        .expect("D3 is a legal opening move");
+    let before = log.clone();
+
+    let reply = log.next_command(MoveAction::Place(18));
+    log.apply(reply).expect("C3 is a legal reply");
+
+    assert_eq!(log.undo(), Some(reply));
+    assert_eq!(log, before);
+    assert_eq!(log.to_move(), PlayerColor::White);
+}
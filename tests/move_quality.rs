@@ -0,0 +1,130 @@
+// 走法质量测试 - 两层搜索的分档、开局练习中后台分档和非玩家回合时清空
+
+use bevy::prelude::*;
+use reversi::{
+    ai::evaluation::EvalConfig,
+    config_assets::ActiveEvalConfig,
+    game::{parse_position_string, Board},
+    input::{InputSource, PlayerSeats},
+    practice::{
+        grade_moves, update_move_grades, MoveQuality, MoveQualityGrades, OpeningAutoplay,
+        OpeningPractice, SelectedOpening, BAD_MARGIN, DUBIOUS_MARGIN,
+    },
+    sandbox::SandboxMode,
+    ui::CurrentPlayer,
+    PlayerColor,
+};
+
+/// 黑方在B2（X位）旁边有一串棋子，C5会让白方得到好的应手
+fn position() -> Board {
+    parse_position_string(concat!(
+        "--------",
+        "-X------",
+        "--XO----",
+        "---XO---",
+        "---OX---",
+        "--------",
+        "--------",
+        "-------- X",
+    ))
+    .unwrap()
+    .0
+}
+
+fn square(notation: &str) -> u8 {
+    Board::notation_to_position(notation).unwrap()
+}
+
+#[test]
+fn losses_map_to_three_grades() {
+    assert_eq!(MoveQuality::from_loss(0), MoveQuality::Good);
+    assert_eq!(
+        MoveQuality::from_loss(DUBIOUS_MARGIN - 1),
+        MoveQuality::Good
+    );
+    assert_eq!(MoveQuality::from_loss(DUBIOUS_MARGIN), MoveQuality::Dubious);
+    assert_eq!(MoveQuality::from_loss(BAD_MARGIN), MoveQuality::Bad);
+}
+
+#[test]
+fn every_legal_move_is_graded_and_the_best_is_good() {
+    let board = position();
+    let grades = grade_moves(&board, PlayerColor::Black, EvalConfig::standard());
+    assert_eq!(
+        grades.len(),
+        board.get_valid_moves_list(PlayerColor::Black).len()
+    );
+    assert!(grades
+        .iter()
+        .any(|&(_, quality)| quality == MoveQuality::Good));
+    assert!(grades.contains(&(square("C5"), MoveQuality::Bad)));
+    assert!(grades.contains(&(square("D6"), MoveQuality::Good)));
+
+    // 开局的四个走法对称，都是好棋
+    let opening = grade_moves(
+        &Board::new_standard(),
+        PlayerColor::Black,
+        EvalConfig::standard(),
+    );
+    assert_eq!(opening.len(), 4);
+    assert!(opening
+        .iter()
+        .all(|&(_, quality)| quality == MoveQuality::Good));
+}
+
+fn practice_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<MoveQualityGrades>()
+        .init_resource::<OpeningAutoplay>()
+        .init_resource::<PlayerSeats>()
+        .init_resource::<ActiveEvalConfig>()
+        .insert_resource(SandboxMode(false))
+        .insert_resource(SelectedOpening(OpeningPractice::new(0)))
+        .insert_resource(CurrentPlayer(PlayerColor::Black))
+        .add_systems(Update, update_move_grades);
+    app.world_mut().spawn(position());
+    app
+}
+
+/// 等后台分档完成
+fn wait_for_grades(app: &mut App) {
+    app.update();
+    for _ in 0..1000 {
+        if !app.world().resource::<MoveQualityGrades>().is_pending() {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        app.update();
+    }
+    panic!("grading did not finish");
+}
+
+#[test]
+fn practice_games_grade_the_players_moves_in_the_background() {
+    let mut app = practice_app();
+    wait_for_grades(&mut app);
+
+    let grades = app.world().resource::<MoveQualityGrades>();
+    assert_eq!(grades.quality(square("C5")), Some(MoveQuality::Bad));
+    assert_eq!(grades.quality(square("A1")), None);
+
+    // 轮到AI时清空，指示器恢复主题颜色
+    app.world_mut()
+        .resource_mut::<PlayerSeats>()
+        .assign(PlayerColor::Black, InputSource::Ai);
+    app.update();
+    let grades = app.world().resource::<MoveQualityGrades>();
+    assert!(!grades.is_pending());
+    assert_eq!(grades.quality(square("C5")), None);
+}
+
+#[test]
+fn ordinary_games_are_not_graded() {
+    let mut app = practice_app();
+    app.insert_resource(SelectedOpening(None));
+    app.update();
+    let grades = app.world().resource::<MoveQualityGrades>();
+    assert!(!grades.is_pending());
+    assert_eq!(grades.quality(square("C5")), None);
+}
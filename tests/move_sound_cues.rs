@@ -0,0 +1,107 @@
+// 局面事件音效测试 - 占角、占满一条边和即将全灭的识别，以及落子后播放对应的提示音
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    audio::{classify_game_events, GameEventCue, MoveOutcome, PlaySoundEvent, SoundType},
+    game::parse_position_string,
+    ui::sound_name_key,
+    Board, PlayerColor,
+};
+
+/// 按"X"黑、"O"白、"."空的8行文本构造棋盘
+fn board_from(rows: [&str; 8]) -> Board {
+    let squares = rows.concat().replace('.', "-");
+    parse_position_string(&format!("{squares} X")).unwrap().0
+}
+
+fn outcome(before: Board, position: u8, player: PlayerColor) -> MoveOutcome {
+    let mut after = before;
+    assert!(after.make_move(position, player));
+    MoveOutcome {
+        player,
+        position,
+        before,
+        after,
+    }
+}
+
+#[test]
+fn ordinary_opening_moves_have_no_cue() {
+    let board = Board::new_standard();
+    assert_eq!(outcome(board, 19, PlayerColor::Black).classify(), None);
+}
+
+#[test]
+fn corner_and_edge_are_recognised() {
+    let board = board_from([
+        ".OOOOOOX", "..O.....", "...X....", "........", "........", "........", "........",
+        "........",
+    ]);
+    // A1占角同时占满上边，占角更重要
+    assert_eq!(
+        outcome(board, 0, PlayerColor::Black).classify(),
+        Some(GameEventCue::CornerCaptured)
+    );
+
+    let board = board_from([
+        "XOO.OOOX", "...X....", "........", "........", "........", "........", "........",
+        "........",
+    ]);
+    assert_eq!(
+        outcome(board, 3, PlayerColor::Black).classify(),
+        Some(GameEventCue::EdgeCompleted)
+    );
+}
+
+#[test]
+fn wipeout_threat_only_when_crossing_the_threshold_after_the_opening() {
+    // 白棋有4枚，黑棋下C4翻掉B4后只剩3枚
+    let board = board_from([
+        "XXXXXXXX", "XXXXXXXX", "XXXXXXXX", "XO......", "........", "........", "........",
+        ".....OOO",
+    ]);
+    let threat = outcome(board, 26, PlayerColor::Black);
+    assert_eq!(threat.after.count_pieces(PlayerColor::White), 3);
+    assert_eq!(threat.classify(), Some(GameEventCue::WipeoutThreat));
+
+    // 多一枚白棋时剩4枚，不提示
+    let mut board = board;
+    board.place_free(39, PlayerColor::White);
+    assert_eq!(outcome(board, 26, PlayerColor::Black).classify(), None);
+
+    // 开局阶段子数很少，不提示
+    let board = board_from([
+        "........", "........", "........", "XO......", "........", "........", "........",
+        ".....OOO",
+    ]);
+    assert_eq!(outcome(board, 26, PlayerColor::Black).classify(), None);
+}
+
+#[test]
+fn classifier_plays_the_cue_for_the_mover() {
+    let mut world = World::new();
+    world.init_resource::<Events<MoveOutcome>>();
+    world.init_resource::<Events<PlaySoundEvent>>();
+    let board = board_from([
+        ".OOOOOOX", "..O.....", "...X....", "........", "........", "........", "........",
+        "........",
+    ]);
+    world.send_event(outcome(board, 0, PlayerColor::Black));
+    world.send_event(outcome(Board::new_standard(), 19, PlayerColor::Black));
+    world.run_system_once(classify_game_events).unwrap();
+
+    let sounds: Vec<_> = world
+        .resource_mut::<Events<PlaySoundEvent>>()
+        .drain()
+        .map(|event| (event.sound_type, event.actor))
+        .collect();
+    assert_eq!(
+        sounds,
+        vec![(SoundType::CornerCapture, Some(PlayerColor::Black))]
+    );
+    // 提示音在音效测试面板中有自己的名称
+    assert_ne!(
+        sound_name_key(SoundType::CornerCapture),
+        sound_name_key(SoundType::EdgeComplete)
+    );
+}
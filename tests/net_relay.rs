@@ -0,0 +1,196 @@
+// 联机协议测试 - 校验版本握手、座位分配、观战命令流以及观战状态中的只读棋盘
+
+mod common;
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use common::{insert_board_assets, insert_fonts};
+use reversi::{
+    game::{Board, MoveAction, MoveCommand, PlayerColor},
+    localization::{TextArg, TextArgs, TextKey},
+    net::{
+        decode, encode, finish_spectating, receive_spectator_frames, start_spectating,
+        ClientMessage, HandshakeError, MatchRelay, MoveRejection, Role, ServerMessage,
+        SpectatorLink, SpectatorView, SyncEvent, PROTOCOL_VERSION,
+    },
+    ui::{
+        render_spectator_board, setup_board_ui, spawn_spectator_panel, Piece, SpectatorStatusText,
+        ToDelete,
+    },
+};
+
+fn hello(role: Role) -> ClientMessage {
+    ClientMessage::Hello {
+        version: PROTOCOL_VERSION,
+        role,
+    }
+}
+
+#[test]
+fn rejects_incompatible_versions_and_full_seats() {
+    let mut relay = MatchRelay::new(Board::new_standard());
+
+    let reply = relay.handle(
+        1,
+        ClientMessage::Hello {
+            version: PROTOCOL_VERSION + 1,
+            role: Role::Player,
+        },
+    );
+    assert!(matches!(
+        reply[0].message,
+        ServerMessage::Rejected(HandshakeError::UnsupportedVersion { .. })
+    ));
+
+    relay.handle(1, hello(Role::Player));
+    relay.handle(2, hello(Role::Player));
+    let reply = relay.handle(3, hello(Role::Player));
+    assert_eq!(
+        reply[0].message,
+        ServerMessage::Rejected(HandshakeError::SeatsFull)
+    );
+}
+
+#[test]
+fn spectator_follows_the_command_stream() {
+    let mut relay = MatchRelay::new(Board::new_standard());
+    relay.handle(1, hello(Role::Player));
+    relay.handle(2, hello(Role::Player));
+
+    // 黑棋先走一步，观战者之后才加入
+    let first = relay.log().next_command(MoveAction::Place(19));
+    relay.handle(1, ClientMessage::Move(first));
+
+    let mut spectator = SpectatorView::default();
+    for outgoing in relay.handle(3, SpectatorView::hello()) {
+        // 经过编码和解码，模拟真实传输
+        let frame = encode(&outgoing.message).unwrap();
+        spectator.receive(&decode(&frame).unwrap());
+    }
+    assert_eq!(relay.spectator_count(), 1);
+    assert_eq!(spectator.log().unwrap().board(), relay.log().board());
+
+    // 观战者不能走棋
+    let reply = relay.handle(
+        3,
+        ClientMessage::Move(MoveCommand {
+            sequence: 1,
+            player: PlayerColor::White,
+            action: MoveAction::Place(18),
+        }),
+    );
+    assert_eq!(
+        reply[0].message,
+        ServerMessage::MoveRejected(MoveRejection::ReadOnly)
+    );
+
+    // 白棋的走法广播给包括观战者在内的所有人
+    let second = relay.log().next_command(MoveAction::Place(18));
+    let broadcast = relay.handle(2, ClientMessage::Move(second));
+    assert_eq!(broadcast.len(), 3);
+    for outgoing in broadcast.iter().filter(|outgoing| outgoing.to == 3) {
+        let step = spectator.receive(&outgoing.message);
+        assert_eq!(step.event, None);
+    }
+    assert_eq!(spectator.log().unwrap(), relay.log());
+}
+
+/// 观战状态需要的资源
+fn spectator_world() -> World {
+    let mut world = World::new();
+    insert_board_assets(&mut world);
+    insert_fonts(&mut world);
+    world.init_resource::<SpectatorLink>();
+    world.init_resource::<Events<SyncEvent>>();
+    world
+}
+
+/// 把服务器发给观战者的消息编码后交给观战连接，再处理并渲染
+fn deliver(world: &mut World, messages: Vec<ServerMessage>) {
+    let mut link = world.resource_mut::<SpectatorLink>();
+    for message in messages {
+        link.incoming.push_back(encode(&message).unwrap());
+    }
+    world.run_system_once(receive_spectator_frames).unwrap();
+    world.run_system_once(render_spectator_board).unwrap();
+}
+
+/// 取出观战连接中等待发送的消息
+fn sent(world: &mut World) -> Vec<ClientMessage> {
+    let frames = std::mem::take(&mut world.resource_mut::<SpectatorLink>().outgoing);
+    frames.iter().map(|frame| decode(frame).unwrap()).collect()
+}
+
+fn status(world: &mut World) -> (TextKey, TextArgs) {
+    let mut query = world.query_filtered::<(&TextKey, &TextArgs), With<SpectatorStatusText>>();
+    let (key, args) = query.single(world).unwrap();
+    (*key, args.clone())
+}
+
+#[test]
+fn spectating_state_renders_the_command_stream_read_only() {
+    let mut relay = MatchRelay::new(Board::new_standard());
+    relay.handle(1, hello(Role::Player));
+    relay.handle(2, hello(Role::Player));
+    let first = relay.log().next_command(MoveAction::Place(19));
+    relay.handle(1, ClientMessage::Move(first));
+
+    // 进入观战：创建棋盘和状态面板并发送握手
+    let mut world = spectator_world();
+    world.run_system_once(setup_board_ui).unwrap();
+    world.run_system_once(spawn_spectator_panel).unwrap();
+    world.run_system_once(start_spectating).unwrap();
+    let hello = sent(&mut world);
+    assert_eq!(hello, vec![SpectatorView::hello()]);
+    assert_eq!(status(&mut world).0, TextKey::SpectatorConnecting);
+
+    // 中途加入时棋盘显示当前局面
+    let welcome = relay.handle(3, hello[0].clone());
+    deliver(
+        &mut world,
+        welcome.into_iter().map(|out| out.message).collect(),
+    );
+    // 被替换的棋子只标记删除，不算在显示的棋子中
+    let mut pieces = world.query_filtered::<&Piece, Without<ToDelete>>();
+    assert_eq!(pieces.iter(&world).count(), 5);
+    assert_eq!(
+        status(&mut world),
+        (
+            TextKey::SpectatorStatus,
+            TextArgs(vec![TextArg::Number(1), TextArg::Key(TextKey::WhiteName)])
+        )
+    );
+
+    // 之后的命令逐条应用，并回报局面哈希
+    let second = relay.log().next_command(MoveAction::Place(18));
+    let broadcast = relay.handle(2, ClientMessage::Move(second));
+    deliver(
+        &mut world,
+        broadcast
+            .into_iter()
+            .filter(|out| out.to == 3)
+            .map(|out| out.message)
+            .collect(),
+    );
+    assert_eq!(
+        world.resource::<SpectatorView>().log().unwrap(),
+        relay.log()
+    );
+    assert!(matches!(sent(&mut world)[..], [ClientMessage::Checksum(_)]));
+    let board = relay.log().board();
+    let shown = pieces
+        .iter(&world)
+        .map(|piece| (piece.position, piece.color))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        shown.len(),
+        (board.black | board.white).count_ones() as usize
+    );
+    for (position, color) in shown {
+        assert_eq!(board.get_piece(position), Some(color));
+    }
+
+    // 退出观战时通知服务器离开
+    world.run_system_once(finish_spectating).unwrap();
+    assert!(world.get_resource::<SpectatorView>().is_none());
+    assert_eq!(sent(&mut world), vec![ClientMessage::Leave]);
+}
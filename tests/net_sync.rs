@@ -0,0 +1,249 @@
+// 联机同步测试 - 局面字符串和哈希、每步交换哈希、失步后自动重新同步和同步提示文本
+
+use reversi::{
+    assist::MatchRules,
+    game::{
+        parse_position_string, position_hash, position_string, Board, CommandError, GameLog,
+        MoveAction, MoveCommand, PlayerColor,
+    },
+    localization::{TextKey, CHINESE_TEXTS, ENGLISH_TEXTS},
+    net::{
+        ClientMessage, ClientSync, DesyncReason, MatchRelay, PositionChecksum, Role, ServerMessage,
+        SyncEvent, PROTOCOL_VERSION,
+    },
+    ui::sync_notice_text,
+};
+
+fn hello(role: Role) -> ClientMessage {
+    ClientMessage::Hello {
+        version: PROTOCOL_VERSION,
+        role,
+    }
+}
+
+/// 两名对弈者已就座，观战者3的同步状态已完成握手
+fn joined_match() -> (MatchRelay, ClientSync) {
+    let mut relay = MatchRelay::new(Board::new_standard());
+    relay.handle(1, hello(Role::Player));
+    relay.handle(2, hello(Role::Player));
+
+    let mut sync = ClientSync::default();
+    for outgoing in relay.handle(3, hello(Role::Spectator)) {
+        sync.receive(&outgoing.message);
+    }
+    (relay, sync)
+}
+
+/// 对弈者走一步，返回发给观战者的消息
+fn play(relay: &mut MatchRelay, from: u64, position: u8) -> ServerMessage {
+    let command = relay.log().next_command(MoveAction::Place(position));
+    relay
+        .handle(from, ClientMessage::Move(command))
+        .into_iter()
+        .find(|outgoing| outgoing.to == 3)
+        .expect("command is broadcast to the spectator")
+        .message
+}
+
+#[test]
+fn position_string_round_trips_and_hash_is_stable() {
+    let mut board = Board::new_standard();
+    board.make_move(19, PlayerColor::Black);
+
+    let position = position_string(&board, PlayerColor::White);
+    assert_eq!(
+        parse_position_string(&position),
+        Some((board, PlayerColor::White))
+    );
+    assert_eq!(parse_position_string("X O"), None);
+    assert_eq!(parse_position_string(&position.replace(" O", " Z")), None);
+
+    // 同一局面哈希相同，轮到的一方不同时哈希不同
+    assert_eq!(
+        position_hash(&board, PlayerColor::White),
+        position_hash(&board, PlayerColor::White)
+    );
+    assert_ne!(
+        position_hash(&board, PlayerColor::White),
+        position_hash(&board, PlayerColor::Black)
+    );
+}
+
+#[test]
+fn every_command_carries_a_checksum_that_the_client_echoes() {
+    let (mut relay, mut sync) = joined_match();
+
+    let message = play(&mut relay, 1, 19);
+    let ServerMessage::Command { checksum, .. } = message else {
+        panic!("expected a command, got {message:?}");
+    };
+    assert_eq!(checksum, PositionChecksum::of(relay.log()));
+
+    let step = sync.receive(&message);
+    assert_eq!(step.event, None);
+    assert_eq!(step.reply, Some(ClientMessage::Checksum(checksum)));
+    assert_eq!(sync.log(), Some(relay.log()));
+
+    // 回报的哈希与权威日志一致时服务器不回复
+    assert!(relay.handle(3, step.reply.unwrap()).is_empty());
+}
+
+#[test]
+fn mismatched_checksum_triggers_a_resync() {
+    let (mut relay, mut sync) = joined_match();
+    play(&mut relay, 1, 19);
+
+    // 模拟传输中损坏的哈希
+    let ServerMessage::Command { command, checksum } = play(&mut relay, 2, 18) else {
+        panic!("expected a command");
+    };
+    let corrupted = ServerMessage::Command {
+        command,
+        checksum: PositionChecksum {
+            hash: checksum.hash ^ 1,
+            ..checksum
+        },
+    };
+
+    // 第一步没有收到，第二步在本地不合法
+    let step = sync.receive(&corrupted);
+    assert_eq!(step.reply, Some(ClientMessage::RequestResync));
+    assert!(matches!(
+        step.event,
+        Some(SyncEvent::Desync {
+            sequence: 1,
+            reason: DesyncReason::InvalidCommand(CommandError::OutOfSequence { .. })
+        })
+    ));
+    assert!(sync.is_awaiting_resync());
+
+    // 等待重新同步期间忽略后续命令
+    let third = play(&mut relay, 1, 17);
+    assert_eq!(sync.receive(&third), Default::default());
+
+    let resync = relay.handle(3, ClientMessage::RequestResync);
+    let ServerMessage::Resync { ref position, .. } = resync[0].message else {
+        panic!("expected a resync");
+    };
+    assert_eq!(
+        *position,
+        position_string(&relay.log().board(), relay.log().to_move())
+    );
+
+    // 本地停在开局局面，重新同步后补上三步的变化
+    let step = sync.receive(&resync[0].message);
+    assert_eq!(
+        step.event,
+        Some(SyncEvent::Resynced {
+            sequence: 3,
+            diff: Board::new_standard().diff(&relay.log().board()),
+        })
+    );
+    assert!(!sync.is_awaiting_resync());
+    assert_eq!(sync.log(), Some(relay.log()));
+}
+
+#[test]
+fn local_divergence_is_detected_by_checksum() {
+    let (mut relay, mut sync) = joined_match();
+
+    // 客户端本地错误地以白棋先行，命令合法但走完后的局面不同
+    let mut diverged = ClientSync::default();
+    diverged.receive(&ServerMessage::Welcome {
+        version: PROTOCOL_VERSION,
+        role: Role::Spectator,
+        seat: None,
+        initial: Board::new_standard(),
+        first_player: PlayerColor::White,
+        history: Vec::new(),
+        rules: MatchRules::default(),
+    });
+    let message = play(&mut relay, 1, 19);
+    let ServerMessage::Command { command, checksum } = message.clone() else {
+        panic!("expected a command");
+    };
+    let forged = ServerMessage::Command {
+        command: MoveCommand {
+            player: PlayerColor::White,
+            action: MoveAction::Place(20),
+            ..command
+        },
+        checksum,
+    };
+    let step = diverged.receive(&forged);
+    assert!(matches!(
+        step.event,
+        Some(SyncEvent::Desync {
+            sequence: 0,
+            reason: DesyncReason::ChecksumMismatch { expected, .. }
+        }) if expected == checksum.hash
+    ));
+
+    // 服务器收到不一致的哈希回报时主动重新同步
+    let reply = relay.handle(
+        3,
+        ClientMessage::Checksum(PositionChecksum {
+            sequence: 1,
+            hash: 0,
+        }),
+    );
+    assert!(matches!(reply[0].message, ServerMessage::Resync { .. }));
+    assert_eq!(sync.receive(&message).event, None);
+}
+
+#[test]
+fn resync_with_a_wrong_position_fails() {
+    let (mut relay, mut sync) = joined_match();
+    play(&mut relay, 1, 19);
+
+    let resync = relay.handle(3, ClientMessage::RequestResync);
+    let ServerMessage::Resync {
+        initial,
+        first_player,
+        history,
+        ..
+    } = resync[0].message.clone()
+    else {
+        panic!("expected a resync");
+    };
+    let step = sync.receive(&ServerMessage::Resync {
+        initial,
+        first_player,
+        history,
+        position: position_string(&Board::new_standard(), PlayerColor::Black),
+    });
+    assert_eq!(step.event, Some(SyncEvent::ResyncFailed { sequence: 1 }));
+    assert_eq!(sync.log(), Some(&GameLog::new(initial, first_player)));
+}
+
+#[test]
+fn unjoined_clients_cannot_request_resync() {
+    let (mut relay, _) = joined_match();
+    assert!(relay.handle(9, ClientMessage::RequestResync).is_empty());
+}
+
+#[test]
+fn sync_notices_are_localized() {
+    let (key, args) = sync_notice_text(&SyncEvent::Desync {
+        sequence: 4,
+        reason: DesyncReason::ChecksumMismatch {
+            expected: 1,
+            actual: 2,
+        },
+    });
+    assert_eq!(
+        ENGLISH_TEXTS.format(key, &args.0),
+        "Out of sync at move 5 (position mismatch), resyncing…"
+    );
+    assert_eq!(
+        CHINESE_TEXTS.format(key, &args.0),
+        "第5步与对方不同步（局面不一致），正在重新同步…"
+    );
+
+    let (key, args) = sync_notice_text(&SyncEvent::ResyncFailed { sequence: 7 });
+    assert_eq!(key, TextKey::NetResyncFailedFormat);
+    assert_eq!(
+        ENGLISH_TEXTS.format(key, &args.0),
+        "Resync failed after move 7, please reconnect"
+    );
+}
@@ -0,0 +1,87 @@
+// 神经网络评估测试 - 权重文件解析、量化推理和安装后的评估器选择（需要启用nn-eval特性）
+#![cfg(feature = "nn-eval")]
+
+use reversi::ai::{
+    evaluation::EvalConfig,
+    nn_eval::{install_network, installed_network, NnEvaluator, NnLoadError, NETWORK_INPUTS},
+    Evaluator, EvaluatorKind,
+};
+use reversi::game::{Board, PlayerColor};
+
+/// 两个隐藏单元的网络：第一个单元数己方的角，第二个单元数对方的子
+fn corner_network() -> NnEvaluator {
+    let mut input_weights = vec![0i8; 2 * NETWORK_INPUTS];
+    for corner in [0, 7, 56, 63] {
+        input_weights[corner] = 10;
+    }
+    for square in 0..64 {
+        input_weights[NETWORK_INPUTS + 64 + square] = 1;
+    }
+    NnEvaluator {
+        input_weights,
+        hidden_bias: vec![0, 0],
+        output_weights: vec![4, -2],
+        output_bias: 3,
+        output_shift: 1,
+    }
+}
+
+#[test]
+fn weights_round_trip_through_bytes() {
+    let network = corner_network();
+    assert_eq!(NnEvaluator::from_bytes(&network.to_bytes()), Ok(network));
+}
+
+#[test]
+fn malformed_weights_are_rejected() {
+    let bytes = corner_network().to_bytes();
+    assert_eq!(
+        NnEvaluator::from_bytes(b"ONNX0000"),
+        Err(NnLoadError::BadMagic)
+    );
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[4] = 9;
+    assert_eq!(
+        NnEvaluator::from_bytes(&wrong_version),
+        Err(NnLoadError::UnsupportedVersion(9))
+    );
+
+    assert!(matches!(
+        NnEvaluator::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(NnLoadError::SizeMismatch { .. })
+    ));
+}
+
+#[test]
+fn inference_uses_the_quantized_weights() {
+    let network = corner_network();
+    // 黑方占a1角，白方3个子
+    let board = Board {
+        black: 1 | (1 << 27),
+        white: (1 << 28) | (1 << 35) | (1 << 36),
+    };
+    // (3 + 10×4 + 3×(-2)) >> 1 = 18
+    assert_eq!(network.evaluate(&board, PlayerColor::Black), 18);
+
+    // 终局按子差计分
+    let full = Board {
+        black: u64::MAX << 8,
+        white: 0xFF,
+    };
+    assert_eq!(network.evaluate(&full, PlayerColor::White), -48 * 10_000);
+}
+
+#[test]
+fn installed_network_is_selected() {
+    assert!(install_network(corner_network()));
+    assert!(!install_network(corner_network()));
+    assert_eq!(installed_network(), Some(&corner_network()));
+
+    let board = Board::new_standard();
+    let evaluator = EvaluatorKind::Network.evaluator(EvalConfig::standard());
+    assert_eq!(
+        evaluator.evaluate(&board, PlayerColor::Black),
+        corner_network().evaluate(&board, PlayerColor::Black)
+    );
+}
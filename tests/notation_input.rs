@@ -0,0 +1,136 @@
+// 棋谱输入测试 - 键入记法后回车落子，记法无效或走法不合法时保留输入并标记
+
+use bevy::{
+    ecs::system::RunSystemOnce,
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+use reversi::{
+    ai::AiPlayer,
+    assist::CornerWarningState,
+    audio::PlaySoundEvent,
+    game::{Board, GameLog, PlayerColor},
+    history::HistoryPreview,
+    input::{handle_notation_input, notation_move, InputGate, NotationInput, NotationKeyAction},
+    ui::{NotationInputBox, UiState},
+    AiDifficulty, PlayerMoveEvent,
+};
+
+fn character(text: &str) -> Key {
+    Key::Character(text.into())
+}
+
+#[test]
+fn typing_keeps_two_lowercase_characters() {
+    let mut input = NotationInput::default();
+    assert_eq!(input.apply_key(&character("F")), NotationKeyAction::Edited);
+    assert_eq!(input.apply_key(&character("5")), NotationKeyAction::Edited);
+    assert_eq!(input.apply_key(&character("3")), NotationKeyAction::Ignored);
+    assert_eq!(input.text, "f5");
+
+    assert_eq!(input.apply_key(&Key::Backspace), NotationKeyAction::Edited);
+    assert_eq!(input.text, "f");
+    // 标点和功能键与输入框无关
+    assert_eq!(input.apply_key(&character("-")), NotationKeyAction::Ignored);
+    assert_eq!(input.apply_key(&Key::Escape), NotationKeyAction::Ignored);
+}
+
+#[test]
+fn enter_submits_only_non_empty_text() {
+    let mut input = NotationInput::default();
+    assert_eq!(input.apply_key(&Key::Enter), NotationKeyAction::Ignored);
+    input.apply_key(&character("d"));
+    assert_eq!(input.apply_key(&Key::Enter), NotationKeyAction::Submit);
+}
+
+#[test]
+fn editing_clears_rejection() {
+    let mut input = NotationInput {
+        text: "z9".to_string(),
+        rejected: true,
+    };
+    input.apply_key(&Key::Backspace);
+    assert!(!input.rejected);
+}
+
+#[test]
+fn notation_is_checked_against_the_position() {
+    let log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    assert_eq!(notation_move("f5", &log), Some((37, true)));
+    assert_eq!(notation_move("a1", &log), Some((0, false)));
+    assert_eq!(notation_move("z9", &log), None);
+}
+
+fn notation_world() -> World {
+    let mut world = World::new();
+    world.init_resource::<Events<KeyboardInput>>();
+    world.init_resource::<Events<PlayerMoveEvent>>();
+    world.init_resource::<Events<PlaySoundEvent>>();
+    world.init_resource::<NotationInput>();
+    world.init_resource::<UiState>();
+    world.init_resource::<CornerWarningState>();
+    world.init_resource::<HistoryPreview>();
+    world.init_resource::<InputGate>();
+    world.init_resource::<Time<Real>>();
+    world.spawn(GameLog::new(Board::new_standard(), PlayerColor::Black));
+    world.spawn(AiPlayer::new(AiDifficulty::Beginner, PlayerColor::White));
+    world.spawn(NotationInputBox);
+    world
+}
+
+fn type_keys(world: &mut World, keys: &[Key]) {
+    let window = world.spawn_empty().id();
+    for key in keys {
+        world.send_event(KeyboardInput {
+            key_code: KeyCode::Unidentified(bevy::input::keyboard::NativeKeyCode::Unidentified),
+            logical_key: key.clone(),
+            state: ButtonState::Pressed,
+            text: None,
+            repeat: false,
+            window,
+        });
+    }
+    world.run_system_once(handle_notation_input).unwrap();
+}
+
+fn sent_moves(world: &mut World) -> Vec<u8> {
+    world
+        .resource_mut::<Events<PlayerMoveEvent>>()
+        .drain()
+        .map(|event| event.position)
+        .collect()
+}
+
+#[test]
+fn enter_plays_the_typed_move() {
+    let mut world = notation_world();
+    type_keys(&mut world, &[character("f"), character("5"), Key::Enter]);
+    assert_eq!(sent_moves(&mut world), vec![37]);
+    assert!(world.resource::<NotationInput>().text.is_empty());
+}
+
+#[test]
+fn invalid_notation_is_rejected_and_kept() {
+    let mut world = notation_world();
+    type_keys(&mut world, &[character("z"), character("9"), Key::Enter]);
+    assert!(sent_moves(&mut world).is_empty());
+    let input = world.resource::<NotationInput>();
+    assert_eq!(input.text, "z9");
+    assert!(input.rejected);
+}
+
+#[test]
+fn typing_is_ignored_without_the_box() {
+    let mut world = notation_world();
+    let entity = world
+        .query_filtered::<Entity, With<NotationInputBox>>()
+        .single(&world)
+        .unwrap();
+    world.despawn(entity);
+    type_keys(&mut world, &[character("f"), character("5"), Key::Enter]);
+    assert!(sent_moves(&mut world).is_empty());
+    assert!(world.resource::<NotationInput>().text.is_empty());
+}
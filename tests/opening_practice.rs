@@ -0,0 +1,87 @@
+// 开局练习测试 - 开局库的走法都合法，练习模式依次自动走完开局
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    ai::opening_book::OPENING_BOOK,
+    game::{Board, GameLog, MoveCommand, PlayerColor},
+    practice::{autoplay_opening_moves, OpeningAutoplay, OpeningPractice, SelectedOpening},
+    ui::UiState,
+};
+use std::time::Duration;
+
+#[test]
+fn notation_round_trips() {
+    for position in 0..64 {
+        let notation = Board::position_to_notation(position);
+        assert_eq!(Board::notation_to_position(&notation), Some(position));
+        assert_eq!(
+            Board::notation_to_position(&notation.to_lowercase()),
+            Some(position)
+        );
+    }
+    assert_eq!(Board::notation_to_position("F5"), Some(37));
+    assert_eq!(Board::notation_to_position("I1"), None);
+    assert_eq!(Board::notation_to_position("A9"), None);
+    assert_eq!(Board::notation_to_position("A10"), None);
+}
+
+#[test]
+fn every_book_opening_is_playable() {
+    for opening in OPENING_BOOK {
+        let log = opening
+            .play(opening.plies())
+            .unwrap_or_else(|| panic!("{} is not playable", opening.name));
+        assert_eq!(log.commands().len(), opening.plies(), "{}", opening.name);
+    }
+}
+
+#[test]
+fn selection_cycles_through_book_and_back() {
+    let mut selected = SelectedOpening::default();
+    for index in 0..OPENING_BOOK.len() {
+        selected.cycle();
+        assert_eq!(selected.0, OpeningPractice::new(index));
+    }
+    selected.cycle();
+    assert_eq!(selected.0, None);
+}
+
+#[test]
+fn autoplay_plays_the_opening_then_stops() {
+    let practice = OpeningPractice {
+        plies: 3,
+        ..OpeningPractice::new(3).unwrap()
+    };
+    let expected = practice.opening().play(3).unwrap();
+
+    let mut world = World::new();
+    world.init_resource::<Events<MoveCommand>>();
+    world.init_resource::<UiState>();
+    world.init_resource::<Time>();
+    world.insert_resource(OpeningAutoplay::new(practice.positions()));
+    let board = Board::new_standard();
+    let entity = world.spawn(GameLog::new(board, PlayerColor::Black)).id();
+
+    while world.resource::<OpeningAutoplay>().is_playing() {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs(1));
+        world.run_system_once(autoplay_opening_moves).unwrap();
+
+        // 代替apply_move_commands应用本帧的命令
+        let commands: Vec<MoveCommand> = world
+            .resource_mut::<Events<MoveCommand>>()
+            .drain()
+            .collect();
+        assert_eq!(commands.len(), 1);
+        let mut log = world.get_mut::<GameLog>(entity).unwrap();
+        for command in commands {
+            log.apply(command).unwrap();
+        }
+    }
+
+    assert_eq!(
+        world.get::<GameLog>(entity).unwrap().commands(),
+        expected.commands()
+    );
+}
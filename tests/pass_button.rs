@@ -0,0 +1,129 @@
+// 跳过按钮测试 - 只有玩家确实无棋可走时才能跳过，跳过请求转换为跳过命令
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    ai::AiPlayer,
+    clock::GameClock,
+    game::{Board, GameLog, LegalMovesCache, MoveAction, MoveCommand, PlayerColor},
+    history::{HistoryPreview, MoveHistory},
+    localization::{TextArgs, TextKey, CHINESE_TEXTS, ENGLISH_TEXTS},
+    practice::OpeningAutoplay,
+    ui::{
+        handle_pass_requests, pass_available, update_game_status_text, CurrentPlayer,
+        GameStatusText, PassRequestEvent,
+    },
+    AiDifficulty,
+};
+
+/// 黑棋无棋可走而白棋可以走C1吃掉B1的局面：白A1，黑B1
+fn black_must_pass() -> GameLog {
+    let board = Board {
+        black: 1 << 1,
+        white: 1 << 0,
+    };
+    GameLog::new(board, PlayerColor::Black)
+}
+
+fn pass_world(log: GameLog, ai_color: PlayerColor) -> World {
+    let mut world = World::new();
+    world.init_resource::<Events<PassRequestEvent>>();
+    world.init_resource::<Events<MoveCommand>>();
+    world.init_resource::<OpeningAutoplay>();
+    world.spawn((log.board(), log));
+    world.spawn(AiPlayer::new(AiDifficulty::Beginner, ai_color));
+    world
+}
+
+fn request_pass(world: &mut World) -> Vec<MoveCommand> {
+    world.send_event(PassRequestEvent);
+    world.run_system_once(handle_pass_requests).unwrap();
+    world
+        .resource_mut::<Events<MoveCommand>>()
+        .drain()
+        .collect()
+}
+
+#[test]
+fn pass_requires_no_legal_moves() {
+    let log = black_must_pass();
+    assert!(!log.board().has_valid_moves(PlayerColor::Black));
+    assert!(log.board().has_valid_moves(PlayerColor::White));
+    assert!(pass_available(&log, None));
+
+    // 开局时黑棋有棋可走，不能跳过
+    let start = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    assert!(!pass_available(&start, None));
+}
+
+#[test]
+fn ai_turn_is_not_passed_by_the_player() {
+    let log = black_must_pass();
+    let ai_black = AiPlayer::new(AiDifficulty::Beginner, PlayerColor::Black);
+    let ai_white = AiPlayer::new(AiDifficulty::Beginner, PlayerColor::White);
+    assert!(!pass_available(&log, Some(&ai_black)));
+    assert!(pass_available(&log, Some(&ai_white)));
+}
+
+#[test]
+fn pass_request_becomes_a_pass_command() {
+    let mut world = pass_world(black_must_pass(), PlayerColor::White);
+    // 同一帧的多次点击只产生一条命令
+    world.send_event(PassRequestEvent);
+    let commands = request_pass(&mut world);
+    assert_eq!(
+        commands,
+        vec![MoveCommand {
+            sequence: 0,
+            player: PlayerColor::Black,
+            action: MoveAction::Pass,
+        }]
+    );
+
+    let mut log = black_must_pass();
+    log.apply(commands[0]).unwrap();
+    assert_eq!(log.to_move(), PlayerColor::White);
+}
+
+#[test]
+fn invalid_pass_requests_are_ignored() {
+    let start = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    let mut world = pass_world(start, PlayerColor::White);
+    assert!(request_pass(&mut world).is_empty());
+
+    let mut world = pass_world(black_must_pass(), PlayerColor::Black);
+    assert!(request_pass(&mut world).is_empty());
+
+    // 开局自动演示期间不接受跳过
+    let mut world = pass_world(black_must_pass(), PlayerColor::White);
+    world.insert_resource(OpeningAutoplay::new([19]));
+    assert!(request_pass(&mut world).is_empty());
+}
+
+#[test]
+fn status_line_names_the_passing_side_in_each_language() {
+    let log = black_must_pass();
+    let mut world = World::new();
+    world.init_resource::<LegalMovesCache>();
+    world.init_resource::<MoveHistory>();
+    world.init_resource::<HistoryPreview>();
+    world.init_resource::<GameClock>();
+    world.insert_resource(CurrentPlayer(PlayerColor::Black));
+    world.spawn((log.board(), log));
+    let status = world
+        .spawn((GameStatusText, TextKey::GameInProgress, TextArgs::default()))
+        .id();
+
+    world.run_system_once(update_game_status_text).unwrap();
+    let entity = world.entity(status);
+    let key = *entity.get::<TextKey>().unwrap();
+    let args = &entity.get::<TextArgs>().unwrap().0;
+    assert_eq!(key, TextKey::PassTurnFormat);
+    assert_eq!(
+        ENGLISH_TEXTS.format(key, args),
+        "Black has no valid moves. Pass turn."
+    );
+    assert_eq!(
+        CHINESE_TEXTS.format(key, args),
+        "黑棋无可用走法，跳过回合。"
+    );
+}
@@ -0,0 +1,101 @@
+// 性能信息层测试 - 文本格式、缺少数据时的占位、AI计算用时统计，以及F2开关随偏好设置保存
+
+use bevy::{
+    ecs::system::RunSystemOnce,
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, TaskPool},
+};
+use reversi::{
+    ai::{AiDifficulty, AiPlayer},
+    fonts::FontAssets,
+    game::{Board, PlayerColor},
+    input::InputMap,
+    localization::{LanguageSettings, TextKey, CHINESE_TEXTS, ENGLISH_TEXTS},
+    preferences::Preferences,
+    ui::{
+        manage_performance_overlay, toggle_performance_overlay, PerformanceOverlay,
+        PerformanceSample, ToDelete,
+    },
+};
+use std::time::Duration;
+
+#[test]
+fn sample_formats_every_field() {
+    let sample = PerformanceSample {
+        fps: Some(59.6),
+        frame_time_ms: Some(16.78),
+        entities: Some(1234.0),
+        ai_last: Some(Duration::from_millis(523)),
+        ai_average: Some(Duration::from_millis(480)),
+    };
+    let args = sample.text_args();
+    assert_eq!(
+        ENGLISH_TEXTS.format(TextKey::PerformanceOverlayFormat, &args.0),
+        "FPS 60 · 16.8 ms\nEntities 1,234\nAI move 523 ms (avg 480 ms)"
+    );
+    assert_eq!(
+        CHINESE_TEXTS.format(TextKey::PerformanceOverlayFormat, &args.0),
+        "帧率 60 · 16.8 毫秒\n实体 1,234\nAI每步 523 ms（平均 480 ms）"
+    );
+}
+
+#[test]
+fn missing_data_shows_placeholders() {
+    let sample = PerformanceSample::read(None, None);
+    assert_eq!(sample, PerformanceSample::default());
+    assert_eq!(
+        ENGLISH_TEXTS.format(TextKey::PerformanceOverlayFormat, &sample.text_args().0),
+        "FPS - · - ms\nEntities -\nAI move - (avg -)"
+    );
+
+    // 还没有走过棋的AI没有用时
+    let ai_player = AiPlayer::new(AiDifficulty::Beginner, PlayerColor::White);
+    let sample = PerformanceSample::read(None, Some(&ai_player));
+    assert_eq!(sample.ai_last, None);
+    assert_eq!(sample.ai_average, None);
+}
+
+#[test]
+fn ai_player_records_search_times() {
+    AsyncComputeTaskPool::get_or_init(TaskPool::new);
+    let mut ai_player = AiPlayer::new(AiDifficulty::Beginner, PlayerColor::Black);
+    for _ in 0..2 {
+        ai_player.start_thinking(&Board::new_standard());
+        while ai_player.check_thinking_result().is_none() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    let last = ai_player.last_search_time().unwrap();
+    let average = ai_player.average_search_time().unwrap();
+    assert!(last <= ai_player.watchdog_deadline());
+    assert!(average <= ai_player.watchdog_deadline());
+}
+
+#[test]
+fn f2_toggles_the_overlay() {
+    let mut world = World::new();
+    world.init_resource::<Preferences>();
+    world.init_resource::<InputMap>();
+    world.init_resource::<LanguageSettings>();
+    world.init_resource::<FontAssets>();
+    let mut keyboard = ButtonInput::<KeyCode>::default();
+    keyboard.press(KeyCode::F2);
+    world.insert_resource(keyboard);
+
+    world.run_system_once(toggle_performance_overlay).unwrap();
+    assert!(world.resource::<Preferences>().display.performance_overlay);
+    world.run_system_once(manage_performance_overlay).unwrap();
+    world.run_system_once(manage_performance_overlay).unwrap();
+    let overlays: Vec<Entity> = world
+        .query_filtered::<Entity, With<PerformanceOverlay>>()
+        .iter(&world)
+        .collect();
+    assert_eq!(overlays.len(), 1);
+
+    // 再按一次关闭，信息层标记为待删除
+    world.run_system_once(toggle_performance_overlay).unwrap();
+    assert!(!world.resource::<Preferences>().display.performance_overlay);
+    world.run_system_once(manage_performance_overlay).unwrap();
+    assert!(world.get::<ToDelete>(overlays[0]).is_some());
+}
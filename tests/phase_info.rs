@@ -0,0 +1,97 @@
+// 阶段信息测试 - 评估分解的计数、按子数划分阶段，以及分数下方的信息随棋盘变化轮换
+
+use bevy::prelude::*;
+use reversi::{
+    ai::evaluation::{EvalBreakdown, GamePhase},
+    game::{Board, PlayerColor},
+    localization::{TextArg, TextArgs, TextKey, CHINESE_TEXTS, ENGLISH_TEXTS},
+    ui::{phase_info, update_phase_info, PhaseInfoText},
+};
+
+/// 第一行全部为黑、第二行全部为白，其余位置按给定数量填入黑子
+fn edge_board(extra_black: u32) -> Board {
+    let extra = (0..extra_black).fold(0u64, |bits, index| bits | 1 << (16 + index));
+    Board {
+        black: 0xFF | extra,
+        white: 0xFF00,
+    }
+}
+
+#[test]
+fn breakdown_counts_each_side() {
+    let breakdown = EvalBreakdown::of(&Board::new_standard());
+    assert_eq!(breakdown.mobility, [4, 4]);
+    assert_eq!(breakdown.stable, [0, 0]);
+    assert_eq!(breakdown.corners, [0, 0]);
+    assert_eq!(breakdown.empties, 60);
+    assert!(!breakdown.odd_parity());
+
+    // 第一行的8个黑子都在边上，白棋的第二行只有两端在边上
+    let breakdown = EvalBreakdown::of(&edge_board(0));
+    assert_eq!(breakdown.stable_of(PlayerColor::Black), 8);
+    assert_eq!(breakdown.stable_of(PlayerColor::White), 2);
+    assert_eq!(breakdown.corners_of(PlayerColor::Black), 2);
+    assert_eq!(breakdown.empties, 48);
+}
+
+#[test]
+fn phases_follow_the_evaluation_stages() {
+    assert_eq!(GamePhase::of(4), GamePhase::Opening);
+    assert_eq!(GamePhase::of(20), GamePhase::Opening);
+    assert_eq!(GamePhase::of(21), GamePhase::Midgame);
+    assert_eq!(GamePhase::of(45), GamePhase::Midgame);
+    assert_eq!(GamePhase::of(46), GamePhase::Endgame);
+    assert_eq!(
+        GamePhase::of_board(&Board::new_standard()),
+        GamePhase::Opening
+    );
+}
+
+#[test]
+fn info_rotates_with_the_phase() {
+    let (key, args) = phase_info(&Board::new_standard());
+    assert_eq!(key, TextKey::PhaseMobilityFormat);
+    assert_eq!(ENGLISH_TEXTS.format(key, &args.0), "Mobility 4–4");
+
+    // 25子进入中局
+    let (key, args) = phase_info(&edge_board(9));
+    assert_eq!(key, TextKey::PhaseStableFormat);
+    assert_eq!(CHINESE_TEXTS.format(key, &args.0), "稳定子 11–2");
+
+    // 47子进入残局，剩余17个空位
+    let (key, args) = phase_info(&edge_board(31));
+    assert_eq!(key, TextKey::PhaseEmptiesFormat);
+    assert_eq!(
+        args,
+        TextArgs(vec![TextArg::Number(17), TextArg::Key(TextKey::ParityOdd)])
+    );
+    assert_eq!(ENGLISH_TEXTS.format(key, &args.0), "Empties 17 (odd)");
+}
+
+#[test]
+fn updates_after_the_board_changes() {
+    let mut world = World::new();
+    let board = world.spawn(Board::new_standard()).id();
+    let text = world
+        .spawn((
+            TextKey::PhaseMobilityFormat,
+            TextArgs::default(),
+            PhaseInfoText,
+        ))
+        .id();
+    let mut schedule = Schedule::default();
+    schedule.add_systems(update_phase_info);
+
+    schedule.run(&mut world);
+    assert_eq!(
+        world.get::<TextArgs>(text),
+        Some(&TextArgs(vec![TextArg::Number(4), TextArg::Number(4)]))
+    );
+
+    *world.get_mut::<Board>(board).unwrap() = edge_board(31);
+    schedule.run(&mut world);
+    assert_eq!(
+        world.get::<TextKey>(text),
+        Some(&TextKey::PhaseEmptiesFormat)
+    );
+}
@@ -0,0 +1,104 @@
+// 平台配置测试 - 资源档次的选择和精简资源路径
+
+use std::path::Path;
+
+use reversi::{
+    audio::SoundType,
+    fonts::CHINESE_FONT_PATH,
+    platform::{AssetTier, Platform, PlatformProfile, SHIPPED_COMPACT_ASSETS},
+};
+
+const PLATFORMS: [Platform; 3] = [Platform::Desktop, Platform::Web, Platform::Mobile];
+
+#[test]
+fn web_and_mobile_default_to_compact_assets() {
+    assert_eq!(
+        PlatformProfile::new(Platform::Desktop).asset_tier,
+        AssetTier::Full
+    );
+    assert_eq!(
+        PlatformProfile::new(Platform::Web).asset_tier,
+        AssetTier::Compact
+    );
+    assert_eq!(
+        PlatformProfile::new(Platform::Mobile).asset_tier,
+        AssetTier::Compact
+    );
+    assert_eq!(PlatformProfile::default().platform, Platform::current());
+    assert_eq!(
+        PlatformProfile::new(Platform::Web)
+            .with_asset_tier(AssetTier::Full)
+            .asset_tier,
+        AssetTier::Full
+    );
+}
+
+#[test]
+fn shipped_compact_assets_exist() {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+    for path in SHIPPED_COMPACT_ASSETS {
+        let compact = AssetTier::Compact.path(path);
+        assert!(
+            assets.join(&compact).is_file(),
+            "{path} is listed without {compact}"
+        );
+    }
+}
+
+#[test]
+fn default_profiles_only_pick_shipped_assets() {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+    for platform in PLATFORMS {
+        let profile = PlatformProfile::new(platform);
+        let paths = std::iter::once(CHINESE_FONT_PATH).chain(
+            SoundType::ALL
+                .iter()
+                .map(|sound_type| sound_type.asset_path()),
+        );
+        for path in paths {
+            let picked = profile.asset_path(path);
+            assert!(
+                assets.join(&picked).is_file(),
+                "{platform:?} picks missing asset {picked}"
+            );
+        }
+    }
+}
+
+#[test]
+fn assets_without_compact_files_fall_back_to_full_quality() {
+    let web = PlatformProfile::new(Platform::Web).with_compact_assets(&[CHINESE_FONT_PATH]);
+    assert_eq!(
+        web.asset_path(CHINESE_FONT_PATH),
+        "fonts/compact/NotoSerifCJKsc-Regular.otf"
+    );
+    for sound_type in SoundType::ALL {
+        assert_eq!(
+            web.asset_path(sound_type.asset_path()),
+            sound_type.asset_path()
+        );
+    }
+}
+
+#[test]
+fn compact_assets_live_in_compact_subdirectory() {
+    let web = PlatformProfile::new(Platform::Web)
+        .with_compact_assets(&[CHINESE_FONT_PATH, "sounds/victory.ogg"]);
+    assert_eq!(
+        web.asset_path(CHINESE_FONT_PATH),
+        "fonts/compact/NotoSerifCJKsc-Regular.otf"
+    );
+    assert_eq!(
+        web.asset_path(SoundType::Victory.asset_path()),
+        "sounds/compact/victory.ogg"
+    );
+    assert_eq!(AssetTier::Compact.path("readme.txt"), "compact/readme.txt");
+
+    let desktop = PlatformProfile::new(Platform::Desktop);
+    for sound_type in SoundType::ALL {
+        assert_eq!(
+            desktop.asset_path(sound_type.asset_path()),
+            sound_type.asset_path()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+// 局面字符串输入测试 - 键入和粘贴时的字符规范化、长度限制、载入有效和无效的局面，以及回车载入到查看器
+
+use bevy::{
+    ecs::system::RunSystemOnce,
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+use reversi::{
+    analysis::{
+        handle_position_input, AnalysisSession, GameTree, PositionInput, PositionInputBar,
+        PositionKeyAction, PositionStatus,
+    },
+    game::{position_string, Board, GameLog, MoveAction, PlayerColor, POSITION_STRING_LEN},
+    localization::LanguageSettings,
+};
+
+fn character(text: &str) -> Key {
+    Key::Character(text.into())
+}
+
+#[test]
+fn typing_keeps_only_position_characters() {
+    let mut input = PositionInput::default();
+    assert_eq!(input.apply_key(&character("x")), PositionKeyAction::Edited);
+    assert_eq!(input.apply_key(&character("O")), PositionKeyAction::Edited);
+    assert_eq!(input.apply_key(&character(".")), PositionKeyAction::Edited);
+    assert_eq!(input.apply_key(&Key::Space), PositionKeyAction::Edited);
+    assert_eq!(input.text, "XO- ");
+
+    // 其他字符不影响输入
+    assert_eq!(input.apply_key(&character("h")), PositionKeyAction::Ignored);
+    assert_eq!(input.apply_key(&Key::Backspace), PositionKeyAction::Edited);
+    assert_eq!(input.text, "XO-");
+    assert_eq!(input.apply_key(&Key::Enter), PositionKeyAction::Submit);
+
+    // 最多键入一个完整局面字符串的长度
+    for _ in 0..POSITION_STRING_LEN {
+        input.apply_key(&character("-"));
+    }
+    assert_eq!(input.text.len(), POSITION_STRING_LEN);
+    assert_eq!(input.apply_key(&character("X")), PositionKeyAction::Ignored);
+}
+
+#[test]
+fn loads_pasted_positions_and_rejects_invalid_ones() {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    log.apply(log.next_command(MoveAction::Place(19))).unwrap();
+    let expected = position_string(&log.board(), log.to_move());
+
+    // 从论坛复制的字符串可能带有首尾空白、小写字母和"."
+    let mut input = PositionInput::default();
+    input.paste(&format!(
+        "  {}\n",
+        expected.to_lowercase().replace('-', ".")
+    ));
+    assert_eq!(input.text, expected);
+    let tree = input.load().unwrap();
+    assert_eq!(input.status, PositionStatus::Loaded);
+    assert_eq!(tree.board(), log.board());
+    assert_eq!(tree.to_move(), PlayerColor::White);
+    assert_eq!(tree.ply(), 0);
+
+    input.paste("XO-- X");
+    assert!(input.load().is_none());
+    assert_eq!(input.status, PositionStatus::Invalid);
+
+    // 修改输入后恢复操作说明
+    input.apply_key(&Key::Backspace);
+    assert_eq!(input.status, PositionStatus::Hint);
+}
+
+#[test]
+fn copying_fills_the_input_with_the_current_position() {
+    let tree = GameTree::new(Board::new_standard(), PlayerColor::Black);
+    let mut input = PositionInput::default();
+    input.copy_current(&tree);
+    // 没有剪贴板的环境中提示失败，但输入栏中仍然有当前局面
+    assert_eq!(
+        input.text,
+        position_string(&Board::new_standard(), PlayerColor::Black)
+    );
+    assert!(matches!(
+        input.status,
+        PositionStatus::Copied | PositionStatus::ClipboardFailed
+    ));
+}
+
+#[test]
+fn enter_replaces_the_analysis_tree() {
+    let mut board = Board::new_standard();
+    board.make_move(19, PlayerColor::Black);
+    let mut input = PositionInput::default();
+    input.paste(&position_string(&board, PlayerColor::White));
+
+    let mut world = World::new();
+    world.insert_resource(input);
+    world.insert_resource(AnalysisSession {
+        tree: GameTree::new(Board::new_standard(), PlayerColor::Black),
+    });
+    world.init_resource::<ButtonInput<KeyCode>>();
+    world.init_resource::<LanguageSettings>();
+    world.init_resource::<Events<KeyboardInput>>();
+    world.spawn(PositionInputBar);
+    let window = world.spawn_empty().id();
+    world.send_event(KeyboardInput {
+        key_code: KeyCode::Enter,
+        logical_key: Key::Enter,
+        state: ButtonState::Pressed,
+        text: None,
+        repeat: false,
+        window,
+    });
+
+    world.run_system_once(handle_position_input).unwrap();
+    let tree = &world.resource::<AnalysisSession>().tree;
+    assert_eq!(tree.board(), board);
+    assert_eq!(tree.to_move(), PlayerColor::White);
+    assert_eq!(
+        world.resource::<PositionInput>().status,
+        PositionStatus::Loaded
+    );
+}
@@ -0,0 +1,112 @@
+// 练习中调整AI强度测试 - 难度阶梯的逐档调整、对局中修改难度、只在开局练习中显示滑块，以及暂停菜单按钮
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    ai::{adaptive::AdaptiveLevel, AiDifficulty, AiPlayer},
+    localization::{TextArg, TextArgs, TextKey},
+    practice::{OpeningPractice, SelectedOpening},
+    ui::{
+        handle_pause_difficulty_buttons, practice_difficulty, PauseDifficultyButton,
+        PauseDifficultyPip, PauseDifficultyText,
+    },
+    PlayerColor,
+};
+
+#[test]
+fn difficulty_steps_along_the_ladder() {
+    assert_eq!(
+        AiDifficulty::Beginner.stepped(1),
+        AiDifficulty::Intermediate
+    );
+    assert_eq!(AiDifficulty::Expert.stepped(-2), AiDifficulty::Intermediate);
+    assert_eq!(AiDifficulty::Beginner.stepped(-1), AiDifficulty::Beginner);
+    assert_eq!(
+        AiDifficulty::ExpertPlus.stepped(3),
+        AiDifficulty::ExpertPlus
+    );
+
+    // 不在阶梯中的难度按等级分找到相邻的一档
+    assert_eq!(AiDifficulty::Chaos(1).stepped(1), AiDifficulty::Beginner);
+    assert_eq!(AiDifficulty::Chaos(1).stepped(-1), AiDifficulty::Chaos(1));
+    let adaptive = AiDifficulty::Adaptive(AdaptiveLevel {
+        max_depth: 3,
+        mistake_probability: 0.1,
+    });
+    assert_eq!(adaptive.estimated_elo(), 950);
+    assert_eq!(adaptive.stepped(1), AiDifficulty::Intermediate);
+    assert_eq!(adaptive.stepped(-1), AiDifficulty::Beginner);
+}
+
+#[test]
+fn changing_difficulty_mid_game_uses_new_search_params() {
+    let mut ai_player = AiPlayer::new(AiDifficulty::Beginner, PlayerColor::White);
+    ai_player.set_difficulty(AiDifficulty::Expert);
+    assert_eq!(ai_player.difficulty, AiDifficulty::Expert);
+    assert_eq!(
+        ai_player.time_manager().remaining(),
+        AiPlayer::new(AiDifficulty::Expert, PlayerColor::White)
+            .time_manager()
+            .remaining()
+    );
+}
+
+#[test]
+fn slider_is_only_shown_in_opening_practice() {
+    let ai_player = AiPlayer::new(AiDifficulty::Advanced, PlayerColor::White);
+    let practice = SelectedOpening(OpeningPractice::new(0));
+    assert_eq!(
+        practice_difficulty(&practice, Some(&ai_player)),
+        Some(AiDifficulty::Advanced)
+    );
+    assert_eq!(practice_difficulty(&practice, None), None);
+    assert_eq!(
+        practice_difficulty(&SelectedOpening::default(), Some(&ai_player)),
+        None
+    );
+}
+
+/// 初级AI和暂停菜单中的滑块，按下增强按钮后运行一次处理系统
+fn press_stronger(selected_opening: SelectedOpening) -> World {
+    let mut world = World::new();
+    world.insert_resource(selected_opening);
+    world.spawn(AiPlayer::new(AiDifficulty::Beginner, PlayerColor::White));
+    world.spawn((Interaction::Pressed, PauseDifficultyButton(1)));
+    world.spawn((TextArgs::default(), PauseDifficultyText));
+    for pip in 0..AiDifficulty::PRACTICE_LADDER.len() {
+        world.spawn((BackgroundColor::default(), PauseDifficultyPip(pip)));
+    }
+    world
+        .run_system_once(handle_pause_difficulty_buttons)
+        .unwrap();
+    world
+}
+
+fn ai_difficulty(world: &mut World) -> AiDifficulty {
+    world.query::<&AiPlayer>().single(world).unwrap().difficulty
+}
+
+#[test]
+fn pause_menu_buttons_adjust_the_opponent() {
+    let mut world = press_stronger(SelectedOpening(OpeningPractice::new(0)));
+    assert_eq!(ai_difficulty(&mut world), AiDifficulty::Intermediate);
+
+    let args = world
+        .query_filtered::<&TextArgs, With<PauseDifficultyText>>()
+        .single(&world)
+        .unwrap();
+    assert_eq!(args.0, [TextArg::Key(TextKey::DifficultyMedium)]);
+    let filled: Vec<_> = world
+        .query::<(&PauseDifficultyPip, &BackgroundColor)>()
+        .iter(&world)
+        .map(|(PauseDifficultyPip(pip), background)| (*pip, background.0))
+        .collect();
+    let first = filled.iter().find(|(pip, _)| *pip == 0).unwrap().1;
+    let second = filled.iter().find(|(pip, _)| *pip == 1).unwrap().1;
+    let third = filled.iter().find(|(pip, _)| *pip == 2).unwrap().1;
+    assert_eq!(first, second);
+    assert_ne!(second, third);
+
+    // 普通对局中不能调整
+    let mut world = press_stronger(SelectedOpening::default());
+    assert_eq!(ai_difficulty(&mut world), AiDifficulty::Beginner);
+}
@@ -0,0 +1,95 @@
+// 预走测试 - 选择和取消预走格子，轮到玩家时合法则落子、不合法则丢弃
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    ai::AiPlayer,
+    game::{Board, GameLog, MoveAction, PlayerColor},
+    input::{play_premove, InputGate, Premove},
+    preferences::Preferences,
+    ui::CurrentPlayer,
+    AiDifficulty, PlayerMoveEvent,
+};
+
+#[test]
+fn selecting_the_same_square_again_cancels() {
+    let mut premove = Premove::default();
+    premove.toggle(19);
+    assert_eq!(premove.position(), Some(19));
+
+    // 选择另一格时替换
+    premove.toggle(26);
+    assert_eq!(premove.position(), Some(26));
+
+    premove.toggle(26);
+    assert_eq!(premove.position(), None);
+
+    premove.toggle(37);
+    premove.cancel();
+    assert_eq!(premove.position(), None);
+}
+
+/// AI执白的对局，黑棋（玩家）已经走了给定的步数
+fn premove_world(log: GameLog, premove: u8) -> World {
+    let mut world = World::new();
+    let mut preferences = Preferences::default();
+    preferences.input.premove = true;
+    world.insert_resource(preferences);
+    world.insert_resource(CurrentPlayer(log.to_move()));
+    let mut pending = Premove::default();
+    pending.toggle(premove);
+    world.insert_resource(pending);
+    world.init_resource::<InputGate>();
+    world.init_resource::<Time<Real>>();
+    world.init_resource::<Events<PlayerMoveEvent>>();
+    world.spawn(log);
+    world.spawn(AiPlayer::new(AiDifficulty::Beginner, PlayerColor::White));
+    world
+}
+
+fn played_moves(world: &mut World) -> Vec<u8> {
+    world.run_system_once(play_premove).unwrap();
+    world
+        .resource_mut::<Events<PlayerMoveEvent>>()
+        .drain()
+        .map(|event| event.position)
+        .collect()
+}
+
+#[test]
+fn waits_for_the_players_turn() {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    log.apply(log.next_command(MoveAction::Place(19))).unwrap();
+
+    // 轮到AI时保留预走
+    let mut world = premove_world(log.clone(), 20);
+    assert!(played_moves(&mut world).is_empty());
+    assert_eq!(world.resource::<Premove>().position(), Some(20));
+
+    // AI落子后预走的格子仍然合法，立即走出
+    log.apply(log.next_command(MoveAction::Place(18))).unwrap();
+    world.insert_resource(CurrentPlayer(log.to_move()));
+    for mut game_log in world.query::<&mut GameLog>().iter_mut(&mut world) {
+        *game_log = log.clone();
+    }
+    assert!(log.board().is_valid_move(17, PlayerColor::Black));
+    world.resource_mut::<Premove>().toggle(17);
+    assert_eq!(played_moves(&mut world), vec![17]);
+    assert_eq!(world.resource::<Premove>().position(), None);
+}
+
+#[test]
+fn illegal_premoves_are_discarded() {
+    let log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    let mut world = premove_world(log, 0);
+    assert!(played_moves(&mut world).is_empty());
+    assert_eq!(world.resource::<Premove>().position(), None);
+}
+
+#[test]
+fn turning_the_setting_off_cancels() {
+    let log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    let mut world = premove_world(log, 19);
+    world.resource_mut::<Preferences>().input.premove = false;
+    assert!(played_moves(&mut world).is_empty());
+    assert_eq!(world.resource::<Premove>().position(), None);
+}
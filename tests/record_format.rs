@@ -0,0 +1,182 @@
+// 存档二进制格式测试 - 往返、版本检查、损坏数据，以及从旧版本RON存档迁移
+
+use reversi::ai::{adaptive::AdaptiveLevel, endgame::GameOutcome, AiDifficulty};
+use reversi::game::{Board, GameLog, MoveAction, PlayerColor};
+use reversi::preferences::storage::StorageLocation;
+use reversi::record::{
+    format, ArchivedGame, GameArchive, GameRecord, RecordFormatError, RecordHeader, RecordedMove,
+    FORMAT_VERSION,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+fn sample_archive() -> GameArchive {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    let mut record = GameRecord::default();
+    for think_ms in [800, 1500, 2300, 120] {
+        let position = log.board().get_valid_moves_list(log.to_move())[0].position;
+        let command = log.next_command(MoveAction::Place(position));
+        log.apply(command).unwrap();
+        record.moves.push(RecordedMove {
+            player: command.player,
+            action: command.action,
+            think_time: Duration::from_millis(think_ms),
+        });
+    }
+
+    let mut archive = GameArchive::default();
+    archive.push(ArchivedGame::new(
+        &log,
+        &record,
+        GameOutcome::Loss,
+        AiDifficulty::Adaptive(AdaptiveLevel::default()),
+        1_700_000_000,
+    ));
+    archive.push(ArchivedGame::new(
+        &log,
+        &record,
+        GameOutcome::Win,
+        AiDifficulty::Expert,
+        1_700_000_600,
+    ));
+    archive
+}
+
+#[test]
+fn archive_round_trips_and_is_smaller_than_ron() {
+    let archive = sample_archive();
+    let bytes = archive.to_bytes().unwrap();
+    assert_eq!(GameArchive::from_bytes(&bytes).unwrap(), archive);
+
+    let header = RecordHeader::parse(&bytes).unwrap();
+    assert_eq!(header, RecordHeader::CURRENT);
+    assert_eq!(header.version, FORMAT_VERSION);
+
+    let ron = ron::ser::to_string_pretty(&archive, ron::ser::PrettyConfig::default()).unwrap();
+    assert!(bytes.len() < ron.len(), "{} >= {}", bytes.len(), ron.len());
+}
+
+#[test]
+fn rejects_foreign_and_truncated_data() {
+    assert_eq!(
+        GameArchive::from_bytes(b"RVS"),
+        Err(RecordFormatError::Truncated)
+    );
+    assert_eq!(
+        GameArchive::from_bytes(b"(games: [])"),
+        Err(RecordFormatError::BadMagic)
+    );
+
+    let bytes = sample_archive().to_bytes().unwrap();
+    assert!(matches!(
+        GameArchive::from_bytes(&bytes[..bytes.len() - 10]),
+        Err(RecordFormatError::Decode(_))
+    ));
+}
+
+/// 以后的版本：多了一个字段
+#[derive(Serialize)]
+struct FutureArchive {
+    games: Vec<ArchivedGame>,
+    cloud_revision: u64,
+}
+
+fn with_header(header: RecordHeader, bytes: &[u8]) -> Vec<u8> {
+    let mut data = header.to_bytes().to_vec();
+    data.extend_from_slice(&bytes[8..]);
+    data
+}
+
+#[test]
+fn newer_compatible_versions_are_readable() {
+    let games = sample_archive().games;
+    let future = FutureArchive {
+        games: games.clone(),
+        cloud_revision: 42,
+    };
+    let bytes = with_header(
+        RecordHeader {
+            version: FORMAT_VERSION + 1,
+            min_reader_version: FORMAT_VERSION,
+        },
+        &format::encode(&future).unwrap(),
+    );
+
+    // 新增字段被忽略
+    assert_eq!(GameArchive::from_bytes(&bytes).unwrap().games, games);
+}
+
+#[test]
+fn incompatible_versions_are_not_overwritten() {
+    let bytes = with_header(
+        RecordHeader {
+            version: FORMAT_VERSION + 1,
+            min_reader_version: FORMAT_VERSION + 1,
+        },
+        &sample_archive().to_bytes().unwrap(),
+    );
+    assert_eq!(
+        GameArchive::from_bytes(&bytes),
+        Err(RecordFormatError::UnsupportedVersion {
+            required: FORMAT_VERSION + 1,
+            supported: FORMAT_VERSION,
+        })
+    );
+
+    let archive = GameArchive::restore(Some(&bytes), None);
+    assert!(archive.games.is_empty());
+    assert!(archive.is_read_only());
+    // 只读存档在写入存储之前就拒绝保存
+    let storage = StorageLocation(std::env::temp_dir().join("reversi-record-format"));
+    assert!(archive.save(&storage).is_err());
+
+    // 损坏的存档可以被新的对局覆盖
+    let archive = GameArchive::restore(Some(b"garbage!!"), None);
+    assert!(!archive.is_read_only());
+}
+
+/// 旧版本的存档结构：没有后来增加的字段
+#[derive(Deserialize)]
+struct OlderArchive {
+    games: Vec<OlderGame>,
+}
+
+#[derive(Deserialize)]
+struct OlderGame {
+    finished_at: u64,
+    moves: Vec<RecordedMove>,
+}
+
+#[test]
+fn older_readers_skip_unknown_fields() {
+    let archive = sample_archive();
+    let older: OlderArchive = format::decode(&archive.to_bytes().unwrap()).unwrap();
+    assert_eq!(older.games.len(), 2);
+    assert_eq!(older.games[1].finished_at, 1_700_000_000);
+    assert_eq!(older.games[1].moves, archive.games[1].moves);
+}
+
+#[test]
+fn migrates_legacy_ron_archives() {
+    let archive = sample_archive();
+    let ron = ron::ser::to_string_pretty(&archive, ron::ser::PrettyConfig::default()).unwrap();
+
+    let migrated = GameArchive::restore(None, Some(&ron));
+    assert_eq!(migrated, archive);
+    assert!(!migrated.is_read_only());
+    for game in &migrated.games {
+        assert!(game.replay().is_ok());
+    }
+
+    // 已有二进制存档时不再读取RON存档
+    let bytes = GameArchive::default().to_bytes().unwrap();
+    assert!(GameArchive::restore(Some(&bytes), Some(&ron))
+        .games
+        .is_empty());
+
+    // 无法解析的RON存档视为空
+    assert_eq!(
+        GameArchive::restore(None, Some("not ron")),
+        GameArchive::default()
+    );
+}
@@ -0,0 +1,151 @@
+// 外部引擎测试 - 局面字符串和回复的格式、命令行参数，以及引擎出错时改用内置搜索
+#![cfg(feature = "remote_engine")]
+
+use reversi::{
+    ai::{
+        evaluation::EvalConfig,
+        remote::{http_response_body, parse_engine_move, position_string, RemoteEngine},
+    },
+    game::{Board, PlayerColor},
+    AiDifficulty,
+};
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    thread,
+};
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+/// 回复固定内容的引擎进程
+fn shell_engine(reply: &str) -> RemoteEngine {
+    RemoteEngine::Process {
+        program: "sh".to_string(),
+        args: vec!["-c".to_string(), format!("read position; echo {reply}")],
+    }
+}
+
+#[test]
+fn position_string_lists_squares_and_side_to_move() {
+    let position = position_string(&Board::new_standard(), PlayerColor::Black);
+    assert_eq!(position.len(), 66);
+    assert!(position.ends_with(" X"));
+    // D4和E5为白棋，E4和D5为黑棋
+    assert_eq!(&position[27..29], "OX");
+    assert_eq!(&position[35..37], "XO");
+    assert_eq!(position.matches('-').count(), 60);
+}
+
+#[test]
+fn replies_are_parsed_as_notation_or_pass() {
+    assert_eq!(parse_engine_move("F5\n").unwrap(), Some(37));
+    assert_eq!(parse_engine_move(" d3 ").unwrap(), Some(19));
+    assert_eq!(parse_engine_move("PS").unwrap(), None);
+    assert_eq!(parse_engine_move("pass").unwrap(), None);
+    assert!(parse_engine_move("Z9").is_err());
+}
+
+#[test]
+fn command_line_selects_the_engine() {
+    assert_eq!(
+        RemoteEngine::from_args(args("--difficulty expert")).unwrap(),
+        None
+    );
+    assert_eq!(
+        RemoteEngine::from_args(args("--remote-engine edax")).unwrap(),
+        Some(RemoteEngine::Process {
+            program: "edax".to_string(),
+            args: Vec::new(),
+        })
+    );
+    assert_eq!(
+        RemoteEngine::from_args(args("--remote-engine-url http://localhost:8080/move")).unwrap(),
+        Some(RemoteEngine::Http {
+            url: "http://localhost:8080/move".to_string(),
+        })
+    );
+    assert!(RemoteEngine::from_args(args("--remote-engine")).is_err());
+    assert!(RemoteEngine::from_args(args("--remote-engine-url https://example.com")).is_err());
+}
+
+#[test]
+fn http_response_requires_success_status() {
+    assert_eq!(
+        http_response_body("HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nF5").unwrap(),
+        "F5"
+    );
+    assert!(http_response_body("HTTP/1.1 500 Internal Server Error\r\n\r\n").is_err());
+    assert!(http_response_body("garbage").is_err());
+}
+
+#[test]
+fn process_engine_move_is_played() {
+    let board = Board::new_standard();
+    let chosen = shell_engine("D3").choose_move(
+        AiDifficulty::Beginner,
+        &board,
+        PlayerColor::Black,
+        EvalConfig::standard(),
+    );
+    assert_eq!(chosen.map(|m| m.position), Some(19));
+}
+
+#[test]
+fn illegal_or_failed_engine_falls_back_to_search() {
+    let board = Board::new_standard();
+    let legal: Vec<u8> = board
+        .get_valid_moves_list(PlayerColor::Black)
+        .iter()
+        .map(|m| m.position)
+        .collect();
+
+    let engines = [
+        shell_engine("A1"),
+        shell_engine("PS"),
+        RemoteEngine::Process {
+            program: "reversi-engine-that-does-not-exist".to_string(),
+            args: Vec::new(),
+        },
+    ];
+    for engine in engines {
+        let chosen = engine.choose_move(
+            AiDifficulty::Beginner,
+            &board,
+            PlayerColor::Black,
+            EvalConfig::standard(),
+        );
+        assert!(legal.contains(&chosen.unwrap().position));
+    }
+}
+
+#[test]
+fn http_engine_receives_position_and_replies() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/move", listener.local_addr().unwrap());
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0; 512];
+        // 请求头之后是66字节的局面字符串
+        while !String::from_utf8_lossy(&request).contains(" X") {
+            let read = stream.read(&mut buffer).unwrap();
+            request.extend_from_slice(&buffer[..read]);
+        }
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nC4")
+            .unwrap();
+        String::from_utf8(request).unwrap()
+    });
+
+    let engine = RemoteEngine::Http { url };
+    let reply = engine
+        .request_move(&Board::new_standard(), PlayerColor::Black)
+        .unwrap();
+    assert_eq!(reply, Some(26));
+
+    let request = server.join().unwrap();
+    assert!(request.starts_with("POST /move HTTP/1.1\r\n"));
+    assert!(request.ends_with(&position_string(&Board::new_standard(), PlayerColor::Black)));
+}
@@ -0,0 +1,89 @@
+// 规则示例测试 - 按当前局面选择示例走法和被翻转的棋子，无棋可走时的回退，以及说明文字
+
+use reversi::{
+    game::{Board, GameLog, MoveAction, PlayerColor},
+    localization::{CHINESE_TEXTS, ENGLISH_TEXTS},
+    ui::RulesExample,
+};
+
+#[test]
+fn opening_example_uses_the_first_of_equal_moves() {
+    let example = RulesExample::for_game(None);
+    assert_eq!(example.board, Board::new_standard());
+    assert_eq!(example.player, PlayerColor::Black);
+    // 开局四种走法都只翻转一枚棋子，取序号最小的D3
+    assert_eq!(Board::position_to_notation(example.position), "D3");
+    assert_eq!(
+        example.flipped,
+        vec![Board::notation_to_position("D4").unwrap()]
+    );
+
+    let (key, args) = example.text();
+    assert_eq!(
+        ENGLISH_TEXTS.format(key, &args.0),
+        "Example from the current board: Black plays at D3 (ring) and flips D4 (outlined)"
+    );
+    assert_eq!(
+        CHINESE_TEXTS.format(key, &args.0),
+        "当前局面示例：黑棋下在D3（圆环），翻转D4（描边）"
+    );
+}
+
+#[test]
+fn example_follows_the_live_game() {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    for notation in ["F5", "F6", "E6"] {
+        let position = Board::notation_to_position(notation).unwrap();
+        log.apply(log.next_command(MoveAction::Place(position)))
+            .unwrap();
+    }
+
+    let example = RulesExample::for_game(Some(&log));
+    let board = log.board();
+    assert_eq!(example.board, board);
+    assert_eq!(example.player, PlayerColor::White);
+    assert!(board.is_valid_move(example.position, PlayerColor::White));
+
+    // 示例翻转的棋子就是真正落子后变色的棋子，并且是所有走法中最多的
+    let mut after = board;
+    after.make_move(example.position, PlayerColor::White);
+    let changed: Vec<u8> = (0..64)
+        .filter(|&position| {
+            position != example.position && board.get_piece(position) != after.get_piece(position)
+        })
+        .collect();
+    assert_eq!(example.flipped, changed);
+    for valid in board.get_valid_moves_list(PlayerColor::White) {
+        let count: usize = board
+            .flipped_rays(valid.position, PlayerColor::White)
+            .iter()
+            .map(Vec::len)
+            .sum();
+        assert!(count <= example.flipped.len());
+    }
+}
+
+#[test]
+fn falls_back_when_the_side_to_move_must_pass() {
+    // 轮到白棋，但只有黑棋能在C1落子翻转B1
+    let board = Board {
+        black: 1 << Board::notation_to_position("A1").unwrap(),
+        white: 1 << Board::notation_to_position("B1").unwrap(),
+    };
+    assert!(board.get_valid_moves_list(PlayerColor::White).is_empty());
+    let example = RulesExample::for_position(&board, PlayerColor::White).unwrap();
+    assert_eq!(example.player, PlayerColor::Black);
+    assert_eq!(Board::position_to_notation(example.position), "C1");
+
+    // 双方都无棋可走时没有示例，当前对局的示例回到标准开局
+    let full = Board {
+        black: u64::MAX,
+        white: 0,
+    };
+    assert!(RulesExample::for_position(&full, PlayerColor::Black).is_none());
+    let log = GameLog::new(full, PlayerColor::Black);
+    assert_eq!(
+        RulesExample::for_game(Some(&log)).board,
+        Board::new_standard()
+    );
+}
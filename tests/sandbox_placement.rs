@@ -0,0 +1,99 @@
+// 自由落子沙盒测试 - 任意空格都能落子并照常翻转，棋盘下满才结束
+
+use reversi::{
+    game::{Board, CommandError, GameLog, MoveAction, PlacementRule, PlayerColor},
+    sandbox::SandboxMode,
+};
+
+fn sandbox_log() -> GameLog {
+    GameLog::new(Board::new_standard(), PlayerColor::Black).with_rule(PlacementRule::Free)
+}
+
+#[test]
+fn free_placement_flips_only_sandwiched_discs() {
+    // A1不夹住任何棋子：只落下一枚，不翻转
+    let mut board = Board::new_standard();
+    assert!(!board.is_valid_move(0, PlayerColor::Black));
+    assert!(board.place_free(0, PlayerColor::Black));
+    assert_eq!(board.get_piece(0), Some(PlayerColor::Black));
+    assert_eq!(board.count_pieces(PlayerColor::Black), 3);
+    assert_eq!(board.count_pieces(PlayerColor::White), 2);
+
+    // C4夹住D4：与标准规则的落子结果相同
+    let mut free = Board::new_standard();
+    let mut standard = Board::new_standard();
+    assert!(free.place_free(26, PlayerColor::Black));
+    assert!(standard.make_move(26, PlayerColor::Black));
+    assert_eq!(free, standard);
+
+    // 已有棋子的格子和棋盘外不能落子
+    assert!(!board.place_free(0, PlayerColor::White));
+    assert!(!board.place_free(27, PlayerColor::Black));
+    assert!(!board.place_free(64, PlayerColor::Black));
+}
+
+#[test]
+fn sandbox_log_accepts_any_empty_square() {
+    let mut log = sandbox_log();
+    log.apply(log.next_command(MoveAction::Place(0)))
+        .expect("any empty square is playable in the sandbox");
+    assert_eq!(log.to_move(), PlayerColor::White);
+
+    assert_eq!(
+        log.apply(log.next_command(MoveAction::Place(0))),
+        Err(CommandError::IllegalMove(0))
+    );
+    assert_eq!(
+        log.apply(log.next_command(MoveAction::Pass)),
+        Err(CommandError::IllegalPass)
+    );
+
+    // 标准规则下同样的落子不合法
+    let standard = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    assert_eq!(
+        standard.validate(&standard.next_command(MoveAction::Place(0))),
+        Err(CommandError::IllegalMove(0))
+    );
+}
+
+#[test]
+fn sandbox_ends_only_when_the_board_is_full() {
+    let mut log = sandbox_log();
+    assert_eq!(log.rule(), PlacementRule::Free);
+
+    for position in 0..64 {
+        if !log.board().is_empty(position) {
+            continue;
+        }
+        assert!(!log.is_game_over());
+        assert!(!log.must_pass());
+        log.apply(log.next_command(MoveAction::Place(position)))
+            .expect("empty square is playable");
+    }
+
+    assert!(log.is_game_over());
+    assert_eq!(log.board().get_empty_squares(), 0);
+    assert_eq!(
+        log.validate(&log.next_command(MoveAction::Place(0))),
+        Err(CommandError::GameOver)
+    );
+}
+
+#[test]
+fn undo_keeps_the_placement_rule() {
+    let mut log = sandbox_log();
+    log.apply(log.next_command(MoveAction::Place(0))).unwrap();
+    log.apply(log.next_command(MoveAction::Place(63))).unwrap();
+
+    assert!(log.undo().is_some());
+    assert_eq!(log.rule(), PlacementRule::Free);
+    assert_eq!(log.commands().len(), 1);
+    assert_eq!(log.board().get_piece(0), Some(PlayerColor::Black));
+    assert!(log.board().is_empty(63));
+}
+
+#[test]
+fn sandbox_mode_selects_free_placement() {
+    assert_eq!(SandboxMode(false).placement_rule(), PlacementRule::Standard);
+    assert_eq!(SandboxMode(true).placement_rule(), PlacementRule::Free);
+}
@@ -0,0 +1,75 @@
+// 存档冲突测试 - 修订号的读写格式、另一个实例保存后写入时发现冲突、新修订号总是更晚
+
+use reversi::{
+    localization::TextKey,
+    preferences::save_guard::{SaveLedger, SaveRevision},
+    ui::save_data_name_key,
+};
+
+fn revision(saved_at: u64, instance: u64) -> SaveRevision {
+    SaveRevision { saved_at, instance }
+}
+
+#[test]
+fn revision_text_round_trips() {
+    let saved = revision(1_700_000_000_123, 42);
+    assert_eq!(SaveRevision::parse(&saved.to_text()), Some(saved));
+    assert_eq!(SaveRevision::parse("not a revision"), None);
+    assert_eq!(SaveRevision::parse(""), None);
+}
+
+#[test]
+fn own_writes_do_not_conflict() {
+    let mut ledger = SaveLedger::new(1);
+    ledger.observe("stats.ron", None);
+    let (first, conflict) = ledger.prepare_write("stats.ron", None, 1000);
+    assert_eq!(first, revision(1000, 1));
+    assert_eq!(conflict, None);
+    ledger.commit("stats.ron", first);
+
+    let (second, conflict) = ledger.prepare_write("stats.ron", Some(first), 2000);
+    assert_eq!(second, revision(2000, 1));
+    assert_eq!(conflict, None);
+}
+
+#[test]
+fn another_instance_saving_in_between_is_a_conflict() {
+    let mut ledger = SaveLedger::new(1);
+    ledger.observe("stats.ron", Some(revision(500, 1)));
+
+    // 另一个实例保存过，时钟还比本实例快
+    let other = revision(5000, 2);
+    let (next, conflict) = ledger.prepare_write("stats.ron", Some(other), 3000);
+    let conflict = conflict.unwrap();
+    assert_eq!(conflict.key, "stats.ron");
+    assert_eq!(conflict.other, other);
+    // 仍然保存本实例的数据，修订号晚于另一个实例
+    assert_eq!(next, revision(5001, 1));
+
+    // 另一个实例第一次创建了本实例读取时还不存在的存档
+    let mut ledger = SaveLedger::new(1);
+    ledger.observe("preferences.ron", None);
+    let (_, conflict) = ledger.prepare_write("preferences.ron", Some(other), 3000);
+    assert!(conflict.is_some());
+}
+
+#[test]
+fn unseen_keys_are_not_checked() {
+    let ledger = SaveLedger::new(1);
+    let (_, conflict) = ledger.prepare_write("last_game.txt", Some(revision(10, 2)), 20);
+    assert_eq!(conflict, None);
+}
+
+#[test]
+fn conflicts_name_the_data() {
+    assert_eq!(save_data_name_key("stats.ron"), TextKey::SaveDataStats);
+    assert_eq!(
+        save_data_name_key("preferences.ron"),
+        TextKey::SaveDataPreferences
+    );
+    assert_eq!(
+        save_data_name_key("game_archive.bin"),
+        TextKey::SaveDataArchive
+    );
+    assert_eq!(save_data_name_key("last_game.txt"), TextKey::SaveDataOther);
+}
@@ -0,0 +1,140 @@
+// 残局比分预测测试 - 每个走法的精确终局比分、标注文本，以及只在开局练习的残局中且对局规则允许时标注
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use reversi::{
+    ai::{
+        endgame::{solve_each_move, solve_endgame},
+        AiPlayer,
+    },
+    assist::{ActiveMatchRules, MatchRules},
+    game::{Board, PlayerColor},
+    practice::{OpeningPractice, SelectedOpening},
+    preferences::Preferences,
+    sandbox::SandboxMode,
+    ui::{
+        projection_label, update_score_projections, CurrentPlayer, ScoreProjectionLabel,
+        ValidMoveIndicator, PROJECTION_MAX_EMPTIES,
+    },
+    AiDifficulty,
+};
+
+/// 随机走到只剩`empties`个空位、轮到的一方有棋可走的局面
+fn endgame_position(seed: u64, empties: u32) -> (Board, PlayerColor) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    loop {
+        let mut board = Board::new_standard();
+        let mut player = PlayerColor::Black;
+        while !board.is_game_over() {
+            let moves = board.get_valid_moves_list(player);
+            if board.get_empty_squares().count_ones() <= empties && !moves.is_empty() {
+                return (board, player);
+            }
+            if !moves.is_empty() {
+                board.make_move(moves[rng.gen_range(0..moves.len())].position, player);
+            }
+            player = player.opposite();
+        }
+    }
+}
+
+#[test]
+fn each_move_gets_its_exact_final_score() {
+    for seed in 0..5 {
+        let (board, player) = endgame_position(seed, 8);
+        let scores = solve_each_move(&board, player);
+        assert_eq!(scores.len(), board.get_valid_moves_list(player).len());
+
+        // 最好的走法与求解器的结果一致
+        let best = scores.iter().map(|(_, score)| *score).max().unwrap();
+        assert_eq!(best, solve_endgame(&board, player).score);
+
+        // 每个走法的比分等于对手在走后局面的求解结果取反
+        for (position, score) in scores {
+            let mut after = board;
+            after.make_move(position, player);
+            assert_eq!(score, -solve_endgame(&after, player.opposite()).score);
+        }
+    }
+}
+
+#[test]
+fn labels_show_the_sign() {
+    assert_eq!(projection_label(4), "+4");
+    assert_eq!(projection_label(-2), "-2");
+    assert_eq!(projection_label(0), "0");
+}
+
+fn projection_world(board: Board, player: PlayerColor, practice: bool) -> World {
+    let mut world = World::new();
+    world.insert_resource(CurrentPlayer(player));
+    world.insert_resource(SelectedOpening(if practice {
+        OpeningPractice::new(0)
+    } else {
+        None
+    }));
+    world.init_resource::<SandboxMode>();
+    world.init_resource::<ActiveMatchRules>();
+    world.init_resource::<Preferences>();
+    world.spawn(board);
+    world.spawn(AiPlayer::new(AiDifficulty::Beginner, player.opposite()));
+    for chess_move in board.get_valid_moves_list(player) {
+        world.spawn(ValidMoveIndicator {
+            position: chess_move.position,
+        });
+    }
+    world
+}
+
+fn label_scores(world: &mut World) -> Vec<(u8, i32)> {
+    let mut scores: Vec<(u8, i32)> = world
+        .query::<(&ChildOf, &ScoreProjectionLabel)>()
+        .iter(world)
+        .map(|(parent, label)| {
+            let indicator = world.get::<ValidMoveIndicator>(parent.parent()).unwrap();
+            (indicator.position, label.score)
+        })
+        .collect();
+    scores.sort_unstable();
+    scores
+}
+
+#[test]
+fn practice_endgame_indicators_are_labelled() {
+    let (board, player) = endgame_position(7, PROJECTION_MAX_EMPTIES);
+    let mut world = projection_world(board, player, true);
+    let system = world.register_system(update_score_projections);
+    world.run_system(system).unwrap();
+
+    let mut expected = solve_each_move(&board, player);
+    expected.sort_unstable();
+    assert_eq!(label_scores(&mut world), expected);
+
+    // 已经标注过的指示器不再重复标注
+    world.run_system(system).unwrap();
+    assert_eq!(label_scores(&mut world).len(), expected.len());
+}
+
+#[test]
+fn no_labels_outside_practice_or_before_the_endgame() {
+    let (board, player) = endgame_position(7, PROJECTION_MAX_EMPTIES);
+    let mut world = projection_world(board, player, false);
+    world.run_system_once(update_score_projections).unwrap();
+    assert!(label_scores(&mut world).is_empty());
+
+    let (board, player) = endgame_position(7, 14);
+    if board.get_empty_squares().count_ones() > PROJECTION_MAX_EMPTIES {
+        let mut world = projection_world(board, player, true);
+        world.run_system_once(update_score_projections).unwrap();
+        assert!(label_scores(&mut world).is_empty());
+    }
+}
+
+#[test]
+fn no_labels_when_match_rules_forbid() {
+    let (board, player) = endgame_position(7, PROJECTION_MAX_EMPTIES);
+    let mut world = projection_world(board, player, true);
+    world.insert_resource(ActiveMatchRules(Some(MatchRules::default())));
+    world.run_system_once(update_score_projections).unwrap();
+    assert!(label_scores(&mut world).is_empty());
+}
@@ -0,0 +1,181 @@
+// AI搜索说明测试 - Minimax统计搜索的节点数，搜索结果带上深度和节点数，说明文本按语言格式化，以及说明的显示和淡出
+
+use bevy::prelude::*;
+use reversi::{
+    accessibility::MotionSettings,
+    ai::{
+        evaluation::EvalConfig,
+        minimax::{find_best_move, find_best_move_with_time_limit},
+        SearchSummary,
+    },
+    game::{parse_position_string, Board, PlayerColor},
+    localization::{TextArgs, TextKey, CHINESE_TEXTS, ENGLISH_TEXTS},
+    plugin::AiSearchEvent,
+    preferences::Preferences,
+    ui::{
+        search_caption_alpha, search_caption_args, update_search_caption, SearchCaption,
+        SEARCH_CAPTION_FADE_SECS, SEARCH_CAPTION_HOLD_SECS,
+    },
+    AiDifficulty,
+};
+use std::time::Duration;
+
+/// 从标准开局双方总是走第一个合法位置，走出若干步后的中局局面，对手无棋可走时同一方继续走
+fn midgame(plies: usize) -> (Board, PlayerColor) {
+    let mut board = Board::new_standard();
+    let mut player = PlayerColor::Black;
+    for _ in 0..plies {
+        let moves = board.get_valid_moves(player);
+        board.make_move(moves.trailing_zeros() as u8, player);
+        if board.get_valid_moves(player.opposite()) != 0 {
+            player = player.opposite();
+        }
+    }
+    (board, player)
+}
+
+#[test]
+fn minimax_counts_every_visited_node() {
+    let board = Board::new_standard();
+    let eval = EvalConfig::standard();
+
+    // 黑方4种走法，之后白方各有3种应对
+    let shallow = find_best_move(&board, 1, PlayerColor::Black, eval);
+    assert_eq!(shallow.nodes_evaluated, 1 + 4);
+    let deeper = find_best_move(&board, 2, PlayerColor::Black, eval);
+    assert_eq!(deeper.nodes_evaluated, 1 + 4 * (1 + 3));
+
+    // 迭代加深的节点数包括每一轮
+    let result = find_best_move_with_time_limit(
+        &board,
+        Duration::from_secs(30),
+        2,
+        PlayerColor::Black,
+        eval,
+        None,
+        None,
+    );
+    assert_eq!(result.depth_reached, 2);
+    assert!(result.completed);
+    assert_eq!(
+        result.nodes_evaluated,
+        shallow.nodes_evaluated + deeper.nodes_evaluated
+    );
+}
+
+#[test]
+fn ai_search_reports_depth_and_nodes() {
+    let eval = EvalConfig::standard();
+    let (board, player) = midgame(20);
+    let result = AiDifficulty::Beginner.search_ai_move(
+        &board,
+        player,
+        eval,
+        None,
+        Duration::from_secs(30),
+        None,
+    );
+    let chosen = result.best_move.unwrap();
+    assert!(board.is_valid_move(chosen.position, player));
+    assert_eq!(result.depth_reached, 2);
+    assert!(result.nodes_evaluated > board.get_valid_moves(player).count_ones() as u64);
+
+    // 残局完美求解时深度为空位数
+    let (board, player) = parse_position_string(concat!(
+        "XXXXXXXX",
+        "XXXXXXXX",
+        "XXXOOOXX",
+        "XXOOOOXX",
+        "XXXOOOXX",
+        "XXXXXXXX",
+        "XXXXOO--",
+        "OOOO---- X",
+    ))
+    .unwrap();
+    let result = AiDifficulty::Expert.search_ai_move(
+        &board,
+        player,
+        eval,
+        None,
+        Duration::from_secs(30),
+        None,
+    );
+    assert!(result.best_move.is_some());
+    assert_eq!(result.depth_reached, 6);
+    assert!(result.nodes_evaluated > 0);
+}
+
+#[test]
+fn caption_is_formatted_per_language() {
+    let args = search_caption_args(&SearchSummary {
+        depth: 8,
+        nodes: 2_100_000,
+        elapsed: Duration::from_millis(1_400),
+    });
+    assert_eq!(
+        ENGLISH_TEXTS.format(TextKey::SearchCaptionFormat, &args.0),
+        "Searched 8 plies in 1.4s, 2.1M nodes"
+    );
+    assert_eq!(
+        CHINESE_TEXTS.format(TextKey::SearchCaptionFormat, &args.0),
+        "搜索了8步，用时1.4秒，210万个节点"
+    );
+}
+
+#[test]
+fn caption_fades_unless_motion_is_reduced() {
+    assert_eq!(search_caption_alpha(0.0, false), 1.0);
+    assert_eq!(search_caption_alpha(SEARCH_CAPTION_HOLD_SECS, false), 1.0);
+    let halfway = SEARCH_CAPTION_HOLD_SECS + SEARCH_CAPTION_FADE_SECS / 2.0;
+    assert!((search_caption_alpha(halfway, false) - 0.5).abs() < 1e-4);
+    assert_eq!(search_caption_alpha(halfway, true), 0.0);
+    let gone = SEARCH_CAPTION_HOLD_SECS + SEARCH_CAPTION_FADE_SECS;
+    assert_eq!(search_caption_alpha(gone, false), 0.0);
+}
+
+#[test]
+fn caption_appears_only_when_enabled() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_event::<AiSearchEvent>()
+        .init_resource::<Preferences>()
+        .init_resource::<MotionSettings>()
+        .add_systems(Update, update_search_caption);
+    let caption = app
+        .world_mut()
+        .spawn((
+            SearchCaption::default(),
+            TextArgs::default(),
+            TextColor::WHITE,
+            Visibility::Hidden,
+        ))
+        .id();
+    let summary = SearchSummary {
+        depth: 4,
+        nodes: 1_234,
+        elapsed: Duration::from_millis(300),
+    };
+
+    // 默认关闭
+    app.world_mut().send_event(AiSearchEvent { summary });
+    app.update();
+    assert_eq!(
+        app.world().get::<Visibility>(caption),
+        Some(&Visibility::Hidden)
+    );
+
+    app.world_mut()
+        .resource_mut::<Preferences>()
+        .display
+        .search_caption = true;
+    app.world_mut().send_event(AiSearchEvent { summary });
+    app.update();
+    assert_eq!(
+        app.world().get::<Visibility>(caption),
+        Some(&Visibility::Inherited)
+    );
+    assert_eq!(
+        app.world().get::<TextArgs>(caption),
+        Some(&search_caption_args(&summary))
+    );
+}
@@ -0,0 +1,112 @@
+// 快捷键帮助测试 - 默认绑定、按游戏状态筛选、修改绑定后帮助内容更新、输入棋谱时忽略字母键和开关帮助
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    input::{InputAction, InputMap},
+    localization::{TextArgs, TextKey, CHINESE_TEXTS, ENGLISH_TEXTS},
+    state::GameState,
+    ui::{shortcut_rows, toggle_shortcuts_overlay, NotationInputBox, UiState},
+};
+
+/// 帮助中每一行的英文文本
+fn english_rows(map: &InputMap, state: GameState) -> Vec<String> {
+    shortcut_rows(map, state)
+        .iter()
+        .map(|TextArgs(args)| ENGLISH_TEXTS.format(TextKey::ShortcutRowFormat, args))
+        .collect()
+}
+
+#[test]
+fn default_bindings_cover_every_action() {
+    let map = InputMap::default();
+    for action in InputAction::ALL {
+        assert_eq!(map.keys(action), action.default_keys());
+        assert!(!map.keys(action).is_empty(), "{action:?}");
+    }
+    assert_eq!(
+        map.keys(InputAction::ToggleHelp),
+        &[KeyCode::Slash, KeyCode::KeyH]
+    );
+}
+
+#[test]
+fn rows_follow_the_game_state() {
+    let map = InputMap::default();
+
+    let playing = english_rows(&map, GameState::Playing);
+    assert_eq!(playing[0], "? / H: Show/hide this help");
+    assert!(playing.contains(&"M: Mute/unmute sound".to_string()));
+    assert!(playing.contains(&"Esc: Pause/resume".to_string()));
+    assert!(playing.contains(&"U: Undo".to_string()));
+    assert!(playing.contains(&"F11: Toggle fullscreen".to_string()));
+    assert!(!playing.iter().any(|row| row.contains("Next game")));
+
+    let game_over = english_rows(&map, GameState::GameOver);
+    assert!(game_over.contains(&"Space / Enter: Next game".to_string()));
+    assert!(!game_over.iter().any(|row| row.contains("Undo")));
+
+    let analysis = english_rows(&map, GameState::Analysis);
+    assert!(analysis.contains(&"Home: Back to main line".to_string()));
+    assert!(analysis.contains(&"Esc: Leave analysis".to_string()));
+    assert!(!analysis.iter().any(|row| row.contains("Pause")));
+}
+
+#[test]
+fn rows_are_localized() {
+    let map = InputMap::default();
+    let rows: Vec<String> = shortcut_rows(&map, GameState::GameOver)
+        .iter()
+        .map(|TextArgs(args)| CHINESE_TEXTS.format(TextKey::ShortcutRowFormat, args))
+        .collect();
+    assert!(rows.contains(&"空格 / 回车：开始下一局".to_string()));
+    assert!(rows.contains(&"M：开关音效".to_string()));
+}
+
+#[test]
+fn rebinding_updates_the_help() {
+    let mut map = InputMap::default();
+    map.bind(InputAction::Undo, vec![KeyCode::KeyZ, KeyCode::Backspace]);
+    map.bind(InputAction::ToggleSquareLabels, Vec::new());
+
+    let rows = english_rows(&map, GameState::Playing);
+    assert!(rows.contains(&"Z / Backspace: Undo".to_string()));
+    assert!(!rows.iter().any(|row| row.contains("square labels")));
+}
+
+#[test]
+fn letter_keys_are_ignored_while_typing() {
+    let map = InputMap::default();
+    let mut keyboard = ButtonInput::<KeyCode>::default();
+    keyboard.press(KeyCode::KeyH);
+
+    assert!(map.just_pressed(InputAction::ToggleHelp, &keyboard));
+    assert!(map.just_pressed_unless_typing(InputAction::ToggleHelp, &keyboard, false));
+    assert!(!map.just_pressed_unless_typing(InputAction::ToggleHelp, &keyboard, true));
+
+    keyboard.press(KeyCode::Slash);
+    assert!(map.just_pressed_unless_typing(InputAction::ToggleHelp, &keyboard, true));
+}
+
+#[test]
+fn help_key_toggles_the_overlay() {
+    let mut world = World::new();
+    world.init_resource::<InputMap>();
+    world.init_resource::<UiState>();
+    world.init_resource::<ButtonInput<KeyCode>>();
+
+    let press = |world: &mut World, key: KeyCode| {
+        let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.reset_all();
+        keyboard.press(key);
+        world.run_system_once(toggle_shortcuts_overlay).unwrap();
+        world.resource::<UiState>().show_shortcuts
+    };
+
+    assert!(press(&mut world, KeyCode::Slash));
+    assert!(!press(&mut world, KeyCode::KeyH));
+
+    // 棋谱输入框显示时H键用于输入棋谱
+    world.spawn(NotationInputBox);
+    assert!(!press(&mut world, KeyCode::KeyH));
+    assert!(press(&mut world, KeyCode::Slash));
+}
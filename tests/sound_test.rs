@@ -0,0 +1,117 @@
+// 音效测试面板测试 - 音量按档调整，试听绕过静音直接播放，未加载的音效不播放
+
+use bevy::{asset::LoadState, audio::Volume, ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    audio::{AudioAssets, AudioSettings, SoundType},
+    boot::BootStatus,
+    ui::{
+        handle_sound_test_buttons, sound_name_key, step_volume, volume_percent,
+        SoundTestPlayButton, SoundTestPreview, SoundTestVolumeButton, UiState,
+    },
+};
+use std::{collections::HashSet, sync::Arc};
+
+#[test]
+fn volume_steps_are_clamped_and_aligned() {
+    assert_eq!(volume_percent(step_volume(0.5, 1)), 60);
+    assert_eq!(volume_percent(step_volume(0.5, -1)), 40);
+    assert_eq!(volume_percent(step_volume(0.95, 1)), 100);
+    assert_eq!(volume_percent(step_volume(1.0, 1)), 100);
+    assert_eq!(volume_percent(step_volume(0.0, -1)), 0);
+    // 不在整档上的音量先对齐到最近的一档
+    assert_eq!(volume_percent(step_volume(0.33, 1)), 40);
+}
+
+#[test]
+fn every_sound_has_its_own_name() {
+    let keys: HashSet<_> = SoundType::ALL.into_iter().map(sound_name_key).collect();
+    assert_eq!(keys.len(), SoundType::ALL.len());
+}
+
+#[test]
+fn load_state_maps_to_status() {
+    assert_eq!(
+        BootStatus::from_load_state(&LoadState::Loaded),
+        BootStatus::Loaded
+    );
+    assert_eq!(
+        BootStatus::from_load_state(&LoadState::Loading),
+        BootStatus::Loading
+    );
+    assert_eq!(
+        BootStatus::from_load_state(&LoadState::NotLoaded),
+        BootStatus::Loading
+    );
+}
+
+/// 创建测试用的世界，只有落子音效已经加载
+fn sound_test_world() -> World {
+    let mut world = World::new();
+    let mut sources = Assets::<AudioSource>::default();
+    let piece_place = sources.add(AudioSource {
+        bytes: Arc::from(Vec::new()),
+    });
+    world.insert_resource(sources);
+    world.insert_resource(AudioAssets {
+        piece_place,
+        piece_flip: Handle::default(),
+        victory: Handle::default(),
+        defeat: Handle::default(),
+        invalid_move: Handle::default(),
+        pass: Handle::default(),
+        low_time: Handle::default(),
+        corner_capture: Handle::default(),
+        edge_complete: Handle::default(),
+        wipeout_threat: Handle::default(),
+    });
+    world.insert_resource(AudioSettings {
+        enabled: false,
+        volume: 0.5,
+    });
+    world.init_resource::<UiState>();
+    world
+}
+
+fn preview_volumes(world: &mut World) -> Vec<f32> {
+    world
+        .query_filtered::<&PlaybackSettings, With<SoundTestPreview>>()
+        .iter(world)
+        .map(|settings| match settings.volume {
+            Volume::Linear(volume) => volume,
+            Volume::Decibels(_) => panic!("preview volume should be linear"),
+        })
+        .collect()
+}
+
+#[test]
+fn play_button_previews_even_when_muted() {
+    let mut world = sound_test_world();
+    world.spawn((
+        Interaction::Pressed,
+        SoundTestPlayButton(SoundType::PiecePlace),
+    ));
+    world.run_system_once(handle_sound_test_buttons).unwrap();
+    assert_eq!(preview_volumes(&mut world), vec![0.5]);
+}
+
+#[test]
+fn unloaded_sound_is_not_previewed() {
+    let mut world = sound_test_world();
+    world.spawn((
+        Interaction::Pressed,
+        SoundTestPlayButton(SoundType::Victory),
+    ));
+    world.run_system_once(handle_sound_test_buttons).unwrap();
+    assert!(preview_volumes(&mut world).is_empty());
+}
+
+#[test]
+fn volume_button_changes_volume_and_previews_it() {
+    let mut world = sound_test_world();
+    world.spawn((Interaction::Pressed, SoundTestVolumeButton(1)));
+    world.run_system_once(handle_sound_test_buttons).unwrap();
+
+    let volume = world.resource::<AudioSettings>().volume;
+    assert_eq!(volume_percent(volume), 60);
+    assert_eq!(preview_volumes(&mut world), vec![volume]);
+}
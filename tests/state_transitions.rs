@@ -0,0 +1,114 @@
+// 状态切换测试 - 校验状态切换图
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*, state::app::StatesPlugin};
+use reversi::{
+    net::{
+        decode, finish_spectating, start_spectating, ClientMessage, SpectatorLink, SpectatorView,
+    },
+    state::{GameState, GameTransition, GameTransitions},
+};
+
+#[test]
+fn follows_the_transition_graph() {
+    assert_eq!(
+        GameTransition::FinishLoading.target(GameState::LoadingScreen),
+        Ok(GameState::LanguageSelection)
+    );
+    assert_eq!(
+        GameTransition::StartGame.target(GameState::DifficultySelection),
+        Ok(GameState::Playing)
+    );
+    assert_eq!(
+        GameTransition::Restart.target(GameState::GameOver),
+        Ok(GameState::Restarting)
+    );
+    assert_eq!(
+        GameTransition::BackToMenu.target(GameState::GameOver),
+        Ok(GameState::DifficultySelection)
+    );
+    assert_eq!(
+        GameTransition::Analyze.target(GameState::GameOver),
+        Ok(GameState::Analysis)
+    );
+    assert_eq!(
+        GameTransition::LeaveAnalysis.target(GameState::Analysis),
+        Ok(GameState::GameOver)
+    );
+    assert_eq!(
+        GameTransition::OpenReplay.target(GameState::DifficultySelection),
+        Ok(GameState::Replay)
+    );
+    assert_eq!(
+        GameTransition::LeaveAnalysis.target(GameState::Replay),
+        Ok(GameState::DifficultySelection)
+    );
+    assert_eq!(
+        GameTransition::Spectate.target(GameState::DifficultySelection),
+        Ok(GameState::Spectating)
+    );
+    assert_eq!(
+        GameTransition::LeaveAnalysis.target(GameState::Spectating),
+        Ok(GameState::DifficultySelection)
+    );
+}
+
+#[test]
+fn rejects_jumps_outside_the_graph() {
+    assert!(GameTransition::EndGame
+        .target(GameState::DifficultySelection)
+        .is_err());
+    assert!(GameTransition::StartGame
+        .target(GameState::GameOver)
+        .is_err());
+    assert!(GameTransition::BackToMenu
+        .target(GameState::LoadingScreen)
+        .is_err());
+    assert!(GameTransition::FinishRestart
+        .target(GameState::Playing)
+        .is_err());
+    assert!(GameTransition::Analyze.target(GameState::Playing).is_err());
+    assert!(GameTransition::OpenReplay
+        .target(GameState::GameOver)
+        .is_err());
+    assert!(GameTransition::Spectate.target(GameState::Playing).is_err());
+}
+
+/// 宿主应用请求切换，下一次更新时生效
+fn request(app: &mut App, transition: GameTransition) {
+    app.world_mut()
+        .run_system_once(move |mut transitions: GameTransitions| transitions.request(transition))
+        .unwrap();
+    app.update();
+}
+
+fn sent(app: &mut App) -> Vec<ClientMessage> {
+    let frames = std::mem::take(&mut app.world_mut().resource_mut::<SpectatorLink>().outgoing);
+    frames.iter().map(|frame| decode(frame).unwrap()).collect()
+}
+
+#[test]
+fn host_app_enters_and_leaves_spectating() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::DifficultySelection)
+        .init_resource::<SpectatorLink>()
+        .add_systems(OnEnter(GameState::Spectating), start_spectating)
+        .add_systems(OnExit(GameState::Spectating), finish_spectating);
+    app.update();
+
+    request(&mut app, GameTransition::Spectate);
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::Spectating
+    );
+    assert!(app.world().contains_resource::<SpectatorView>());
+    assert_eq!(sent(&mut app), vec![SpectatorView::hello()]);
+
+    request(&mut app, GameTransition::LeaveAnalysis);
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::DifficultySelection
+    );
+    assert!(!app.world().contains_resource::<SpectatorView>());
+    assert_eq!(sent(&mut app), vec![ClientMessage::Leave]);
+}
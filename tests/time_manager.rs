@@ -0,0 +1,80 @@
+// 时间管理测试 - 按局面复杂度分配每步的搜索时间，受整局预算约束
+
+use reversi::{
+    ai::minimax::{
+        position_complexity, TimeManager, FORCED_MOVE_FACTOR, GAME_BUDGET_MOVES, MAX_COMPLEXITY,
+    },
+    game::{parse_position_string, Board},
+    PlayerColor,
+};
+use std::time::Duration;
+
+const PER_MOVE: Duration = Duration::from_secs(1);
+
+fn position(squares: &str) -> Board {
+    parse_position_string(&format!("{squares} X")).unwrap().0
+}
+
+/// 黑方只有A1一步可走
+fn forced_position() -> Board {
+    position(&format!("-OX{}", "-".repeat(61)))
+}
+
+/// 开局后几步的中局局面，双方都有多步可走
+fn open_position() -> Board {
+    position(concat!(
+        "--------", "--------", "--OXO---", "--XXXO--", "--OXXX--", "---O-X--", "--------",
+        "--------",
+    ))
+}
+
+#[test]
+fn forced_moves_get_almost_no_time() {
+    let board = forced_position();
+    assert_eq!(board.get_valid_moves(PlayerColor::Black).count_ones(), 1);
+    assert_eq!(
+        position_complexity(&board, PlayerColor::Black),
+        FORCED_MOVE_FACTOR
+    );
+
+    let manager = TimeManager::new(PER_MOVE);
+    assert_eq!(
+        manager.budget(&board, PlayerColor::Black),
+        PER_MOVE.mul_f32(FORCED_MOVE_FACTOR)
+    );
+}
+
+#[test]
+fn open_positions_get_more_time_than_forced_ones() {
+    let manager = TimeManager::new(PER_MOVE);
+    let open = open_position();
+    assert!(open.get_valid_moves(PlayerColor::Black).count_ones() > 5);
+
+    let complexity = position_complexity(&open, PlayerColor::Black);
+    assert!(complexity > FORCED_MOVE_FACTOR && complexity <= MAX_COMPLEXITY);
+    assert!(
+        manager.budget(&open, PlayerColor::Black)
+            > manager.budget(&forced_position(), PlayerColor::Black)
+    );
+    assert!(manager.budget(&open, PlayerColor::Black) <= PER_MOVE.mul_f32(MAX_COMPLEXITY));
+}
+
+#[test]
+fn spending_the_game_budget_shrinks_later_moves() {
+    let open = open_position();
+    let mut manager = TimeManager::new(PER_MOVE);
+    let fresh = manager.budget(&open, PlayerColor::Black);
+    assert_eq!(manager.remaining(), PER_MOVE * GAME_BUDGET_MOVES);
+
+    manager.record(PER_MOVE * (GAME_BUDGET_MOVES - 1));
+    let tight = manager.budget(&open, PlayerColor::Black);
+    assert!(tight < fresh);
+
+    // 预算用完后仍保留最低时间
+    manager.record(PER_MOVE * 5);
+    assert_eq!(manager.remaining(), Duration::ZERO);
+    assert_eq!(
+        manager.budget(&open, PlayerColor::Black),
+        PER_MOVE.mul_f32(FORCED_MOVE_FACTOR)
+    );
+}
@@ -0,0 +1,103 @@
+// 通知提示测试 - 提示的排队、叠放、自动消失和去重，以及联机同步事件和保存失败的提示文本
+
+use bevy::prelude::*;
+use reversi::{
+    localization::{TextArg, TextKey, CHINESE_TEXTS, ENGLISH_TEXTS},
+    net::SyncEvent,
+    ui::{
+        queue_toasts, show_sync_notices, ToastEvent, ToastKind, ToastQueue, MAX_PENDING_TOASTS,
+        MAX_VISIBLE_TOASTS,
+    },
+};
+use std::time::Duration;
+
+fn numbered(n: u64) -> ToastEvent {
+    ToastEvent::info(TextKey::ToastSaveFailedFormat).with_args(vec![TextArg::Number(n)])
+}
+
+#[test]
+fn toasts_stack_up_and_queue_the_rest() {
+    let mut queue = ToastQueue::default();
+    for n in 0..5 {
+        assert!(queue.push(numbered(n)));
+    }
+    assert_eq!(queue.visible().count(), MAX_VISIBLE_TOASTS);
+    assert_eq!(queue.pending_len(), 5 - MAX_VISIBLE_TOASTS);
+    assert_eq!(queue.visible().next(), Some(&numbered(0)));
+
+    // 还没到时不变化，到时后排队的提示补上
+    assert!(!queue.tick(Duration::from_secs(1)));
+    assert!(queue.tick(ToastKind::Info.duration()));
+    let visible: Vec<_> = queue.visible().cloned().collect();
+    assert_eq!(visible, vec![numbered(3), numbered(4)]);
+    assert_eq!(queue.pending_len(), 0);
+
+    assert!(queue.tick(ToastKind::Info.duration()));
+    assert_eq!(queue.visible().count(), 0);
+}
+
+#[test]
+fn duplicates_are_ignored_and_the_queue_is_bounded() {
+    let mut queue = ToastQueue::default();
+    assert!(queue.push(numbered(0)));
+    assert!(!queue.push(numbered(0)));
+
+    for n in 1..=(MAX_VISIBLE_TOASTS + MAX_PENDING_TOASTS) as u64 {
+        queue.push(numbered(n));
+    }
+    assert_eq!(queue.pending_len(), MAX_PENDING_TOASTS);
+    // 排满后丢弃最早排队的提示
+    assert!(!queue.push(numbered(MAX_VISIBLE_TOASTS as u64 + 1)));
+    assert!(queue.push(numbered(MAX_VISIBLE_TOASTS as u64)));
+}
+
+#[test]
+fn errors_stay_longer_than_info() {
+    let mut queue = ToastQueue::default();
+    queue.push(ToastEvent::info(TextKey::ToastGameSaved));
+    queue.push(ToastEvent::error(TextKey::ToastExportFailed));
+    assert!(ToastKind::Error.duration() > ToastKind::Info.duration());
+
+    queue.tick(ToastKind::Info.duration());
+    let visible: Vec<_> = queue.visible().map(|toast| toast.kind).collect();
+    assert_eq!(visible, vec![ToastKind::Error]);
+}
+
+#[test]
+fn sync_events_become_toasts() {
+    let mut app = App::new();
+    app.init_resource::<ToastQueue>()
+        .add_event::<SyncEvent>()
+        .add_event::<ToastEvent>()
+        .add_systems(Update, (show_sync_notices, queue_toasts).chain());
+
+    app.world_mut()
+        .send_event(SyncEvent::ResyncFailed { sequence: 7 });
+    app.update();
+
+    let queue = app.world().resource::<ToastQueue>();
+    let toast = queue.visible().next().unwrap();
+    assert_eq!(toast.kind, ToastKind::Error);
+    assert_eq!(toast.key, TextKey::NetResyncFailedFormat);
+}
+
+#[test]
+fn toast_texts_are_localized() {
+    let pass = ToastEvent::info(TextKey::ToastPassFormat)
+        .with_args(vec![TextArg::Key(TextKey::WhiteName)]);
+    assert_eq!(
+        ENGLISH_TEXTS.format(pass.key, &pass.args.0),
+        "White has no valid moves and passes"
+    );
+
+    let failed = ToastEvent::save_failed(TextKey::SaveDataArchive);
+    assert_eq!(failed.kind, ToastKind::Error);
+    assert_eq!(
+        ENGLISH_TEXTS.format(failed.key, &failed.args.0),
+        "Could not save game history"
+    );
+    assert_eq!(
+        CHINESE_TEXTS.format(failed.key, &failed.args.0),
+        "无法保存历史对局"
+    );
+}
@@ -0,0 +1,96 @@
+// 无界面AI对战测试 - 校验参数解析、评估配置读写和对战统计
+
+use reversi::ai::{
+    evaluation::EvalConfig,
+    tournament::{run_tournament, TournamentConfig, TournamentPlayer},
+    AiDifficulty,
+};
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn parses_tournament_arguments() {
+    assert!(TournamentConfig::from_args(args(&["--games", "4"]))
+        .unwrap()
+        .is_none());
+
+    let config = TournamentConfig::from_args(args(&[
+        "--headless-tournament",
+        "--games",
+        "6",
+        "--first",
+        "beginner",
+        "--second",
+        "expert",
+    ]))
+    .unwrap()
+    .expect("tournament flag present");
+    assert_eq!(config.games, 6);
+    assert_eq!(config.first.label, "beginner");
+    assert_eq!(config.second.label, "expert");
+
+    assert!(TournamentConfig::from_args(args(&[
+        "--headless-tournament",
+        "--first",
+        "grandmaster"
+    ]))
+    .is_err());
+}
+
+#[test]
+fn player_specs_split_at_the_difficulty_name() {
+    let chaos = TournamentPlayer::parse("chaos:7").unwrap();
+    assert_eq!(chaos.difficulty, AiDifficulty::Chaos(7));
+    assert_eq!(chaos.eval, EvalConfig::default());
+
+    // Windows路径中的盘符不会被当作难度和路径的分隔符
+    let err = TournamentPlayer::parse(r"expert:C:\missing.eval.ron").unwrap_err();
+    assert!(
+        err.starts_with(r"failed to read C:\missing.eval.ron"),
+        "{err}"
+    );
+    let err = TournamentPlayer::parse(r"chaos:7:C:\missing.eval.ron").unwrap_err();
+    assert!(
+        err.starts_with(r"failed to read C:\missing.eval.ron"),
+        "{err}"
+    );
+
+    assert!(TournamentPlayer::parse(r"C:\missing.eval.ron").is_err());
+}
+
+#[test]
+fn eval_config_round_trips_through_ron() {
+    let config = EvalConfig::default();
+    let text = config.to_ron().unwrap();
+    assert_eq!(EvalConfig::from_ron(&text).unwrap(), config);
+}
+
+#[test]
+fn tournament_reports_every_game() {
+    let config = TournamentConfig::from_args(args(&[
+        "--headless-tournament",
+        "--games",
+        "2",
+        "--first",
+        "beginner",
+        "--second",
+        "beginner",
+    ]))
+    .unwrap()
+    .unwrap();
+
+    let mut reported = 0;
+    let result = run_tournament(&config, |_| reported += 1);
+
+    assert_eq!(reported, 2);
+    assert_eq!(result.games.len(), 2);
+    assert_eq!(
+        result.first_wins() + result.second_wins() + result.draws(),
+        2
+    );
+    // 两局交换颜色
+    assert!(result.games[0].first_is_black);
+    assert!(!result.games[1].first_is_black);
+}
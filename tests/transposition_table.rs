@@ -0,0 +1,144 @@
+// 置换表测试 - 内存上限、条目读写、深度优先加老化的替换策略、搜索结果不变，以及设置和调试层文本
+
+use reversi::{
+    ai::{
+        evaluation::EvalConfig,
+        minimax::{find_best_move, find_best_move_with_table},
+        transposition::{Bound, TABLE_SIZES_MB},
+        AiPlayer, SearchPreferences, TranspositionTable,
+    },
+    game::{Board, PlayerColor},
+    localization::{TextKey, ENGLISH_TEXTS},
+    ui::debug_overlay_text,
+    AiDifficulty,
+};
+
+const MB: usize = 1024 * 1024;
+
+#[test]
+fn table_never_exceeds_the_configured_size() {
+    for size_mb in TABLE_SIZES_MB {
+        let table = TranspositionTable::with_size_mb(size_mb);
+        let stats = table.stats();
+        assert_eq!(stats.size_bytes, size_mb as usize * MB);
+        assert!(table.buckets().is_power_of_two());
+    }
+
+    // 不足一个桶时保留一个桶
+    let tiny = TranspositionTable::with_size_mb(0);
+    assert_eq!(tiny.buckets(), 1);
+    assert_eq!(tiny.stats().capacity, 4);
+}
+
+#[test]
+fn entries_round_trip() {
+    let table = TranspositionTable::with_size_mb(1);
+    let cases = [
+        (11, 7, -1234, Bound::Exact, Some(63)),
+        (22, 1, i32::MIN, Bound::Upper, None),
+        (33, 12, i32::MAX, Bound::Lower, Some(0)),
+    ];
+    for (key, depth, score, bound, best_move) in cases {
+        table.store(key, depth, score, bound, best_move);
+        let entry = table.probe(key).unwrap();
+        assert_eq!(
+            (entry.depth, entry.score, entry.bound, entry.best_move),
+            (depth, score, bound, best_move)
+        );
+    }
+
+    assert!(table.probe(44).is_none());
+    let stats = table.stats();
+    assert_eq!((stats.probes, stats.hits, stats.stores), (4, 3, 3));
+    assert_eq!(stats.hit_rate(), 0.75);
+
+    table.clear();
+    assert!(table.probe(11).is_none());
+    assert_eq!(table.stats().stores, 0);
+}
+
+#[test]
+fn same_position_keeps_the_deeper_result() {
+    let table = TranspositionTable::with_size_mb(0);
+    table.store(7, 8, 50, Bound::Lower, None);
+    table.store(7, 3, 10, Bound::Lower, None);
+    assert_eq!(table.probe(7).unwrap().depth, 8);
+
+    // 精确值总是覆盖
+    table.store(7, 3, 10, Bound::Exact, None);
+    assert_eq!(table.probe(7).unwrap().score, 10);
+
+    // 以前搜索的结果可以被更浅的结果覆盖
+    table.store(7, 9, 20, Bound::Lower, None);
+    table.new_search();
+    table.store(7, 2, 30, Bound::Upper, None);
+    assert_eq!(table.probe(7).unwrap().depth, 2);
+}
+
+#[test]
+fn replacement_prefers_shallow_and_old_entries() {
+    // 只有一个桶，所有键都落在同一个桶里
+    let table = TranspositionTable::with_size_mb(0);
+    for (key, depth) in [(1, 5), (2, 6), (3, 7), (4, 8)] {
+        table.store(key, depth, 0, Bound::Exact, None);
+    }
+    table.store(5, 1, 0, Bound::Exact, None);
+    assert!(table.probe(1).is_none(), "shallowest entry is replaced");
+    assert!([2, 3, 4, 5].iter().all(|&key| table.probe(key).is_some()));
+
+    // 几次搜索之后，旧的深条目比新的浅条目先被替换
+    for _ in 0..3 {
+        table.new_search();
+    }
+    table.store(6, 1, 0, Bound::Exact, None);
+    table.store(7, 1, 0, Bound::Exact, None);
+    assert!(table.probe(6).is_some() && table.probe(7).is_some());
+    assert!(table.probe(5).is_none() && table.probe(2).is_none());
+}
+
+#[test]
+fn table_does_not_change_search_results() {
+    let eval = EvalConfig::standard();
+    let mut board = Board::new_standard();
+    let mut player = PlayerColor::Black;
+    for position in [37, 43, 34] {
+        board.make_move(position, player);
+        player = player.opposite();
+
+        let plain = find_best_move(&board, 4, player, eval);
+        let table = TranspositionTable::with_size_mb(1);
+        // 先做一次浅搜索，模拟迭代加深
+        find_best_move_with_table(&board, 3, player, eval, Some(&table), None);
+        let cached = find_best_move_with_table(&board, 4, player, eval, Some(&table), None);
+        assert_eq!(cached.evaluation, plain.evaluation);
+        assert_eq!(cached.best_move, plain.best_move);
+        assert!(table.stats().hits > 0);
+    }
+}
+
+#[test]
+fn table_size_setting_cycles() {
+    let mut preferences = SearchPreferences {
+        table_size_mb: TABLE_SIZES_MB[0],
+    };
+    for expected in TABLE_SIZES_MB.iter().skip(1).chain(&TABLE_SIZES_MB[..1]) {
+        preferences.next_table_size();
+        assert_eq!(preferences.table_size_mb, *expected);
+    }
+    assert!(TABLE_SIZES_MB.contains(&SearchPreferences::default().table_size_mb));
+}
+
+#[test]
+fn debug_overlay_shows_table_statistics() {
+    let (key, _) = debug_overlay_text(None);
+    assert_eq!(key, TextKey::DebugNoSearch);
+
+    let mut ai_player = AiPlayer::new(AiDifficulty::Expert, PlayerColor::White);
+    ai_player.set_table_size_mb(8);
+    assert!(ai_player.table_stats().is_none());
+    let (key, args) = debug_overlay_text(Some(&ai_player));
+    assert_eq!(
+        ENGLISH_TEXTS.format(key, &args.0),
+        "Transposition table 8 MB · hit rate 0.0% · 0 probes · 0 stores"
+    );
+}
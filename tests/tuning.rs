@@ -0,0 +1,117 @@
+// 评估权重调优测试 - 命令行参数、参数向量的展开、SPSA的扰动和更新，以及一次最小调优写出的配置能重新载入
+#![cfg(feature = "tuning")]
+
+use reversi::{
+    ai::{
+        evaluation::EvalConfig,
+        tuning::{
+            from_params, perturb, run_from_cli, spsa_update, to_params, TuningConfig,
+            DEFAULT_OUTPUT,
+        },
+    },
+    AiDifficulty,
+};
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+#[test]
+fn parses_tuning_arguments() {
+    // 没有--tune时不进入调优
+    assert!(TuningConfig::from_args(args("--iterations 5"))
+        .unwrap()
+        .is_none());
+
+    let config = TuningConfig::from_args(args("--tune")).unwrap().unwrap();
+    assert_eq!(config.iterations, 100);
+    assert_eq!(config.output, DEFAULT_OUTPUT);
+
+    let config = TuningConfig::from_args(args(
+        "--iterations 3 --tune --games 4 --check-every 0 --check-games 6 \
+         --openings 2 --difficulty hard --output out/eval.ron",
+    ))
+    .unwrap()
+    .unwrap();
+    assert_eq!(config.iterations, 3);
+    assert_eq!(config.games_per_iteration, 4);
+    assert_eq!(config.check_every, 1, "check interval is at least 1");
+    assert_eq!(config.check_games, 6);
+    assert_eq!(config.opening_plies, 2);
+    assert_eq!(config.difficulty, AiDifficulty::Advanced);
+    assert_eq!(config.output, "out/eval.ron");
+}
+
+#[test]
+fn rejects_bad_tuning_arguments() {
+    for line in [
+        "--tune --iterations",
+        "--tune --games many",
+        "--tune --difficulty grandmaster",
+        "--tune --verbose 1",
+    ] {
+        assert!(TuningConfig::from_args(args(line)).is_err(), "{line}");
+    }
+}
+
+#[test]
+fn params_round_trip_through_the_eval_config() {
+    let config = EvalConfig::default();
+    let params = to_params(&config);
+    assert_eq!(params.len(), 25);
+    assert_eq!(from_params(&params), config);
+
+    // 一类位置权重按八方向对称展开：第16个参数是角
+    let mut changed = params.clone();
+    changed[15] = 321.0;
+    let weights = from_params(&changed).position_weights;
+    for (row, col) in [(0, 0), (0, 7), (7, 0), (7, 7)] {
+        assert_eq!(weights[row][col], 321);
+    }
+}
+
+#[test]
+fn perturbs_and_updates_along_the_signs() {
+    let params = [1.0, 2.0, 3.0];
+    let signs = [1.0, -1.0, 1.0];
+    let steps = [0.5, 0.5, 2.0];
+
+    assert_eq!(perturb(&params, &signs, &steps, 1.0), [1.5, 1.5, 5.0]);
+    assert_eq!(perturb(&params, &signs, &steps, -1.0), [0.5, 2.5, 1.0]);
+
+    // 正向配置全胜时沿扰动方向走满一步，全负时反向，平局时不变
+    let mut updated = params;
+    spsa_update(&mut updated, &signs, &steps, 1.0, 1.0);
+    assert_eq!(updated, [1.5, 1.5, 5.0]);
+
+    let mut updated = params;
+    spsa_update(&mut updated, &signs, &steps, 0.5, 0.0);
+    assert_eq!(updated, [0.75, 2.25, 2.0]);
+
+    let mut updated = params;
+    spsa_update(&mut updated, &signs, &steps, 1.0, 0.5);
+    assert_eq!(updated, params);
+}
+
+#[test]
+fn tiny_tuning_run_writes_a_loadable_config() {
+    let output = std::env::temp_dir().join(format!(
+        "reversi-tuning-{}/eval_config.ron",
+        std::process::id()
+    ));
+    let config = TuningConfig {
+        iterations: 1,
+        games_per_iteration: 2,
+        check_games: 2,
+        opening_plies: 2,
+        difficulty: AiDifficulty::Beginner,
+        output: output.to_string_lossy().into_owned(),
+        ..TuningConfig::default()
+    };
+    run_from_cli(&config).unwrap();
+
+    let contents = std::fs::read_to_string(&output).unwrap();
+    let loaded = EvalConfig::from_ron(&contents).unwrap();
+    assert_eq!(to_params(&loaded).len(), 25);
+    std::fs::remove_dir_all(output.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,163 @@
+// 回合切换动画测试 - 滑入曲线、光晕脉动范围，以及只在换手时更新提示文本并重新滑入
+
+use std::time::Duration;
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    accessibility::MotionSettings,
+    game::{Board, GameLog, MoveAction, PlayerColor},
+    localization::TextKey,
+    sandbox::SandboxMode,
+    ui::{
+        animate_turn_change, avatar_glow, glow_alpha, pulse_active_avatar,
+        start_turn_change_animation, turn_slide_pose, PlayerAvatar, TurnChangeAnimation,
+        TurnIndicator, TURN_SLIDE_DURATION_SECS,
+    },
+};
+
+fn turn_world(reduced_motion: bool) -> (World, Entity, Entity) {
+    let mut world = World::new();
+    world.init_resource::<Time>();
+    world.init_resource::<SandboxMode>();
+    world.insert_resource(MotionSettings { reduced_motion });
+    let log = world
+        .spawn(GameLog::new(Board::new_standard(), PlayerColor::Black))
+        .id();
+    let indicator = world
+        .spawn((
+            TextKey::YourTurn,
+            Node::default(),
+            TextColor(Color::WHITE),
+            TurnIndicator,
+            TurnChangeAnimation::default(),
+        ))
+        .id();
+    (world, log, indicator)
+}
+
+fn play(world: &mut World, log: Entity, notation: &str) {
+    let mut log = world.get_mut::<GameLog>(log).unwrap();
+    let position = Board::notation_to_position(notation).unwrap();
+    let command = log.next_command(MoveAction::Place(position));
+    log.apply(command).unwrap();
+}
+
+fn advance(world: &mut World, seconds: f32) {
+    world
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs_f32(seconds));
+    world.run_system_once(start_turn_change_animation).unwrap();
+    world.run_system_once(animate_turn_change).unwrap();
+}
+
+#[test]
+fn slide_starts_offset_and_ends_in_place() {
+    let (start_offset, start_alpha) = turn_slide_pose(0.0);
+    assert!(start_offset > 0.0);
+    assert_eq!(start_alpha, 0.0);
+    assert_eq!(turn_slide_pose(1.0), (0.0, 1.0));
+
+    // 偏移单调减小、透明度单调增大
+    let poses: Vec<(f32, f32)> = (0..=10).map(|i| turn_slide_pose(i as f32 / 10.0)).collect();
+    for pair in poses.windows(2) {
+        assert!(pair[1].0 <= pair[0].0);
+        assert!(pair[1].1 >= pair[0].1);
+    }
+}
+
+#[test]
+fn glow_pulses_within_range() {
+    let samples: Vec<f32> = (0..100)
+        .map(|i| glow_alpha(i as f32 * 0.05, false))
+        .collect();
+    let min = samples.iter().copied().fold(f32::MAX, f32::min);
+    let max = samples.iter().copied().fold(f32::MIN, f32::max);
+    assert!(min > 0.0 && max <= 1.0);
+    assert!(max - min > 0.2, "glow should visibly pulse");
+
+    // 减少动效时保持常亮
+    assert_eq!(glow_alpha(0.0, true), glow_alpha(0.7, true));
+}
+
+#[test]
+fn text_slides_in_when_the_turn_switches() {
+    let (mut world, log, indicator) = turn_world(false);
+
+    advance(&mut world, 0.0);
+    assert_eq!(*world.get::<TextKey>(indicator).unwrap(), TextKey::YourTurn);
+    assert!(!world
+        .get::<TurnChangeAnimation>(indicator)
+        .unwrap()
+        .is_finished());
+
+    advance(&mut world, TURN_SLIDE_DURATION_SECS);
+    let animation = world.get::<TurnChangeAnimation>(indicator).unwrap();
+    assert!(animation.is_finished());
+    assert_eq!(world.get::<Node>(indicator).unwrap().left, Val::Px(0.0));
+    assert_eq!(world.get::<TextColor>(indicator).unwrap().0.alpha(), 1.0);
+
+    // 回合状态不变时不再修改文本和节点
+    let tick = world.change_tick();
+    advance(&mut world, 0.1);
+    let entity = world.entity(indicator);
+    for last_changed in [
+        entity.get_ref::<Node>().unwrap().last_changed(),
+        entity.get_ref::<TextKey>().unwrap().last_changed(),
+    ] {
+        assert!(!last_changed.is_newer_than(tick, world.change_tick()));
+    }
+
+    // 黑方落子后轮到AI，文本更新并重新滑入
+    play(&mut world, log, "F5");
+    advance(&mut world, 0.0);
+    assert_eq!(*world.get::<TextKey>(indicator).unwrap(), TextKey::AiTurn);
+    let animation = world.get::<TurnChangeAnimation>(indicator).unwrap();
+    assert!(!animation.is_finished());
+    assert_eq!(animation.turn.unwrap().to_move, PlayerColor::White);
+}
+
+#[test]
+fn reduced_motion_shows_text_at_once() {
+    let (mut world, log, indicator) = turn_world(true);
+    play(&mut world, log, "F5");
+    advance(&mut world, 0.0);
+
+    assert_eq!(*world.get::<TextKey>(indicator).unwrap(), TextKey::AiTurn);
+    assert!(world
+        .get::<TurnChangeAnimation>(indicator)
+        .unwrap()
+        .is_finished());
+    assert_eq!(world.get::<Node>(indicator).unwrap().left, Val::Px(0.0));
+}
+
+#[test]
+fn only_the_side_to_move_glows() {
+    let (mut world, log, _) = turn_world(false);
+    let black = world
+        .spawn((
+            PlayerAvatar {
+                player_color: PlayerColor::Black,
+            },
+            avatar_glow(),
+        ))
+        .id();
+    let white = world
+        .spawn((
+            PlayerAvatar {
+                player_color: PlayerColor::White,
+            },
+            avatar_glow(),
+        ))
+        .id();
+    let glow =
+        |world: &World, entity: Entity| world.get::<BoxShadow>(entity).unwrap().0[0].color.alpha();
+
+    world.run_system_once(pulse_active_avatar).unwrap();
+    assert!(glow(&world, black) > 0.0);
+    assert_eq!(glow(&world, white), 0.0);
+
+    play(&mut world, log, "F5");
+    world.run_system_once(pulse_active_avatar).unwrap();
+    assert_eq!(glow(&world, black), 0.0);
+    assert!(glow(&world, white) > 0.0);
+}
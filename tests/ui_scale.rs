@@ -0,0 +1,81 @@
+// 界面缩放测试 - 按窗口尺寸和文字大小设置计算UiScale，棋盘边距随界面缩放保持比例，以及旧版本偏好设置的兼容
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*, window::PrimaryWindow};
+use reversi::{
+    display::{ui_scale_for_window, update_board_layout, BoardLayout, TextSize, WindowPreset},
+    preferences::Preferences,
+};
+
+#[test]
+fn scale_follows_the_smaller_window_side() {
+    for preset in [
+        WindowPreset::Compact,
+        WindowPreset::Medium,
+        WindowPreset::Large,
+    ] {
+        let (width, height) = preset.size();
+        let expected = width / 400.0;
+        assert!((ui_scale_for_window(width, height, TextSize::Normal) - expected).abs() < 1e-4);
+    }
+    // 宽屏按高度缩放，极小和极大的窗口有上下限
+    assert_eq!(ui_scale_for_window(1920.0, 1080.0, TextSize::Normal), 1.8);
+    assert_eq!(ui_scale_for_window(200.0, 300.0, TextSize::Normal), 0.75);
+    assert_eq!(ui_scale_for_window(4000.0, 6000.0, TextSize::Normal), 2.5);
+}
+
+#[test]
+fn text_size_multiplies_the_window_scale() {
+    let mut text_size = TextSize::default();
+    let mut factors = Vec::new();
+    for _ in 0..4 {
+        factors.push(ui_scale_for_window(400.0, 600.0, text_size));
+        text_size = text_size.next();
+    }
+    assert_eq!(text_size, TextSize::Normal);
+    assert_eq!(factors, [1.0, 1.2, 1.4, 0.85]);
+    assert_eq!(
+        ui_scale_for_window(200.0, 300.0, TextSize::ExtraLarge),
+        1.05
+    );
+
+    // 旧版本保存的显示设置没有文字大小
+    let old: Preferences = ron::from_str("(display: (fullscreen: true))").unwrap();
+    assert_eq!(old.display.text_size, TextSize::Normal);
+}
+
+#[test]
+fn board_keeps_its_share_of_the_window() {
+    // 与紧凑预设成比例的窗口中，棋盘和界面同比例放大
+    let compact = BoardLayout::board_size_for_window(400.0, 600.0, 1.0);
+    let large = BoardLayout::board_size_for_window(720.0, 1080.0, 1.8);
+    assert!((large - compact * 1.8).abs() < 1e-3);
+
+    // 放大文字时为界面留出更多空间
+    let larger_text = ui_scale_for_window(400.0, 600.0, TextSize::Large);
+    assert!(BoardLayout::board_size_for_window(400.0, 600.0, larger_text) < compact);
+}
+
+#[test]
+fn layout_system_applies_the_text_size_setting() {
+    let mut world = World::new();
+    world.spawn((
+        Window {
+            resolution: (720.0, 1080.0).into(),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    let mut preferences = Preferences::default();
+    preferences.display.text_size = TextSize::Large;
+    world.insert_resource(preferences);
+    world.init_resource::<UiScale>();
+    world.init_resource::<BoardLayout>();
+    world.run_system_once(update_board_layout).unwrap();
+
+    let scale = world.resource::<UiScale>().0;
+    assert!((scale - 1.8 * 1.2).abs() < 1e-4);
+    assert_eq!(
+        world.resource::<BoardLayout>().board_size,
+        BoardLayout::board_size_for_window(720.0, 1080.0, scale)
+    );
+}
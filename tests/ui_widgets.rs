@@ -0,0 +1,115 @@
+// 界面部件测试 - 按钮、面板和标签的生成函数按样式生成组件、插入额外组件并返回实体id，角落按钮绝对定位
+
+use bevy::prelude::*;
+use reversi::{
+    fonts::LocalizedText,
+    localization::{TextArg, TextArgs, TextKey},
+    ui::{
+        spawn_button, spawn_label, spawn_panel, ButtonColors, ButtonStyle, LabelStyle, PanelStyle,
+    },
+};
+
+#[derive(Component)]
+struct TestPanel;
+
+#[derive(Component)]
+struct TestButton;
+
+/// 在一个面板中生成一个按钮和一个带参数的标签，返回三个实体
+fn spawn_widgets(world: &mut World, button_style: &ButtonStyle) -> (Entity, Entity, Entity) {
+    let font = Handle::<Font>::default();
+    let mut commands = world.commands();
+    let panel = spawn_panel(&mut commands, &PanelStyle::default(), TestPanel);
+    let (mut button, mut label) = (Entity::PLACEHOLDER, Entity::PLACEHOLDER);
+    commands.entity(panel).with_children(|panel| {
+        button = spawn_button(panel, button_style, &font, TextKey::PauseResume, TestButton);
+        label = spawn_label(
+            panel,
+            &LabelStyle::default(),
+            &font,
+            TextKey::PauseAiStrengthFormat,
+            TextArgs(vec![TextArg::Key(TextKey::DifficultyMedium)]),
+        );
+    });
+    world.flush();
+    (panel, button, label)
+}
+
+#[test]
+fn button_uses_its_style_and_has_a_localized_label() {
+    let mut world = World::new();
+    let style = ButtonStyle {
+        width: Val::Px(80.0),
+        color: Color::srgb(0.2, 0.5, 0.2),
+        font_size: 14.0,
+        ..default()
+    };
+    let (panel, button, _) = spawn_widgets(&mut world, &style);
+
+    let entity = world.entity(button);
+    assert!(entity.contains::<Button>() && entity.contains::<TestButton>());
+    assert_eq!(entity.get::<ChildOf>().unwrap().parent(), panel);
+    let node = entity.get::<Node>().unwrap();
+    assert_eq!((node.width, node.height), (Val::Px(80.0), Val::Px(44.0)));
+    assert_eq!(entity.get::<BackgroundColor>().unwrap().0, style.color);
+    let colors = entity.get::<ButtonColors>().unwrap();
+    assert_eq!(colors.normal, style.color);
+    assert_ne!(colors.hovered, colors.pressed);
+
+    let text = entity.get::<Children>().unwrap()[0];
+    let text = world.entity(text);
+    assert_eq!(text.get::<TextKey>(), Some(&TextKey::PauseResume));
+    assert_eq!(text.get::<TextFont>().unwrap().font_size, 14.0);
+    assert!(text.contains::<LocalizedText>());
+}
+
+#[test]
+fn panel_and_label_take_extra_components() {
+    let mut world = World::new();
+    let (panel, _, label) = spawn_widgets(&mut world, &ButtonStyle::default());
+
+    let panel = world.entity(panel);
+    assert!(panel.contains::<TestPanel>());
+    assert_eq!(
+        panel.get::<Node>().unwrap().flex_direction,
+        FlexDirection::Column
+    );
+    assert_eq!(panel.get::<Children>().unwrap().len(), 2);
+
+    let label = world.entity(label);
+    assert_eq!(
+        label.get::<TextKey>(),
+        Some(&TextKey::PauseAiStrengthFormat)
+    );
+    assert_eq!(
+        label.get::<TextArgs>().unwrap().0,
+        [TextArg::Key(TextKey::DifficultyMedium)]
+    );
+}
+
+#[test]
+fn corner_button_is_positioned_and_keeps_custom_colors() {
+    let mut world = World::new();
+    let colors = ButtonColors {
+        normal: Color::srgba(0.15, 0.15, 0.15, 0.5),
+        hovered: Color::srgba(0.15, 0.15, 0.15, 0.5),
+        pressed: Color::srgba(0.15, 0.15, 0.15, 0.5),
+    };
+    let style = ButtonStyle {
+        colors: Some(colors),
+        ..ButtonStyle::corner(60.0)
+    };
+    let (_, button, _) = spawn_widgets(&mut world, &style);
+
+    // 左上角绝对定位，宽度随文本变化但不小于触摸目标
+    let entity = world.entity(button);
+    let node = entity.get::<Node>().unwrap();
+    assert_eq!(node.position_type, PositionType::Absolute);
+    assert_eq!((node.left, node.top), (Val::Px(8.0), Val::Px(60.0)));
+    assert_eq!((node.right, node.bottom), (Val::Auto, Val::Auto));
+    assert_eq!((node.width, node.min_width), (Val::Auto, Val::Px(44.0)));
+
+    // 自定义颜色代替由color生成的颜色
+    assert_eq!(entity.get::<ButtonColors>(), Some(&colors));
+    assert_eq!(entity.get::<BackgroundColor>().unwrap().0, colors.normal);
+}
@@ -0,0 +1,139 @@
+// 悔棋规则测试 - 按对局模式限制次数，悔棋撤销到玩家上一次落子之前
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    ai::{adaptive::AdaptiveLevel, AiPlayer},
+    assist::CornerWarningState,
+    coach::CoachState,
+    game::{Board, GameLog, MoveAction, PlayerColor},
+    input::InputGate,
+    practice::OpeningAutoplay,
+    ui::{CurrentPlayer, UiState},
+    undo::{
+        apply_undo_requests, undo_depth, UndoBlock, UndoPolicy, UndoRequestEvent, UndoState,
+        NORMAL_UNDO_LIMIT,
+    },
+    AiDifficulty,
+};
+
+/// 双方各走一步后的命令日志，轮到黑棋（玩家）
+fn log_after_one_exchange() -> GameLog {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    for _ in 0..2 {
+        let position = log.board().get_valid_moves_list(log.to_move())[0].position;
+        log.apply(log.next_command(MoveAction::Place(position)))
+            .unwrap();
+    }
+    log
+}
+
+#[test]
+fn policy_follows_game_mode() {
+    let normal = AiDifficulty::Intermediate;
+    let rated = AiDifficulty::Adaptive(AdaptiveLevel::default());
+    assert_eq!(
+        UndoPolicy::for_game(true, false, normal),
+        UndoPolicy::Unlimited
+    );
+    assert_eq!(
+        UndoPolicy::for_game(false, true, rated),
+        UndoPolicy::Unlimited
+    );
+    assert_eq!(
+        UndoPolicy::for_game(false, false, normal),
+        UndoPolicy::PerGame(NORMAL_UNDO_LIMIT)
+    );
+    assert_eq!(
+        UndoPolicy::for_game(false, false, rated),
+        UndoPolicy::Disabled
+    );
+}
+
+#[test]
+fn depth_reaches_back_to_the_players_last_move() {
+    let log = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    assert_eq!(undo_depth(&log, Some(PlayerColor::Black)), None);
+
+    let log = log_after_one_exchange();
+    assert_eq!(undo_depth(&log, Some(PlayerColor::Black)), Some(2));
+    // 沙盒中撤销最后一次落子，不论是哪一方
+    assert_eq!(undo_depth(&log, None), Some(1));
+}
+
+#[test]
+fn limited_policy_runs_out() {
+    let log = log_after_one_exchange();
+    let mut undo = UndoState::new(UndoPolicy::PerGame(2));
+    assert_eq!(undo.check(&log, Some(PlayerColor::Black)), Ok(2));
+    undo.used = 2;
+    assert_eq!(undo.remaining(), Some(0));
+    assert_eq!(
+        undo.check(&log, Some(PlayerColor::Black)),
+        Err(UndoBlock::LimitReached)
+    );
+
+    let disabled = UndoState::new(UndoPolicy::Disabled);
+    assert_eq!(
+        disabled.check(&log, Some(PlayerColor::Black)),
+        Err(UndoBlock::Disabled)
+    );
+    assert_eq!(UndoState::new(UndoPolicy::Unlimited).remaining(), None);
+}
+
+fn undo_world(policy: UndoPolicy) -> (World, Entity) {
+    let mut world = World::new();
+    world.init_resource::<Events<UndoRequestEvent>>();
+    world.insert_resource(UndoState::new(policy));
+    world.init_resource::<OpeningAutoplay>();
+    world.insert_resource(CurrentPlayer(PlayerColor::Black));
+    world.init_resource::<InputGate>();
+    world.init_resource::<CornerWarningState>();
+    world.init_resource::<CoachState>();
+    world.init_resource::<UiState>();
+
+    let log = log_after_one_exchange();
+    let entity = world.spawn((log.board(), log)).id();
+    world.spawn(AiPlayer::new(AiDifficulty::Beginner, PlayerColor::White));
+    (world, entity)
+}
+
+fn request_undo(world: &mut World) {
+    world.send_event(UndoRequestEvent);
+    world.run_system_once(apply_undo_requests).unwrap();
+}
+
+#[test]
+fn undo_restores_the_position_before_the_players_move() {
+    let (mut world, entity) = undo_world(UndoPolicy::PerGame(NORMAL_UNDO_LIMIT));
+    request_undo(&mut world);
+
+    let log = world.get::<GameLog>(entity).unwrap();
+    assert!(log.commands().is_empty());
+    assert_eq!(log.to_move(), PlayerColor::Black);
+    assert_eq!(*world.get::<Board>(entity).unwrap(), Board::new_standard());
+    assert_eq!(world.resource::<UndoState>().used, 1);
+
+    // 已经回到开局，没有可以撤销的落子，次数不再增加
+    request_undo(&mut world);
+    assert_eq!(world.resource::<UndoState>().used, 1);
+}
+
+#[test]
+fn disabled_policy_keeps_the_game_unchanged() {
+    let (mut world, entity) = undo_world(UndoPolicy::Disabled);
+    request_undo(&mut world);
+    assert_eq!(world.get::<GameLog>(entity).unwrap().commands().len(), 2);
+}
+
+#[test]
+fn undo_waits_for_the_players_turn() {
+    let (mut world, entity) = undo_world(UndoPolicy::Unlimited);
+    let mut log = world.get_mut::<GameLog>(entity).unwrap();
+    let position = log.board().get_valid_moves_list(PlayerColor::Black)[0].position;
+    let command = log.next_command(MoveAction::Place(position));
+    log.apply(command).unwrap();
+
+    // 轮到AI时不能悔棋
+    request_undo(&mut world);
+    assert_eq!(world.get::<GameLog>(entity).unwrap().commands().len(), 3);
+}
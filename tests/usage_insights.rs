@@ -0,0 +1,103 @@
+// 使用统计测试 - 只在开启后记录对局，平均对局长度和统计页面的内容
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use reversi::{
+    ai::endgame::GameOutcome,
+    game::{MoveAction, PlayerColor},
+    localization::{TextArg, TextKey},
+    preferences::Preferences,
+    record::{GameRecord, RecordedMove},
+    stats::{
+        record_game_insights, DifficultyTier, GameFinishedEvent, PlayerStats, UsageFeature,
+        UsageInsights,
+    },
+    ui::insights_lines,
+    AiDifficulty,
+};
+use std::time::Duration;
+
+#[test]
+fn average_game_covers_recorded_games() {
+    let mut insights = UsageInsights::default();
+    assert_eq!(insights.average_game(), None);
+    assert!(insights.is_empty());
+
+    insights.record_game(DifficultyTier::Beginner, 60, Duration::from_secs(300));
+    insights.record_game(DifficultyTier::Expert, 57, Duration::from_secs(500));
+    assert_eq!(insights.games_at(DifficultyTier::Beginner), 1);
+    assert_eq!(insights.games_at(DifficultyTier::Advanced), 0);
+    // 58.5步四舍五入为59步
+    assert_eq!(
+        insights.average_game(),
+        Some((59, Duration::from_secs(400)))
+    );
+}
+
+#[test]
+fn lines_skip_unused_entries() {
+    let mut insights = UsageInsights::default();
+    assert!(insights_lines(&insights).is_empty());
+
+    insights.record_game(DifficultyTier::Advanced, 60, Duration::from_secs(120));
+    insights.record_feature(UsageFeature::Undo);
+    insights.record_feature(UsageFeature::Undo);
+    let keys: Vec<_> = insights_lines(&insights)
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            TextKey::InsightsGamesFormat,
+            TextKey::InsightsAverageFormat,
+            TextKey::InsightsFeaturesHeader,
+            TextKey::InsightsFeatureFormat,
+        ]
+    );
+
+    let (_, args) = insights_lines(&insights).pop().unwrap();
+    assert_eq!(args.0[1], TextArg::Number(2));
+}
+
+fn finished_game_world(insights_enabled: bool) -> World {
+    let mut world = World::new();
+    world.insert_resource(Preferences {
+        insights: insights_enabled,
+        ..default()
+    });
+    let mut record = GameRecord::default();
+    for (player, think_secs) in [(PlayerColor::Black, 4), (PlayerColor::White, 1)] {
+        record.moves.push(RecordedMove {
+            player,
+            action: MoveAction::Place(19),
+            think_time: Duration::from_secs(think_secs),
+        });
+    }
+    record.moves.push(RecordedMove {
+        player: PlayerColor::Black,
+        action: MoveAction::Pass,
+        think_time: Duration::ZERO,
+    });
+    world.insert_resource(record);
+    world.init_resource::<PlayerStats>();
+    world.init_resource::<Events<GameFinishedEvent>>();
+    world.send_event(GameFinishedEvent {
+        outcome: GameOutcome::Win,
+        difficulty: AiDifficulty::Intermediate,
+        margin: 10,
+    });
+    world.run_system_once(record_game_insights).unwrap();
+    world
+}
+
+#[test]
+fn games_are_recorded_only_when_opted_in() {
+    let world = finished_game_world(false);
+    assert!(world.resource::<PlayerStats>().insights.is_empty());
+
+    let world = finished_game_world(true);
+    let insights = &world.resource::<PlayerStats>().insights;
+    assert_eq!(insights.games_at(DifficultyTier::Intermediate), 1);
+    // 跳过回合不计入步数
+    assert_eq!(insights.average_game(), Some((2, Duration::from_secs(5))));
+}
@@ -0,0 +1,102 @@
+// 胜负条件测试 - 领先规定子数获胜、先到目标子数提前结束，以及规则随命令日志和偏好设置保存
+
+use reversi::{
+    game::{Board, CommandError, GameLog, MoveAction, PlayerColor, RulesConfig, VictoryCondition},
+    preferences::Preferences,
+};
+
+/// 下满的棋盘：黑棋占前black_count格，其余为白棋
+fn full_board(black_count: u32) -> Board {
+    let black = if black_count == 64 {
+        u64::MAX
+    } else {
+        (1u64 << black_count) - 1
+    };
+    Board {
+        black,
+        white: !black,
+    }
+}
+
+fn play(log: &mut GameLog, notation: &str) -> Result<(), CommandError> {
+    let position = Board::notation_to_position(notation).unwrap();
+    let command = log.next_command(MoveAction::Place(position));
+    log.apply(command)
+}
+
+#[test]
+fn standard_victory_matches_disc_count() {
+    let standard = VictoryCondition::Standard;
+    assert_eq!(standard.winner(&full_board(33)), Some(PlayerColor::Black));
+    assert_eq!(standard.winner(&full_board(31)), Some(PlayerColor::White));
+    assert_eq!(standard.winner(&full_board(32)), None);
+    assert!(!standard.ends_early(&full_board(60)));
+}
+
+#[test]
+fn winning_by_too_little_is_a_draw() {
+    let margin = VictoryCondition::WinByMargin(10);
+    // 领先8子不够，领先10子获胜
+    assert_eq!(margin.winner(&full_board(36)), None);
+    assert_eq!(margin.winner(&full_board(37)), Some(PlayerColor::Black));
+    assert_eq!(margin.winner(&full_board(27)), Some(PlayerColor::White));
+    assert!(!margin.ends_early(&full_board(64)));
+}
+
+#[test]
+fn reaching_the_target_ends_the_game_early() {
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black)
+        .with_victory(VictoryCondition::FirstTo(4));
+    assert!(!log.is_game_over());
+    assert_eq!(log.winner(), None);
+
+    // 黑棋F5后有4子，达到目标
+    play(&mut log, "F5").unwrap();
+    assert_eq!(log.board().count_pieces(PlayerColor::Black), 4);
+    assert!(log.is_game_over());
+    assert!(!log.must_pass());
+    assert_eq!(log.winner(), Some(PlayerColor::Black));
+    assert_eq!(play(&mut log, "F6"), Err(CommandError::GameOver));
+
+    // 标准规则下同样的局面继续进行
+    let mut standard = GameLog::new(Board::new_standard(), PlayerColor::Black);
+    play(&mut standard, "F5").unwrap();
+    assert!(!standard.is_game_over());
+    assert_eq!(standard.winner(), None);
+}
+
+#[test]
+fn undo_keeps_the_victory_condition() {
+    let victory = VictoryCondition::FirstTo(5);
+    let mut log = GameLog::new(Board::new_standard(), PlayerColor::Black).with_victory(victory);
+    play(&mut log, "F5").unwrap();
+    play(&mut log, "F6").unwrap();
+    log.undo().unwrap();
+    assert_eq!(log.victory(), victory);
+}
+
+#[test]
+fn settings_cycle_through_presets() {
+    let mut victory = VictoryCondition::default();
+    let mut seen = Vec::new();
+    for _ in 0..3 {
+        seen.push(victory);
+        victory = victory.next();
+    }
+    assert_eq!(victory, VictoryCondition::Standard);
+    assert!(matches!(seen[1], VictoryCondition::WinByMargin(_)));
+    assert!(matches!(seen[2], VictoryCondition::FirstTo(_)));
+}
+
+#[test]
+fn rules_are_saved_with_preferences() {
+    let mut preferences = Preferences::default();
+    preferences.rules.victory = VictoryCondition::WinByMargin(12);
+    let contents = ron::to_string(&preferences).unwrap();
+    let loaded: Preferences = ron::from_str(&contents).unwrap();
+    assert_eq!(loaded.rules.victory, VictoryCondition::WinByMargin(12));
+
+    // 旧版本保存的文件没有规则字段，使用标准规则
+    let old: Preferences = ron::from_str("(insights: true)").unwrap();
+    assert_eq!(old.rules, RulesConfig::default());
+}
@@ -0,0 +1,44 @@
+// 胜率模型测试 - 内置系数的性质和离线拟合
+
+use reversi::ai::win_probability::{fit, WinProbabilityModel, WinSample};
+use reversi::game::{Board, PlayerColor};
+
+#[test]
+fn baked_model_favours_the_better_side_more_as_the_game_ends() {
+    let model = WinProbabilityModel::BAKED;
+    assert_eq!(model.probability(0, 40), 0.5);
+
+    let early = model.probability(200, 50);
+    let late = model.probability(200, 10);
+    assert!(0.5 < early && early < late);
+    assert!((model.probability(-200, 10) - (1.0 - late)).abs() < 1e-6);
+
+    // 初始局面双方机会相当
+    let opening = model.for_board(&Board::new_standard(), PlayerColor::Black);
+    assert!((opening - 0.5).abs() < 0.2);
+}
+
+#[test]
+fn fitting_recovers_the_sign_and_phase_of_the_evaluation() {
+    // 合成样本：评估占优的一方获胜，残局样本比开局样本更可靠
+    let mut samples = Vec::new();
+    for eval in [-300, -100, 100, 300] {
+        for (empties, upsets) in [(50, 3), (10, 0)] {
+            for game in 0..10 {
+                let favourite_wins = game >= upsets;
+                let won = (eval > 0) == favourite_wins;
+                samples.push(WinSample {
+                    eval,
+                    empties,
+                    outcome: if won { 1.0 } else { 0.0 },
+                });
+            }
+        }
+    }
+
+    let model = fit(&samples, 2000, 0.5);
+    assert!(model.late_weight > 0.0);
+    assert!(model.probability(300, 50) > 0.5);
+    assert!(model.bias.abs() < 0.1);
+    assert!(model.probability(300, 10) > model.probability(300, 50));
+}